@@ -40,6 +40,7 @@ fn bench_no_batching(c: &mut Criterion) {
         window_ms: 0,
         max_batch_size: 1,
         methods: vec!["tools/list".to_string()],
+        adaptive: Default::default(),
     });
 
     let mut group = c.benchmark_group("batching/disabled");
@@ -70,6 +71,7 @@ fn bench_batching_enabled(c: &mut Criterion) {
         window_ms: 100,
         max_batch_size: 10,
         methods: vec!["tools/list".to_string()],
+        adaptive: Default::default(),
     });
 
     let mut group = c.benchmark_group("batching/enabled");
@@ -100,6 +102,7 @@ fn bench_varying_sizes(c: &mut Criterion) {
             window_ms: 100,
             max_batch_size: size,
             methods: vec!["tools/list".to_string()],
+            adaptive: Default::default(),
         });
 
         group.throughput(Throughput::Elements(1));
@@ -132,6 +135,7 @@ fn bench_concurrent(c: &mut Criterion) {
         window_ms: 100,
         max_batch_size: 50,
         methods: vec!["tools/list".to_string()],
+        adaptive: Default::default(),
     }));
 
     let mut group = c.benchmark_group("batching/concurrent");
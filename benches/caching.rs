@@ -23,11 +23,19 @@ fn test_cache() -> LayeredCache {
     LayeredCache::new(CacheConfig {
         enabled: true,
         l1_capacity: 1000,
+        l1_max_bytes: None,
         l1_ttl: Duration::from_secs(300),
         l2_capacity: 500,
+        l2_max_bytes: None,
         l2_ttl: Duration::from_secs(1800),
         l3_capacity: 200,
+        l3_max_bytes: None,
         l3_ttl: Duration::from_secs(7200),
+        method_ttls: Default::default(),
+        stale_grace: Duration::ZERO,
+        l1_compression: None,
+        l2_compression: None,
+        l3_compression: None,
     })
 }
 
@@ -125,11 +133,19 @@ fn bench_lru_eviction(c: &mut Criterion) {
     let cache = LayeredCache::new(CacheConfig {
         enabled: true,
         l1_capacity: 10, // Very small to trigger evictions
+        l1_max_bytes: None,
         l1_ttl: Duration::from_secs(300),
         l2_capacity: 5,
+        l2_max_bytes: None,
         l2_ttl: Duration::from_secs(1800),
         l3_capacity: 2,
+        l3_max_bytes: None,
         l3_ttl: Duration::from_secs(7200),
+        method_ttls: Default::default(),
+        stale_grace: Duration::ZERO,
+        l1_compression: None,
+        l2_compression: None,
+        l3_compression: None,
     });
 
     let mut group = c.benchmark_group("caching/eviction");
@@ -111,6 +111,7 @@ pub fn mock_tools_response(count: usize) -> McpResponse {
                     "param2": { "type": "number" }
                 }
             }),
+            output_schema: None,
         })
         .collect();
 
@@ -0,0 +1,96 @@
+//! JSON Parsing Benchmarks
+//!
+//! This benchmark suite compares plain serde_json against the hot-path parser
+//! in `only1mcp::json` on representative MCP payloads. Run it twice to
+//! document the gain from SIMD parsing:
+//!
+//! ```text
+//! cargo bench --bench json_parsing
+//! cargo bench --bench json_parsing --features simd-json
+//! ```
+//!
+//! Benchmarks:
+//! - Small request (serde_json vs hot path)
+//! - tools/list response with 10/100/1000 tools (serde_json vs hot path)
+//!
+//! Total: 8 benchmarks
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use serde_json::{json, Value};
+
+/// Generate a tools/list response with `count` tools
+fn tools_list_response(count: usize) -> Vec<u8> {
+    let tools: Vec<Value> = (0..count)
+        .map(|i| {
+            json!({
+                "name": format!("tool_{}", i),
+                "description": format!("Performs operation number {} on the target resource", i),
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "path": {"type": "string", "description": "Target path"},
+                        "recursive": {"type": "boolean", "default": false},
+                        "limit": {"type": "integer", "minimum": 1, "maximum": 1000}
+                    },
+                    "required": ["path"]
+                }
+            })
+        })
+        .collect();
+
+    serde_json::to_vec(&json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "result": {"tools": tools}
+    }))
+    .unwrap()
+}
+
+/// Benchmark ID for the hot-path parser, naming the active backend
+fn hot_path_id() -> String {
+    format!("hot_path_{}", only1mcp::json::backend())
+}
+
+/// Benchmark parsing of a small client request
+fn bench_small_request(c: &mut Criterion) {
+    let mut group = c.benchmark_group("json_parsing/small_request");
+    let payload = serde_json::to_vec(&json!({
+        "jsonrpc": "2.0",
+        "id": 42,
+        "method": "tools/call",
+        "params": {"name": "read_file", "arguments": {"path": "/tmp/example.txt"}}
+    }))
+    .unwrap();
+
+    group.throughput(Throughput::Bytes(payload.len() as u64));
+    group.bench_function("serde_json", |b| {
+        b.iter(|| black_box(serde_json::from_slice::<Value>(black_box(&payload)).unwrap()));
+    });
+    group.bench_function(hot_path_id(), |b| {
+        b.iter(|| black_box(only1mcp::json::from_slice::<Value>(black_box(&payload)).unwrap()));
+    });
+
+    group.finish();
+}
+
+/// Benchmark parsing of aggregated tools/list responses of increasing size
+fn bench_tools_list(c: &mut Criterion) {
+    let mut group = c.benchmark_group("json_parsing/tools_list");
+
+    for count in [10, 100, 1000] {
+        let payload = tools_list_response(count);
+        group.throughput(Throughput::Bytes(payload.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("serde_json", count), &payload, |b, p| {
+            b.iter(|| black_box(serde_json::from_slice::<Value>(black_box(p)).unwrap()));
+        });
+        group.bench_with_input(BenchmarkId::new(hot_path_id(), count), &payload, |b, p| {
+            b.iter(|| black_box(only1mcp::json::from_slice::<Value>(black_box(p)).unwrap()));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_small_request, bench_tools_list);
+criterion_main!(benches);
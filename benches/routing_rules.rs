@@ -0,0 +1,112 @@
+//! Routing Rule Matching Benchmarks
+//!
+//! This benchmark suite compares the compiled tool matcher against a naive
+//! per-request scan of every glob pattern, across rule counts from 10 to
+//! 10,000. The compiled matcher should stay roughly flat as rules grow,
+//! while the linear scan degrades proportionally.
+//!
+//! Benchmarks:
+//! - Compiled matcher lookup (4 sizes)
+//! - Linear glob scan (4 sizes)
+//! - Matcher compilation (4 sizes)
+//!
+//! Total: 12 benchmarks
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use only1mcp::routing::tool_matcher::{glob_match, ToolMatcher};
+
+const RULE_COUNTS: [usize; 4] = [10, 100, 1_000, 10_000];
+const SERVERS: usize = 20;
+
+/// Generate rules spread across servers: a mix of exact, prefix and suffix patterns
+fn mock_rules(count: usize) -> Vec<(String, Vec<String>)> {
+    let mut servers: Vec<(String, Vec<String>)> =
+        (0..SERVERS).map(|i| (format!("server-{}", i), Vec::new())).collect();
+
+    for i in 0..count {
+        let pattern = match i % 3 {
+            0 => format!("tool_{}", i),
+            1 => format!("ns{}_*", i),
+            _ => format!("*_op{}", i),
+        };
+        servers[i % SERVERS].1.push(pattern);
+    }
+
+    servers
+}
+
+/// Tool name matching a rule near the end of the list (worst case for a scan)
+fn probe_tool(count: usize) -> String {
+    let last_prefix = (0..count).rev().find(|i| i % 3 == 1).unwrap_or(1);
+    format!("ns{}_read", last_prefix)
+}
+
+fn compile(rules: &[(String, Vec<String>)]) -> ToolMatcher {
+    ToolMatcher::compile(rules.iter().map(|(id, patterns)| (id.as_str(), patterns)))
+}
+
+/// Benchmark compiled matcher lookups
+fn bench_compiled_lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("routing_rules/compiled");
+
+    for count in RULE_COUNTS {
+        let rules = mock_rules(count);
+        let matcher = compile(&rules);
+        let tool = probe_tool(count);
+
+        group.throughput(Throughput::Elements(1));
+        group.bench_with_input(BenchmarkId::from_parameter(count), &count, |b, _| {
+            b.iter(|| black_box(matcher.servers_for(black_box(&tool))));
+        });
+    }
+
+    group.finish();
+}
+
+/// Benchmark the naive per-request pattern scan for comparison
+fn bench_linear_scan(c: &mut Criterion) {
+    let mut group = c.benchmark_group("routing_rules/linear_scan");
+
+    for count in RULE_COUNTS {
+        let rules = mock_rules(count);
+        let tool = probe_tool(count);
+
+        group.throughput(Throughput::Elements(1));
+        group.bench_with_input(BenchmarkId::from_parameter(count), &count, |b, _| {
+            b.iter(|| {
+                let servers: Vec<&String> = rules
+                    .iter()
+                    .filter(|(_, patterns)| patterns.iter().any(|p| glob_match(p, &tool)))
+                    .map(|(id, _)| id)
+                    .collect();
+                black_box(servers)
+            });
+        });
+    }
+
+    group.finish();
+}
+
+/// Benchmark the cold-path compilation cost paid at config load/reload
+fn bench_compile(c: &mut Criterion) {
+    let mut group = c.benchmark_group("routing_rules/compile");
+
+    for count in RULE_COUNTS {
+        let rules = mock_rules(count);
+
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(count), &count, |b, _| {
+            b.iter(|| black_box(compile(&rules)));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_compiled_lookup,
+    bench_linear_scan,
+    bench_compile
+);
+criterion_main!(benches);
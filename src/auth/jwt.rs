@@ -93,9 +93,11 @@ pub struct Claims {
     pub exp: i64,
 
     /// Not before
+    #[serde(default)]
     pub nbf: i64,
 
     /// JWT ID (for revocation)
+    #[serde(default)]
     pub jti: String,
 
     /// Issuer
@@ -104,9 +106,12 @@ pub struct Claims {
     /// Audience
     pub aud: Vec<String>,
 
-    /// Custom claims
+    /// Custom claims (optional in tokens from other issuers)
+    #[serde(default)]
     pub roles: Vec<String>,
+    #[serde(default)]
     pub permissions: Vec<String>,
+    #[serde(default)]
     pub mfa_verified: bool,
 
     /// Session ID for tracking
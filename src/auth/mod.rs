@@ -3,9 +3,13 @@
 //! Provides comprehensive authentication and authorization:
 //! - JWT token validation - IMPLEMENTED in jwt.rs
 //! - OAuth2 flow - IMPLEMENTED in oauth.rs
+//! - Client certificate identities (mTLS) - IMPLEMENTED in mtls.rs
 //! - RBAC permission checking - IMPLEMENTED in rbac.rs
+//! - Tool access policies - IMPLEMENTED in policy.rs
 //! - API key management - Phase 3 feature (planned)
 
 pub mod jwt;
+pub mod mtls;
 pub mod oauth;
+pub mod policy;
 pub mod rbac;
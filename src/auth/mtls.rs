@@ -0,0 +1,140 @@
+//! Client certificate identities for mutual TLS.
+//!
+//! A listener with `tls.client_ca_path` verifies client certificates during
+//! the handshake. The verified certificate then identifies its holder by the
+//! subject common name (CN) and the subject alternative names, which
+//! `tls.client_roles` maps to the roles used by `auth.policies`.
+
+use simple_asn1::{from_der, oid, ASN1Block, ASN1Class, BigUint};
+
+/// GeneralName tags of the subject alternative names used as identities
+const SAN_EMAIL: u8 = 1;
+const SAN_DNS: u8 = 2;
+const SAN_URI: u8 = 6;
+
+/// Names identifying the holder of a DER-encoded X.509 certificate: the
+/// subject CN first, then the email, DNS and URI subject alternative names
+/// in certificate order. Empty when the certificate cannot be parsed.
+pub fn certificate_names(der: &[u8]) -> Vec<String> {
+    let Some(tbs) = tbs_certificate(der) else {
+        return Vec::new();
+    };
+
+    // serial, signature, issuer, validity, subject follow the optional
+    // explicit [0] version
+    let version = usize::from(matches!(tbs.first(), Some(ASN1Block::Explicit(..))));
+    let mut names: Vec<String> = tbs.get(version + 4).and_then(common_name).into_iter().collect();
+
+    let extensions = tbs.iter().find_map(|block| match block {
+        ASN1Block::Explicit(ASN1Class::ContextSpecific, _, tag, inner)
+            if *tag == BigUint::from(3u8) =>
+        {
+            Some(inner.as_ref())
+        },
+        _ => None,
+    });
+    if let Some(ASN1Block::Sequence(_, extensions)) = extensions {
+        names.extend(extensions.iter().flat_map(subject_alt_names));
+    }
+    names
+}
+
+/// Fields of the `tbsCertificate` sequence.
+fn tbs_certificate(der: &[u8]) -> Option<Vec<ASN1Block>> {
+    let mut blocks = from_der(der).ok()?;
+    let ASN1Block::Sequence(_, mut certificate) = blocks.pop()? else {
+        return None;
+    };
+    if certificate.is_empty() {
+        return None;
+    }
+    match certificate.swap_remove(0) {
+        ASN1Block::Sequence(_, tbs) => Some(tbs),
+        _ => None,
+    }
+}
+
+/// CN attribute of a distinguished name.
+fn common_name(name: &ASN1Block) -> Option<String> {
+    let ASN1Block::Sequence(_, rdns) = name else {
+        return None;
+    };
+    rdns.iter()
+        .filter_map(|rdn| match rdn {
+            ASN1Block::Set(_, attributes) => Some(attributes),
+            _ => None,
+        })
+        .flatten()
+        .find_map(|attribute| match attribute {
+            ASN1Block::Sequence(_, pair) => match pair.as_slice() {
+                [ASN1Block::ObjectIdentifier(_, id), value] if *id == oid!(2, 5, 4, 3) => {
+                    string_value(value)
+                },
+                _ => None,
+            },
+            _ => None,
+        })
+}
+
+/// Email, DNS and URI names of a subjectAltName extension; nothing for
+/// other extensions.
+fn subject_alt_names(extension: &ASN1Block) -> Vec<String> {
+    let ASN1Block::Sequence(_, fields) = extension else {
+        return Vec::new();
+    };
+    let (Some(ASN1Block::ObjectIdentifier(_, id)), Some(ASN1Block::OctetString(_, value))) =
+        (fields.first(), fields.last())
+    else {
+        return Vec::new();
+    };
+    if *id != oid!(2, 5, 29, 17) {
+        return Vec::new();
+    }
+
+    let Ok(mut blocks) = from_der(value) else {
+        return Vec::new();
+    };
+    let Some(ASN1Block::Sequence(_, general_names)) = blocks.pop() else {
+        return Vec::new();
+    };
+    general_names
+        .iter()
+        .filter_map(|name| match name {
+            ASN1Block::Unknown(ASN1Class::ContextSpecific, false, _, tag, bytes)
+                if [SAN_EMAIL, SAN_DNS, SAN_URI].iter().any(|t| *tag == BigUint::from(*t)) =>
+            {
+                String::from_utf8(bytes.clone()).ok()
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+fn string_value(block: &ASN1Block) -> Option<String> {
+    match block {
+        ASN1Block::UTF8String(_, s)
+        | ASN1Block::PrintableString(_, s)
+        | ASN1Block::IA5String(_, s)
+        | ASN1Block::TeletexString(_, s)
+        | ASN1Block::UniversalString(_, s)
+        | ASN1Block::BMPString(_, s) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_certificate_names() {
+        let pem = include_bytes!("../../tests/fixtures/tls/client.pem");
+        let der = rustls_pemfile::certs(&mut pem.as_slice()).next().unwrap().unwrap();
+
+        assert_eq!(
+            certificate_names(&der),
+            vec!["analyst-bot", "bot@example.com", "bot.example.com"]
+        );
+        assert!(certificate_names(b"not a certificate").is_empty());
+    }
+}
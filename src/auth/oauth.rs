@@ -1,8 +1,11 @@
 //! OAuth 2.0 / OIDC implementation with PKCE
 //!
 //! Supports multiple OAuth providers with automatic discovery,
-//! PKCE flow for enhanced security, and token introspection.
+//! PKCE flow for enhanced security, and token introspection. Backends that
+//! require bearer tokens use the client-credentials grant via
+//! [`ClientCredentials`].
 
+use crate::config::{BackendOAuthConfig, OutboundProxyConfig};
 use base64::Engine;
 use chrono::{DateTime, Duration, Utc};
 use dashmap::DashMap;
@@ -42,6 +45,9 @@ pub enum OAuthError {
 
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
+
+    #[error("Proxy configuration error: {0}")]
+    ProxyError(String),
 }
 
 /// OAuth2 configuration
@@ -55,6 +61,10 @@ pub struct OAuth2Config {
 
     /// Default scopes
     pub default_scopes: Vec<String>,
+
+    /// Outbound proxy for discovery and token requests
+    #[serde(default)]
+    pub outbound_proxy: Option<OutboundProxyConfig>,
 }
 
 /// Individual provider configuration
@@ -168,6 +178,8 @@ pub struct AuthorizeUrl {
 pub struct TokenResponse {
     pub access_token: String,
     pub token_type: String,
+    /// Token lifetime in seconds (0 when the server omits it)
+    #[serde(default)]
     pub expires_in: u64,
     pub refresh_token: Option<String>,
     pub id_token: Option<String>,
@@ -254,6 +266,9 @@ pub struct OAuth2Authenticator {
     /// Token introspection cache
     introspection_cache: Arc<DashMap<String, TokenInfo>>,
 
+    /// Client for discovery and token requests
+    client: reqwest::Client,
+
     /// Configuration
     config: OAuth2Config,
 }
@@ -261,6 +276,7 @@ pub struct OAuth2Authenticator {
 impl OAuth2Authenticator {
     /// Initialize OAuth with provider discovery
     pub async fn new(config: OAuth2Config) -> Result<Self, OAuthError> {
+        let client = http_client(config.outbound_proxy.as_ref())?;
         let mut providers = HashMap::new();
 
         for provider_config in &config.providers {
@@ -270,7 +286,8 @@ impl OAuth2Authenticator {
                 provider_config.issuer
             );
 
-            let discovery: OpenIdConfiguration = reqwest::get(&discovery_url).await?.json().await?;
+            let discovery: OpenIdConfiguration =
+                client.get(&discovery_url).send().await?.json().await?;
 
             let provider = OAuthProvider {
                 issuer: provider_config.issuer.clone(),
@@ -291,6 +308,7 @@ impl OAuth2Authenticator {
             _jwks_cache: Arc::new(RwLock::new(HashMap::new())),
             auth_codes: Arc::new(DashMap::new()),
             introspection_cache: Arc::new(DashMap::new()),
+            client,
             config,
         })
     }
@@ -373,7 +391,8 @@ impl OAuth2Authenticator {
         }
 
         // Exchange code for tokens
-        let token_response: TokenResponse = reqwest::Client::new()
+        let token_response: TokenResponse = self
+            .client
             .post(&provider.token_endpoint)
             .form(&params)
             .send()
@@ -467,7 +486,8 @@ impl OAuth2Authenticator {
             .get(provider_id)
             .ok_or_else(|| OAuthError::UnknownProvider(provider_id.to_string()))?;
 
-        let token_response: TokenResponse = reqwest::Client::new()
+        let token_response: TokenResponse = self
+            .client
             .post(&provider.token_endpoint)
             .form(&[
                 ("grant_type", "refresh_token"),
@@ -510,6 +530,121 @@ impl OAuth2Authenticator {
     }
 }
 
+/// Refresh tokens this long before the server says they expire.
+const TOKEN_REFRESH_MARGIN: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Lifetime assumed for tokens issued without `expires_in`.
+const DEFAULT_TOKEN_LIFETIME: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Access token cached by [`ClientCredentials`]
+#[derive(Debug, Clone)]
+struct CachedToken {
+    access_token: String,
+    refresh_at: std::time::Instant,
+}
+
+/// OAuth2 client-credentials token source for one backend.
+///
+/// Tokens are requested on first use and cached until shortly before they
+/// expire. Concurrent callers share a single token request.
+#[derive(Debug)]
+pub struct ClientCredentials {
+    config: BackendOAuthConfig,
+    client: reqwest::Client,
+    token: tokio::sync::Mutex<Option<CachedToken>>,
+}
+
+impl ClientCredentials {
+    /// Token source requesting tokens through `proxy`, if any (see
+    /// [`crate::config::Config::outbound_proxy`]).
+    pub fn new(
+        config: BackendOAuthConfig,
+        proxy: Option<&OutboundProxyConfig>,
+    ) -> Result<Self, OAuthError> {
+        Ok(Self {
+            config,
+            client: http_client(proxy)?,
+            token: tokio::sync::Mutex::new(None),
+        })
+    }
+
+    /// A valid access token, requesting a new one if none is cached or the
+    /// cached one is about to expire.
+    pub async fn access_token(&self) -> Result<String, OAuthError> {
+        let mut token = self.token.lock().await;
+        if let Some(cached) = token.as_ref() {
+            if std::time::Instant::now() < cached.refresh_at {
+                return Ok(cached.access_token.clone());
+            }
+        }
+
+        let fresh = self.request_token().await?;
+        let access_token = fresh.access_token.clone();
+        *token = Some(fresh);
+        Ok(access_token)
+    }
+
+    /// Drop the cached token, e.g. after the backend rejected it.
+    pub async fn invalidate(&self) {
+        *self.token.lock().await = None;
+    }
+
+    async fn request_token(&self) -> Result<CachedToken, OAuthError> {
+        let secret = self.config.client_secret().ok_or_else(|| {
+            OAuthError::ValidationError(format!(
+                "No client secret for OAuth client {}",
+                self.config.client_id
+            ))
+        })?;
+
+        let scope = self.config.scopes.join(" ");
+        let mut form = vec![
+            ("grant_type", "client_credentials"),
+            ("client_id", self.config.client_id.as_str()),
+            ("client_secret", secret.as_str()),
+        ];
+        if !scope.is_empty() {
+            form.push(("scope", scope.as_str()));
+        }
+
+        let response: TokenResponse = self
+            .client
+            .post(&self.config.token_url)
+            .form(&form)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let lifetime = match response.expires_in {
+            0 => DEFAULT_TOKEN_LIFETIME,
+            seconds => std::time::Duration::from_secs(seconds),
+        };
+        tracing::debug!(
+            "Obtained OAuth token for client {} from {} (expires in {:?})",
+            self.config.client_id,
+            self.config.token_url,
+            lifetime
+        );
+
+        Ok(CachedToken {
+            access_token: response.access_token,
+            refresh_at: std::time::Instant::now() + lifetime.saturating_sub(TOKEN_REFRESH_MARGIN),
+        })
+    }
+}
+
+/// Client for requests to OAuth servers, connecting through `proxy`.
+fn http_client(proxy: Option<&OutboundProxyConfig>) -> Result<reqwest::Client, OAuthError> {
+    let mut builder = reqwest::Client::builder();
+    if let Some(proxy) = proxy {
+        builder = crate::transport::outbound_proxy::configure_client(builder, proxy)
+            .map_err(OAuthError::ProxyError)?;
+    }
+    Ok(builder.build()?)
+}
+
 /// Generate secure random string
 fn generate_secure_random(length: usize) -> String {
     use rand::Rng;
@@ -546,9 +681,56 @@ mod tests {
             providers: vec![],
             redirect_uri: "http://localhost:8080/callback".to_string(),
             default_scopes: vec!["openid".to_string(), "profile".to_string()],
+            outbound_proxy: None,
         };
 
         let auth = OAuth2Authenticator::new(config).await;
         assert!(auth.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_client_credentials_requested_through_proxy() {
+        use wiremock::{
+            matchers::{body_string_contains, method, path},
+            Mock, MockServer, Request, ResponseTemplate,
+        };
+
+        // A forward proxy answering for a token server only it can reach
+        let proxy = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(|request: &Request| request.url.host_str() == Some("auth.invalid"))
+            .and(body_string_contains("grant_type=client_credentials"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "t0ken",
+                "token_type": "Bearer",
+                "expires_in": 3600
+            })))
+            .expect(1)
+            .mount(&proxy)
+            .await;
+
+        let config = BackendOAuthConfig {
+            token_url: "http://auth.invalid/token".to_string(),
+            client_id: "bot".to_string(),
+            client_secret: Some("s3cret".to_string()),
+            client_secret_env: None,
+            scopes: vec![],
+        };
+        let outbound = OutboundProxyConfig {
+            http_proxy: Some(proxy.uri()),
+            ..Default::default()
+        };
+        let credentials = ClientCredentials::new(config.clone(), Some(&outbound)).unwrap();
+        assert_eq!(credentials.access_token().await.unwrap(), "t0ken");
+
+        let invalid = OutboundProxyConfig {
+            http_proxy: Some("ftp://proxy.invalid".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            ClientCredentials::new(config, Some(&invalid)),
+            Err(OAuthError::ProxyError(_))
+        ));
+    }
 }
@@ -0,0 +1,137 @@
+//! Tool access policies (`auth.policies`).
+//!
+//! Each policy grants one role access to a set of tools, described with the
+//! same selectors as tool groups (`tag:`, `server:` or a name glob). A caller
+//! may use a tool when at least one of its roles allows it and none denies
+//! it. Callers without any role that has a policy, including unauthenticated
+//! ones, get `auth.default_policy`.
+
+use crate::config::{AuthConfig, McpServerConfig, PolicyAction, PolicyConfig, ToolSelector};
+use crate::routing::tool_matcher::glob_match;
+
+/// Whether a caller holding `roles` may see and call `tool` on `server`.
+///
+/// Without policies every tool is allowed. `server` is `None` when the
+/// providing backend is unknown; only name globs can match such a tool.
+pub fn tool_allowed(
+    auth: &AuthConfig,
+    roles: &[String],
+    server: Option<&McpServerConfig>,
+    tool: &str,
+) -> bool {
+    if auth.policies.is_empty() {
+        return true;
+    }
+
+    let policies: Vec<&PolicyConfig> =
+        auth.policies.iter().filter(|policy| roles.contains(&policy.role)).collect();
+    if policies.is_empty() {
+        return auth.default_policy == PolicyAction::Allow;
+    }
+
+    let matches = |selectors: &[ToolSelector]| {
+        selectors.iter().any(|selector| match (selector, server) {
+            (_, Some(server)) => selector.matches(server, tool),
+            (ToolSelector::Name(pattern), None) => glob_match(pattern, tool),
+            _ => false,
+        })
+    };
+
+    if policies.iter().any(|policy| matches(&policy.deny)) {
+        return false;
+    }
+    policies.iter().any(|policy| policy.allow.is_empty() || matches(&policy.allow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(id: &str) -> McpServerConfig {
+        serde_yaml::from_str(&format!(
+            "id: {id}\nname: {id}\ntransport: {{type: stdio, command: mcp-{id}}}\ntags: [{id}]\n"
+        ))
+        .unwrap()
+    }
+
+    fn auth() -> AuthConfig {
+        serde_yaml::from_str(
+            r#"
+policies:
+  - role: analyst
+    allow: ["github.*", "tag:search"]
+    deny: ["server:filesystem"]
+  - role: admin
+"#,
+        )
+        .unwrap()
+    }
+
+    fn roles(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_no_policies_allow_everything() {
+        let auth = AuthConfig::default();
+        assert!(tool_allowed(&auth, &[], None, "anything"));
+    }
+
+    #[test]
+    fn test_role_allow_and_deny() {
+        let auth = auth();
+        let analyst = roles(&["analyst"]);
+        let github = server("github");
+        let filesystem = server("filesystem");
+
+        assert!(tool_allowed(
+            &auth,
+            &analyst,
+            Some(&github),
+            "github.search_issues"
+        ));
+        assert!(tool_allowed(
+            &auth,
+            &analyst,
+            Some(&server("search")),
+            "web_search"
+        ));
+        assert!(!tool_allowed(&auth, &analyst, Some(&github), "create_repo"));
+        assert!(!tool_allowed(
+            &auth,
+            &analyst,
+            Some(&filesystem),
+            "github.read"
+        ));
+
+        // Empty allow list grants every tool
+        assert!(tool_allowed(
+            &auth,
+            &roles(&["admin"]),
+            Some(&filesystem),
+            "delete_file"
+        ));
+        // Deny from one role wins over another role's allow
+        assert!(!tool_allowed(
+            &auth,
+            &roles(&["admin", "analyst"]),
+            Some(&filesystem),
+            "read"
+        ));
+    }
+
+    #[test]
+    fn test_default_policy_for_uncovered_callers() {
+        let mut auth = auth();
+        let github = server("github");
+        assert!(!tool_allowed(&auth, &[], Some(&github), "github.search"));
+
+        auth.default_policy = PolicyAction::Allow;
+        assert!(tool_allowed(
+            &auth,
+            &roles(&["guest"]),
+            Some(&github),
+            "github.search"
+        ));
+    }
+}
@@ -0,0 +1,180 @@
+//! Adaptive batching window (`context_optimization.batching.adaptive`).
+//!
+//! Every server and method pair gets its own window, starting at
+//! `window_ms` and retuned after each batch it dispatches:
+//!
+//! - When the p95 of recent batch waits is over `target_p95_ms`, the window
+//!   is halved.
+//! - When requests arrive too rarely to share a window (less than one
+//!   expected arrival per window), waiting buys nothing and the window
+//!   shrinks by a quarter.
+//! - Otherwise it grows by a quarter, so more requests share a backend call
+//!   while the latency budget allows.
+//!
+//! Windows stay within `min_window_ms..=max_window_ms` and are exported as
+//! `only1mcp_batch_window_seconds{server_id,method}`.
+
+use super::{BatchKey, PendingBatch};
+use crate::config::AdaptiveBatchingConfig;
+use dashmap::DashMap;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Batch waits kept per key for the p95.
+const WAIT_SAMPLES: usize = 100;
+
+/// Weight of the newest gap in the mean gap between arrivals.
+const GAP_SMOOTHING: f64 = 0.2;
+
+/// Tuning state of one server and method.
+struct KeyState {
+    window: Duration,
+    last_arrival: Option<Instant>,
+    /// Smoothed time between arrivals
+    mean_gap: Option<Duration>,
+    waits: VecDeque<Duration>,
+}
+
+/// Windows of all server and method pairs.
+pub(super) struct WindowTuner {
+    config: AdaptiveBatchingConfig,
+    initial: Duration,
+    keys: DashMap<BatchKey, KeyState>,
+}
+
+impl WindowTuner {
+    pub(super) fn new(config: AdaptiveBatchingConfig, window_ms: u64) -> Self {
+        let initial =
+            Duration::from_millis(window_ms.clamp(config.min_window_ms, config.max_window_ms));
+        Self {
+            config,
+            initial,
+            keys: DashMap::new(),
+        }
+    }
+
+    /// Note a request arriving for `key` and return the window to use.
+    pub(super) fn arrive(&self, key: &BatchKey) -> Duration {
+        let now = Instant::now();
+        let mut state = self.keys.entry(key.clone()).or_insert_with(|| KeyState {
+            window: self.initial,
+            last_arrival: None,
+            mean_gap: None,
+            waits: VecDeque::with_capacity(WAIT_SAMPLES),
+        });
+        if let Some(last) = state.last_arrival {
+            let gap = now - last;
+            state.mean_gap = Some(match state.mean_gap {
+                Some(mean) => mean.mul_f64(1.0 - GAP_SMOOTHING) + gap.mul_f64(GAP_SMOOTHING),
+                None => gap,
+            });
+        }
+        state.last_arrival = Some(now);
+        state.window
+    }
+
+    /// Record the waits of a batch about to be dispatched and retune its
+    /// window.
+    pub(super) fn record_batch(&self, key: &BatchKey, batch: &PendingBatch) {
+        let Some(mut state) = self.keys.get_mut(key) else {
+            return;
+        };
+        for pending in &batch.requests {
+            if state.waits.len() == WAIT_SAMPLES {
+                state.waits.pop_front();
+            }
+            state.waits.push_back(pending.submitted_at.elapsed());
+        }
+
+        let p95 = percentile(&state.waits, 0.95);
+        let rare = state.mean_gap.map_or(true, |gap| gap > state.window);
+        let window = if p95 > Duration::from_millis(self.config.target_p95_ms) {
+            state.window / 2
+        } else if rare {
+            state.window.mul_f64(0.75)
+        } else {
+            state.window.mul_f64(1.25)
+        };
+        state.window = window.clamp(
+            Duration::from_millis(self.config.min_window_ms),
+            Duration::from_millis(self.config.max_window_ms),
+        );
+
+        crate::metrics::BATCH_WINDOW_SECONDS
+            .with_label_values(&[&key.server_id, &key.method])
+            .set(state.window.as_secs_f64());
+    }
+
+    /// Current window of `key`.
+    #[cfg(test)]
+    fn window(&self, key: &BatchKey) -> Duration {
+        self.keys.get(key).map_or(self.initial, |state| state.window)
+    }
+}
+
+/// The `quantile` of `samples`, zero when there are none.
+fn percentile(samples: &VecDeque<Duration>, quantile: f64) -> Duration {
+    let mut sorted: Vec<Duration> = samples.iter().copied().collect();
+    sorted.sort();
+    let index = ((sorted.len() as f64 * quantile).ceil() as usize).saturating_sub(1);
+    sorted.get(index).copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::batching::PendingRequest;
+    use crate::types::McpRequest;
+
+    fn key() -> BatchKey {
+        BatchKey {
+            server_id: "adaptive".to_string(),
+            method: "tools/list".to_string(),
+        }
+    }
+
+    /// A batch of requests submitted `waited` ago.
+    fn batch(size: usize, waited: Duration) -> PendingBatch {
+        let submitted_at = Instant::now() - waited;
+        PendingBatch {
+            requests: (0..size)
+                .map(|_| PendingRequest {
+                    request: McpRequest::new("tools/list", serde_json::json!({}), None),
+                    response_tx: tokio::sync::oneshot::channel().0,
+                    submitted_at,
+                })
+                .collect(),
+            deadline: submitted_at,
+            created_at: submitted_at,
+        }
+    }
+
+    #[test]
+    fn test_window_follows_arrivals_and_budget() {
+        let config = AdaptiveBatchingConfig {
+            enabled: true,
+            min_window_ms: 5,
+            max_window_ms: 200,
+            target_p95_ms: 50,
+        };
+        let tuner = WindowTuner::new(config, 40);
+        let key = key();
+
+        // Busy traffic within budget widens the window
+        for _ in 0..3 {
+            tuner.arrive(&key);
+        }
+        tuner.record_batch(&key, &batch(3, Duration::from_millis(10)));
+        assert_eq!(tuner.window(&key), Duration::from_millis(50));
+
+        // Waits over budget halve it
+        tuner.record_batch(&key, &batch(100, Duration::from_millis(80)));
+        assert_eq!(tuner.window(&key), Duration::from_millis(25));
+
+        // It never leaves its bounds
+        for _ in 0..20 {
+            tuner.record_batch(&key, &batch(100, Duration::from_millis(80)));
+        }
+        assert_eq!(tuner.window(&key), Duration::from_millis(5));
+    }
+}
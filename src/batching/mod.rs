@@ -24,6 +24,7 @@
 //!     window_ms: 100,
 //!     max_batch_size: 10,
 //!     methods: vec!["tools/list".to_string()],
+//!     adaptive: Default::default(),
 //! };
 //!
 //! let aggregator = BatchAggregator::new(config);
@@ -36,6 +37,8 @@
 //! # }
 //! ```
 
+mod adaptive;
+
 use crate::config::BatchingConfig;
 use crate::error::{Error, Result};
 use crate::types::{McpRequest, McpResponse};
@@ -59,8 +62,6 @@ struct BatchKey {
 struct PendingRequest {
     request: McpRequest,
     response_tx: oneshot::Sender<Result<McpResponse>>,
-    // Reserved for future metrics/logging - timestamp when request was submitted
-    #[allow(dead_code)]
     submitted_at: Instant,
 }
 
@@ -80,6 +81,8 @@ struct PendingBatch {
 pub struct BatchAggregator {
     batches: Arc<DashMap<BatchKey, PendingBatch>>,
     config: Arc<BatchConfig>,
+    /// Per server and method windows (`batching.adaptive`)
+    tuner: Option<Arc<adaptive::WindowTuner>>,
     /// Function to execute backend call (injected for testing)
     backend_caller: Arc<dyn Fn(String, McpRequest) -> Result<McpResponse> + Send + Sync>,
 }
@@ -87,9 +90,16 @@ pub struct BatchAggregator {
 impl BatchAggregator {
     /// Create a new batch aggregator with configuration
     pub fn new(config: BatchConfig) -> Self {
+        let tuner = config.adaptive.enabled.then(|| {
+            Arc::new(adaptive::WindowTuner::new(
+                config.adaptive.clone(),
+                config.window_ms,
+            ))
+        });
         Self {
             batches: Arc::new(DashMap::new()),
             config: Arc::new(config),
+            tuner,
             backend_caller: Arc::new(|_, _| {
                 Err(Error::Server(
                     "Backend caller not initialized - use with_backend_caller".to_string(),
@@ -140,17 +150,21 @@ impl BatchAggregator {
         }
 
         let key = BatchKey {
-            server_id: server_id.clone(),
+            server_id,
             method: request.method.clone(),
         };
 
         let (tx, rx) = oneshot::channel();
         let submitted_at = Instant::now();
+        let window = match &self.tuner {
+            Some(tuner) => tuner.arrive(&key),
+            None => Duration::from_millis(self.config.window_ms),
+        };
 
         // Add to batch
         let should_start_timer = {
             let mut batch = self.batches.entry(key.clone()).or_insert_with(|| {
-                let deadline = Instant::now() + Duration::from_millis(self.config.window_ms);
+                let deadline = Instant::now() + window;
                 PendingBatch {
                     requests: Vec::new(),
                     deadline,
@@ -171,11 +185,12 @@ impl BatchAggregator {
             if batch_size >= self.config.max_batch_size {
                 // Remove and process immediately
                 drop(batch); // Release DashMap lock
-                if let Some((_, batch)) = self.batches.remove(&key) {
+                if let Some((key, batch)) = self.batches.remove(&key) {
                     Self::process_batch_static(
-                        server_id.clone(),
+                        key,
                         batch,
                         self.backend_caller.clone(),
+                        self.tuner.clone(),
                     );
                 }
                 return rx
@@ -190,12 +205,12 @@ impl BatchAggregator {
         if should_start_timer {
             let batches = self.batches.clone();
             let backend_caller = self.backend_caller.clone();
-            let window = Duration::from_millis(self.config.window_ms);
+            let tuner = self.tuner.clone();
 
             tokio::spawn(async move {
                 sleep(window).await;
-                if let Some((_, batch)) = batches.remove(&key) {
-                    Self::process_batch_static(server_id, batch, backend_caller);
+                if let Some((key, batch)) = batches.remove(&key) {
+                    Self::process_batch_static(key, batch, backend_caller, tuner);
                 }
             });
         }
@@ -207,10 +222,15 @@ impl BatchAggregator {
 
     /// Process a batch and distribute responses to all waiting clients
     fn process_batch_static(
-        server_id: String,
+        key: BatchKey,
         batch: PendingBatch,
         backend_caller: Arc<dyn Fn(String, McpRequest) -> Result<McpResponse> + Send + Sync>,
+        tuner: Option<Arc<adaptive::WindowTuner>>,
     ) {
+        if let Some(tuner) = &tuner {
+            tuner.record_batch(&key, &batch);
+        }
+        let server_id = key.server_id;
         tokio::spawn(async move {
             let batch_size = batch.requests.len();
             let wait_time = batch.created_at.elapsed();
@@ -233,6 +253,18 @@ impl BatchAggregator {
             // Make single backend call
             let result = backend_caller(server_id.clone(), representative_request.clone());
 
+            // Every waiter but the first gets the response without a backend call
+            if let (true, Ok(response)) = (batch_size > 1, &result) {
+                if let Ok(serialized) = serde_json::to_vec(response) {
+                    let tokens = crate::metrics::tokens::estimate_payload_tokens(&serialized);
+                    crate::metrics::record_context_optimization(
+                        &server_id,
+                        "batching",
+                        tokens * (batch_size as u64 - 1),
+                    );
+                }
+            }
+
             // Distribute response to all waiters
             for pending in batch.requests {
                 // Clone response for each waiter
@@ -260,6 +292,29 @@ impl BatchAggregator {
         self.batches.len()
     }
 
+    /// Dispatch every pending batch immediately instead of waiting for its
+    /// window to close (used when draining on shutdown).
+    ///
+    /// Returns the number of batches flushed.
+    pub fn flush_all(&self) -> usize {
+        let keys: Vec<BatchKey> = self.batches.iter().map(|entry| entry.key().clone()).collect();
+        let mut flushed = 0;
+
+        for key in keys {
+            if let Some((key, batch)) = self.batches.remove(&key) {
+                Self::process_batch_static(
+                    key,
+                    batch,
+                    self.backend_caller.clone(),
+                    self.tuner.clone(),
+                );
+                flushed += 1;
+            }
+        }
+
+        flushed
+    }
+
     /// Clear all pending batches (for testing/shutdown)
     pub async fn clear(&self) {
         self.batches.clear();
@@ -277,6 +332,7 @@ mod tests {
             window_ms: 50, // Shorter for tests
             max_batch_size: 3,
             methods: vec!["tools/list".to_string()],
+            adaptive: Default::default(),
         }
     }
 
@@ -337,6 +393,62 @@ mod tests {
         assert_eq!(response.result.unwrap()["direct"], json!(true));
     }
 
+    #[tokio::test]
+    async fn test_flush_all_dispatches_pending_batches() {
+        let config = BatchConfig {
+            window_ms: 60_000, // Would never flush on its own during the test
+            ..test_config()
+        };
+        let aggregator = BatchAggregator::new(config).with_backend_caller(|_, req| {
+            Ok(McpResponse {
+                jsonrpc: "2.0".to_string(),
+                id: req.id,
+                result: Some(json!({"tools": []})),
+                error: None,
+            })
+        });
+
+        let pending = {
+            let aggregator = aggregator.clone();
+            tokio::spawn(async move {
+                aggregator.submit_request("server1".to_string(), sample_request(1)).await
+            })
+        };
+        while aggregator.active_batch_count() == 0 {
+            tokio::task::yield_now().await;
+        }
+
+        assert_eq!(aggregator.flush_all(), 1);
+        let response = tokio::time::timeout(Duration::from_secs(1), pending)
+            .await
+            .expect("flushed batch should complete")
+            .unwrap()
+            .unwrap();
+        assert!(response.result.is_some());
+        assert_eq!(aggregator.active_batch_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_batched_waiters_record_tokens_saved() {
+        let aggregator =
+            BatchAggregator::new(test_config()).with_backend_caller(|_, _| Ok(sample_response()));
+
+        let responses =
+            futures::future::join_all((1..=3).map(|id| {
+                aggregator.submit_request("batch-tokens".to_string(), sample_request(id))
+            }))
+            .await;
+        assert!(responses.iter().all(|r| r.is_ok()));
+
+        let per_response = crate::metrics::tokens::estimate_payload_tokens(
+            &serde_json::to_vec(&sample_response()).unwrap(),
+        );
+        let saved = crate::metrics::CONTEXT_TOKENS_SAVED
+            .with_label_values(&["batch-tokens", "batching"])
+            .get();
+        assert_eq!(saved, (per_response * 2) as f64);
+    }
+
     #[tokio::test]
     async fn test_default_config() {
         let config = BatchConfig::default();
@@ -0,0 +1,278 @@
+//! Built-in load generator used by `only1mcp benchmark` and the load tests.
+//!
+//! Drives a running proxy with JSON-RPC requests from a fixed number of
+//! concurrent workers, optionally paced to a target request rate, and
+//! summarises latency percentiles and error counts in a [`BenchmarkReport`].
+//!
+//! When a target rate is set, latency is measured from each request's
+//! scheduled send time rather than the moment a worker got around to it, so
+//! a stalled proxy cannot hide its queueing delay (coordinated omission).
+
+use crate::error::{Error, Result};
+use serde_json::{json, Value};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Load generation parameters.
+#[derive(Debug, Clone)]
+pub struct BenchmarkConfig {
+    /// JSON-RPC endpoint of the proxy (e.g. `http://127.0.0.1:8080/mcp`)
+    pub url: String,
+    /// Total number of requests to send
+    pub requests: usize,
+    /// Number of concurrent workers
+    pub concurrency: usize,
+    /// Requests per second to pace at; `None` sends as fast as possible
+    pub target_rps: Option<f64>,
+    /// JSON-RPC method to call
+    pub method: String,
+    /// JSON-RPC params sent with every request
+    pub params: Value,
+    /// Per-request client timeout
+    pub timeout: Duration,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            url: "http://127.0.0.1:8080/mcp".to_string(),
+            requests: 10_000,
+            concurrency: 100,
+            target_rps: None,
+            method: "tools/list".to_string(),
+            params: json!({}),
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Latency distribution of successful requests.
+#[derive(Debug, Clone, Default)]
+pub struct LatencySummary {
+    pub min: Duration,
+    pub mean: Duration,
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+impl LatencySummary {
+    /// Summarise a set of samples (sorted in place).
+    pub fn from_samples(samples: &mut [Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_unstable();
+
+        let total: Duration = samples.iter().sum();
+        Self {
+            min: samples[0],
+            mean: total / samples.len() as u32,
+            p50: percentile(samples, 50.0),
+            p90: percentile(samples, 90.0),
+            p99: percentile(samples, 99.0),
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted samples.
+fn percentile(sorted: &[Duration], pct: f64) -> Duration {
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Outcome of a benchmark run.
+#[derive(Debug, Clone)]
+pub struct BenchmarkReport {
+    /// Requests that returned a JSON-RPC result
+    pub succeeded: usize,
+    /// Transport failures, non-2xx statuses and JSON-RPC error responses
+    pub errors: usize,
+    /// Wall-clock duration of the run
+    pub elapsed: Duration,
+    /// Latency of successful requests
+    pub latency: LatencySummary,
+}
+
+impl BenchmarkReport {
+    /// Total requests sent.
+    pub fn total(&self) -> usize {
+        self.succeeded + self.errors
+    }
+
+    /// Achieved throughput in requests per second.
+    pub fn achieved_rps(&self) -> f64 {
+        self.total() as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// Fraction of requests that failed (0.0 - 1.0).
+    pub fn error_rate(&self) -> f64 {
+        if self.total() == 0 {
+            return 0.0;
+        }
+        self.errors as f64 / self.total() as f64
+    }
+}
+
+impl fmt::Display for BenchmarkReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Requests:   {} ({} ok, {} errors)",
+            self.total(),
+            self.succeeded,
+            self.errors
+        )?;
+        writeln!(
+            f,
+            "Duration:   {:.2?} ({:.1} req/s)",
+            self.elapsed,
+            self.achieved_rps()
+        )?;
+        write!(
+            f,
+            "Latency:    min {:.2?}  mean {:.2?}  p50 {:.2?}  p90 {:.2?}  p99 {:.2?}  max {:.2?}",
+            self.latency.min,
+            self.latency.mean,
+            self.latency.p50,
+            self.latency.p90,
+            self.latency.p99,
+            self.latency.max
+        )
+    }
+}
+
+/// Run a benchmark against `config.url`.
+pub async fn run(config: BenchmarkConfig) -> Result<BenchmarkReport> {
+    if config.requests == 0 || config.concurrency == 0 {
+        return Err(Error::Config(
+            "benchmark requests and concurrency must be non-zero".into(),
+        ));
+    }
+    if matches!(config.target_rps, Some(rps) if rps <= 0.0) {
+        return Err(Error::Config(
+            "benchmark target rps must be positive".into(),
+        ));
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(config.timeout)
+        .pool_max_idle_per_host(config.concurrency)
+        .build()
+        .map_err(|e| Error::Http(e.to_string()))?;
+
+    let config = Arc::new(config);
+    let next = Arc::new(AtomicUsize::new(0));
+    let start = Instant::now();
+
+    let workers: Vec<_> = (0..config.concurrency.min(config.requests))
+        .map(|_| {
+            let client = client.clone();
+            let config = config.clone();
+            let next = next.clone();
+            tokio::spawn(async move { worker(client, config, next, start).await })
+        })
+        .collect();
+
+    let mut samples = Vec::with_capacity(config.requests);
+    let mut errors = 0;
+    for worker in workers {
+        let (worker_samples, worker_errors) =
+            worker.await.map_err(|e| Error::Internal(e.to_string()))?;
+        samples.extend(worker_samples);
+        errors += worker_errors;
+    }
+
+    Ok(BenchmarkReport {
+        succeeded: samples.len(),
+        errors,
+        elapsed: start.elapsed(),
+        latency: LatencySummary::from_samples(&mut samples),
+    })
+}
+
+/// Claim and send requests until the run's budget is used up.
+async fn worker(
+    client: reqwest::Client,
+    config: Arc<BenchmarkConfig>,
+    next: Arc<AtomicUsize>,
+    start: Instant,
+) -> (Vec<Duration>, usize) {
+    let mut samples = Vec::new();
+    let mut errors = 0;
+
+    loop {
+        let index = next.fetch_add(1, Ordering::Relaxed);
+        if index >= config.requests {
+            break;
+        }
+
+        let scheduled = match config.target_rps {
+            Some(rps) => {
+                let at = start + Duration::from_secs_f64(index as f64 / rps);
+                tokio::time::sleep_until(at).await;
+                at
+            },
+            None => Instant::now(),
+        };
+
+        let payload = json!({
+            "jsonrpc": "2.0",
+            "id": index,
+            "method": config.method,
+            "params": config.params,
+        });
+
+        if send(&client, &config.url, &payload).await {
+            samples.push(scheduled.elapsed());
+        } else {
+            errors += 1;
+        }
+    }
+
+    (samples, errors)
+}
+
+/// Send one request; true if it produced a JSON-RPC result.
+async fn send(client: &reqwest::Client, url: &str, payload: &Value) -> bool {
+    let response = match client.post(url).json(payload).send().await {
+        Ok(response) if response.status().is_success() => response,
+        _ => return false,
+    };
+
+    match response.json::<Value>().await {
+        Ok(body) => body.get("error").is_none(),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_percentiles() {
+        let mut samples: Vec<Duration> = (1..=100).rev().map(Duration::from_millis).collect();
+        let summary = LatencySummary::from_samples(&mut samples);
+
+        assert_eq!(summary.min, Duration::from_millis(1));
+        assert_eq!(summary.p50, Duration::from_millis(50));
+        assert_eq!(summary.p90, Duration::from_millis(90));
+        assert_eq!(summary.p99, Duration::from_millis(99));
+        assert_eq!(summary.max, Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_empty_run() {
+        let config = BenchmarkConfig {
+            requests: 0,
+            ..Default::default()
+        };
+        assert!(run(config).await.is_err());
+    }
+}
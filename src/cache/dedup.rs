@@ -0,0 +1,140 @@
+//! Content-addressed storage of cached response bodies.
+//!
+//! Backends often return identical payloads, such as shared documentation
+//! resources, and the same body can be cached under many keys (per-server
+//! lists, the aggregated list, reads with different params). Bodies are
+//! stored once per blake3 hash and reference-counted per cache key using
+//! them; the last entry to be evicted frees the body. Each further key a
+//! stored body is cached under adds its estimated tokens to
+//! `only1mcp_context_tokens_saved_total{optimization_type="deduplication"}`
+//! of the servers it came from,
+//! and the memory all bodies take is exported as
+//! `only1mcp_memory_usage_bytes{type="cache"}`.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use bytes::Bytes;
+use dashmap::DashMap;
+
+use super::TokenSources;
+
+/// blake3 hash of a body.
+pub(super) type BodyHash = [u8; 32];
+
+/// A stored body and the cache entries referencing it.
+struct StoredBody {
+    body: Bytes,
+    /// Size before compression
+    raw_len: usize,
+    /// References per cache key. Re-setting a key briefly holds two, as
+    /// the replaced entry is evicted asynchronously.
+    refs: HashMap<String, usize>,
+}
+
+/// Response bodies by content hash.
+#[derive(Default)]
+pub(super) struct ContentStore {
+    bodies: DashMap<BodyHash, StoredBody>,
+    raw_bytes: AtomicU64,
+    stored_bytes: AtomicU64,
+}
+
+impl ContentStore {
+    /// Take a reference to `body` for the entry cached under `key`,
+    /// returning its hash and the stored copy, which is shared with every
+    /// other entry holding the same content. `raw_len` is the size of the
+    /// body before compression, and `sources` where it came from.
+    pub(super) fn intern(
+        &self,
+        key: &str,
+        body: Bytes,
+        raw_len: usize,
+        sources: &TokenSources,
+    ) -> (BodyHash, Bytes) {
+        let hash = *blake3::hash(&body).as_bytes();
+        let mut stored = self.bodies.entry(hash).or_insert_with(|| {
+            self.raw_bytes.fetch_add(raw_len as u64, Ordering::Relaxed);
+            let stored = self.stored_bytes.fetch_add(body.len() as u64, Ordering::Relaxed);
+            record_memory(stored + body.len() as u64);
+            StoredBody {
+                body: body.clone(),
+                raw_len,
+                refs: HashMap::new(),
+            }
+        });
+        // Replacing a key's entry with the same body saves nothing
+        if !stored.refs.is_empty() && !stored.refs.contains_key(key) {
+            sources.record("deduplication");
+        }
+        *stored.refs.entry(key.to_string()).or_default() += 1;
+        (hash, stored.body.clone())
+    }
+
+    /// Drop a reference taken by [`ContentStore::intern`] for `key`,
+    /// freeing the body with its last reference.
+    pub(super) fn release(&self, hash: &BodyHash, key: &str) {
+        let removed = self.bodies.remove_if_mut(hash, |_, stored| {
+            if let Some(refs) = stored.refs.get_mut(key) {
+                *refs -= 1;
+                if *refs == 0 {
+                    stored.refs.remove(key);
+                }
+            }
+            stored.refs.is_empty()
+        });
+        if let Some((_, stored)) = removed {
+            self.raw_bytes.fetch_sub(stored.raw_len as u64, Ordering::Relaxed);
+            let len = stored.body.len() as u64;
+            record_memory(self.stored_bytes.fetch_sub(len, Ordering::Relaxed) - len);
+        }
+    }
+
+    /// Number of distinct bodies stored.
+    pub(super) fn len(&self) -> usize {
+        self.bodies.len()
+    }
+
+    /// Size of the distinct bodies before compression.
+    pub(super) fn raw_bytes(&self) -> u64 {
+        self.raw_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Memory the distinct bodies take as stored.
+    pub(super) fn stored_bytes(&self) -> u64 {
+        self.stored_bytes.load(Ordering::Relaxed)
+    }
+}
+
+fn record_memory(bytes: u64) {
+    crate::metrics::MEMORY_USAGE_BYTES
+        .with_label_values(&["cache"])
+        .set(bytes as f64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_bodies_are_stored_once() {
+        let store = ContentStore::default();
+        let body = || Bytes::from(r#"{"contents":[{"text":"shared docs"}]}"#);
+        let sources = TokenSources::default();
+
+        let (first, a) = store.intern("a", body(), body().len(), &sources);
+        let (second, b) = store.intern("b", body(), body().len(), &sources);
+        let (other, _) = store.intern("c", Bytes::from_static(b"other"), 5, &sources);
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+        assert_eq!(a.as_ptr(), b.as_ptr());
+        assert_eq!(store.len(), 2);
+
+        // The body lives until its last reference is released
+        store.release(&first, "a");
+        assert_eq!(store.len(), 2);
+        store.release(&second, "b");
+        store.release(&other, "c");
+        assert_eq!(store.len(), 0);
+    }
+}
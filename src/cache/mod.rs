@@ -10,33 +10,237 @@
 //! - Automatic LRU eviction when capacity is reached
 //! - Lock-free concurrent access
 //! - Async API compatible with Tokio
+//!
+//! Values are stored as [`Bytes`], so a hit is a reference-count bump rather
+//! than a copy of the serialized response.
+//!
+//! With a stale grace period (`stale_grace_seconds`), expired entries are
+//! kept that much longer. [`LayeredCache::get_or_revalidate`] serves them
+//! while a single background task refreshes the entry, so the clients of a
+//! popular key do not all reach the backend at once when it expires.
+//!
+//! Each entry carries the estimated tokens of its body per backend server it
+//! came from ([`TokenSources`]), worked out when it is stored, so hits and
+//! deduplicated copies are reported as tokens saved per server without
+//! looking at the body again.
+//!
+//! Identical bodies cached under different keys are stored once (see
+//! [`dedup`]), and bodies above a size threshold can be stored
+//! zstd-compressed, per tier, trading CPU on every hit for more entries in
+//! the same memory.
+//!
+//! Tiers are bounded by entry count, or by the bytes their entries take
+//! when given a memory budget (`max_bytes`), so a few huge responses cannot
+//! take all memory.
+
+mod dedup;
 
-use crate::error::{Error, Result};
+use self::dedup::{BodyHash, ContentStore};
+use crate::error::Result;
 use crate::types::{McpRequest, McpResponse};
+use arc_swap::ArcSwap;
+use bytes::Bytes;
+use dashmap::DashSet;
 use moka::future::Cache;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
-use tracing::info;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
 
 /// Multi-layer caching system with different TTLs per operation type.
 /// Implements automatic TTL expiration and LRU eviction using moka.
+///
+/// The tiers sit behind an [`ArcSwap`] so [`LayeredCache::reconfigure`] can
+/// replace them on a config reload while handlers keep sharing one instance.
 pub struct LayeredCache {
+    /// Current cache tiers and the configuration they were built from
+    tiers: ArcSwap<CacheTiers>,
+
+    /// Metrics for cache effectiveness monitoring
+    metrics: Arc<CacheMetrics>,
+
+    /// Keys a background refresh is running for
+    refreshing: Arc<DashSet<String>>,
+
+    /// Bodies of all entries, stored once per content hash
+    bodies: Arc<ContentStore>,
+}
+
+/// Estimated tokens of a cached body per backend server it came from.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TokenSources(Arc<[(String, u64)]>);
+
+impl TokenSources {
+    /// A body that came from `server_id` alone.
+    pub fn server(server_id: &str, body: &[u8]) -> Self {
+        let tokens = crate::metrics::tokens::estimate_payload_tokens(body);
+        Self(Arc::from([(server_id.to_string(), tokens)]))
+    }
+
+    /// Tokens per server.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.0.iter().map(|(server, tokens)| (server.as_str(), *tokens))
+    }
+
+    /// Record the tokens as saved by `optimization_type`, per server.
+    pub fn record(&self, optimization_type: &str) {
+        for (server, tokens) in self.iter().filter(|(_, tokens)| *tokens > 0) {
+            crate::metrics::record_context_optimization(server, optimization_type, tokens);
+        }
+    }
+}
+
+/// Sums the tokens of each server.
+impl FromIterator<(String, u64)> for TokenSources {
+    fn from_iter<I: IntoIterator<Item = (String, u64)>>(iter: I) -> Self {
+        let mut totals = std::collections::BTreeMap::new();
+        for (server, tokens) in iter {
+            *totals.entry(server).or_insert(0) += tokens;
+        }
+        Self(totals.into_iter().collect())
+    }
+}
+
+/// A cached value and how long it lives.
+#[derive(Clone)]
+struct CacheEntry {
+    /// Shared with every entry holding the same body
+    value: Bytes,
+    hash: BodyHash,
+    /// Where the body came from, for reporting tokens saved
+    sources: TokenSources,
+    /// Size of the body before compression; `None` when stored raw
+    raw_len: Option<usize>,
+    /// Lifetime in the cache, including the stale grace period
+    ttl: Duration,
+    /// When the value goes stale
+    fresh_until: Instant,
+}
+
+impl CacheEntry {
+    fn is_fresh(&self) -> bool {
+        Instant::now() < self.fresh_until
+    }
+
+    /// The body as it was stored, decompressed if necessary.
+    fn body(&self) -> Option<Bytes> {
+        let Some(raw_len) = self.raw_len else {
+            return Some(self.value.clone());
+        };
+        match zstd::bulk::decompress(&self.value, raw_len) {
+            Ok(body) => Some(body.into()),
+            Err(e) => {
+                warn!("Dropping undecodable cache entry: {}", e);
+                None
+            },
+        }
+    }
+}
+
+/// Expires each entry after its own TTL, so methods can override the TTL of
+/// the tier they are stored in (`method_ttls`).
+struct EntryExpiry;
+
+impl moka::Expiry<String, CacheEntry> for EntryExpiry {
+    fn expire_after_create(
+        &self,
+        _key: &String,
+        entry: &CacheEntry,
+        _created_at: Instant,
+    ) -> Option<Duration> {
+        Some(entry.ttl)
+    }
+
+    fn expire_after_update(
+        &self,
+        _key: &String,
+        entry: &CacheEntry,
+        _updated_at: Instant,
+        _duration_until_expiry: Option<Duration>,
+    ) -> Option<Duration> {
+        Some(entry.ttl)
+    }
+}
+
+/// The three moka caches built from one [`CacheConfig`].
+struct CacheTiers {
     /// L1: Hot cache for frequently accessed tools (5 min TTL)
-    l1_tools: Arc<Cache<String, Vec<u8>>>,
+    l1_tools: Arc<Cache<String, CacheEntry>>,
 
     /// L2: Warm cache for resource listings (30 min TTL)
-    l2_resources: Arc<Cache<String, Vec<u8>>>,
+    l2_resources: Arc<Cache<String, CacheEntry>>,
 
     /// L3: Cold cache for static prompts (2 hour TTL)
-    l3_prompts: Arc<Cache<String, Vec<u8>>>,
+    l3_prompts: Arc<Cache<String, CacheEntry>>,
 
     /// Configuration for cache behavior
     config: CacheConfig,
+}
 
-    /// Metrics for cache effectiveness monitoring
-    metrics: Arc<CacheMetrics>,
+impl CacheTiers {
+    fn new(config: CacheConfig, bodies: &Arc<ContentStore>) -> Self {
+        let tier = |capacity: u64, max_bytes: Option<u64>| {
+            let bodies = bodies.clone();
+            let builder = match max_bytes {
+                Some(max_bytes) => Cache::builder()
+                    .weigher(|key: &String, entry: &CacheEntry| {
+                        (key.len() + entry.value.len()).try_into().unwrap_or(u32::MAX)
+                    })
+                    .max_capacity(max_bytes),
+                None => Cache::builder().max_capacity(capacity),
+            };
+            builder
+                .expire_after(EntryExpiry)
+                .support_invalidation_closures()
+                .eviction_listener(move |key, entry: CacheEntry, _cause| {
+                    bodies.release(&entry.hash, &key);
+                    crate::metrics::CACHE_EVICTIONS_TOTAL.inc();
+                })
+                .build()
+        };
+
+        Self {
+            // L1 (tools), L2 (resources) and L3 (prompts)
+            l1_tools: Arc::new(tier(config.l1_capacity, config.l1_max_bytes)),
+            l2_resources: Arc::new(tier(config.l2_capacity, config.l2_max_bytes)),
+            l3_prompts: Arc::new(tier(config.l3_capacity, config.l3_max_bytes)),
+            config,
+        }
+    }
+
+    /// Intelligent cache layer selection based on request type.
+    fn layer_for(&self, method: &str) -> &Cache<String, CacheEntry> {
+        match method {
+            // Tool operations are frequently accessed, short TTL
+            "tools/list" | "tools/call" => &self.l1_tools,
+
+            // Resource operations are less frequent, medium TTL
+            "resources/list" | "resources/read" => &self.l2_resources,
+
+            // Prompts are static, long TTL
+            "prompts/list" | "prompts/get" => &self.l3_prompts,
+
+            // Default to L1 for unknown methods
+            _ => &self.l1_tools,
+        }
+    }
+
+    /// Get total cache size across all layers.
+    fn total_size(&self) -> u64 {
+        self.l1_tools.entry_count()
+            + self.l2_resources.entry_count()
+            + self.l3_prompts.entry_count()
+    }
+
+    /// Run pending moka maintenance on every layer.
+    async fn run_pending_tasks(&self) {
+        self.l1_tools.run_pending_tasks().await;
+        self.l2_resources.run_pending_tasks().await;
+        self.l3_prompts.run_pending_tasks().await;
+    }
 }
 
 /// Alias for the main cache type used by the application
@@ -45,75 +249,171 @@ pub type ResponseCache = LayeredCache;
 impl LayeredCache {
     /// Create a new multi-layer cache with moka-based TTL and LRU.
     pub fn new(config: CacheConfig) -> Self {
-        // Create L1 cache (tools) with 5-minute TTL
-        let l1_tools = Cache::builder()
-            .max_capacity(config.l1_capacity)
-            .time_to_live(config.l1_ttl)
-            .eviction_listener(|_key, _value: Vec<u8>, _cause| {
-                crate::metrics::CACHE_EVICTIONS_TOTAL.inc();
-            })
-            .build();
-
-        // Create L2 cache (resources) with 30-minute TTL
-        let l2_resources = Cache::builder()
-            .max_capacity(config.l2_capacity)
-            .time_to_live(config.l2_ttl)
-            .eviction_listener(|_key, _value: Vec<u8>, _cause| {
-                crate::metrics::CACHE_EVICTIONS_TOTAL.inc();
-            })
-            .build();
-
-        // Create L3 cache (prompts) with 2-hour TTL
-        let l3_prompts = Cache::builder()
-            .max_capacity(config.l3_capacity)
-            .time_to_live(config.l3_ttl)
-            .eviction_listener(|_key, _value: Vec<u8>, _cause| {
-                crate::metrics::CACHE_EVICTIONS_TOTAL.inc();
-            })
-            .build();
-
+        let bodies = Arc::new(ContentStore::default());
         Self {
-            l1_tools: Arc::new(l1_tools),
-            l2_resources: Arc::new(l2_resources),
-            l3_prompts: Arc::new(l3_prompts),
-            config,
+            tiers: ArcSwap::from_pointee(CacheTiers::new(config, &bodies)),
             metrics: Arc::new(CacheMetrics::default()),
+            refreshing: Arc::new(DashSet::new()),
+            bodies,
         }
     }
 
+    /// Replace the cache settings, discarding all cached entries.
+    ///
+    /// Capacities and TTLs are fixed when a moka cache is built, so new tiers
+    /// are created; in-flight readers finish against the old ones.
+    pub async fn reconfigure(&self, config: CacheConfig) {
+        let old = self.tiers.swap(Arc::new(CacheTiers::new(config, &self.bodies)));
+        old.l1_tools.invalidate_all();
+        old.l2_resources.invalidate_all();
+        old.l3_prompts.invalidate_all();
+        old.run_pending_tasks().await;
+
+        crate::metrics::CACHE_SIZE_ENTRIES.set(0);
+        info!("Cache reconfigured");
+    }
+
+    /// Settings the cache is currently running with.
+    pub fn config(&self) -> CacheConfig {
+        self.tiers.load().config.clone()
+    }
+
     /// Get a cached response if available (moka handles TTL automatically).
-    pub async fn get(&self, key: &str) -> Option<Vec<u8>> {
-        if !self.config.enabled {
+    ///
+    /// Stale entries count as misses; see [`LayeredCache::get_or_revalidate`].
+    pub async fn get(&self, key: &str) -> Option<Bytes> {
+        self.get_with_sources(key).await.map(|(value, _)| value)
+    }
+
+    /// Like [`LayeredCache::get`], also returning where the body came from.
+    pub async fn get_with_sources(&self, key: &str) -> Option<(Bytes, TokenSources)> {
+        match self.lookup(key).await {
+            Some((value, sources, true)) => {
+                self.record_hit();
+                Some((value, sources))
+            },
+            _ => {
+                self.record_miss();
+                None
+            },
+        }
+    }
+
+    /// Get a cached response, serving it for up to the stale grace period
+    /// after it expires.
+    ///
+    /// The first lookup of a stale entry spawns `refresh`, which should
+    /// fetch the response again and [`set`](LayeredCache::set) it; later
+    /// lookups keep getting the stale value until it does, without starting
+    /// another refresh. Stale serves are counted in
+    /// `only1mcp_cache_stale_serves_total`. Hits come with the servers the
+    /// body came from.
+    pub async fn get_or_revalidate<F, Fut>(
+        &self,
+        key: &str,
+        refresh: F,
+    ) -> Option<(Bytes, TokenSources)>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let Some((value, sources, fresh)) = self.lookup(key).await else {
+            self.record_miss();
+            return None;
+        };
+        self.record_hit();
+        if fresh {
+            return Some((value, sources));
+        }
+
+        crate::metrics::CACHE_STALE_SERVES_TOTAL.inc();
+        if self.refreshing.insert(key.to_string()) {
+            let claim = RefreshClaim {
+                keys: self.refreshing.clone(),
+                key: key.to_string(),
+            };
+            let refresh = refresh();
+            tokio::spawn(async move {
+                let _claim = claim;
+                refresh.await;
+            });
+        }
+        Some((value, sources))
+    }
+
+    /// Find `key` in any layer, returning its body, where it came from and
+    /// whether it is fresh.
+    async fn lookup(&self, key: &str) -> Option<(Bytes, TokenSources, bool)> {
+        let tiers = self.tiers.load_full();
+        if !tiers.config.enabled {
             return None;
         }
 
         // Try all cache layers in order
-        for cache in [&self.l1_tools, &self.l2_resources, &self.l3_prompts] {
-            if let Some(value) = cache.get(key).await {
-                self.metrics.hits.fetch_add(1, Ordering::Relaxed);
-                crate::metrics::CACHE_HITS_TOTAL.inc();
-                crate::metrics::CACHE_SIZE_ENTRIES.set(self.total_size() as i64);
-                return Some(value);
+        for cache in [&tiers.l1_tools, &tiers.l2_resources, &tiers.l3_prompts] {
+            if let Some(entry) = cache.get(key).await {
+                crate::metrics::CACHE_SIZE_ENTRIES.set(tiers.total_size() as i64);
+                let fresh = entry.is_fresh();
+                return entry.body().map(|body| (body, entry.sources, fresh));
             }
         }
+        None
+    }
+
+    fn record_hit(&self) {
+        self.metrics.hits.fetch_add(1, Ordering::Relaxed);
+        crate::metrics::CACHE_HITS_TOTAL.inc();
+    }
 
+    fn record_miss(&self) {
         self.metrics.misses.fetch_add(1, Ordering::Relaxed);
         crate::metrics::CACHE_MISSES_TOTAL.inc();
-        None
     }
 
     /// Store response in cache (moka handles eviction automatically).
-    pub async fn set(&self, key: String, value: Vec<u8>, method: &str) {
-        if !self.config.enabled {
+    ///
+    /// The body is not attributed to a backend; savings from it are
+    /// reported under `proxy`. See [`LayeredCache::set_from`].
+    pub async fn set(&self, key: String, value: impl Into<Bytes>, method: &str) {
+        let value = value.into();
+        let sources = TokenSources::server("proxy", &value);
+        self.set_from(key, value, method, sources).await;
+    }
+
+    /// Store a response that came from `sources`, under which its hits and
+    /// deduplicated copies are reported as tokens saved.
+    pub async fn set_from(
+        &self,
+        key: String,
+        value: impl Into<Bytes>,
+        method: &str,
+        sources: TokenSources,
+    ) {
+        let tiers = self.tiers.load_full();
+        if !tiers.config.enabled {
             return;
         }
 
         // Select cache layer based on method
-        let cache = self.select_cache_layer(method);
-        cache.insert(key, value).await;
+        let ttl = tiers.config.ttl_for(method);
+        let value = value.into();
+        let (stored, raw_len) = match tiers.config.compression_for(method) {
+            Some(compression) => compression.compress(&value),
+            None => (value.clone(), None),
+        };
+        let (hash, value) = self.bodies.intern(&key, stored, value.len(), &sources);
+        let entry = CacheEntry {
+            value,
+            hash,
+            sources,
+            raw_len,
+            ttl: ttl + tiers.config.stale_grace,
+            fresh_until: Instant::now() + ttl,
+        };
+        tiers.layer_for(method).insert(key, entry).await;
 
         self.metrics.inserts.fetch_add(1, Ordering::Relaxed);
-        crate::metrics::CACHE_SIZE_ENTRIES.set(self.total_size() as i64);
+        crate::metrics::CACHE_SIZE_ENTRIES.set(tiers.total_size() as i64);
     }
 
     /// Run pending maintenance tasks to ensure immediate visibility (for testing).
@@ -124,29 +424,38 @@ impl LayeredCache {
     /// operations have completed.
     #[doc(hidden)]
     pub async fn sync(&self) {
-        self.l1_tools.run_pending_tasks().await;
-        self.l2_resources.run_pending_tasks().await;
-        self.l3_prompts.run_pending_tasks().await;
+        self.tiers.load_full().run_pending_tasks().await;
     }
 
     /// Invalidate specific key from all layers.
     pub async fn invalidate(&self, key: &str) {
-        self.l1_tools.invalidate(key).await;
-        self.l2_resources.invalidate(key).await;
-        self.l3_prompts.invalidate(key).await;
-        crate::metrics::CACHE_SIZE_ENTRIES.set(self.total_size() as i64);
+        let tiers = self.tiers.load_full();
+        tiers.l1_tools.invalidate(key).await;
+        tiers.l2_resources.invalidate(key).await;
+        tiers.l3_prompts.invalidate(key).await;
+        crate::metrics::CACHE_SIZE_ENTRIES.set(tiers.total_size() as i64);
+    }
+
+    /// Invalidate every entry whose key starts with `prefix`, e.g. all
+    /// cached reads of one resource (see [`LayeredCache::content_key`]).
+    pub fn invalidate_prefix(&self, prefix: &str) {
+        let tiers = self.tiers.load_full();
+        for cache in [&tiers.l1_tools, &tiers.l2_resources, &tiers.l3_prompts] {
+            let prefix = prefix.to_string();
+            // Only fails if the cache was built without closure support
+            let _ = cache.invalidate_entries_if(move |key, _| key.starts_with(&prefix));
+        }
     }
 
     /// Clear all cache entries across all layers.
     pub async fn clear(&self) {
-        self.l1_tools.invalidate_all();
-        self.l2_resources.invalidate_all();
-        self.l3_prompts.invalidate_all();
+        let tiers = self.tiers.load_full();
+        tiers.l1_tools.invalidate_all();
+        tiers.l2_resources.invalidate_all();
+        tiers.l3_prompts.invalidate_all();
 
         // Run pending tasks to ensure invalidation completes
-        self.l1_tools.run_pending_tasks().await;
-        self.l2_resources.run_pending_tasks().await;
-        self.l3_prompts.run_pending_tasks().await;
+        tiers.run_pending_tasks().await;
 
         self.metrics.clears.fetch_add(1, Ordering::Relaxed);
         crate::metrics::CACHE_SIZE_ENTRIES.set(0);
@@ -155,14 +464,18 @@ impl LayeredCache {
 
     /// Get cache statistics.
     pub async fn stats(&self) -> CacheStats {
+        let tiers = self.tiers.load();
         CacheStats {
-            l1_entries: self.l1_tools.entry_count(),
-            l2_entries: self.l2_resources.entry_count(),
-            l3_entries: self.l3_prompts.entry_count(),
+            l1_entries: tiers.l1_tools.entry_count(),
+            l2_entries: tiers.l2_resources.entry_count(),
+            l3_entries: tiers.l3_prompts.entry_count(),
             total_hits: self.metrics.hits.load(Ordering::Relaxed),
             total_misses: self.metrics.misses.load(Ordering::Relaxed),
             total_evictions: self.metrics.evictions.load(Ordering::Relaxed),
             hit_rate: self.metrics.hit_rate(),
+            unique_bodies: self.bodies.len() as u64,
+            raw_bytes: self.bodies.raw_bytes(),
+            stored_bytes: self.bodies.stored_bytes(),
         }
     }
 
@@ -178,28 +491,13 @@ impl LayeredCache {
         hasher.finalize().to_hex().to_string()
     }
 
-    /// Intelligent cache layer selection based on request type.
-    fn select_cache_layer(&self, method: &str) -> Arc<Cache<String, Vec<u8>>> {
-        match method {
-            // Tool operations are frequently accessed, short TTL
-            "tools/list" | "tools/call" => self.l1_tools.clone(),
-
-            // Resource operations are less frequent, medium TTL
-            "resources/list" | "resources/read" => self.l2_resources.clone(),
-
-            // Prompts are static, long TTL
-            "prompts/list" | "prompts/get" => self.l3_prompts.clone(),
-
-            // Default to L1 for unknown methods
-            _ => self.l1_tools.clone(),
-        }
-    }
-
-    /// Get total cache size across all layers.
-    fn total_size(&self) -> u64 {
-        self.l1_tools.entry_count()
-            + self.l2_resources.entry_count()
-            + self.l3_prompts.entry_count()
+    /// Cache key of a request addressing one item, such as a resources/read
+    /// of a URI or a prompts/get of a prompt name: `method:item#hash`, where
+    /// the hash covers all params. Every key for `item` starts with
+    /// `method:item#`, so [`LayeredCache::invalidate_prefix`] can drop them
+    /// together.
+    pub fn content_key(method: &str, item: &str, params: &serde_json::Value) -> String {
+        format!("{}:{}#{}", method, item, Self::cache_key(method, params))
     }
 
     /// Check if a request should be cached.
@@ -212,13 +510,13 @@ impl LayeredCache {
     }
 
     /// Serialize response for storage.
-    pub fn serialize_response(&self, response: &McpResponse) -> Result<Vec<u8>> {
-        serde_json::to_vec(response).map_err(|e| Error::Serialization(e.to_string()))
+    pub fn serialize_response(&self, response: &McpResponse) -> Result<Bytes> {
+        crate::json::to_bytes(response)
     }
 
     /// Deserialize response from storage.
     pub fn deserialize_response(&self, data: &[u8]) -> Result<McpResponse> {
-        serde_json::from_slice(data).map_err(|e| Error::Deserialization(e.to_string()))
+        crate::json::from_slice(data)
     }
 }
 
@@ -230,15 +528,77 @@ pub struct CacheConfig {
 
     /// L1 cache capacity (hot, tools)
     pub l1_capacity: u64,
+    /// Memory budget replacing `l1_capacity`: keys plus stored values
+    pub l1_max_bytes: Option<u64>,
     pub l1_ttl: Duration,
 
     /// L2 cache capacity (warm, resources)
     pub l2_capacity: u64,
+    pub l2_max_bytes: Option<u64>,
     pub l2_ttl: Duration,
 
     /// L3 cache capacity (cold, prompts)
     pub l3_capacity: u64,
+    pub l3_max_bytes: Option<u64>,
     pub l3_ttl: Duration,
+
+    /// TTLs replacing the tier's TTL for responses to specific methods
+    pub method_ttls: HashMap<String, Duration>,
+
+    /// How long expired entries may still be served while they are refreshed
+    pub stale_grace: Duration,
+
+    /// Compression of large values per tier; `None` stores them raw
+    pub l1_compression: Option<Compression>,
+    pub l2_compression: Option<Compression>,
+    pub l3_compression: Option<Compression>,
+}
+
+/// zstd compression of cached values.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Compression {
+    /// Values smaller than this are stored raw
+    pub min_bytes: usize,
+    /// zstd level
+    pub level: i32,
+}
+
+impl Compression {
+    /// The bytes to store for `value` and its raw size when compressed;
+    /// small values, and values compression does not shrink, stay raw.
+    fn compress(&self, value: &Bytes) -> (Bytes, Option<usize>) {
+        if value.len() >= self.min_bytes {
+            if let Ok(compressed) = zstd::bulk::compress(value, self.level) {
+                if compressed.len() < value.len() {
+                    return (compressed.into(), Some(value.len()));
+                }
+            }
+        }
+        (value.clone(), None)
+    }
+}
+
+impl CacheConfig {
+    /// How long a response to `method` is cached.
+    pub fn ttl_for(&self, method: &str) -> Duration {
+        if let Some(ttl) = self.method_ttls.get(method) {
+            return *ttl;
+        }
+        match method {
+            "resources/list" | "resources/read" => self.l2_ttl,
+            "prompts/list" | "prompts/get" => self.l3_ttl,
+            _ => self.l1_ttl,
+        }
+    }
+
+    /// How responses to `method` are compressed, if at all.
+    pub fn compression_for(&self, method: &str) -> Option<Compression> {
+        match method {
+            "resources/list" | "resources/read" => self.l2_compression,
+            "prompts/list" | "prompts/get" => self.l3_compression,
+            _ => self.l1_compression,
+        }
+    }
 }
 
 impl Default for CacheConfig {
@@ -246,17 +606,82 @@ impl Default for CacheConfig {
         Self {
             enabled: true,
             l1_capacity: 1000,
+            l1_max_bytes: None,
             l1_ttl: Duration::from_secs(300), // 5 minutes
 
             l2_capacity: 500,
+            l2_max_bytes: None,
             l2_ttl: Duration::from_secs(1800), // 30 minutes
 
             l3_capacity: 200,
+            l3_max_bytes: None,
             l3_ttl: Duration::from_secs(7200), // 2 hours
+
+            method_ttls: HashMap::new(),
+            stale_grace: Duration::ZERO,
+
+            l1_compression: None,
+            l2_compression: None,
+            l3_compression: None,
         }
     }
 }
 
+impl From<&crate::config::CacheConfig> for CacheConfig {
+    /// Derive tier sizes from the user-facing settings.
+    ///
+    /// `max_entries` and `ttl_seconds` size the hot tier; the warm and cold
+    /// tiers keep the default ratios (1/2 and 1/5 of the capacity, 6x and
+    /// 24x the TTL). `method_ttl_seconds` overrides the TTL per method, and
+    /// `max_bytes` bounds a tier by memory instead of entries.
+    fn from(config: &crate::config::CacheConfig) -> Self {
+        let capacity = config.max_entries as u64;
+        let ttl = Duration::from_secs(config.ttl_seconds);
+        let compression = |layer: &str| {
+            let settings = &config.compression;
+            (settings.enabled && settings.layers.iter().any(|l| l == layer)).then_some(
+                Compression {
+                    min_bytes: settings.min_bytes,
+                    level: settings.level,
+                },
+            )
+        };
+        Self {
+            enabled: config.enabled,
+            l1_capacity: capacity,
+            l1_max_bytes: config.max_bytes.get("tools").copied(),
+            l1_ttl: ttl,
+            l2_capacity: (capacity / 2).max(1),
+            l2_max_bytes: config.max_bytes.get("resources").copied(),
+            l2_ttl: ttl * 6,
+            l3_capacity: (capacity / 5).max(1),
+            l3_max_bytes: config.max_bytes.get("prompts").copied(),
+            l3_ttl: ttl * 24,
+            method_ttls: config
+                .method_ttl_seconds
+                .iter()
+                .map(|(method, seconds)| (method.clone(), Duration::from_secs(*seconds)))
+                .collect(),
+            stale_grace: Duration::from_secs(config.stale_grace_seconds),
+            l1_compression: compression("tools"),
+            l2_compression: compression("resources"),
+            l3_compression: compression("prompts"),
+        }
+    }
+}
+
+/// Marks a key as being refreshed until dropped.
+struct RefreshClaim {
+    keys: Arc<DashSet<String>>,
+    key: String,
+}
+
+impl Drop for RefreshClaim {
+    fn drop(&mut self) {
+        self.keys.remove(&self.key);
+    }
+}
+
 /// Cache metrics for monitoring effectiveness.
 #[derive(Default)]
 pub struct CacheMetrics {
@@ -283,7 +708,7 @@ impl CacheMetrics {
 }
 
 /// Cache statistics for monitoring.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheStats {
     pub l1_entries: u64,
     pub l2_entries: u64,
@@ -292,6 +717,15 @@ pub struct CacheStats {
     pub total_misses: u64,
     pub total_evictions: u64,
     pub hit_rate: f64,
+    /// Distinct response bodies held by all entries
+    #[serde(default)]
+    pub unique_bodies: u64,
+    /// Size of those bodies before compression
+    #[serde(default)]
+    pub raw_bytes: u64,
+    /// Memory the bodies take as stored, the cache's current byte usage
+    #[serde(default)]
+    pub stored_bytes: u64,
 }
 
 #[cfg(test)]
@@ -309,7 +743,7 @@ mod tests {
         cache.set(key.clone(), value.clone(), "tools/list").await;
 
         let retrieved = cache.get(&key).await;
-        assert_eq!(retrieved, Some(value));
+        assert_eq!(retrieved.as_deref(), Some(&value[..]));
 
         // Test invalidate
         cache.invalidate(&key).await;
@@ -325,17 +759,17 @@ mod tests {
         // Tools should go to L1
         cache.set("tools_key".to_string(), vec![1], "tools/list").await;
         cache.sync().await;
-        assert_eq!(cache.l1_tools.entry_count(), 1);
+        assert_eq!(cache.tiers.load().l1_tools.entry_count(), 1);
 
         // Resources should go to L2
         cache.set("resources_key".to_string(), vec![2], "resources/list").await;
         cache.sync().await;
-        assert_eq!(cache.l2_resources.entry_count(), 1);
+        assert_eq!(cache.tiers.load().l2_resources.entry_count(), 1);
 
         // Prompts should go to L3
         cache.set("prompts_key".to_string(), vec![3], "prompts/list").await;
         cache.sync().await;
-        assert_eq!(cache.l3_prompts.entry_count(), 1);
+        assert_eq!(cache.tiers.load().l3_prompts.entry_count(), 1);
     }
 
     #[tokio::test]
@@ -352,9 +786,9 @@ mod tests {
         cache.clear().await;
 
         // Verify all layers are empty
-        assert_eq!(cache.l1_tools.entry_count(), 0);
-        assert_eq!(cache.l2_resources.entry_count(), 0);
-        assert_eq!(cache.l3_prompts.entry_count(), 0);
+        assert_eq!(cache.tiers.load().l1_tools.entry_count(), 0);
+        assert_eq!(cache.tiers.load().l2_resources.entry_count(), 0);
+        assert_eq!(cache.tiers.load().l3_prompts.entry_count(), 0);
     }
 
     #[tokio::test]
@@ -371,4 +805,113 @@ mod tests {
         assert_eq!(stats.total_misses, 1);
         assert!(stats.hit_rate > 0.0);
     }
+
+    #[tokio::test]
+    async fn test_identical_bodies_shared_across_keys() {
+        let cache = LayeredCache::new(CacheConfig::default());
+        let docs = b"{\"contents\":[{\"text\":\"shared docs\"}]}".to_vec();
+
+        cache.set("server-a".to_string(), docs.clone(), "resources/read").await;
+        cache.set("server-b".to_string(), docs.clone(), "resources/read").await;
+        cache.set("other".to_string(), vec![1], "tools/list").await;
+        assert_eq!(cache.stats().await.unique_bodies, 2);
+
+        let (a, b) = (cache.get("server-a").await, cache.get("server-b").await);
+        assert_eq!(a.as_deref(), Some(&docs[..]));
+        assert_eq!(a.unwrap().as_ptr(), b.unwrap().as_ptr());
+
+        // The body is freed with the last entry using it
+        cache.invalidate("server-a").await;
+        cache.sync().await;
+        assert_eq!(cache.stats().await.unique_bodies, 2);
+        cache.invalidate("server-b").await;
+        cache.sync().await;
+        assert_eq!(cache.stats().await.unique_bodies, 1);
+    }
+
+    #[tokio::test]
+    async fn test_large_values_compressed_per_tier() {
+        let compression = Compression {
+            min_bytes: 1024,
+            level: 3,
+        };
+        let cache = LayeredCache::new(CacheConfig {
+            l1_compression: Some(compression),
+            ..Default::default()
+        });
+        let tools = serde_json::to_vec(&serde_json::json!({
+            "tools": vec![serde_json::json!({"name": "read_file", "description": "Read a file"}); 100]
+        }))
+        .unwrap();
+
+        cache.set("tools".to_string(), tools.clone(), "tools/list").await;
+        cache.set("small".to_string(), vec![1; 100], "tools/list").await;
+        cache.set("resources".to_string(), tools.clone(), "resources/list").await;
+
+        // Lookups see the original bytes
+        assert_eq!(cache.get("tools").await.as_deref(), Some(&tools[..]));
+        assert_eq!(cache.get("small").await.as_deref(), Some(&[1; 100][..]));
+
+        // Only the large tools/list body is stored compressed
+        let stats = cache.stats().await;
+        assert_eq!(stats.raw_bytes, 2 * tools.len() as u64 + 100);
+        assert!(stats.stored_bytes < tools.len() as u64 + 200);
+    }
+
+    #[tokio::test]
+    async fn test_byte_budget_bounds_tier_memory() {
+        let cache = LayeredCache::new(CacheConfig {
+            l1_max_bytes: Some(64 * 1024),
+            ..Default::default()
+        });
+
+        // Far fewer than l1_capacity entries, but over the byte budget
+        for i in 0..100 {
+            cache.set(format!("tools-{}", i), vec![i as u8; 4096], "tools/list").await;
+        }
+        cache.sync().await;
+
+        let stats = cache.stats().await;
+        assert!(stats.l1_entries < 16, "{} entries", stats.l1_entries);
+        assert!(
+            stats.stored_bytes <= 64 * 1024,
+            "{} bytes",
+            stats.stored_bytes
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cache_reconfigure() {
+        let cache = LayeredCache::new(CacheConfig::default());
+        cache.set("key1".to_string(), vec![1], "tools/list").await;
+
+        let settings = crate::config::CacheConfig {
+            enabled: false,
+            max_entries: 100,
+            ttl_seconds: 60,
+            method_ttl_seconds: [("prompts/get".to_string(), 5)].into(),
+            stale_grace_seconds: 0,
+            max_bytes: Default::default(),
+            compression: Default::default(),
+        };
+        cache.reconfigure(CacheConfig::from(&settings)).await;
+
+        assert!(!cache.config().enabled);
+        assert_eq!(cache.config().l1_capacity, 100);
+        assert_eq!(cache.config().l2_ttl, Duration::from_secs(360));
+        assert_eq!(
+            cache.config().ttl_for("resources/read"),
+            Duration::from_secs(360)
+        );
+        assert_eq!(
+            cache.config().ttl_for("prompts/get"),
+            Duration::from_secs(5)
+        );
+
+        // Old entries are gone and the disabled cache ignores writes
+        cache.set("key2".to_string(), vec![2], "tools/list").await;
+        cache.sync().await;
+        assert_eq!(cache.get("key1").await, None);
+        assert_eq!(cache.stats().await.l1_entries, 0);
+    }
 }
@@ -0,0 +1,393 @@
+//! Timestamped backups of the configuration file, written before every edit
+//! made through [`crate::config::edit`], and rollback to them
+//! (`only1mcp config rollback`).
+//!
+//! Backups live in a `backups/` directory next to the configuration file and
+//! are named `<stem>-<YYYYMMDD-HHMMSS-mmm>.<ext>`, so several configurations
+//! can share a directory. Only the newest `backups.keep` are retained.
+//!
+//! The versions a running proxy applied, including hand edits picked up by
+//! hot reload, are kept the same way in `history/`
+//! ([`record_applied`]), so a bad but valid reload can be undone with
+//! `only1mcp config rollback --applied` ([`rollback_applied`]).
+
+use std::path::{Path, PathBuf};
+
+use chrono::{NaiveDateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+use crate::config::Config;
+use crate::error::{Error, Result};
+
+/// Directory, next to the configuration file, holding its backups.
+pub const BACKUP_DIR: &str = "backups";
+
+/// Directory, next to the configuration file, holding the versions of it
+/// that were applied.
+pub const HISTORY_DIR: &str = "history";
+
+/// Timestamp format used in backup file names.
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S-%3f";
+
+/// Backup retention settings (`backups` section).
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct BackupConfig {
+    /// Write a backup before each edit
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Number of most recent backups to keep
+    #[serde(default = "default_keep")]
+    pub keep: usize,
+    /// Number of most recently applied versions to keep (0 disables the
+    /// history)
+    #[serde(default = "default_keep_applied")]
+    pub keep_applied: usize,
+}
+
+fn default_true() -> bool {
+    true
+}
+fn default_keep() -> usize {
+    20
+}
+fn default_keep_applied() -> usize {
+    10
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            keep: default_keep(),
+            keep_applied: default_keep_applied(),
+        }
+    }
+}
+
+/// A backup of a configuration file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+    pub path: PathBuf,
+    /// Timestamp from the file name, as accepted by `rollback --to`
+    pub timestamp: String,
+}
+
+/// Backup directory for the configuration file at `config_path`.
+pub fn backup_dir(config_path: &Path) -> PathBuf {
+    config_path.parent().unwrap_or_else(|| Path::new(".")).join(BACKUP_DIR)
+}
+
+/// Directory of the applied versions of the configuration file at
+/// `config_path`.
+pub fn history_dir(config_path: &Path) -> PathBuf {
+    config_path.parent().unwrap_or_else(|| Path::new(".")).join(HISTORY_DIR)
+}
+
+/// File name stem and extension backups of `config_path` are written with.
+fn name_parts(config_path: &Path) -> (String, String) {
+    let stem = config_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "config".into());
+    let ext = config_path
+        .extension()
+        .map(|e| e.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "yaml".into());
+    (stem, ext)
+}
+
+/// Copy the current configuration file into the backup directory, then
+/// prune old backups. Does nothing if backups are disabled or the file does
+/// not exist yet.
+pub fn create_backup(config_path: &Path, settings: &BackupConfig) -> Result<Option<Backup>> {
+    if !settings.enabled || !config_path.exists() {
+        return Ok(None);
+    }
+
+    let backup = copy_into(&backup_dir(config_path), config_path)?;
+    prune(&backup_dir(config_path), config_path, settings.keep)?;
+    Ok(Some(backup))
+}
+
+/// Record the configuration file as applied, unless it is the same as the
+/// newest version recorded, then prune old versions. Does nothing if the
+/// history is disabled or the file does not exist.
+pub fn record_applied(config_path: &Path, settings: &BackupConfig) -> Result<Option<Backup>> {
+    if settings.keep_applied == 0 || !config_path.exists() {
+        return Ok(None);
+    }
+    if list_applied(config_path)?
+        .last()
+        .is_some_and(|newest| same_content(&newest.path, config_path))
+    {
+        return Ok(None);
+    }
+
+    let version = copy_into(&history_dir(config_path), config_path)?;
+    prune(
+        &history_dir(config_path),
+        config_path,
+        settings.keep_applied,
+    )?;
+    Ok(Some(version))
+}
+
+fn same_content(a: &Path, b: &Path) -> bool {
+    matches!((std::fs::read(a), std::fs::read(b)), (Ok(a), Ok(b)) if a == b)
+}
+
+/// Copy the configuration file into `dir` under a new timestamp.
+fn copy_into(dir: &Path, config_path: &Path) -> Result<Backup> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| Error::Config(format!("Failed to create backup directory: {}", e)))?;
+
+    let (stem, ext) = name_parts(config_path);
+    let (timestamp, path) = loop {
+        let timestamp = Utc::now().format(TIMESTAMP_FORMAT).to_string();
+        let path = dir.join(format!("{}-{}.{}", stem, timestamp, ext));
+        if !path.exists() {
+            break (timestamp, path);
+        }
+        // Another edit in the same millisecond; timestamps must stay unique
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    };
+    std::fs::copy(config_path, &path)
+        .map_err(|e| Error::Config(format!("Failed to back up config file: {}", e)))?;
+    debug!("Copied {} to {}", config_path.display(), path.display());
+    Ok(Backup { path, timestamp })
+}
+
+/// Backups of the configuration file at `config_path`, oldest first.
+pub fn list_backups(config_path: &Path) -> Result<Vec<Backup>> {
+    list_in(&backup_dir(config_path), config_path)
+}
+
+/// Applied versions of the configuration file at `config_path`, oldest
+/// first.
+pub fn list_applied(config_path: &Path) -> Result<Vec<Backup>> {
+    list_in(&history_dir(config_path), config_path)
+}
+
+/// Copies of the configuration file at `config_path` in `dir`, oldest first.
+fn list_in(dir: &Path, config_path: &Path) -> Result<Vec<Backup>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let (stem, ext) = name_parts(config_path);
+    let (prefix, suffix) = (format!("{}-", stem), format!(".{}", ext));
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| Error::Config(format!("Failed to read backup directory: {}", e)))?;
+
+    let mut backups: Vec<Backup> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let timestamp = name.strip_prefix(&prefix)?.strip_suffix(&suffix)?;
+            NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT).ok()?;
+            Some(Backup {
+                path: entry.path(),
+                timestamp: timestamp.to_string(),
+            })
+        })
+        .collect();
+    // The timestamp format sorts chronologically as text
+    backups.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    Ok(backups)
+}
+
+/// Delete all but the newest `keep` copies in `dir`.
+fn prune(dir: &Path, config_path: &Path, keep: usize) -> Result<()> {
+    let backups = list_in(dir, config_path)?;
+    let excess = backups.len().saturating_sub(keep);
+    for backup in &backups[..excess] {
+        std::fs::remove_file(&backup.path)
+            .map_err(|e| Error::Config(format!("Failed to remove old backup: {}", e)))?;
+    }
+    Ok(())
+}
+
+/// Restore the configuration file from a backup: the one whose timestamp
+/// starts with `to`, or the newest if `to` is `None`.
+///
+/// The backup must parse and validate. The current file is backed up first,
+/// so a rollback can itself be undone. A running instance picks up the
+/// restored file through hot reload.
+pub fn rollback(config_path: &Path, to: Option<&str>) -> Result<Backup> {
+    let backups = list_backups(config_path)?;
+    let backup = match to {
+        None => backups.last(),
+        Some(to) => find(&backups, to)?,
+    }
+    .cloned()
+    .ok_or_else(|| match to {
+        None => Error::Config(format!(
+            "No backups of {} found in {}",
+            config_path.display(),
+            backup_dir(config_path).display()
+        )),
+        Some(to) => Error::Config(format!("No backup with timestamp '{}'", to)),
+    })?;
+    restore(config_path, backup)
+}
+
+/// Restore the configuration file to an applied version: the one whose
+/// timestamp starts with `to`, or by default the last good one. That is the
+/// version before the current one, or the newest version if the file has
+/// changed since it was applied, e.g. because it failed to load.
+pub fn rollback_applied(config_path: &Path, to: Option<&str>) -> Result<Backup> {
+    let applied = list_applied(config_path)?;
+    let version = match to {
+        None => match applied.last() {
+            Some(newest) if same_content(&newest.path, config_path) => applied.iter().nth_back(1),
+            newest => newest,
+        },
+        Some(to) => find(&applied, to)?,
+    }
+    .cloned()
+    .ok_or_else(|| match to {
+        None => Error::Config(format!(
+            "No earlier applied version of {} found in {}",
+            config_path.display(),
+            history_dir(config_path).display()
+        )),
+        Some(to) => Error::Config(format!("No applied version with timestamp '{}'", to)),
+    })?;
+    restore(config_path, version)
+}
+
+/// The copy whose timestamp starts with `to`, if exactly one does.
+fn find<'a>(backups: &'a [Backup], to: &str) -> Result<Option<&'a Backup>> {
+    let mut matching = backups.iter().filter(|b| b.timestamp.starts_with(to));
+    match (matching.next(), matching.next()) {
+        (Some(_), Some(_)) => Err(Error::Config(format!(
+            "Timestamp '{}' matches several backups; be more specific",
+            to
+        ))),
+        (backup, _) => Ok(backup),
+    }
+}
+
+/// Replace the configuration file with a validated copy, backing up the
+/// current file first.
+fn restore(config_path: &Path, backup: Backup) -> Result<Backup> {
+    let restored = Config::from_file(&backup.path)?;
+    restored.validate()?;
+
+    // Keep the backup being restored even if retention would prune it
+    let content = std::fs::read_to_string(&backup.path)
+        .map_err(|e| Error::Config(format!("Failed to read backup: {}", e)))?;
+    let settings = Config::from_file(config_path).map(|c| c.backups).unwrap_or_default();
+    create_backup(config_path, &settings)?;
+    crate::config::edit::write_atomically(config_path, &content)?;
+
+    info!(
+        "Restored {} from backup {}",
+        config_path.display(),
+        backup.timestamp
+    );
+    Ok(backup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_config(path: &Path, port: u16) {
+        std::fs::write(path, format!("server:\n  port: {}\nservers: []\n", port)).unwrap();
+    }
+
+    #[test]
+    fn test_backups_are_pruned() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("only1mcp.yaml");
+        write_config(&path, 9000);
+
+        let settings = BackupConfig {
+            enabled: true,
+            keep: 2,
+            ..Default::default()
+        };
+        for _ in 0..4 {
+            create_backup(&path, &settings).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        let backups = list_backups(&path).unwrap();
+        assert_eq!(backups.len(), 2);
+        assert!(backups[0].timestamp < backups[1].timestamp);
+        assert_eq!(
+            backups[0].path.parent().unwrap(),
+            dir.path().join(BACKUP_DIR)
+        );
+
+        let disabled = BackupConfig {
+            enabled: false,
+            keep: 2,
+            ..Default::default()
+        };
+        assert!(create_backup(&path, &disabled).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_rollback() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("only1mcp.yaml");
+        write_config(&path, 9000);
+        let first = create_backup(&path, &BackupConfig::default()).unwrap().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        write_config(&path, 9001);
+        create_backup(&path, &BackupConfig::default()).unwrap();
+        write_config(&path, 9002);
+
+        // Latest backup by default
+        rollback(&path, None).unwrap();
+        assert_eq!(Config::from_file(&path).unwrap().server.port, 9001);
+
+        // A specific backup by timestamp; the replaced file was backed up too
+        rollback(&path, Some(&first.timestamp)).unwrap();
+        assert_eq!(Config::from_file(&path).unwrap().server.port, 9000);
+        assert_eq!(list_backups(&path).unwrap().len(), 4);
+
+        assert!(rollback(&path, Some("19990101")).is_err());
+    }
+
+    #[test]
+    fn test_rollback_to_applied_version() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("only1mcp.yaml");
+        let settings = BackupConfig::default();
+        write_config(&path, 9000);
+        record_applied(&path, &settings).unwrap().unwrap();
+        assert!(record_applied(&path, &settings).unwrap().is_none());
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        write_config(&path, 9001);
+        record_applied(&path, &settings).unwrap().unwrap();
+
+        // The version before the running one
+        let restored = rollback_applied(&path, None).unwrap();
+        assert_eq!(Config::from_file(&path).unwrap().server.port, 9000);
+        assert_eq!(restored, list_applied(&path).unwrap()[0]);
+
+        // The last applied one when the file changed since, e.g. a typo
+        // that failed to load
+        std::fs::write(&path, "server: [\n").unwrap();
+        rollback_applied(&path, None).unwrap();
+        assert_eq!(Config::from_file(&path).unwrap().server.port, 9001);
+    }
+
+    #[test]
+    fn test_rollback_rejects_invalid_backup() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("only1mcp.yaml");
+        std::fs::write(&path, "server:\n  port: 0\nservers: []\n").unwrap();
+        create_backup(&path, &BackupConfig::default()).unwrap();
+        write_config(&path, 9000);
+
+        assert!(rollback(&path, None).is_err());
+        assert_eq!(Config::from_file(&path).unwrap().server.port, 9000);
+    }
+}
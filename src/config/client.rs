@@ -0,0 +1,235 @@
+//! Client configuration snippets (`only1mcp config export --for <client>`).
+//!
+//! Each MCP client has its own way of naming a server: Claude Desktop only
+//! launches STDIO commands, so the proxy is reached through the `mcp-remote`
+//! bridge, while Cursor and most other clients take the HTTP endpoint
+//! directly. Snippets point at the first listener serving the MCP routes.
+//! API keys are never copied from the configuration; listeners that require
+//! one get a placeholder to fill in.
+
+use std::net::SocketAddr;
+use std::str::FromStr;
+
+use serde_json::{json, Value};
+
+use crate::config::{Config, ListenAddress, RouteGroup};
+use crate::error::{Error, Result};
+
+/// Placeholder for the API key in snippets for authenticated listeners.
+pub const API_KEY_PLACEHOLDER: &str = "<YOUR_API_KEY>";
+
+/// Clients a snippet can be generated for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientKind {
+    ClaudeDesktop,
+    Cursor,
+    Generic,
+}
+
+impl FromStr for ClientKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "claude-desktop" => Ok(Self::ClaudeDesktop),
+            "cursor" => Ok(Self::Cursor),
+            "generic" => Ok(Self::Generic),
+            other => Err(Error::Config(format!(
+                "Unknown client '{}' (expected claude-desktop, cursor or generic)",
+                other
+            ))),
+        }
+    }
+}
+
+impl ClientKind {
+    /// Where the client reads its MCP server list from, for the hint printed
+    /// alongside the snippet.
+    pub fn config_location(&self) -> &'static str {
+        match self {
+            Self::ClaudeDesktop => {
+                "claude_desktop_config.json (Settings > Developer > Edit Config in Claude Desktop)"
+            },
+            Self::Cursor => "~/.cursor/mcp.json, or .cursor/mcp.json in a project",
+            Self::Generic => "your client's MCP server settings",
+        }
+    }
+}
+
+/// The MCP endpoint of the proxy as clients should reach it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct McpEndpoint {
+    /// Streamable HTTP endpoint, e.g. `http://127.0.0.1:8080/mcp`
+    pub url: String,
+    /// WebSocket endpoint on the same listener
+    pub websocket_url: String,
+    /// Whether the listener requires an API key or token
+    pub requires_auth: bool,
+}
+
+impl McpEndpoint {
+    /// Endpoint of the first TCP listener serving the MCP routes.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        config
+            .server
+            .effective_listeners()
+            .iter()
+            .filter(|listener| listener.serves(RouteGroup::Mcp))
+            .find_map(|listener| match listener.address() {
+                Ok(ListenAddress::Tcp(addr)) => {
+                    let host = client_host(addr);
+                    let (http, ws) =
+                        if listener.tls.enabled { ("https", "wss") } else { ("http", "ws") };
+                    Some(Self {
+                        url: format!("{}://{}/mcp", http, host),
+                        websocket_url: format!("{}://{}/ws", ws, host),
+                        requires_auth: listener.auth.is_some(),
+                    })
+                },
+                _ => None,
+            })
+            .ok_or_else(|| {
+                Error::Config(
+                    "No TCP listener serves the MCP routes; clients cannot connect".into(),
+                )
+            })
+    }
+}
+
+/// `host:port` for clients: a wildcard bind address is reached on loopback.
+fn client_host(mut addr: SocketAddr) -> String {
+    if addr.ip().is_unspecified() {
+        addr.set_ip(if addr.is_ipv4() {
+            std::net::Ipv4Addr::LOCALHOST.into()
+        } else {
+            std::net::Ipv6Addr::LOCALHOST.into()
+        });
+    }
+    addr.to_string()
+}
+
+/// The snippet `client` needs to connect to `endpoint` under `name`.
+pub fn client_snippet(client: ClientKind, endpoint: &McpEndpoint, name: &str) -> Value {
+    let authorization = format!("Bearer {}", API_KEY_PLACEHOLDER);
+    match client {
+        ClientKind::ClaudeDesktop => {
+            let mut server = json!({
+                "command": "npx",
+                "args": ["-y", "mcp-remote", endpoint.url],
+            });
+            if endpoint.requires_auth {
+                // mcp-remote expands ${VAR} in header values
+                server["args"]
+                    .as_array_mut()
+                    .expect("args is an array")
+                    .extend([json!("--header"), json!("Authorization:${ONLY1MCP_AUTH}")]);
+                server["env"] = json!({ "ONLY1MCP_AUTH": authorization });
+            }
+            json!({ "mcpServers": { name: server } })
+        },
+        ClientKind::Cursor => {
+            let mut server = json!({ "url": endpoint.url });
+            if endpoint.requires_auth {
+                server["headers"] = json!({ "Authorization": authorization });
+            }
+            json!({ "mcpServers": { name: server } })
+        },
+        ClientKind::Generic => {
+            let mut server = json!({
+                "name": name,
+                "transport": "streamable-http",
+                "url": endpoint.url,
+                "websocket_url": endpoint.websocket_url,
+            });
+            if endpoint.requires_auth {
+                server["headers"] = json!({ "Authorization": authorization });
+            }
+            server
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ApiKeyConfig, ListenerAuthConfig, ListenerConfig};
+
+    fn config(bind: &str, auth: bool) -> Config {
+        let mut config = Config::default();
+        config.server.listeners = vec![
+            ListenerConfig {
+                name: "admin".to_string(),
+                bind: "127.0.0.1:9000".to_string(),
+                routes: vec![RouteGroup::Admin],
+                tls: Default::default(),
+                auth: None,
+                ipv6_only: false,
+            },
+            ListenerConfig {
+                name: "public".to_string(),
+                bind: bind.to_string(),
+                routes: vec![RouteGroup::Mcp],
+                tls: Default::default(),
+                auth: auth.then(|| ListenerAuthConfig {
+                    api_keys: vec![ApiKeyConfig::Key("secret-key".to_string())],
+                    jwt: None,
+                }),
+                ipv6_only: false,
+            },
+        ];
+        config
+    }
+
+    #[test]
+    fn test_endpoint_from_listeners() {
+        let endpoint = McpEndpoint::from_config(&config("0.0.0.0:8443", false)).unwrap();
+        assert_eq!(endpoint.url, "http://127.0.0.1:8443/mcp");
+        assert_eq!(endpoint.websocket_url, "ws://127.0.0.1:8443/ws");
+        assert!(!endpoint.requires_auth);
+
+        let endpoint = McpEndpoint::from_config(&config("[::]:8080", true)).unwrap();
+        assert_eq!(endpoint.url, "http://[::1]:8080/mcp");
+        assert!(endpoint.requires_auth);
+
+        assert!(McpEndpoint::from_config(&config("unix:/tmp/only1mcp.sock", false)).is_err());
+    }
+
+    #[test]
+    fn test_snippets() {
+        let endpoint = McpEndpoint::from_config(&config("127.0.0.1:8080", true)).unwrap();
+
+        let claude = client_snippet(ClientKind::ClaudeDesktop, &endpoint, "only1mcp");
+        assert_eq!(
+            claude["mcpServers"]["only1mcp"]["args"],
+            json!([
+                "-y",
+                "mcp-remote",
+                "http://127.0.0.1:8080/mcp",
+                "--header",
+                "Authorization:${ONLY1MCP_AUTH}"
+            ])
+        );
+
+        let cursor = client_snippet(ClientKind::Cursor, &endpoint, "proxy");
+        assert_eq!(
+            cursor["mcpServers"]["proxy"]["url"],
+            "http://127.0.0.1:8080/mcp"
+        );
+        assert_eq!(
+            cursor["mcpServers"]["proxy"]["headers"]["Authorization"],
+            "Bearer <YOUR_API_KEY>"
+        );
+
+        // Configured keys never leave the machine
+        for client in [
+            ClientKind::ClaudeDesktop,
+            ClientKind::Cursor,
+            ClientKind::Generic,
+        ] {
+            let snippet = client_snippet(client, &endpoint, "only1mcp");
+            assert!(!snippet.to_string().contains("secret-key"));
+        }
+
+        assert!("zed".parse::<ClientKind>().is_err());
+    }
+}
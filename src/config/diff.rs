@@ -0,0 +1,301 @@
+//! Structural diff between two configurations.
+//!
+//! Computed on every hot reload so the log records exactly what changed and
+//! the proxy only does the work a change requires (e.g. the response cache
+//! is rebuilt only when its settings moved).
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::config::{Config, McpServerConfig};
+
+/// Summary of the differences between an old and a new configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigDiff {
+    /// Backend IDs present only in the new configuration
+    pub servers_added: Vec<String>,
+    /// Backend IDs present only in the old configuration
+    pub servers_removed: Vec<String>,
+    /// Backend IDs present in both whose settings differ
+    pub servers_changed: Vec<String>,
+    /// Backend IDs whose tags or notes alone differ (no reconnect needed)
+    pub servers_annotated: Vec<String>,
+    /// `context_optimization.cache` differs
+    pub cache_changed: bool,
+    /// `context_optimization.aggregation` differs
+    pub aggregation_changed: bool,
+    /// `proxy` (load balancing, pooling, routing) differs
+    pub proxy_changed: bool,
+    /// `observability.alerts` differs
+    pub alerts_changed: bool,
+    /// `observability.request_history` differs
+    pub history_changed: bool,
+    /// `observability.recording` differs
+    pub recording_changed: bool,
+    /// Changed sections that only take effect after a restart
+    pub restart_required: Vec<&'static str>,
+}
+
+impl ConfigDiff {
+    /// Compare `old` against `new`.
+    pub fn between(old: &Config, new: &Config) -> Self {
+        let mut diff = Self::default();
+
+        for server in &new.servers {
+            match old.servers.iter().find(|s| s.id == server.id) {
+                None => diff.servers_added.push(server.id.clone()),
+                Some(previous) if !same(&without_metadata(previous), &without_metadata(server)) => {
+                    diff.servers_changed.push(server.id.clone())
+                },
+                Some(previous) if !same(previous, server) => {
+                    diff.servers_annotated.push(server.id.clone())
+                },
+                Some(_) => {},
+            }
+        }
+        diff.servers_removed = old
+            .servers
+            .iter()
+            .filter(|s| !new.servers.iter().any(|n| n.id == s.id))
+            .map(|s| s.id.clone())
+            .collect();
+
+        diff.cache_changed = !same(
+            &old.context_optimization.cache,
+            &new.context_optimization.cache,
+        );
+        diff.aggregation_changed = !same(
+            &old.context_optimization.aggregation,
+            &new.context_optimization.aggregation,
+        );
+        diff.proxy_changed = !same(&old.proxy, &new.proxy);
+        diff.alerts_changed = !same(&old.observability.alerts, &new.observability.alerts);
+        diff.history_changed = !same(
+            &old.observability.request_history,
+            &new.observability.request_history,
+        );
+        diff.recording_changed = !same(&old.observability.recording, &new.observability.recording);
+
+        let restart_sections: [(&'static str, bool); 9] = [
+            ("server", !same(&old.server, &new.server)),
+            (
+                "context_optimization.batching",
+                !same(
+                    &old.context_optimization.batching,
+                    &new.context_optimization.batching,
+                ),
+            ),
+            (
+                "context_optimization.catalog",
+                !same(
+                    &old.context_optimization.catalog,
+                    &new.context_optimization.catalog,
+                ),
+            ),
+            ("auth", !same(&old.auth, &new.auth)),
+            (
+                "observability.logging",
+                !same(&old.observability.logging, &new.observability.logging),
+            ),
+            ("tui", !same(&old.tui, &new.tui)),
+            ("cluster", !same(&old.cluster, &new.cluster)),
+            ("discovery", !same(&old.discovery, &new.discovery)),
+            ("gitops", !same(&old.gitops, &new.gitops)),
+        ];
+        diff.restart_required = restart_sections
+            .into_iter()
+            .filter(|(_, changed)| *changed)
+            .map(|(name, _)| name)
+            .collect();
+
+        diff
+    }
+
+    /// Whether the backend set changed in any way.
+    pub fn servers_changed(&self) -> bool {
+        !self.servers_added.is_empty()
+            || !self.servers_removed.is_empty()
+            || !self.servers_changed.is_empty()
+    }
+
+    /// Sections outside `servers` that changed and apply without a restart.
+    pub fn settings_changed(&self) -> Vec<&'static str> {
+        [
+            ("context_optimization.cache", self.cache_changed),
+            ("context_optimization.aggregation", self.aggregation_changed),
+            ("proxy", self.proxy_changed),
+            ("observability.alerts", self.alerts_changed),
+            ("observability.request_history", self.history_changed),
+            ("observability.recording", self.recording_changed),
+        ]
+        .into_iter()
+        .filter(|(_, changed)| *changed)
+        .map(|(name, _)| name)
+        .collect()
+    }
+
+    /// Whether the two configurations are equivalent.
+    pub fn is_empty(&self) -> bool {
+        !self.servers_changed()
+            && self.servers_annotated.is_empty()
+            && !self.cache_changed
+            && !self.aggregation_changed
+            && !self.proxy_changed
+            && !self.alerts_changed
+            && !self.history_changed
+            && !self.recording_changed
+            && self.restart_required.is_empty()
+    }
+}
+
+impl fmt::Display for ConfigDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "no changes");
+        }
+
+        let mut parts = Vec::new();
+        for (label, ids) in [
+            ("added", &self.servers_added),
+            ("removed", &self.servers_removed),
+            ("changed", &self.servers_changed),
+            ("annotated", &self.servers_annotated),
+        ] {
+            if !ids.is_empty() {
+                parts.push(format!("servers {}: {}", label, ids.join(", ")));
+            }
+        }
+        if self.cache_changed {
+            parts.push("cache settings changed".to_string());
+        }
+        if self.aggregation_changed {
+            parts.push("aggregation settings changed".to_string());
+        }
+        if self.proxy_changed {
+            parts.push("proxy settings changed".to_string());
+        }
+        if self.alerts_changed {
+            parts.push("alert rules changed".to_string());
+        }
+        if self.history_changed {
+            parts.push("request history settings changed".to_string());
+        }
+        if self.recording_changed {
+            parts.push("recording settings changed".to_string());
+        }
+        if !self.restart_required.is_empty() {
+            parts.push(format!(
+                "restart required for: {}",
+                self.restart_required.join(", ")
+            ));
+        }
+
+        write!(f, "{}", parts.join("; "))
+    }
+}
+
+/// A backend's settings with operator tags and notes stripped.
+fn without_metadata(server: &McpServerConfig) -> McpServerConfig {
+    let mut server = server.clone();
+    server.tags.clear();
+    server.notes = None;
+    server.tool_metadata.clear();
+    server
+}
+
+/// Compare two config sections by their serialized form.
+fn same<T: Serialize>(a: &T, b: &T) -> bool {
+    serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(yaml: &str) -> Config {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    const BASE: &str = r#"
+servers:
+  - id: a
+    name: A
+    transport: {type: http, url: "http://localhost:9001"}
+  - id: b
+    name: B
+    transport: {type: http, url: "http://localhost:9002"}
+"#;
+
+    #[test]
+    fn test_identical_configs() {
+        let diff = ConfigDiff::between(&config(BASE), &config(BASE));
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "no changes");
+    }
+
+    #[test]
+    fn test_server_and_cache_changes() {
+        let new = config(
+            r#"
+servers:
+  - id: a
+    name: A
+    transport: {type: http, url: "http://localhost:9101"}
+  - id: c
+    name: C
+    transport: {type: http, url: "http://localhost:9003"}
+context_optimization:
+  cache:
+    ttl_seconds: 60
+"#,
+        );
+        let diff = ConfigDiff::between(&config(BASE), &new);
+
+        assert_eq!(diff.servers_added, vec!["c"]);
+        assert_eq!(diff.servers_removed, vec!["b"]);
+        assert_eq!(diff.servers_changed, vec!["a"]);
+        assert!(diff.cache_changed);
+        assert!(!diff.proxy_changed);
+        assert_eq!(diff.settings_changed(), vec!["context_optimization.cache"]);
+        assert!(diff.restart_required.is_empty());
+        assert_eq!(
+            diff.to_string(),
+            "servers added: c; servers removed: b; servers changed: a; cache settings changed"
+        );
+    }
+
+    #[test]
+    fn test_listener_change_requires_restart() {
+        let mut new = config(BASE);
+        new.server.port += 1;
+
+        let diff = ConfigDiff::between(&config(BASE), &new);
+        assert!(!diff.servers_changed());
+        assert_eq!(diff.restart_required, vec!["server"]);
+    }
+
+    #[test]
+    fn test_aggregation_change_applies_live() {
+        let mut new = config(BASE);
+        new.context_optimization.aggregation.timeout_ms = 2000;
+
+        let diff = ConfigDiff::between(&config(BASE), &new);
+        assert!(diff.aggregation_changed);
+        assert!(diff.restart_required.is_empty());
+        assert_eq!(diff.to_string(), "aggregation settings changed");
+    }
+
+    #[test]
+    fn test_metadata_change_keeps_backend() {
+        let mut new = config(BASE);
+        new.servers[0].tags = vec!["prod".into()];
+        new.servers[1].notes = Some("Owned by the data team".into());
+
+        let diff = ConfigDiff::between(&config(BASE), &new);
+        assert!(!diff.servers_changed());
+        assert_eq!(diff.servers_annotated, vec!["a", "b"]);
+        assert!(!diff.is_empty());
+        assert_eq!(diff.to_string(), "servers annotated: a, b");
+    }
+}
@@ -0,0 +1,708 @@
+//! In-place edits of configuration files, used by `only1mcp add` and the
+//! admin API's metadata and enable/disable endpoints.
+//!
+//! The file is edited as a generic YAML/TOML document rather than by
+//! re-serializing [`Config`], so settings the user never wrote (defaults)
+//! stay out of the file. Comments are not preserved. The result is parsed
+//! and validated as a [`Config`] before it replaces the original, and the
+//! write goes through a temporary file so a running proxy's hot reload never
+//! sees a half-written config.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::config::{backup, Config, Metadata, TransportConfig};
+use crate::error::{Error, Result};
+
+/// Backend definition assembled from `only1mcp add` flags.
+#[derive(Debug, Clone, Default)]
+pub struct ServerSpec {
+    pub id: String,
+    pub name: String,
+    /// Transport type: `stdio`, `http`, `sse`, `streamable_http`, `grpc`,
+    /// `unix` or `docker`
+    pub transport: String,
+    /// Executable for STDIO transport
+    pub command: Option<String>,
+    /// Arguments passed to `command` (or the container), in order
+    pub args: Vec<String>,
+    /// Extra environment variables for the child process or container
+    pub env: Vec<(String, String)>,
+    /// Working directory for the child process
+    pub cwd: Option<String>,
+    /// Endpoint for HTTP-based transports, or socket path for `unix`
+    pub url: Option<String>,
+    /// Request headers for HTTP-based transports
+    pub headers: Vec<(String, String)>,
+    /// Container image for Docker transport
+    pub image: Option<String>,
+}
+
+impl ServerSpec {
+    /// Build the transport configuration, rejecting options that do not
+    /// apply to the chosen transport.
+    pub fn transport_config(&self) -> Result<TransportConfig> {
+        match self.transport.as_str() {
+            "stdio" => {
+                if self.url.is_some() || !self.headers.is_empty() || self.image.is_some() {
+                    return Err(Error::Config(
+                        "--url, --header and --image do not apply to stdio transport".into(),
+                    ));
+                }
+                let command = self
+                    .command
+                    .clone()
+                    .ok_or_else(|| Error::Config("stdio transport requires --command".into()))?;
+
+                Ok(TransportConfig::Stdio {
+                    command,
+                    args: self.args.clone(),
+                    env: self.env.iter().cloned().collect(),
+                    cwd: self.cwd.clone(),
+                })
+            },
+            "docker" => {
+                if self.command.is_some()
+                    || self.cwd.is_some()
+                    || self.url.is_some()
+                    || !self.headers.is_empty()
+                {
+                    return Err(Error::Config(
+                        "docker transport only takes --image, --arg and --env".into(),
+                    ));
+                }
+                let image = self
+                    .image
+                    .clone()
+                    .ok_or_else(|| Error::Config("docker transport requires --image".into()))?;
+
+                Ok(TransportConfig::Docker {
+                    image,
+                    args: self.args.clone(),
+                    entrypoint: None,
+                    env: self.env.iter().cloned().collect(),
+                    mounts: Vec::new(),
+                    memory: None,
+                    cpus: None,
+                    network: None,
+                })
+            },
+            "unix" => {
+                if self.command.is_some()
+                    || !self.args.is_empty()
+                    || !self.env.is_empty()
+                    || self.cwd.is_some()
+                    || !self.headers.is_empty()
+                    || self.image.is_some()
+                {
+                    return Err(Error::Config(
+                        "unix transport only takes --url with the socket path".into(),
+                    ));
+                }
+                let url = self.url.clone().ok_or_else(|| {
+                    Error::Config("unix transport requires --url with the socket path".into())
+                })?;
+                let path = url.strip_prefix("unix:").unwrap_or(&url);
+
+                Ok(TransportConfig::Unix { path: path.into() })
+            },
+            "http" | "sse" | "streamable_http" | "grpc" => {
+                if self.command.is_some()
+                    || !self.args.is_empty()
+                    || !self.env.is_empty()
+                    || self.cwd.is_some()
+                    || self.image.is_some()
+                {
+                    return Err(Error::Config(format!(
+                        "--command, --arg, --env, --cwd and --image do not apply to {} transport",
+                        self.transport
+                    )));
+                }
+                let url = self.url.clone().ok_or_else(|| {
+                    Error::Config(format!("{} transport requires --url", self.transport))
+                })?;
+                let headers: HashMap<String, String> = self.headers.iter().cloned().collect();
+
+                Ok(match self.transport.as_str() {
+                    "http" => TransportConfig::Http {
+                        url,
+                        headers,
+                        oauth: None,
+                        tls: None,
+                        signing: None,
+                        proxy: None,
+                    },
+                    "sse" => TransportConfig::Sse {
+                        url,
+                        headers,
+                        proxy: None,
+                    },
+                    "grpc" => TransportConfig::Grpc {
+                        endpoint: url,
+                        metadata: headers,
+                        tls: None,
+                    },
+                    _ => TransportConfig::StreamableHttp {
+                        url,
+                        headers,
+                        timeout_ms: None,
+                        oauth: None,
+                        tls: None,
+                        signing: None,
+                        proxy: None,
+                    },
+                })
+            },
+            other => Err(Error::Config(format!(
+                "Unknown transport '{}' (expected stdio, http, sse, streamable_http, grpc, unix or docker)",
+                other
+            ))),
+        }
+    }
+
+    /// Minimal config entry: id, name and transport, without defaulted fields.
+    fn to_entry(&self) -> Result<ServerEntry<'_>> {
+        Ok(ServerEntry {
+            id: &self.id,
+            name: &self.name,
+            transport: self.transport_config()?,
+        })
+    }
+}
+
+/// Serialized form of a new `servers` entry.
+#[derive(Serialize)]
+struct ServerEntry<'a> {
+    id: &'a str,
+    name: &'a str,
+    transport: TransportConfig,
+}
+
+/// Append a backend to the `servers` list of the config file at `path`.
+///
+/// Fails without touching the file if the ID is already taken or the
+/// resulting configuration does not validate.
+pub fn add_server(path: &Path, spec: &ServerSpec) -> Result<()> {
+    let entry = spec.to_entry()?;
+
+    edit_document(
+        path,
+        |doc| {
+            let entry =
+                serde_yaml::to_value(&entry).map_err(|e| Error::Serialization(e.to_string()))?;
+            push_yaml_server(doc, entry, &spec.id)
+        },
+        |doc| {
+            let entry =
+                toml::Value::try_from(&entry).map_err(|e| Error::Serialization(e.to_string()))?;
+            push_toml_server(doc, entry, &spec.id)
+        },
+    )
+}
+
+/// Replace the tags and notes of a backend, or of one of its tools when
+/// `tool` is given, in the config file at `path`.
+///
+/// Empty metadata removes the keys. Fails without touching the file if the
+/// backend does not exist or the result does not validate.
+pub fn set_metadata(
+    path: &Path,
+    server_id: &str,
+    tool: Option<&str>,
+    metadata: &Metadata,
+) -> Result<()> {
+    edit_document(
+        path,
+        |doc| {
+            let server = find_yaml_server(doc, server_id)?;
+            let target = match tool {
+                None => server,
+                Some(tool) => {
+                    let tools = server
+                        .entry("tool_metadata".into())
+                        .or_insert_with(|| serde_yaml::Value::Mapping(Default::default()));
+                    let tools = tools
+                        .as_mapping_mut()
+                        .ok_or_else(|| Error::Config("'tool_metadata' must be a mapping".into()))?;
+                    if metadata.is_empty() {
+                        tools.remove(tool);
+                        if tools.is_empty() {
+                            server.remove("tool_metadata");
+                        }
+                        return Ok(());
+                    }
+                    tools
+                        .entry(tool.into())
+                        .or_insert_with(|| serde_yaml::Value::Mapping(Default::default()))
+                        .as_mapping_mut()
+                        .ok_or_else(|| {
+                            Error::Config(format!("Metadata for '{}' must be a mapping", tool))
+                        })?
+                },
+            };
+
+            target.remove("tags");
+            target.remove("notes");
+            if !metadata.tags.is_empty() {
+                let tags = serde_yaml::to_value(&metadata.tags)
+                    .map_err(|e| Error::Serialization(e.to_string()))?;
+                target.insert("tags".into(), tags);
+            }
+            if let Some(notes) = &metadata.notes {
+                target.insert("notes".into(), notes.as_str().into());
+            }
+            Ok(())
+        },
+        |doc| {
+            let server = find_toml_server(doc, server_id)?;
+            let target = match tool {
+                None => server,
+                Some(tool) => {
+                    let tools = server
+                        .entry("tool_metadata")
+                        .or_insert_with(|| toml::Value::Table(Default::default()))
+                        .as_table_mut()
+                        .ok_or_else(|| Error::Config("'tool_metadata' must be a table".into()))?;
+                    if metadata.is_empty() {
+                        tools.remove(tool);
+                        if tools.is_empty() {
+                            server.remove("tool_metadata");
+                        }
+                        return Ok(());
+                    }
+                    tools
+                        .entry(tool)
+                        .or_insert_with(|| toml::Value::Table(Default::default()))
+                        .as_table_mut()
+                        .ok_or_else(|| {
+                            Error::Config(format!("Metadata for '{}' must be a table", tool))
+                        })?
+                },
+            };
+
+            target.remove("tags");
+            target.remove("notes");
+            if !metadata.tags.is_empty() {
+                let tags = toml::Value::try_from(&metadata.tags)
+                    .map_err(|e| Error::Serialization(e.to_string()))?;
+                target.insert("tags".into(), tags);
+            }
+            if let Some(notes) = &metadata.notes {
+                target.insert("notes".into(), notes.as_str().into());
+            }
+            Ok(())
+        },
+    )
+}
+
+/// Enable or disable a backend in the config file at `path`.
+///
+/// `enabled` defaults to true, so enabling removes the key. Fails without
+/// touching the file if the backend does not exist.
+pub fn set_enabled(path: &Path, server_id: &str, enabled: bool) -> Result<()> {
+    edit_document(
+        path,
+        |doc| {
+            let server = find_yaml_server(doc, server_id)?;
+            server.remove("enabled");
+            if !enabled {
+                server.insert("enabled".into(), false.into());
+            }
+            Ok(())
+        },
+        |doc| {
+            let server = find_toml_server(doc, server_id)?;
+            server.remove("enabled");
+            if !enabled {
+                server.insert("enabled".into(), false.into());
+            }
+            Ok(())
+        },
+    )
+}
+
+/// Apply an edit to the config file at `path` as a YAML or TOML document
+/// (by extension), then validate the result and write it back atomically,
+/// backing up the previous version first.
+fn edit_document<Y, T>(path: &Path, edit_yaml: Y, edit_toml: T) -> Result<()>
+where
+    Y: FnOnce(&mut serde_yaml::Value) -> Result<()>,
+    T: FnOnce(&mut toml::Value) -> Result<()>,
+{
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| Error::Config(format!("Failed to read config file: {}", e)))?;
+
+    let (config, updated) = match path.extension().and_then(|ext| ext.to_str()).unwrap_or("yaml") {
+        "yaml" | "yml" => {
+            let mut doc: serde_yaml::Value = serde_yaml::from_str(&content)
+                .map_err(|e| Error::Config(format!("Failed to parse YAML: {}", e)))?;
+            edit_yaml(&mut doc)?;
+
+            let config: Config = serde_yaml::from_value(doc.clone())
+                .map_err(|e| Error::Config(format!("Invalid configuration: {}", e)))?;
+            let updated =
+                serde_yaml::to_string(&doc).map_err(|e| Error::Serialization(e.to_string()))?;
+            (config, updated)
+        },
+        "toml" => {
+            let mut doc: toml::Value = toml::from_str(&content)
+                .map_err(|e| Error::Config(format!("Failed to parse TOML: {}", e)))?;
+            edit_toml(&mut doc)?;
+
+            let config: Config = doc
+                .clone()
+                .try_into()
+                .map_err(|e| Error::Config(format!("Invalid configuration: {}", e)))?;
+            let updated =
+                toml::to_string_pretty(&doc).map_err(|e| Error::Serialization(e.to_string()))?;
+            (config, updated)
+        },
+        other => {
+            return Err(Error::Config(format!(
+                "Unsupported config format: {}",
+                other
+            )))
+        },
+    };
+
+    config.validate()?;
+
+    backup::create_backup(path, &config.backups)?;
+    write_atomically(path, &updated)
+}
+
+fn find_yaml_server<'a>(
+    doc: &'a mut serde_yaml::Value,
+    id: &str,
+) -> Result<&'a mut serde_yaml::Mapping> {
+    doc.get_mut("servers")
+        .and_then(|servers| servers.as_sequence_mut())
+        .and_then(|servers| {
+            servers.iter_mut().find(|s| s.get("id").and_then(|v| v.as_str()) == Some(id))
+        })
+        .and_then(|server| server.as_mapping_mut())
+        .ok_or_else(|| Error::Config(format!("Server '{}' not found", id)))
+}
+
+fn find_toml_server<'a>(doc: &'a mut toml::Value, id: &str) -> Result<&'a mut toml::Table> {
+    doc.get_mut("servers")
+        .and_then(|servers| servers.as_array_mut())
+        .and_then(|servers| {
+            servers.iter_mut().find(|s| s.get("id").and_then(|v| v.as_str()) == Some(id))
+        })
+        .and_then(|server| server.as_table_mut())
+        .ok_or_else(|| Error::Config(format!("Server '{}' not found", id)))
+}
+
+fn push_yaml_server(doc: &mut serde_yaml::Value, entry: serde_yaml::Value, id: &str) -> Result<()> {
+    let root = doc
+        .as_mapping_mut()
+        .ok_or_else(|| Error::Config("Config file root must be a mapping".into()))?;
+    let servers = root
+        .entry("servers".into())
+        .or_insert_with(|| serde_yaml::Value::Sequence(Vec::new()));
+    if servers.is_null() {
+        *servers = serde_yaml::Value::Sequence(Vec::new());
+    }
+    let servers = servers
+        .as_sequence_mut()
+        .ok_or_else(|| Error::Config("'servers' must be a list".into()))?;
+
+    if servers.iter().any(|s| s.get("id").and_then(|v| v.as_str()) == Some(id)) {
+        return Err(Error::Config(format!("Server '{}' already exists", id)));
+    }
+    servers.push(entry);
+    Ok(())
+}
+
+fn push_toml_server(doc: &mut toml::Value, entry: toml::Value, id: &str) -> Result<()> {
+    let root = doc
+        .as_table_mut()
+        .ok_or_else(|| Error::Config("Config file root must be a table".into()))?;
+    let servers = root
+        .entry("servers")
+        .or_insert_with(|| toml::Value::Array(Vec::new()))
+        .as_array_mut()
+        .ok_or_else(|| Error::Config("'servers' must be an array".into()))?;
+
+    if servers.iter().any(|s| s.get("id").and_then(|v| v.as_str()) == Some(id)) {
+        return Err(Error::Config(format!("Server '{}' already exists", id)));
+    }
+    servers.push(entry);
+    Ok(())
+}
+
+/// Replace `path` with `content` via a sibling temporary file and rename.
+pub(crate) fn write_atomically(path: &Path, content: &str) -> Result<()> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| Error::Config(format!("Invalid config path: {}", path.display())))?;
+    let tmp = path.with_file_name(format!(".{}.tmp", file_name.to_string_lossy()));
+
+    std::fs::write(&tmp, content)
+        .map_err(|e| Error::Config(format!("Failed to write config file: {}", e)))?;
+    std::fs::rename(&tmp, path)
+        .map_err(|e| Error::Config(format!("Failed to replace config file: {}", e)))
+}
+
+/// Parse a `KEY=VALUE` pair (for `--env`).
+pub fn parse_env_pair(s: &str) -> Result<(String, String)> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(Error::Config(format!("expected KEY=VALUE, got '{}'", s))),
+    }
+}
+
+/// Parse a `Name: value` header (for `--header`).
+pub fn parse_header(s: &str) -> Result<(String, String)> {
+    match s.split_once(':') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        },
+        _ => Err(Error::Config(format!(
+            "expected 'Name: value', got '{}'",
+            s
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn stdio_spec() -> ServerSpec {
+        ServerSpec {
+            id: "fs".into(),
+            name: "Filesystem".into(),
+            transport: "stdio".into(),
+            command: Some("npx".into()),
+            args: vec![
+                "-y".into(),
+                "@modelcontextprotocol/server-filesystem".into(),
+            ],
+            env: vec![("NODE_ENV".into(), "production".into())],
+            cwd: Some("/srv/data".into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_add_stdio_server_to_yaml() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("only1mcp.yaml");
+        std::fs::write(&path, "server:\n  port: 9000\nservers: []\n").unwrap();
+
+        add_server(&path, &stdio_spec()).unwrap();
+
+        let config = Config::from_file(&path).unwrap();
+        assert_eq!(config.server.port, 9000);
+        match &config.servers[0].transport {
+            TransportConfig::Stdio {
+                command,
+                args,
+                env,
+                cwd,
+            } => {
+                assert_eq!(command, "npx");
+                assert_eq!(args, &["-y", "@modelcontextprotocol/server-filesystem"]);
+                assert_eq!(env["NODE_ENV"], "production");
+                assert_eq!(cwd.as_deref(), Some("/srv/data"));
+            },
+            other => panic!("unexpected transport {:?}", other),
+        }
+
+        // Defaults are not written out
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(!written.contains("health_check"));
+
+        // Duplicate IDs are rejected and leave the file untouched
+        assert!(add_server(&path, &stdio_spec()).is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), written);
+
+        // Only the successful edit backed up the previous version
+        let backups = backup::list_backups(&path).unwrap();
+        assert_eq!(backups.len(), 1);
+        assert!(std::fs::read_to_string(&backups[0].path).unwrap().contains("port: 9000"));
+    }
+
+    #[test]
+    fn test_add_http_server_to_toml() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("only1mcp.toml");
+        std::fs::write(&path, "[server]\nport = 9000\n").unwrap();
+
+        let spec = ServerSpec {
+            id: "api".into(),
+            name: "API".into(),
+            transport: "http".into(),
+            url: Some("http://localhost:3000/mcp".into()),
+            headers: vec![parse_header("Authorization: Bearer abc").unwrap()],
+            ..Default::default()
+        };
+        add_server(&path, &spec).unwrap();
+
+        let config = Config::from_file(&path).unwrap();
+        match &config.servers[0].transport {
+            TransportConfig::Http { url, headers, .. } => {
+                assert_eq!(url, "http://localhost:3000/mcp");
+                assert_eq!(headers["Authorization"], "Bearer abc");
+            },
+            other => panic!("unexpected transport {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_set_metadata() {
+        let dir = TempDir::new().unwrap();
+        for file in ["only1mcp.yaml", "only1mcp.toml"] {
+            let path = dir.path().join(file);
+            std::fs::write(
+                &path,
+                if file.ends_with("toml") { "[server]\nport = 9000\n" } else { "servers: []\n" },
+            )
+            .unwrap();
+            add_server(&path, &stdio_spec()).unwrap();
+
+            let destructive = Metadata {
+                tags: vec!["destructive".into()],
+                notes: Some("Deletes without confirmation".into()),
+            };
+            set_metadata(
+                &path,
+                "fs",
+                None,
+                &Metadata {
+                    tags: vec!["local".into()],
+                    notes: None,
+                },
+            )
+            .unwrap();
+            set_metadata(&path, "fs", Some("delete_file"), &destructive).unwrap();
+
+            let config = Config::from_file(&path).unwrap();
+            assert_eq!(config.servers[0].tags, vec!["local"]);
+            assert_eq!(
+                config.servers[0].tool_metadata("delete_file"),
+                Some(&destructive)
+            );
+
+            // Empty metadata removes the entry again
+            set_metadata(&path, "fs", Some("delete_file"), &Metadata::default()).unwrap();
+            let written = std::fs::read_to_string(&path).unwrap();
+            assert!(!written.contains("tool_metadata"), "{}", file);
+
+            assert!(set_metadata(&path, "missing", None, &destructive).is_err());
+            assert!(set_metadata(
+                &path,
+                "fs",
+                None,
+                &Metadata {
+                    tags: vec!["two words".into()],
+                    notes: None,
+                }
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn test_set_enabled() {
+        let dir = TempDir::new().unwrap();
+        for file in ["only1mcp.yaml", "only1mcp.toml"] {
+            let path = dir.path().join(file);
+            std::fs::write(
+                &path,
+                if file.ends_with("toml") { "[server]\nport = 9000\n" } else { "servers: []\n" },
+            )
+            .unwrap();
+            add_server(&path, &stdio_spec()).unwrap();
+
+            set_enabled(&path, "fs", false).unwrap();
+            assert!(!Config::from_file(&path).unwrap().servers[0].enabled);
+
+            set_enabled(&path, "fs", true).unwrap();
+            assert!(Config::from_file(&path).unwrap().servers[0].enabled);
+            let written = std::fs::read_to_string(&path).unwrap();
+            assert!(!written.contains("enabled"), "{}", file);
+
+            assert!(set_enabled(&path, "missing", false).is_err());
+        }
+    }
+
+    #[test]
+    fn test_transport_option_mismatch() {
+        let mut spec = stdio_spec();
+        spec.url = Some("http://localhost".into());
+        assert!(spec.transport_config().is_err());
+
+        let spec = ServerSpec {
+            transport: "http".into(),
+            url: Some("http://localhost".into()),
+            cwd: Some("/tmp".into()),
+            ..Default::default()
+        };
+        assert!(spec.transport_config().is_err());
+
+        let spec = ServerSpec {
+            transport: "stdio".into(),
+            ..Default::default()
+        };
+        assert!(spec.transport_config().is_err());
+    }
+
+    #[test]
+    fn test_docker_spec_runs_container() {
+        let spec = ServerSpec {
+            transport: "docker".into(),
+            image: Some("mcp/fetch:latest".into()),
+            args: vec!["--verbose".into()],
+            env: vec![("API_KEY".into(), "secret".into())],
+            ..Default::default()
+        };
+        let transport = spec.transport_config().unwrap();
+        assert!(transport.is_process());
+
+        let process = transport.process_command().unwrap();
+        assert_eq!(process.command, "docker");
+        assert_eq!(
+            process.args,
+            [
+                "run",
+                "-i",
+                "--rm",
+                "--init",
+                "-e",
+                "API_KEY",
+                "mcp/fetch:latest",
+                "--verbose"
+            ]
+        );
+        // The value reaches the container through the docker process only
+        assert_eq!(process.env["API_KEY"], "secret");
+        assert!(!process.sandbox);
+
+        let spec = ServerSpec {
+            url: Some("http://localhost".into()),
+            ..spec
+        };
+        assert!(spec.transport_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_pairs() {
+        assert_eq!(
+            parse_env_pair("TOKEN=a=b").unwrap(),
+            ("TOKEN".to_string(), "a=b".to_string())
+        );
+        assert!(parse_env_pair("=x").is_err());
+        assert!(parse_env_pair("novalue").is_err());
+        assert_eq!(
+            parse_header("X-Api-Key:  secret ").unwrap(),
+            ("X-Api-Key".to_string(), "secret".to_string())
+        );
+        assert!(parse_header("broken").is_err());
+    }
+}
@@ -0,0 +1,353 @@
+//! GitOps sync (`gitops`).
+//!
+//! The configuration file follows a file in a git repository. Every
+//! `poll_interval_ms`, or when `POST /api/v1/admin/gitops/sync` is called
+//! (e.g. by a push webhook), the branch is fetched into a `gitops/` checkout
+//! next to the configuration file. When its commit moved, the file at `path`
+//! is validated, written over the configuration file (which is backed up
+//! first) and applied. A commit that does not validate is skipped and
+//! reported in `GET /api/v1/admin/system`; the running configuration stays.
+//!
+//! The repository's file replaces the whole configuration file, so it should
+//! keep the `gitops` section, or syncing stops at the next restart. Git runs
+//! through the `git` command, so its credential helpers and SSH keys apply.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+use tracing::debug;
+
+use crate::config::{backup, schema::Format, Config};
+use crate::error::{Error, Result};
+use crate::types::GitOpsStatus;
+
+/// Directory, next to the configuration file, holding the checkout.
+pub const CHECKOUT_DIR: &str = "gitops";
+
+/// Longest a git command may run.
+const GIT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Where the configuration comes from (`gitops` section).
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct GitOpsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Repository URL, as accepted by `git clone`
+    #[serde(default)]
+    pub repository: String,
+    #[serde(default = "default_branch")]
+    pub branch: String,
+    /// Configuration file, relative to the repository root
+    #[serde(default = "default_path")]
+    pub path: String,
+    /// How often the branch is fetched, in milliseconds (0 fetches only
+    /// when a sync is requested)
+    #[serde(default = "default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+}
+
+fn default_branch() -> String {
+    "main".to_string()
+}
+fn default_path() -> String {
+    "only1mcp.yaml".to_string()
+}
+fn default_poll_interval_ms() -> u64 {
+    60_000
+}
+
+impl Default for GitOpsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            repository: String::new(),
+            branch: default_branch(),
+            path: default_path(),
+            poll_interval_ms: default_poll_interval_ms(),
+        }
+    }
+}
+
+impl GitOpsConfig {
+    pub fn poll_interval(&self) -> Option<Duration> {
+        (self.poll_interval_ms > 0).then(|| Duration::from_millis(self.poll_interval_ms))
+    }
+}
+
+/// Directory of the checkout for the configuration file at `config_path`.
+pub fn checkout_dir(config_path: &Path) -> PathBuf {
+    config_path.parent().unwrap_or_else(|| Path::new(".")).join(CHECKOUT_DIR)
+}
+
+/// The configuration file at a commit.
+#[derive(Debug, Clone)]
+pub struct Revision {
+    pub commit: String,
+    pub content: String,
+}
+
+/// Fetches the repository and installs new revisions, keeping the state
+/// reported in `GET /api/v1/admin/system`.
+#[derive(Debug)]
+pub struct GitOpsSync {
+    settings: GitOpsConfig,
+    config_path: PathBuf,
+    status: Mutex<GitOpsStatus>,
+    /// Commit last installed or rejected, so each is tried once
+    seen: Mutex<Option<String>>,
+    requested: Notify,
+}
+
+impl GitOpsSync {
+    pub fn new(settings: GitOpsConfig, config_path: PathBuf) -> Self {
+        let status = GitOpsStatus {
+            repository: settings.repository.clone(),
+            branch: settings.branch.clone(),
+            path: settings.path.clone(),
+            commit: None,
+            applied_at: None,
+            checked_at: None,
+            error: None,
+        };
+        Self {
+            settings,
+            config_path,
+            status: Mutex::new(status),
+            seen: Mutex::new(None),
+            requested: Notify::new(),
+        }
+    }
+
+    pub fn settings(&self) -> &GitOpsConfig {
+        &self.settings
+    }
+
+    pub fn status(&self) -> GitOpsStatus {
+        self.status.lock().clone()
+    }
+
+    /// Ask for a sync now instead of at the next poll.
+    pub fn request(&self) {
+        self.requested.notify_one();
+    }
+
+    /// Wait until a sync is requested.
+    pub async fn requested(&self) {
+        self.requested.notified().await
+    }
+
+    /// Fetch the branch, returning the configuration file if the commit
+    /// moved since the last one installed or rejected.
+    pub async fn fetch(&self) -> Result<Option<Revision>> {
+        let checkout = checkout_dir(&self.config_path);
+        let result = self.update_checkout(&checkout).await;
+        self.status.lock().checked_at = Some(chrono::Utc::now());
+        let commit = self.record(result)?;
+        if self.seen.lock().as_deref() == Some(commit.as_str()) {
+            return Ok(None);
+        }
+
+        let path = checkout.join(&self.settings.path);
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| Error::Config(format!("Failed to read {}: {}", path.display(), e)));
+        let content = self.reject(&commit, content)?;
+        Ok(Some(Revision { commit, content }))
+    }
+
+    /// Validate a revision and write it over the configuration file,
+    /// returning the configuration to apply.
+    pub fn install(&self, revision: &Revision) -> Result<Config> {
+        let config = self.reject(&revision.commit, self.load(revision))?;
+        let settings = Config::from_file(&self.config_path)
+            .map(|config| config.backups)
+            .unwrap_or_default();
+        let written = backup::create_backup(&self.config_path, &settings).and_then(|_| {
+            crate::config::edit::write_atomically(&self.config_path, &revision.content)
+        });
+        self.reject(&revision.commit, written)?;
+        Ok(config)
+    }
+
+    /// Record that a revision was applied.
+    pub fn applied(&self, revision: &Revision) {
+        *self.seen.lock() = Some(revision.commit.clone());
+        let mut status = self.status.lock();
+        status.commit = Some(revision.commit.clone());
+        status.applied_at = Some(chrono::Utc::now());
+        status.error = None;
+    }
+
+    /// Record that a revision could not be applied, so it is not tried
+    /// again.
+    pub fn rejected(&self, commit: &str, error: &Error) {
+        *self.seen.lock() = Some(commit.to_string());
+        self.status.lock().error = Some(format!("Commit {}: {}", short(commit), error));
+    }
+
+    fn load(&self, revision: &Revision) -> Result<Config> {
+        let format = Format::from_path(Path::new(&self.settings.path))?;
+        if Format::from_path(&self.config_path)? != format {
+            return Err(Error::Config(format!(
+                "gitops.path {} is not in the format of {}",
+                self.settings.path,
+                self.config_path.display()
+            )));
+        }
+        let config = Config::from_content(&revision.content, format)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Pass `result` through, recording its error.
+    fn record<T>(&self, result: Result<T>) -> Result<T> {
+        if let Err(e) = &result {
+            self.status.lock().error = Some(e.to_string());
+        }
+        result
+    }
+
+    /// Pass `result` through, rejecting `commit` on error.
+    fn reject<T>(&self, commit: &str, result: Result<T>) -> Result<T> {
+        if let Err(e) = &result {
+            self.rejected(commit, e);
+        }
+        result
+    }
+
+    /// Clone the branch, or fetch and check out its head, returning the
+    /// commit.
+    async fn update_checkout(&self, checkout: &Path) -> Result<String> {
+        let GitOpsConfig {
+            repository, branch, ..
+        } = &self.settings;
+        if checkout.join(".git").exists() {
+            git(
+                checkout,
+                &["fetch", "--quiet", "--depth", "1", "origin", branch],
+            )
+            .await?;
+            git(checkout, &["reset", "--quiet", "--hard", "FETCH_HEAD"]).await?;
+        } else {
+            let parent = checkout.parent().unwrap_or_else(|| Path::new("."));
+            let target = checkout.to_string_lossy();
+            git(
+                parent,
+                &[
+                    "clone",
+                    "--quiet",
+                    "--depth",
+                    "1",
+                    "--single-branch",
+                    "--branch",
+                    branch,
+                    "--",
+                    repository,
+                    &target,
+                ],
+            )
+            .await?;
+        }
+        git(checkout, &["rev-parse", "HEAD"]).await
+    }
+}
+
+/// Run git in `dir`, returning its trimmed output.
+async fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    debug!("Running git {} in {}", args.join(" "), dir.display());
+    let output = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        // Fail instead of waiting for credentials nobody will type
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(GIT_TIMEOUT, output)
+        .await
+        .map_err(|_| Error::Config(format!("git {} timed out", args[0])))?
+        .map_err(|e| Error::Config(format!("Failed to run git: {}", e)))?;
+    if !output.status.success() {
+        return Err(Error::Config(format!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Abbreviated commit hash, for messages.
+pub fn short(commit: &str) -> &str {
+    &commit[..commit.len().min(12)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn run_git(repo: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git").arg("-C").arg(repo).args(args).status();
+        assert!(status.unwrap().success(), "git {:?}", args);
+    }
+
+    fn commit(repo: &Path, content: &str) {
+        std::fs::write(repo.join("only1mcp.yaml"), content).unwrap();
+        run_git(repo, &["add", "only1mcp.yaml"]);
+        run_git(
+            repo,
+            &[
+                "-c",
+                "user.name=test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-qm",
+                "update",
+            ],
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sync_installs_new_commits() {
+        let dir = TempDir::new().unwrap();
+        let repo = dir.path().join("repo");
+        std::fs::create_dir(&repo).unwrap();
+        run_git(&repo, &["init", "--quiet", "--initial-branch", "main"]);
+        commit(&repo, "server:\n  port: 8081\nservers: []\n");
+
+        let config_path = dir.path().join("etc").join("only1mcp.yaml");
+        std::fs::create_dir(config_path.parent().unwrap()).unwrap();
+        std::fs::write(&config_path, "server:\n  port: 8080\nservers: []\n").unwrap();
+        let sync = GitOpsSync::new(
+            GitOpsConfig {
+                enabled: true,
+                repository: repo.display().to_string(),
+                ..Default::default()
+            },
+            config_path.clone(),
+        );
+
+        let revision = sync.fetch().await.unwrap().unwrap();
+        let config = sync.install(&revision).unwrap();
+        sync.applied(&revision);
+        assert_eq!(config.server.port, 8081);
+        assert!(std::fs::read_to_string(&config_path).unwrap().contains("8081"));
+        assert_eq!(backup::list_backups(&config_path).unwrap().len(), 1);
+        assert_eq!(sync.status().commit, Some(revision.commit));
+        assert!(sync.fetch().await.unwrap().is_none());
+
+        // A commit that does not validate is reported and skipped
+        commit(&repo, "server:\n  port: 0\nservers: []\n");
+        let revision = sync.fetch().await.unwrap().unwrap();
+        assert!(sync.install(&revision).is_err());
+        assert!(sync.status().error.unwrap().contains(short(&revision.commit)));
+        assert!(std::fs::read_to_string(&config_path).unwrap().contains("8081"));
+        assert!(sync.fetch().await.unwrap().is_none());
+    }
+}
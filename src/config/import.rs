@@ -0,0 +1,283 @@
+//! Import backend definitions from other MCP clients.
+//!
+//! Claude Desktop, Cursor and Windsurf all describe servers with the same
+//! `mcpServers` JSON object, keyed by server name:
+//!
+//! ```json
+//! {
+//!   "mcpServers": {
+//!     "filesystem": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-filesystem"]},
+//!     "remote": {"url": "https://example.com/mcp"}
+//!   }
+//! }
+//! ```
+//!
+//! Entries are converted to [`ServerSpec`]s and written with
+//! [`add_server`], so imports go through the same validation as
+//! `only1mcp add`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::config::edit::{add_server, ServerSpec};
+use crate::config::Config;
+use crate::error::{Error, Result};
+
+/// MCP clients whose configuration can be imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum McpClient {
+    ClaudeDesktop,
+    Cursor,
+    Windsurf,
+}
+
+impl McpClient {
+    /// All supported clients, in discovery order.
+    pub const ALL: [McpClient; 3] = [Self::ClaudeDesktop, Self::Cursor, Self::Windsurf];
+
+    /// Human-readable client name.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::ClaudeDesktop => "Claude Desktop",
+            Self::Cursor => "Cursor",
+            Self::Windsurf => "Windsurf",
+        }
+    }
+
+    /// Default location of the client's MCP configuration on this platform.
+    pub fn config_path(&self) -> Option<PathBuf> {
+        match self {
+            // ~/Library/Application Support, %APPDATA% or ~/.config
+            Self::ClaudeDesktop => {
+                dirs::config_dir().map(|dir| dir.join("Claude").join("claude_desktop_config.json"))
+            },
+            Self::Cursor => dirs::home_dir().map(|home| home.join(".cursor").join("mcp.json")),
+            Self::Windsurf => dirs::home_dir()
+                .map(|home| home.join(".codeium").join("windsurf").join("mcp_config.json")),
+        }
+    }
+}
+
+/// Servers found in one client's configuration file.
+#[derive(Debug, Clone)]
+pub struct ClientServers {
+    pub client: McpClient,
+    pub path: PathBuf,
+    pub servers: Vec<ServerSpec>,
+}
+
+/// Outcome of [`import_servers`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// IDs written to the config file
+    pub added: Vec<String>,
+    /// IDs skipped because a backend with the same ID already exists
+    pub skipped: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ClientConfig {
+    #[serde(default, rename = "mcpServers")]
+    mcp_servers: BTreeMap<String, ClientServer>,
+}
+
+#[derive(Deserialize)]
+struct ClientServer {
+    command: Option<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    env: HashMap<String, String>,
+    cwd: Option<String>,
+    // Windsurf uses `serverUrl` for remote servers
+    #[serde(alias = "serverUrl")]
+    url: Option<String>,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    #[serde(default)]
+    disabled: bool,
+}
+
+/// Parse a client's `mcpServers` JSON document.
+///
+/// Disabled entries are left out, as are entries that have neither a
+/// command nor a URL.
+pub fn parse_client_config(content: &str) -> Result<Vec<ServerSpec>> {
+    let config: ClientConfig = serde_json::from_str(content)
+        .map_err(|e| Error::Config(format!("Failed to parse MCP client config: {}", e)))?;
+
+    Ok(config
+        .mcp_servers
+        .into_iter()
+        .filter(|(_, server)| !server.disabled)
+        .filter_map(|(name, server)| to_spec(name, server))
+        .collect())
+}
+
+fn to_spec(name: String, server: ClientServer) -> Option<ServerSpec> {
+    let mut env: Vec<_> = server.env.into_iter().collect();
+    env.sort();
+    let mut headers: Vec<_> = server.headers.into_iter().collect();
+    headers.sort();
+
+    let spec = ServerSpec {
+        id: server_id(&name),
+        name,
+        ..Default::default()
+    };
+
+    if let Some(command) = server.command {
+        return Some(ServerSpec {
+            transport: "stdio".into(),
+            command: Some(command),
+            args: server.args,
+            env,
+            cwd: server.cwd,
+            ..spec
+        });
+    }
+
+    let url = server.url?;
+    let transport = match server.kind.as_deref() {
+        Some("sse") => "sse",
+        Some("streamable-http") | Some("streamable_http") => "streamable_http",
+        _ => "http",
+    };
+    Some(ServerSpec {
+        transport: transport.into(),
+        url: Some(url),
+        headers,
+        ..spec
+    })
+}
+
+/// Lowercased server name with anything but letters, digits, `-` and `_`
+/// replaced by `-`.
+fn server_id(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Read the servers configured in `client`, if its config file exists.
+pub fn load_client(client: McpClient) -> Result<Option<ClientServers>> {
+    let Some(path) = client.config_path() else {
+        return Ok(None);
+    };
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| Error::Config(format!("Failed to read {}: {}", path.display(), e)))?;
+    Ok(Some(ClientServers {
+        client,
+        servers: parse_client_config(&content)?,
+        path,
+    }))
+}
+
+/// Look for every supported client's configuration on this machine.
+///
+/// Unreadable files are logged and skipped.
+pub fn discover() -> Vec<ClientServers> {
+    McpClient::ALL
+        .into_iter()
+        .filter_map(|client| match load_client(client) {
+            Ok(found) => found,
+            Err(e) => {
+                tracing::warn!("Skipping {} config: {}", client.label(), e);
+                None
+            },
+        })
+        .collect()
+}
+
+/// Add `servers` to the config file at `path`, skipping IDs already taken.
+pub fn import_servers(path: &Path, servers: &[ServerSpec]) -> Result<ImportSummary> {
+    let existing = Config::from_file(path)?;
+    let mut summary = ImportSummary::default();
+
+    for spec in servers {
+        let taken =
+            existing.servers.iter().any(|s| s.id == spec.id) || summary.added.contains(&spec.id);
+        if taken {
+            summary.skipped.push(spec.id.clone());
+            continue;
+        }
+        add_server(path, spec)?;
+        summary.added.push(spec.id.clone());
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TransportConfig;
+    use tempfile::TempDir;
+
+    const CLAUDE_CONFIG: &str = r#"{
+        "mcpServers": {
+            "filesystem": {
+                "command": "npx",
+                "args": ["-y", "@modelcontextprotocol/server-filesystem", "/tmp"],
+                "env": {"DEBUG": "1"}
+            },
+            "Remote API": {"url": "https://example.com/sse", "type": "sse"},
+            "old": {"command": "old-server", "disabled": true}
+        }
+    }"#;
+
+    #[test]
+    fn test_parse_client_config() {
+        let specs = parse_client_config(CLAUDE_CONFIG).unwrap();
+        assert_eq!(specs.len(), 2);
+
+        let remote = &specs[0];
+        assert_eq!(remote.id, "remote-api");
+        assert_eq!(remote.name, "Remote API");
+        assert_eq!(remote.transport, "sse");
+
+        let fs = &specs[1];
+        assert_eq!(fs.transport, "stdio");
+        assert_eq!(fs.command.as_deref(), Some("npx"));
+        assert_eq!(fs.args.len(), 3);
+        assert_eq!(fs.env, vec![("DEBUG".to_string(), "1".to_string())]);
+    }
+
+    #[test]
+    fn test_import_skips_existing_ids() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("only1mcp.yaml");
+        std::fs::write(
+            &path,
+            "servers:\n  - id: filesystem\n    name: FS\n    transport:\n      type: stdio\n      command: fs\n",
+        )
+        .unwrap();
+
+        let specs = parse_client_config(CLAUDE_CONFIG).unwrap();
+        let summary = import_servers(&path, &specs).unwrap();
+        assert_eq!(summary.added, vec!["remote-api"]);
+        assert_eq!(summary.skipped, vec!["filesystem"]);
+
+        let config = Config::from_file(&path).unwrap();
+        assert_eq!(config.servers.len(), 2);
+        assert!(matches!(
+            config.servers[1].transport,
+            TransportConfig::Sse { .. }
+        ));
+    }
+}
@@ -83,6 +83,9 @@ impl ConfigLoader {
         // Load initial configuration
         let initial_config = Config::from_file(&config_path)?;
 
+        // Watcher events carry absolute paths, so compare against one
+        let config_path = config_path.canonicalize().unwrap_or(config_path);
+
         // Validate initial config
         initial_config.validate()?;
 
@@ -138,42 +141,45 @@ impl ConfigLoader {
             Duration::from_millis(500),
             None,
             move |result: std::result::Result<Vec<DebouncedEvent>, Vec<notify::Error>>| {
-                match result {
-                    Ok(events) => {
-                        for event in events {
-                            // Nesting required for: event loop → path checking → reload handling
-                            #[allow(clippy::excessive_nesting)]
-                            if event.paths.contains(&config_path) {
-                                debug!("Config file changed: {:?}", event.kind);
-                                if let Err(e) =
-                                    Self::reload_config_internal(&config_path, &config, &reload_tx)
-                                {
-                                    error!("Failed to reload config: {}", e);
-
-                                    // Update error metrics
-                                    #[cfg(feature = "metrics")]
-                                    {
-                                        use crate::metrics::CONFIG_RELOAD_ERRORS;
-                                        CONFIG_RELOAD_ERRORS.inc();
-                                    }
-                                }
-                            }
-                        }
-                    },
+                let events = match result {
+                    Ok(events) => events,
                     Err(errors) => {
                         for e in errors {
                             error!("File watcher error: {}", e);
                         }
+                        return;
                     },
+                };
+
+                // The whole directory is watched; reload once per batch that
+                // modified the config file (reads are ignored, since reloading
+                // reads the file itself)
+                let changed = events
+                    .iter()
+                    .find(|event| !event.kind.is_access() && event.paths.contains(&config_path));
+                let Some(event) = changed else {
+                    return;
+                };
+
+                debug!("Config file changed: {:?}", event.kind);
+                if let Err(e) = Self::reload_config_internal(&config_path, &config, &reload_tx) {
+                    error!("Failed to reload config: {}", e);
+
+                    // Update error metrics
+                    #[cfg(feature = "metrics")]
+                    crate::metrics::CONFIG_RELOAD_ERRORS.inc();
                 }
             },
         )
         .map_err(|e| Error::Config(format!("Failed to create file watcher: {}", e)))?;
 
-        // Watch the config file
+        // Watch the containing directory rather than the file itself: editors
+        // that save by writing a temp file and renaming it over the original
+        // would otherwise leave the watch on the replaced inode
+        let watch_dir = self.config_path.parent().unwrap_or(Path::new("."));
         debouncer
             .watcher()
-            .watch(self.config_path.as_path(), RecursiveMode::NonRecursive)
+            .watch(watch_dir, RecursiveMode::NonRecursive)
             .map_err(|e| Error::Config(format!("Failed to watch config file: {}", e)))?;
 
         info!("File watcher started for: {}", self.config_path.display());
@@ -0,0 +1,252 @@
+//! Catalog of known MCP servers (`only1mcp search` and `only1mcp install`).
+//!
+//! The built-in catalog (`config/marketplace.yaml`) lists widely used
+//! servers with the command, arguments and environment they need, so that
+//! installing one is a single command instead of a trip to its README. A
+//! remote or local catalog in the same format can replace it.
+//!
+//! Installed servers never get secrets written into the config file: each
+//! environment variable an entry needs is stored as a `${VAR}` reference
+//! (see [`super::secrets`]) unless a value is given explicitly.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::edit::ServerSpec;
+use crate::error::{Error, Result};
+
+/// The catalog shipped with the binary.
+const BUILTIN: &str = include_str!("../../config/marketplace.yaml");
+
+/// A list of installable MCP servers.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Default)]
+pub struct Marketplace {
+    #[serde(default)]
+    pub servers: Vec<MarketplaceEntry>,
+}
+
+/// One installable MCP server.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct MarketplaceEntry {
+    /// Name used with `install` and as the default server id
+    pub name: String,
+    /// Display name (default: `name`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub description: String,
+    /// Extra search terms
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Transport type, as for `only1mcp add`
+    pub transport: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Arguments the user must append, in order, e.g. `<allowed directory>`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arg_hints: Vec<String>,
+    /// Environment variables the server needs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+}
+
+/// Choices made by the user when installing an entry.
+#[derive(Debug, Clone, Default)]
+pub struct InstallOptions {
+    /// Server id (default: the entry's name)
+    pub id: Option<String>,
+    /// Arguments appended to the entry's own, filling its `arg_hints`
+    pub args: Vec<String>,
+    /// Environment values; the entry's variables not given here are stored
+    /// as `${VAR}` references
+    pub env: Vec<(String, String)>,
+}
+
+impl Marketplace {
+    /// The catalog shipped with the binary.
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN).expect("built-in marketplace catalog is valid")
+    }
+
+    /// Parse a catalog in YAML or JSON.
+    pub fn parse(text: &str) -> Result<Self> {
+        serde_yaml::from_str(text)
+            .map_err(|e| Error::Config(format!("Invalid marketplace catalog: {}", e)))
+    }
+
+    /// The catalog at `source`: an `http(s)://` URL, a file, or the
+    /// built-in catalog when `None`.
+    pub async fn load(source: Option<&str>) -> Result<Self> {
+        let Some(source) = source else {
+            return Ok(Self::builtin());
+        };
+        let text = if source.starts_with("http://") || source.starts_with("https://") {
+            let response = reqwest::get(source)
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(|e| Error::Config(format!("Failed to fetch catalog {}: {}", source, e)))?;
+            response
+                .text()
+                .await
+                .map_err(|e| Error::Config(format!("Failed to read catalog {}: {}", source, e)))?
+        } else {
+            std::fs::read_to_string(Path::new(source))
+                .map_err(|e| Error::Config(format!("Failed to read catalog {}: {}", source, e)))?
+        };
+        Self::parse(&text)
+    }
+
+    /// Entries matching every word of `query`, best matches first: name
+    /// matches, then tag matches, then description matches. An empty query
+    /// lists the whole catalog.
+    pub fn search(&self, query: &str) -> Vec<&MarketplaceEntry> {
+        let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        let mut matches: Vec<(u32, &MarketplaceEntry)> = self
+            .servers
+            .iter()
+            .filter_map(|entry| {
+                words
+                    .iter()
+                    .map(|word| entry.score(word))
+                    .try_fold(0, |total, score| score.map(|score| total + score))
+                    .map(|score| (score, entry))
+            })
+            .collect();
+        matches.sort_by(|(a, x), (b, y)| b.cmp(a).then_with(|| x.name.cmp(&y.name)));
+        matches.into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    /// The entry called `name`.
+    pub fn get(&self, name: &str) -> Result<&MarketplaceEntry> {
+        self.servers.iter().find(|entry| entry.name == name).ok_or_else(|| {
+            Error::Config(format!(
+                "No server named '{}' in the catalog; try `only1mcp search {}`",
+                name, name
+            ))
+        })
+    }
+}
+
+impl MarketplaceEntry {
+    /// Display name.
+    pub fn title(&self) -> &str {
+        self.title.as_deref().unwrap_or(&self.name)
+    }
+
+    /// How well `word` (lowercase) matches, or `None` if it does not.
+    fn score(&self, word: &str) -> Option<u32> {
+        let name = self.name.to_lowercase();
+        if name == *word {
+            Some(100)
+        } else if name.contains(word) || self.title().to_lowercase().contains(word) {
+            Some(50)
+        } else if self.tags.iter().any(|tag| tag.to_lowercase().contains(word)) {
+            Some(20)
+        } else if self.description.to_lowercase().contains(word) {
+            Some(10)
+        } else {
+            None
+        }
+    }
+
+    /// The `only1mcp add` equivalent of installing this entry.
+    pub fn server_spec(&self, options: &InstallOptions) -> Result<ServerSpec> {
+        if options.args.len() < self.arg_hints.len() {
+            let missing = self.arg_hints[options.args.len()..].join(" ");
+            return Err(Error::Config(format!(
+                "{} needs more arguments: {} (pass each with --arg)",
+                self.name, missing
+            )));
+        }
+        let mut env: Vec<(String, String)> = self
+            .env
+            .iter()
+            .filter(|name| !options.env.iter().any(|(given, _)| given == *name))
+            .map(|name| (name.clone(), format!("${{{}}}", name)))
+            .collect();
+        env.extend(options.env.iter().cloned());
+
+        Ok(ServerSpec {
+            id: options.id.clone().unwrap_or_else(|| self.name.clone()),
+            name: self.title().to_string(),
+            transport: self.transport.clone(),
+            command: self.command.clone(),
+            args: self.args.iter().chain(&options.args).cloned().collect(),
+            env,
+            cwd: None,
+            url: self.url.clone(),
+            headers: Vec::new(),
+            image: self.image.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TransportConfig;
+
+    #[test]
+    fn test_builtin_catalog_installs() {
+        let marketplace = Marketplace::builtin();
+        assert!(!marketplace.servers.is_empty());
+        for entry in &marketplace.servers {
+            let options = InstallOptions {
+                args: entry.arg_hints.clone(),
+                ..Default::default()
+            };
+            let spec = entry.server_spec(&options).unwrap();
+            assert!(spec.transport_config().is_ok(), "{}", entry.name);
+        }
+    }
+
+    #[test]
+    fn test_search_ranking() {
+        let marketplace = Marketplace::builtin();
+        let names = |query: &str| -> Vec<String> {
+            marketplace.search(query).iter().map(|entry| entry.name.clone()).collect()
+        };
+
+        assert_eq!(names("git")[..2], ["git", "github"]);
+        assert!(names("database").contains(&"postgres".to_string()));
+        assert_eq!(names("sql database"), ["postgres", "sqlite"]);
+        assert!(names("no-such-server").is_empty());
+        assert_eq!(names("").len(), marketplace.servers.len());
+    }
+
+    #[test]
+    fn test_server_spec() {
+        let marketplace = Marketplace::builtin();
+
+        // Required arguments must be given
+        let filesystem = marketplace.get("filesystem").unwrap();
+        assert!(filesystem.server_spec(&InstallOptions::default()).is_err());
+
+        // Environment is referenced, not copied, unless given
+        let slack = marketplace.get("slack").unwrap();
+        let spec = slack
+            .server_spec(&InstallOptions {
+                id: Some("team-slack".to_string()),
+                env: vec![("SLACK_TEAM_ID".to_string(), "T123".to_string())],
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(spec.id, "team-slack");
+        let TransportConfig::Stdio { env, .. } = spec.transport_config().unwrap() else {
+            panic!("slack is a stdio server");
+        };
+        assert_eq!(env["SLACK_BOT_TOKEN"], "${SLACK_BOT_TOKEN}");
+        assert_eq!(env["SLACK_TEAM_ID"], "T123");
+
+        assert!(marketplace.get("unknown").is_err());
+    }
+}
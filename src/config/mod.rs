@@ -3,16 +3,33 @@
 //! Handles loading, validation, and hot-reloading of configuration files.
 
 use crate::error::{Error, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
+pub mod backup;
+pub mod client;
+pub mod diff;
+pub mod edit;
+pub mod gitops;
+pub mod import;
 pub mod loader;
+pub mod marketplace;
+pub mod overrides;
+pub mod redact;
 pub mod schema;
+pub mod secrets;
+pub mod selector;
 pub mod validation;
 
+pub use backup::BackupConfig;
+pub use diff::ConfigDiff;
+pub use gitops::GitOpsConfig;
 pub use loader::ConfigLoader;
+pub use selector::ToolSelector;
 
-#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, Default)]
 pub struct Config {
     #[serde(default)]
     pub server: ServerConfig,
@@ -27,9 +44,21 @@ pub struct Config {
     pub observability: ObservabilityConfig,
     #[serde(default)]
     pub tui: TuiConfig,
+    /// Backups written before each edit of the configuration file
+    #[serde(default)]
+    pub backups: BackupConfig,
+    /// Health and sticky sessions shared with other instances
+    #[serde(default)]
+    pub cluster: ClusterConfig,
+    /// Backends found at runtime instead of listed in `servers`
+    #[serde(default)]
+    pub discovery: DiscoveryConfig,
+    /// Configuration synced from a git repository
+    #[serde(default)]
+    pub gitops: GitOpsConfig,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
 pub struct ServerConfig {
     #[serde(default = "default_host")]
     pub host: String,
@@ -41,17 +70,222 @@ pub struct ServerConfig {
     pub max_connections: usize,
     #[serde(default)]
     pub tls: TlsConfig,
+    /// How long shutdown waits for in-flight requests before forcing exit
+    #[serde(default = "default_drain_timeout_seconds")]
+    pub drain_timeout_seconds: u64,
+    /// How long a binary upgrade (`SIGUSR2`) waits for the new process to
+    /// serve before giving up on it
+    #[serde(default = "default_upgrade_timeout_seconds")]
+    pub upgrade_timeout_seconds: u64,
+    /// Separate sockets for MCP, admin and metrics routes. When empty, a
+    /// single listener on `host:port` serves everything.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub listeners: Vec<ListenerConfig>,
+    /// Also accept connections on this Unix domain socket, serving every
+    /// route group (listener name `socket`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub listen_socket: Option<PathBuf>,
+}
+
+/// A socket serving a subset of the proxy's routes (`server.listeners`).
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq)]
+pub struct ListenerConfig {
+    /// Name used in logs
+    pub name: String,
+    /// `host:port` (`[::]:8080` for IPv6), or `unix:<path>` for a Unix
+    /// domain socket
+    pub bind: String,
+    /// Route groups served on this listener
+    #[serde(default = "default_listener_routes")]
+    pub routes: Vec<RouteGroup>,
+    #[serde(default)]
+    pub tls: TlsConfig,
+    /// Require an API key on every request (none when absent)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<ListenerAuthConfig>,
+    /// Accept IPv6 connections only on an IPv6 address; by default `[::]`
+    /// also accepts IPv4 (dual-stack)
+    #[serde(default)]
+    pub ipv6_only: bool,
+}
+
+/// `host:port`, with an IPv6 literal `host` in brackets (`[::1]:8080`).
+pub fn host_port(host: &str, port: u16) -> String {
+    match host.parse::<std::net::Ipv6Addr>() {
+        Ok(ip) => std::net::SocketAddr::from((ip, port)).to_string(),
+        Err(_) => format!("{}:{}", host, port),
+    }
+}
+
+/// Where a listener accepts connections.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListenAddress {
+    Tcp(std::net::SocketAddr),
+    Unix(PathBuf),
+}
+
+impl std::fmt::Display for ListenAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "{}", addr),
+            Self::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+impl ListenerConfig {
+    /// Parse `bind` into a TCP or Unix socket address.
+    pub fn address(&self) -> Result<ListenAddress> {
+        if let Some(path) = self.bind.strip_prefix("unix:") {
+            if path.is_empty() {
+                return Err(Error::Config(format!(
+                    "Listener {} has an empty socket path",
+                    self.name
+                )));
+            }
+            return Ok(ListenAddress::Unix(PathBuf::from(path)));
+        }
+        self.bind.parse().map(ListenAddress::Tcp).map_err(|e| {
+            Error::Config(format!(
+                "Listener {} has invalid address '{}': {}",
+                self.name, self.bind, e
+            ))
+        })
+    }
+
+    /// Whether this listener serves `group`.
+    pub fn serves(&self, group: RouteGroup) -> bool {
+        self.routes.contains(&group)
+    }
+}
+
+/// Groups of routes that can be placed on different listeners.
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum RouteGroup {
+    /// MCP JSON-RPC endpoints (`/`, `/mcp`, `/ws`) and `/health`
+    Mcp,
+    /// Management API under `/api/v1/admin`
+    Admin,
+    /// Prometheus metrics at `/metrics`
+    Metrics,
+}
+
+impl RouteGroup {
+    pub const ALL: [RouteGroup; 3] = [RouteGroup::Mcp, RouteGroup::Admin, RouteGroup::Metrics];
+}
+
+/// Client authentication for a listener.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Default)]
+pub struct ListenerAuthConfig {
+    /// Accepted keys, sent as `Authorization: Bearer <key>` or `X-API-Key`
+    #[serde(default)]
+    pub api_keys: Vec<ApiKeyConfig>,
+    /// Also accept HS256-signed JWT bearer tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jwt: Option<JwtAuthConfig>,
+}
+
+/// An accepted API key: either the bare key or the key with the roles it
+/// grants for `auth.policies`.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum ApiKeyConfig {
+    Key(String),
+    WithRoles {
+        key: String,
+        #[serde(default)]
+        roles: Vec<String>,
+    },
+}
+
+impl ApiKeyConfig {
+    /// The secret key.
+    pub fn key(&self) -> &str {
+        match self {
+            Self::Key(key) | Self::WithRoles { key, .. } => key,
+        }
+    }
+
+    /// Mutable access to the secret key, e.g. for redaction.
+    pub fn key_mut(&mut self) -> &mut String {
+        match self {
+            Self::Key(key) | Self::WithRoles { key, .. } => key,
+        }
+    }
+
+    /// Roles granted to callers presenting this key.
+    pub fn roles(&self) -> &[String] {
+        match self {
+            Self::Key(_) => &[],
+            Self::WithRoles { roles, .. } => roles,
+        }
+    }
+}
+
+impl From<&str> for ApiKeyConfig {
+    fn from(key: &str) -> Self {
+        Self::Key(key.to_string())
+    }
+}
+
+/// JWT bearer tokens accepted by a listener. The token's `sub` identifies
+/// the caller and its `roles` claim feeds `auth.policies`.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct JwtAuthConfig {
+    /// HMAC secret; prefer `secret_env` to keep it out of the file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    /// Environment variable holding the HMAC secret
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_env: Option<String>,
+    /// Required `iss` claim
+    #[serde(default = "default_jwt_issuer")]
+    pub issuer: String,
+    /// Required `aud` claim
+    #[serde(default = "default_jwt_audience")]
+    pub audience: String,
+}
+
+impl JwtAuthConfig {
+    /// The signing secret, read from `secret_env` when set.
+    pub fn secret(&self) -> Option<String> {
+        match &self.secret_env {
+            Some(var) => std::env::var(var).ok(),
+            None => self.secret.clone(),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, Default, PartialEq)]
 pub struct TlsConfig {
     #[serde(default)]
     pub enabled: bool,
     pub cert_path: Option<PathBuf>,
     pub key_path: Option<PathBuf>,
+    /// CA bundle that client certificates must chain to (mutual TLS)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_ca_path: Option<PathBuf>,
+    /// Whether clients must present a certificate when `client_ca_path` is set
+    #[serde(default)]
+    pub client_auth: ClientAuthMode,
+    /// Roles for client certificates, keyed by subject CN or SAN
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub client_roles: BTreeMap<String, Vec<String>>,
+}
+
+/// Client certificate requirement of a mutual TLS listener.
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ClientAuthMode {
+    /// Reject handshakes without a valid client certificate
+    #[default]
+    Required,
+    /// Verify a client certificate if one is presented
+    Optional,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
 pub struct McpServerConfig {
     pub id: String,
     pub name: String,
@@ -64,39 +298,840 @@ pub struct McpServerConfig {
     pub routing: RoutingConfig,
     #[serde(default = "default_weight")]
     pub weight: u32,
+    /// Per-request timeout for this backend, in milliseconds
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+    #[serde(default)]
+    pub retry: RetryConfig,
+    /// Operator tags, usable as selectors (`tag:<name>`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Free-form operator notes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Tags and notes for individual tools of this backend, by tool name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tool_metadata: BTreeMap<String, Metadata>,
+    /// Cost charged for calls to this backend's tools
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<CostConfig>,
+    /// When the backend's STDIO processes are started
+    #[serde(default)]
+    pub startup: StartupPolicy,
+    /// Whether crashed STDIO processes are started again
+    #[serde(default)]
+    pub restart: RestartConfig,
+    /// CPU and memory limits of the backend's STDIO processes
+    #[serde(default)]
+    pub resources: ResourceLimitsConfig,
+    /// Isolation of the backend's STDIO processes
+    #[serde(default)]
+    pub sandbox: SandboxConfig,
+    /// Environment of the backend's STDIO or Docker process
+    #[serde(default)]
+    pub env_policy: EnvPolicyConfig,
+    /// Shadow backend receiving a copy of this backend's tools/call traffic
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mirror: Option<MirrorConfig>,
+    /// Rewrites applied to requests to and responses from this backend
+    #[serde(default, skip_serializing_if = "TransformConfig::is_empty")]
+    pub transforms: TransformConfig,
+}
+
+/// Declarative rewrites of the traffic between the proxy and one backend
+/// (`servers[].transforms`), applied in
+/// [`transform`](crate::proxy::transform).
+///
+/// Tools are named as the backend advertises them.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize, PartialEq)]
+pub struct TransformConfig {
+    /// Headers added to requests to HTTP, SSE and Streamable HTTP backends,
+    /// replacing the transport's headers of the same name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub set_headers: BTreeMap<String, String>,
+    /// Header names (case-insensitive) removed from those requests
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove_headers: Vec<String>,
+    /// Tools exposed to clients under another name, by backend tool name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub rename_tools: BTreeMap<String, String>,
+    /// Arguments added to tools/call when the client does not pass them, by
+    /// tool name glob (`*`, `?`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub default_arguments: BTreeMap<String, serde_json::Map<String, serde_json::Value>>,
+    /// Dotted paths of fields removed from tools/call results, e.g. `_meta`
+    /// or `structuredContent.debug`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub strip_response_fields: Vec<String>,
+}
+
+impl TransformConfig {
+    /// Whether no rewrite is configured.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Copy of a backend's tools/call traffic sent to a shadow backend
+/// (`servers[].mirror`).
+///
+/// Mirrored calls are sent in the background once the call is routed. Their
+/// responses are discarded and their outcomes only counted, so the shadow
+/// never affects clients. A server that is a mirror target is reachable
+/// only through mirroring: it is left out of routing and aggregated lists.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct MirrorConfig {
+    /// Server ID of the shadow backend
+    pub server: String,
+    /// Tool name globs (`*`, `?`) to mirror; empty mirrors every tool
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+    /// Percentage of matching calls to mirror (default: 100)
+    #[serde(default = "default_mirror_percent")]
+    pub percent: u8,
+}
+
+fn default_mirror_percent() -> u8 {
+    100
+}
+
+impl MirrorConfig {
+    /// Whether calls to `tool` are mirrored.
+    pub fn mirrors_tool(&self, tool: &str) -> bool {
+        self.tools.is_empty()
+            || self
+                .tools
+                .iter()
+                .any(|pattern| crate::routing::tool_matcher::glob_match(pattern, tool))
+    }
+}
+
+/// When a STDIO backend's processes are started (`servers[].startup`).
+/// Other transports connect per request and ignore it.
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupPolicy {
+    /// Started and initialized before the proxy accepts requests
+    Eager,
+    /// Started by the first request that needs them
+    #[default]
+    Lazy,
+    /// Started in the background at proxy startup; requests arriving
+    /// earlier start them on demand
+    OnDemandWithPreinit,
+}
+
+impl StartupPolicy {
+    /// Name as written in the config file.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Eager => "eager",
+            Self::Lazy => "lazy",
+            Self::OnDemandWithPreinit => "on_demand_with_preinit",
+        }
+    }
+}
+
+/// Restart policy of a STDIO backend's processes (`servers[].restart`).
+///
+/// Restarts are delayed by `initial_backoff_ms`, doubling with every
+/// restart in the current window up to `max_backoff_ms`. A server whose
+/// processes exit more than `max_restarts` times within `window_seconds`
+/// is crash looping: it is marked unhealthy and left stopped until it is
+/// restarted from the Admin API or TUI.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct RestartConfig {
+    #[serde(default)]
+    pub policy: RestartPolicy,
+    /// Restarts allowed within `window_seconds`
+    #[serde(default = "default_max_restarts")]
+    pub max_restarts: u32,
+    #[serde(default = "default_restart_window_seconds")]
+    pub window_seconds: u64,
+    /// Delay before the first restart, in milliseconds
+    #[serde(default = "default_restart_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    /// Upper bound for any restart delay, in milliseconds
+    #[serde(default = "default_restart_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+}
+
+/// When an exited STDIO process is started again.
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RestartPolicy {
+    /// Only the next request starts it again
+    #[default]
+    Never,
+    /// Restarted when it exits with an error or is killed by a signal
+    OnFailure,
+    /// Restarted whenever it exits
+    Always,
+}
+
+impl RestartPolicy {
+    /// Name as written in the config file.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Never => "never",
+            Self::OnFailure => "on_failure",
+            Self::Always => "always",
+        }
+    }
+}
+
+impl RestartConfig {
+    /// Delay before a restart preceded by `recent` restarts in the window.
+    pub fn backoff_for(&self, recent: u32) -> std::time::Duration {
+        let delay = self.initial_backoff_ms.saturating_mul(1u64 << recent.min(32));
+        std::time::Duration::from_millis(delay.min(self.max_backoff_ms))
+    }
+
+    /// Period over which restarts are counted.
+    pub fn window(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.window_seconds)
+    }
+}
+
+/// Resource limits of a STDIO backend's processes (`servers[].resources`).
+///
+/// Every process is sampled every 10 seconds. One staying above
+/// `max_memory_mb` of resident memory or `max_cpu_percent` of one core
+/// for `violation_seconds` is killed and handled like a crash: it is
+/// restarted according to `restart`, or by the next request. Sandboxed
+/// processes are also started with `max_memory_mb` (512 when unset) as
+/// their address space limit.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct ResourceLimitsConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_memory_mb: Option<u64>,
+    /// Percent of one core, so 200 allows two busy cores
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cpu_percent: Option<u32>,
+    /// How long a limit may be exceeded before the process is killed
+    #[serde(default = "default_violation_seconds")]
+    pub violation_seconds: u64,
+}
+
+impl ResourceLimitsConfig {
+    /// Period a process may stay over its limits.
+    pub fn violation_window(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.violation_seconds)
+    }
+}
+
+/// Isolation of a STDIO backend's processes (`servers[].sandbox`).
+///
+/// `rlimit` only drops root and applies resource limits. `bubblewrap` and
+/// `firejail` also run the process through that tool with the filesystem
+/// and network restrictions below, and `seccomp` installs the compiled BPF
+/// filter in `seccomp_profile`. Docker backends are isolated by their
+/// container and ignore this setting.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct SandboxConfig {
+    #[serde(default)]
+    pub strategy: SandboxStrategy,
+    /// Paths the process may read (bubblewrap, firejail)
+    #[serde(default = "default_sandbox_read_only_paths")]
+    pub read_only_paths: Vec<String>,
+    /// Paths the process may write (bubblewrap, firejail)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub writable_paths: Vec<String>,
+    /// Whether the process may use the network (bubblewrap, firejail)
+    #[serde(default = "default_true")]
+    pub network: bool,
+    /// Raw seccomp BPF program, as exported by `seccomp_export_bpf`
+    /// (seccomp)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seccomp_profile: Option<String>,
+}
+
+/// How a STDIO backend's processes are isolated.
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SandboxStrategy {
+    /// Run with the proxy's privileges
+    None,
+    /// Drop root and apply resource limits
+    #[default]
+    Rlimit,
+    /// Run through `bwrap` in fresh namespaces
+    Bubblewrap,
+    /// Run through `firejail`
+    Firejail,
+    /// Install a seccomp syscall filter
+    Seccomp,
+}
+
+impl SandboxStrategy {
+    /// Name as written in the config file.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Rlimit => "rlimit",
+            Self::Bubblewrap => "bubblewrap",
+            Self::Firejail => "firejail",
+            Self::Seccomp => "seccomp",
+        }
+    }
+}
+
+/// Environment of a STDIO or Docker backend's process (`servers[].env_policy`).
+///
+/// By default the process inherits the proxy's whole environment plus the
+/// transport's `env`. Values of `secrets` are secret references resolved
+/// like `env` values (`${VAR}`, `file://`, `keyring://`); they and the
+/// `env` variables named in `redact` are replaced by `[REDACTED]` wherever
+/// the proxy logs or reports backend output and errors.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct EnvPolicyConfig {
+    #[serde(default)]
+    pub inherit: EnvInherit,
+    /// Variables of the proxy passed on with `inherit: allowlist`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowlist: Vec<String>,
+    /// Variables set from secret references
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub secrets: std::collections::HashMap<String, String>,
+    /// Names of `env` variables whose values are redacted too
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redact: Vec<String>,
+}
+
+/// Which of the proxy's environment variables a backend process inherits.
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum EnvInherit {
+    /// All of them
+    #[default]
+    All,
+    /// None; the process only gets `env` and `secrets`
+    None,
+    /// Those named in `allowlist`
+    Allowlist,
+}
+
+impl EnvPolicyConfig {
+    /// Names of the proxy's variables the process inherits, `None` for all.
+    pub fn inherited(&self) -> Option<&[String]> {
+        match self.inherit {
+            EnvInherit::All => None,
+            EnvInherit::None => Some(&[]),
+            EnvInherit::Allowlist => Some(&self.allowlist),
+        }
+    }
+}
+
+/// Cost model of a backend (`servers[].cost`), in dollars.
+///
+/// Every tools/call is charged `per_call` plus `per_1k_tokens` for each
+/// thousand estimated tokens of the call's arguments and result. An entry
+/// in `tools` replaces both rates for that tool.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, JsonSchema, Serialize)]
+pub struct CostConfig {
+    #[serde(flatten)]
+    pub rates: CostRates,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tools: BTreeMap<String, CostRates>,
+}
+
+/// Charges for one tools/call, in dollars.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, JsonSchema, Serialize)]
+pub struct CostRates {
+    #[serde(default)]
+    pub per_call: f64,
+    #[serde(default)]
+    pub per_1k_tokens: f64,
+}
+
+impl CostConfig {
+    /// Rates charged for calls to `tool`.
+    pub fn rates_for(&self, tool: &str) -> CostRates {
+        self.tools.get(tool).copied().unwrap_or(self.rates)
+    }
+}
+
+impl CostRates {
+    /// Cost of one call that moved `tokens` estimated tokens.
+    pub fn cost(&self, tokens: u64) -> f64 {
+        self.per_call + self.per_1k_tokens * tokens as f64 / 1000.0
+    }
+}
+
+/// Operator-assigned tags and notes for a server or tool.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+pub struct Metadata {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+impl Metadata {
+    /// Whether neither tags nor notes are set.
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty() && self.notes.is_none()
+    }
+}
+
+impl McpServerConfig {
+    /// Tags and notes of this server itself.
+    pub fn metadata(&self) -> Metadata {
+        Metadata {
+            tags: self.tags.clone(),
+            notes: self.notes.clone(),
+        }
+    }
+
+    /// Tags and notes configured for one of this backend's tools.
+    pub fn tool_metadata(&self, tool: &str) -> Option<&Metadata> {
+        self.tool_metadata.get(tool)
+    }
+
+    /// Effective tags of a tool: the server's tags followed by the tool's own.
+    pub fn tool_tags<'a>(&'a self, tool: &str) -> impl Iterator<Item = &'a str> + 'a {
+        let own = self.tool_metadata.get(tool).map(|m| m.tags.as_slice()).unwrap_or_default();
+        self.tags.iter().chain(own).map(String::as_str)
+    }
+
+    /// Effective request timeout in milliseconds.
+    ///
+    /// A transport-level `timeout_ms` (streamable HTTP only) takes precedence
+    /// over the server-level setting.
+    pub fn request_timeout_ms(&self) -> u64 {
+        match &self.transport {
+            TransportConfig::StreamableHttp {
+                timeout_ms: Some(timeout_ms),
+                ..
+            } => *timeout_ms,
+            _ => self.timeout_ms,
+        }
+    }
+
+    /// Effective request timeout.
+    pub fn request_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.request_timeout_ms())
+    }
+
+    /// Command line of the backend's local process, with the `env_policy`
+    /// secrets added to its environment (and forwarded into a container).
+    pub fn process_command(&self) -> Option<ProcessCommand> {
+        let mut transport = self.transport.clone();
+        if let TransportConfig::Stdio { env, .. } | TransportConfig::Docker { env, .. } =
+            &mut transport
+        {
+            env.extend(self.env_policy.secrets.clone());
+        }
+        transport.process_command()
+    }
+
+    /// Values hidden from logs and the Admin API: the `env_policy` secrets
+    /// and the `env` values it names in `redact`.
+    pub fn redacted_env_values(&self) -> Vec<String> {
+        let mut values: Vec<String> = self.env_policy.secrets.values().cloned().collect();
+        if let TransportConfig::Stdio { env, .. } | TransportConfig::Docker { env, .. } =
+            &self.transport
+        {
+            values.extend(self.env_policy.redact.iter().filter_map(|name| env.get(name)).cloned());
+        }
+        values.retain(|value| !value.is_empty());
+        values
+    }
+}
+
+/// Retry policy for requests to a single backend.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct RetryConfig {
+    /// Total attempts including the first one (1 disables retries)
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    #[serde(default)]
+    pub backoff: BackoffStrategy,
+    /// Delay before the first retry, in milliseconds
+    #[serde(default = "default_retry_base_delay_ms")]
+    pub base_delay_ms: u64,
+    /// Upper bound for any single retry delay, in milliseconds
+    #[serde(default = "default_retry_max_delay_ms")]
+    pub max_delay_ms: u64,
+}
+
+/// How the delay grows between retry attempts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackoffStrategy {
+    /// Always wait `base_delay_ms`
+    Fixed,
+    /// Wait `base_delay_ms * retry`
+    #[default]
+    Linear,
+    /// Wait `base_delay_ms * 2^(retry - 1)`
+    Exponential,
+}
+
+impl RetryConfig {
+    /// Delay before retry number `retry` (1-based), capped at `max_delay_ms`.
+    pub fn delay_for(&self, retry: u32) -> std::time::Duration {
+        let retry = retry.max(1);
+        let delay = match self.backoff {
+            BackoffStrategy::Fixed => self.base_delay_ms,
+            BackoffStrategy::Linear => self.base_delay_ms.saturating_mul(retry as u64),
+            BackoffStrategy::Exponential => {
+                self.base_delay_ms.saturating_mul(1u64 << (retry - 1).min(32))
+            },
+        };
+        std::time::Duration::from_millis(delay.min(self.max_delay_ms))
+    }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum TransportConfig {
     Stdio {
         command: String,
-        #[serde(default)]
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
         args: Vec<String>,
-        #[serde(default)]
+        #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
         env: std::collections::HashMap<String, String>,
+        /// Working directory for the child process (inherits the proxy's if unset)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cwd: Option<String>,
     },
     Http {
         url: String,
-        #[serde(default)]
+        #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
         headers: std::collections::HashMap<String, String>,
+        /// Authenticate with OAuth2 client credentials
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        oauth: Option<BackendOAuthConfig>,
+        /// Client certificate and trust roots for HTTPS backends
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tls: Option<BackendTlsConfig>,
+        /// Sign every request with an HMAC of its body
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        signing: Option<RequestSigningConfig>,
+        /// Outbound proxy for this backend, replacing `proxy.outbound_proxy`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        proxy: Option<OutboundProxyConfig>,
     },
     Sse {
         url: String,
-        #[serde(default)]
+        #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
         headers: std::collections::HashMap<String, String>,
+        /// Outbound proxy for this backend, replacing `proxy.outbound_proxy`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        proxy: Option<OutboundProxyConfig>,
     },
     #[serde(rename = "streamable_http")]
     StreamableHttp {
         url: String,
-        #[serde(default)]
+        #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
         headers: std::collections::HashMap<String, String>,
-        #[serde(default = "default_timeout_ms")]
-        timeout_ms: u64,
+        /// Overrides the server-level `timeout_ms` when set
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_ms: Option<u64>,
+        /// Authenticate with OAuth2 client credentials
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        oauth: Option<BackendOAuthConfig>,
+        /// Client certificate and trust roots for HTTPS backends
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tls: Option<BackendTlsConfig>,
+        /// Sign every request with an HMAC of its body
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        signing: Option<RequestSigningConfig>,
+        /// Outbound proxy for this backend, replacing `proxy.outbound_proxy`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        proxy: Option<OutboundProxyConfig>,
+    },
+    /// MCP-over-gRPC: each JSON-RPC message is the payload of a unary
+    /// `only1mcp.v1.McpService/Call`
+    Grpc {
+        /// `http://host:port`, or `https://` for TLS
+        endpoint: String,
+        /// Metadata sent with every call
+        #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+        metadata: std::collections::HashMap<String, String>,
+        /// Client certificate and trust roots for `https` endpoints
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tls: Option<BackendTlsConfig>,
+    },
+    /// Line-delimited JSON-RPC over a Unix domain socket, for backends
+    /// already running on this host
+    Unix {
+        /// Path of the socket the backend listens on
+        path: PathBuf,
+    },
+    /// MCP server shipped as a container image, run with `docker run -i`
+    /// and managed like a STDIO process
+    Docker {
+        image: String,
+        /// Arguments after the image, replacing its default command
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
+        /// Overrides the image's entrypoint
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        entrypoint: Option<String>,
+        /// Environment variables set in the container
+        #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+        env: std::collections::HashMap<String, String>,
+        /// Bind mounts, `host_path:container_path[:ro]`
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        mounts: Vec<String>,
+        /// Memory limit (`--memory`), e.g. `512m`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        memory: Option<String>,
+        /// CPU limit (`--cpus`), e.g. `1.5`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cpus: Option<f64>,
+        /// Network to attach to (`--network`), e.g. `none`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        network: Option<String>,
     },
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+/// Command line of the local process behind a STDIO or Docker backend.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessCommand {
+    pub command: String,
+    pub args: Vec<String>,
+    pub env: std::collections::HashMap<String, String>,
+    pub cwd: Option<String>,
+    /// Apply the STDIO sandbox and resource limits (containers bring
+    /// their own)
+    pub sandbox: bool,
+}
+
+impl TransportConfig {
+    /// OAuth2 client-credentials settings, for transports that support them.
+    pub fn oauth(&self) -> Option<&BackendOAuthConfig> {
+        match self {
+            TransportConfig::Http { oauth, .. } | TransportConfig::StreamableHttp { oauth, .. } => {
+                oauth.as_ref()
+            },
+            _ => None,
+        }
+    }
+
+    /// Outbound proxy settings of this backend, for transports that
+    /// support them. See [`Config::outbound_proxy`] for the effective ones.
+    pub fn proxy(&self) -> Option<&OutboundProxyConfig> {
+        match self {
+            TransportConfig::Http { proxy, .. }
+            | TransportConfig::Sse { proxy, .. }
+            | TransportConfig::StreamableHttp { proxy, .. } => proxy.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// HMAC request signing settings, for transports that support them.
+    pub fn signing(&self) -> Option<&RequestSigningConfig> {
+        match self {
+            TransportConfig::Http { signing, .. }
+            | TransportConfig::StreamableHttp { signing, .. } => signing.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Whether the backend is a local process managed by the STDIO
+    /// transport.
+    pub fn is_process(&self) -> bool {
+        matches!(
+            self,
+            TransportConfig::Stdio { .. } | TransportConfig::Docker { .. }
+        )
+    }
+
+    /// The process to run for STDIO and Docker backends.
+    ///
+    /// Container environment variables are passed by name (`-e KEY`) and set
+    /// on the `docker` process, so their values stay off the command line.
+    pub fn process_command(&self) -> Option<ProcessCommand> {
+        match self {
+            TransportConfig::Stdio {
+                command,
+                args,
+                env,
+                cwd,
+            } => Some(ProcessCommand {
+                command: command.clone(),
+                args: args.clone(),
+                env: env.clone(),
+                cwd: cwd.clone(),
+                sandbox: true,
+            }),
+            TransportConfig::Docker {
+                image,
+                args,
+                entrypoint,
+                env,
+                mounts,
+                memory,
+                cpus,
+                network,
+            } => {
+                let mut run = ["run", "-i", "--rm", "--init"].map(String::from).to_vec();
+                let mut names: Vec<_> = env.keys().collect();
+                names.sort();
+                for name in names {
+                    run.extend(["-e".to_string(), name.clone()]);
+                }
+                for mount in mounts {
+                    run.extend(["-v".to_string(), mount.clone()]);
+                }
+                let options = [
+                    ("--entrypoint", entrypoint.clone()),
+                    ("--memory", memory.clone()),
+                    ("--cpus", cpus.map(|cpus| cpus.to_string())),
+                    ("--network", network.clone()),
+                ];
+                for (flag, value) in options {
+                    if let Some(value) = value {
+                        run.extend([flag.to_string(), value]);
+                    }
+                }
+                run.push(image.clone());
+                run.extend(args.iter().cloned());
+
+                Some(ProcessCommand {
+                    command: "docker".to_string(),
+                    args: run,
+                    env: env.clone(),
+                    cwd: None,
+                    sandbox: false,
+                })
+            },
+            _ => None,
+        }
+    }
+
+    /// Backend TLS settings, for transports that support them.
+    pub fn tls(&self) -> Option<&BackendTlsConfig> {
+        match self {
+            TransportConfig::Http { tls, .. }
+            | TransportConfig::StreamableHttp { tls, .. }
+            | TransportConfig::Grpc { tls, .. } => tls.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// TLS settings for connecting to an HTTPS backend.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize, PartialEq, Eq, Hash)]
+pub struct BackendTlsConfig {
+    /// PEM certificate chain presented to the backend (mutual TLS)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_cert_path: Option<PathBuf>,
+    /// PEM private key for `client_cert_path`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_key_path: Option<PathBuf>,
+    /// Extra CA bundle trusted for the backend's certificate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_path: Option<PathBuf>,
+}
+
+/// OAuth2 client-credentials grant used to obtain bearer tokens for a
+/// backend. Tokens are fetched on first use and refreshed before they expire.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct BackendOAuthConfig {
+    /// Token endpoint of the authorization server
+    pub token_url: String,
+    pub client_id: String,
+    /// Client secret; prefer `client_secret_env` to keep it out of the file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+    /// Environment variable holding the client secret
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_secret_env: Option<String>,
+    /// Scopes requested with each token
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<String>,
+}
+
+impl BackendOAuthConfig {
+    /// The client secret, read from `client_secret_env` when set.
+    pub fn client_secret(&self) -> Option<String> {
+        match &self.client_secret_env {
+            Some(var) => std::env::var(var).ok(),
+            None => self.client_secret.clone(),
+        }
+    }
+}
+
+/// HMAC signature sent with every request to a backend
+/// (`transport.signing`); see [`crate::transport::signing`]
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct RequestSigningConfig {
+    #[serde(default)]
+    pub algorithm: SigningAlgorithm,
+    /// Shared secret; may be a secret reference (`${VAR}`, `file://`,
+    /// `keyring://`)
+    pub secret: String,
+    /// Header carrying the signature (default: `X-Signature`)
+    #[serde(default = "default_signature_header")]
+    pub header: String,
+    /// Text sent before the signature, e.g. `sha256=`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub prefix: String,
+    #[serde(default)]
+    pub encoding: SignatureEncoding,
+    /// Header carrying the Unix time of signing; when set, the signed
+    /// message is `<timestamp>.<body>`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_header: Option<String>,
+}
+
+/// HMAC hash function of request signatures.
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SigningAlgorithm {
+    /// For backends that only accept SHA-1 signatures
+    HmacSha1,
+    #[default]
+    HmacSha256,
+    HmacSha384,
+    HmacSha512,
+}
+
+/// How a request signature is written in its header.
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureEncoding {
+    /// Lowercase hexadecimal
+    #[default]
+    Hex,
+    /// Standard base64 with padding
+    Base64,
+}
+
+/// Proxy that connections to HTTP, SSE and Streamable HTTP backends go
+/// through (`proxy.outbound_proxy`, or `transport.proxy` per server); see
+/// [`crate::transport::outbound_proxy`]
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize, PartialEq, Eq, Hash)]
+pub struct OutboundProxyConfig {
+    /// Proxy for `http://` backends, e.g. `http://proxy:3128`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_proxy: Option<String>,
+    /// Proxy for `https://` backends, reached with CONNECT when it is an
+    /// HTTP proxy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub https_proxy: Option<String>,
+    /// Proxy for backends of either scheme not covered above, e.g.
+    /// `socks5://proxy:1080`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub all_proxy: Option<String>,
+    /// Hosts reached directly: names (`example.com` also matches its
+    /// subdomains), IP addresses, CIDR ranges, or `*`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub no_proxy: Vec<String>,
+    /// User name sent to the proxies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// Password sent to the proxies; may be a secret reference (`${VAR}`,
+    /// `file://`, `keyring://`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
 pub struct HealthCheckConfig {
     #[serde(default = "default_true")]
     pub enabled: bool,
@@ -112,24 +1147,124 @@ pub struct HealthCheckConfig {
     pub path: String,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, Default)]
 pub struct RoutingConfig {
+    /// Tool name globs routed to this server (`*`, `?`); prefix with `!` to
+    /// deny. Empty means the server accepts any tool.
     #[serde(default)]
     pub tools: Vec<String>,
+    /// Servers with a higher priority are preferred; lower tiers only take
+    /// traffic while no higher-priority server is healthy
     #[serde(default = "default_priority")]
     pub priority: u32,
     #[serde(default = "default_weight")]
     pub weight: u32,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
 pub struct RoutingAlgorithmConfig {
     #[serde(default = "default_algorithm")]
     pub algorithm: String,
     #[serde(default = "default_virtual_nodes")]
     pub virtual_nodes: usize,
+    /// Pin each client to the backend that first served it
     #[serde(default)]
     pub sticky_sessions: bool,
+    /// Seconds an unused sticky session is kept (default: 3600)
+    #[serde(default = "default_session_ttl_seconds")]
+    pub session_ttl_seconds: u64,
+    /// Request header identifying the client for sticky sessions; the
+    /// authenticated API key is used when it is absent
+    #[serde(default = "default_client_id_header")]
+    pub client_id_header: String,
+    /// Primary servers with warm standbys taking over their traffic
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failover_groups: Vec<FailoverGroupConfig>,
+    /// Versions of one logical service sharing its traffic by weight
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub traffic_splits: Vec<TrafficSplitConfig>,
+    /// Dispatch over other Only1MCP instances, sharding clients between them
+    #[serde(default)]
+    pub front_door: FrontDoorConfig,
+}
+
+/// Front-door mode (`proxy.routing.front_door`): this proxy acts as an L7
+/// dispatcher over other Only1MCP instances, configured as `http` servers
+/// pointing at their `/mcp` endpoints.
+///
+/// Among the instances serving a tool, each client is placed on the
+/// consistent hash ring by its client id instead of the configured
+/// algorithm, so it keeps talking to one instance while instances come and
+/// go. Instances can host different backends to partition a catalog too
+/// large for one proxy.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct FrontDoorConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Server IDs of the downstream Only1MCP instances
+    #[serde(default)]
+    pub instances: Vec<String>,
+}
+
+impl FrontDoorConfig {
+    /// Whether `server_id` is a downstream instance of an enabled front door.
+    pub fn is_instance(&self, server_id: &str) -> bool {
+        self.enabled && self.instances.iter().any(|id| id == server_id)
+    }
+}
+
+/// A primary server and the standbys exposing the same tools
+/// (`proxy.routing.failover_groups[]`).
+///
+/// Standbys receive no traffic while the primary is available. When it is
+/// not, the first available standby, in listed order, takes over until the
+/// primary recovers.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct FailoverGroupConfig {
+    pub name: String,
+    /// Server ID of the primary
+    pub primary: String,
+    /// Server IDs of the standbys, in order of preference
+    pub standbys: Vec<String>,
+}
+
+impl FailoverGroupConfig {
+    /// Primary followed by the standbys, in order of preference.
+    pub fn members(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.primary).chain(&self.standbys)
+    }
+}
+
+/// Servers running different versions of one logical service
+/// (`proxy.routing.traffic_splits[]`), e.g. a stable release and a canary.
+///
+/// Each request for the service goes to one variant, picked at random in
+/// proportion to the variants' weights. Unavailable variants are skipped and
+/// their share goes to the others.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct TrafficSplitConfig {
+    /// Logical service name, used as the `service` metric label
+    pub service: String,
+    pub variants: Vec<SplitVariantConfig>,
+}
+
+/// One variant of a traffic split.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct SplitVariantConfig {
+    /// Server ID of the variant
+    pub server: String,
+    /// Share of the service's traffic, relative to the other variants
+    pub weight: u32,
+}
+
+impl TrafficSplitConfig {
+    /// Weight of `server_id` in this split, if it is one of its variants.
+    pub fn weight_of(&self, server_id: &str) -> Option<u32> {
+        self.variants
+            .iter()
+            .find(|variant| variant.server == server_id)
+            .map(|variant| variant.weight)
+    }
 }
 
 impl Default for RoutingAlgorithmConfig {
@@ -138,11 +1273,23 @@ impl Default for RoutingAlgorithmConfig {
             algorithm: default_algorithm(),
             virtual_nodes: default_virtual_nodes(),
             sticky_sessions: false,
+            session_ttl_seconds: default_session_ttl_seconds(),
+            client_id_header: default_client_id_header(),
+            failover_groups: Vec::new(),
+            traffic_splits: Vec::new(),
+            front_door: FrontDoorConfig::default(),
         }
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+impl RoutingAlgorithmConfig {
+    /// How long an unused sticky session is kept.
+    pub fn session_ttl(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.session_ttl_seconds)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, Default)]
 pub struct ProxyConfig {
     #[serde(default)]
     pub load_balancer: LoadBalancerConfig,
@@ -150,33 +1297,456 @@ pub struct ProxyConfig {
     pub connection_pool: ConnectionPoolConfig,
     #[serde(default)]
     pub routing: RoutingAlgorithmConfig,
+    #[serde(default)]
+    pub limits: LimitsConfig,
+    #[serde(default)]
+    pub streaming: StreamingConfig,
+    #[serde(default)]
+    pub admission: AdmissionConfig,
+    #[serde(default)]
+    pub chaos: ChaosConfig,
+    #[serde(default)]
+    pub initialization: InitializationConfig,
+    #[serde(default)]
+    pub npx: NpxConfig,
+    #[serde(default)]
+    pub scripts: ScriptsConfig,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub composite_tools: Vec<CompositeToolConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_aliases: Vec<ToolAliasConfig>,
+    #[serde(default)]
+    pub argument_validation: ArgumentValidationConfig,
+    #[serde(default)]
+    pub sanitization: SanitizationConfig,
+    #[serde(default)]
+    pub redaction: RedactionConfig,
+    /// Proxy for connections to HTTP, SSE and Streamable HTTP backends,
+    /// unless a server sets its own `transport.proxy`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outbound_proxy: Option<OutboundProxyConfig>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct LoadBalancerConfig {
-    #[serde(default = "default_algorithm")]
-    pub algorithm: String,
-    #[serde(default = "default_virtual_nodes")]
+/// Redaction of personal data and secrets in tools/call arguments and
+/// results (`proxy.redaction`); see [`crate::proxy::redact`]
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct RedactionConfig {
+    /// Redact tools/call arguments and results (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Built-in patterns applied (default: all)
+    #[serde(default = "default_builtin_patterns")]
+    pub builtin: Vec<BuiltinPattern>,
+    /// Named regular expressions applied after the built-in patterns
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom: Vec<RedactionPatternConfig>,
+    /// Redact arguments before they are sent to the backend (default: true)
+    #[serde(default = "default_true")]
+    pub arguments: bool,
+    /// Redact results before they are returned (default: true)
+    #[serde(default = "default_true")]
+    pub results: bool,
+    /// Tool name globs (`*`, `?`) whose calls are not redacted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skip_tools: Vec<String>,
+}
+
+/// A built-in redaction pattern.
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BuiltinPattern {
+    /// Email addresses
+    Email,
+    /// API keys of well-known services
+    ApiKey,
+    /// AWS access key IDs and secret access keys
+    AwsCredentials,
+    /// Credit card numbers passing the Luhn check
+    CreditCard,
+}
+
+impl BuiltinPattern {
+    /// Name as written in the config file.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Email => "email",
+            Self::ApiKey => "api_key",
+            Self::AwsCredentials => "aws_credentials",
+            Self::CreditCard => "credit_card",
+        }
+    }
+}
+
+/// A custom redaction pattern (`proxy.redaction.custom`).
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct RedactionPatternConfig {
+    /// Name shown in `[REDACTED:<name>]` and in metrics
+    pub name: String,
+    /// Regular expression whose matches are redacted
+    pub regex: String,
+}
+
+/// Cleanup of tools/call results before they reach clients
+/// (`proxy.sanitization`); see [`crate::proxy::sanitize`]
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct SanitizationConfig {
+    /// Sanitize tools/call results (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Remove ANSI escape sequences and other control characters from
+    /// strings (default: true)
+    #[serde(default = "default_true")]
+    pub strip_ansi: bool,
+    /// Deepest nesting of arrays and objects kept; deeper values are
+    /// replaced by a notice (default: 32)
+    #[serde(default = "default_sanitize_max_depth")]
+    pub max_depth: usize,
+    /// MIME type globs (`*`, `?`) of images, audio and binary resources
+    /// passed through; others, and payloads that are not base64, are
+    /// replaced by a notice (default: `image/*`, `audio/*`)
+    #[serde(default = "default_binary_mime_types")]
+    pub binary_mime_types: Vec<String>,
+    /// Fail results whose `structuredContent` does not match the tool's
+    /// `outputSchema` (default: false)
+    #[serde(default)]
+    pub validate_output: bool,
+}
+
+/// Checking tools/call arguments against the called tool's `inputSchema`
+/// before the backend is called (`proxy.argument_validation`)
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct ArgumentValidationConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Tool name globs (`*`, `?`) whose arguments are not checked
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skip_tools: Vec<String>,
+}
+
+/// One name for equivalent tools of several backends (`proxy.tool_aliases`);
+/// see [`crate::proxy::aliases`]
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct ToolAliasConfig {
+    /// Name the tool is listed and called under
+    pub name: String,
+    /// Backend tools called under the alias, in order of preference
+    pub targets: Vec<AliasTargetConfig>,
+}
+
+/// A backend tool an alias stands for.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct AliasTargetConfig {
+    /// Server ID of the backend
+    pub server: String,
+    /// Tool name as the server lists it
+    pub tool: String,
+}
+
+/// A tool served by the proxy itself that calls backend tools one after
+/// another (`proxy.composite_tools`); see [`crate::proxy::composite`] for
+/// the templates
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq)]
+pub struct CompositeToolConfig {
+    /// Name the tool is listed and called under, hiding a backend tool of
+    /// the same name
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// JSON Schema of the tool's arguments (default: any object)
+    #[serde(default = "default_composite_input_schema")]
+    pub input_schema: serde_json::Value,
+    /// Backend tool calls, made in order
+    pub steps: Vec<CompositeStepConfig>,
+    /// Template of the tool's result (default: the last step's result)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<serde_json::Value>,
+}
+
+/// One backend tool call of a composite tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq)]
+pub struct CompositeStepConfig {
+    /// Name later steps and the output refer to this step's result by
+    pub id: String,
+    /// Backend tool called
+    pub tool: String,
+    /// Template of the call's arguments
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub arguments: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Rhai scripts run on every tools/call (`proxy.scripts`); see
+/// [`crate::proxy::scripts`] for what each hook can do
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct ScriptsConfig {
+    /// Script run before routing, which may change the arguments or reject
+    /// the call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_request: Option<PathBuf>,
+    /// Script run after routing, which may pick another server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_route: Option<PathBuf>,
+    /// Script run on the backend's result, which may change it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_response: Option<PathBuf>,
+    /// Operations a script may run before it is stopped (default: 100000)
+    #[serde(default = "default_script_max_operations")]
+    pub max_operations: u64,
+}
+
+/// How STDIO servers launched with `npx` are run (`proxy.npx`)
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct NpxConfig {
+    /// Run an installed copy of the package with `node` instead of going
+    /// through npx (default: true)
+    #[serde(default = "default_true")]
+    pub resolve: bool,
+    /// Reject npx servers whose package is not pinned as `package@version`
+    /// (default: false)
+    #[serde(default)]
+    pub require_pinned: bool,
+    /// Install packages found nowhere into `install_dir` (default: false)
+    #[serde(default)]
+    pub auto_install: bool,
+    /// Directory of packages installed by the proxy (default: the user
+    /// cache directory's `only1mcp/npx`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub install_dir: Option<PathBuf>,
+}
+
+/// How backends are initialized when the proxy starts
+/// (`proxy.initialization`)
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct InitializationConfig {
+    /// Backends initialized at once (default: 8)
+    #[serde(default = "default_initialization_max_parallel")]
+    pub max_parallel: usize,
+    /// Milliseconds a backend may take to start and list its tools before
+    /// it is reported as failed (default: 30000)
+    #[serde(default = "default_initialization_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+impl InitializationConfig {
+    /// How long one backend may take to initialize.
+    pub fn timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.timeout_ms)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct LoadBalancerConfig {
+    #[serde(default = "default_algorithm")]
+    pub algorithm: String,
+    #[serde(default = "default_virtual_nodes")]
     pub virtual_nodes: usize,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
 pub struct ConnectionPoolConfig {
     #[serde(default = "default_max_per_backend")]
     pub max_per_backend: usize,
     #[serde(default = "default_min_idle")]
     pub min_idle: usize,
+    /// Processes started per STDIO backend; requests are spread across them
+    /// round-robin (default: 1)
+    #[serde(default = "default_stdio_processes")]
+    pub stdio_processes_per_server: usize,
+    /// Seconds a STDIO process may sit idle before it is stopped; it is
+    /// started again on the next request (default: 0, never stopped)
+    #[serde(default)]
+    pub stdio_idle_timeout_seconds: u64,
+    /// Idle keep-alive connections kept per HTTP and Streamable HTTP
+    /// backend host (default: 10)
+    #[serde(default = "default_max_idle_per_host")]
+    pub max_idle_per_host: usize,
+    /// Seconds an idle backend connection is kept open (default: 90, 0
+    /// keeps it until the backend closes it)
+    #[serde(default = "default_pool_idle_timeout")]
+    pub idle_timeout_seconds: u64,
+    /// Seconds between HTTP/2 keep-alive pings to backends, also sent
+    /// while idle (default: 0, no pings)
+    #[serde(default)]
+    pub http2_keep_alive_seconds: u64,
+    /// Connect to HTTP and Streamable HTTP backends at startup so the
+    /// first request does not pay for connection setup (default: true)
+    #[serde(default = "default_true")]
+    pub prewarm: bool,
+}
+
+impl ConnectionPoolConfig {
+    /// How long an idle backend connection is kept, `None` for no limit.
+    pub fn idle_timeout(&self) -> Option<std::time::Duration> {
+        Some(self.idle_timeout_seconds)
+            .filter(|&seconds| seconds > 0)
+            .map(std::time::Duration::from_secs)
+    }
+
+    /// Interval of HTTP/2 keep-alive pings, `None` when disabled.
+    pub fn http2_keep_alive(&self) -> Option<std::time::Duration> {
+        Some(self.http2_keep_alive_seconds)
+            .filter(|&seconds| seconds > 0)
+            .map(std::time::Duration::from_secs)
+    }
+}
+
+/// Size limits that protect the proxy from oversized payloads
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct LimitsConfig {
+    /// Largest JSON-RPC request body accepted from clients (default: 2 MiB)
+    #[serde(default = "default_max_request_bytes")]
+    pub max_request_bytes: usize,
+    /// Largest response body read from an HTTP, SSE, Streamable HTTP or
+    /// gRPC backend, and longest line read from a Unix socket backend
+    /// (default: 32 MiB)
+    #[serde(default = "default_max_response_bytes")]
+    pub max_response_bytes: usize,
+    /// Longest line read from a STDIO backend's stdout (default: 16 MiB)
+    #[serde(default = "default_max_stdio_line_bytes")]
+    pub max_stdio_line_bytes: usize,
+}
+
+/// Streaming passthrough of large tool results from HTTP and Streamable
+/// HTTP backends
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct StreamingConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Results at least this large, or of unknown length, are streamed to
+    /// the client instead of being parsed (default: 1 MiB)
+    #[serde(default = "default_stream_min_bytes")]
+    pub min_bytes: usize,
+}
+
+/// Concurrency limits that queue and then shed requests under overload
+/// (`proxy.admission`)
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AdmissionConfig {
+    /// Requests handled at once across the proxy
+    #[serde(default)]
+    pub global: ConcurrencyLimit,
+    /// Requests sent at once to each backend
+    #[serde(default)]
+    pub per_backend: ConcurrencyLimit,
+    /// Per-backend limits replacing `per_backend`, by server ID
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub servers: BTreeMap<String, ConcurrencyLimit>,
+    /// Milliseconds a request may wait for a slot before it is shed
+    /// (default: 5000)
+    #[serde(default = "default_admission_queue_timeout_ms")]
+    pub queue_timeout_ms: u64,
+}
+
+impl AdmissionConfig {
+    /// Limit on requests to `server_id`.
+    pub fn backend_limit(&self, server_id: &str) -> &ConcurrencyLimit {
+        self.servers.get(server_id).unwrap_or(&self.per_backend)
+    }
+
+    /// How long a request may wait for a slot.
+    pub fn queue_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.queue_timeout_ms)
+    }
+}
+
+/// A concurrency limit and the queue in front of it
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct ConcurrencyLimit {
+    /// Requests in progress at once (default: 0, unlimited)
+    #[serde(default)]
+    pub max_concurrent: usize,
+    /// Requests waiting for a slot; more are shed at once (default: 1000)
+    #[serde(default = "default_admission_max_queued")]
+    pub max_queued: usize,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+/// Fault injection into backend requests (`proxy.chaos`), for testing how
+/// clients and the proxy handle slow, lost and failing replies
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, Default)]
+pub struct ChaosConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Faults to inject; every rule matching a request applies
+    #[serde(default)]
+    pub faults: Vec<FaultConfig>,
+}
+
+/// Faults injected into requests to matching backends.
+///
+/// Latency is added first; then the request either fails with `error`
+/// (`error_percent`), is sent but has its reply dropped so it times out
+/// (`drop_percent`), or goes through.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct FaultConfig {
+    /// Server ID globs (`*`, `?`) the faults apply to
+    pub servers: Vec<String>,
+    /// Method globs the faults apply to, e.g. `tools/call`; empty matches
+    /// every method
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub methods: Vec<String>,
+    /// Added delay in milliseconds (default: 0)
+    #[serde(default)]
+    pub latency_ms: u64,
+    /// Percentage of requests delayed (default: 100)
+    #[serde(default = "default_fault_latency_percent")]
+    pub latency_percent: u8,
+    /// Percentage of requests whose reply is dropped (default: 0)
+    #[serde(default)]
+    pub drop_percent: u8,
+    /// Percentage of requests failed with `error` (default: 0)
+    #[serde(default)]
+    pub error_percent: u8,
+    /// JSON-RPC error returned for failed requests
+    #[serde(default)]
+    pub error: FaultError,
+    /// Also apply to the server's health checks, whatever `methods` says,
+    /// so failures open its circuit and trigger failover (default: false)
+    #[serde(default)]
+    pub health_checks: bool,
+}
+
+impl FaultConfig {
+    /// Whether the faults apply to `server_id`.
+    pub fn matches_server(&self, server_id: &str) -> bool {
+        let glob = crate::routing::tool_matcher::glob_match;
+        self.servers.iter().any(|pattern| glob(pattern, server_id))
+    }
+
+    /// Whether the faults apply to `method` requests to `server_id`.
+    pub fn matches(&self, server_id: &str, method: &str) -> bool {
+        let glob = crate::routing::tool_matcher::glob_match;
+        self.matches_server(server_id)
+            && (self.methods.is_empty() || self.methods.iter().any(|pattern| glob(pattern, method)))
+    }
+}
+
+/// JSON-RPC error of an injected failure
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct FaultError {
+    /// Error code (default: -32603)
+    #[serde(default = "default_fault_error_code")]
+    pub code: i32,
+    /// Error message (default: "Injected fault")
+    #[serde(default = "default_fault_error_message")]
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, Default)]
 pub struct ContextOptimizationConfig {
     #[serde(default)]
     pub cache: CacheConfig,
     #[serde(default)]
     pub batching: BatchingConfig,
+    #[serde(default)]
+    pub catalog: CatalogConfig,
+    #[serde(default)]
+    pub aggregation: AggregationConfig,
+    #[serde(default)]
+    pub truncation: TruncationConfig,
+    #[serde(default)]
+    pub slim: SlimConfig,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
 pub struct CacheConfig {
     #[serde(default = "default_true")]
     pub enabled: bool,
@@ -184,9 +1754,47 @@ pub struct CacheConfig {
     pub max_entries: usize,
     #[serde(default = "default_ttl_seconds")]
     pub ttl_seconds: u64,
+    /// Seconds responses to a method are cached, replacing the default for
+    /// its tier, e.g. `resources/read: 60`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub method_ttl_seconds: BTreeMap<String, u64>,
+    /// Seconds an expired response is still served while a single
+    /// background request refreshes it (default: 0, expired entries are
+    /// refetched by the next request)
+    #[serde(default)]
+    pub stale_grace_seconds: u64,
+    /// Memory budgets in bytes of the cache layers they name (`tools`,
+    /// `resources`, `prompts`), replacing their entry limits, e.g.
+    /// `tools: 67108864`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub max_bytes: BTreeMap<String, u64>,
+    /// Compress large cached responses
+    #[serde(default)]
+    pub compression: CacheCompressionConfig,
+}
+
+/// Compression of cached responses (`context_optimization.cache.compression`)
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct CacheCompressionConfig {
+    /// Enable zstd compression of cached responses (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Responses smaller than this are stored raw (default: 4096)
+    #[serde(default = "default_compression_min_bytes")]
+    pub min_bytes: usize,
+    /// zstd level, 1 (fastest) to 22 (smallest) (default: 3)
+    #[serde(default = "default_compression_level")]
+    pub level: i32,
+    /// Cache layers to compress: `tools`, `resources` and `prompts`
+    /// (default: all)
+    #[serde(default = "default_compression_layers")]
+    pub layers: Vec<String>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+/// Cache layers, named after the responses they hold
+pub const CACHE_LAYERS: [&str; 3] = ["tools", "resources", "prompts"];
+
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
 pub struct BatchingConfig {
     /// Enable or disable request batching (default: false for backward compatibility)
     #[serde(default)]
@@ -203,49 +1811,676 @@ pub struct BatchingConfig {
     /// Whitelist of methods that support batching (default: list methods)
     #[serde(default = "default_batch_methods")]
     pub methods: Vec<String>,
+
+    /// Tune the window per server and method from arrival rates and the
+    /// latency budget, starting from `window_ms`
+    #[serde(default)]
+    pub adaptive: AdaptiveBatchingConfig,
+}
+
+/// Adaptive batching window (`context_optimization.batching.adaptive`)
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AdaptiveBatchingConfig {
+    /// Enable window tuning (default: false, `window_ms` is used as is)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Shortest window in milliseconds (default: 5)
+    #[serde(default = "default_adaptive_min_window_ms")]
+    pub min_window_ms: u64,
+    /// Longest window in milliseconds (default: 200)
+    #[serde(default = "default_adaptive_max_window_ms")]
+    pub max_window_ms: u64,
+    /// Latency budget: p95 of the time requests wait in a batch, in
+    /// milliseconds (default: 50)
+    #[serde(default = "default_adaptive_target_p95_ms")]
+    pub target_p95_ms: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct CatalogConfig {
+    /// Maintain precomputed tools/resources/prompts catalogs in the background
+    /// (default: false, lists are aggregated on demand)
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Interval between background catalog refreshes in seconds (default: 60)
+    #[serde(default = "default_catalog_refresh_seconds")]
+    pub refresh_interval_seconds: u64,
+
+    /// Fetch every backend's tools, resources and prompts into the server
+    /// registry in the background even when `enabled` is false, so on-demand
+    /// aggregation is served from fresh lists (default: false)
+    #[serde(default)]
+    pub prefetch: bool,
+}
+
+impl CatalogConfig {
+    /// Whether the background refresher runs at all.
+    pub fn refresher_enabled(&self) -> bool {
+        self.enabled || self.prefetch
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AggregationConfig {
+    /// Time budget in milliseconds for fanning a list request out to all
+    /// backends; slower backends are skipped and served from cache
+    /// (default: 800, 0 waits for every backend)
+    #[serde(default = "default_aggregation_timeout_ms")]
+    pub timeout_ms: u64,
+
+    /// Add `_meta.health` (the providing server's health) to each tool in
+    /// tools/list results (default: true)
+    #[serde(default = "default_true")]
+    pub annotate_health: bool,
+
+    /// Seconds a server's discovered tools, resources and prompts are reused
+    /// before its lists are fetched again (default: 300, 0 always fetches)
+    #[serde(default = "default_server_catalog_ttl_seconds")]
+    pub server_catalog_ttl_seconds: u64,
+
+    /// Return tools/list in pages of this many tools, fetched from the
+    /// backends page by page and continued with `nextCursor` (default: 0,
+    /// the full list at once)
+    #[serde(default)]
+    pub page_size: usize,
+}
+
+impl AggregationConfig {
+    /// How long a server's discovered lists are reused.
+    pub fn server_catalog_ttl(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.server_catalog_ttl_seconds)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct TruncationConfig {
+    /// Cut tools/call results down to the limits below (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Largest result content, in bytes, returned to clients (default: 65536)
+    #[serde(default = "default_truncation_max_bytes")]
+    pub max_bytes: usize,
+
+    /// Largest result content, in estimated tokens (default: unlimited)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u64>,
+
+    /// Limits for tools matching a glob, replacing the defaults above, e.g.
+    /// `"read_*": {max_bytes: 262144}`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tools: BTreeMap<String, ToolTruncationConfig>,
+}
+
+/// Limits of the tools matching one `context_optimization.truncation.tools`
+/// pattern; unset limits keep the defaults.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct ToolTruncationConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u64>,
+}
+
+impl TruncationConfig {
+    /// Byte and token limits of results of `tool`, taken from the first
+    /// pattern matching it.
+    pub fn limits_for(&self, tool: &str) -> (usize, Option<u64>) {
+        let tool_limits = self
+            .tools
+            .iter()
+            .find(|(pattern, _)| crate::routing::tool_matcher::glob_match(pattern, tool))
+            .map(|(_, limits)| limits);
+        match tool_limits {
+            Some(limits) => (
+                limits.max_bytes.unwrap_or(self.max_bytes),
+                limits.max_tokens.or(self.max_tokens),
+            ),
+            None => (self.max_bytes, self.max_tokens),
+        }
+    }
+}
+
+/// How much of each tool definition a tools/list result carries.
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CatalogMode {
+    /// Tool definitions as the backends return them
+    #[default]
+    Full,
+    /// Long descriptions shortened and large input schemas stripped of
+    /// descriptions and examples
+    Slim,
+    /// Tool names only; clients fetch definitions with `tools/describe`
+    Names,
+}
+
+impl std::str::FromStr for CatalogMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "full" => Ok(Self::Full),
+            "slim" => Ok(Self::Slim),
+            "names" => Ok(Self::Names),
+            other => Err(format!("unknown catalog mode '{}'", other)),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct SlimConfig {
+    /// Catalog mode of clients that do not choose one (default: full)
+    #[serde(default)]
+    pub mode: CatalogMode,
+
+    /// Request header a client sets to `full`, `slim` or `names` to choose
+    /// its catalog mode (default: x-only1mcp-catalog)
+    #[serde(default = "default_catalog_mode_header")]
+    pub header: String,
+
+    /// Catalog modes of clients by client id (the `proxy.routing`
+    /// client id header or authenticated principal); the header wins
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub clients: BTreeMap<String, CatalogMode>,
+
+    /// Longest tool description kept in slim mode, in characters
+    /// (default: 200)
+    #[serde(default = "default_slim_max_description_chars")]
+    pub max_description_chars: usize,
+
+    /// Input schemas larger than this, in bytes, lose their descriptions and
+    /// examples in slim mode (default: 1024)
+    #[serde(default = "default_slim_max_schema_bytes")]
+    pub max_schema_bytes: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, Default)]
 pub struct AuthConfig {
-    // Auth configuration (placeholder)
+    /// Which tools each role may see and call. When empty, every caller may
+    /// use every tool.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub policies: Vec<PolicyConfig>,
+    /// Applies to callers none of whose roles has a policy (default: deny)
+    #[serde(default)]
+    pub default_policy: PolicyAction,
+}
+
+/// Tool access rules for one role (`auth.policies`).
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+pub struct PolicyConfig {
+    pub role: String,
+    /// Tools the role may use; empty allows every tool not denied
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<ToolSelector>,
+    /// Tools the role may not use; deny wins over allow
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<ToolSelector>,
+}
+
+/// Outcome for callers not covered by any policy.
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyAction {
+    Allow,
+    #[default]
+    Deny,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, Default)]
 pub struct ObservabilityConfig {
     #[serde(default)]
     pub logging: LoggingConfig,
+    /// Alert rules evaluated by the proxy itself
+    #[serde(default)]
+    pub alerts: AlertsConfig,
+    /// Recent requests kept for the admin API and TUI
+    #[serde(default)]
+    pub request_history: RequestHistoryConfig,
+    /// Request/response capture for `only1mcp replay`
+    #[serde(default)]
+    pub recording: RecordingConfig,
+    /// Per-server files of STDIO backend output
+    #[serde(default)]
+    pub backend_logs: BackendLogsConfig,
+}
+
+/// Capture of STDIO backends' stderr into one rotating file per server
+/// (`observability.backend_logs`), read by `only1mcp logs --server <id>`
+/// and `GET /api/v1/admin/servers/:id/logs`.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq)]
+pub struct BackendLogsConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Directory of the `<server id>.log` files (default: `logs/` in the
+    /// config directory, e.g. `~/.config/only1mcp/logs`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dir: Option<PathBuf>,
+    /// Size at which a file is rotated (default: 10 MiB)
+    #[serde(default = "default_backend_log_max_bytes")]
+    pub max_file_bytes: u64,
+    /// Rotated files kept per server, as `<id>.log.1` (newest) and up
+    /// (default: 3)
+    #[serde(default = "default_backend_log_max_files")]
+    pub max_files: usize,
+    /// Also capture stdout lines that are not JSON-RPC messages
+    /// (default: false)
+    #[serde(default)]
+    pub capture_stdout: bool,
+}
+
+impl BackendLogsConfig {
+    /// Directory the files are written to.
+    pub fn dir(&self) -> Option<PathBuf> {
+        self.dir.clone().or_else(|| Some(config_dir()?.join("logs")))
+    }
+
+    /// Current log file of `server_id`. IDs are sanitized to file names.
+    pub fn path(&self, server_id: &str) -> Option<PathBuf> {
+        let name: String = server_id
+            .chars()
+            .map(
+                |c| match c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                    true => c,
+                    false => '_',
+                },
+            )
+            .collect();
+        Some(self.dir()?.join(format!("{}.log", name)))
+    }
+}
+
+/// In-memory history of recent requests (`observability.request_history`),
+/// served at `GET /api/v1/admin/requests`.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq)]
+pub struct RequestHistoryConfig {
+    /// How many requests are kept, oldest dropped first; 0 disables the
+    /// history (default: 500)
+    #[serde(default = "default_request_history_capacity")]
+    pub capacity: usize,
+}
+
+/// Recording of full request/response pairs (`observability.recording`).
+///
+/// Every buffered MCP request and its reply are appended as a JSON line to
+/// `path`, with the values of `redact_fields` replaced, so a session can be
+/// re-sent later with `only1mcp replay`. Tool results are buffered rather
+/// than streamed while recording.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq)]
+pub struct RecordingConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// File exchanges are appended to (default: only1mcp-recording.jsonl)
+    #[serde(default = "default_recording_path")]
+    pub path: PathBuf,
+    /// Object keys whose values are redacted anywhere in a request or reply,
+    /// compared case-insensitively
+    #[serde(default = "default_recording_redact_fields")]
+    pub redact_fields: Vec<String>,
+}
+
+impl Default for RecordingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: default_recording_path(),
+            redact_fields: default_recording_redact_fields(),
+        }
+    }
+}
+
+impl Default for BackendLogsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dir: None,
+            max_file_bytes: default_backend_log_max_bytes(),
+            max_files: default_backend_log_max_files(),
+            capture_stdout: false,
+        }
+    }
+}
+
+impl Default for RequestHistoryConfig {
+    fn default() -> Self {
+        Self {
+            capacity: default_request_history_capacity(),
+        }
+    }
+}
+
+/// Built-in alerting (`observability.alerts`).
+///
+/// Rules are evaluated against the proxy's own metrics every
+/// `interval_seconds`, without an external Prometheus or Alertmanager.
+/// Firing and resolved alerts are always logged, and sent to the rule's
+/// notifiers.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq)]
+pub struct AlertsConfig {
+    /// How often rules are evaluated (default: 30)
+    #[serde(default = "default_alert_interval_seconds")]
+    pub interval_seconds: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notifiers: Vec<AlertNotifierConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<AlertRuleConfig>,
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            interval_seconds: default_alert_interval_seconds(),
+            notifiers: Vec::new(),
+            rules: Vec::new(),
+        }
+    }
+}
+
+/// Where alerts are sent (`observability.alerts.notifiers`).
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq)]
+pub struct AlertNotifierConfig {
+    /// Name used in rules' `notify`
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: AlertNotifierKind,
+    /// Webhook URL
+    pub url: String,
+    /// Extra request headers, e.g. `Authorization`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+}
+
+/// Payload format of a notifier.
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertNotifierKind {
+    /// Slack incoming webhook: `{"text": ...}`
+    Slack,
+    /// The alert as JSON
+    Webhook,
+}
+
+/// One alert rule (`observability.alerts.rules`).
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq)]
+pub struct AlertRuleConfig {
+    pub name: String,
+    #[serde(flatten)]
+    pub condition: AlertCondition,
+    /// Notifiers to send to, by name; empty sends to every notifier
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notify: Vec<String>,
+}
+
+/// What an alert rule watches for.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AlertCondition {
+    /// A backend stays unhealthy for `for_seconds`
+    BackendUnhealthy {
+        #[serde(default = "default_alert_for_seconds")]
+        for_seconds: u64,
+        /// Backends watched; empty watches every enabled backend
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        servers: Vec<String>,
+    },
+    /// More than `above_percent` of a backend's requests failed or returned
+    /// errors over the last `window_seconds`
+    ErrorRate {
+        above_percent: f64,
+        #[serde(default = "default_alert_window_seconds")]
+        window_seconds: u64,
+        /// Fewer requests in the window never fire (default: 10)
+        #[serde(default = "default_alert_min_requests")]
+        min_requests: u64,
+        /// Backends watched; empty watches every enabled backend
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        servers: Vec<String>,
+    },
+    /// The response cache hit rate over the last `window_seconds` is below
+    /// `below_percent`
+    CacheHitRate {
+        below_percent: f64,
+        #[serde(default = "default_alert_window_seconds")]
+        window_seconds: u64,
+        /// Fewer lookups in the window never fire (default: 100)
+        #[serde(default = "default_alert_min_lookups")]
+        min_lookups: u64,
+    },
+}
+
+/// Cluster mode (`cluster`): instances behind one load balancer gossip
+/// their backends' health and their sticky session pins over the Admin API,
+/// so every instance routes a client the same way.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq)]
+pub struct ClusterConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Name of this instance among its peers (default: `$HOSTNAME:<port>`,
+    /// else a random ID)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<String>,
+    /// Admin API base URLs of the other instances, e.g.
+    /// `http://10.0.0.2:8080`
+    #[serde(default)]
+    pub peers: Vec<String>,
+    /// API key sent to peers whose admin listener requires one (supports
+    /// secret references)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// How often state is exchanged with each peer (default: 1000)
+    #[serde(default = "default_gossip_interval_ms")]
+    pub gossip_interval_ms: u64,
+    /// Silence after which a peer is considered down and its health
+    /// reports are ignored (default: 5000)
+    #[serde(default = "default_peer_timeout_ms")]
+    pub peer_timeout_ms: u64,
+}
+
+impl ClusterConfig {
+    pub fn gossip_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.gossip_interval_ms)
+    }
+
+    pub fn peer_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.peer_timeout_ms)
+    }
+}
+
+/// Runtime backend discovery (`discovery`). Discovered backends are added
+/// to `servers` while they exist, without being written to the file.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize, PartialEq)]
+pub struct DiscoveryConfig {
+    #[serde(default)]
+    pub docker: DockerDiscoveryConfig,
+}
+
+/// Containers labelled `only1mcp.*` become backends
+/// (`discovery.docker`), see [`crate::discovery::docker`].
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq)]
+pub struct DockerDiscoveryConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Docker Engine API socket (default: `/var/run/docker.sock`)
+    #[serde(default = "default_docker_socket")]
+    pub socket: PathBuf,
+    /// How often running containers are listed (default: 2000)
+    #[serde(default = "default_docker_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+}
+
+impl DockerDiscoveryConfig {
+    pub fn poll_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.poll_interval_ms)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct TuiConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_tui_default_tab")]
+    pub default_tab: String,
+    #[serde(default = "default_tui_refresh_ms")]
+    pub refresh_ms: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct LoggingConfig {
+    #[serde(default = "default_log_level")]
+    pub level: String,
+    #[serde(default = "default_log_format")]
+    pub format: String,
+    /// Rotation of the daemon's `only1mcp.log`
+    #[serde(default)]
+    pub rotation: LogRotationConfig,
+}
+
+/// Rotation of the daemon log file (`observability.logging.rotation`).
+/// Rotated files are kept as `only1mcp.log.1` (newest) and up.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq)]
+pub struct LogRotationConfig {
+    /// Size at which the file is rotated, 0 for no size limit
+    /// (default: 50 MiB)
+    #[serde(default = "default_log_rotation_max_bytes")]
+    pub max_file_bytes: u64,
+    /// Also rotate when the hour or day (UTC) changes (default: never)
+    #[serde(default)]
+    pub interval: LogRotationInterval,
+    /// Rotated files kept (default: 5)
+    #[serde(default = "default_log_rotation_max_files")]
+    pub max_files: usize,
+    /// Gzip rotated files to `only1mcp.log.<n>.gz` (default: false)
+    #[serde(default)]
+    pub compress: bool,
+}
+
+/// Time-based rotation of the daemon log.
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRotationInterval {
+    #[default]
+    Never,
+    Hourly,
+    Daily,
+}
+
+// Default functions
+fn default_host() -> String {
+    "0.0.0.0".to_string()
+}
+fn default_port() -> u16 {
+    8080
+}
+fn default_max_connections() -> usize {
+    10000
+}
+fn default_true() -> bool {
+    true
+}
+fn default_script_max_operations() -> u64 {
+    100_000
+}
+fn default_sanitize_max_depth() -> usize {
+    32
+}
+fn default_binary_mime_types() -> Vec<String> {
+    vec!["image/*".to_string(), "audio/*".to_string()]
+}
+fn default_signature_header() -> String {
+    "X-Signature".to_string()
+}
+fn default_builtin_patterns() -> Vec<BuiltinPattern> {
+    vec![
+        BuiltinPattern::Email,
+        BuiltinPattern::ApiKey,
+        BuiltinPattern::AwsCredentials,
+        BuiltinPattern::CreditCard,
+    ]
+}
+fn default_composite_input_schema() -> serde_json::Value {
+    serde_json::json!({"type": "object"})
+}
+fn default_initialization_max_parallel() -> usize {
+    8
+}
+fn default_initialization_timeout_ms() -> u64 {
+    30_000
+}
+fn default_compression_min_bytes() -> usize {
+    4096
+}
+fn default_compression_level() -> i32 {
+    3
+}
+fn default_compression_layers() -> Vec<String> {
+    CACHE_LAYERS.iter().map(|layer| layer.to_string()).collect()
+}
+fn default_adaptive_min_window_ms() -> u64 {
+    5
+}
+fn default_adaptive_max_window_ms() -> u64 {
+    200
+}
+fn default_adaptive_target_p95_ms() -> u64 {
+    50
+}
+fn default_admission_queue_timeout_ms() -> u64 {
+    5000
+}
+fn default_admission_max_queued() -> usize {
+    1000
+}
+fn default_fault_latency_percent() -> u8 {
+    100
+}
+fn default_fault_error_code() -> i32 {
+    -32603
+}
+fn default_fault_error_message() -> String {
+    "Injected fault".to_string()
+}
+fn default_request_history_capacity() -> usize {
+    500
+}
+fn default_backend_log_max_bytes() -> u64 {
+    10 * 1024 * 1024
+}
+fn default_backend_log_max_files() -> usize {
+    3
 }
-
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct TuiConfig {
-    #[serde(default)]
-    pub enabled: bool,
-    #[serde(default = "default_tui_default_tab")]
-    pub default_tab: String,
-    #[serde(default = "default_tui_refresh_ms")]
-    pub refresh_ms: u64,
+fn default_recording_path() -> PathBuf {
+    PathBuf::from("only1mcp-recording.jsonl")
 }
-
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct LoggingConfig {
-    #[serde(default = "default_log_level")]
-    pub level: String,
-    #[serde(default = "default_log_format")]
-    pub format: String,
+fn default_recording_redact_fields() -> Vec<String> {
+    [
+        "authorization",
+        "password",
+        "secret",
+        "client_secret",
+        "token",
+        "access_token",
+        "refresh_token",
+        "api_key",
+        "apikey",
+    ]
+    .map(String::from)
+    .to_vec()
 }
-
-// Default functions
-fn default_host() -> String {
-    "0.0.0.0".to_string()
+fn default_alert_interval_seconds() -> u64 {
+    30
 }
-fn default_port() -> u16 {
-    8080
+fn default_alert_for_seconds() -> u64 {
+    300
 }
-fn default_max_connections() -> usize {
-    10000
+fn default_alert_window_seconds() -> u64 {
+    300
 }
-fn default_true() -> bool {
-    true
+fn default_alert_min_requests() -> u64 {
+    10
+}
+fn default_alert_min_lookups() -> u64 {
+    100
 }
 fn default_interval_seconds() -> u64 {
     10
@@ -274,12 +2509,39 @@ fn default_algorithm() -> String {
 fn default_virtual_nodes() -> usize {
     150
 }
+fn default_session_ttl_seconds() -> u64 {
+    3600
+}
+fn default_client_id_header() -> String {
+    "x-client-id".to_string()
+}
 fn default_max_per_backend() -> usize {
     100
 }
 fn default_min_idle() -> usize {
     10
 }
+fn default_stdio_processes() -> usize {
+    1
+}
+fn default_max_idle_per_host() -> usize {
+    10
+}
+fn default_pool_idle_timeout() -> u64 {
+    90
+}
+fn default_max_request_bytes() -> usize {
+    2 * 1024 * 1024
+}
+fn default_max_response_bytes() -> usize {
+    32 * 1024 * 1024
+}
+fn default_max_stdio_line_bytes() -> usize {
+    16 * 1024 * 1024
+}
+fn default_stream_min_bytes() -> usize {
+    1024 * 1024
+}
 fn default_max_entries() -> usize {
     10000
 }
@@ -299,21 +2561,103 @@ fn default_batch_methods() -> Vec<String> {
         "prompts/list".to_string(),
     ]
 }
+fn default_catalog_refresh_seconds() -> u64 {
+    60
+}
+fn default_aggregation_timeout_ms() -> u64 {
+    800
+}
+fn default_server_catalog_ttl_seconds() -> u64 {
+    300
+}
+fn default_truncation_max_bytes() -> usize {
+    65536
+}
+fn default_catalog_mode_header() -> String {
+    "x-only1mcp-catalog".to_string()
+}
+fn default_slim_max_description_chars() -> usize {
+    200
+}
+fn default_slim_max_schema_bytes() -> usize {
+    1024
+}
 fn default_log_level() -> String {
     "info".to_string()
 }
 fn default_log_format() -> String {
     "json".to_string()
 }
+fn default_log_rotation_max_bytes() -> u64 {
+    50 * 1024 * 1024
+}
+fn default_log_rotation_max_files() -> usize {
+    5
+}
+fn default_gossip_interval_ms() -> u64 {
+    1000
+}
+fn default_peer_timeout_ms() -> u64 {
+    5000
+}
+fn default_docker_socket() -> PathBuf {
+    PathBuf::from("/var/run/docker.sock")
+}
+fn default_docker_poll_interval_ms() -> u64 {
+    2000
+}
 fn default_tui_default_tab() -> String {
     "overview".to_string()
 }
 fn default_tui_refresh_ms() -> u64 {
     1000
 }
+fn default_jwt_issuer() -> String {
+    "only1mcp".to_string()
+}
+fn default_jwt_audience() -> String {
+    "only1mcp-api".to_string()
+}
+fn default_drain_timeout_seconds() -> u64 {
+    30
+}
+fn default_upgrade_timeout_seconds() -> u64 {
+    30
+}
+fn default_listener_routes() -> Vec<RouteGroup> {
+    RouteGroup::ALL.to_vec()
+}
 fn default_timeout_ms() -> u64 {
     30000
 }
+fn default_max_attempts() -> u32 {
+    3
+}
+fn default_retry_base_delay_ms() -> u64 {
+    100
+}
+fn default_retry_max_delay_ms() -> u64 {
+    5000
+}
+
+fn default_max_restarts() -> u32 {
+    5
+}
+fn default_restart_window_seconds() -> u64 {
+    60
+}
+fn default_restart_initial_backoff_ms() -> u64 {
+    500
+}
+fn default_restart_max_backoff_ms() -> u64 {
+    30_000
+}
+fn default_violation_seconds() -> u64 {
+    30
+}
+fn default_sandbox_read_only_paths() -> Vec<String> {
+    ["/usr", "/bin", "/sbin", "/lib", "/lib64", "/etc"].map(String::from).to_vec()
+}
 
 impl Default for ServerConfig {
     fn default() -> Self {
@@ -323,6 +2667,117 @@ impl Default for ServerConfig {
             worker_threads: 0,
             max_connections: default_max_connections(),
             tls: TlsConfig::default(),
+            drain_timeout_seconds: default_drain_timeout_seconds(),
+            upgrade_timeout_seconds: default_upgrade_timeout_seconds(),
+            listeners: Vec::new(),
+            listen_socket: None,
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Listeners to open: the configured ones, or a single listener on
+    /// `host:port` serving every route group, plus `listen_socket` if set.
+    pub fn effective_listeners(&self) -> Vec<ListenerConfig> {
+        let mut listeners = if self.listeners.is_empty() {
+            vec![ListenerConfig {
+                name: "default".to_string(),
+                bind: host_port(
+                    self.host.trim_start_matches('[').trim_end_matches(']'),
+                    self.port,
+                ),
+                routes: default_listener_routes(),
+                tls: self.tls.clone(),
+                auth: None,
+                ipv6_only: false,
+            }]
+        } else {
+            self.listeners.clone()
+        };
+        if let Some(path) = &self.listen_socket {
+            listeners.push(ListenerConfig {
+                name: "socket".to_string(),
+                bind: format!("unix:{}", path.display()),
+                routes: default_listener_routes(),
+                tls: TlsConfig::default(),
+                auth: None,
+                ipv6_only: false,
+            });
+        }
+        listeners
+    }
+
+    /// Host and port of the first plain TCP listener serving the admin API,
+    /// used by CLI commands that talk to a running instance. A wildcard
+    /// address is reached on loopback.
+    pub fn admin_address(&self) -> (String, u16) {
+        self.effective_listeners()
+            .iter()
+            .filter(|l| l.serves(RouteGroup::Admin) && !l.tls.enabled)
+            .find_map(|l| match l.address() {
+                Ok(ListenAddress::Tcp(addr)) => {
+                    Some((loopback_if_unspecified(addr.ip()), addr.port()))
+                },
+                _ => None,
+            })
+            .unwrap_or_else(|| (self.host.clone(), self.port))
+    }
+}
+
+/// `ip` as a host to connect to: a wildcard address is reached on loopback.
+fn loopback_if_unspecified(ip: std::net::IpAddr) -> String {
+    match ip {
+        std::net::IpAddr::V4(ip) if ip.is_unspecified() => {
+            std::net::Ipv4Addr::LOCALHOST.to_string()
+        },
+        std::net::IpAddr::V6(ip) if ip.is_unspecified() => {
+            std::net::Ipv6Addr::LOCALHOST.to_string()
+        },
+        ip => ip.to_string(),
+    }
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_max_attempts(),
+            backoff: BackoffStrategy::default(),
+            base_delay_ms: default_retry_base_delay_ms(),
+            max_delay_ms: default_retry_max_delay_ms(),
+        }
+    }
+}
+
+impl Default for RestartConfig {
+    fn default() -> Self {
+        Self {
+            policy: RestartPolicy::default(),
+            max_restarts: default_max_restarts(),
+            window_seconds: default_restart_window_seconds(),
+            initial_backoff_ms: default_restart_initial_backoff_ms(),
+            max_backoff_ms: default_restart_max_backoff_ms(),
+        }
+    }
+}
+
+impl Default for ResourceLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_memory_mb: None,
+            max_cpu_percent: None,
+            violation_seconds: default_violation_seconds(),
+        }
+    }
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            strategy: SandboxStrategy::default(),
+            read_only_paths: default_sandbox_read_only_paths(),
+            writable_paths: Vec::new(),
+            network: true,
+            seccomp_profile: None,
         }
     }
 }
@@ -364,6 +2819,116 @@ impl Default for ConnectionPoolConfig {
         Self {
             max_per_backend: default_max_per_backend(),
             min_idle: default_min_idle(),
+            stdio_processes_per_server: default_stdio_processes(),
+            stdio_idle_timeout_seconds: 0,
+            max_idle_per_host: default_max_idle_per_host(),
+            idle_timeout_seconds: default_pool_idle_timeout(),
+            http2_keep_alive_seconds: 0,
+            prewarm: true,
+        }
+    }
+}
+
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_request_bytes: default_max_request_bytes(),
+            max_response_bytes: default_max_response_bytes(),
+            max_stdio_line_bytes: default_max_stdio_line_bytes(),
+        }
+    }
+}
+
+impl Default for InitializationConfig {
+    fn default() -> Self {
+        Self {
+            max_parallel: default_initialization_max_parallel(),
+            timeout_ms: default_initialization_timeout_ms(),
+        }
+    }
+}
+
+impl Default for SanitizationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            strip_ansi: true,
+            max_depth: default_sanitize_max_depth(),
+            binary_mime_types: default_binary_mime_types(),
+            validate_output: false,
+        }
+    }
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            builtin: default_builtin_patterns(),
+            custom: Vec::new(),
+            arguments: true,
+            results: true,
+            skip_tools: Vec::new(),
+        }
+    }
+}
+
+impl Default for ScriptsConfig {
+    fn default() -> Self {
+        Self {
+            on_request: None,
+            on_route: None,
+            on_response: None,
+            max_operations: default_script_max_operations(),
+        }
+    }
+}
+
+impl Default for NpxConfig {
+    fn default() -> Self {
+        Self {
+            resolve: true,
+            require_pinned: false,
+            auto_install: false,
+            install_dir: None,
+        }
+    }
+}
+
+impl Default for AdmissionConfig {
+    fn default() -> Self {
+        Self {
+            global: ConcurrencyLimit::default(),
+            per_backend: ConcurrencyLimit::default(),
+            servers: BTreeMap::new(),
+            queue_timeout_ms: default_admission_queue_timeout_ms(),
+        }
+    }
+}
+
+impl Default for ConcurrencyLimit {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 0,
+            max_queued: default_admission_max_queued(),
+        }
+    }
+}
+
+impl Default for FaultError {
+    fn default() -> Self {
+        Self {
+            code: default_fault_error_code(),
+            message: default_fault_error_message(),
+        }
+    }
+}
+
+impl Default for StreamingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_bytes: default_stream_min_bytes(),
         }
     }
 }
@@ -374,6 +2939,21 @@ impl Default for CacheConfig {
             enabled: true,
             max_entries: default_max_entries(),
             ttl_seconds: default_ttl_seconds(),
+            method_ttl_seconds: BTreeMap::new(),
+            stale_grace_seconds: 0,
+            max_bytes: BTreeMap::new(),
+            compression: CacheCompressionConfig::default(),
+        }
+    }
+}
+
+impl Default for CacheCompressionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_bytes: default_compression_min_bytes(),
+            level: default_compression_level(),
+            layers: default_compression_layers(),
         }
     }
 }
@@ -385,6 +2965,62 @@ impl Default for BatchingConfig {
             window_ms: default_batch_window_ms(),
             max_batch_size: default_max_batch_size(),
             methods: default_batch_methods(),
+            adaptive: AdaptiveBatchingConfig::default(),
+        }
+    }
+}
+
+impl Default for AdaptiveBatchingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_window_ms: default_adaptive_min_window_ms(),
+            max_window_ms: default_adaptive_max_window_ms(),
+            target_p95_ms: default_adaptive_target_p95_ms(),
+        }
+    }
+}
+
+impl Default for CatalogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            refresh_interval_seconds: default_catalog_refresh_seconds(),
+            prefetch: false,
+        }
+    }
+}
+
+impl Default for AggregationConfig {
+    fn default() -> Self {
+        Self {
+            timeout_ms: default_aggregation_timeout_ms(),
+            annotate_health: true,
+            server_catalog_ttl_seconds: default_server_catalog_ttl_seconds(),
+            page_size: 0,
+        }
+    }
+}
+
+impl Default for TruncationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_bytes: default_truncation_max_bytes(),
+            max_tokens: None,
+            tools: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for SlimConfig {
+    fn default() -> Self {
+        Self {
+            mode: CatalogMode::Full,
+            header: default_catalog_mode_header(),
+            clients: BTreeMap::new(),
+            max_description_chars: default_slim_max_description_chars(),
+            max_schema_bytes: default_slim_max_schema_bytes(),
         }
     }
 }
@@ -394,27 +3030,112 @@ impl Default for LoggingConfig {
         Self {
             level: default_log_level(),
             format: default_log_format(),
+            rotation: LogRotationConfig::default(),
+        }
+    }
+}
+
+impl Default for ClusterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            node_id: None,
+            peers: Vec::new(),
+            token: None,
+            gossip_interval_ms: default_gossip_interval_ms(),
+            peer_timeout_ms: default_peer_timeout_ms(),
+        }
+    }
+}
+
+impl Default for DockerDiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            socket: default_docker_socket(),
+            poll_interval_ms: default_docker_poll_interval_ms(),
+        }
+    }
+}
+
+impl Default for LogRotationConfig {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: default_log_rotation_max_bytes(),
+            interval: LogRotationInterval::default(),
+            max_files: default_log_rotation_max_files(),
+            compress: false,
         }
     }
 }
 
+/// Directory of the default config file and the daemon's files:
+/// `$XDG_CONFIG_HOME/only1mcp`, else `~/.config/only1mcp`.
+pub fn config_dir() -> Option<PathBuf> {
+    match std::env::var("XDG_CONFIG_HOME") {
+        Ok(xdg_config) => Some(PathBuf::from(xdg_config).join("only1mcp")),
+        Err(_) => Some(dirs::home_dir()?.join(".config").join("only1mcp")),
+    }
+}
+
 impl Config {
-    /// Load configuration from a file
+    /// Whether `server_id` is the mirror target of another server.
+    pub fn is_shadow(&self, server_id: &str) -> bool {
+        self.servers
+            .iter()
+            .any(|server| server.mirror.as_ref().is_some_and(|mirror| mirror.server == server_id))
+    }
+
+    /// Enabled servers taking routed traffic, i.e. all but mirror targets.
+    pub fn routed_servers(&self) -> impl Iterator<Item = &McpServerConfig> {
+        self.servers
+            .iter()
+            .filter(|server| server.enabled && !self.is_shadow(&server.id))
+    }
+
+    /// The outbound proxy used to reach a backend with this transport: its
+    /// own `transport.proxy`, else `proxy.outbound_proxy`. A proxy block
+    /// without URLs connects directly.
+    pub fn outbound_proxy<'a>(
+        &'a self,
+        transport: &'a TransportConfig,
+    ) -> Option<&'a OutboundProxyConfig> {
+        transport.proxy().or(self.proxy.outbound_proxy.as_ref())
+    }
+
+    /// Load configuration from a file, applying environment and `--set`
+    /// overrides (see [`overrides`]) and resolving secret references
+    /// (see [`secrets`])
     pub fn from_file(path: &Path) -> Result<Self> {
         let content = std::fs::read_to_string(path)
             .map_err(|e| Error::Config(format!("Failed to read config file: {}", e)))?;
+        Self::from_content(&content, schema::Format::from_path(path)?)
+    }
 
-        let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("yaml");
+    /// Load configuration from the content of a file in the given format,
+    /// like [`from_file`](Self::from_file)
+    pub fn from_content(content: &str, format: schema::Format) -> Result<Self> {
+        let active = overrides::active_overrides();
+        let mut config: Self = if active.is_empty() {
+            Self::parse(content, format)?
+        } else {
+            let mut document: serde_json::Value = Self::parse(content, format)?;
+            overrides::apply_overrides(&mut document, &active)?;
+            serde_json::from_value(document).map_err(|e| {
+                Error::Config(format!("Invalid configuration after overrides: {}", e))
+            })?
+        };
+        secrets::resolve_secrets(&mut config)?;
+        Ok(config)
+    }
 
-        match extension {
-            "yaml" | "yml" => serde_yaml::from_str(&content)
+    /// Parse a configuration document in the given format.
+    fn parse<T: serde::de::DeserializeOwned>(content: &str, format: schema::Format) -> Result<T> {
+        match format {
+            schema::Format::Yaml => serde_yaml::from_str(content)
                 .map_err(|e| Error::Config(format!("Failed to parse YAML: {}", e))),
-            "toml" => toml::from_str(&content)
+            schema::Format::Toml => toml::from_str(content)
                 .map_err(|e| Error::Config(format!("Failed to parse TOML: {}", e))),
-            _ => Err(Error::Config(format!(
-                "Unsupported config format: {}",
-                extension
-            ))),
         }
     }
 
@@ -426,13 +3147,22 @@ impl Config {
     /// Discover and load configuration from standard locations with optional CLI override
     /// Discover and load configuration, returning both Config and path
     pub fn discover_and_load_with_path_tuple(cli_path: Option<PathBuf>) -> Result<(Self, PathBuf)> {
+        let path = Self::discover_path(cli_path)?;
+        let config = Self::from_file(&path)?;
+        Ok((config, path))
+    }
+
+    /// Find the configuration file like
+    /// [`discover_and_load_with_path_tuple`](Self::discover_and_load_with_path_tuple),
+    /// without loading it. The default file is still created when none
+    /// exists.
+    pub fn discover_path(cli_path: Option<PathBuf>) -> Result<PathBuf> {
         use tracing::{info, warn};
 
         // 1. CLI flag (highest priority)
         if let Some(path) = cli_path {
             info!("Using config from CLI path: {:?}", path);
-            let config = Self::from_file(&path)?;
-            return Ok((config, path));
+            return Ok(path);
         }
 
         // 2. XDG_CONFIG_HOME (new default)
@@ -449,8 +3179,7 @@ impl Config {
 
         if config_path.exists() {
             info!("Using config from: {:?}", config_path);
-            let config = Self::from_file(&config_path)?;
-            return Ok((config, config_path));
+            return Ok(config_path);
         }
 
         // 3. Legacy paths (for backwards compatibility)
@@ -471,8 +3200,7 @@ impl Config {
             if legacy_path.exists() {
                 warn!("Using legacy config path: {:?}", legacy_path);
                 warn!("Consider migrating to: {:?}", config_path);
-                let config = Self::from_file(&legacy_path)?;
-                return Ok((config, legacy_path));
+                return Ok(legacy_path);
             }
         }
 
@@ -485,8 +3213,7 @@ impl Config {
         std::fs::write(&config_path, default_config)
             .map_err(|e| Error::Config(format!("Failed to write default config: {}", e)))?;
 
-        let config = Self::from_file(&config_path)?;
-        Ok((config, config_path))
+        Ok(config_path)
     }
 
     pub fn discover_and_load_with_path(cli_path: Option<PathBuf>) -> Result<Self> {
@@ -562,10 +3289,31 @@ impl Config {
         Ok(())
     }
 
-    /// Validate configuration file
+    /// Check a configuration file against the config schema and
+    /// [`Config::validate`], returning every problem found. Unknown keys are
+    /// errors with `deny_unknown_fields` and warnings otherwise.
+    pub fn check_file(path: &Path, deny_unknown_fields: bool) -> Result<Vec<schema::Diagnostic>> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| Error::Config(format!("Failed to read config file: {}", e)))?;
+        let format = schema::Format::from_path(path)?;
+        Ok(schema::check_document(
+            &content,
+            format,
+            deny_unknown_fields,
+        ))
+    }
+
+    /// Validate configuration file, failing with a report of its errors.
     pub fn validate_file(path: &Path) -> Result<()> {
-        let _config = Self::from_file(path)?;
-        // Additional validation logic here
-        Ok(())
+        let errors: Vec<String> = Self::check_file(path, false)?
+            .iter()
+            .filter(|d| d.severity == schema::Severity::Error)
+            .map(ToString::to_string)
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Config(errors.join("\n")))
+        }
     }
 }
@@ -0,0 +1,248 @@
+//! Overrides layered over the configuration file.
+//!
+//! Containers often need to tweak a port, a cache TTL or a feature flag
+//! without editing the mounted file. Two sources are applied, in this order,
+//! to the parsed document before it is turned into a [`Config`](super::Config):
+//!
+//! 1. Environment variables named `ONLY1MCP_<SECTION>__<FIELD>`, with `__`
+//!    between path segments, e.g. `ONLY1MCP_SERVER__PORT=9090` or
+//!    `ONLY1MCP_CONTEXT_OPTIMIZATION__BATCHING__ENABLED=false`. Variables
+//!    without `__`, such as `ONLY1MCP_CONFIG`, are not overrides.
+//! 2. `--set key=value` flags, with `.` between path segments, e.g.
+//!    `--set server.port=9090`.
+//!
+//! A numeric segment indexes a list and any other segment selects the list
+//! item with that `id`, so `servers.github.enabled=false` disables one
+//! server. Values are read as YAML scalars or flow collections (`false`,
+//! `30`, `[a, b]`), except that a field already holding a string keeps the
+//! value as text. Overrides also apply when the file is reloaded.
+
+use std::sync::RwLock;
+
+use serde_json::{Map, Value};
+
+use crate::error::{Error, Result};
+
+/// Prefix of override environment variables.
+pub const ENV_PREFIX: &str = "ONLY1MCP_";
+
+/// Separator between path segments in environment variable names.
+const ENV_SEPARATOR: &str = "__";
+
+/// `--set` overrides given on the command line.
+static CLI_OVERRIDES: RwLock<Vec<Override>> = RwLock::new(Vec::new());
+
+/// A value to write at a path of the configuration document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Override {
+    /// Where the override came from, for error messages
+    pub source: String,
+    /// Path segments, e.g. `["server", "port"]`
+    pub path: Vec<String>,
+    /// Raw value
+    pub value: String,
+}
+
+impl Override {
+    /// Parse a `key.path=value` assignment as given to `--set`.
+    pub fn parse_assignment(assignment: &str) -> Result<Self> {
+        let (key, value) = assignment.split_once('=').ok_or_else(|| {
+            Error::Config(format!(
+                "Invalid --set '{}': expected key=value",
+                assignment
+            ))
+        })?;
+        let path: Vec<String> = key.trim().split('.').map(str::to_string).collect();
+        if path.iter().any(String::is_empty) {
+            return Err(Error::Config(format!(
+                "Invalid --set '{}': empty key segment",
+                assignment
+            )));
+        }
+        Ok(Self {
+            source: format!("--set {}", key.trim()),
+            path,
+            value: value.to_string(),
+        })
+    }
+
+    /// Parse an environment variable, or `None` if it is not an override.
+    pub fn from_env(name: &str, value: &str) -> Option<Self> {
+        let key = name.strip_prefix(ENV_PREFIX)?;
+        if !key.contains(ENV_SEPARATOR) {
+            return None;
+        }
+        let path: Vec<String> = key.split(ENV_SEPARATOR).map(str::to_lowercase).collect();
+        if path.iter().any(String::is_empty) {
+            return None;
+        }
+        Some(Self {
+            source: name.to_string(),
+            path,
+            value: value.to_string(),
+        })
+    }
+}
+
+/// Record the `--set` flags of this process, replacing earlier ones.
+pub fn set_cli_overrides(assignments: &[String]) -> Result<()> {
+    let overrides = assignments
+        .iter()
+        .map(|assignment| Override::parse_assignment(assignment))
+        .collect::<Result<Vec<_>>>()?;
+    *CLI_OVERRIDES.write().unwrap_or_else(|e| e.into_inner()) = overrides;
+    Ok(())
+}
+
+/// Overrides from the environment (sorted by name) followed by `--set` flags.
+pub fn active_overrides() -> Vec<Override> {
+    let mut env: Vec<Override> = std::env::vars()
+        .filter_map(|(name, value)| Override::from_env(&name, &value))
+        .collect();
+    env.sort_by(|a, b| a.source.cmp(&b.source));
+    env.extend(CLI_OVERRIDES.read().unwrap_or_else(|e| e.into_inner()).iter().cloned());
+    env
+}
+
+/// Apply `overrides` in order to a parsed configuration document.
+pub fn apply_overrides(document: &mut Value, overrides: &[Override]) -> Result<()> {
+    for entry in overrides {
+        let target = lookup_mut(document, &entry.path)
+            .map_err(|e| Error::Config(format!("Invalid override {}: {}", entry.source, e)))?;
+        *target = match target {
+            Value::String(_) => Value::String(entry.value.clone()),
+            _ => parse_value(&entry.value),
+        };
+    }
+    Ok(())
+}
+
+/// The value at `path`, creating missing object fields along the way.
+fn lookup_mut<'a>(
+    mut node: &'a mut Value,
+    path: &[String],
+) -> std::result::Result<&'a mut Value, String> {
+    for segment in path {
+        if node.is_null() {
+            *node = Value::Object(Map::new());
+        }
+        node = match node {
+            Value::Object(fields) => fields.entry(segment.clone()).or_insert(Value::Null),
+            Value::Array(items) => {
+                let len = items.len();
+                let position = match segment.parse::<usize>() {
+                    Ok(index) if index < len => Some(index),
+                    Ok(index) => {
+                        return Err(format!("index {} is out of range ({} items)", index, len));
+                    },
+                    Err(_) => items.iter().position(|item| id_matches(item, segment)),
+                };
+                let index = position.ok_or_else(|| format!("no item with id '{}'", segment))?;
+                &mut items[index]
+            },
+            _ => return Err(format!("'{}' is not inside a section or list", segment)),
+        };
+    }
+    Ok(node)
+}
+
+/// Whether a list item's `id` is `segment`, ignoring case and treating `-`
+/// as `_` since environment variable names cannot hold either.
+fn id_matches(item: &Value, segment: &str) -> bool {
+    let normalize = |text: &str| text.to_lowercase().replace('-', "_");
+    item.get("id")
+        .and_then(Value::as_str)
+        .is_some_and(|id| normalize(id) == normalize(segment))
+}
+
+/// Read a raw value as YAML, falling back to the text itself.
+fn parse_value(raw: &str) -> Value {
+    serde_yaml::from_str::<Value>(raw)
+        .ok()
+        .filter(|value| !value.is_null() || raw.trim() == "null" || raw.trim() == "~")
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn document() -> Value {
+        json!({
+            "server": {"host": "127.0.0.1", "port": 8080},
+            "servers": [
+                {"id": "github-tools", "name": "GitHub", "enabled": true},
+                {"id": "db", "name": "Database", "enabled": true}
+            ]
+        })
+    }
+
+    #[test]
+    fn test_env_names() {
+        let entry = Override::from_env("ONLY1MCP_CONTEXT_OPTIMIZATION__CACHE__TTL", "60").unwrap();
+        assert_eq!(entry.path, ["context_optimization", "cache", "ttl"]);
+        assert!(Override::from_env("ONLY1MCP_CONFIG", "x.yaml").is_none());
+        assert!(Override::from_env("ONLY1MCP_LOG_LEVEL", "debug").is_none());
+        assert!(Override::from_env("OTHER_SERVER__PORT", "1").is_none());
+    }
+
+    #[test]
+    fn test_apply_overrides() {
+        let mut doc = document();
+        let overrides = [
+            Override::from_env("ONLY1MCP_SERVER__PORT", "9090").unwrap(),
+            Override::from_env("ONLY1MCP_SERVERS__GITHUB_TOOLS__ENABLED", "false").unwrap(),
+            Override::parse_assignment("servers.1.name=42").unwrap(),
+            Override::parse_assignment("server.host=0.0.0.0").unwrap(),
+            Override::parse_assignment("context_optimization.batching.enabled=false").unwrap(),
+        ];
+        apply_overrides(&mut doc, &overrides).unwrap();
+        assert_eq!(doc["server"]["port"], 9090);
+        assert_eq!(doc["server"]["host"], "0.0.0.0");
+        assert_eq!(doc["servers"][0]["enabled"], false);
+        // String fields keep the value as text
+        assert_eq!(doc["servers"][1]["name"], "42");
+        assert_eq!(doc["context_optimization"]["batching"]["enabled"], false);
+    }
+
+    #[test]
+    fn test_overridden_document_loads() {
+        let mut doc: Value = serde_yaml::from_str("server:\n  port: 8080\nservers: []\n").unwrap();
+        apply_overrides(
+            &mut doc,
+            &[
+                Override::parse_assignment("server.port=9090").unwrap(),
+                Override::parse_assignment("context_optimization.cache.enabled=false").unwrap(),
+            ],
+        )
+        .unwrap();
+        let config: crate::config::Config = serde_json::from_value(doc).unwrap();
+        assert_eq!(config.server.port, 9090);
+        assert!(!config.context_optimization.cache.enabled);
+    }
+
+    #[test]
+    fn test_invalid_overrides() {
+        assert!(Override::parse_assignment("server.port").is_err());
+        assert!(Override::parse_assignment("server..port=1").is_err());
+
+        let mut doc = document();
+        let err = apply_overrides(
+            &mut doc,
+            &[Override::parse_assignment("servers.5.enabled=false").unwrap()],
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("--set servers.5.enabled"));
+        assert!(apply_overrides(
+            &mut doc,
+            &[Override::parse_assignment("servers.missing.enabled=false").unwrap()],
+        )
+        .is_err());
+        assert!(apply_overrides(
+            &mut doc,
+            &[Override::parse_assignment("server.port.value=1").unwrap()],
+        )
+        .is_err());
+    }
+}
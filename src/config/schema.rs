@@ -1 +1,704 @@
+//! JSON Schema of the configuration, and validation of config files against
+//! it (`only1mcp validate`).
+//!
+//! Serde stops at the first problem and silently ignores keys it does not
+//! know, so a misspelled `max_entires` quietly falls back to its default.
+//! Checking the parsed document against the schema first reports every
+//! problem at once, each with the line and column it was found on, and flags
+//! unknown keys with the closest known key as a suggestion. Unknown keys are
+//! warnings unless `deny_unknown_fields` is set.
 
+use std::fmt;
+use std::path::Path;
+
+use jsonschema::{error::ValidationErrorKind, paths::PathChunk, JSONSchema};
+use serde_json::{json, Value};
+
+use crate::config::Config;
+use crate::error::{Error, Result};
+
+/// Smallest similarity for a known key to be suggested for an unknown one.
+const SUGGESTION_THRESHOLD: f64 = 0.6;
+
+/// JSON Schema (draft 7) of the configuration file.
+pub fn config_schema() -> Value {
+    serde_json::to_value(schemars::schema_for!(Config)).unwrap_or_default()
+}
+
+/// Format of a configuration file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Yaml,
+    Toml,
+}
+
+impl Format {
+    /// Format of the file at `path`, by extension (YAML when it has none).
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match path.extension().and_then(|ext| ext.to_str()).unwrap_or("yaml") {
+            "yaml" | "yml" => Ok(Self::Yaml),
+            "toml" => Ok(Self::Toml),
+            other => Err(Error::Config(format!(
+                "Unsupported config format: {}",
+                other
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A problem found in a configuration file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Location in the config, e.g. `servers[0].transport.url`; empty for
+    /// the whole file
+    pub path: String,
+    /// 1-based line and column, when the location could be found in the file
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+    /// Known key closest to a misspelled one
+    pub suggestion: Option<String>,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{}", severity)?;
+        if !self.path.is_empty() {
+            write!(f, " at {}", self.path)?;
+        }
+        if let (Some(line), Some(column)) = (self.line, self.column) {
+            write!(f, " (line {}, column {})", line, column)?;
+        }
+        write!(f, ": {}", self.message)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, ", did you mean `{}`?", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+/// One step of a path into the configuration document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// Check a configuration document against the config schema, then against
+/// [`Config::validate`] if the schema found no errors.
+///
+/// Secret references are not resolved, so unset variables are not reported.
+pub fn check_document(text: &str, format: Format, deny_unknown_fields: bool) -> Vec<Diagnostic> {
+    let document = match parse(text, format) {
+        Ok(document) => document,
+        Err(diagnostic) => return vec![diagnostic],
+    };
+
+    let mut schema = config_schema();
+    close_objects(&mut schema);
+    let definitions = schema.get("definitions").cloned().unwrap_or_else(|| json!({}));
+    let mut problems = Vec::new();
+    collect_problems(&schema, &definitions, &document, &[], &mut problems);
+
+    let mut diagnostics: Vec<Diagnostic> = problems
+        .into_iter()
+        .map(|problem| {
+            let (line, column) = locate(text, format, &problem.path).unzip();
+            let severity = if problem.unknown_field && !deny_unknown_fields {
+                Severity::Warning
+            } else {
+                Severity::Error
+            };
+            Diagnostic {
+                severity,
+                path: display_path(&problem.path),
+                line,
+                column,
+                message: problem.message,
+                suggestion: problem.suggestion,
+            }
+        })
+        .collect();
+    diagnostics.sort_by_key(|d| (d.line.is_none(), d.line, d.column));
+
+    // Semantic checks need a config the schema accepts
+    if diagnostics.iter().all(|d| d.severity == Severity::Warning) {
+        let result = serde_json::from_value::<Config>(document)
+            .map_err(|e| Error::Config(e.to_string()))
+            .and_then(|config| config.validate());
+        if let Err(e) = result {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Error,
+                path: String::new(),
+                line: None,
+                column: None,
+                message: match e {
+                    Error::Config(message) => message,
+                    other => other.to_string(),
+                },
+                suggestion: None,
+            });
+        }
+    }
+    diagnostics
+}
+
+/// Parse a document into JSON, or describe where its syntax is broken.
+fn parse(text: &str, format: Format) -> std::result::Result<Value, Diagnostic> {
+    let syntax_error = |message: String, position: Option<(usize, usize)>| Diagnostic {
+        severity: Severity::Error,
+        path: String::new(),
+        line: position.map(|(line, _)| line),
+        column: position.map(|(_, column)| column),
+        message,
+        suggestion: None,
+    };
+    match format {
+        Format::Yaml => serde_yaml::from_str(text).map_err(|e| {
+            let position = e.location().map(|location| (location.line(), location.column()));
+            syntax_error(format!("invalid YAML: {}", e), position)
+        }),
+        Format::Toml => toml::from_str(text).map_err(|e| {
+            let position = e.span().map(|span| line_column(text, span.start));
+            syntax_error(format!("invalid TOML: {}", e.message()), position)
+        }),
+    }
+}
+
+/// Forbid keys the schema does not list in every object schema that lists
+/// its properties.
+///
+/// A struct flattening a tagged enum lists its own properties next to a
+/// `oneOf` of the variants; those properties are copied into every variant,
+/// which is closed instead of the struct.
+fn close_objects(schema: &mut Value) {
+    match schema {
+        Value::Object(fields) => {
+            let shared = fields.get("properties").and_then(Value::as_object).cloned();
+            match (shared, fields.get_mut("oneOf")) {
+                (Some(shared), Some(Value::Array(variants))) => {
+                    for properties in variants
+                        .iter_mut()
+                        .filter_map(|variant| variant.get_mut("properties"))
+                        .filter_map(Value::as_object_mut)
+                    {
+                        for (key, property) in &shared {
+                            properties.entry(key.clone()).or_insert_with(|| property.clone());
+                        }
+                    }
+                },
+                _ => {
+                    if fields.contains_key("properties")
+                        && !fields.contains_key("additionalProperties")
+                    {
+                        fields.insert("additionalProperties".into(), Value::Bool(false));
+                    }
+                },
+            }
+            fields.values_mut().for_each(close_objects);
+        },
+        Value::Array(items) => items.iter_mut().for_each(close_objects),
+        _ => {},
+    }
+}
+
+/// A schema violation, before it is located in the file.
+struct Problem {
+    path: Vec<Segment>,
+    message: String,
+    suggestion: Option<String>,
+    unknown_field: bool,
+}
+
+/// Validate `instance` against `schema`, whose `$ref`s point into
+/// `definitions`, and add its violations to `problems` under `prefix`.
+///
+/// `oneOf`/`anyOf` failures say only that no alternative matched, so the
+/// alternative the value was most likely meant to be is validated instead
+/// to report what is actually wrong with it.
+fn collect_problems(
+    schema: &Value,
+    definitions: &Value,
+    instance: &Value,
+    prefix: &[Segment],
+    problems: &mut Vec<Problem>,
+) {
+    let Some(validator) = compile(schema, definitions) else {
+        return;
+    };
+    let Err(errors) = validator.validate(instance) else {
+        return;
+    };
+    for error in errors {
+        let relative = segments(&error.instance_path);
+        let path: Vec<Segment> = prefix.iter().chain(&relative).cloned().collect();
+        match &error.kind {
+            ValidationErrorKind::AdditionalProperties { unexpected } => {
+                let known = subschema_at(schema, definitions, &relative)
+                    .and_then(|node| node.get("properties"))
+                    .and_then(Value::as_object);
+                for key in unexpected {
+                    let suggestion = known.and_then(|known| closest(key, known.keys()));
+                    let mut path = path.clone();
+                    path.push(Segment::Key(key.to_string()));
+                    problems.push(Problem {
+                        path,
+                        message: "unknown field".to_string(),
+                        suggestion,
+                        unknown_field: true,
+                    });
+                }
+            },
+            ValidationErrorKind::OneOfNotValid | ValidationErrorKind::AnyOf => {
+                let alternatives = subschema_at(schema, definitions, &relative)
+                    .and_then(|node| node.get("oneOf").or_else(|| node.get("anyOf")))
+                    .and_then(Value::as_array);
+                let value = error.instance.as_ref();
+                match alternatives.and_then(|a| likeliest(a, definitions, value)) {
+                    Some(alternative) => {
+                        collect_problems(alternative, definitions, value, &path, problems)
+                    },
+                    None => problems.push(Problem {
+                        path,
+                        message: error.to_string(),
+                        suggestion: None,
+                        unknown_field: false,
+                    }),
+                }
+            },
+            _ => problems.push(Problem {
+                path,
+                message: error.to_string(),
+                suggestion: None,
+                unknown_field: false,
+            }),
+        }
+    }
+}
+
+/// Compile `schema` with the definitions its `$ref`s point to.
+fn compile(schema: &Value, definitions: &Value) -> Option<JSONSchema> {
+    let mut schema = schema.clone();
+    if let Some(fields) = schema.as_object_mut() {
+        fields.insert("definitions".into(), definitions.clone());
+    }
+    JSONSchema::options()
+        .with_draft(jsonschema::Draft::Draft7)
+        .compile(&schema)
+        .ok()
+}
+
+/// The alternative `value` was most likely meant to match: the one with the
+/// fewest violations, where a wrong type or tag counts heavily since it
+/// means the value is a different kind of thing.
+fn likeliest<'a>(
+    alternatives: &'a [Value],
+    definitions: &Value,
+    value: &Value,
+) -> Option<&'a Value> {
+    alternatives.iter().min_by_key(|alternative| {
+        let Some(validator) = compile(alternative, definitions) else {
+            return usize::MAX;
+        };
+        let score = match validator.validate(value) {
+            Ok(()) => 0,
+            Err(errors) => errors
+                .map(|error| match error.kind {
+                    ValidationErrorKind::Type { .. } if error.instance_path.iter().count() == 0 => {
+                        100
+                    },
+                    ValidationErrorKind::Enum { .. } | ValidationErrorKind::Constant { .. } => 10,
+                    _ => 1,
+                })
+                .sum(),
+        };
+        score
+    })
+}
+
+/// The schema describing the value at `path`, following `$ref`s.
+fn subschema_at<'a>(
+    schema: &'a Value,
+    definitions: &'a Value,
+    path: &[Segment],
+) -> Option<&'a Value> {
+    let mut node = resolve(schema, definitions)?;
+    for segment in path {
+        node = match segment {
+            Segment::Key(key) => node
+                .get("properties")
+                .and_then(|properties| properties.get(key))
+                .or_else(|| node.get("additionalProperties").filter(|s| s.is_object()))?,
+            Segment::Index(_) => node.get("items")?,
+        };
+        node = resolve(node, definitions)?;
+    }
+    Some(node)
+}
+
+/// Follow `$ref`s, and the single-item `allOf`s schemars wraps them in.
+fn resolve<'a>(mut node: &'a Value, definitions: &'a Value) -> Option<&'a Value> {
+    loop {
+        if let Some(reference) = node.get("$ref").and_then(Value::as_str) {
+            node = definitions.get(reference.strip_prefix("#/definitions/")?)?;
+        } else if let Some([only]) = node.get("allOf").and_then(Value::as_array).map(Vec::as_slice)
+        {
+            node = only;
+        } else {
+            return Some(node);
+        }
+    }
+}
+
+fn segments(pointer: &jsonschema::paths::JSONPointer) -> Vec<Segment> {
+    pointer
+        .iter()
+        .filter_map(|chunk| match chunk {
+            PathChunk::Property(key) => Some(Segment::Key(key.to_string())),
+            PathChunk::Index(index) => Some(Segment::Index(*index)),
+            PathChunk::Keyword(_) => None,
+        })
+        .collect()
+}
+
+/// The known key most similar to `key`, if any is similar enough.
+fn closest<'a>(key: &str, known: impl Iterator<Item = &'a String>) -> Option<String> {
+    known
+        .map(|candidate| {
+            (
+                strsim::normalized_damerau_levenshtein(key, candidate),
+                candidate,
+            )
+        })
+        .filter(|(similarity, _)| *similarity >= SUGGESTION_THRESHOLD)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, candidate)| candidate.clone())
+}
+
+/// `servers[0].transport.url` style rendering of a path.
+fn display_path(path: &[Segment]) -> String {
+    let mut rendered = String::new();
+    for segment in path {
+        match segment {
+            Segment::Key(key) if rendered.is_empty() => rendered.push_str(key),
+            Segment::Key(key) => {
+                rendered.push('.');
+                rendered.push_str(key);
+            },
+            Segment::Index(index) => rendered.push_str(&format!("[{}]", index)),
+        }
+    }
+    rendered
+}
+
+/// 1-based line and column of byte `offset` in `text`.
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// Line and column of the value at `path` in the file, or of the deepest
+/// part of it that could be found. Best effort: flow-style YAML and inline
+/// TOML tables are only partly understood.
+fn locate(text: &str, format: Format, path: &[Segment]) -> Option<(usize, usize)> {
+    match format {
+        Format::Yaml => locate_yaml(text, path),
+        Format::Toml => locate_toml(text, path),
+    }
+}
+
+/// A YAML line without its indentation and list markers, and the column its
+/// content starts at. Blank and comment lines have none.
+fn yaml_content(line: &str) -> Option<(usize, &str)> {
+    let mut content = line.trim_start();
+    while let Some(rest) = content
+        .strip_prefix("- ")
+        .or_else(|| content.strip_prefix('-').filter(|r| r.is_empty()))
+    {
+        content = rest.trim_start();
+    }
+    if content.is_empty() || content.starts_with('#') {
+        return None;
+    }
+    Some((line.len() - content.len(), content))
+}
+
+fn locate_yaml(text: &str, path: &[Segment]) -> Option<(usize, usize)> {
+    let lines: Vec<&str> = text.lines().collect();
+    // Line and column of the deepest segment found so far; children of a
+    // key are indented past its column, items of a list start at its line
+    let mut found: Option<(usize, usize)> = None;
+    let mut start = 0;
+    for segment in path {
+        let parent_column = found.map(|(_, column)| column);
+        let in_block = |content_column: usize| parent_column.map_or(true, |c| content_column > c);
+        let next = match segment {
+            Segment::Key(key) => lines.iter().enumerate().skip(start).find_map(|(i, line)| {
+                let (column, content) = yaml_content(line)?;
+                let key_here = content
+                    .strip_prefix(key.as_str())
+                    .or_else(|| content.strip_prefix(&format!("\"{}\"", key)))
+                    .or_else(|| content.strip_prefix(&format!("'{}'", key)))
+                    .is_some_and(|rest| rest.trim_start().starts_with(':'));
+                match (in_block(column), key_here) {
+                    (false, _) if i > start => Some(None),
+                    (true, true) => Some(Some((i, column))),
+                    _ => None,
+                }
+            }),
+            Segment::Index(index) => yaml_item(&lines, start, parent_column, *index),
+        };
+        match next.flatten() {
+            Some((line, column)) => {
+                start = match segment {
+                    Segment::Key(_) => line + 1,
+                    Segment::Index(_) => line,
+                };
+                found = Some((line, column));
+            },
+            None => break,
+        }
+    }
+    found.map(|(line, column)| (line + 1, column + 1))
+}
+
+/// Line and column of the `index`th item of the YAML list starting at
+/// line `start`. `Some(None)` means the list ended before the item.
+fn yaml_item(
+    lines: &[&str],
+    start: usize,
+    parent_column: Option<usize>,
+    index: usize,
+) -> Option<Option<(usize, usize)>> {
+    let items = lines.iter().enumerate().skip(start).filter_map(|(i, line)| {
+        let (column, _) = yaml_content(line)?;
+        let dash = line.len() - line.trim_start().len();
+        let item = line.trim_start().starts_with('-');
+        let outside = parent_column.is_some_and(|c| dash < c || (!item && column <= c));
+        Some((i, dash, item, outside))
+    });
+    let mut seen = 0;
+    let mut item_column = None;
+    for (i, dash, item, outside) in items {
+        if outside && i > start {
+            return Some(None);
+        }
+        if !item || item_column.is_some_and(|c| c != dash) {
+            continue;
+        }
+        item_column = Some(dash);
+        if seen == index {
+            return Some(Some((i, dash)));
+        }
+        seen += 1;
+    }
+    None
+}
+
+/// Keys of a dotted TOML key or table name, without quotes.
+fn toml_keys(name: &str) -> impl Iterator<Item = String> + '_ {
+    name.split('.')
+        .map(|key| key.trim().trim_matches('"').trim_matches('\'').to_string())
+}
+
+fn locate_toml(text: &str, path: &[Segment]) -> Option<(usize, usize)> {
+    let matched =
+        |candidate: &[Segment]| candidate.iter().zip(path).take_while(|(a, b)| a == b).count();
+    // Array tables seen so far and how many of each
+    let mut arrays: Vec<(Vec<Segment>, usize)> = Vec::new();
+    let mut table: Vec<Segment> = Vec::new();
+    let mut best: Option<(usize, usize, usize)> = None;
+    let mut consider = |candidate: &[Segment], line: usize, column: usize| {
+        let depth = matched(candidate);
+        if depth == candidate.len() && best.map_or(true, |(d, _, _)| depth > d) {
+            best = Some((depth, line, column));
+        }
+    };
+
+    for (i, line) in text.lines().enumerate() {
+        let content = line.trim_start();
+        let column = line.len() - content.len();
+        if let Some(name) = content.strip_prefix("[[").and_then(|r| r.split_once("]]")) {
+            let keys: Vec<Segment> = toml_keys(name.0).map(Segment::Key).collect();
+            match arrays.iter_mut().find(|(k, _)| *k == keys) {
+                Some((_, count)) => *count += 1,
+                None => arrays.push((keys.clone(), 1)),
+            }
+            table = with_array_indices(&keys, &arrays);
+            consider(&table, i, column);
+        } else if let Some(name) = content.strip_prefix('[').and_then(|r| r.split_once(']')) {
+            let keys: Vec<Segment> = toml_keys(name.0).map(Segment::Key).collect();
+            table = with_array_indices(&keys, &arrays);
+            consider(&table, i, column);
+        } else if let Some((key, value)) = content.split_once('=') {
+            let mut candidate = table.clone();
+            candidate.extend(toml_keys(key).map(Segment::Key));
+            consider(&candidate, i, column);
+
+            // Keys of an inline table on the same line
+            if matched(&candidate) == candidate.len() {
+                let offset = line.len() - value.len();
+                for (key, column) in inline_keys(line, offset, &path[candidate.len()..]) {
+                    candidate.push(key);
+                    consider(&candidate, i, column);
+                }
+            }
+        }
+    }
+    best.map(|(_, line, column)| (line + 1, column + 1))
+}
+
+/// Leading keys of `path` found in order on an inline table line after
+/// `offset`, with their columns.
+fn inline_keys(line: &str, mut offset: usize, path: &[Segment]) -> Vec<(Segment, usize)> {
+    let mut keys = Vec::new();
+    for segment in path {
+        let Segment::Key(key) = segment else {
+            break;
+        };
+        let Some(found) = line[offset..].find(&format!("{} =", key)) else {
+            break;
+        };
+        offset += found;
+        keys.push((segment.clone(), offset));
+    }
+    keys
+}
+
+/// Path of a table name, with the current index inserted after each prefix
+/// that names an array of tables.
+fn with_array_indices(keys: &[Segment], arrays: &[(Vec<Segment>, usize)]) -> Vec<Segment> {
+    let mut path = Vec::new();
+    for (depth, key) in keys.iter().enumerate() {
+        path.push(key.clone());
+        if let Some((_, count)) = arrays.iter().find(|(k, _)| k[..] == keys[..=depth]) {
+            path.push(Segment::Index(count - 1));
+        }
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problems(diagnostics: &[Diagnostic]) -> Vec<String> {
+        diagnostics.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_yaml_problems_located_with_suggestions() {
+        let yaml = r#"
+servers:
+  - id: fs
+    name: Files
+    transport:
+      type: stdio
+      comand: mcp-fs
+  - id: web
+    name: Web
+    transport: {type: http, url: "http://localhost:9000"}
+    timeout_ms: fast
+context_optimization:
+  cache:
+    max_entires: 10
+"#;
+        assert_eq!(
+            problems(&check_document(yaml, Format::Yaml, false)),
+            vec![
+                "error at servers[0].transport (line 5, column 5): \"command\" is a required property",
+                "warning at servers[0].transport.comand (line 7, column 7): unknown field, did you mean `command`?",
+                "error at servers[1].timeout_ms (line 11, column 5): \"fast\" is not of type \"integer\"",
+                "warning at context_optimization.cache.max_entires (line 14, column 5): unknown field, did you mean `max_entries`?",
+            ]
+        );
+
+        // Unknown keys become errors on request
+        let yaml = "servers: []\nproxy:\n  timout: 5\n";
+        let diagnostics = check_document(yaml, Format::Yaml, true);
+        assert_eq!(
+            problems(&diagnostics),
+            vec!["error at proxy.timout (line 3, column 3): unknown field"]
+        );
+        assert!(check_document(yaml, Format::Yaml, false)
+            .iter()
+            .all(|d| d.severity == Severity::Warning));
+
+        // Fields of a flattened enum are known to the struct holding it
+        let yaml = r#"
+servers: []
+observability:
+  alerts:
+    rules:
+      - name: down
+        type: backend_unhealthy
+        notifyy: []
+      - {name: errors, type: error_rate, above_percent: 5}
+"#;
+        assert_eq!(
+            problems(&check_document(yaml, Format::Yaml, false)),
+            vec![
+                "warning at observability.alerts.rules[0].notifyy (line 8, column 9): unknown field, did you mean `notify`?",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_toml_problems_located() {
+        let toml = r#"
+[[servers]]
+id = "a"
+name = "A"
+transport = { type = "http", url = "http://x", header = {} }
+
+[[servers]]
+id = "b"
+name = "B"
+
+[servers.transport]
+type = "http"
+ur = "http://y"
+"#;
+        assert_eq!(
+            problems(&check_document(toml, Format::Toml, false)),
+            vec![
+                "warning at servers[0].transport.header (line 5, column 48): unknown field, did you mean `headers`?",
+                "error at servers[1].transport (line 11, column 1): \"url\" is a required property",
+                "warning at servers[1].transport.ur (line 13, column 1): unknown field, did you mean `url`?",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_syntax_and_semantic_errors() {
+        let diagnostics = check_document("servers: [\n", Format::Yaml, false);
+        assert_eq!(
+            (diagnostics[0].line, diagnostics[0].column),
+            (Some(2), Some(1))
+        );
+
+        let yaml =
+            "servers: []\ncontext_optimization:\n  truncation: {enabled: true, max_bytes: 0}\n";
+        assert_eq!(
+            problems(&check_document(yaml, Format::Yaml, false)),
+            vec!["error: truncation limits must be non-zero"]
+        );
+
+        assert!(check_document("servers: []\n", Format::Yaml, true).is_empty());
+    }
+}
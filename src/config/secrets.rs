@@ -0,0 +1,297 @@
+//! Secret references in configuration values.
+//!
+//! Fields that commonly carry credentials are resolved when a configuration
+//! file is loaded, so the file itself can stay free of plaintext secrets:
+//! transport headers, STDIO environment values and `env_policy` secrets,
+//! OAuth client secrets, request signing secrets, outbound proxy passwords,
+//! listener API keys, JWT secrets, and alert notifier URLs and headers. Each value may use:
+//!
+//! - `${VAR}` anywhere in the value, replaced by that environment variable
+//!   (`$${` is a literal `${`);
+//! - `file:///path/to/secret` as the whole value, replaced by the file's
+//!   contents without the trailing newline;
+//! - `keyring://<service>/<account>` as the whole value, looked up in the OS
+//!   keyring (`security` on macOS, `secret-tool` elsewhere on Unix).
+//!
+//! Errors name the field and the reference, never a resolved value. Exports
+//! of the configuration are covered by [`super::redact`].
+
+use std::process::Command;
+
+use crate::config::{Config, TransportConfig};
+use crate::error::{Error, Result};
+
+const FILE_PREFIX: &str = "file://";
+const KEYRING_PREFIX: &str = "keyring://";
+
+/// Resolve secret references in every secret-bearing field of `config`.
+pub fn resolve_secrets(config: &mut Config) -> Result<()> {
+    for server in &mut config.servers {
+        let prefix = format!("servers[{}].transport", server.id);
+        match &mut server.transport {
+            TransportConfig::Stdio { env, .. } | TransportConfig::Docker { env, .. } => {
+                for (name, value) in env.iter_mut() {
+                    resolve_field(&format!("{}.env.{}", prefix, name), value)?;
+                }
+            },
+            TransportConfig::Http { headers, .. }
+            | TransportConfig::Sse { headers, .. }
+            | TransportConfig::StreamableHttp { headers, .. } => {
+                for (name, value) in headers.iter_mut() {
+                    resolve_field(&format!("{}.headers.{}", prefix, name), value)?;
+                }
+            },
+            TransportConfig::Grpc { metadata, .. } => {
+                for (name, value) in metadata.iter_mut() {
+                    resolve_field(&format!("{}.metadata.{}", prefix, name), value)?;
+                }
+            },
+            TransportConfig::Unix { .. } => {},
+        }
+        for (name, value) in server.env_policy.secrets.iter_mut() {
+            resolve_field(
+                &format!("servers[{}].env_policy.secrets.{}", server.id, name),
+                value,
+            )?;
+        }
+        if let TransportConfig::Http {
+            oauth: Some(oauth), ..
+        }
+        | TransportConfig::StreamableHttp {
+            oauth: Some(oauth), ..
+        } = &mut server.transport
+        {
+            if let Some(secret) = oauth.client_secret.as_mut() {
+                resolve_field(&format!("{}.oauth.client_secret", prefix), secret)?;
+            }
+        }
+        if let TransportConfig::Http {
+            signing: Some(signing),
+            ..
+        }
+        | TransportConfig::StreamableHttp {
+            signing: Some(signing),
+            ..
+        } = &mut server.transport
+        {
+            resolve_field(&format!("{}.signing.secret", prefix), &mut signing.secret)?;
+        }
+        if let Some(password) = proxy_password(&mut server.transport) {
+            resolve_field(&format!("{}.proxy.password", prefix), password)?;
+        }
+    }
+
+    let global_proxy = config.proxy.outbound_proxy.as_mut();
+    if let Some(password) = global_proxy.and_then(|proxy| proxy.password.as_mut()) {
+        resolve_field("proxy.outbound_proxy.password", password)?;
+    }
+
+    for listener in &mut config.server.listeners {
+        let Some(auth) = &mut listener.auth else {
+            continue;
+        };
+        let prefix = format!("server.listeners[{}].auth", listener.name);
+        for (i, key) in auth.api_keys.iter_mut().enumerate() {
+            resolve_field(&format!("{}.api_keys[{}]", prefix, i), key.key_mut())?;
+        }
+        if let Some(secret) = auth.jwt.as_mut().and_then(|jwt| jwt.secret.as_mut()) {
+            resolve_field(&format!("{}.jwt.secret", prefix), secret)?;
+        }
+    }
+
+    if let Some(token) = config.cluster.token.as_mut() {
+        resolve_field("cluster.token", token)?;
+    }
+
+    for notifier in &mut config.observability.alerts.notifiers {
+        let prefix = format!("observability.alerts.notifiers[{}]", notifier.name);
+        resolve_field(&format!("{}.url", prefix), &mut notifier.url)?;
+        for (name, value) in notifier.headers.iter_mut() {
+            resolve_field(&format!("{}.headers.{}", prefix, name), value)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolve the secret references in one configuration value.
+pub fn resolve_value(value: &str) -> std::result::Result<String, String> {
+    if let Some(path) = value.strip_prefix(FILE_PREFIX) {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read secret file {}: {}", path, e))?;
+        return Ok(content.trim_end_matches(['\n', '\r']).to_string());
+    }
+    if let Some(reference) = value.strip_prefix(KEYRING_PREFIX) {
+        let (service, account) = reference
+            .split_once('/')
+            .ok_or_else(|| format!("keyring reference {} must be service/account", value))?;
+        return keyring_lookup(service, account);
+    }
+    interpolate_env(value)
+}
+
+/// The password of a transport's own outbound proxy, if any.
+fn proxy_password(transport: &mut TransportConfig) -> Option<&mut String> {
+    match transport {
+        TransportConfig::Http { proxy, .. }
+        | TransportConfig::Sse { proxy, .. }
+        | TransportConfig::StreamableHttp { proxy, .. } => {
+            proxy.as_mut().and_then(|proxy| proxy.password.as_mut())
+        },
+        _ => None,
+    }
+}
+
+fn resolve_field(field: &str, value: &mut String) -> Result<()> {
+    *value = resolve_value(value).map_err(|e| Error::Config(format!("{}: {}", field, e)))?;
+    Ok(())
+}
+
+/// Replace `${VAR}` with the environment variable's value.
+fn interpolate_env(value: &str) -> std::result::Result<String, String> {
+    let mut resolved = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find('$') {
+        resolved.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(after) = rest.strip_prefix("$${") {
+            resolved.push_str("${");
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("${") {
+            let end = after.find('}').ok_or_else(|| "unterminated ${ in value".to_string())?;
+            let name = &after[..end];
+            let var = std::env::var(name)
+                .map_err(|_| format!("environment variable {} is not set", name))?;
+            resolved.push_str(&var);
+            rest = &after[end + 1..];
+        } else {
+            resolved.push('$');
+            rest = &rest[1..];
+        }
+    }
+
+    resolved.push_str(rest);
+    Ok(resolved)
+}
+
+/// Look up a password in the OS keyring through the platform's CLI.
+fn keyring_lookup(service: &str, account: &str) -> std::result::Result<String, String> {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("security");
+        command.args(["find-generic-password", "-s", service, "-a", account, "-w"]);
+        command
+    } else if cfg!(unix) {
+        let mut command = Command::new("secret-tool");
+        command.args(["lookup", "service", service, "account", account]);
+        command
+    } else {
+        return Err("OS keyring lookups are not supported on this platform".to_string());
+    };
+
+    let output = command.output().map_err(|e| {
+        format!(
+            "cannot run keyring lookup for {}/{}: {}",
+            service, account, e
+        )
+    })?;
+    if !output.status.success() {
+        return Err(format!("no keyring entry for {}/{}", service, account));
+    }
+    let secret = String::from_utf8(output.stdout)
+        .map_err(|_| format!("keyring entry for {}/{} is not UTF-8", service, account))?;
+    Ok(secret.trim_end_matches(['\n', '\r']).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_interpolation() {
+        std::env::set_var("ONLY1MCP_TEST_SECRET_TOKEN", "t0ken");
+
+        assert_eq!(
+            resolve_value("Bearer ${ONLY1MCP_TEST_SECRET_TOKEN}").unwrap(),
+            "Bearer t0ken"
+        );
+        assert_eq!(
+            resolve_value("cost: $5, $${HOME}").unwrap(),
+            "cost: $5, ${HOME}"
+        );
+
+        let err = resolve_value("${ONLY1MCP_TEST_SECRET_UNSET}").unwrap_err();
+        assert!(err.contains("ONLY1MCP_TEST_SECRET_UNSET"), "{}", err);
+        assert!(resolve_value("${UNTERMINATED").is_err());
+    }
+
+    #[test]
+    fn test_file_reference() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("token");
+        std::fs::write(&path, "from-file\n").unwrap();
+
+        let reference = format!("file://{}", path.display());
+        assert_eq!(resolve_value(&reference).unwrap(), "from-file");
+        assert!(resolve_value("file:///nonexistent/only1mcp-secret").is_err());
+    }
+
+    #[test]
+    fn test_resolve_secrets_in_config() {
+        std::env::set_var("ONLY1MCP_TEST_SECRET_HEADER", "abc");
+        std::env::set_var("ONLY1MCP_TEST_SECRET_KEY", "listener-key");
+        let mut config: Config = serde_yaml::from_str(
+            r#"
+server:
+  listeners:
+    - name: mcp
+      bind: 127.0.0.1:0
+      auth:
+        api_keys: ["${ONLY1MCP_TEST_SECRET_KEY}"]
+servers:
+  - id: api
+    name: API
+    transport:
+      type: http
+      url: http://localhost:9000
+      headers:
+        Authorization: "Bearer ${ONLY1MCP_TEST_SECRET_HEADER}"
+      signing:
+        secret: "hmac-${ONLY1MCP_TEST_SECRET_HEADER}"
+      proxy:
+        http_proxy: http://proxy:3128
+        username: only1mcp
+        password: "proxy-${ONLY1MCP_TEST_SECRET_HEADER}"
+  - id: db
+    name: DB
+    transport:
+      type: stdio
+      command: db-server
+      env:
+        PGPASSWORD: "${ONLY1MCP_TEST_SECRET_MISSING}"
+"#,
+        )
+        .unwrap();
+
+        let err = resolve_secrets(&mut config).unwrap_err().to_string();
+        assert!(
+            err.contains("servers[db].transport.env.PGPASSWORD"),
+            "{}",
+            err
+        );
+
+        config.servers.pop();
+        resolve_secrets(&mut config).unwrap();
+        let TransportConfig::Http { headers, .. } = &config.servers[0].transport else {
+            panic!("expected HTTP transport");
+        };
+        assert_eq!(headers["Authorization"], "Bearer abc");
+        let signing = config.servers[0].transport.signing().unwrap();
+        assert_eq!(signing.secret, "hmac-abc");
+        let proxy = config.servers[0].transport.proxy().unwrap();
+        assert_eq!(proxy.password.as_deref(), Some("proxy-abc"));
+        let auth = config.server.listeners[0].auth.as_ref().unwrap();
+        assert_eq!(auth.api_keys[0].key(), "listener-key");
+    }
+}
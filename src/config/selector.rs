@@ -0,0 +1,146 @@
+//! Tool selectors for groups and policies.
+//!
+//! A selector is written as a string so it reads naturally in config files:
+//!
+//! - `tag:<tag>` matches tools carrying the tag, either on the tool itself
+//!   (`tool_metadata`) or inherited from its server (`tags`)
+//! - `server:<id>` matches every tool of one backend
+//! - anything else (optionally prefixed with `tool:`) is a glob over the tool
+//!   name, with the same `*`/`?` syntax as `routing.tools`
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::McpServerConfig;
+use crate::error::{Error, Result};
+use crate::routing::tool_matcher::glob_match;
+
+/// Selects a set of tools by tag, owning server or name.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum ToolSelector {
+    /// Tools whose effective tags include this tag
+    Tag(String),
+    /// All tools of the backend with this ID
+    Server(String),
+    /// Tools whose name matches this glob
+    Name(String),
+}
+
+impl ToolSelector {
+    /// Whether `tool`, served by `server`, is selected.
+    pub fn matches(&self, server: &McpServerConfig, tool: &str) -> bool {
+        match self {
+            Self::Tag(tag) => server.tool_tags(tool).any(|t| t == tag),
+            Self::Server(id) => server.id == *id,
+            Self::Name(pattern) => glob_match(pattern, tool),
+        }
+    }
+}
+
+impl FromStr for ToolSelector {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let selector = match s.split_once(':') {
+            Some(("tag", tag)) => Self::Tag(tag.to_string()),
+            Some(("server", id)) => Self::Server(id.to_string()),
+            Some(("tool", pattern)) => Self::Name(pattern.to_string()),
+            _ => Self::Name(s.to_string()),
+        };
+        let (Self::Tag(value) | Self::Server(value) | Self::Name(value)) = &selector;
+        if value.is_empty() {
+            return Err(Error::Config(format!("Empty tool selector '{}'", s)));
+        }
+        Ok(selector)
+    }
+}
+
+impl TryFrom<String> for ToolSelector {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl From<ToolSelector> for String {
+    fn from(selector: ToolSelector) -> Self {
+        selector.to_string()
+    }
+}
+
+/// Selectors are plain strings in config files.
+impl schemars::JsonSchema for ToolSelector {
+    fn schema_name() -> String {
+        "ToolSelector".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        String::json_schema(gen)
+    }
+}
+
+impl fmt::Display for ToolSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tag(tag) => write!(f, "tag:{}", tag),
+            Self::Server(id) => write!(f, "server:{}", id),
+            Self::Name(pattern) => write!(f, "{}", pattern),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server() -> McpServerConfig {
+        serde_yaml::from_str(
+            r#"
+id: fs
+name: Files
+transport: {type: stdio, command: mcp-fs}
+tags: [local]
+tool_metadata:
+  delete_file: {tags: [destructive], notes: "Irreversible"}
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_selectors() {
+        assert_eq!(
+            "tag:destructive".parse::<ToolSelector>().unwrap(),
+            ToolSelector::Tag("destructive".into())
+        );
+        assert_eq!(
+            "server:fs".parse::<ToolSelector>().unwrap(),
+            ToolSelector::Server("fs".into())
+        );
+        assert_eq!(
+            "tool:read_*".parse::<ToolSelector>().unwrap(),
+            ToolSelector::Name("read_*".into())
+        );
+        assert_eq!(
+            "read_*".parse::<ToolSelector>().unwrap(),
+            ToolSelector::Name("read_*".into())
+        );
+        assert!("tag:".parse::<ToolSelector>().is_err());
+    }
+
+    #[test]
+    fn test_tag_selector_includes_server_tags() {
+        let server = server();
+        let destructive: ToolSelector = "tag:destructive".parse().unwrap();
+        let local: ToolSelector = "tag:local".parse().unwrap();
+
+        assert!(destructive.matches(&server, "delete_file"));
+        assert!(!destructive.matches(&server, "read_file"));
+        assert!(local.matches(&server, "read_file"));
+        assert!(local.matches(&server, "delete_file"));
+    }
+}
@@ -1,6 +1,10 @@
 //! Configuration validation logic
 
-use crate::config::Config;
+use crate::config::{
+    AlertCondition, Config, EnvInherit, EnvPolicyConfig, ListenAddress, McpServerConfig,
+    OutboundProxyConfig, RestartPolicy, RouteGroup, SandboxConfig, SandboxStrategy,
+    TransformConfig, TransportConfig, CACHE_LAYERS,
+};
 use crate::error::{Error, Result};
 
 impl Config {
@@ -31,6 +35,93 @@ impl Config {
             }
         }
 
+        // Validate listeners
+        let mut listener_names = std::collections::HashSet::new();
+        for listener in &self.server.listeners {
+            if listener.name.is_empty() {
+                return Err(Error::Config("Listener name cannot be empty".to_string()));
+            }
+            if !listener_names.insert(listener.name.as_str()) {
+                return Err(Error::Config(format!(
+                    "Duplicate listener name: {}",
+                    listener.name
+                )));
+            }
+            if listener.routes.is_empty() {
+                return Err(Error::Config(format!(
+                    "Listener {} serves no routes",
+                    listener.name
+                )));
+            }
+            let address = listener.address()?;
+            if listener.ipv6_only && !matches!(address, ListenAddress::Tcp(addr) if addr.is_ipv6())
+            {
+                return Err(Error::Config(format!(
+                    "Listener {}: ipv6_only requires an IPv6 address",
+                    listener.name
+                )));
+            }
+            if listener.tls.enabled {
+                if matches!(address, ListenAddress::Unix(_)) {
+                    return Err(Error::Config(format!(
+                        "Listener {}: TLS is not supported on Unix sockets",
+                        listener.name
+                    )));
+                }
+                if listener.tls.cert_path.is_none() || listener.tls.key_path.is_none() {
+                    return Err(Error::Config(format!(
+                        "Listener {}: TLS enabled but cert_path or key_path not specified",
+                        listener.name
+                    )));
+                }
+            }
+            if listener.tls.client_ca_path.is_none() && !listener.tls.client_roles.is_empty() {
+                return Err(Error::Config(format!(
+                    "Listener {}: client_roles requires client_ca_path",
+                    listener.name
+                )));
+            }
+            if let Some(auth) = &listener.auth {
+                if auth.api_keys.is_empty() && auth.jwt.is_none() {
+                    return Err(Error::Config(format!(
+                        "Listener {}: auth requires at least one API key or jwt",
+                        listener.name
+                    )));
+                }
+                if auth
+                    .jwt
+                    .as_ref()
+                    .is_some_and(|jwt| jwt.secret.is_none() && jwt.secret_env.is_none())
+                {
+                    return Err(Error::Config(format!(
+                        "Listener {}: jwt requires secret or secret_env",
+                        listener.name
+                    )));
+                }
+            }
+        }
+        if self.server.listeners.is_empty() {
+            // The default listener on server.host:server.port
+            self.server.effective_listeners()[0].address()?;
+        }
+        if let Some(path) = &self.server.listen_socket {
+            if path.as_os_str().is_empty() {
+                return Err(Error::Config(
+                    "server.listen_socket cannot be empty".to_string(),
+                ));
+            }
+            if listener_names.contains("socket") {
+                return Err(Error::Config(
+                    "server.listen_socket conflicts with the listener named socket".to_string(),
+                ));
+            }
+        }
+        if !self.server.listeners.is_empty()
+            && !self.server.listeners.iter().any(|l| l.serves(RouteGroup::Mcp))
+        {
+            tracing::warn!("No listener serves the MCP routes");
+        }
+
         // Validate backend servers
         if self.servers.is_empty() {
             tracing::warn!("No backend servers configured");
@@ -52,6 +143,189 @@ impl Config {
                     server.id
                 )));
             }
+            if server.request_timeout_ms() == 0 {
+                return Err(Error::Config(format!(
+                    "Server {} has zero request timeout",
+                    server.id
+                )));
+            }
+            if server.retry.max_attempts == 0 {
+                return Err(Error::Config(format!(
+                    "Server {} retry max_attempts must be at least 1",
+                    server.id
+                )));
+            }
+
+            if let Some(cost) = &server.cost {
+                let rates = std::iter::once(&cost.rates).chain(cost.tools.values());
+                if rates
+                    .flat_map(|r| [r.per_call, r.per_1k_tokens])
+                    .any(|v| !v.is_finite() || v < 0.0)
+                {
+                    return Err(Error::Config(format!(
+                        "Server {} cost rates must be non-negative numbers",
+                        server.id
+                    )));
+                }
+            }
+
+            if let Some(tls) = server.transport.tls() {
+                if tls.client_cert_path.is_some() != tls.client_key_path.is_some() {
+                    return Err(Error::Config(format!(
+                        "Server {} tls requires both client_cert_path and client_key_path",
+                        server.id
+                    )));
+                }
+            }
+
+            if let Some(oauth) = server.transport.oauth() {
+                if oauth.token_url.is_empty() || oauth.client_id.is_empty() {
+                    return Err(Error::Config(format!(
+                        "Server {} oauth requires token_url and client_id",
+                        server.id
+                    )));
+                }
+                if oauth.client_secret.is_none() && oauth.client_secret_env.is_none() {
+                    return Err(Error::Config(format!(
+                        "Server {} oauth requires client_secret or client_secret_env",
+                        server.id
+                    )));
+                }
+            }
+
+            if let Some(signing) = server.transport.signing() {
+                if signing.secret.is_empty() {
+                    return Err(Error::Config(format!(
+                        "Server {} signing requires a secret",
+                        server.id
+                    )));
+                }
+                let invalid = std::iter::once(&signing.header)
+                    .chain(&signing.timestamp_header)
+                    .find(|header| {
+                        reqwest::header::HeaderName::from_bytes(header.as_bytes()).is_err()
+                    });
+                if let Some(header) = invalid {
+                    return Err(Error::Config(format!(
+                        "Server {} signing header {:?} is not a valid header name",
+                        server.id, header
+                    )));
+                }
+            }
+
+            if let Some(proxy) = server.transport.proxy() {
+                validate_outbound_proxy(&format!("servers[{}].transport.proxy", server.id), proxy)?;
+            }
+
+            if let TransportConfig::Grpc { endpoint, .. } = &server.transport {
+                if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+                    return Err(Error::Config(format!(
+                        "Server {} grpc endpoint must be an http:// or https:// URL",
+                        server.id
+                    )));
+                }
+            }
+            if let TransportConfig::Unix { path } = &server.transport {
+                if path.as_os_str().is_empty() {
+                    return Err(Error::Config(format!(
+                        "Server {} unix transport requires a socket path",
+                        server.id
+                    )));
+                }
+            }
+            if let TransportConfig::Docker { image, cpus, .. } = &server.transport {
+                if image.is_empty() {
+                    return Err(Error::Config(format!(
+                        "Server {} docker transport requires an image",
+                        server.id
+                    )));
+                }
+                if cpus.is_some_and(|cpus| cpus <= 0.0) {
+                    return Err(Error::Config(format!(
+                        "Server {} docker cpus must be positive",
+                        server.id
+                    )));
+                }
+            }
+
+            let restart = &server.restart;
+            if restart.policy != RestartPolicy::Never
+                && (restart.max_restarts == 0 || restart.window_seconds == 0)
+            {
+                return Err(Error::Config(format!(
+                    "Server {} restart requires positive max_restarts and window_seconds",
+                    server.id
+                )));
+            }
+            if restart.initial_backoff_ms > restart.max_backoff_ms {
+                return Err(Error::Config(format!(
+                    "Server {} restart initial_backoff_ms exceeds max_backoff_ms",
+                    server.id
+                )));
+            }
+
+            let resources = &server.resources;
+            if resources.max_memory_mb == Some(0) || resources.max_cpu_percent == Some(0) {
+                return Err(Error::Config(format!(
+                    "Server {} resources limits must be positive",
+                    server.id
+                )));
+            }
+            validate_sandbox(&server.id, &server.sandbox)?;
+            validate_env_policy(server)?;
+            if let TransportConfig::Stdio { command, args, .. } = &server.transport {
+                let pinned = crate::transport::npx::parse_args(args)
+                    .is_some_and(|(spec, _)| spec.version.is_some());
+                if self.proxy.npx.require_pinned && command == "npx" && !pinned {
+                    return Err(Error::Config(format!(
+                        "Server {} npx package must be pinned as package@version",
+                        server.id
+                    )));
+                }
+            }
+
+            if let Some(mirror) = &server.mirror {
+                let Some(target) = self.servers.iter().find(|s| s.id == mirror.server) else {
+                    return Err(Error::Config(format!(
+                        "Server {} mirrors to unknown server {}",
+                        server.id, mirror.server
+                    )));
+                };
+                if target.id == server.id {
+                    return Err(Error::Config(format!(
+                        "Server {} cannot mirror to itself",
+                        server.id
+                    )));
+                }
+                if target.mirror.is_some() {
+                    return Err(Error::Config(format!(
+                        "Server {} cannot mirror to {}, which mirrors in turn",
+                        server.id, mirror.server
+                    )));
+                }
+                if mirror.percent == 0 || mirror.percent > 100 {
+                    return Err(Error::Config(format!(
+                        "Server {} mirror percent must be between 1 and 100",
+                        server.id
+                    )));
+                }
+            }
+
+            validate_transforms(&server.id, &server.transforms)?;
+
+            // Tags are matched verbatim by `tag:` selectors
+            let tags = server
+                .tags
+                .iter()
+                .chain(server.tool_metadata.values().flat_map(|m| m.tags.iter()));
+            for tag in tags {
+                if tag.is_empty() || tag.contains(char::is_whitespace) {
+                    return Err(Error::Config(format!(
+                        "Server {} has invalid tag '{}' (tags must be non-empty without whitespace)",
+                        server.id, tag
+                    )));
+                }
+            }
 
             // Validate health check config
             if server.health_check.enabled {
@@ -108,6 +382,137 @@ impl Config {
             ));
         }
 
+        if self.proxy.connection_pool.stdio_processes_per_server == 0 {
+            return Err(Error::Config(
+                "stdio_processes_per_server must be non-zero".to_string(),
+            ));
+        }
+
+        // Validate failover groups
+        let mut group_names = std::collections::HashSet::new();
+        let mut grouped = std::collections::HashSet::new();
+        for group in &self.proxy.routing.failover_groups {
+            if group.name.is_empty() {
+                return Err(Error::Config(
+                    "Failover group name cannot be empty".to_string(),
+                ));
+            }
+            if !group_names.insert(group.name.as_str()) {
+                return Err(Error::Config(format!(
+                    "Duplicate failover group: {}",
+                    group.name
+                )));
+            }
+            if group.standbys.is_empty() {
+                return Err(Error::Config(format!(
+                    "Failover group {} has no standbys",
+                    group.name
+                )));
+            }
+            for member in group.members() {
+                if !self.servers.iter().any(|server| server.id == *member) {
+                    return Err(Error::Config(format!(
+                        "Failover group {} references unknown server {}",
+                        group.name, member
+                    )));
+                }
+                if !grouped.insert(member.as_str()) {
+                    return Err(Error::Config(format!(
+                        "Server {} belongs to more than one failover group",
+                        member
+                    )));
+                }
+            }
+        }
+
+        // Validate traffic splits
+        let mut services = std::collections::HashSet::new();
+        let mut split = std::collections::HashSet::new();
+        for traffic_split in &self.proxy.routing.traffic_splits {
+            let service = &traffic_split.service;
+            if service.is_empty() {
+                return Err(Error::Config(
+                    "Traffic split service cannot be empty".to_string(),
+                ));
+            }
+            if !services.insert(service.as_str()) {
+                return Err(Error::Config(format!(
+                    "Duplicate traffic split: {}",
+                    service
+                )));
+            }
+            if traffic_split.variants.len() < 2 {
+                return Err(Error::Config(format!(
+                    "Traffic split {} needs at least two variants",
+                    service
+                )));
+            }
+            if traffic_split.variants.iter().all(|variant| variant.weight == 0) {
+                return Err(Error::Config(format!(
+                    "Traffic split {} has no variant with a non-zero weight",
+                    service
+                )));
+            }
+            for variant in &traffic_split.variants {
+                let member = variant.server.as_str();
+                if !self.servers.iter().any(|server| server.id == member) {
+                    return Err(Error::Config(format!(
+                        "Traffic split {} references unknown server {}",
+                        service, member
+                    )));
+                }
+                if grouped.contains(member) {
+                    return Err(Error::Config(format!(
+                        "Server {} cannot be in both a failover group and a traffic split",
+                        member
+                    )));
+                }
+                if !split.insert(member) {
+                    return Err(Error::Config(format!(
+                        "Server {} belongs to more than one traffic split",
+                        member
+                    )));
+                }
+            }
+        }
+
+        // Validate front-door instances
+        let front_door = &self.proxy.routing.front_door;
+        if front_door.enabled && front_door.instances.is_empty() {
+            return Err(Error::Config(
+                "front_door.instances cannot be empty when the front door is enabled".to_string(),
+            ));
+        }
+        for instance in &front_door.instances {
+            match self.servers.iter().find(|server| server.id == *instance) {
+                None => {
+                    return Err(Error::Config(format!(
+                        "front_door references unknown server {}",
+                        instance
+                    )));
+                },
+                Some(server) if !matches!(server.transport, TransportConfig::Http { .. }) => {
+                    return Err(Error::Config(format!(
+                        "front_door instance {} must use the http transport",
+                        instance
+                    )));
+                },
+                Some(_) => {},
+            }
+        }
+
+        // Validate payload size limits
+        let limits = &self.proxy.limits;
+        for (name, value) in [
+            ("max_request_bytes", limits.max_request_bytes),
+            ("max_response_bytes", limits.max_response_bytes),
+            ("max_stdio_line_bytes", limits.max_stdio_line_bytes),
+        ] {
+            if value == 0 {
+                return Err(Error::Config(format!("limits.{} must be non-zero", name)));
+            }
+        }
+
         // Validate cache config
         if self.context_optimization.cache.enabled {
             if self.context_optimization.cache.max_entries == 0 {
@@ -120,6 +525,39 @@ impl Config {
                     "cache ttl_seconds must be non-zero".to_string(),
                 ));
             }
+            for (layer, budget) in &self.context_optimization.cache.max_bytes {
+                if !CACHE_LAYERS.contains(&layer.as_str()) {
+                    return Err(Error::Config(format!(
+                        "unknown cache layer '{}' in max_bytes (expected one of {})",
+                        layer,
+                        CACHE_LAYERS.join(", ")
+                    )));
+                }
+                if *budget == 0 {
+                    return Err(Error::Config(format!(
+                        "cache max_bytes for {} must be non-zero",
+                        layer
+                    )));
+                }
+            }
+            let compression = &self.context_optimization.cache.compression;
+            if compression.enabled {
+                if !(1..=22).contains(&compression.level) {
+                    return Err(Error::Config(format!(
+                        "cache compression level must be between 1 and 22, got {}",
+                        compression.level
+                    )));
+                }
+                if let Some(layer) =
+                    compression.layers.iter().find(|layer| !CACHE_LAYERS.contains(&layer.as_str()))
+                {
+                    return Err(Error::Config(format!(
+                        "unknown cache compression layer '{}' (expected one of {})",
+                        layer,
+                        CACHE_LAYERS.join(", ")
+                    )));
+                }
+            }
         }
 
         // Validate batching config
@@ -131,31 +569,1223 @@ impl Config {
             ));
         }
 
-        Ok(())
-    }
-}
+        // Validate catalog config
+        if self.context_optimization.catalog.refresher_enabled()
+            && self.context_optimization.catalog.refresh_interval_seconds == 0
+        {
+            return Err(Error::Config(
+                "catalog refresh_interval_seconds must be non-zero".to_string(),
+            ));
+        }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        // Validate truncation config
+        let truncation = &self.context_optimization.truncation;
+        let zero_limit = truncation.max_bytes == 0
+            || truncation.max_tokens == Some(0)
+            || truncation
+                .tools
+                .values()
+                .any(|limits| limits.max_bytes == Some(0) || limits.max_tokens == Some(0));
+        if truncation.enabled && zero_limit {
+            return Err(Error::Config(
+                "truncation limits must be non-zero".to_string(),
+            ));
+        }
 
-    #[test]
-    fn test_valid_config() {
-        let config = Config::default();
-        assert!(config.validate().is_ok());
-    }
+        // Validate slim catalog config
+        if self.context_optimization.slim.header.is_empty() {
+            return Err(Error::Config(
+                "slim catalog mode header must not be empty".to_string(),
+            ));
+        }
 
-    #[test]
-    fn test_invalid_port() {
-        let mut config = Config::default();
-        config.server.port = 0;
-        assert!(config.validate().is_err());
-    }
+        let mut policy_roles = std::collections::HashSet::new();
+        for policy in &self.auth.policies {
+            if policy.role.is_empty() {
+                return Err(Error::Config("Policy role cannot be empty".to_string()));
+            }
+            if !policy_roles.insert(policy.role.as_str()) {
+                return Err(Error::Config(format!(
+                    "Duplicate policy for role: {}",
+                    policy.role
+                )));
+            }
+        }
 
-    #[test]
-    fn test_tls_without_cert() {
-        let mut config = Config::default();
-        config.server.tls.enabled = true;
-        assert!(config.validate().is_err());
+        if self.backups.enabled && self.backups.keep == 0 {
+            return Err(Error::Config(
+                "backups keep must be non-zero (set enabled: false to disable backups)".to_string(),
+            ));
+        }
+
+        let recording = &self.observability.recording;
+        if recording.enabled && recording.path.as_os_str().is_empty() {
+            return Err(Error::Config(
+                "recording path cannot be empty when recording is enabled".to_string(),
+            ));
+        }
+
+        let adaptive = &self.context_optimization.batching.adaptive;
+        if adaptive.enabled {
+            if adaptive.min_window_ms == 0 || adaptive.min_window_ms > adaptive.max_window_ms {
+                return Err(Error::Config(
+                    "batching.adaptive needs 0 < min_window_ms <= max_window_ms".to_string(),
+                ));
+            }
+            if adaptive.target_p95_ms == 0 {
+                return Err(Error::Config(
+                    "batching.adaptive.target_p95_ms must be non-zero".to_string(),
+                ));
+            }
+        }
+
+        if let Some(unknown) = self
+            .proxy
+            .admission
+            .servers
+            .keys()
+            .find(|id| !self.servers.iter().any(|server| server.id == **id))
+        {
+            return Err(Error::Config(format!(
+                "proxy.admission.servers names unknown server {}",
+                unknown
+            )));
+        }
+
+        let initialization = &self.proxy.initialization;
+        if initialization.max_parallel == 0 || initialization.timeout_ms == 0 {
+            return Err(Error::Config(
+                "proxy.initialization max_parallel and timeout_ms must be non-zero".to_string(),
+            ));
+        }
+
+        for (index, fault) in self.proxy.chaos.faults.iter().enumerate() {
+            if fault.servers.is_empty() {
+                return Err(Error::Config(format!(
+                    "proxy.chaos.faults[{}] names no servers",
+                    index
+                )));
+            }
+            if [
+                fault.latency_percent,
+                fault.drop_percent,
+                fault.error_percent,
+            ]
+            .iter()
+            .any(|&percent| percent > 100)
+            {
+                return Err(Error::Config(format!(
+                    "proxy.chaos.faults[{}] percentages must be at most 100",
+                    index
+                )));
+            }
+            if u16::from(fault.drop_percent) + u16::from(fault.error_percent) > 100 {
+                return Err(Error::Config(format!(
+                    "proxy.chaos.faults[{}] drop_percent and error_percent add up to more than 100",
+                    index
+                )));
+            }
+        }
+
+        self.validate_alerts()?;
+        self.validate_cluster()?;
+
+        let docker = &self.discovery.docker;
+        if docker.enabled {
+            if docker.poll_interval_ms == 0 {
+                return Err(Error::Config(
+                    "discovery.docker.poll_interval_ms must be non-zero".to_string(),
+                ));
+            }
+            if docker.socket.as_os_str().is_empty() {
+                return Err(Error::Config(
+                    "discovery.docker.socket cannot be empty".to_string(),
+                ));
+            }
+        }
+
+        self.validate_gitops()?;
+        self.validate_scripts()?;
+        self.validate_composite_tools()?;
+        self.validate_tool_aliases()?;
+        self.validate_redaction()?;
+
+        if self.proxy.sanitization.max_depth == 0 {
+            return Err(Error::Config(
+                "proxy.sanitization.max_depth must be non-zero".to_string(),
+            ));
+        }
+        if let Some(proxy) = &self.proxy.outbound_proxy {
+            validate_outbound_proxy("proxy.outbound_proxy", proxy)?;
+        }
+        if self.server.upgrade_timeout_seconds == 0 {
+            return Err(Error::Config(
+                "server.upgrade_timeout_seconds must be non-zero".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Check that the configured scripts compile and can be stopped.
+    fn validate_scripts(&self) -> Result<()> {
+        let scripts = &self.proxy.scripts;
+        if scripts.max_operations == 0 {
+            return Err(Error::Config(
+                "proxy.scripts.max_operations must be non-zero".to_string(),
+            ));
+        }
+        let hooks = [
+            ("on_request", &scripts.on_request),
+            ("on_route", &scripts.on_route),
+            ("on_response", &scripts.on_response),
+        ];
+        for (hook, path) in hooks {
+            if let Some(path) = path {
+                crate::proxy::scripts::check(path)
+                    .map_err(|e| Error::Config(format!("proxy.scripts.{}: {}", hook, e)))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that composite tools have distinct names, call only backend
+    /// tools and fill in their templates from what is known.
+    fn validate_composite_tools(&self) -> Result<()> {
+        let tools = &self.proxy.composite_tools;
+        for (i, tool) in tools.iter().enumerate() {
+            if tool.name.is_empty() {
+                return Err(Error::Config(
+                    "proxy.composite_tools: a tool has an empty name".to_string(),
+                ));
+            }
+            if tools[..i].iter().any(|other| other.name == tool.name) {
+                return Err(Error::Config(format!(
+                    "proxy.composite_tools: duplicate tool name {}",
+                    tool.name
+                )));
+            }
+            let invalid =
+                |e: String| Error::Config(format!("proxy.composite_tools.{}: {}", tool.name, e));
+            crate::proxy::composite::check(tool).map_err(invalid)?;
+            if let Some(step) =
+                tool.steps.iter().find(|step| tools.iter().any(|other| other.name == step.tool))
+            {
+                return Err(invalid(format!(
+                    "step {} calls composite tool {}",
+                    step.id, step.tool
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that tool aliases have distinct names, taken by no composite
+    /// tool, and targets on defined servers.
+    fn validate_tool_aliases(&self) -> Result<()> {
+        let aliases = &self.proxy.tool_aliases;
+        for (i, alias) in aliases.iter().enumerate() {
+            if alias.name.is_empty() {
+                return Err(Error::Config(
+                    "proxy.tool_aliases: an alias has an empty name".to_string(),
+                ));
+            }
+            let taken = aliases[..i].iter().any(|other| other.name == alias.name)
+                || self.proxy.composite_tools.iter().any(|tool| tool.name == alias.name);
+            if taken {
+                return Err(Error::Config(format!(
+                    "proxy.tool_aliases: name {} is already taken",
+                    alias.name
+                )));
+            }
+            if alias.targets.is_empty() {
+                return Err(Error::Config(format!(
+                    "proxy.tool_aliases.{}: no targets",
+                    alias.name
+                )));
+            }
+            for target in &alias.targets {
+                if !self.servers.iter().any(|server| server.id == target.server) {
+                    return Err(Error::Config(format!(
+                        "proxy.tool_aliases.{}: unknown server {}",
+                        alias.name, target.server
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that custom redaction patterns have distinct names and compile.
+    fn validate_redaction(&self) -> Result<()> {
+        let redaction = &self.proxy.redaction;
+        for (i, pattern) in redaction.custom.iter().enumerate() {
+            if pattern.name.is_empty() {
+                return Err(Error::Config(
+                    "proxy.redaction.custom: a pattern has an empty name".to_string(),
+                ));
+            }
+            let taken = redaction.custom[..i].iter().any(|other| other.name == pattern.name)
+                || redaction.builtin.iter().any(|builtin| builtin.as_str() == pattern.name);
+            if taken {
+                return Err(Error::Config(format!(
+                    "proxy.redaction.custom: name {} is already taken",
+                    pattern.name
+                )));
+            }
+        }
+        crate::proxy::redact::Redactor::new(redaction)
+            .map(drop)
+            .map_err(|e| Error::Config(format!("proxy.redaction.custom: {}", e)))
+    }
+
+    /// Check that a repository is given and that the file is inside it.
+    fn validate_gitops(&self) -> Result<()> {
+        let gitops = &self.gitops;
+        if !gitops.enabled {
+            return Ok(());
+        }
+        if gitops.repository.trim().is_empty() {
+            return Err(Error::Config(
+                "gitops.repository is required when gitops is enabled".to_string(),
+            ));
+        }
+        if gitops.branch.trim().is_empty() {
+            return Err(Error::Config("gitops.branch cannot be empty".to_string()));
+        }
+        let path = std::path::Path::new(&gitops.path);
+        let outside = path.is_absolute()
+            || path.components().any(|c| matches!(c, std::path::Component::ParentDir));
+        if gitops.path.is_empty() || outside {
+            return Err(Error::Config(format!(
+                "gitops.path must be a file inside the repository, got '{}'",
+                gitops.path
+            )));
+        }
+        crate::config::schema::Format::from_path(path)?;
+        Ok(())
+    }
+
+    /// Check that cluster peers are HTTP(S) URLs and that a peer is not
+    /// declared down between two exchanges.
+    fn validate_cluster(&self) -> Result<()> {
+        let cluster = &self.cluster;
+        if !cluster.enabled {
+            return Ok(());
+        }
+        if cluster.gossip_interval_ms == 0 {
+            return Err(Error::Config(
+                "cluster.gossip_interval_ms must be non-zero".to_string(),
+            ));
+        }
+        if cluster.peer_timeout_ms <= cluster.gossip_interval_ms {
+            return Err(Error::Config(
+                "cluster.peer_timeout_ms must be longer than gossip_interval_ms".to_string(),
+            ));
+        }
+        if cluster.node_id.as_deref().is_some_and(str::is_empty) {
+            return Err(Error::Config("cluster.node_id cannot be empty".to_string()));
+        }
+        for peer in &cluster.peers {
+            let valid = url::Url::parse(peer)
+                .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.has_host());
+            if !valid {
+                return Err(Error::Config(format!(
+                    "cluster.peers: {} is not an http(s) URL",
+                    peer
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that alert rules are well formed and refer to known notifiers
+    /// and servers.
+    fn validate_alerts(&self) -> Result<()> {
+        let alerts = &self.observability.alerts;
+        if !alerts.rules.is_empty() && alerts.interval_seconds == 0 {
+            return Err(Error::Config(
+                "alerts interval_seconds must be non-zero".to_string(),
+            ));
+        }
+
+        let mut notifiers = std::collections::HashSet::new();
+        for notifier in &alerts.notifiers {
+            if notifier.name.is_empty() {
+                return Err(Error::Config(
+                    "Alert notifier name cannot be empty".to_string(),
+                ));
+            }
+            if !notifiers.insert(notifier.name.as_str()) {
+                return Err(Error::Config(format!(
+                    "Duplicate alert notifier: {}",
+                    notifier.name
+                )));
+            }
+            if notifier.url.is_empty() {
+                return Err(Error::Config(format!(
+                    "Alert notifier {}: url cannot be empty",
+                    notifier.name
+                )));
+            }
+        }
+
+        let mut rules = std::collections::HashSet::new();
+        for rule in &alerts.rules {
+            if rule.name.is_empty() {
+                return Err(Error::Config("Alert rule name cannot be empty".to_string()));
+            }
+            if !rules.insert(rule.name.as_str()) {
+                return Err(Error::Config(format!(
+                    "Duplicate alert rule: {}",
+                    rule.name
+                )));
+            }
+            if let Some(unknown) =
+                rule.notify.iter().find(|name| !notifiers.contains(name.as_str()))
+            {
+                return Err(Error::Config(format!(
+                    "Alert rule {} notifies unknown notifier {}",
+                    rule.name, unknown
+                )));
+            }
+
+            let (percent, window, servers) = match &rule.condition {
+                AlertCondition::BackendUnhealthy { servers, .. } => {
+                    (None, None, servers.as_slice())
+                },
+                AlertCondition::ErrorRate {
+                    above_percent,
+                    window_seconds,
+                    servers,
+                    ..
+                } => (
+                    Some(*above_percent),
+                    Some(*window_seconds),
+                    servers.as_slice(),
+                ),
+                AlertCondition::CacheHitRate {
+                    below_percent,
+                    window_seconds,
+                    ..
+                } => (Some(*below_percent), Some(*window_seconds), &[][..]),
+            };
+            if percent.is_some_and(|percent| !(0.0..=100.0).contains(&percent)) {
+                return Err(Error::Config(format!(
+                    "Alert rule {}: percentage must be between 0 and 100",
+                    rule.name
+                )));
+            }
+            if window == Some(0) {
+                return Err(Error::Config(format!(
+                    "Alert rule {}: window_seconds must be non-zero",
+                    rule.name
+                )));
+            }
+            if let Some(unknown) =
+                servers.iter().find(|id| !self.servers.iter().any(|server| server.id == **id))
+            {
+                return Err(Error::Config(format!(
+                    "Alert rule {} references unknown server {}",
+                    rule.name, unknown
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Validate a server's sandbox: its strategy must be supported on this
+/// platform, the seccomp profile goes with the seccomp strategy only, and
+/// mounted paths are absolute.
+fn validate_sandbox(server_id: &str, sandbox: &SandboxConfig) -> Result<()> {
+    let invalid = |reason: String| {
+        Err(Error::Config(format!(
+            "Server {} sandbox: {}",
+            server_id, reason
+        )))
+    };
+    let isolating = matches!(
+        sandbox.strategy,
+        SandboxStrategy::Bubblewrap | SandboxStrategy::Firejail | SandboxStrategy::Seccomp
+    );
+    if isolating && !cfg!(target_os = "linux") {
+        return invalid(format!(
+            "strategy {} is only supported on Linux",
+            sandbox.strategy.as_str()
+        ));
+    }
+    if (sandbox.strategy == SandboxStrategy::Seccomp) != sandbox.seccomp_profile.is_some() {
+        return invalid(
+            "seccomp_profile is required by, and only used with, strategy seccomp".into(),
+        );
+    }
+    let paths = sandbox.read_only_paths.iter().chain(&sandbox.writable_paths);
+    if let Some(path) = paths.into_iter().find(|path| !path.starts_with('/')) {
+        return invalid(format!("path {} must be absolute", path));
+    }
+    Ok(())
+}
+
+/// Check that a server's `env_policy` fits its transport and `env`.
+fn validate_env_policy(server: &McpServerConfig) -> Result<()> {
+    let invalid = |reason: String| {
+        Err(Error::Config(format!(
+            "Server {} env_policy: {}",
+            server.id, reason
+        )))
+    };
+    let policy = &server.env_policy;
+    let env = match &server.transport {
+        TransportConfig::Stdio { env, .. } | TransportConfig::Docker { env, .. } => env,
+        _ if *policy == EnvPolicyConfig::default() => return Ok(()),
+        _ => return invalid("only applies to stdio and docker transports".into()),
+    };
+    if !policy.allowlist.is_empty() && policy.inherit != EnvInherit::Allowlist {
+        return invalid("allowlist is only used with inherit: allowlist".into());
+    }
+    if let Some(name) = policy.secrets.keys().find(|name| env.contains_key(*name)) {
+        return invalid(format!("{} is set by both env and secrets", name));
+    }
+    if let Some(name) = policy.redact.iter().find(|name| !env.contains_key(*name)) {
+        return invalid(format!("redacted variable {} is not in env", name));
+    }
+    Ok(())
+}
+
+/// Check that a server's transform rules are well formed and renames are
+/// unambiguous.
+fn validate_transforms(server_id: &str, transforms: &TransformConfig) -> Result<()> {
+    let invalid = |reason: String| {
+        Err(Error::Config(format!(
+            "Server {} transforms: {}",
+            server_id, reason
+        )))
+    };
+
+    let header_names = transforms.set_headers.keys().chain(&transforms.remove_headers);
+    for name in header_names {
+        if reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err() {
+            return invalid(format!("invalid header name {:?}", name));
+        }
+    }
+
+    let mut exposed = std::collections::HashSet::new();
+    for (tool, name) in &transforms.rename_tools {
+        if tool.is_empty() || name.is_empty() {
+            return invalid("tool names cannot be empty".to_string());
+        }
+        if !exposed.insert(name) || transforms.rename_tools.contains_key(name) {
+            return invalid(format!(
+                "tool {} is renamed to an ambiguous name {}",
+                tool, name
+            ));
+        }
+    }
+
+    if transforms.default_arguments.keys().any(String::is_empty) {
+        return invalid("default_arguments tool patterns cannot be empty".to_string());
+    }
+    for path in &transforms.strip_response_fields {
+        if path.split('.').any(str::is_empty) {
+            return invalid(format!("invalid response field path {:?}", path));
+        }
+    }
+    Ok(())
+}
+
+/// Check that an outbound proxy block at `field` has usable proxy URLs and
+/// a user name for its password.
+fn validate_outbound_proxy(field: &str, proxy: &OutboundProxyConfig) -> Result<()> {
+    let urls = [&proxy.http_proxy, &proxy.https_proxy, &proxy.all_proxy];
+    for url in urls.into_iter().flatten() {
+        crate::transport::outbound_proxy::check_scheme(url)
+            .map_err(|e| Error::Config(format!("{}: {}", field, e)))?;
+    }
+    if proxy.password.is_some() && proxy.username.is_none() {
+        return Err(Error::Config(format!(
+            "{}: password requires a username",
+            field
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BackoffStrategy, RetryConfig};
+
+    #[test]
+    fn test_valid_config() {
+        let config = Config::default();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_port() {
+        let mut config = Config::default();
+        config.server.port = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_tls_without_cert() {
+        let mut config = Config::default();
+        config.server.tls.enabled = true;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_retry_policy_validation() {
+        let mut config = Config::default();
+        config.servers.push(
+            serde_yaml::from_str(
+                "id: a\nname: A\ntransport: {type: http, url: 'http://localhost'}\n\
+                 timeout_ms: 500\nretry: {max_attempts: 5, backoff: exponential}",
+            )
+            .unwrap(),
+        );
+        assert!(config.validate().is_ok());
+        assert_eq!(config.servers[0].request_timeout_ms(), 500);
+        assert_eq!(
+            config.servers[0].retry.backoff,
+            BackoffStrategy::Exponential
+        );
+
+        config.servers[0].retry.max_attempts = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_alert_rule_validation() {
+        let mut config = Config::default();
+        config.observability.alerts = serde_yaml::from_str(
+            "notifiers: [{name: ops, type: slack, url: 'https://hooks.example.com/x'}]\n\
+             rules:\n  - {name: errors, type: error_rate, above_percent: 5, notify: [ops]}\n",
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+
+        let mut unknown_notifier = config.clone();
+        unknown_notifier.observability.alerts.rules[0].notify = vec!["pager".to_string()];
+        assert!(unknown_notifier.validate().is_err());
+
+        let mut unknown_server = config.clone();
+        unknown_server.observability.alerts.rules[0].condition =
+            serde_yaml::from_str("{type: backend_unhealthy, servers: [missing]}").unwrap();
+        assert!(unknown_server.validate().is_err());
+
+        let mut bad_percent = config;
+        bad_percent.observability.alerts.rules[0].condition =
+            serde_yaml::from_str("{type: cache_hit_rate, below_percent: 150}").unwrap();
+        assert!(bad_percent.validate().is_err());
+    }
+
+    #[test]
+    fn test_retry_backoff_delays() {
+        let mut retry = RetryConfig::default();
+        assert_eq!(retry.delay_for(1).as_millis(), 100);
+        assert_eq!(retry.delay_for(3).as_millis(), 300);
+
+        retry.backoff = BackoffStrategy::Exponential;
+        assert_eq!(retry.delay_for(4).as_millis(), 800);
+        assert_eq!(retry.delay_for(40).as_millis(), 5000);
+
+        retry.backoff = BackoffStrategy::Fixed;
+        assert_eq!(retry.delay_for(7).as_millis(), 100);
+    }
+
+    #[test]
+    fn test_cost_rates_validation() {
+        let mut config = Config::default();
+        config.servers.push(
+            serde_yaml::from_str(
+                "id: a\nname: A\ntransport: {type: http, url: 'http://localhost'}\n\
+                 cost: {per_call: 0.01, tools: {render: {per_1k_tokens: 0.5}}}",
+            )
+            .unwrap(),
+        );
+        assert!(config.validate().is_ok());
+        let cost = config.servers[0].cost.as_ref().unwrap();
+        assert_eq!(cost.rates_for("search").per_call, 0.01);
+        assert_eq!(cost.rates_for("render").per_call, 0.0);
+
+        config.servers[0]
+            .cost
+            .as_mut()
+            .unwrap()
+            .tools
+            .get_mut("render")
+            .unwrap()
+            .per_call = -1.0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_ipv6_listeners() {
+        let mut config = Config::default();
+        config.servers.push(
+            serde_yaml::from_str("id: a\nname: A\ntransport: {type: unix, path: /run/tools.sock}")
+                .unwrap(),
+        );
+        config.server.host = "::".to_string();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.server.effective_listeners()[0].bind, "[::]:8080");
+        assert_eq!(config.server.admin_address(), ("::1".to_string(), 8080));
+
+        config.server.host = "[::1]".to_string();
+        assert_eq!(config.server.effective_listeners()[0].bind, "[::1]:8080");
+        config.server.host = "::1::2".to_string();
+        assert!(config.validate().is_err());
+
+        config.server.listeners = serde_yaml::from_str(
+            r#"
+            - {name: v6, bind: "[::]:8080", ipv6_only: true}
+            - {name: v4, bind: "127.0.0.1:8081", ipv6_only: true}
+            "#,
+        )
+        .unwrap();
+        let error = config.validate().unwrap_err();
+        assert!(error.to_string().contains("Listener v4"), "{}", error);
+    }
+
+    #[test]
+    fn test_unix_socket_settings() {
+        let mut config = Config::default();
+        config.servers.push(
+            serde_yaml::from_str("id: a\nname: A\ntransport: {type: unix, path: /run/tools.sock}")
+                .unwrap(),
+        );
+        config.server.listen_socket = Some("/run/only1mcp.sock".into());
+        assert!(config.validate().is_ok());
+
+        let listeners = config.server.effective_listeners();
+        assert_eq!(listeners.len(), 2);
+        assert_eq!(listeners[1].bind, "unix:/run/only1mcp.sock");
+
+        config.servers[0].transport = TransportConfig::Unix {
+            path: Default::default(),
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_restart_settings() {
+        let mut config = Config::default();
+        config.servers.push(
+            serde_yaml::from_str(
+                "id: a\nname: A\ntransport: {type: stdio, command: mcp}\nrestart: {policy: on_failure}",
+            )
+            .unwrap(),
+        );
+        assert!(config.validate().is_ok());
+        assert_eq!(config.servers[0].restart.max_restarts, 5);
+
+        config.servers[0].restart.window_seconds = 0;
+        assert!(config.validate().is_err());
+
+        config.servers[0].restart.window_seconds = 60;
+        config.servers[0].restart.initial_backoff_ms = 60_000;
+        assert!(config.validate().is_err());
+
+        config.servers[0].restart.initial_backoff_ms = 500;
+        config.servers[0].resources.max_cpu_percent = Some(0);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_sandbox_settings() {
+        let mut config = Config::default();
+        config.servers.push(
+            serde_yaml::from_str(
+                "id: a\nname: A\ntransport: {type: stdio, command: mcp}\nsandbox: {strategy: seccomp}",
+            )
+            .unwrap(),
+        );
+        // The seccomp strategy needs a profile
+        assert!(config.validate().is_err());
+
+        config.servers[0].sandbox.seccomp_profile = Some("/etc/only1mcp/mcp.bpf".into());
+        assert_eq!(config.validate().is_ok(), cfg!(target_os = "linux"));
+
+        config.servers[0].sandbox.strategy = SandboxStrategy::Rlimit;
+        assert!(config.validate().is_err());
+
+        config.servers[0].sandbox.seccomp_profile = None;
+        config.servers[0].sandbox.writable_paths = vec!["data".into()];
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_env_policy_settings() {
+        let mut config = Config::default();
+        config.servers.push(
+            serde_yaml::from_str(
+                "id: a\nname: A\ntransport: {type: stdio, command: mcp, env: {DB_URL: x}}\n\
+                 env_policy: {inherit: allowlist, allowlist: [PATH], redact: [DB_URL]}",
+            )
+            .unwrap(),
+        );
+        assert!(config.validate().is_ok());
+
+        config.servers[0].env_policy.inherit = EnvInherit::None;
+        assert!(config.validate().is_err());
+
+        config.servers[0].env_policy.allowlist.clear();
+        config.servers[0].env_policy.secrets.insert("DB_URL".into(), "y".into());
+        assert!(config.validate().is_err());
+
+        config.servers[0].env_policy.secrets.clear();
+        config.servers[0].env_policy.redact.push("TOKEN".into());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_cluster_settings() {
+        let config = Config {
+            cluster: serde_yaml::from_str(
+                "{enabled: true, peers: ['http://10.0.0.2:8080', 'https://proxy-b.internal']}",
+            )
+            .unwrap(),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        let mut bad_peer = config.clone();
+        bad_peer.cluster.peers.push("10.0.0.3:8080".to_string());
+        assert!(bad_peer.validate().is_err());
+
+        let mut short_timeout = config.clone();
+        short_timeout.cluster.peer_timeout_ms = config.cluster.gossip_interval_ms;
+        assert!(short_timeout.validate().is_err());
+    }
+
+    #[test]
+    fn test_docker_discovery_settings() {
+        let mut config = Config {
+            discovery: serde_yaml::from_str("{docker: {enabled: true}}").unwrap(),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.discovery.docker.socket,
+            std::path::Path::new("/var/run/docker.sock")
+        );
+
+        config.discovery.docker.poll_interval_ms = 0;
+        let error = config.validate().unwrap_err();
+        assert!(error.to_string().contains("poll_interval_ms"), "{}", error);
+    }
+
+    #[test]
+    fn test_gitops_settings() {
+        let mut config = Config {
+            gitops: serde_yaml::from_str(
+                "{enabled: true, repository: 'https://git.example.com/mcp.git'}",
+            )
+            .unwrap(),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+        assert_eq!(config.gitops.branch, "main");
+
+        config.gitops.path = "../only1mcp.yaml".to_string();
+        let error = config.validate().unwrap_err();
+        assert!(error.to_string().contains("gitops.path"), "{}", error);
+
+        config.gitops.path = "only1mcp.yaml".to_string();
+        config.gitops.repository.clear();
+        let error = config.validate().unwrap_err();
+        assert!(error.to_string().contains("gitops.repository"), "{}", error);
+    }
+
+    #[test]
+    fn test_script_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("on_request.rhai");
+        std::fs::write(&path, "arguments.limit = 10;").unwrap();
+        let mut config = Config::default();
+        config.proxy.scripts.on_request = Some(path.clone());
+        assert!(config.validate().is_ok());
+
+        std::fs::write(&path, "arguments.limit = ").unwrap();
+        let error = config.validate().unwrap_err();
+        assert!(
+            error.to_string().contains("proxy.scripts.on_request"),
+            "{}",
+            error
+        );
+
+        config.proxy.scripts.on_request = Some(dir.path().join("missing.rhai"));
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_composite_tools() {
+        let mut config = Config::default();
+        config.proxy.composite_tools = serde_yaml::from_str(
+            r#"
+            - name: summary
+              steps:
+                - {id: search, tool: search, arguments: {query: "{{ $.arguments.query }}"}}
+                - {id: fetch, tool: fetch, arguments: {id: "{{ $.steps.search.content[0].text }}"}}
+            "#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+
+        let mut nested = config.proxy.composite_tools[0].clone();
+        nested.name = "nested".to_string();
+        nested.steps[1].tool = "summary".to_string();
+        config.proxy.composite_tools.push(nested);
+        let error = config.validate().unwrap_err();
+        assert!(
+            error.to_string().contains("calls composite tool"),
+            "{}",
+            error
+        );
+
+        let duplicate = config.proxy.composite_tools[0].clone();
+        config.proxy.composite_tools[1] = duplicate;
+        let error = config.validate().unwrap_err();
+        assert!(
+            error.to_string().contains("duplicate tool name"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_tool_aliases() {
+        let mut config = Config {
+            servers: serde_yaml::from_str(
+                r#"
+                - {id: github, name: GitHub, transport: {type: http, url: "http://github:8080"}}
+                "#,
+            )
+            .unwrap(),
+            ..Config::default()
+        };
+        config.proxy.tool_aliases = serde_yaml::from_str(
+            "[{name: search, targets: [{server: github, tool: search_repositories}]}]",
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+
+        config.proxy.tool_aliases[0].targets[0].server = "gitlab".to_string();
+        let error = config.validate().unwrap_err();
+        assert!(
+            error.to_string().contains("unknown server gitlab"),
+            "{}",
+            error
+        );
+
+        config.proxy.tool_aliases[0].targets.clear();
+        let error = config.validate().unwrap_err();
+        assert!(error.to_string().contains("no targets"), "{}", error);
+    }
+
+    #[test]
+    fn test_request_signing() {
+        let mut config = Config {
+            servers: serde_yaml::from_str(
+                r#"
+                - id: hooks
+                  name: Hooks
+                  transport:
+                    type: http
+                    url: "http://hooks:8080"
+                    signing: {secret: s3cret, header: X-Hub-Signature-256}
+                "#,
+            )
+            .unwrap(),
+            ..Config::default()
+        };
+        assert!(config.validate().is_ok());
+
+        let TransportConfig::Http {
+            signing: Some(signing),
+            ..
+        } = &mut config.servers[0].transport
+        else {
+            unreachable!()
+        };
+        signing.timestamp_header = Some("X Timestamp".to_string());
+        let error = config.validate().unwrap_err();
+        assert!(
+            error.to_string().contains("not a valid header name"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_outbound_proxy() {
+        let mut config = Config {
+            servers: serde_yaml::from_str(
+                r#"
+                - id: remote
+                  name: Remote
+                  transport:
+                    type: sse
+                    url: "https://mcp.example.com/sse"
+                    proxy: {all_proxy: "socks5h://proxy:1080"}
+                "#,
+            )
+            .unwrap(),
+            ..Config::default()
+        };
+        config.proxy.outbound_proxy =
+            serde_yaml::from_str("{https_proxy: 'http://proxy:3128', username: u, password: p}")
+                .unwrap();
+        assert!(config.validate().is_ok());
+
+        config.proxy.outbound_proxy.as_mut().unwrap().username = None;
+        let error = config.validate().unwrap_err();
+        assert!(
+            error.to_string().contains("requires a username"),
+            "{}",
+            error
+        );
+
+        config.proxy.outbound_proxy = None;
+        let TransportConfig::Sse {
+            proxy: Some(proxy), ..
+        } = &mut config.servers[0].transport
+        else {
+            unreachable!()
+        };
+        proxy.all_proxy = Some("socks4://proxy:1080".to_string());
+        let error = config.validate().unwrap_err();
+        assert!(
+            error.to_string().contains("servers[remote].transport.proxy"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_redaction_patterns() {
+        let mut config = Config::default();
+        config.proxy.redaction.custom =
+            serde_yaml::from_str(r#"[{name: ticket, regex: "TCK-\\d+"}]"#).unwrap();
+        assert!(config.validate().is_ok());
+
+        config.proxy.redaction.custom[0].regex = "TCK-(".to_string();
+        let error = config.validate().unwrap_err();
+        assert!(error.to_string().contains("pattern ticket"), "{}", error);
+
+        config.proxy.redaction.custom[0].name = "email".to_string();
+        let error = config.validate().unwrap_err();
+        assert!(error.to_string().contains("already taken"), "{}", error);
+    }
+
+    #[test]
+    fn test_npx_pinning_required() {
+        let mut config = Config::default();
+        config.proxy.npx.require_pinned = true;
+        config.servers.push(
+            serde_yaml::from_str(
+                "id: a\nname: A\ntransport: {type: stdio, command: npx, args: [-y, '@mcp/fs@1.0.2']}",
+            )
+            .unwrap(),
+        );
+        assert!(config.validate().is_ok());
+
+        config.servers[0].transport =
+            serde_yaml::from_str("{type: stdio, command: npx, args: [-y, '@mcp/fs']}").unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_failover_group_settings() {
+        let mut config = Config::default();
+        for id in ["primary", "standby"] {
+            config.servers.push(
+                serde_yaml::from_str(&format!(
+                    "id: {id}\nname: {id}\ntransport: {{type: http, url: 'http://{id}'}}"
+                ))
+                .unwrap(),
+            );
+        }
+        config.proxy.routing.failover_groups =
+            serde_yaml::from_str("[{name: search, primary: primary, standbys: [standby]}]")
+                .unwrap();
+        assert!(config.validate().is_ok());
+
+        // A server may serve only one role
+        config.proxy.routing.failover_groups[0].standbys.push("primary".into());
+        assert!(config.validate().is_err());
+
+        config.proxy.routing.failover_groups[0].standbys = vec!["missing".into()];
+        let error = config.validate().unwrap_err();
+        assert!(
+            error.to_string().contains("unknown server missing"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_transform_rules() {
+        let mut config = Config::default();
+        config.servers.push(
+            serde_yaml::from_str("id: web\nname: web\ntransport: {type: http, url: 'http://web'}")
+                .unwrap(),
+        );
+        config.servers[0].transforms = serde_yaml::from_str(
+            "{set_headers: {X-Team: search}, rename_tools: {search: web_search}, strip_response_fields: [_meta]}",
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+
+        // Two tools cannot share an exposed name
+        config.servers[0]
+            .transforms
+            .rename_tools
+            .insert("find".into(), "web_search".into());
+        let error = config.validate().unwrap_err();
+        assert!(
+            error.to_string().contains("ambiguous name web_search"),
+            "{}",
+            error
+        );
+
+        config.servers[0].transforms.rename_tools.remove("find");
+        config.servers[0].transforms.remove_headers = vec!["bad header".into()];
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_truncation_limits() {
+        let mut config = Config::default();
+        config.context_optimization.truncation = serde_yaml::from_str(
+            "{enabled: true, max_tokens: 1000, tools: {'read_*': {max_bytes: 262144}}}",
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+
+        // Tool limits replace only the defaults they set
+        let truncation = &config.context_optimization.truncation;
+        assert_eq!(truncation.limits_for("read_file"), (262144, Some(1000)));
+        assert_eq!(truncation.limits_for("search"), (65536, Some(1000)));
+
+        config.context_optimization.truncation.max_tokens = Some(0);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_mirror_settings() {
+        let mut config = Config::default();
+        for id in ["live", "shadow"] {
+            config.servers.push(
+                serde_yaml::from_str(&format!(
+                    "id: {id}\nname: {id}\ntransport: {{type: http, url: 'http://{id}'}}"
+                ))
+                .unwrap(),
+            );
+        }
+        config.servers[0].mirror = serde_yaml::from_str("{server: shadow}").unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.servers[0].mirror.as_ref().unwrap().percent, 100);
+        assert!(config.is_shadow("shadow"));
+        assert_eq!(config.routed_servers().count(), 1);
+
+        // Mirror targets do not mirror in turn
+        config.servers[1].mirror = serde_yaml::from_str("{server: live}").unwrap();
+        assert!(config.validate().is_err());
+
+        config.servers[1].mirror = None;
+        config.servers[0].mirror = serde_yaml::from_str("{server: missing}").unwrap();
+        let error = config.validate().unwrap_err();
+        assert!(
+            error.to_string().contains("unknown server missing"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_traffic_split_settings() {
+        let mut config = Config::default();
+        for id in ["stable", "canary", "standby"] {
+            config.servers.push(
+                serde_yaml::from_str(&format!(
+                    "id: {id}\nname: {id}\ntransport: {{type: http, url: 'http://{id}'}}"
+                ))
+                .unwrap(),
+            );
+        }
+        config.proxy.routing.traffic_splits = serde_yaml::from_str(
+            "[{service: search, variants: [{server: stable, weight: 95}, {server: canary, weight: 5}]}]",
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+
+        config.proxy.routing.traffic_splits[0].variants.truncate(1);
+        let error = config.validate().unwrap_err();
+        assert!(
+            error.to_string().contains("at least two variants"),
+            "{}",
+            error
+        );
+
+        config.proxy.routing.traffic_splits[0]
+            .variants
+            .push(serde_yaml::from_str("{server: canary, weight: 5}").unwrap());
+        config.proxy.routing.failover_groups =
+            serde_yaml::from_str("[{name: search, primary: canary, standbys: [standby]}]").unwrap();
+        let error = config.validate().unwrap_err();
+        assert!(
+            error.to_string().contains("both a failover group and a traffic split"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_front_door_settings() {
+        let mut config = Config::default();
+        config.servers.push(
+            serde_yaml::from_str(
+                "id: shard-a\nname: a\ntransport: {type: http, url: 'http://a/mcp'}",
+            )
+            .unwrap(),
+        );
+        config.servers.push(
+            serde_yaml::from_str("id: local\nname: local\ntransport: {type: stdio, command: cat}")
+                .unwrap(),
+        );
+        config.proxy.routing.front_door =
+            serde_yaml::from_str("{enabled: true, instances: [shard-a]}").unwrap();
+        assert!(config.validate().is_ok());
+
+        config.proxy.routing.front_door.instances.push("local".into());
+        let error = config.validate().unwrap_err();
+        assert!(
+            error.to_string().contains("must use the http transport"),
+            "{}",
+            error
+        );
+
+        config.proxy.routing.front_door.instances.clear();
+        let error = config.validate().unwrap_err();
+        assert!(error.to_string().contains("cannot be empty"), "{}", error);
+    }
+
+    #[test]
+    fn test_zero_size_limit_rejected() {
+        let mut config = Config::default();
+        assert_eq!(config.proxy.limits.max_request_bytes, 2 * 1024 * 1024);
+
+        config.proxy.limits.max_stdio_line_bytes = 0;
+        let error = config.validate().unwrap_err();
+        assert!(
+            error.to_string().contains("max_stdio_line_bytes"),
+            "{}",
+            error
+        );
     }
 }
@@ -0,0 +1,296 @@
+//! The daemon's own log file with rotation (`observability.logging.rotation`).
+//!
+//! [`DaemonLog`] is the tracing writer of a daemonized proxy. Before each
+//! write it checks the file's size and, with an `interval`, whether the hour
+//! or day has changed since the file was started; either rotates it:
+//! `only1mcp.log` becomes `only1mcp.log.1`, older files shift up and those
+//! beyond `max_files` are deleted. With `compress`, a rotated file is
+//! gzipped on a background thread. `only1mcp logs --rotate-now` rotates
+//! through the Admin API ([`current`]).
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::thread::JoinHandle;
+
+use chrono::{DateTime, Timelike, Utc};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use parking_lot::Mutex;
+
+use crate::config::{LogRotationConfig, LogRotationInterval};
+
+/// Log of this process, set when it runs as a daemon.
+static CURRENT: OnceLock<Arc<DaemonLog>> = OnceLock::new();
+
+/// The daemon log, `None` in the foreground, where logs go to stdout.
+pub fn current() -> Option<Arc<DaemonLog>> {
+    CURRENT.get().cloned()
+}
+
+/// The rotating daemon log file.
+#[derive(Debug)]
+pub struct DaemonLog {
+    path: PathBuf,
+    config: LogRotationConfig,
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    file: File,
+    size: u64,
+    /// Start of the hour or day the file belongs to
+    period: DateTime<Utc>,
+    /// Gzip of the last rotated file, finished before the next rotation
+    compressing: Option<JoinHandle<()>>,
+}
+
+impl DaemonLog {
+    /// Open `path` for appending.
+    pub fn open(path: &Path, config: LogRotationConfig) -> io::Result<Self> {
+        let state = State::open(path, config.interval)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            config,
+            state: Mutex::new(state),
+        })
+    }
+
+    /// Make this the log [`current`] returns, once per process.
+    pub fn install(self) -> Arc<Self> {
+        CURRENT.get_or_init(|| Arc::new(self)).clone()
+    }
+
+    /// Writer for `tracing_subscriber::fmt::layer().with_writer(..)`.
+    pub fn writer(self: &Arc<Self>) -> DaemonLogWriter {
+        DaemonLogWriter(self.clone())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Rotate now, returning the path the current file was moved to.
+    pub fn rotate(&self) -> io::Result<PathBuf> {
+        let mut state = self.state.lock();
+        self.rotate_locked(&mut state)
+    }
+
+    fn write(&self, buf: &[u8]) -> io::Result<()> {
+        let mut state = self.state.lock();
+        let full = self.config.max_file_bytes > 0 && state.size >= self.config.max_file_bytes;
+        let expired = period_start(Utc::now(), self.config.interval) > state.period;
+        if (full || expired) && state.size > 0 {
+            self.rotate_locked(&mut state)?;
+        }
+        state.file.write_all(buf)?;
+        state.size += buf.len() as u64;
+        Ok(())
+    }
+
+    fn rotate_locked(&self, state: &mut State) -> io::Result<PathBuf> {
+        if let Some(compressing) = state.compressing.take() {
+            let _ = compressing.join();
+        }
+        state.file.flush()?;
+
+        let rotated = shift(&self.path, self.config.max_files)?;
+        let mut next = State::open(&self.path, self.config.interval)?;
+        // Only the installed log owns the process's streams
+        if CURRENT.get().is_some_and(|current| std::ptr::eq(current.as_ref(), self)) {
+            redirect_std_streams(&next.file);
+        }
+
+        if let Some(plain) = rotated.clone().filter(|_| self.config.compress) {
+            next.compressing = Some(std::thread::spawn(move || gzip(&plain)));
+        }
+        *state = next;
+        Ok(rotated
+            .map(|plain| match self.config.compress {
+                true => gz_path(&plain),
+                false => plain,
+            })
+            .unwrap_or_else(|| self.path.clone()))
+    }
+}
+
+impl State {
+    fn open(path: &Path, interval: LogRotationInterval) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let metadata = file.metadata()?;
+        // A file left from an earlier run belongs to the period it was last
+        // written in, so it is rotated on the first write of a later one
+        let modified = match metadata.len() {
+            0 => Utc::now(),
+            _ => metadata.modified().map(DateTime::<Utc>::from).unwrap_or_else(|_| Utc::now()),
+        };
+        Ok(Self {
+            file,
+            size: metadata.len(),
+            period: period_start(modified, interval),
+            compressing: None,
+        })
+    }
+}
+
+/// Handle passed to tracing for each event.
+#[derive(Debug, Clone)]
+pub struct DaemonLogWriter(Arc<DaemonLog>);
+
+impl Write for DaemonLogWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.state.lock().file.flush()
+    }
+}
+
+/// Start of the hour or day `time` is in; the epoch when rotation by time
+/// is off, so a file never expires.
+fn period_start(time: DateTime<Utc>, interval: LogRotationInterval) -> DateTime<Utc> {
+    let start = match interval {
+        LogRotationInterval::Never => return DateTime::<Utc>::UNIX_EPOCH,
+        LogRotationInterval::Hourly => time.date_naive().and_hms_opt(time.hour(), 0, 0),
+        LogRotationInterval::Daily => time.date_naive().and_hms_opt(0, 0, 0),
+    };
+    start.map_or(time, |start| start.and_utc())
+}
+
+/// `<path>.<n>`, the `n`th newest rotated file.
+fn numbered(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
+
+fn gz_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".gz");
+    PathBuf::from(name)
+}
+
+/// Move `path` to `path.1` and older files (compressed or not) one up,
+/// deleting those beyond `max_files`. Returns where `path` went, `None`
+/// when none are kept.
+fn shift(path: &Path, max_files: usize) -> io::Result<Option<PathBuf>> {
+    for n in (1..=max_files.max(1)).rev() {
+        for from in [numbered(path, n), gz_path(&numbered(path, n))] {
+            if !from.exists() {
+                continue;
+            }
+            if n >= max_files {
+                fs::remove_file(&from)?;
+            } else {
+                let to = numbered(path, n + 1);
+                let to = match from.extension().is_some_and(|ext| ext == "gz") {
+                    true => gz_path(&to),
+                    false => to,
+                };
+                fs::rename(&from, to)?;
+            }
+        }
+    }
+    if max_files == 0 {
+        fs::remove_file(path)?;
+        return Ok(None);
+    }
+    let rotated = numbered(path, 1);
+    fs::rename(path, &rotated)?;
+    Ok(Some(rotated))
+}
+
+/// Replace `path` with `path.gz`, logging a failure.
+fn gzip(path: &Path) {
+    if let Err(e) = try_gzip(path) {
+        tracing::warn!("Failed to compress {}: {}", path.display(), e);
+    }
+}
+
+fn try_gzip(path: &Path) -> io::Result<()> {
+    let mut input = File::open(path)?;
+    let mut encoder = GzEncoder::new(File::create(gz_path(path))?, Compression::default());
+    io::copy(&mut input, &mut encoder)?;
+    encoder.finish()?;
+    fs::remove_file(path)
+}
+
+/// Point stdout and stderr, which daemonizing sent to the old file, at the
+/// new one, so panics and other direct writes follow the rotation.
+#[cfg(unix)]
+fn redirect_std_streams(file: &File) {
+    use std::os::unix::io::AsRawFd;
+
+    for fd in [libc::STDOUT_FILENO, libc::STDERR_FILENO] {
+        let _ = nix::unistd::dup2(file.as_raw_fd(), fd);
+    }
+}
+
+/// Windows cannot swap the handles of a running process; direct writes
+/// keep going to the rotated file.
+#[cfg(not(unix))]
+fn redirect_std_streams(_file: &File) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_size_rotation_with_retention_and_compression() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("only1mcp.log");
+        let config = LogRotationConfig {
+            max_file_bytes: 20,
+            max_files: 2,
+            compress: true,
+            ..Default::default()
+        };
+        let log = Arc::new(DaemonLog::open(&path, config).unwrap());
+        let mut writer = log.writer();
+        for i in 0..4 {
+            writer.write_all(format!("line {:02} ......\n", i).as_bytes()).unwrap();
+            writer.write_all(b"second line ...\n").unwrap();
+        }
+        let rotated = log.rotate().unwrap();
+        assert_eq!(rotated, dir.path().join("only1mcp.log.1.gz"));
+        let compressing = log.state.lock().compressing.take().unwrap();
+        compressing.join().unwrap();
+
+        let mut names: Vec<String> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            ["only1mcp.log", "only1mcp.log.1.gz", "only1mcp.log.2.gz"]
+        );
+        let mut newest = String::new();
+        flate2::read::GzDecoder::new(File::open(dir.path().join("only1mcp.log.1.gz")).unwrap())
+            .read_to_string(&mut newest)
+            .unwrap();
+        assert_eq!(newest, "line 03 ......\nsecond line ...\n");
+    }
+
+    #[test]
+    fn test_period_start() {
+        let time = "2026-03-04T05:06:07Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(
+            period_start(time, LogRotationInterval::Hourly).to_rfc3339(),
+            "2026-03-04T05:00:00+00:00"
+        );
+        assert_eq!(
+            period_start(time, LogRotationInterval::Daily).to_rfc3339(),
+            "2026-03-04T00:00:00+00:00"
+        );
+        assert_eq!(
+            period_start(time, LogRotationInterval::Never),
+            DateTime::<Utc>::UNIX_EPOCH
+        );
+    }
+}
@@ -1,20 +1,32 @@
 //! Daemon lifecycle management for Only1MCP
 //!
-//! Provides Unix daemon functionality including:
-//! - Process daemonization (fork/detach)
+//! Provides daemon functionality including:
+//! - Process daemonization (fork/detach on Unix, detached child process on Windows)
 //! - PID file management
 //! - Process lifecycle tracking
 //! - Graceful shutdown coordination
+//! - Rotation of the daemon log file
+//! - Zero-downtime binary upgrades on `SIGUSR2` (Unix)
 
 use crate::error::{Error, Result};
+#[cfg(unix)]
 use daemonize::Daemonize;
+#[cfg(unix)]
 use nix::sys::signal::{self, Signal};
+#[cfg(unix)]
 use nix::unistd::Pid;
 use std::fs;
 use std::path::{Path, PathBuf};
 use tracing::{info, warn};
 
+pub mod log_file;
 pub mod signals;
+#[cfg(unix)]
+pub mod upgrade;
+
+/// Environment marker set on the detached child spawned by `daemonize()` on Windows
+#[cfg(windows)]
+const DAEMON_CHILD_ENV: &str = "ONLY1MCP_DAEMON_CHILD";
 
 /// Daemon manager for Only1MCP
 ///
@@ -23,6 +35,7 @@ pub mod signals;
 pub struct DaemonManager {
     pid_file: PathBuf,
     log_file: PathBuf,
+    stop_file: PathBuf,
     config_dir: PathBuf,
 }
 
@@ -47,6 +60,7 @@ impl DaemonManager {
         Ok(Self {
             pid_file: config_dir.join("only1mcp.pid"),
             log_file: config_dir.join("only1mcp.log"),
+            stop_file: config_dir.join("only1mcp.stop"),
             config_dir,
         })
     }
@@ -56,10 +70,21 @@ impl DaemonManager {
     /// Forks the process, detaches from the controlling terminal, and redirects
     /// stdout/stderr to the log file. The parent process exits, leaving the child
     /// as a background daemon.
+    ///
+    /// A process started by a binary upgrade inherits the detached state and
+    /// log file of the daemon it replaces, so it only records its own PID.
     #[cfg(unix)]
     pub fn daemonize(&self) -> Result<()> {
         use std::fs::OpenOptions;
 
+        if upgrade::is_successor() {
+            std::env::set_current_dir("/tmp")
+                .map_err(|e| Error::Server(format!("Failed to change directory: {}", e)))?;
+            fs::write(&self.pid_file, std::process::id().to_string())
+                .map_err(|e| Error::Server(format!("Failed to write PID file: {}", e)))?;
+            return Ok(());
+        }
+
         info!("Daemonizing process...");
 
         // Open log file for stdout/stderr redirection
@@ -86,8 +111,63 @@ impl DaemonManager {
         Ok(())
     }
 
-    /// Daemonize the current process (non-Unix platforms - not supported)
-    #[cfg(not(unix))]
+    /// Daemonize the current process (Windows version)
+    ///
+    /// Windows has no fork, so the executable is re-launched with the same
+    /// arguments as a detached process whose stdout/stderr go to the log file.
+    /// The parent process exits; the relaunched child takes this same path,
+    /// records its own PID and returns, leaving it as the background daemon.
+    #[cfg(windows)]
+    pub fn daemonize(&self) -> Result<()> {
+        use std::fs::OpenOptions;
+        use std::os::windows::process::CommandExt;
+        use std::process::{Command, Stdio};
+
+        const DETACHED_PROCESS: u32 = 0x0000_0008;
+        const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+
+        if std::env::var_os(DAEMON_CHILD_ENV).is_some() {
+            // We are the detached child: a leftover stop request must not
+            // shut us down straight away
+            let _ = fs::remove_file(&self.stop_file);
+            fs::write(&self.pid_file, std::process::id().to_string())
+                .map_err(|e| Error::Server(format!("Failed to write PID file: {}", e)))?;
+
+            info!("Process daemonized successfully");
+            return Ok(());
+        }
+
+        info!("Daemonizing process...");
+
+        // Open log file for stdout/stderr redirection
+        let log_file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.log_file)
+            .map_err(|e| Error::Server(format!("Failed to open log file: {}", e)))?;
+
+        let exe = std::env::current_exe()
+            .map_err(|e| Error::Server(format!("Failed to locate executable: {}", e)))?;
+
+        let child =
+            Command::new(exe)
+                .args(std::env::args_os().skip(1))
+                .env(DAEMON_CHILD_ENV, "1")
+                .stdin(Stdio::null())
+                .stdout(log_file.try_clone().map_err(|e| {
+                    Error::Server(format!("Failed to clone log file handle: {}", e))
+                })?)
+                .stderr(log_file)
+                .creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP)
+                .spawn()
+                .map_err(|e| Error::Server(format!("Failed to daemonize: {}", e)))?;
+
+        info!("Started background process {}", child.id());
+        std::process::exit(0);
+    }
+
+    /// Daemonize the current process (other platforms - not supported)
+    #[cfg(not(any(unix, windows)))]
     pub fn daemonize(&self) -> Result<()> {
         Err(Error::Server(
             "Daemon mode is not supported on this platform. Use --foreground flag.".into(),
@@ -105,20 +185,15 @@ impl DaemonManager {
 
         // Read PID and check if process exists
         match fs::read_to_string(&self.pid_file) {
-            Ok(pid_str) => match pid_str.trim().parse::<i32>() {
+            Ok(pid_str) => match pid_str.trim().parse::<u32>() {
                 Ok(pid) => {
-                    // Send signal 0 to check if process exists (doesn't actually send a signal)
-                    match signal::kill(Pid::from_raw(pid), None) {
-                        Ok(_) => {
-                            // Process exists
-                            true
-                        },
-                        Err(_) => {
-                            // Process doesn't exist, clean up stale PID file
-                            warn!("Stale PID file detected, cleaning up");
-                            let _ = fs::remove_file(&self.pid_file);
-                            false
-                        },
+                    if process_alive(pid) {
+                        true
+                    } else {
+                        // Process doesn't exist, clean up stale PID file
+                        warn!("Stale PID file detected, cleaning up");
+                        let _ = fs::remove_file(&self.pid_file);
+                        false
                     }
                 },
                 Err(_) => {
@@ -196,8 +271,73 @@ impl DaemonManager {
         Ok(())
     }
 
-    /// Stop a running daemon instance (non-Unix platforms - not supported)
-    #[cfg(not(unix))]
+    /// Stop a running daemon instance (Windows version)
+    ///
+    /// Windows has no SIGTERM, so a stop request file is written that the
+    /// daemon's shutdown handler watches for. If the process doesn't exit
+    /// within 3 seconds, it is terminated with `taskkill /F`.
+    #[cfg(windows)]
+    pub fn stop(&self) -> Result<()> {
+        use std::process::Command;
+        use std::thread;
+        use std::time::Duration;
+
+        if !self.pid_file.exists() {
+            return Err(Error::Server(
+                "No running instance found (PID file missing)".into(),
+            ));
+        }
+
+        let pid_str = fs::read_to_string(&self.pid_file)
+            .map_err(|e| Error::Server(format!("Failed to read PID file: {}", e)))?;
+
+        let pid = pid_str
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| Error::Server("Invalid PID file format".into()))?;
+
+        info!("Requesting shutdown of process {}", pid);
+
+        fs::write(&self.stop_file, pid.to_string())
+            .map_err(|e| Error::Server(format!("Failed to write stop request: {}", e)))?;
+
+        // Wait for process to exit (30 iterations * 100ms = 3 seconds)
+        for _ in 0..30 {
+            thread::sleep(Duration::from_millis(100));
+
+            if !process_alive(pid) {
+                info!("Process exited gracefully");
+                let _ = fs::remove_file(&self.stop_file);
+                let _ = fs::remove_file(&self.pid_file);
+                return Ok(());
+            }
+        }
+
+        warn!("Process did not exit gracefully, terminating");
+        let status = Command::new("taskkill")
+            .args(["/F", "/PID", &pid.to_string()])
+            .status()
+            .map_err(|e| Error::Server(format!("Failed to run taskkill: {}", e)))?;
+
+        if !status.success() {
+            return Err(Error::Server(format!(
+                "taskkill failed for process {}",
+                pid
+            )));
+        }
+
+        // Give it a moment to die
+        thread::sleep(Duration::from_millis(500));
+
+        let _ = fs::remove_file(&self.stop_file);
+        let _ = fs::remove_file(&self.pid_file);
+        info!("Process forcefully terminated");
+
+        Ok(())
+    }
+
+    /// Stop a running daemon instance (other platforms - not supported)
+    #[cfg(not(any(unix, windows)))]
     pub fn stop(&self) -> Result<()> {
         Err(Error::Server(
             "Daemon stop is not supported on this platform".into(),
@@ -214,12 +354,50 @@ impl DaemonManager {
         &self.pid_file
     }
 
+    /// Get the path to the stop request file watched by the Windows daemon
+    pub fn get_stop_path(&self) -> &Path {
+        &self.stop_file
+    }
+
     /// Get the configuration directory
     pub fn get_config_dir(&self) -> &Path {
         &self.config_dir
     }
 }
 
+/// Check whether a process with the given PID exists
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    // Signal 0 checks for existence without actually sending a signal
+    signal::kill(Pid::from_raw(pid as i32), None).is_ok()
+}
+
+/// Check whether a process with the given PID exists
+#[cfg(windows)]
+fn process_alive(pid: u32) -> bool {
+    std::process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/NH", "/FO", "CSV"])
+        .output()
+        .map(|out| tasklist_lists_pid(&String::from_utf8_lossy(&out.stdout), pid))
+        .unwrap_or(false)
+}
+
+/// Whether CSV output of `tasklist /NH /FO CSV` has a row for `pid`.
+///
+/// tasklist prints an informational line (not a CSV row) when nothing
+/// matches, so the PID column is compared rather than searched for.
+#[cfg(any(windows, test))]
+fn tasklist_lists_pid(output: &str, pid: u32) -> bool {
+    let pid = format!("\"{}\"", pid);
+    output.lines().any(|row| row.split(',').nth(1) == Some(pid.as_str()))
+}
+
+/// Check whether a process with the given PID exists (not supported)
+#[cfg(not(any(unix, windows)))]
+fn process_alive(_pid: u32) -> bool {
+    false
+}
+
 impl Default for DaemonManager {
     fn default() -> Self {
         Self::new().expect("Failed to create DaemonManager")
@@ -235,6 +413,19 @@ mod tests {
         let mgr = DaemonManager::new().unwrap();
         assert!(mgr.get_pid_path().to_string_lossy().contains("only1mcp.pid"));
         assert!(mgr.get_log_path().to_string_lossy().contains("only1mcp.log"));
+        assert_eq!(mgr.get_stop_path().parent(), Some(mgr.get_config_dir()));
+    }
+
+    #[test]
+    fn test_tasklist_rows_matched_by_pid_column() {
+        let output = "\"only1mcp.exe\",\"4242\",\"Console\",\"1\",\"12,345 K\"\r\n";
+        assert!(tasklist_lists_pid(output, 4242));
+        assert!(!tasklist_lists_pid(output, 42));
+        // A PID that only appears in another column is not a match
+        assert!(!tasklist_lists_pid(output, 1));
+
+        let none = "INFO: No tasks are running which match the specified criteria.\r\n";
+        assert!(!tasklist_lists_pid(none, 4242));
     }
 
     #[test]
@@ -3,9 +3,15 @@
 //! Provides asynchronous signal handling for SIGTERM and SIGINT to enable
 //! graceful shutdown of the server.
 
+use std::path::Path;
+use std::time::Duration;
 use tokio::sync::broadcast;
 use tracing::{error, info};
 
+/// How often the Windows daemon checks for a stop request file
+#[cfg(windows)]
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
 /// Setup signal handlers for graceful shutdown
 ///
 /// Returns a broadcast sender that will send a shutdown signal when SIGTERM or SIGINT is received.
@@ -54,7 +60,10 @@ pub fn setup_signal_handlers() -> (broadcast::Sender<()>, broadcast::Receiver<()
 
 /// Setup signal handlers for graceful shutdown (Windows version)
 ///
-/// Windows doesn't support SIGTERM, so we only handle Ctrl+C.
+/// Windows doesn't support SIGTERM, so we handle Ctrl+C plus the stop request
+/// file written by `only1mcp stop` (see [`DaemonManager::stop`]).
+///
+/// [`DaemonManager::stop`]: crate::daemon::DaemonManager::stop
 #[cfg(windows)]
 pub fn setup_signal_handlers() -> (broadcast::Sender<()>, broadcast::Receiver<()>) {
     use tokio::signal;
@@ -62,14 +71,29 @@ pub fn setup_signal_handlers() -> (broadcast::Sender<()>, broadcast::Receiver<()
     let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
     let shutdown_tx_clone = shutdown_tx.clone();
 
+    let stop_file = crate::daemon::DaemonManager::new()
+        .ok()
+        .map(|mgr| mgr.get_stop_path().to_path_buf());
+
     tokio::spawn(async move {
-        if let Err(e) = signal::ctrl_c().await {
-            error!("Failed to listen for Ctrl+C: {}", e);
-            return;
+        tokio::select! {
+            result = signal::ctrl_c() => {
+                if let Err(e) = result {
+                    error!("Failed to listen for Ctrl+C: {}", e);
+                    return;
+                }
+                info!("Received Ctrl+C, initiating graceful shutdown");
+            }
+            _ = async {
+                match &stop_file {
+                    Some(stop_file) => wait_for_stop_file(stop_file, STOP_POLL_INTERVAL).await,
+                    None => std::future::pending().await,
+                }
+            } => {
+                info!("Received stop request, initiating graceful shutdown");
+            }
         }
 
-        info!("Received Ctrl+C, initiating graceful shutdown");
-
         if let Err(e) = shutdown_tx_clone.send(()) {
             error!("Failed to send shutdown signal: {}", e);
         }
@@ -78,6 +102,22 @@ pub fn setup_signal_handlers() -> (broadcast::Sender<()>, broadcast::Receiver<()
     (shutdown_tx, shutdown_rx)
 }
 
+/// Resolve once `stop_file` appears, checking every `poll` and removing
+/// the file so the next daemon does not stop straight away.
+///
+/// This is how the Windows daemon learns of `only1mcp stop`, which has no
+/// SIGTERM to send.
+pub async fn wait_for_stop_file(stop_file: &Path, poll: Duration) {
+    let mut interval = tokio::time::interval(poll);
+    loop {
+        interval.tick().await;
+        if stop_file.exists() {
+            let _ = std::fs::remove_file(stop_file);
+            return;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -87,4 +127,25 @@ mod tests {
         let (_tx, _rx) = setup_signal_handlers();
         // If we get here without panicking, the signal handler was set up successfully
     }
+
+    #[tokio::test]
+    async fn test_stop_file_fires_watcher_and_is_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let stop_file = dir.path().join("only1mcp.stop");
+        let watcher = tokio::spawn({
+            let stop_file = stop_file.clone();
+            async move { wait_for_stop_file(&stop_file, Duration::from_millis(10)).await }
+        });
+
+        // Nothing happens until the file is written
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!watcher.is_finished());
+
+        std::fs::write(&stop_file, "1234").unwrap();
+        tokio::time::timeout(Duration::from_secs(2), watcher)
+            .await
+            .expect("watcher did not fire")
+            .unwrap();
+        assert!(!stop_file.exists());
+    }
 }
@@ -0,0 +1,278 @@
+//! Zero-downtime binary upgrades (Unix only)
+//!
+//! On `SIGUSR2` the running process starts the executable at its own path
+//! (normally a freshly installed binary) with the same arguments, handing it
+//! every listening socket as an inherited file descriptor. The successor
+//! adopts those sockets instead of binding new ones, so connections keep
+//! being accepted throughout, and reports back once it is serving. Only
+//! then does the old process stop accepting, drain in-flight requests and
+//! exit. If the successor fails to start or does not report within
+//! `server.upgrade_timeout_seconds`, the old process keeps serving.
+
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::os::unix::net::UnixStream;
+use std::os::unix::process::CommandExt;
+use std::path::PathBuf;
+use std::process::{Child, Command};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use tracing::{error, info, warn};
+
+use crate::error::{Error, Result};
+
+/// Inherited listening sockets, as `name=fd` pairs separated by commas.
+pub const LISTEN_FDS_ENV: &str = "ONLY1MCP_LISTEN_FDS";
+
+/// Inherited socket the successor writes to once it is serving.
+pub const READY_FD_ENV: &str = "ONLY1MCP_UPGRADE_READY_FD";
+
+/// Listening sockets inherited from the previous process, until adopted.
+static INHERITED: OnceLock<Mutex<BTreeMap<String, RawFd>>> = OnceLock::new();
+
+/// Set once a successor has taken over the listening sockets.
+static HANDED_OVER: AtomicBool = AtomicBool::new(false);
+
+/// Whether this process was started by an upgrade of a running one.
+pub fn is_successor() -> bool {
+    std::env::var_os(LISTEN_FDS_ENV).is_some()
+}
+
+/// Whether the listening sockets now belong to a successor, so they must
+/// be left in place (e.g. Unix socket files) when this process exits.
+pub fn handed_over() -> bool {
+    HANDED_OVER.load(Ordering::SeqCst)
+}
+
+/// Take the socket inherited for listener `name`, if any.
+pub fn take_listener(name: &str) -> Option<OwnedFd> {
+    let fd = inherited().lock().ok()?.remove(name)?;
+    // SAFETY: the fd was passed to this process for this listener alone and
+    // is removed from the table, so it is owned exactly once
+    Some(unsafe { OwnedFd::from_raw_fd(fd) })
+}
+
+/// Tell the previous process that every listener is serving, closing the
+/// inherited sockets no listener claimed. Does nothing outside an upgrade.
+pub fn notify_ready() {
+    if let Ok(mut unclaimed) = inherited().lock() {
+        for (name, fd) in std::mem::take(&mut *unclaimed) {
+            info!("Closing inherited socket of removed listener {}", name);
+            // SAFETY: as in `take_listener`, unclaimed fds are owned here
+            drop(unsafe { OwnedFd::from_raw_fd(fd) });
+        }
+    }
+
+    let Some(fd) = ready_fd() else {
+        return;
+    };
+    // Only once: the variable stays set in the environment
+    static NOTIFIED: AtomicBool = AtomicBool::new(false);
+    if NOTIFIED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    // SAFETY: the previous process passed this end of its socket pair to us
+    let mut ready = unsafe { UnixStream::from_raw_fd(fd) };
+    if let Err(e) = ready.write_all(b"1") {
+        warn!("Failed to report upgrade readiness: {}", e);
+    }
+}
+
+/// Wait for `SIGUSR2` and hand `listeners` (name and socket) to a freshly
+/// started successor. Returns once one is serving them; the caller then
+/// drains and exits.
+pub async fn watch(listeners: Vec<(String, RawFd)>, ready_timeout: Duration) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut sigusr2 = match signal(SignalKind::user_defined2()) {
+        Ok(sigusr2) => sigusr2,
+        Err(e) => {
+            warn!("Binary upgrades disabled: failed to handle SIGUSR2: {}", e);
+            return;
+        },
+    };
+
+    while sigusr2.recv().await.is_some() {
+        info!("Received SIGUSR2, starting upgraded process");
+        match upgrade(&listeners, ready_timeout).await {
+            Ok(pid) => {
+                info!("Upgraded process {} is serving; draining this one", pid);
+                HANDED_OVER.store(true, Ordering::SeqCst);
+                return;
+            },
+            Err(e) => error!("Upgrade failed, continuing to serve: {}", e),
+        }
+    }
+}
+
+/// Start the successor and wait until it reports that it is serving.
+async fn upgrade(listeners: &[(String, RawFd)], ready_timeout: Duration) -> Result<u32> {
+    let (ours, theirs) = UnixStream::pair()
+        .map_err(|e| Error::Server(format!("Failed to create readiness socket: {}", e)))?;
+    let mut child = spawn_successor(listeners, theirs)?;
+    let pid = child.id();
+
+    // EOF before the readiness byte means the successor exited or failed
+    let wait = tokio::task::spawn_blocking(move || {
+        let mut ready = [0u8; 1];
+        (&ours).read(&mut ready).map(|read| read == 1)
+    });
+    let outcome = match tokio::time::timeout(ready_timeout, wait).await {
+        Ok(Ok(Ok(true))) => return Ok(pid),
+        Ok(Ok(Ok(false))) => format!("process {} exited before serving", pid),
+        Ok(Ok(Err(e))) => format!("failed to read readiness of process {}: {}", pid, e),
+        Ok(Err(e)) => format!("readiness wait failed: {}", e),
+        Err(_) => format!(
+            "process {} not serving after {:?}, stopping it",
+            pid, ready_timeout
+        ),
+    };
+
+    // Whatever state it is in, the successor must not keep the sockets
+    let _ = child.kill();
+    tokio::task::spawn_blocking(move || child.wait());
+    Err(Error::Server(outcome))
+}
+
+/// Exec this executable with the same arguments, passing `listeners` and
+/// the successor's end of the readiness socket pair.
+fn spawn_successor(listeners: &[(String, RawFd)], ready: UnixStream) -> Result<Child> {
+    let exe = executable()?;
+    let mut fds: Vec<RawFd> = listeners.iter().map(|(_, fd)| *fd).collect();
+    fds.push(ready.as_raw_fd());
+
+    let mut command = Command::new(&exe);
+    command
+        .args(std::env::args_os().skip(1))
+        .env(LISTEN_FDS_ENV, format_listen_fds(listeners))
+        .env(READY_FD_ENV, ready.as_raw_fd().to_string());
+    if let Some(dir) = launch_dir() {
+        command.current_dir(dir);
+    }
+    // SAFETY: only clears close-on-exec (async-signal-safe) in the forked
+    // child, so the sockets are not inherited by other spawned processes
+    unsafe {
+        command.pre_exec(move || {
+            for fd in &fds {
+                set_cloexec(*fd, false);
+            }
+            Ok(())
+        });
+    }
+
+    let child = command
+        .spawn()
+        .map_err(|e| Error::Server(format!("Failed to start {}: {}", exe.display(), e)))?;
+    info!(
+        "Started upgraded process {} from {}",
+        child.id(),
+        exe.display()
+    );
+    Ok(child)
+}
+
+/// Prepare for upgrades at startup, before daemonizing or spawning any
+/// process: claims the sockets inherited from a previous process, and
+/// remembers the working directory so relative paths in the arguments still
+/// resolve for a successor started after daemonizing.
+pub fn init() {
+    inherited();
+    if let Ok(dir) = std::env::current_dir() {
+        let _ = LAUNCH_DIR.set(dir);
+    }
+}
+
+static LAUNCH_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+fn launch_dir() -> Option<&'static PathBuf> {
+    LAUNCH_DIR.get()
+}
+
+/// Path of the executable, which after an in-place upgrade holds the new
+/// binary. Linux reports a replaced executable as `<path> (deleted)`.
+fn executable() -> Result<PathBuf> {
+    let exe = std::env::current_exe()
+        .map_err(|e| Error::Server(format!("Failed to locate executable: {}", e)))?;
+    let path = exe.to_string_lossy();
+    Ok(match path.strip_suffix(" (deleted)") {
+        Some(installed) => PathBuf::from(installed),
+        None => exe,
+    })
+}
+
+/// Inherited sockets, marked close-on-exec again on first use (before any
+/// backend is spawned) so they do not leak into processes started later.
+fn inherited() -> &'static Mutex<BTreeMap<String, RawFd>> {
+    INHERITED.get_or_init(|| {
+        let value = std::env::var(LISTEN_FDS_ENV).unwrap_or_default();
+        let fds = parse_listen_fds(&value).unwrap_or_else(|e| {
+            warn!("Ignoring {}: {}", LISTEN_FDS_ENV, e);
+            BTreeMap::new()
+        });
+        for fd in fds.values().copied().chain(ready_fd()) {
+            set_cloexec(fd, true);
+        }
+        Mutex::new(fds)
+    })
+}
+
+fn ready_fd() -> Option<RawFd> {
+    std::env::var(READY_FD_ENV).ok()?.parse().ok().filter(|fd| *fd > 2)
+}
+
+fn format_listen_fds(listeners: &[(String, RawFd)]) -> String {
+    listeners
+        .iter()
+        .map(|(name, fd)| format!("{}={}", name, fd))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn parse_listen_fds(value: &str) -> std::result::Result<BTreeMap<String, RawFd>, String> {
+    let mut fds = BTreeMap::new();
+    for entry in value.split(',').filter(|entry| !entry.is_empty()) {
+        let (name, fd) = entry
+            .rsplit_once('=')
+            .ok_or_else(|| format!("expected name=fd, got {:?}", entry))?;
+        let fd = fd
+            .parse::<RawFd>()
+            .ok()
+            .filter(|fd| *fd > 2)
+            .ok_or_else(|| format!("invalid descriptor in {:?}", entry))?;
+        fds.insert(name.to_string(), fd);
+    }
+    Ok(fds)
+}
+
+fn set_cloexec(fd: RawFd, cloexec: bool) {
+    let flags = if cloexec { libc::FD_CLOEXEC } else { 0 };
+    // SAFETY: F_SETFD on a descriptor only changes its flags
+    unsafe {
+        libc::fcntl(fd, libc::F_SETFD, flags);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_listen_fds_round_trip() {
+        let listeners = vec![("mcp".to_string(), 7), ("admin".to_string(), 9)];
+        let value = format_listen_fds(&listeners);
+        assert_eq!(value, "mcp=7,admin=9");
+
+        let parsed = parse_listen_fds(&value).unwrap();
+        assert_eq!(parsed.get("mcp"), Some(&7));
+        assert_eq!(parsed.get("admin"), Some(&9));
+        assert!(parse_listen_fds("").unwrap().is_empty());
+
+        assert!(parse_listen_fds("mcp").is_err());
+        assert!(parse_listen_fds("mcp=stdin").is_err());
+        // Standard streams are never listening sockets
+        assert!(parse_listen_fds("mcp=1").is_err());
+    }
+}
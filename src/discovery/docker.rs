@@ -0,0 +1,249 @@
+//! Docker discovery (`discovery.docker`).
+//!
+//! Every `poll_interval_ms`, the running containers with an
+//! `only1mcp.transport` label are listed through the Docker Engine API
+//! socket, and each becomes a backend described by its labels:
+//!
+//! | Label | Meaning |
+//! |-------|---------|
+//! | `only1mcp.transport` | `http`, `streamable_http`, `sse` or `stdio` |
+//! | `only1mcp.url` | Endpoint of the HTTP transports |
+//! | `only1mcp.command` | For `stdio`, run in the container with `docker exec -i` |
+//! | `only1mcp.tools` | Comma-separated tool globs routed to it (default: all) |
+//! | `only1mcp.id`, `only1mcp.name` | Server ID and name (default: container name) |
+//!
+//! Discovered servers are tagged `docker`. Containers whose labels do not
+//! describe a backend are skipped with a warning.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use serde_json::json;
+use tracing::warn;
+
+use crate::config::McpServerConfig;
+use crate::error::{Error, Result};
+
+/// Prefix of the labels describing a backend.
+pub const LABEL_PREFIX: &str = "only1mcp.";
+
+/// Tag of discovered servers.
+pub const TAG: &str = "docker";
+
+/// Longest container list read from the Docker API.
+const MAX_LIST_BYTES: usize = 16 * 1024 * 1024;
+
+/// A running container, as listed by `GET /containers/json`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Container {
+    pub id: String,
+    #[serde(default)]
+    pub names: Vec<String>,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+}
+
+impl Container {
+    /// Name without Docker's leading slash, else the short ID.
+    pub fn name(&self) -> &str {
+        match self.names.first() {
+            Some(name) => name.trim_start_matches('/'),
+            None => &self.id[..self.id.len().min(12)],
+        }
+    }
+
+    fn label(&self, key: &str) -> Option<&str> {
+        self.labels
+            .get(&format!("{}{}", LABEL_PREFIX, key))
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+
+    /// The backend described by the container's labels.
+    pub fn server_config(&self) -> std::result::Result<McpServerConfig, String> {
+        let name = self.name();
+        let transport = self
+            .label("transport")
+            .ok_or_else(|| format!("missing {}transport", LABEL_PREFIX))?;
+        let transport = match transport {
+            "http" | "streamable_http" | "sse" => {
+                let url = self.label("url").ok_or_else(|| {
+                    format!("transport {} requires {}url", transport, LABEL_PREFIX)
+                })?;
+                json!({"type": transport, "url": url})
+            },
+            "stdio" => {
+                let command = self
+                    .label("command")
+                    .ok_or_else(|| format!("transport stdio requires {}command", LABEL_PREFIX))?;
+                let args: Vec<&str> = ["exec", "-i", self.id.as_str()]
+                    .into_iter()
+                    .chain(command.split_whitespace())
+                    .collect();
+                json!({"type": "stdio", "command": "docker", "args": args})
+            },
+            other => return Err(format!("unsupported transport {}", other)),
+        };
+        let tools: Vec<&str> = self
+            .label("tools")
+            .map(|tools| tools.split(',').map(str::trim).filter(|t| !t.is_empty()).collect())
+            .unwrap_or_default();
+
+        serde_json::from_value(json!({
+            "id": self.label("id").unwrap_or(name),
+            "name": self.label("name").unwrap_or(name),
+            "transport": transport,
+            "routing": {"tools": tools},
+            "tags": [TAG],
+        }))
+        .map_err(|e| e.to_string())
+    }
+}
+
+/// Lists labelled containers, remembering which were already reported as
+/// invalid so each is warned about once.
+#[derive(Debug)]
+pub struct DockerDiscovery {
+    socket: PathBuf,
+    invalid: HashSet<String>,
+}
+
+impl DockerDiscovery {
+    pub fn new(socket: PathBuf) -> Self {
+        Self {
+            socket,
+            invalid: HashSet::new(),
+        }
+    }
+
+    /// The backends of the running containers. The first container
+    /// claiming a server ID gets it.
+    pub async fn poll(&mut self) -> Result<Vec<McpServerConfig>> {
+        let containers = list_containers(&self.socket).await?;
+        let mut servers: Vec<McpServerConfig> = Vec::new();
+        let mut invalid = HashSet::new();
+        for container in &containers {
+            let server = container.server_config().and_then(|server| {
+                match servers.iter().any(|s| s.id == server.id) {
+                    true => Err(format!("server ID {} is already taken", server.id)),
+                    false => Ok(server),
+                }
+            });
+            match server {
+                Ok(server) => servers.push(server),
+                Err(e) => {
+                    if !self.invalid.contains(&container.id) {
+                        warn!("Ignoring container {}: {}", container.name(), e);
+                    }
+                    invalid.insert(container.id.clone());
+                },
+            }
+        }
+        self.invalid = invalid;
+        Ok(servers)
+    }
+}
+
+/// Running containers with an `only1mcp.transport` label.
+pub async fn list_containers(socket: &Path) -> Result<Vec<Container>> {
+    let filters = json!({"label": [format!("{}transport", LABEL_PREFIX)]}).to_string();
+    let path = format!(
+        "/containers/json?filters={}",
+        url::form_urlencoded::byte_serialize(filters.as_bytes()).collect::<String>()
+    );
+    let body = get(socket, &path).await?;
+    serde_json::from_slice(&body).map_err(|e| Error::Transport(format!("Docker API: {}", e)))
+}
+
+/// `GET path` on the Docker Engine API.
+#[cfg(unix)]
+async fn get(socket: &Path, path: &str) -> Result<bytes::Bytes> {
+    use hyper_util::rt::TokioIo;
+
+    let api_error = |e: &dyn std::fmt::Display| Error::Transport(format!("Docker API: {}", e));
+    let stream = tokio::net::UnixStream::connect(socket)
+        .await
+        .map_err(|e| api_error(&format!("{}: {}", socket.display(), e)))?;
+    let (mut sender, connection) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+        .await
+        .map_err(|e| api_error(&e))?;
+    tokio::spawn(connection);
+
+    let request = hyper::Request::get(path)
+        .header(hyper::header::HOST, "docker")
+        .body(axum::body::Body::empty())
+        .map_err(|e| api_error(&e))?;
+    let response = sender.send_request(request).await.map_err(|e| api_error(&e))?;
+    let status = response.status();
+    let body = axum::body::to_bytes(axum::body::Body::new(response.into_body()), MAX_LIST_BYTES)
+        .await
+        .map_err(|e| api_error(&e))?;
+    if !status.is_success() {
+        return Err(api_error(&format!(
+            "{} {}",
+            status,
+            String::from_utf8_lossy(&body)
+        )));
+    }
+    Ok(body)
+}
+
+/// Docker Desktop on Windows serves its API on a named pipe, which is not
+/// supported.
+#[cfg(not(unix))]
+async fn get(_socket: &Path, _path: &str) -> Result<bytes::Bytes> {
+    Err(Error::Transport(
+        "Docker discovery requires a Unix socket".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn container(labels: &[(&str, &str)]) -> Container {
+        Container {
+            id: "0123456789abcdef".to_string(),
+            names: vec!["/weather".to_string()],
+            labels: labels.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        }
+    }
+
+    #[test]
+    fn test_labels_describe_server() {
+        let server = container(&[
+            ("only1mcp.transport", "streamable_http"),
+            ("only1mcp.url", "http://localhost:3000/mcp"),
+            ("only1mcp.tools", "weather_*, forecast"),
+        ])
+        .server_config()
+        .unwrap();
+        assert_eq!(server.id, "weather");
+        assert_eq!(server.routing.tools, ["weather_*", "forecast"]);
+        assert_eq!(server.tags, [TAG]);
+
+        let server = container(&[
+            ("only1mcp.transport", "stdio"),
+            ("only1mcp.command", "node server.js"),
+            ("only1mcp.id", "weather-dev"),
+        ])
+        .server_config()
+        .unwrap();
+        assert_eq!(server.id, "weather-dev");
+        match server.transport {
+            crate::config::TransportConfig::Stdio { command, args, .. } => {
+                assert_eq!(command, "docker");
+                assert_eq!(
+                    args,
+                    ["exec", "-i", "0123456789abcdef", "node", "server.js"]
+                );
+            },
+            other => panic!("unexpected transport {:?}", other),
+        }
+
+        let error = container(&[("only1mcp.transport", "http")]).server_config().unwrap_err();
+        assert!(error.contains("only1mcp.url"), "{}", error);
+    }
+}
@@ -0,0 +1,118 @@
+//! Backends found at runtime (`discovery`).
+//!
+//! A provider, such as [`docker`], reports every backend it currently sees
+//! to [`DiscoveredServers`]. The proxy adds them to each configuration it
+//! applies, whether from the file or the Admin API, so they come and go
+//! without being written to the configuration file. A server the file
+//! defines under the same ID wins over a discovered one.
+
+pub mod docker;
+
+use parking_lot::Mutex;
+use tracing::warn;
+
+use crate::config::{Config, McpServerConfig};
+
+/// The backends discovery currently sees.
+#[derive(Debug, Default)]
+pub struct DiscoveredServers {
+    servers: Mutex<Vec<McpServerConfig>>,
+    /// IDs the last [`merge_into`](Self::merge_into) added
+    merged: Mutex<Vec<String>>,
+}
+
+impl DiscoveredServers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the discovered backends, returning whether they changed.
+    pub fn set(&self, servers: Vec<McpServerConfig>) -> bool {
+        let mut current = self.servers.lock();
+        let changed = serde_json::to_value(&*current).ok() != serde_json::to_value(&servers).ok();
+        *current = servers;
+        changed
+    }
+
+    /// Add the discovered backends to `config`, except those whose ID it
+    /// already uses.
+    pub fn merge_into(&self, config: &mut Config) {
+        let mut merged = self.merged.lock();
+        *merged = self.add_to(config);
+        for id in self.servers.lock().iter().map(|s| &s.id).filter(|id| !merged.contains(id)) {
+            warn!(
+                "Discovered server {} is ignored: the configuration defines it",
+                id
+            );
+        }
+    }
+
+    /// Add the discovered backends to `config` like
+    /// [`merge_into`](Self::merge_into), without changing what
+    /// [`strip`](Self::strip) removes, e.g. to preview a configuration.
+    pub fn preview(&self, config: &mut Config) {
+        self.add_to(config);
+    }
+
+    /// Add the discovered backends whose ID `config` does not use, returning
+    /// their IDs.
+    fn add_to(&self, config: &mut Config) -> Vec<String> {
+        let servers = self.servers.lock();
+        let mut added = Vec::new();
+        for server in servers.iter() {
+            if config.servers.iter().any(|s| s.id == server.id) {
+                continue;
+            }
+            added.push(server.id.clone());
+            config.servers.push(server.clone());
+        }
+        added
+    }
+
+    /// `config` without the backends [`merge_into`](Self::merge_into) added.
+    pub fn strip(&self, config: &Config) -> Config {
+        let merged = self.merged.lock();
+        let mut config = config.clone();
+        config.servers.retain(|server| !merged.contains(&server.id));
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(id: &str, url: &str) -> McpServerConfig {
+        serde_yaml::from_str(&format!(
+            "id: {id}\nname: {id}\ntransport: {{type: http, url: '{url}'}}"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_merge_keeps_configured_servers() {
+        let discovered = DiscoveredServers::new();
+        assert!(discovered.set(vec![
+            server("weather", "http://discovered"),
+            server("search", "http://search"),
+        ]));
+        assert!(!discovered.set(vec![
+            server("weather", "http://discovered"),
+            server("search", "http://search"),
+        ]));
+
+        let mut config = Config::default();
+        config.servers.push(server("weather", "http://configured"));
+        discovered.merge_into(&mut config);
+        let ids: Vec<&str> = config.servers.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["weather", "search"]);
+        assert!(matches!(
+            &config.servers[0].transport,
+            crate::config::TransportConfig::Http { url, .. } if url == "http://configured"
+        ));
+
+        let stripped = discovered.strip(&config);
+        let ids: Vec<&str> = stripped.servers.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["weather"]);
+    }
+}
@@ -5,12 +5,18 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
-use serde_json::json;
+use serde_json::{json, Value};
 use std::io;
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// JSON-RPC error code for backend responses over `proxy.limits`
+pub const RESPONSE_TOO_LARGE: i64 = -32006;
+
+/// JSON-RPC error code for requests shed under `proxy.admission`
+pub const SERVER_BUSY: i64 = -32007;
+
 #[derive(Error, Debug, Clone)]
 pub enum Error {
     #[error("Configuration error: {0}")]
@@ -40,6 +46,9 @@ pub enum Error {
     #[error("All backends unhealthy for tool: {0}")]
     AllBackendsUnhealthy(String),
 
+    #[error("Backend response too large: {0}")]
+    ResponseTooLarge(String),
+
     #[error("Circuit breaker open for server: {0}")]
     CircuitBreakerOpen(String),
 
@@ -84,6 +93,7 @@ impl Error {
             Error::NoBackendAvailable(_) => 503,
             Error::AllBackendsUnhealthy(_) => 503,
             Error::BackendTimeout(_) => 504,
+            Error::ResponseTooLarge(_) => 502,
             Error::CircuitBreakerOpen(_) => 503,
             Error::RateLimitExceeded => 429,
             Error::AuthFailed(_) => 401,
@@ -98,9 +108,21 @@ pub enum ProxyError {
     #[error("Invalid request: {0}")]
     InvalidRequest(String),
 
+    #[error("Invalid params: {0}")]
+    InvalidParams(String),
+
     #[error("No backend available: {0}")]
     NoBackendAvailable(String),
 
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("Payload too large: {0}")]
+    PayloadTooLarge(String),
+
+    #[error("Server busy: {0}")]
+    Busy(String),
+
     #[error("Backend error: {0}")]
     BackendError(String),
 
@@ -130,37 +152,77 @@ impl ProxyError {
     pub fn is_retryable(&self) -> bool {
         matches!(self, ProxyError::BackendError(_) | ProxyError::Timeout(_))
     }
-}
 
-impl IntoResponse for ProxyError {
-    fn into_response(self) -> Response {
-        let (status, error_message) = match &self {
-            ProxyError::InvalidRequest(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
-            ProxyError::NoBackendAvailable(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg.clone()),
-            ProxyError::BackendError(msg) => (StatusCode::BAD_GATEWAY, msg.clone()),
-            ProxyError::Timeout(msg) => (StatusCode::GATEWAY_TIMEOUT, msg.clone()),
-            ProxyError::Transport(msg) => (StatusCode::BAD_GATEWAY, msg.clone()),
-            ProxyError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg.clone()),
-            ProxyError::Json(err) => (StatusCode::BAD_REQUEST, err.to_string()),
-            ProxyError::Serialization(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
-            ProxyError::Deserialization(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
-            ProxyError::Core(err) => (
-                StatusCode::from_u16(err.status_code())
-                    .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
-                err.to_string(),
-            ),
+    /// HTTP status code corresponding to this error.
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            ProxyError::InvalidRequest(_) => StatusCode::BAD_REQUEST,
+            ProxyError::InvalidParams(_) => StatusCode::BAD_REQUEST,
+            ProxyError::NoBackendAvailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            ProxyError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ProxyError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            ProxyError::Busy(_) => StatusCode::SERVICE_UNAVAILABLE,
+            ProxyError::BackendError(_) => StatusCode::BAD_GATEWAY,
+            ProxyError::Timeout(_) => StatusCode::GATEWAY_TIMEOUT,
+            ProxyError::Transport(_) => StatusCode::BAD_GATEWAY,
+            ProxyError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ProxyError::Json(_) => StatusCode::BAD_REQUEST,
+            ProxyError::Serialization(_) => StatusCode::BAD_REQUEST,
+            ProxyError::Deserialization(_) => StatusCode::BAD_REQUEST,
+            ProxyError::Core(err) => {
+                StatusCode::from_u16(err.status_code()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+            },
+        }
+    }
+
+    /// JSON-RPC error code: the HTTP status code, except for oversized
+    /// requests (-32600, Invalid Request), invalid params (-32602),
+    /// oversized backend responses ([`RESPONSE_TOO_LARGE`]) and shed
+    /// requests ([`SERVER_BUSY`]).
+    pub fn jsonrpc_code(&self) -> i64 {
+        match self {
+            ProxyError::PayloadTooLarge(_) => -32600,
+            ProxyError::InvalidParams(_) => -32602,
+            ProxyError::Busy(_) => SERVER_BUSY,
+            ProxyError::Core(Error::ResponseTooLarge(_)) => RESPONSE_TOO_LARGE,
+            _ => self.status_code().as_u16() as i64,
+        }
+    }
+
+    /// Build a JSON-RPC error response object for the request with `id`.
+    pub fn to_jsonrpc(&self, id: Value) -> Value {
+        let error_message = match self {
+            ProxyError::InvalidRequest(msg)
+            | ProxyError::InvalidParams(msg)
+            | ProxyError::NoBackendAvailable(msg)
+            | ProxyError::Forbidden(msg)
+            | ProxyError::PayloadTooLarge(msg)
+            | ProxyError::Busy(msg)
+            | ProxyError::BackendError(msg)
+            | ProxyError::Timeout(msg)
+            | ProxyError::Transport(msg)
+            | ProxyError::Internal(msg)
+            | ProxyError::Serialization(msg)
+            | ProxyError::Deserialization(msg) => msg.clone(),
+            ProxyError::Json(err) => err.to_string(),
+            ProxyError::Core(err) => err.to_string(),
         };
 
-        let body = Json(json!({
+        json!({
             "jsonrpc": "2.0",
             "error": {
-                "code": status.as_u16(),
+                "code": self.jsonrpc_code(),
                 "message": error_message,
             },
-            "id": null
-        }));
+            "id": id
+        })
+    }
+}
 
-        (status, body).into_response()
+impl IntoResponse for ProxyError {
+    fn into_response(self) -> Response {
+        let body = Json(self.to_jsonrpc(Value::Null));
+        (self.status_code(), body).into_response()
     }
 }
 
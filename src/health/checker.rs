@@ -2,7 +2,9 @@
 //! to quickly detect failures while minimizing overhead.
 
 use dashmap::DashMap;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -42,6 +44,12 @@ pub struct HealthStatus {
     /// Consecutive failures
     pub failure_count: u32,
 
+    /// When the last check completed
+    pub last_checked: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// Reason given by the last failed check
+    pub last_failure_reason: Option<String>,
+
     /// Average latency (ms)
     pub avg_latency: f64,
 
@@ -67,6 +75,8 @@ impl HealthStatus {
             last_failure: None,
             success_count: 0,
             failure_count: 0,
+            last_checked: None,
+            last_failure_reason: None,
             avg_latency: 0.0,
             error_rate: 0.0,
             resources: ResourceMetrics::default(),
@@ -74,7 +84,8 @@ impl HealthStatus {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum HealthState {
     /// Service is healthy and accepting requests
     Healthy,
@@ -95,11 +106,135 @@ impl HealthState {
     }
 }
 
+/// Shared map of backend ID to latest health state, written by checkers and
+/// read by the server registry for routing decisions.
+pub type HealthStateMap = Arc<DashMap<String, HealthState>>;
+
+/// Number of transitions kept by [`HealthHistory`]
+pub const HEALTH_HISTORY_CAPACITY: usize = 256;
+
+/// A backend's health state change, as observed by its active checker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthTransition {
+    pub server_id: String,
+    pub from: String,
+    pub to: String,
+    pub at: chrono::DateTime<chrono::Utc>,
+    /// Failure reason of the check that caused the transition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Bounded log of recent health transitions across all backends, shared by
+/// the health checkers; the oldest entries are dropped first.
+#[derive(Debug, Clone, Default)]
+pub struct HealthHistory {
+    entries: Arc<Mutex<VecDeque<HealthTransition>>>,
+}
+
+impl HealthHistory {
+    /// Append a transition, evicting the oldest once full.
+    pub fn record(&self, transition: HealthTransition) {
+        let mut entries = self.entries.lock();
+        if entries.len() >= HEALTH_HISTORY_CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back(transition);
+    }
+
+    /// Recorded transitions, oldest first.
+    pub fn snapshot(&self) -> Vec<HealthTransition> {
+        self.entries.lock().iter().cloned().collect()
+    }
+}
+
 /// Transport type for health checking
 #[derive(Debug, Clone)]
 pub enum HealthCheckTransport {
-    Http { endpoint: String },
-    Stdio { command: String, args: Vec<String> },
+    Http {
+        endpoint: String,
+        tls: Option<crate::config::BackendTlsConfig>,
+        proxy: Option<crate::config::OutboundProxyConfig>,
+    },
+    Stdio {
+        command: String,
+        args: Vec<String>,
+    },
+    Grpc {
+        endpoint: String,
+        tls: Option<crate::config::BackendTlsConfig>,
+    },
+    Unix {
+        path: std::path::PathBuf,
+    },
+}
+
+impl HealthCheckTransport {
+    /// Build the health check transport for a configured backend.
+    ///
+    /// SSE and Streamable HTTP backends are probed over plain HTTP, through
+    /// `proxy` (see [`crate::config::Config::outbound_proxy`]); gRPC and
+    /// Unix socket backends by opening a connection.
+    pub fn from_transport_config(
+        transport: &crate::config::TransportConfig,
+        proxy: Option<&crate::config::OutboundProxyConfig>,
+    ) -> Self {
+        use crate::config::TransportConfig;
+
+        match transport {
+            TransportConfig::Http { url, .. }
+            | TransportConfig::Sse { url, .. }
+            | TransportConfig::StreamableHttp { url, .. } => HealthCheckTransport::Http {
+                endpoint: url.clone(),
+                tls: transport.tls().cloned(),
+                proxy: proxy.cloned(),
+            },
+            TransportConfig::Stdio { command, args, .. } => HealthCheckTransport::Stdio {
+                command: command.clone(),
+                args: args.clone(),
+            },
+            TransportConfig::Grpc { endpoint, tls, .. } => HealthCheckTransport::Grpc {
+                endpoint: endpoint.clone(),
+                tls: tls.clone(),
+            },
+            TransportConfig::Unix { path } => HealthCheckTransport::Unix { path: path.clone() },
+            // Only checks the Docker CLI; starting a container per check is too costly
+            TransportConfig::Docker { .. } => HealthCheckTransport::Stdio {
+                command: "docker".to_string(),
+                args: Vec::new(),
+            },
+        }
+    }
+
+    /// Client for HTTP checks, presenting the backend's client certificate
+    /// and trusting its CA bundle when TLS settings are configured, and
+    /// connecting through its outbound proxy.
+    fn http_client(&self, timeout: Duration) -> crate::error::Result<reqwest::Client> {
+        let mut builder = reqwest::Client::builder().timeout(timeout);
+        if let HealthCheckTransport::Http { tls, proxy, .. } = self {
+            if let Some(tls) = tls {
+                builder = crate::transport::tls::configure_client(builder, tls)
+                    .map_err(crate::error::Error::Transport)?;
+            }
+            if let Some(proxy) = proxy {
+                builder = crate::transport::outbound_proxy::configure_client(builder, proxy)
+                    .map_err(crate::error::Error::Transport)?;
+            }
+        }
+        builder.build().map_err(|e| {
+            crate::error::Error::Transport(format!("Failed to build health check client: {}", e))
+        })
+    }
+
+    /// Metric label for this transport
+    fn label(&self) -> &'static str {
+        match self {
+            HealthCheckTransport::Http { .. } => "http",
+            HealthCheckTransport::Stdio { .. } => "stdio",
+            HealthCheckTransport::Grpc { .. } => "grpc",
+            HealthCheckTransport::Unix { .. } => "unix",
+        }
+    }
 }
 
 /// Individual backend health checker
@@ -134,18 +269,40 @@ pub struct HealthChecker {
     /// Circuit breaker integration (optional)
     circuit_breaker: Option<Arc<crate::health::circuit_breaker::CircuitBreakerManager>>,
 
+    /// Shared state map to publish health transitions to (optional)
+    state_sink: Option<HealthStateMap>,
+
+    /// Transition log to append state changes to (optional)
+    history: Option<HealthHistory>,
+
+    /// Restart supervision of STDIO processes (optional); a crash-looping
+    /// server is published as unhealthy whatever its checks report
+    restarts: Option<Arc<crate::transport::supervisor::RestartSupervisor>>,
+
+    /// Transport label for BACKEND_HEALTH_STATUS
+    transport_label: String,
+
+    /// Live configuration whose `proxy.chaos` faults apply to checks
+    /// (optional)
+    faults: Option<Arc<arc_swap::ArcSwap<crate::config::Config>>>,
+
     /// Shutdown signal
     shutdown: Arc<AtomicBool>,
 }
 
 impl HealthChecker {
     /// Create a new health checker from config module's HealthCheckConfig
+    ///
+    /// Fails when the backend's TLS material cannot be loaded or its proxy
+    /// URL is invalid.
     pub fn from_config(
         backend_id: String,
         transport: HealthCheckTransport,
         config: crate::config::HealthCheckConfig,
-    ) -> Self {
-        Self {
+    ) -> crate::error::Result<Self> {
+        let transport_label = transport.label().to_string();
+        let http_client = transport.http_client(Duration::from_secs(config.timeout_seconds))?;
+        Ok(Self {
             backend_id,
             transport,
             health_path: config.path,
@@ -154,13 +311,15 @@ impl HealthChecker {
             failure_threshold: config.unhealthy_threshold,
             success_threshold: config.healthy_threshold,
             status: Arc::new(RwLock::new(HealthStatus::new())),
-            http_client: reqwest::Client::builder()
-                .timeout(Duration::from_secs(config.timeout_seconds))
-                .build()
-                .unwrap(),
+            http_client,
             circuit_breaker: None,
+            state_sink: None,
+            history: None,
+            restarts: None,
+            transport_label,
+            faults: None,
             shutdown: Arc::new(AtomicBool::new(false)),
-        }
+        })
     }
 
     /// Create a new health checker (legacy method using local HealthCheckConfig)
@@ -168,8 +327,10 @@ impl HealthChecker {
         backend_id: String,
         transport: HealthCheckTransport,
         config: HealthCheckConfig,
-    ) -> Self {
-        Self {
+    ) -> crate::error::Result<Self> {
+        let transport_label = transport.label().to_string();
+        let http_client = transport.http_client(config.timeout)?;
+        Ok(Self {
             backend_id,
             transport,
             health_path: "/health".to_string(), // Default path
@@ -178,10 +339,15 @@ impl HealthChecker {
             failure_threshold: config.failure_threshold,
             success_threshold: config.success_threshold,
             status: Arc::new(RwLock::new(HealthStatus::new())),
-            http_client: reqwest::Client::builder().timeout(config.timeout).build().unwrap(),
+            http_client,
             circuit_breaker: None,
+            state_sink: None,
+            history: None,
+            restarts: None,
+            transport_label,
+            faults: None,
             shutdown: Arc::new(AtomicBool::new(false)),
-        }
+        })
     }
 
     /// Set circuit breaker for integration
@@ -193,6 +359,39 @@ impl HealthChecker {
         self
     }
 
+    /// Publish health state changes into a shared map (e.g. the server registry)
+    pub fn with_state_sink(mut self, sink: HealthStateMap) -> Self {
+        self.state_sink = Some(sink);
+        self
+    }
+
+    /// Record state changes in a shared transition log
+    pub fn with_history(mut self, history: HealthHistory) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Keep the backend unhealthy while its STDIO processes crash loop
+    pub fn with_restart_supervisor(
+        mut self,
+        restarts: Arc<crate::transport::supervisor::RestartSupervisor>,
+    ) -> Self {
+        self.restarts = Some(restarts);
+        self
+    }
+
+    /// Override the transport label used for backend health metrics
+    pub fn with_transport_label(mut self, label: impl Into<String>) -> Self {
+        self.transport_label = label.into();
+        self
+    }
+
+    /// Inject the `proxy.chaos` faults marked for health checks
+    pub fn with_faults(mut self, config: Arc<arc_swap::ArcSwap<crate::config::Config>>) -> Self {
+        self.faults = Some(config);
+        self
+    }
+
     /// Start continuous health checking
     pub async fn start(self: Arc<Self>) {
         let mut interval = interval(self.interval);
@@ -209,17 +408,94 @@ impl HealthChecker {
         }
     }
 
+    /// Run one check right away, outside the regular interval, and return
+    /// the resulting state.
+    pub async fn check_now(&self) -> HealthState {
+        let result = self.perform_check().await;
+        self.update_status(result).await;
+        self.status.read().await.state.clone()
+    }
+
+    /// Run one check without recording it, returning its latency or why it
+    /// failed. A STDIO command is only looked up in `PATH`, not run.
+    pub async fn probe(&self) -> std::result::Result<Duration, String> {
+        let start = Instant::now();
+        let result = match &self.transport {
+            HealthCheckTransport::Stdio { command, .. } => match which::which(command) {
+                Ok(_) => Self::connect_result(Ok(()), start),
+                Err(_) => Self::connect_result(
+                    Err(format!("Command '{}' not found in PATH", command)),
+                    start,
+                ),
+            },
+            _ => self.perform_check().await,
+        };
+        match result {
+            HealthCheckResult::Success { latency, .. } => Ok(latency),
+            HealthCheckResult::Failure { reason, .. } => Err(reason),
+        }
+    }
+
     /// Perform single health check
     async fn perform_check(&self) -> HealthCheckResult {
         let start = Instant::now();
 
+        if let Some(config) = &self.faults {
+            let injection = crate::proxy::chaos::Injection::for_health_check(
+                &config.load().proxy.chaos,
+                &self.backend_id,
+            );
+            injection.delay().await;
+            let reason = match injection.fault {
+                Some(crate::proxy::chaos::Fault::Error(error)) => {
+                    Some(format!("Injected fault: {}", error.message))
+                },
+                Some(crate::proxy::chaos::Fault::Drop) => {
+                    tokio::time::sleep(self.timeout).await;
+                    Some("Injected fault: no reply".to_string())
+                },
+                None => None,
+            };
+            if let Some(reason) = reason {
+                return HealthCheckResult::Failure {
+                    reason,
+                    _latency: Some(start.elapsed()),
+                };
+            }
+        }
+
         match &self.transport {
-            HealthCheckTransport::Http { endpoint } => {
+            HealthCheckTransport::Http { endpoint, .. } => {
                 self.perform_http_check(endpoint, start).await
             },
             HealthCheckTransport::Stdio { command, args } => {
                 self.perform_stdio_check(command, args, start).await
             },
+            HealthCheckTransport::Grpc { endpoint, tls } => {
+                let probe = crate::transport::grpc::probe(endpoint, tls.as_ref(), self.timeout);
+                Self::connect_result(probe.await.map_err(|e| e.to_string()), start)
+            },
+            HealthCheckTransport::Unix { path } => {
+                let probe = crate::transport::unix::probe(path, self.timeout);
+                Self::connect_result(probe.await.map_err(|e| e.to_string()), start)
+            },
+        }
+    }
+
+    /// Result of a check that only opens a connection
+    fn connect_result(
+        result: std::result::Result<(), String>,
+        start: Instant,
+    ) -> HealthCheckResult {
+        match result {
+            Ok(()) => HealthCheckResult::Success {
+                latency: start.elapsed(),
+                details: None,
+            },
+            Err(reason) => HealthCheckResult::Failure {
+                reason,
+                _latency: Some(start.elapsed()),
+            },
         }
     }
 
@@ -349,6 +625,12 @@ impl HealthChecker {
         use crate::metrics::HEALTH_CHECK_TOTAL;
 
         let mut status = self.status.write().await;
+        let state_before = status.state.clone();
+        status.last_checked = Some(chrono::Utc::now());
+        let failure_reason = match &result {
+            HealthCheckResult::Failure { reason, .. } => Some(reason.clone()),
+            HealthCheckResult::Success { .. } => None,
+        };
 
         match result {
             HealthCheckResult::Success { latency, details } => {
@@ -400,6 +682,7 @@ impl HealthChecker {
                 HEALTH_CHECK_TOTAL.with_label_values(&[&self.backend_id, "failure"]).inc();
 
                 status.last_failure = Some(Instant::now());
+                status.last_failure_reason = Some(reason.clone());
                 status.failure_count += 1;
                 status.success_count = 0;
 
@@ -441,6 +724,27 @@ impl HealthChecker {
             },
         }
 
+        // Publish latest state for routing decisions
+        if let Some(sink) = &self.state_sink {
+            let crash_looping =
+                self.restarts.as_ref().is_some_and(|r| r.is_crash_looping(&self.backend_id));
+            let published = match crash_looping {
+                true => HealthState::Unhealthy,
+                false => status.state.clone(),
+            };
+            sink.insert(self.backend_id.clone(), published);
+        }
+
+        if let Some(history) = self.history.as_ref().filter(|_| status.state != state_before) {
+            history.record(HealthTransition {
+                server_id: self.backend_id.clone(),
+                from: format!("{:?}", state_before),
+                to: format!("{:?}", status.state),
+                at: chrono::Utc::now(),
+                reason: failure_reason,
+            });
+        }
+
         // Emit metrics
         self.emit_metrics(&status);
     }
@@ -452,6 +756,11 @@ impl HealthChecker {
         // Record health status gauge (0 = unhealthy, 1 = healthy)
         let health_value = if status.state.is_healthy() { 1.0 } else { 0.0 };
         SERVER_HEALTH_STATUS.with_label_values(&[&self.backend_id]).set(health_value);
+        crate::metrics::update_backend_health(
+            &self.backend_id,
+            &self.transport_label,
+            status.state.is_healthy(),
+        );
 
         // Record latency
         HEALTH_CHECK_DURATION_SECONDS
@@ -0,0 +1,174 @@
+//! Hot-path JSON parsing.
+//!
+//! Client payloads, cached responses and backend responses are parsed through
+//! this module rather than calling `serde_json` directly. With the `simd-json`
+//! feature enabled, parsing is done by simd-json (SIMD-accelerated, roughly
+//! 2-3x faster on large tool catalogs); otherwise it falls back to serde_json.
+//! Both paths produce identical values, which is covered by the conformance
+//! tests below.
+//!
+//! Serialization goes through [`to_bytes`], which writes into a per-thread
+//! reusable `BytesMut` and hands out the output as [`Bytes`] of its own, used
+//! both for response cache entries and axum response bodies. Cached list
+//! results are parsed again on a hit, since they are filtered and annotated
+//! for each caller before being sent.
+
+use crate::error::{Error, Result};
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::cell::RefCell;
+
+/// Capacity reserved in the thread-local buffer before each serialization
+const BUFFER_RESERVE: usize = 4 * 1024;
+
+/// Buffers that grew beyond this are dropped instead of being kept for reuse
+const BUFFER_RETAIN_LIMIT: usize = 1024 * 1024;
+
+thread_local! {
+    static BUFFER: RefCell<BytesMut> = RefCell::new(BytesMut::with_capacity(BUFFER_RESERVE));
+}
+
+/// Deserialize a value from a JSON byte slice.
+#[cfg(not(feature = "simd-json"))]
+pub fn from_slice<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    serde_json::from_slice(bytes).map_err(|e| Error::Deserialization(e.to_string()))
+}
+
+/// Deserialize a value from a JSON byte slice.
+///
+/// simd-json parses in place, so the input is copied into a scratch buffer.
+#[cfg(feature = "simd-json")]
+pub fn from_slice<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    let mut buffer = bytes.to_vec();
+    simd_json::serde::from_slice(&mut buffer).map_err(|e| Error::Deserialization(e.to_string()))
+}
+
+/// Deserialize a value from a JSON string.
+pub fn from_str<T: DeserializeOwned>(json: &str) -> Result<T> {
+    from_slice(json.as_bytes())
+}
+
+/// Serialize a value into `Bytes` using the thread-local buffer pool.
+///
+/// The value is written into the pooled buffer, whose capacity is reused by
+/// the next call on this thread, and copied out into an allocation of its
+/// own. Splitting the output off the buffer instead would let a small cached
+/// `Bytes` keep the whole (up to [`BUFFER_RETAIN_LIMIT`]) buffer alive, out
+/// of sight of the cache's memory accounting.
+pub fn to_bytes<T: Serialize + ?Sized>(value: &T) -> Result<Bytes> {
+    BUFFER.with(|cell| {
+        let mut buffer = cell.borrow_mut();
+        if buffer.capacity() > BUFFER_RETAIN_LIMIT {
+            *buffer = BytesMut::with_capacity(BUFFER_RESERVE);
+        }
+        buffer.reserve(BUFFER_RESERVE);
+
+        let written = serde_json::to_writer((&mut *buffer).writer(), value);
+        let out = Bytes::copy_from_slice(&buffer);
+        buffer.clear();
+        match written {
+            Ok(()) => Ok(out),
+            Err(e) => Err(Error::Serialization(e.to_string())),
+        }
+    })
+}
+
+/// Name of the active JSON parser backend (for diagnostics).
+pub fn backend() -> &'static str {
+    if cfg!(feature = "simd-json") {
+        "simd-json"
+    } else {
+        "serde_json"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{McpRequest, McpResponse};
+    use serde_json::Value;
+
+    /// Documents covering escapes, unicode, numbers and nesting edge cases.
+    const CONFORMANCE_CASES: &[&str] = &[
+        r#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#,
+        r#"{"jsonrpc":"2.0","id":"abc","result":{"tools":[]}}"#,
+        r#"{"s":"tab\tnewline\nquote\"backslash\\slash\/"}"#,
+        r#"{"u":"é中😀","raw":"é中😀"}"#,
+        r#"{"i":-9223372036854775808,"u":18446744073709551615,"z":0}"#,
+        r#"{"f":1.5,"e":-2.5e-10,"big":1e300,"pi":3.141592653589793}"#,
+        r#"{"a":[1,[2,[3,[4,[5]]]]],"o":{"o":{"o":{}}}}"#,
+        r#"{"t":true,"f":false,"n":null}"#,
+        r#"  {  "ws" :  [ 1 , 2 ]  }  "#,
+        r#"[{"jsonrpc":"2.0","id":1,"method":"a"},{"jsonrpc":"2.0","method":"b"}]"#,
+        r#""plain string""#,
+        r#"42"#,
+    ];
+
+    const INVALID_CASES: &[&str] = &[
+        "",
+        "{",
+        r#"{"a":}"#,
+        r#"{"a":1,}"#,
+        "invalid json{",
+        r#"{"a":"\x"}"#,
+    ];
+
+    #[test]
+    fn test_values_match_serde_json() {
+        for case in CONFORMANCE_CASES {
+            let expected: Value = serde_json::from_str(case).unwrap();
+            let actual: Value = from_str(case).unwrap();
+            assert_eq!(actual, expected, "mismatch for {}", case);
+        }
+    }
+
+    #[test]
+    fn test_invalid_documents_rejected() {
+        for case in INVALID_CASES {
+            assert!(from_str::<Value>(case).is_err(), "accepted {:?}", case);
+        }
+    }
+
+    #[test]
+    fn test_to_bytes_matches_serde_json() {
+        for case in CONFORMANCE_CASES {
+            let value: Value = serde_json::from_str(case).unwrap();
+            let first = to_bytes(&value).unwrap();
+            let second = to_bytes(&value).unwrap();
+
+            // Earlier output must stay intact while the pooled buffer is reused
+            assert_eq!(first, serde_json::to_vec(&value).unwrap());
+            assert_eq!(first, second);
+        }
+    }
+
+    #[test]
+    fn test_small_output_does_not_hold_pooled_buffer() {
+        // Grow the pooled buffer well past what the small value needs
+        let large = Value::String("x".repeat(512 * 1024));
+        to_bytes(&large).unwrap();
+
+        let small = to_bytes(&serde_json::json!({"jsonrpc": "2.0", "id": 1})).unwrap();
+        let owned = small.try_into_mut().expect("output shares the pooled buffer's allocation");
+        assert!(owned.capacity() < 1024, "holds {} bytes", owned.capacity());
+    }
+
+    #[test]
+    fn test_typed_deserialization_matches() {
+        let request = r#"{"jsonrpc":"2.0","id":7,"method":"tools/call","params":{"name":"x"}}"#;
+        let expected: McpRequest = serde_json::from_str(request).unwrap();
+        let actual: McpRequest = from_str(request).unwrap();
+        assert_eq!(actual.method, expected.method);
+        assert_eq!(actual.id, expected.id);
+        assert_eq!(actual.params, expected.params);
+
+        let response = r#"{"jsonrpc":"2.0","id":7,"error":{"code":-32601,"message":"nope"}}"#;
+        let expected: McpResponse = serde_json::from_str(response).unwrap();
+        let actual: McpResponse = from_str(response).unwrap();
+        assert_eq!(
+            serde_json::to_value(actual).unwrap(),
+            serde_json::to_value(expected).unwrap()
+        );
+    }
+}
@@ -5,13 +5,18 @@
 
 pub mod auth;
 pub mod batching;
+pub mod benchmark;
 pub mod cache;
 pub mod config;
 pub mod daemon;
+pub mod discovery;
 pub mod error;
 pub mod health;
+pub mod json;
 pub mod metrics;
+pub mod mock;
 pub mod proxy;
+pub mod replay;
 pub mod routing;
 pub mod transport;
 pub mod tui;
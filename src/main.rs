@@ -16,7 +16,7 @@
 use clap::{Parser, Subcommand};
 use only1mcp::{config, error, proxy, Result};
 use std::path::PathBuf;
-use tracing::info;
+use tracing::{info, warn};
 
 #[derive(Parser)]
 #[command(name = "only1mcp")]
@@ -31,6 +31,10 @@ struct Cli {
     #[arg(long, env = "ONLY1MCP_LOG_LEVEL", default_value = "info")]
     log_level: String,
 
+    /// Override a configuration value, e.g. `--set server.port=9090` (repeatable)
+    #[arg(long = "set", value_name = "KEY=VALUE", global = true)]
+    set: Vec<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -39,7 +43,7 @@ struct Cli {
 enum Commands {
     /// Start the proxy server
     Start {
-        /// Server host
+        /// Server host (IPv6 literals like `::` are accepted)
         #[arg(long, default_value = "127.0.0.1")]
         host: String,
 
@@ -50,6 +54,15 @@ enum Commands {
         /// Run in foreground (do not daemonize)
         #[arg(long, short = 'f')]
         foreground: bool,
+
+        /// Answer from fixtures instead of starting any backend
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Fixture file (YAML/JSON) or recording (.jsonl) for --dry-run
+        /// [default: observability.recording.path]
+        #[arg(long, requires = "dry_run", value_name = "FILE")]
+        fixtures: Option<PathBuf>,
     },
 
     /// Stop a running daemon instance
@@ -60,10 +73,18 @@ enum Commands {
         /// Configuration file to validate
         #[arg(value_name = "CONFIG")]
         config: PathBuf,
+
+        /// Report unknown keys as errors instead of warnings
+        #[arg(long)]
+        deny_unknown_fields: bool,
     },
 
     /// List configured servers
-    List,
+    List {
+        /// Also show tags, notes and per-tool metadata
+        #[arg(long)]
+        detailed: bool,
+    },
 
     /// Add a new MCP server
     Add {
@@ -75,7 +96,7 @@ enum Commands {
         #[arg(long)]
         name: String,
 
-        /// Transport type (stdio, http, sse)
+        /// Transport type (stdio, http, sse, streamable_http, grpc, unix, docker)
         #[arg(long)]
         transport: String,
 
@@ -83,9 +104,31 @@ enum Commands {
         #[arg(long)]
         command: Option<String>,
 
-        /// URL for HTTP transport
+        /// Argument passed to the STDIO command or container (repeatable, kept
+        /// in order)
+        #[arg(long = "arg", value_name = "ARG", allow_hyphen_values = true)]
+        args: Vec<String>,
+
+        /// Environment variable for the STDIO process or container (repeatable)
+        #[arg(long, value_name = "KEY=VALUE", value_parser = config::edit::parse_env_pair)]
+        env: Vec<(String, String)>,
+
+        /// Working directory for the STDIO process
+        #[arg(long)]
+        cwd: Option<String>,
+
+        /// URL for HTTP, SSE, streamable HTTP or gRPC transport, or socket
+        /// path for unix
         #[arg(long)]
         url: Option<String>,
+
+        /// Request header for HTTP-based transports (repeatable)
+        #[arg(long = "header", value_name = "NAME: VALUE", value_parser = config::edit::parse_header)]
+        headers: Vec<(String, String)>,
+
+        /// Container image for Docker transport
+        #[arg(long)]
+        image: Option<String>,
     },
 
     /// Remove an MCP server
@@ -94,6 +137,44 @@ enum Commands {
         id: String,
     },
 
+    /// Search the catalog of known MCP servers
+    Search {
+        /// Words to look for in names, tags and descriptions (empty lists all)
+        query: Vec<String>,
+
+        /// Catalog URL or file to use instead of the built-in one
+        #[arg(long, env = "ONLY1MCP_REGISTRY", value_name = "URL|FILE")]
+        registry: Option<String>,
+    },
+
+    /// Add a server from the catalog to the configuration
+    Install {
+        /// Catalog name of the server
+        name: String,
+
+        /// Server ID [default: the catalog name]
+        #[arg(long)]
+        id: Option<String>,
+
+        /// Argument appended to the server's command, e.g. a directory
+        /// (repeatable, kept in order)
+        #[arg(long = "arg", value_name = "ARG", allow_hyphen_values = true)]
+        args: Vec<String>,
+
+        /// Environment value for the server (repeatable); variables it needs
+        /// that are not given are read from the proxy's environment
+        #[arg(long, value_name = "KEY=VALUE", value_parser = config::edit::parse_env_pair)]
+        env: Vec<(String, String)>,
+
+        /// Catalog URL or file to use instead of the built-in one
+        #[arg(long, env = "ONLY1MCP_REGISTRY", value_name = "URL|FILE")]
+        registry: Option<String>,
+
+        /// Run a health check against the server once it is added
+        #[arg(long)]
+        test: bool,
+    },
+
     /// Test connection to a server
     Test {
         /// Server ID to test
@@ -103,15 +184,23 @@ enum Commands {
     /// Show server health status
     Status,
 
-    /// View logs
+    /// View the daemon log, or a STDIO server's captured output
     Logs {
-        /// Filter by server ID
+        /// Show this server's output instead of the daemon log
         #[arg(long)]
         server: Option<String>,
 
+        /// Lines shown from the end
+        #[arg(short = 'n', long, default_value_t = 50)]
+        lines: usize,
+
         /// Follow logs
         #[arg(short, long)]
         follow: bool,
+
+        /// Ask the running daemon to rotate its log file now
+        #[arg(long, conflicts_with_all = ["server", "follow"])]
+        rotate_now: bool,
     },
 
     /// Generate configuration template
@@ -120,8 +209,35 @@ enum Commands {
         action: ConfigCommands,
     },
 
+    /// Export or import runtime state for support
+    State {
+        #[command(subcommand)]
+        action: StateCommands,
+    },
+
     /// Interactive TUI mode
-    Tui,
+    Tui {
+        /// Admin API of a remote daemon, e.g. https://proxy.example.com:8443
+        /// [default: the local daemon, started if needed]
+        #[arg(long)]
+        url: Option<String>,
+
+        /// API key for admin listeners that require one
+        #[arg(long, env = "ONLY1MCP_TOKEN", hide_env_values = true, requires = "url")]
+        token: Option<String>,
+
+        /// Extra CA bundle (PEM) trusted for an https URL
+        #[arg(long, requires = "url")]
+        ca_cert: Option<PathBuf>,
+
+        /// Client certificate (PEM) for daemons that require mutual TLS
+        #[arg(long, requires_all = ["url", "client_key"])]
+        client_cert: Option<PathBuf>,
+
+        /// Private key (PEM) for --client-cert
+        #[arg(long, requires = "client_cert")]
+        client_key: Option<PathBuf>,
+    },
 
     /// Run benchmarks
     Benchmark {
@@ -132,6 +248,61 @@ enum Commands {
         /// Number of concurrent connections
         #[arg(long, default_value = "100")]
         concurrency: usize,
+
+        /// JSON-RPC endpoint of a running proxy
+        #[arg(long, default_value = "http://127.0.0.1:8080/mcp")]
+        url: String,
+
+        /// Target requests per second (unlimited if omitted)
+        #[arg(long)]
+        rps: Option<f64>,
+
+        /// JSON-RPC method to call
+        #[arg(long, default_value = "tools/list")]
+        method: String,
+    },
+
+    /// Run a mock MCP server for testing proxies and clients
+    MockServer {
+        /// Fixture file (YAML/JSON) or recording (.jsonl) with the tools to
+        /// serve [default: a single `echo` tool]
+        #[arg(long, value_name = "FILE")]
+        tools: Option<PathBuf>,
+
+        /// Serve HTTP on this port instead of STDIO
+        #[arg(long)]
+        port: Option<u16>,
+
+        /// Host to bind with --port
+        #[arg(long, default_value = "127.0.0.1", requires = "port")]
+        host: String,
+
+        /// Delay before every reply, in milliseconds
+        #[arg(long, default_value = "0")]
+        latency_ms: u64,
+
+        /// Share of tool calls, 0.0 to 1.0, failed with an error
+        #[arg(long, default_value = "0")]
+        fail_rate: f64,
+    },
+
+    /// Re-send a recorded session and compare the replies
+    Replay {
+        /// Recording written under observability.recording
+        file: PathBuf,
+
+        /// JSON-RPC endpoint of a running proxy
+        #[arg(long, default_value = "http://127.0.0.1:8080/mcp")]
+        url: String,
+
+        /// API key for listeners that require one
+        #[arg(long, env = "ONLY1MCP_TOKEN", hide_env_values = true)]
+        token: Option<String>,
+
+        /// JSON pointer left out of comparisons, e.g. /result/content/0/text
+        /// (repeatable)
+        #[arg(long, value_name = "POINTER")]
+        ignore: Vec<String>,
     },
 }
 
@@ -157,15 +328,75 @@ enum ConfigCommands {
 
     /// Validate and fix configuration
     Doctor,
+
+    /// Print the JSON Schema of the configuration file, for editors and CI
+    Schema,
+
+    /// Print the snippet an MCP client needs to connect to this proxy
+    Export {
+        /// Client to configure (claude-desktop, cursor, generic)
+        #[arg(long = "for", value_name = "CLIENT")]
+        client: config::client::ClientKind,
+
+        /// Server name shown in the client
+        #[arg(long, default_value = "only1mcp")]
+        name: String,
+
+        /// Print YAML instead of JSON
+        #[arg(long)]
+        yaml: bool,
+    },
+
+    /// Restore the configuration file from a backup
+    Rollback {
+        /// Backup timestamp (or a unique prefix of it) [default: the newest]
+        #[arg(long, value_name = "TIMESTAMP")]
+        to: Option<String>,
+
+        /// List available backups instead of restoring
+        #[arg(long, conflicts_with = "to")]
+        list: bool,
+
+        /// Use the versions a running proxy applied instead of the edit
+        /// backups [default: the last good one]
+        #[arg(long)]
+        applied: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum StateCommands {
+    /// Write a sanitized support bundle (config, registry, health history, cache stats)
+    Export {
+        /// Output file [default: only1mcp-state-<timestamp>.json]
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+
+    /// Write a support bundle's configuration for a local instance
+    Import {
+        /// Bundle produced by `state export`
+        bundle: PathBuf,
+
+        /// Where to write the configuration
+        #[arg(long, default_value = "only1mcp-imported.yaml")]
+        config_out: PathBuf,
+
+        /// Overwrite the configuration file if it exists
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // Parse command-line arguments
     let cli = Cli::parse();
+    config::overrides::set_cli_overrides(&cli.set)?;
 
-    // Initialize tracing/logging
-    init_tracing(&cli.log_level)?;
+    // Initialize tracing/logging; a STDIO mock server keeps stdout for MCP
+    let log_to_stderr = matches!(cli.command, Commands::MockServer { port: None, .. });
+    init_tracing(&cli.log_level, log_to_stderr)?;
 
     info!("Only1MCP v{} starting...", env!("CARGO_PKG_VERSION"));
 
@@ -175,6 +406,8 @@ async fn main() -> Result<()> {
             host,
             port,
             foreground,
+            dry_run,
+            fixtures,
         } => {
             // Load configuration with path tracking for Start command
             let (config, config_path) =
@@ -183,15 +416,23 @@ async fn main() -> Result<()> {
 
             let daemon_mgr = DaemonManager::new()?;
 
-            // Check if already running
-            if daemon_mgr.is_running() {
+            // Check if already running; a binary upgrade starts while the
+            // process it replaces is still serving
+            #[cfg(unix)]
+            let upgrading = only1mcp::daemon::upgrade::is_successor();
+            #[cfg(not(unix))]
+            let upgrading = false;
+            if daemon_mgr.is_running() && !upgrading {
                 eprintln!("Only1MCP is already running. Use 'only1mcp stop' to stop it first.");
                 std::process::exit(1);
             }
 
+            #[cfg(unix)]
+            only1mcp::daemon::upgrade::init();
+
             // Daemonize if not in foreground mode
             if !foreground {
-                #[cfg(unix)]
+                #[cfg(any(unix, windows))]
                 {
                     println!("Starting Only1MCP in daemon mode...");
                     println!("Log file: {}", daemon_mgr.get_log_path().display());
@@ -200,12 +441,14 @@ async fn main() -> Result<()> {
 
                     daemon_mgr.daemonize()?;
 
-                    // After daemonization, we're in the child process
-                    // Redirect logging to file
-                    let log_file = std::fs::OpenOptions::new()
-                        .create(true)
-                        .append(true)
-                        .open(daemon_mgr.get_log_path())?;
+                    // After daemonization, we're in the child process (on Windows,
+                    // the detached re-launch of this executable)
+                    // Redirect logging to the rotating log file
+                    let log_file = only1mcp::daemon::log_file::DaemonLog::open(
+                        daemon_mgr.get_log_path(),
+                        config.observability.logging.rotation.clone(),
+                    )?
+                    .install();
 
                     use tracing_subscriber::{fmt, prelude::*, EnvFilter};
                     let filter = EnvFilter::try_from_default_env()
@@ -213,11 +456,11 @@ async fn main() -> Result<()> {
 
                     tracing_subscriber::registry()
                         .with(filter)
-                        .with(fmt::layer().with_writer(log_file).with_ansi(false))
+                        .with(fmt::layer().with_writer(move || log_file.writer()).with_ansi(false))
                         .init();
                 }
 
-                #[cfg(not(unix))]
+                #[cfg(not(any(unix, windows)))]
                 {
                     eprintln!(
                         "Daemon mode is not supported on this platform. Use --foreground flag."
@@ -226,16 +469,54 @@ async fn main() -> Result<()> {
                 }
             }
 
-            info!("Starting proxy server on {}:{}", host, port);
+            info!(
+                "Starting proxy server on {}",
+                config::host_port(&host, port)
+            );
 
             // Create server (config already loaded above)
             let mut modified_config = config.clone();
             modified_config.server.host = host.clone();
             modified_config.server.port = port;
 
-            let server = proxy::ProxyServer::new(modified_config, config_path).await?;
+            // No backend is started in a dry run
+            let fixtures = if dry_run {
+                let path = fixtures.unwrap_or_else(|| config.observability.recording.path.clone());
+                let fixtures = only1mcp::proxy::dry_run::Fixtures::load(&path)?;
+                info!(
+                    "Dry run: answering from {} ({} tools)",
+                    path.display(),
+                    fixtures.tools.len()
+                );
+                modified_config.servers.clear();
+                Some(fixtures)
+            } else {
+                None
+            };
 
-            println!("Server listening on http://{}:{}", host, port);
+            let mut server = proxy::ProxyServer::new(modified_config, config_path).await?;
+            if let Some(fixtures) = fixtures {
+                server = server.with_dry_run(fixtures);
+            }
+
+            let listeners = server.config().server.listeners.clone();
+            if listeners.is_empty() {
+                println!(
+                    "Server listening on http://{}",
+                    config::host_port(&host, port)
+                );
+            } else {
+                for listener in &listeners {
+                    let routes: Vec<String> =
+                        listener.routes.iter().map(|r| format!("{:?}", r).to_lowercase()).collect();
+                    println!(
+                        "Listener {} on {} ({})",
+                        listener.name,
+                        listener.bind,
+                        routes.join(", ")
+                    );
+                }
+            }
 
             // Display or log loaded servers
             if foreground {
@@ -244,31 +525,34 @@ async fn main() -> Result<()> {
                 server.log_loaded_servers().await?;
             }
 
+            // Apply config file edits live (in both foreground and daemon mode);
+            // the loader owns the file watcher, so keep it alive while serving.
+            // A dry run stays without backends.
+            let _config_watcher = if dry_run {
+                None
+            } else {
+                match server.watch_config() {
+                    Ok(loader) => Some(loader),
+                    Err(e) => {
+                        warn!("Configuration hot-reload disabled: {}", e);
+                        None
+                    },
+                }
+            };
+
             // Setup signal handlers for graceful shutdown
             let (_shutdown_tx, mut shutdown_rx) =
                 only1mcp::daemon::signals::setup_signal_handlers();
 
-            // Run server with graceful shutdown
-            let router = server.build_router_public();
-
-            let addr = format!("{}:{}", host, port)
-                .parse::<std::net::SocketAddr>()
-                .map_err(|e| error::Error::Config(format!("Invalid address: {}", e)))?;
-            let listener = tokio::net::TcpListener::bind(addr)
-                .await
-                .map_err(|e| error::Error::Server(format!("Failed to bind: {}", e)))?;
-
-            info!("Server listening on {}", addr);
+            // A signal starts draining: new requests are refused while
+            // in-flight ones finish (bounded by server.drain_timeout_seconds)
+            let handle = server.clone();
+            tokio::spawn(async move {
+                let _ = shutdown_rx.recv().await;
+                handle.shutdown();
+            });
 
-            axum::serve(listener, router)
-                .with_graceful_shutdown(async move {
-                    let _ = shutdown_rx.recv().await;
-                    info!("Shutting down proxy server gracefully...");
-                })
-                .await
-                .map_err(|e| error::Error::Server(format!("Server error: {}", e)))?;
-
-            info!("Proxy server stopped");
+            server.run().await?;
         },
 
         Commands::Stop => {
@@ -288,22 +572,33 @@ async fn main() -> Result<()> {
 
         Commands::Validate {
             config: config_path,
+            deny_unknown_fields,
         } => {
             info!("Validating configuration: {:?}", config_path);
-            match config::Config::validate_file(&config_path) {
-                Ok(_) => {
-                    println!("✓ Configuration valid");
-                    std::process::exit(0);
-                },
+            let diagnostics = match config::Config::check_file(&config_path, deny_unknown_fields) {
+                Ok(diagnostics) => diagnostics,
                 Err(e) => {
-                    eprintln!("✗ Configuration errors found:");
-                    eprintln!("{}", e);
+                    eprintln!("✗ {}", e);
                     std::process::exit(1);
                 },
+            };
+            let (errors, warnings): (Vec<_>, Vec<_>) =
+                diagnostics.iter().partition(|d| d.severity == config::schema::Severity::Error);
+            for warning in &warnings {
+                eprintln!("{}", warning);
+            }
+            if errors.is_empty() {
+                println!("✓ Configuration valid");
+                std::process::exit(0);
             }
+            eprintln!("✗ Configuration errors found:");
+            for error in &errors {
+                eprintln!("{}", error);
+            }
+            std::process::exit(1);
         },
 
-        Commands::List => {
+        Commands::List { detailed } => {
             let config = if let Some(config_path) = &cli.config {
                 config::Config::from_file(config_path)?
             } else {
@@ -316,12 +611,43 @@ async fn main() -> Result<()> {
                     "  - {} ({}): {:?}",
                     server.id, server.name, server.transport
                 );
+                if detailed {
+                    print_server_metadata(server);
+                }
             }
         },
 
-        Commands::Add { .. } => {
-            println!("Server addition via CLI not yet implemented");
-            println!("Please edit configuration file or use admin API");
+        Commands::Add {
+            id,
+            name,
+            transport,
+            command,
+            args,
+            env,
+            cwd,
+            url,
+            headers,
+            image,
+        } => {
+            let (_, config_path) =
+                config::Config::discover_and_load_with_path_tuple(cli.config.clone())?;
+
+            let spec = config::edit::ServerSpec {
+                id,
+                name,
+                transport,
+                command,
+                args,
+                env,
+                cwd,
+                url,
+                headers,
+                image,
+            };
+            config::edit::add_server(&config_path, &spec)?;
+
+            println!("✓ Added server '{}' to {}", spec.id, config_path.display());
+            println!("  A running instance picks up the change automatically");
         },
 
         Commands::Remove { .. } => {
@@ -329,6 +655,72 @@ async fn main() -> Result<()> {
             println!("Please edit configuration file or use admin API");
         },
 
+        Commands::Search { query, registry } => {
+            use config::marketplace::Marketplace;
+
+            let marketplace = Marketplace::load(registry.as_deref()).await?;
+            let entries = marketplace.search(&query.join(" "));
+            if entries.is_empty() {
+                println!("No servers match '{}'", query.join(" "));
+            }
+            for entry in entries {
+                println!("{} - {}", entry.name, entry.description);
+                if !entry.arg_hints.is_empty() {
+                    println!("    arguments: {}", entry.arg_hints.join(" "));
+                }
+                if !entry.env.is_empty() {
+                    println!("    environment: {}", entry.env.join(", "));
+                }
+            }
+        },
+
+        Commands::Install {
+            name,
+            id,
+            args,
+            env,
+            registry,
+            test,
+        } => {
+            use config::marketplace::{InstallOptions, Marketplace};
+
+            let marketplace = Marketplace::load(registry.as_deref()).await?;
+            let entry = marketplace.get(&name)?;
+            let referenced: Vec<&String> = entry
+                .env
+                .iter()
+                .filter(|name| !env.iter().any(|(given, _)| given == *name))
+                .collect();
+            // References to unset variables would stop the config from loading
+            if let Some(unset) = referenced.iter().find(|name| std::env::var(name).is_err()) {
+                return Err(error::Error::Config(format!(
+                    "{} needs {}: set it in the environment, or pass --env {}=<value> \
+                     (file:// and keyring:// references work too)",
+                    entry.name, unset, unset
+                )));
+            }
+            let spec = entry.server_spec(&InstallOptions { id, args, env })?;
+
+            let (_, config_path) =
+                config::Config::discover_and_load_with_path_tuple(cli.config.clone())?;
+            config::edit::add_server(&config_path, &spec)?;
+
+            println!(
+                "✓ Installed {} as server '{}' in {}",
+                entry.title(),
+                spec.id,
+                config_path.display()
+            );
+            for name in referenced {
+                println!("  {} is read from the proxy's environment", name);
+            }
+            println!("  A running instance picks up the change automatically");
+
+            if test {
+                install_test(&config_path, &spec.id).await?;
+            }
+        },
+
         Commands::Test { id } => {
             println!("Testing connection to server: {}", id);
             // Phase 3 feature: Connection testing with diagnostics
@@ -336,12 +728,113 @@ async fn main() -> Result<()> {
         },
 
         Commands::Status => {
+            use only1mcp::tui::TuiClient;
+
+            let config = if let Some(config_path) = &cli.config {
+                config::Config::from_file(config_path)?
+            } else {
+                config::Config::discover_and_load()?
+            };
+            let (admin_host, admin_port) = config.server.admin_address();
+            let client = TuiClient::new(&admin_host, admin_port);
+
+            let servers = match client.get_servers().await {
+                Ok(servers) => servers,
+                Err(e) => {
+                    eprintln!("Only1MCP is not reachable at {}: {}", client.base_url(), e);
+                    std::process::exit(1);
+                },
+            };
+
             println!("Server health status:");
-            println!("  (Status monitoring not yet implemented)");
+            for server in &servers {
+                let health = match (server.enabled, &server.health) {
+                    (false, _) => "Disabled",
+                    (true, Some(health)) => health.as_str(),
+                    (true, None) => "Unknown",
+                };
+                println!(
+                    "  - {} ({}): {}, {} tools",
+                    server.id, server.transport, health, server.tool_count
+                );
+                if let Some(startup) = &server.startup {
+                    print_startup_status(startup);
+                }
+                if let Some(restart) = &server.restart {
+                    print_restart_status(restart);
+                }
+            }
         },
 
-        Commands::Logs { .. } => {
-            println!("Log viewing not yet implemented");
+        Commands::Logs {
+            server,
+            lines,
+            follow,
+            rotate_now,
+        } => {
+            if rotate_now {
+                use only1mcp::tui::TuiClient;
+
+                let config = match &cli.config {
+                    Some(config_path) => config::Config::from_file(config_path)?,
+                    None => config::Config::discover_and_load()?,
+                };
+                let (admin_host, admin_port) = config.server.admin_address();
+                let client = TuiClient::new(&admin_host, admin_port);
+                match client.rotate_logs().await {
+                    Ok(result) => println!("{}", result.message),
+                    Err(e) => {
+                        eprintln!("Failed to rotate logs at {}: {}", client.base_url(), e);
+                        std::process::exit(1);
+                    },
+                }
+                return Ok(());
+            }
+
+            let (path, tail) = match &server {
+                Some(id) => {
+                    let config = match &cli.config {
+                        Some(config_path) => config::Config::from_file(config_path)?,
+                        None => config::Config::discover_and_load()?,
+                    };
+                    let logs = &config.observability.backend_logs;
+                    if !config.servers.iter().any(|s| &s.id == id) {
+                        eprintln!("Server '{}' is not configured", id);
+                        std::process::exit(1);
+                    }
+                    let Some(path) = logs.path(id) else {
+                        eprintln!("Cannot determine the backend log directory");
+                        std::process::exit(1);
+                    };
+                    let tail = only1mcp::transport::backend_logs::tail(logs, id, lines)?;
+                    (path, tail)
+                },
+                None => {
+                    let path = only1mcp::daemon::DaemonManager::new()?.get_log_path().to_path_buf();
+                    let tail = match std::fs::read_to_string(&path) {
+                        Ok(content) => {
+                            let all: Vec<&str> = content.lines().collect();
+                            all[all.len().saturating_sub(lines)..]
+                                .iter()
+                                .map(|line| line.to_string())
+                                .collect()
+                        },
+                        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+                        Err(e) => return Err(e.into()),
+                    };
+                    (path, tail)
+                },
+            };
+
+            if tail.is_empty() && !follow {
+                println!("No logs at {}", path.display());
+            }
+            for line in &tail {
+                println!("{}", line);
+            }
+            if follow {
+                follow_file(&path).await?;
+            }
         },
 
         Commands::Config { action } => {
@@ -360,10 +853,94 @@ async fn main() -> Result<()> {
                     // Phase 3 feature: Config validation and diagnostics
                     println!("  (Config doctor not yet implemented - planned for Phase 3)");
                 },
+                ConfigCommands::Schema => {
+                    let schema = config::schema::config_schema();
+                    println!("{}", serde_json::to_string_pretty(&schema)?);
+                },
+                ConfigCommands::Export { client, name, yaml } => {
+                    use config::client::{client_snippet, McpEndpoint};
+
+                    let (config, _) =
+                        config::Config::discover_and_load_with_path_tuple(cli.config.clone())?;
+                    let endpoint = McpEndpoint::from_config(&config)?;
+                    let snippet = client_snippet(client, &endpoint, &name);
+                    if yaml {
+                        print!("{}", serde_yaml::to_string(&snippet)?);
+                    } else {
+                        println!("{}", serde_json::to_string_pretty(&snippet)?);
+                    }
+                    // Hints go to stderr so the snippet can be piped
+                    eprintln!("Add this to {}", client.config_location());
+                    if endpoint.requires_auth {
+                        eprintln!(
+                            "Replace {} with an API key accepted by the listener",
+                            config::client::API_KEY_PLACEHOLDER
+                        );
+                    }
+                },
+                ConfigCommands::Rollback { to, list, applied } => {
+                    config_rollback(cli.config.clone(), to, list, applied)?;
+                },
             }
         },
 
-        Commands::Tui => {
+        Commands::Tui {
+            url: Some(url),
+            token,
+            ca_cert,
+            client_cert,
+            client_key,
+        } => {
+            use only1mcp::tui::{RemoteOptions, TuiClient};
+
+            info!(
+                "Starting TUI interface for {} (Press 'q' or Ctrl+C to quit)",
+                url
+            );
+
+            let options = RemoteOptions {
+                token,
+                tls: config::BackendTlsConfig {
+                    client_cert_path: client_cert,
+                    client_key_path: client_key,
+                    ca_path: ca_cert,
+                },
+            };
+            let client = TuiClient::connect(&url, &options)?;
+
+            // Older daemons may lack the health endpoint; anything else is fatal
+            match client.get_health().await {
+                Ok(_) => {},
+                Err(e @ error::Error::Http(_)) => {
+                    warn!("Continuing with limited features: {}", e);
+                },
+                Err(e) => {
+                    eprintln!("❌ Cannot connect to {}: {}", url, e);
+                    std::process::exit(1);
+                },
+            }
+
+            // Remote servers are not in the local config, so none is loaded
+            let (event_tx, event_rx) = tokio::sync::mpsc::unbounded_channel();
+            let servers_poller = tokio::spawn(
+                TuiClient::connect(&url, &options)?
+                    .poll_servers(event_tx.clone(), std::time::Duration::from_secs(2)),
+            );
+            let requests_poller = tokio::spawn(
+                TuiClient::connect(&url, &options)?
+                    .poll_requests(event_tx, std::time::Duration::from_secs(2)),
+            );
+
+            let config = std::sync::Arc::new(config::Config::default());
+            let result = only1mcp::tui::run_tui(config, None, client, event_rx).await;
+            servers_poller.abort();
+            requests_poller.abort();
+            result?;
+
+            info!("TUI interface closed");
+        },
+
+        Commands::Tui { url: None, .. } => {
             use only1mcp::daemon::DaemonManager;
             use only1mcp::tui::TuiClient;
             use std::io::Write;
@@ -390,8 +967,7 @@ async fn main() -> Result<()> {
                 println!("📁 Config: {}", config_path.display());
 
                 // Get host/port from config
-                let host = config.server.host.as_str();
-                let port = config.server.port;
+                let (host, port) = config.server.admin_address();
 
                 // Start daemon
                 daemon_mgr.daemonize()?;
@@ -401,7 +977,7 @@ async fn main() -> Result<()> {
                 tokio::time::sleep(std::time::Duration::from_secs(2)).await;
 
                 // Poll for readiness (max 10 attempts = 5 seconds)
-                let tui_client = TuiClient::new(host, port);
+                let tui_client = TuiClient::new(&host, port);
                 let mut attempts = 0;
                 while !tui_client.is_running().await && attempts < 10 {
                     tokio::time::sleep(std::time::Duration::from_millis(500)).await;
@@ -417,21 +993,34 @@ async fn main() -> Result<()> {
                 println!("✅ Daemon started successfully.\n");
             }
 
-            // Load configuration for TUI
-            let config = if let Some(config_path) = &cli.config {
-                config::Config::from_file(config_path)?
-            } else {
-                config::Config::discover_and_load()?
-            };
+            // Load configuration for TUI (the path is where onboarding saves servers)
+            let (config, config_path) =
+                config::Config::discover_and_load_with_path_tuple(cli.config.clone())?;
 
             // Create event channel for TUI communication
-            let (_event_tx, event_rx) = tokio::sync::mpsc::unbounded_channel();
+            let (event_tx, event_rx) = tokio::sync::mpsc::unbounded_channel();
+
+            // Keep the Servers tab current, including after actions taken from it
+            let (admin_host, admin_port) = config.server.admin_address();
+            let servers_poller = tokio::spawn(
+                TuiClient::new(&admin_host, admin_port)
+                    .poll_servers(event_tx.clone(), std::time::Duration::from_secs(2)),
+            );
+            let requests_poller = tokio::spawn(
+                TuiClient::new(&admin_host, admin_port)
+                    .poll_requests(event_tx, std::time::Duration::from_secs(2)),
+            );
+            let client = TuiClient::new(&admin_host, admin_port);
 
             // Convert Config to Arc for thread-safe sharing
             let config_arc = std::sync::Arc::new(config);
 
             // Launch TUI dashboard (blocks until user quits)
-            only1mcp::tui::run_tui(config_arc, event_rx).await?;
+            let result =
+                only1mcp::tui::run_tui(config_arc, Some(config_path), client, event_rx).await;
+            servers_poller.abort();
+            requests_poller.abort();
+            result?;
 
             info!("TUI interface closed");
 
@@ -457,32 +1046,341 @@ async fn main() -> Result<()> {
             }
         },
 
+        Commands::State { action } => {
+            use only1mcp::proxy::snapshot::StateBundle;
+
+            match action {
+                StateCommands::Export { output } => {
+                    let config = if let Some(config_path) = &cli.config {
+                        config::Config::from_file(config_path)?
+                    } else {
+                        config::Config::discover_and_load()?
+                    };
+
+                    // Prefer the live state of a running instance
+                    let (host, port) = config.server.admin_address();
+                    let client = only1mcp::tui::TuiClient::new(&host, port);
+                    let bundle = match client.get_state().await {
+                        Ok(bundle) => bundle,
+                        Err(e) => {
+                            warn!("Could not fetch runtime state: {}", e);
+                            println!(
+                                "No running instance reachable; exporting configuration only."
+                            );
+                            StateBundle::from_config(&config)
+                        },
+                    };
+
+                    let output = output.unwrap_or_else(|| {
+                        PathBuf::from(format!(
+                            "only1mcp-state-{}.json",
+                            bundle.exported_at.format("%Y%m%d-%H%M%S")
+                        ))
+                    });
+                    bundle.write(&output)?;
+
+                    print!("{}", bundle);
+                    println!("State bundle written to {}", output.display());
+                },
+                StateCommands::Import {
+                    bundle,
+                    config_out,
+                    force,
+                } => {
+                    let bundle = StateBundle::read(&bundle)?;
+
+                    if config_out.exists() && !force {
+                        return Err(error::Error::Config(format!(
+                            "{} already exists (use --force to overwrite)",
+                            config_out.display()
+                        )));
+                    }
+                    let yaml = serde_yaml::to_string(&bundle.config)
+                        .map_err(|e| error::Error::Serialization(e.to_string()))?;
+                    std::fs::write(&config_out, yaml)?;
+
+                    print!("{}", bundle);
+                    println!("Configuration written to {}", config_out.display());
+                    println!(
+                        "Start it with: only1mcp --config {} start --foreground",
+                        config_out.display()
+                    );
+                },
+            }
+        },
+
         Commands::Benchmark {
             requests,
             concurrency,
+            url,
+            rps,
+            method,
         } => {
             println!(
-                "Running benchmark with {} requests and {} concurrent connections",
-                requests, concurrency
+                "Running benchmark against {} with {} requests and {} concurrent connections",
+                url, requests, concurrency
             );
-            // Phase 3 feature: Performance benchmarking tool
-            println!("  (Benchmarking not yet implemented - planned for Phase 3)");
+
+            let report = only1mcp::benchmark::run(only1mcp::benchmark::BenchmarkConfig {
+                url,
+                requests,
+                concurrency,
+                target_rps: rps,
+                method,
+                ..Default::default()
+            })
+            .await?;
+
+            println!("{}", report);
+        },
+
+        Commands::MockServer {
+            tools,
+            port,
+            host,
+            latency_ms,
+            fail_rate,
+        } => {
+            use only1mcp::mock::{echo_fixtures, MockOptions, MockServer};
+
+            if !(0.0..=1.0).contains(&fail_rate) {
+                return Err(error::Error::Config(format!(
+                    "--fail-rate must be between 0.0 and 1.0, got {}",
+                    fail_rate
+                )));
+            }
+            let fixtures = match tools {
+                Some(path) => only1mcp::proxy::dry_run::Fixtures::load(&path)?,
+                None => echo_fixtures(),
+            };
+            let server = MockServer::new(MockOptions {
+                fixtures,
+                latency: std::time::Duration::from_millis(latency_ms),
+                fail_rate,
+            });
+
+            match port {
+                Some(port) => {
+                    let addr = config::host_port(&host, port).parse().map_err(|e| {
+                        error::Error::Config(format!(
+                            "Invalid address {}: {}",
+                            config::host_port(&host, port),
+                            e
+                        ))
+                    })?;
+                    server.serve_http(addr).await?;
+                },
+                None => server.serve_stdio().await?,
+            }
+        },
+
+        Commands::Replay {
+            file,
+            url,
+            token,
+            ignore,
+        } => {
+            let exchanges = only1mcp::proxy::recording::read_recording(&file)?;
+            println!(
+                "Replaying {} requests from {} against {}",
+                exchanges.len(),
+                file.display(),
+                url
+            );
+
+            let report = only1mcp::replay::replay(
+                &exchanges,
+                &only1mcp::replay::ReplayOptions {
+                    url,
+                    token,
+                    ignore,
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+            println!("{}", report);
+            if !report.is_clean() {
+                std::process::exit(1);
+            }
         },
     }
 
     Ok(())
 }
 
-fn init_tracing(log_level: &str) -> Result<()> {
+/// Print lines appended to `path` until interrupted, starting over when
+/// the file is rotated.
+async fn follow_file(path: &std::path::Path) -> Result<()> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut offset = std::fs::metadata(path).map_or(0, |meta| meta.len());
+    loop {
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        let Ok(mut file) = std::fs::File::open(path) else {
+            continue;
+        };
+        let len = file.metadata()?.len();
+        if len < offset {
+            offset = 0;
+        }
+        if len == offset {
+            continue;
+        }
+        file.seek(SeekFrom::Start(offset))?;
+        let mut appended = Vec::new();
+        file.take(len - offset).read_to_end(&mut appended)?;
+        print!("{}", String::from_utf8_lossy(&appended));
+        offset = len;
+    }
+}
+
+fn init_tracing(log_level: &str, to_stderr: bool) -> Result<()> {
     use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
     let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(log_level));
 
-    tracing_subscriber::registry().with(filter).with(fmt::layer()).init();
+    if to_stderr {
+        tracing_subscriber::registry()
+            .with(filter)
+            .with(fmt::layer().with_writer(std::io::stderr))
+            .init();
+    } else {
+        tracing_subscriber::registry().with(filter).with(fmt::layer()).init();
+    }
+
+    Ok(())
+}
+
+/// Run one health check against a newly installed server.
+async fn install_test(config_path: &std::path::Path, id: &str) -> Result<()> {
+    use only1mcp::health::checker::{HealthCheckTransport, HealthChecker, HealthState};
+
+    let config = config::Config::from_file(config_path)?;
+    let server = config
+        .servers
+        .iter()
+        .find(|server| server.id == id)
+        .ok_or_else(|| error::Error::Config(format!("Server '{}' not found", id)))?;
+    // One check decides either way
+    let health_check = config::HealthCheckConfig {
+        healthy_threshold: 1,
+        unhealthy_threshold: 1,
+        ..server.health_check.clone()
+    };
+    let checker = HealthChecker::from_config(
+        server.id.clone(),
+        HealthCheckTransport::from_transport_config(
+            &server.transport,
+            config.outbound_proxy(&server.transport),
+        ),
+        health_check,
+    )?;
+
+    println!("Testing connection to '{}'...", id);
+    match checker.check_now().await {
+        HealthState::Healthy => println!("✓ '{}' is healthy", id),
+        state => {
+            eprintln!(
+                "✗ '{}' is {:?}; check its command and environment",
+                id, state
+            );
+            std::process::exit(1);
+        },
+    }
+    Ok(())
+}
+
+/// List configuration backups, or restore the config file from one.
+fn config_rollback(
+    config: Option<PathBuf>,
+    to: Option<String>,
+    list: bool,
+    applied: bool,
+) -> Result<()> {
+    use only1mcp::config::backup;
+
+    // The file may be the bad version being rolled back, so it is not loaded
+    let config_path = config::Config::discover_path(config)?;
+    let kind = if applied { "applied version" } else { "backup" };
+
+    if list {
+        let backups = match applied {
+            true => backup::list_applied(&config_path)?,
+            false => backup::list_backups(&config_path)?,
+        };
+        if backups.is_empty() {
+            println!("No {}s of {}", kind, config_path.display());
+        }
+        for backup in backups.iter().rev() {
+            println!("  {}  {}", backup.timestamp, backup.path.display());
+        }
+        return Ok(());
+    }
 
+    let restored = match applied {
+        true => backup::rollback_applied(&config_path, to.as_deref())?,
+        false => backup::rollback(&config_path, to.as_deref())?,
+    };
+    println!(
+        "✓ Restored {} from {} {}",
+        config_path.display(),
+        kind,
+        restored.timestamp
+    );
+    println!("  A running instance picks up the change automatically");
     Ok(())
 }
 
+/// Print a server's tags, notes and per-tool metadata (for `list --detailed`)
+fn print_server_metadata(server: &config::McpServerConfig) {
+    if !server.tags.is_empty() {
+        println!("      tags: {}", server.tags.join(", "));
+    }
+    if let Some(notes) = &server.notes {
+        println!("      notes: {}", notes);
+    }
+    for (tool, metadata) in &server.tool_metadata {
+        let mut line = format!("      tool {}", tool);
+        if !metadata.tags.is_empty() {
+            line.push_str(&format!(" [{}]", metadata.tags.join(", ")));
+        }
+        if let Some(notes) = &metadata.notes {
+            line.push_str(&format!(": {}", notes));
+        }
+        println!("{}", line);
+    }
+}
+
+fn print_startup_status(startup: &only1mcp::types::StartupStatus) {
+    use only1mcp::types::StartupState;
+
+    let state = match startup.state {
+        StartupState::Deferred => "not started",
+        StartupState::Starting => "starting",
+        StartupState::Ready => "ready",
+        StartupState::Failed => "failed",
+    };
+    match &startup.error {
+        Some(error) => println!("      startup: {} ({}): {}", startup.policy, state, error),
+        None => println!("      startup: {} ({})", startup.policy, state),
+    }
+}
+
+fn print_restart_status(restart: &only1mcp::types::RestartStatus) {
+    let mut line = format!(
+        "      restart: {}, {} restart(s)",
+        restart.policy, restart.restarts
+    );
+    if restart.crash_loop {
+        line.push_str(", crash looping");
+    }
+    if let Some(exit) = &restart.last_exit {
+        line.push_str(&format!(", last exit: {}", exit));
+    }
+    println!("{}", line);
+}
+
 fn generate_config_template(template_type: &str) -> Result<String> {
     match template_type {
         "solo" => Ok(include_str!("../config/templates/solo.yaml").to_string()),
@@ -0,0 +1,466 @@
+//! Built-in alerting (`observability.alerts`).
+//!
+//! Every `interval_seconds` the proxy takes an [`Observation`] of its own
+//! state: which backends are unhealthy, how many requests each backend has
+//! served and failed (from `only1mcp_mcp_requests_total`), and the response
+//! cache's hits and misses. The [`AlertEngine`] compares it with earlier
+//! observations to evaluate every rule over its window, and reports the
+//! alerts that start or stop firing. Those are logged and sent to the rule's
+//! notifiers; the ones still firing are served at
+//! `GET /api/v1/admin/alerts`.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use prometheus::core::Collector;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::config::{AlertCondition, AlertNotifierKind, AlertRuleConfig, AlertsConfig};
+
+/// How long a notifier may take to accept an alert
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Subject of cache alerts
+const CACHE_SUBJECT: &str = "cache";
+
+/// Requests served by one backend since startup.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RequestCounts {
+    pub total: u64,
+    /// Requests whose status was not `success`
+    pub failed: u64,
+}
+
+/// The proxy's state at one evaluation.
+#[derive(Debug, Clone)]
+pub struct Observation {
+    pub at: Instant,
+    /// Enabled backends and whether each is unhealthy
+    pub backends: BTreeMap<String, bool>,
+    /// Requests per backend since startup
+    pub requests: HashMap<String, RequestCounts>,
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+}
+
+/// Whether an alert started or stopped firing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertState {
+    Firing,
+    Resolved,
+}
+
+/// An alert starting or stopping, as sent to notifiers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertEvent {
+    pub rule: String,
+    /// Backend id, or `cache`
+    pub subject: String,
+    pub state: AlertState,
+    pub summary: String,
+    pub at: DateTime<Utc>,
+}
+
+/// An alert that is firing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActiveAlert {
+    pub rule: String,
+    pub subject: String,
+    /// Summary at the latest evaluation
+    pub summary: String,
+    pub since: DateTime<Utc>,
+}
+
+/// Evaluates alert rules against successive observations.
+#[derive(Debug, Default)]
+pub struct AlertEngine {
+    inner: Mutex<EngineState>,
+}
+
+#[derive(Debug, Default)]
+struct EngineState {
+    /// Earlier observations, oldest first, kept for the longest window
+    history: VecDeque<Observation>,
+    /// When each backend was first seen unhealthy in its current outage
+    unhealthy_since: HashMap<String, Instant>,
+    /// Firing alerts by rule and subject
+    firing: BTreeMap<(String, String), ActiveAlert>,
+}
+
+impl AlertEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Alerts firing as of the latest evaluation.
+    pub fn active(&self) -> Vec<ActiveAlert> {
+        self.inner.lock().firing.values().cloned().collect()
+    }
+
+    /// Evaluate `config`'s rules with a new observation, returning the
+    /// alerts that started or stopped firing since the previous one.
+    pub fn evaluate(&self, config: &AlertsConfig, observation: Observation) -> Vec<AlertEvent> {
+        let mut state = self.inner.lock();
+        let now = Utc::now();
+
+        let at = observation.at;
+        state
+            .unhealthy_since
+            .retain(|id, _| observation.backends.get(id) == Some(&true));
+        for (id, _) in observation.backends.iter().filter(|(_, unhealthy)| **unhealthy) {
+            state.unhealthy_since.entry(id.clone()).or_insert(at);
+        }
+
+        let mut firing = BTreeMap::new();
+        for rule in &config.rules {
+            for (subject, summary) in state.check(rule, &observation) {
+                firing.insert((rule.name.clone(), subject), summary);
+            }
+        }
+
+        let mut events = Vec::new();
+        let resolved: Vec<_> =
+            state.firing.keys().filter(|key| !firing.contains_key(*key)).cloned().collect();
+        for key in resolved {
+            if let Some(alert) = state.firing.remove(&key) {
+                events.push(AlertEvent {
+                    rule: alert.rule,
+                    subject: alert.subject,
+                    state: AlertState::Resolved,
+                    summary: alert.summary,
+                    at: now,
+                });
+            }
+        }
+        for ((rule, subject), summary) in firing {
+            match state.firing.get_mut(&(rule.clone(), subject.clone())) {
+                Some(alert) => alert.summary = summary,
+                None => {
+                    events.push(AlertEvent {
+                        rule: rule.clone(),
+                        subject: subject.clone(),
+                        state: AlertState::Firing,
+                        summary: summary.clone(),
+                        at: now,
+                    });
+                    let alert = ActiveAlert {
+                        rule: rule.clone(),
+                        subject: subject.clone(),
+                        summary,
+                        since: now,
+                    };
+                    state.firing.insert((rule, subject), alert);
+                },
+            }
+        }
+
+        // Keep what the longest window needs: the latest observation at
+        // least that old, and every one since
+        let longest = config.rules.iter().filter_map(|rule| rule.condition.window()).max();
+        state.history.push_back(observation);
+        match longest {
+            Some(window) => {
+                while state
+                    .history
+                    .get(1)
+                    .is_some_and(|next| at.saturating_duration_since(next.at) >= window)
+                {
+                    state.history.pop_front();
+                }
+            },
+            None => state.history.clear(),
+        }
+
+        events
+    }
+}
+
+impl EngineState {
+    /// Subjects `rule` fires for, with a summary of each.
+    fn check(&self, rule: &AlertRuleConfig, observation: &Observation) -> Vec<(String, String)> {
+        let watched =
+            |servers: &[String], id: &str| servers.is_empty() || servers.iter().any(|s| s == id);
+
+        match &rule.condition {
+            AlertCondition::BackendUnhealthy {
+                for_seconds,
+                servers,
+            } => observation
+                .backends
+                .keys()
+                .filter(|id| watched(servers, id))
+                .filter_map(|id| {
+                    let down =
+                        observation.at.saturating_duration_since(*self.unhealthy_since.get(id)?);
+                    (down >= Duration::from_secs(*for_seconds)).then(|| {
+                        (
+                            id.clone(),
+                            format!("{} has been unhealthy for {}s", id, down.as_secs()),
+                        )
+                    })
+                })
+                .collect(),
+            AlertCondition::ErrorRate {
+                above_percent,
+                window_seconds,
+                min_requests,
+                servers,
+            } => {
+                let baseline = self.baseline(observation.at, Duration::from_secs(*window_seconds));
+                observation
+                    .backends
+                    .keys()
+                    .filter(|id| watched(servers, id))
+                    .filter_map(|id| {
+                        let now = observation.requests.get(id).copied().unwrap_or_default();
+                        let before = baseline
+                            .and_then(|baseline| baseline.requests.get(id).copied())
+                            .unwrap_or_default();
+                        let total = now.total.saturating_sub(before.total);
+                        let failed = now.failed.saturating_sub(before.failed);
+                        let rate = percent(failed, total)?;
+                        (total >= *min_requests && rate > *above_percent).then(|| {
+                            let summary = format!(
+                                "{:.1}% of {} requests to {} failed in the last {}s",
+                                rate, total, id, window_seconds
+                            );
+                            (id.clone(), summary)
+                        })
+                    })
+                    .collect()
+            },
+            AlertCondition::CacheHitRate {
+                below_percent,
+                window_seconds,
+                min_lookups,
+            } => {
+                let baseline = self.baseline(observation.at, Duration::from_secs(*window_seconds));
+                let (hits_before, misses_before) = baseline.map_or((0, 0), |baseline| {
+                    (baseline.cache_hits, baseline.cache_misses)
+                });
+                let hits = observation.cache_hits.saturating_sub(hits_before);
+                let lookups = hits + observation.cache_misses.saturating_sub(misses_before);
+                match percent(hits, lookups) {
+                    Some(rate) if lookups >= *min_lookups && rate < *below_percent => {
+                        let summary = format!(
+                            "Cache hit rate was {:.1}% over {} lookups in the last {}s",
+                            rate, lookups, window_seconds
+                        );
+                        vec![(CACHE_SUBJECT.to_string(), summary)]
+                    },
+                    _ => Vec::new(),
+                }
+            },
+        }
+    }
+
+    /// The observation rates are measured from: the latest one at least
+    /// `window` before `at`, else the oldest kept, else `None` to count from
+    /// startup.
+    fn baseline(&self, at: Instant, window: Duration) -> Option<&Observation> {
+        self.history
+            .iter()
+            .rev()
+            .find(|earlier| at.saturating_duration_since(earlier.at) >= window)
+            .or_else(|| self.history.front())
+    }
+}
+
+impl AlertCondition {
+    /// Window the condition looks back over, if any.
+    fn window(&self) -> Option<Duration> {
+        match self {
+            Self::BackendUnhealthy { .. } => None,
+            Self::ErrorRate { window_seconds, .. } | Self::CacheHitRate { window_seconds, .. } => {
+                Some(Duration::from_secs(*window_seconds))
+            },
+        }
+    }
+}
+
+/// `part` as a percentage of `total`, or `None` when `total` is zero.
+fn percent(part: u64, total: u64) -> Option<f64> {
+    (total > 0).then(|| part as f64 * 100.0 / total as f64)
+}
+
+/// Requests per backend since startup, from `only1mcp_mcp_requests_total`.
+pub fn request_counts() -> HashMap<String, RequestCounts> {
+    let mut counts: HashMap<String, RequestCounts> = HashMap::new();
+    for family in super::MCP_REQUESTS_TOTAL.collect() {
+        for metric in family.get_metric() {
+            let label = |name: &str| {
+                metric
+                    .get_label()
+                    .iter()
+                    .find(|pair| pair.get_name() == name)
+                    .map(|pair| pair.get_value().to_string())
+                    .unwrap_or_default()
+            };
+            let value = metric.get_counter().get_value() as u64;
+            let entry = counts.entry(label("server_id")).or_default();
+            entry.total += value;
+            if label("status") != "success" {
+                entry.failed += value;
+            }
+        }
+    }
+    counts
+}
+
+/// Log `event` and send it to its rule's notifiers.
+pub async fn notify(client: &reqwest::Client, config: &AlertsConfig, event: &AlertEvent) {
+    match event.state {
+        AlertState::Firing => warn!(
+            "Alert {} firing for {}: {}",
+            event.rule, event.subject, event.summary
+        ),
+        AlertState::Resolved => info!("Alert {} resolved for {}", event.rule, event.subject),
+    }
+
+    let Some(rule) = config.rules.iter().find(|rule| rule.name == event.rule) else {
+        return;
+    };
+    let notifiers = config
+        .notifiers
+        .iter()
+        .filter(|notifier| rule.notify.is_empty() || rule.notify.contains(&notifier.name));
+    for notifier in notifiers {
+        let body = match notifier.kind {
+            AlertNotifierKind::Slack => serde_json::json!({ "text": slack_text(event) }),
+            AlertNotifierKind::Webhook => serde_json::json!(event),
+        };
+        let mut request = client.post(&notifier.url).timeout(NOTIFY_TIMEOUT).json(&body);
+        for (name, value) in &notifier.headers {
+            request = request.header(name, value);
+        }
+        let sent = request.send().await.and_then(|response| response.error_for_status());
+        if let Err(e) = sent {
+            warn!(
+                "Failed to send alert {} to {}: {}",
+                event.rule, notifier.name, e
+            );
+        }
+    }
+}
+
+/// Slack message for `event`.
+fn slack_text(event: &AlertEvent) -> String {
+    match event.state {
+        AlertState::Firing => format!(
+            ":rotating_light: *{}* ({}): {}",
+            event.rule, event.subject, event.summary
+        ),
+        AlertState::Resolved => {
+            format!(
+                ":white_check_mark: *{}* ({}) resolved: {}",
+                event.rule, event.subject, event.summary
+            )
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(yaml: &str) -> AlertsConfig {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    fn observation(
+        at: Instant,
+        unhealthy: &[&str],
+        requests: (u64, u64),
+        cache: (u64, u64),
+    ) -> Observation {
+        Observation {
+            at,
+            backends: ["a", "b"]
+                .iter()
+                .map(|id| (id.to_string(), unhealthy.contains(id)))
+                .collect(),
+            requests: HashMap::from([(
+                "a".to_string(),
+                RequestCounts {
+                    total: requests.0,
+                    failed: requests.1,
+                },
+            )]),
+            cache_hits: cache.0,
+            cache_misses: cache.1,
+        }
+    }
+
+    #[test]
+    fn test_backend_unhealthy_fires_after_duration_and_resolves() {
+        let config = config(
+            "rules:\n  - {name: down, type: backend_unhealthy, for_seconds: 60, servers: [b]}\n",
+        );
+        let engine = AlertEngine::new();
+        let start = Instant::now();
+
+        assert!(engine
+            .evaluate(&config, observation(start, &["a", "b"], (0, 0), (0, 0)))
+            .is_empty());
+        let events = engine.evaluate(
+            &config,
+            observation(start + Duration::from_secs(90), &["a", "b"], (0, 0), (0, 0)),
+        );
+        assert_eq!(events.len(), 1, "{:?}", events);
+        assert_eq!(
+            (events[0].subject.as_str(), events[0].state),
+            ("b", AlertState::Firing)
+        );
+        assert_eq!(engine.active().len(), 1);
+
+        // Still firing: nothing new
+        assert!(engine
+            .evaluate(
+                &config,
+                observation(start + Duration::from_secs(120), &["b"], (0, 0), (0, 0))
+            )
+            .is_empty());
+
+        let events = engine.evaluate(
+            &config,
+            observation(start + Duration::from_secs(150), &[], (0, 0), (0, 0)),
+        );
+        assert_eq!(events[0].state, AlertState::Resolved);
+        assert!(engine.active().is_empty());
+    }
+
+    #[test]
+    fn test_rates_use_window() {
+        let config = config(
+            "rules:\n  - {name: errors, type: error_rate, above_percent: 20, window_seconds: 60, min_requests: 10}\n  - {name: cold, type: cache_hit_rate, below_percent: 50, window_seconds: 60, min_lookups: 10}\n",
+        );
+        let engine = AlertEngine::new();
+        let start = Instant::now();
+
+        // Too few requests and lookups to judge
+        assert!(engine.evaluate(&config, observation(start, &[], (5, 5), (0, 5))).is_empty());
+
+        // 10 of 20 requests failed; 2 of 20 lookups hit
+        let events = engine.evaluate(
+            &config,
+            observation(start + Duration::from_secs(30), &[], (25, 15), (2, 23)),
+        );
+        let subjects: Vec<_> = events
+            .iter()
+            .map(|event| (event.rule.as_str(), event.subject.as_str()))
+            .collect();
+        assert_eq!(subjects, [("cold", "cache"), ("errors", "a")]);
+
+        // The failures fall out of the window; every request since succeeded
+        let events = engine.evaluate(
+            &config,
+            observation(start + Duration::from_secs(100), &[], (45, 15), (22, 23)),
+        );
+        assert_eq!(events.len(), 2, "{:?}", events);
+        assert!(events.iter().all(|event| event.state == AlertState::Resolved));
+    }
+}
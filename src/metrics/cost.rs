@@ -0,0 +1,148 @@
+//! Cost accounting for tool calls (`servers[].cost`).
+//!
+//! Each tools/call is charged with the cost model of the backend that served
+//! it (see [`CostConfig`]). The totals feed `only1mcp_api_cost_dollars_total`
+//! and the report served at `GET /api/v1/admin/costs`.
+
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::config::CostConfig;
+
+/// Cumulative calls, tokens and cost of one tool.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolCost {
+    pub tool: String,
+    pub calls: u64,
+    /// Estimated tokens of the calls' arguments and results
+    pub tokens: u64,
+    /// Dollars
+    pub cost: f64,
+}
+
+/// Cumulative calls, tokens and cost of one backend and its tools.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ServerCost {
+    pub server_id: String,
+    pub calls: u64,
+    pub tokens: u64,
+    pub cost: f64,
+    pub tools: Vec<ToolCost>,
+}
+
+/// Costs since startup, most expensive servers first.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CostReport {
+    pub total_calls: u64,
+    pub total_cost: f64,
+    pub servers: Vec<ServerCost>,
+}
+
+/// Running cost totals per backend and tool.
+#[derive(Default)]
+pub struct CostTracker {
+    totals: DashMap<(String, String), ToolCost>,
+}
+
+impl CostTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Charge one call of `tool` on `server_id` that moved `tokens`
+    /// estimated tokens. Returns the cost of the call, zero for backends
+    /// without a cost model.
+    pub fn record(
+        &self,
+        server_id: &str,
+        tool: &str,
+        model: Option<&CostConfig>,
+        tokens: u64,
+    ) -> f64 {
+        let cost = model.map(|m| m.rates_for(tool).cost(tokens)).unwrap_or(0.0);
+
+        let mut entry = self
+            .totals
+            .entry((server_id.to_string(), tool.to_string()))
+            .or_insert_with(|| ToolCost {
+                tool: tool.to_string(),
+                ..Default::default()
+            });
+        entry.calls += 1;
+        entry.tokens += tokens;
+        entry.cost += cost;
+        drop(entry);
+
+        super::record_api_cost(server_id, tool, cost);
+        cost
+    }
+
+    /// Totals grouped by backend.
+    pub fn report(&self) -> CostReport {
+        let mut servers: Vec<ServerCost> = Vec::new();
+        for item in self.totals.iter() {
+            let (server_id, _) = item.key();
+            let tool = item.value().clone();
+            let index = match servers.iter().position(|s| &s.server_id == server_id) {
+                Some(index) => index,
+                None => {
+                    servers.push(ServerCost {
+                        server_id: server_id.clone(),
+                        ..Default::default()
+                    });
+                    servers.len() - 1
+                },
+            };
+            let server = &mut servers[index];
+            server.calls += tool.calls;
+            server.tokens += tool.tokens;
+            server.cost += tool.cost;
+            server.tools.push(tool);
+        }
+
+        let by_cost = |a: f64, b: f64| b.total_cmp(&a);
+        for server in &mut servers {
+            server.tools.sort_by(|a, b| by_cost(a.cost, b.cost).then(a.tool.cmp(&b.tool)));
+        }
+        servers.sort_by(|a, b| by_cost(a.cost, b.cost).then(a.server_id.cmp(&b.server_id)));
+
+        CostReport {
+            total_calls: servers.iter().map(|s| s.calls).sum(),
+            total_cost: servers.iter().map(|s| s.cost).sum(),
+            servers,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_report() {
+        let model: CostConfig = serde_yaml::from_str(
+            "per_call: 0.01\nper_1k_tokens: 0.5\ntools: {render: {per_call: 0.25}}",
+        )
+        .unwrap();
+        let tracker = CostTracker::new();
+
+        let cost = tracker.record("github", "search", Some(&model), 2_000);
+        assert!((cost - 1.01).abs() < 1e-9, "{}", cost);
+        tracker.record("github", "search", Some(&model), 0);
+        assert_eq!(
+            tracker.record("images", "render", Some(&model), 4_000),
+            0.25
+        );
+        assert_eq!(tracker.record("local", "echo", None, 100), 0.0);
+
+        let report = tracker.report();
+        assert_eq!(report.total_calls, 4);
+        assert!((report.total_cost - 1.27).abs() < 1e-9);
+
+        let ids: Vec<_> = report.servers.iter().map(|s| s.server_id.as_str()).collect();
+        assert_eq!(ids, ["github", "images", "local"]);
+        let github = &report.servers[0];
+        assert_eq!((github.calls, github.tokens), (2, 2_000));
+        assert_eq!(github.tools[0].tool, "search");
+    }
+}
@@ -3,12 +3,20 @@
 use axum::{extract::State, http::StatusCode, response::IntoResponse};
 use lazy_static::lazy_static;
 use prometheus::{
-    histogram_opts, opts, register_counter_vec, register_gauge_vec, register_histogram_vec,
-    CounterVec, Encoder, GaugeVec, HistogramVec, Registry, TextEncoder,
+    histogram_opts, opts, register_counter_vec, register_gauge, register_gauge_vec,
+    register_histogram_vec, CounterVec, Encoder, Gauge, GaugeVec, HistogramVec, Registry,
+    TextEncoder,
 };
 use std::sync::Arc;
 use std::time::Duration;
 
+pub mod alerts;
+pub mod cost;
+pub mod system;
+pub mod tokens;
+
+pub use system::SystemMetricsCollector;
+
 lazy_static! {
     // Request metrics
     pub static ref MCP_REQUESTS_TOTAL: CounterVec = register_counter_vec!(
@@ -32,9 +40,9 @@ lazy_static! {
     pub static ref CONTEXT_TOKENS_SAVED: CounterVec = register_counter_vec!(
         opts!(
             "only1mcp_context_tokens_saved_total",
-            "Total tokens saved through optimization"
+            "Estimated tokens saved through optimization, by server and optimization"
         ),
-        &["optimization_type"]  // cache_hit, deduplication, compression
+        &["server_id", "optimization_type"]  // cache_hit, deduplication, batching, truncation, slim_catalog
     ).unwrap();
 
     pub static ref CONTEXT_CACHE_HIT_RATIO: GaugeVec = register_gauge_vec!(
@@ -84,9 +92,9 @@ lazy_static! {
     pub static ref API_COST_DOLLARS: CounterVec = register_counter_vec!(
         opts!(
             "only1mcp_api_cost_dollars_total",
-            "Cumulative API costs in dollars"
+            "Cumulative cost of tool calls in dollars, from the configured cost models"
         ),
-        &["provider", "model", "operation"]
+        &["server_id", "tool"]
     ).unwrap();
 
     // System resource metrics
@@ -95,17 +103,51 @@ lazy_static! {
             "only1mcp_memory_usage_bytes",
             "Memory usage in bytes"
         ),
-        &["type"]  // heap, stack, cache
+        &["type"]  // rss, cache
     ).unwrap();
 
     pub static ref CPU_USAGE_PERCENT: GaugeVec = register_gauge_vec!(
         opts!(
             "only1mcp_cpu_usage_percent",
-            "CPU usage percentage (0-100)"
+            "Host CPU utilization percentage (0-100) per core, core=\"all\" for every core"
         ),
         &["core"]
     ).unwrap();
 
+    pub static ref PROCESS_CPU_USAGE_PERCENT: Gauge = register_gauge!(
+        "only1mcp_process_cpu_usage_percent",
+        "CPU used by the proxy process, in percent of one core"
+    ).unwrap();
+
+    pub static ref OPEN_FILE_DESCRIPTORS: Gauge = register_gauge!(
+        "only1mcp_open_file_descriptors",
+        "File descriptors open in the proxy process"
+    ).unwrap();
+
+    pub static ref STDIO_PROCESS_MEMORY_BYTES: GaugeVec = register_gauge_vec!(
+        opts!(
+            "only1mcp_stdio_process_memory_bytes",
+            "Resident memory of STDIO backend processes"
+        ),
+        &["server_id", "slot"]
+    ).unwrap();
+
+    pub static ref STDIO_PROCESS_CPU_PERCENT: GaugeVec = register_gauge_vec!(
+        opts!(
+            "only1mcp_stdio_process_cpu_usage_percent",
+            "CPU used by STDIO backend processes, in percent of one core"
+        ),
+        &["server_id", "slot"]
+    ).unwrap();
+
+    pub static ref STDIO_PROCESS_OPEN_FDS: GaugeVec = register_gauge_vec!(
+        opts!(
+            "only1mcp_stdio_process_open_file_descriptors",
+            "File descriptors open in STDIO backend processes"
+        ),
+        &["server_id", "slot"]
+    ).unwrap();
+
     // Circuit breaker metrics
     pub static ref CIRCUIT_BREAKER_STATE: GaugeVec = register_gauge_vec!(
         opts!(
@@ -198,6 +240,11 @@ lazy_static! {
         "Total number of cache evictions"
     ).unwrap();
 
+    pub static ref CACHE_STALE_SERVES_TOTAL: prometheus::IntCounter = prometheus::register_int_counter!(
+        "only1mcp_cache_stale_serves_total",
+        "Total number of expired cache entries served while being refreshed"
+    ).unwrap();
+
     // Batching metrics (Feature 4)
     pub static ref BATCH_REQUESTS_TOTAL: prometheus::IntCounter = prometheus::register_int_counter!(
         "only1mcp_batch_requests_total",
@@ -220,11 +267,214 @@ lazy_static! {
         )
     ).unwrap();
 
+    pub static ref BATCH_WINDOW_SECONDS: prometheus::GaugeVec = prometheus::register_gauge_vec!(
+        "only1mcp_batch_window_seconds",
+        "Batching window currently used under batching.adaptive",
+        &["server_id", "method"]
+    ).unwrap();
+
     pub static ref BATCHING_EFFICIENCY_RATIO: prometheus::Gauge = prometheus::register_gauge!(
         "only1mcp_batching_efficiency_ratio",
         "Batching efficiency ratio: backend_calls / total_requests (lower is better)"
     ).unwrap();
 
+    // Request coalescing metrics
+    pub static ref COALESCED_REQUESTS_TOTAL: CounterVec = register_counter_vec!(
+        opts!(
+            "only1mcp_coalesced_requests_total",
+            "Requests served by awaiting an identical in-flight backend call"
+        ),
+        &["method"]
+    ).unwrap();
+
+    // STDIO process metrics
+    pub static ref STDIO_PROCESSES_REAPED_TOTAL: CounterVec = register_counter_vec!(
+        opts!(
+            "only1mcp_stdio_processes_reaped_total",
+            "STDIO processes stopped after exceeding the idle timeout"
+        ),
+        &["server_id"]
+    ).unwrap();
+
+    pub static ref STDIO_PROCESS_RESTARTS_TOTAL: CounterVec = register_counter_vec!(
+        opts!(
+            "only1mcp_stdio_process_restarts_total",
+            "STDIO processes restarted by the supervisor after exiting"
+        ),
+        &["server_id"]
+    ).unwrap();
+
+    pub static ref STDIO_RESOURCE_KILLS_TOTAL: CounterVec = register_counter_vec!(
+        opts!(
+            "only1mcp_stdio_resource_kills_total",
+            "STDIO processes killed for staying over their resource limits"
+        ),
+        &["server_id", "resource"]
+    ).unwrap();
+
+    pub static ref STDIO_CRASH_LOOPS_TOTAL: CounterVec = register_counter_vec!(
+        opts!(
+            "only1mcp_stdio_crash_loops_total",
+            "STDIO servers marked unhealthy for exceeding their restart limit"
+        ),
+        &["server_id"]
+    ).unwrap();
+
+    // Backend session metrics
+    pub static ref BACKEND_SESSION_RECOVERIES_TOTAL: CounterVec = register_counter_vec!(
+        opts!(
+            "only1mcp_backend_session_recoveries_total",
+            "Requests replayed successfully after re-establishing a broken backend session"
+        ),
+        &["transport", "endpoint"]
+    ).unwrap();
+
+    // Live backend load, used by least-connections routing
+    pub static ref BACKEND_IN_FLIGHT_REQUESTS: prometheus::IntGaugeVec = prometheus::register_int_gauge_vec!(
+        "only1mcp_backend_in_flight_requests",
+        "Requests currently outstanding per backend",
+        &["server_id"]
+    ).unwrap();
+
+    // Admission control metrics (`proxy.admission`)
+    pub static ref ADMISSION_QUEUE_DEPTH: prometheus::IntGaugeVec = prometheus::register_int_gauge_vec!(
+        "only1mcp_admission_queue_depth",
+        "Requests waiting for a concurrency slot",
+        &["scope"]  // global, or a server ID
+    ).unwrap();
+    pub static ref SHED_REQUESTS_TOTAL: CounterVec = register_counter_vec!(
+        opts!(
+            "only1mcp_shed_requests_total",
+            "Requests rejected as busy under proxy.admission"
+        ),
+        &["scope", "reason"]  // queue_full, queue_timeout
+    ).unwrap();
+
+    // Sticky session metrics
+    pub static ref STICKY_SESSION_LOOKUPS_TOTAL: CounterVec = register_counter_vec!(
+        opts!(
+            "only1mcp_sticky_session_lookups_total",
+            "Sticky session lookups by outcome"
+        ),
+        &["outcome"]  // hit, expired, rebalanced
+    ).unwrap();
+
+    pub static ref STICKY_SESSIONS_ACTIVE: prometheus::IntGauge = prometheus::register_int_gauge!(
+        "only1mcp_sticky_sessions_active",
+        "Clients currently pinned to a backend"
+    ).unwrap();
+
+    // Cluster metrics
+    pub static ref CLUSTER_PEERS_UP: prometheus::IntGauge = prometheus::register_int_gauge!(
+        "only1mcp_cluster_peers_up",
+        "Cluster peers heard from within cluster.peer_timeout_ms"
+    ).unwrap();
+
+    // Failover group metrics
+    pub static ref FAILOVER_EVENTS_TOTAL: CounterVec = register_counter_vec!(
+        opts!(
+            "only1mcp_failover_events_total",
+            "Traffic shifts within failover groups"
+        ),
+        &["group", "kind"]  // failover, failback
+    ).unwrap();
+
+    pub static ref FAILOVER_ON_STANDBY: prometheus::IntGaugeVec = prometheus::register_int_gauge_vec!(
+        "only1mcp_failover_on_standby",
+        "Whether a failover group routes to a standby (1) or its primary (0)",
+        &["group"]
+    ).unwrap();
+
+    // Traffic split metrics
+    pub static ref TRAFFIC_SPLIT_REQUESTS_TOTAL: CounterVec = register_counter_vec!(
+        opts!(
+            "only1mcp_traffic_split_requests_total",
+            "Requests sent to each variant of a traffic split"
+        ),
+        &["service", "server_id", "outcome"]  // success, error
+    ).unwrap();
+
+    pub static ref TRAFFIC_SPLIT_REQUEST_DURATION_SECONDS: HistogramVec = register_histogram_vec!(
+        histogram_opts!(
+            "only1mcp_traffic_split_request_duration_seconds",
+            "Backend latency of each variant of a traffic split",
+            vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
+        ),
+        &["service", "server_id"]
+    ).unwrap();
+
+    // Request mirroring metrics
+    pub static ref MIRRORED_REQUESTS_TOTAL: CounterVec = register_counter_vec!(
+        opts!(
+            "only1mcp_mirrored_requests_total",
+            "tools/call requests copied to shadow backends"
+        ),
+        &["server_id", "mirror", "outcome"]  // success, error
+    ).unwrap();
+
+    // Fault injection metrics
+    pub static ref INJECTED_FAULTS_TOTAL: CounterVec = register_counter_vec!(
+        opts!(
+            "only1mcp_injected_faults_total",
+            "Faults injected into backend requests and health checks"
+        ),
+        &["server_id", "fault"]  // latency, error, drop
+    ).unwrap();
+
+    // Result truncation metrics
+    pub static ref TRUNCATED_RESULTS_TOTAL: CounterVec = register_counter_vec!(
+        opts!(
+            "only1mcp_truncated_results_total",
+            "tools/call results cut down to the configured size limit"
+        ),
+        &["server_id", "tool"]
+    ).unwrap();
+
+    // Argument validation metrics
+    pub static ref INVALID_ARGUMENTS_TOTAL: CounterVec = register_counter_vec!(
+        opts!(
+            "only1mcp_invalid_arguments_total",
+            "tools/call requests rejected because their arguments did not match the tool's inputSchema"
+        ),
+        &["server_id", "tool"]
+    ).unwrap();
+
+    // Response sanitization metrics
+    pub static ref SANITIZED_RESULTS_TOTAL: CounterVec = register_counter_vec!(
+        opts!(
+            "only1mcp_sanitized_results_total",
+            "tools/call results changed by sanitization, by kind of fix"
+        ),
+        &["server_id", "fix"]
+    ).unwrap();
+
+    // Redaction metrics
+    pub static ref REDACTIONS_TOTAL: CounterVec = register_counter_vec!(
+        opts!(
+            "only1mcp_redactions_total",
+            "Matches of redaction patterns replaced in tools/call arguments and results"
+        ),
+        &["pattern", "target"]
+    ).unwrap();
+
+    // TLS metrics
+    pub static ref TLS_CERTIFICATE_RELOADS_TOTAL: CounterVec = register_counter_vec!(
+        opts!(
+            "only1mcp_tls_certificate_reloads_total",
+            "TLS certificate reloads by listener and result"
+        ),
+        &["listener", "result"]  // success, failure
+    ).unwrap();
+
+    // Streaming passthrough metrics
+    pub static ref STREAMED_RESPONSE_BYTES_TOTAL: CounterVec = register_counter_vec!(
+        opts!(
+            "only1mcp_streamed_response_bytes_total",
+            "Tool result bytes streamed to clients without buffering"
+        ),
+        &["server_id"]
+    ).unwrap();
+
     // Registry for all metrics
     pub static ref REGISTRY: Registry = {
         let registry = Registry::new();
@@ -239,6 +489,11 @@ lazy_static! {
         registry.register(Box::new(API_COST_DOLLARS.clone())).unwrap();
         registry.register(Box::new(MEMORY_USAGE_BYTES.clone())).unwrap();
         registry.register(Box::new(CPU_USAGE_PERCENT.clone())).unwrap();
+        registry.register(Box::new(PROCESS_CPU_USAGE_PERCENT.clone())).unwrap();
+        registry.register(Box::new(OPEN_FILE_DESCRIPTORS.clone())).unwrap();
+        registry.register(Box::new(STDIO_PROCESS_MEMORY_BYTES.clone())).unwrap();
+        registry.register(Box::new(STDIO_PROCESS_CPU_PERCENT.clone())).unwrap();
+        registry.register(Box::new(STDIO_PROCESS_OPEN_FDS.clone())).unwrap();
         registry.register(Box::new(CIRCUIT_BREAKER_STATE.clone())).unwrap();
         registry.register(Box::new(CIRCUIT_BREAKER_FAILURES.clone())).unwrap();
         registry.register(Box::new(RATE_LIMIT_EXCEEDED.clone())).unwrap();
@@ -250,14 +505,92 @@ lazy_static! {
         registry.register(Box::new(CACHE_MISSES_TOTAL.clone())).unwrap();
         registry.register(Box::new(CACHE_SIZE_ENTRIES.clone())).unwrap();
         registry.register(Box::new(CACHE_EVICTIONS_TOTAL.clone())).unwrap();
+        registry.register(Box::new(CACHE_STALE_SERVES_TOTAL.clone())).unwrap();
         registry.register(Box::new(BATCH_REQUESTS_TOTAL.clone())).unwrap();
         registry.register(Box::new(BATCH_SIZE.clone())).unwrap();
         registry.register(Box::new(BATCH_WAIT_TIME_SECONDS.clone())).unwrap();
+        registry.register(Box::new(BATCH_WINDOW_SECONDS.clone())).unwrap();
         registry.register(Box::new(BATCHING_EFFICIENCY_RATIO.clone())).unwrap();
+        registry.register(Box::new(COALESCED_REQUESTS_TOTAL.clone())).unwrap();
+        registry.register(Box::new(STDIO_PROCESSES_REAPED_TOTAL.clone())).unwrap();
+        registry.register(Box::new(STDIO_PROCESS_RESTARTS_TOTAL.clone())).unwrap();
+        registry.register(Box::new(STDIO_CRASH_LOOPS_TOTAL.clone())).unwrap();
+        registry.register(Box::new(STDIO_RESOURCE_KILLS_TOTAL.clone())).unwrap();
+        registry.register(Box::new(BACKEND_SESSION_RECOVERIES_TOTAL.clone())).unwrap();
+        registry.register(Box::new(BACKEND_IN_FLIGHT_REQUESTS.clone())).unwrap();
+        registry.register(Box::new(ADMISSION_QUEUE_DEPTH.clone())).unwrap();
+        registry.register(Box::new(SHED_REQUESTS_TOTAL.clone())).unwrap();
+        registry.register(Box::new(STICKY_SESSION_LOOKUPS_TOTAL.clone())).unwrap();
+        registry.register(Box::new(STICKY_SESSIONS_ACTIVE.clone())).unwrap();
+        registry.register(Box::new(CLUSTER_PEERS_UP.clone())).unwrap();
+        registry.register(Box::new(FAILOVER_EVENTS_TOTAL.clone())).unwrap();
+        registry.register(Box::new(FAILOVER_ON_STANDBY.clone())).unwrap();
+        registry.register(Box::new(TRAFFIC_SPLIT_REQUESTS_TOTAL.clone())).unwrap();
+        registry.register(Box::new(TRAFFIC_SPLIT_REQUEST_DURATION_SECONDS.clone())).unwrap();
+        registry.register(Box::new(MIRRORED_REQUESTS_TOTAL.clone())).unwrap();
+        registry.register(Box::new(INJECTED_FAULTS_TOTAL.clone())).unwrap();
+        registry.register(Box::new(TRUNCATED_RESULTS_TOTAL.clone())).unwrap();
+        registry.register(Box::new(INVALID_ARGUMENTS_TOTAL.clone())).unwrap();
+        registry.register(Box::new(SANITIZED_RESULTS_TOTAL.clone())).unwrap();
+        registry.register(Box::new(REDACTIONS_TOTAL.clone())).unwrap();
+        registry.register(Box::new(TLS_CERTIFICATE_RELOADS_TOTAL.clone())).unwrap();
+        registry.register(Box::new(STREAMED_RESPONSE_BYTES_TOTAL.clone())).unwrap();
         registry
     };
 }
 
+/// Methods recorded under their own name; anything else a client sends is
+/// recorded as `other` to keep the label set bounded.
+const KNOWN_METHODS: [&str; 22] = [
+    "initialize",
+    "ping",
+    "tools/list",
+    "tools/describe",
+    "tools/call",
+    "resources/list",
+    "resources/templates/list",
+    "resources/read",
+    "resources/subscribe",
+    "resources/unsubscribe",
+    "prompts/list",
+    "prompts/get",
+    "sampling/createMessage",
+    "completion/complete",
+    "logging/setLevel",
+    "notifications/initialized",
+    "notifications/cancelled",
+    "notifications/progress",
+    "notifications/tools/list_changed",
+    "notifications/resources/list_changed",
+    "notifications/resources/updated",
+    "notifications/prompts/list_changed",
+];
+
+/// `method` label for a JSON-RPC method name.
+pub fn method_label(method: &str) -> &str {
+    if KNOWN_METHODS.contains(&method) {
+        method
+    } else {
+        "other"
+    }
+}
+
+/// `status` label of a JSON-RPC reply: `error` if it carries an error.
+pub fn response_status(response: &serde_json::Value) -> &'static str {
+    match response.get("error") {
+        Some(error) if !error.is_null() => "error",
+        _ => "success",
+    }
+}
+
+/// Record a request one backend answered (or failed to): counted under the
+/// backend in the MCP request metrics, with its latency per method.
+pub fn record_backend_request(server_id: &str, method: &str, status: &str, duration: Duration) {
+    let method = method_label(method);
+    record_mcp_request(server_id, method, status, duration);
+    record_backend_latency(server_id, method, duration);
+}
+
 /// Record metrics for an MCP request
 pub fn record_mcp_request(server_id: &str, method: &str, status: &str, duration: Duration) {
     MCP_REQUESTS_TOTAL.with_label_values(&[server_id, method, status]).inc();
@@ -268,12 +601,21 @@ pub fn record_mcp_request(server_id: &str, method: &str, status: &str, duration:
 }
 
 /// Record context optimization metrics
-pub fn record_context_optimization(optimization_type: &str, tokens_saved: u64) {
+pub fn record_context_optimization(server_id: &str, optimization_type: &str, tokens_saved: u64) {
     CONTEXT_TOKENS_SAVED
-        .with_label_values(&[optimization_type])
+        .with_label_values(&[server_id, optimization_type])
         .inc_by(tokens_saved as f64);
 }
 
+/// Record the estimated tokens of a serialized response that a client got
+/// without the backend producing it again.
+pub fn record_tokens_saved(server_id: &str, optimization_type: &str, payload: &[u8]) {
+    let tokens = tokens::estimate_payload_tokens(payload);
+    if tokens > 0 {
+        record_context_optimization(server_id, optimization_type, tokens);
+    }
+}
+
 /// Update cache hit ratio
 pub fn update_cache_hit_ratio(cache_type: &str, ratio: f64) {
     CONTEXT_CACHE_HIT_RATIO.with_label_values(&[cache_type]).set(ratio);
@@ -304,9 +646,9 @@ pub fn update_connection_pool(server_id: &str, active: usize, idle: usize, pendi
         .set(pending as f64);
 }
 
-/// Record API cost
-pub fn record_api_cost(provider: &str, model: &str, operation: &str, cost: f64) {
-    API_COST_DOLLARS.with_label_values(&[provider, model, operation]).inc_by(cost);
+/// Record the cost of a tool call
+pub fn record_api_cost(server_id: &str, tool: &str, cost: f64) {
+    API_COST_DOLLARS.with_label_values(&[server_id, tool]).inc_by(cost);
 }
 
 /// Update circuit breaker state
@@ -403,38 +745,6 @@ pub async fn metrics_handler(
     }
 }
 
-/// System metrics collector
-pub struct SystemMetricsCollector;
-
-impl SystemMetricsCollector {
-    /// Start collecting system metrics
-    pub fn start(interval: Duration) {
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(interval);
-
-            loop {
-                interval.tick().await;
-                Self::collect_metrics();
-            }
-        });
-    }
-
-    /// Collect current system metrics
-    fn collect_metrics() {
-        // Memory metrics
-        if let Ok(mem_info) = sys_info::mem_info() {
-            MEMORY_USAGE_BYTES
-                .with_label_values(&["heap"])
-                .set((mem_info.total - mem_info.avail) as f64 * 1024.0);
-        }
-
-        // CPU metrics
-        if let Ok(cpu_usage) = sys_info::loadavg() {
-            CPU_USAGE_PERCENT.with_label_values(&["all"]).set(cpu_usage.one * 100.0);
-        }
-    }
-}
-
 /// Initialize metrics system
 pub fn init(metrics_port: u16) -> Result<(), Box<dyn std::error::Error>> {
     // Start system metrics collector
@@ -468,32 +778,6 @@ impl Metrics {
     pub fn record_request(&self, server_id: &str, method: &str, status: &str, duration: Duration) {
         record_mcp_request(server_id, method, status, duration);
     }
-
-    /// Increment cache hits
-    pub fn cache_hits(&self) -> CacheHitCounter {
-        CacheHitCounter
-    }
-
-    /// Record for specific tools/list duration
-    pub fn tools_list_duration(&self) -> DurationRecorder {
-        DurationRecorder {
-            metric_name: "tools_list",
-        }
-    }
-
-    /// Record for tools/call duration
-    pub fn tools_call_duration(&self) -> DurationRecorder {
-        DurationRecorder {
-            metric_name: "tools_call",
-        }
-    }
-
-    /// Record for resources/list duration
-    pub fn resources_list_duration(&self) -> DurationRecorder {
-        DurationRecorder {
-            metric_name: "resources_list",
-        }
-    }
 }
 
 impl Default for Metrics {
@@ -502,29 +786,6 @@ impl Default for Metrics {
     }
 }
 
-/// Helper struct for cache hit counting
-pub struct CacheHitCounter;
-
-impl CacheHitCounter {
-    pub fn inc(&self) {
-        CONTEXT_TOKENS_SAVED.with_label_values(&["cache_hit"]).inc();
-    }
-}
-
-/// Helper struct for duration recording
-pub struct DurationRecorder {
-    metric_name: &'static str,
-}
-
-impl DurationRecorder {
-    pub fn record(&self, duration: f64) {
-        // Record to appropriate histogram
-        MCP_REQUEST_DURATION_SECONDS
-            .with_label_values(&["proxy", self.metric_name])
-            .observe(duration);
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -543,6 +804,39 @@ mod tests {
         assert!(!metric_families.is_empty());
     }
 
+    #[test]
+    fn test_request_labels() {
+        assert_eq!(method_label("tools/call"), "tools/call");
+        assert_eq!(
+            method_label("notifications/cancelled"),
+            "notifications/cancelled"
+        );
+        assert_eq!(method_label("vendor/customThing"), "other");
+
+        assert_eq!(
+            response_status(&serde_json::json!({"result": {}})),
+            "success"
+        );
+        assert_eq!(
+            response_status(&serde_json::json!({"error": null})),
+            "success"
+        );
+        assert_eq!(
+            response_status(&serde_json::json!({"error": {"code": -32601}})),
+            "error"
+        );
+
+        record_backend_request(
+            "labels-test",
+            "tools/call",
+            "timeout",
+            Duration::from_millis(5),
+        );
+        let counter =
+            MCP_REQUESTS_TOTAL.with_label_values(&["labels-test", "tools/call", "timeout"]);
+        assert_eq!(counter.get(), 1.0);
+    }
+
     #[test]
     fn test_metrics_exporter() {
         let exporter = MetricsExporter::new();
@@ -566,4 +860,18 @@ mod tests {
         let metric_families = REGISTRY.gather();
         assert!(!metric_families.is_empty());
     }
+
+    #[test]
+    fn test_record_tokens_saved() {
+        let saved = || CONTEXT_TOKENS_SAVED.with_label_values(&["tokens-test", "cache_hit"]).get();
+        let payload = br#"{"tools":[{"name":"search_issues","description":"Search issues"}]}"#;
+
+        record_tokens_saved("tokens-test", "cache_hit", payload);
+        let once = saved();
+        assert_eq!(once, tokens::estimate_payload_tokens(payload) as f64);
+        assert!(once > 1.0);
+
+        record_tokens_saved("tokens-test", "cache_hit", payload);
+        assert_eq!(saved(), once * 2.0);
+    }
 }
@@ -0,0 +1,337 @@
+//! Process and host resource metrics.
+//!
+//! Every pass samples the proxy's own resident memory, CPU time and open
+//! file descriptors, the busy time of each CPU core, and the same
+//! per-process figures for STDIO backend processes. CPU percentages are
+//! computed from the change in CPU time since the previous pass, so the
+//! first pass only records a baseline. Process figures come from `/proc`
+//! on Linux and `proc_pidinfo` on macOS; per-core figures are Linux only.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use super::{
+    CPU_USAGE_PERCENT, MEMORY_USAGE_BYTES, OPEN_FILE_DESCRIPTORS, PROCESS_CPU_USAGE_PERCENT,
+    STDIO_PROCESS_CPU_PERCENT, STDIO_PROCESS_MEMORY_BYTES, STDIO_PROCESS_OPEN_FDS,
+};
+
+/// A STDIO backend process to sample.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChildProcess {
+    pub server_id: String,
+    /// Position in the server's pool
+    pub slot: usize,
+    pub pid: u32,
+}
+
+/// Busy and total time of one core, in clock ticks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct CoreTicks {
+    busy: u64,
+    total: u64,
+}
+
+/// Latest usage of a sampled process.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProcessUsage {
+    /// Resident memory
+    pub memory_bytes: u64,
+    /// Percent of one core since the previous pass, `None` on the first
+    /// pass of a process
+    pub cpu_percent: Option<f64>,
+}
+
+/// Samples resource usage and publishes it as Prometheus metrics.
+#[derive(Default)]
+pub struct SystemMetricsCollector {
+    last_pass: Option<Instant>,
+    /// Per-core ticks by `core` label, `all` being every core together
+    cores: HashMap<String, CoreTicks>,
+    /// CPU time of the proxy process, in seconds
+    own_cpu: Option<f64>,
+    /// PID and CPU seconds of each sampled backend process
+    children: HashMap<(String, usize), (u32, f64)>,
+}
+
+impl SystemMetricsCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collect the proxy's metrics every `interval`, without backend
+    /// processes.
+    pub fn start(interval: Duration) {
+        tokio::spawn(async move {
+            let mut collector = Self::new();
+            let mut interval = tokio::time::interval(interval);
+
+            loop {
+                interval.tick().await;
+                collector.collect(&[]);
+            }
+        });
+    }
+
+    /// Sample the proxy, every core and `children`, publish the figures
+    /// and return the usage of each child that could be sampled. Backend
+    /// processes missing from `children` have their series removed.
+    pub fn collect(&mut self, children: &[ChildProcess]) -> Vec<(ChildProcess, ProcessUsage)> {
+        let now = Instant::now();
+        let elapsed = self.last_pass.map(|last| now.duration_since(last).as_secs_f64());
+        self.last_pass = Some(now);
+
+        if let Ok(stat) = std::fs::read_to_string("/proc/stat") {
+            for (core, ticks) in parse_core_ticks(&stat) {
+                let previous = self.cores.insert(core.clone(), ticks);
+                if let Some(percent) = previous.and_then(|previous| core_percent(previous, ticks)) {
+                    CPU_USAGE_PERCENT.with_label_values(&[&core]).set(percent);
+                }
+            }
+        }
+
+        if let Some(sample) = sample_process(None) {
+            MEMORY_USAGE_BYTES.with_label_values(&["rss"]).set(sample.rss_bytes as f64);
+            if let Some(fds) = sample.open_fds {
+                OPEN_FILE_DESCRIPTORS.set(fds as f64);
+            }
+            if let Some(percent) = cpu_percent(self.own_cpu, sample.cpu_seconds, elapsed) {
+                PROCESS_CPU_USAGE_PERCENT.set(percent);
+            }
+            self.own_cpu = Some(sample.cpu_seconds);
+        }
+
+        let mut usages = Vec::with_capacity(children.len());
+        let mut sampled = HashMap::with_capacity(children.len());
+        for child in children {
+            let Some(sample) = sample_process(Some(child.pid)) else {
+                continue;
+            };
+            let key = (child.server_id.clone(), child.slot);
+            // A restarted process in the same slot starts a new baseline
+            let previous = self
+                .children
+                .get(&key)
+                .filter(|(pid, _)| *pid == child.pid)
+                .map(|(_, cpu)| *cpu);
+            let usage = ProcessUsage {
+                memory_bytes: sample.rss_bytes,
+                cpu_percent: cpu_percent(previous, sample.cpu_seconds, elapsed),
+            };
+
+            let slot = child.slot.to_string();
+            let labels = [child.server_id.as_str(), slot.as_str()];
+            STDIO_PROCESS_MEMORY_BYTES
+                .with_label_values(&labels)
+                .set(usage.memory_bytes as f64);
+            if let Some(fds) = sample.open_fds {
+                STDIO_PROCESS_OPEN_FDS.with_label_values(&labels).set(fds as f64);
+            }
+            if let Some(percent) = usage.cpu_percent {
+                STDIO_PROCESS_CPU_PERCENT.with_label_values(&labels).set(percent);
+            }
+            sampled.insert(key, (child.pid, sample.cpu_seconds));
+            usages.push((child.clone(), usage));
+        }
+
+        for (server_id, slot) in self.children.keys() {
+            if !sampled.contains_key(&(server_id.clone(), *slot)) {
+                let slot = slot.to_string();
+                let labels = [server_id.as_str(), slot.as_str()];
+                let _ = STDIO_PROCESS_MEMORY_BYTES.remove_label_values(&labels);
+                let _ = STDIO_PROCESS_CPU_PERCENT.remove_label_values(&labels);
+                let _ = STDIO_PROCESS_OPEN_FDS.remove_label_values(&labels);
+            }
+        }
+        self.children = sampled;
+        usages
+    }
+}
+
+/// Share of one core used since the previous sample, so a process busy on
+/// two cores reports 200.
+fn cpu_percent(previous: Option<f64>, current: f64, elapsed: Option<f64>) -> Option<f64> {
+    let (previous, elapsed) = (previous?, elapsed?);
+    if elapsed <= 0.0 {
+        return None;
+    }
+    Some((current - previous).max(0.0) / elapsed * 100.0)
+}
+
+struct ProcessSample {
+    rss_bytes: u64,
+    /// User plus system CPU time
+    cpu_seconds: f64,
+    open_fds: Option<usize>,
+}
+
+/// Resident memory, CPU time and open descriptors of a process, the proxy
+/// itself for `None`.
+#[cfg(target_os = "linux")]
+fn sample_process(pid: Option<u32>) -> Option<ProcessSample> {
+    let dir = match pid {
+        Some(pid) => format!("/proc/{}", pid),
+        None => "/proc/self".to_string(),
+    };
+    let stat = std::fs::read_to_string(format!("{}/stat", dir)).ok()?;
+    let statm = std::fs::read_to_string(format!("{}/statm", dir)).ok()?;
+    let resident_pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    // Another user's process can hide its descriptors
+    let open_fds = std::fs::read_dir(format!("{}/fd", dir)).ok().map(|entries| entries.count());
+
+    static CONSTANTS: std::sync::OnceLock<(f64, u64)> = std::sync::OnceLock::new();
+    let (ticks_per_second, page_size) = *CONSTANTS.get_or_init(|| {
+        // SAFETY: sysconf has no preconditions and only reads configuration
+        let (ticks, page) = unsafe {
+            (
+                libc::sysconf(libc::_SC_CLK_TCK),
+                libc::sysconf(libc::_SC_PAGESIZE),
+            )
+        };
+        (
+            if ticks > 0 { ticks as f64 } else { 100.0 },
+            if page > 0 { page as u64 } else { 4096 },
+        )
+    });
+    Some(ProcessSample {
+        rss_bytes: resident_pages * page_size,
+        cpu_seconds: parse_process_ticks(&stat)? as f64 / ticks_per_second,
+        open_fds,
+    })
+}
+
+/// Resident memory, CPU time and, for the proxy itself, open descriptors
+/// of a process, the proxy itself for `None`.
+#[cfg(target_os = "macos")]
+fn sample_process(pid: Option<u32>) -> Option<ProcessSample> {
+    let own = pid.is_none();
+    let pid = pid.unwrap_or_else(std::process::id) as libc::c_int;
+    let size = std::mem::size_of::<libc::proc_taskinfo>() as libc::c_int;
+    let mut info = std::mem::MaybeUninit::<libc::proc_taskinfo>::zeroed();
+    // SAFETY: the buffer is a proc_taskinfo of the size passed
+    let written = unsafe {
+        libc::proc_pidinfo(
+            pid,
+            libc::PROC_PIDTASKINFO,
+            0,
+            info.as_mut_ptr().cast(),
+            size,
+        )
+    };
+    if written != size {
+        return None;
+    }
+    // SAFETY: proc_pidinfo filled the whole struct
+    let info = unsafe { info.assume_init() };
+
+    // CPU times are in Mach absolute time units
+    static NANOS_PER_UNIT: std::sync::OnceLock<f64> = std::sync::OnceLock::new();
+    let nanos_per_unit = *NANOS_PER_UNIT.get_or_init(|| {
+        let mut timebase = libc::mach_timebase_info { numer: 0, denom: 0 };
+        // SAFETY: timebase is a valid mach_timebase_info to fill
+        #[allow(deprecated)]
+        let status = unsafe { libc::mach_timebase_info(&mut timebase) };
+        match (status, timebase.denom) {
+            (0, denom) if denom > 0 => timebase.numer as f64 / denom as f64,
+            _ => 1.0,
+        }
+    });
+    let cpu_units = info.pti_total_user.saturating_add(info.pti_total_system);
+    Some(ProcessSample {
+        rss_bytes: info.pti_resident_size,
+        cpu_seconds: cpu_units as f64 * nanos_per_unit / 1e9,
+        open_fds: own
+            .then(|| std::fs::read_dir("/dev/fd").ok().map(|entries| entries.count()))
+            .flatten(),
+    })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn sample_process(_pid: Option<u32>) -> Option<ProcessSample> {
+    None
+}
+
+/// User plus system CPU ticks from a `/proc/<pid>/stat` line.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_process_ticks(stat: &str) -> Option<u64> {
+    // The command name may contain spaces and parentheses, so fields are
+    // counted from the last ')'; utime and stime are fields 14 and 15
+    let fields: Vec<&str> = stat.get(stat.rfind(')')? + 1..)?.split_whitespace().collect();
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    Some(utime + stime)
+}
+
+/// Ticks of each core from `/proc/stat`, labelled by core number, with the
+/// aggregate `cpu` line labelled `all`.
+fn parse_core_ticks(stat: &str) -> Vec<(String, CoreTicks)> {
+    stat.lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let name = fields.next()?.strip_prefix("cpu")?;
+            let core = match name {
+                "" => "all".to_string(),
+                n if n.chars().all(|c| c.is_ascii_digit()) => n.to_string(),
+                _ => return None,
+            };
+            // user nice system idle iowait irq softirq steal; guest time
+            // is already counted in user
+            let ticks: Vec<u64> = fields.take(8).filter_map(|f| f.parse().ok()).collect();
+            if ticks.len() < 4 {
+                return None;
+            }
+            let idle = ticks[3] + ticks.get(4).copied().unwrap_or(0);
+            let total: u64 = ticks.iter().sum();
+            Some((
+                core,
+                CoreTicks {
+                    busy: total - idle,
+                    total,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Busy share of a core between two samples.
+fn core_percent(previous: CoreTicks, current: CoreTicks) -> Option<f64> {
+    let total = current.total.checked_sub(previous.total)?;
+    if total == 0 {
+        return None;
+    }
+    let busy = current.busy.saturating_sub(previous.busy);
+    Some((busy as f64 / total as f64 * 100.0).min(100.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proc_parsers() {
+        let stat = "4242 (node (mcp) x) S 1 4242 4242 0 -1 4194304 \
+                    1200 0 0 0 250 75 0 0 20 0 11 0 1000 1000000 2000";
+        assert_eq!(parse_process_ticks(stat), Some(325));
+        assert_eq!(parse_process_ticks("garbage"), None);
+
+        let before = parse_core_ticks(
+            "cpu  100 0 100 800 0 0 0 0 0 0\n\
+             cpu0 50 0 50 400 0 0 0 0 0 0\n\
+             cpu1 50 0 50 400 0 0 0 0 0 0\n\
+             intr 12345\n",
+        );
+        let after = parse_core_ticks(
+            "cpu  200 0 100 900 0 0 0 0 0 0\n\
+             cpu0 150 0 50 400 0 0 0 0 0 0\n\
+             cpu1 50 0 50 500 0 0 0 0 0 0\n",
+        );
+        let cores: Vec<_> = before.iter().map(|(core, _)| core.as_str()).collect();
+        assert_eq!(cores, ["all", "0", "1"]);
+
+        let percents: Vec<_> = before
+            .into_iter()
+            .zip(after)
+            .map(|((_, previous), (_, current))| core_percent(previous, current))
+            .collect();
+        assert_eq!(percents, [Some(50.0), Some(100.0), Some(0.0)]);
+    }
+}
@@ -0,0 +1,112 @@
+//! Token estimation for context-savings metrics.
+//!
+//! Savings are reported in tokens, the unit LLM clients pay for, but the
+//! proxy does not know which tokenizer a client uses. The estimate mirrors
+//! how BPE tokenizers such as `cl100k_base` pre-split text and how their
+//! merges typically compress each piece:
+//!
+//! - a run of ASCII letters, with at most one leading space, is one token
+//!   per 6 letters (common words are a single token);
+//! - a run of digits is one token per 3 digits;
+//! - a run of punctuation is one token per 2 characters, so JSON syntax
+//!   such as `":"` or `},{` stays cheap;
+//! - a run of whitespace is one token;
+//! - any other character (CJK, emoji, ...) is one token per character.
+//!
+//! It is an approximation, not a tokenizer: it makes savings from caching,
+//! deduplication and batching measurable and comparable, not billable.
+
+/// Estimated number of tokens in `text`.
+pub fn estimate_tokens(text: &str) -> u64 {
+    let mut tokens = 0u64;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let class = CharClass::of(c);
+        let mut len = 1u64;
+        // A single space is merged into the word that follows it
+        if c == ' ' && chars.peek().is_some_and(|next| CharClass::of(*next) == CharClass::Letter) {
+            while chars.next_if(|next| CharClass::of(*next) == CharClass::Letter).is_some() {
+                len += 1;
+            }
+            tokens += (len - 1).div_ceil(6);
+            continue;
+        }
+        if class == CharClass::Other {
+            tokens += 1;
+            continue;
+        }
+        while chars.next_if(|next| CharClass::of(*next) == class).is_some() {
+            len += 1;
+        }
+        tokens += match class {
+            CharClass::Letter => len.div_ceil(6),
+            CharClass::Digit => len.div_ceil(3),
+            CharClass::Punctuation => len.div_ceil(2),
+            CharClass::Whitespace | CharClass::Other => 1,
+        };
+    }
+
+    tokens
+}
+
+/// Estimated number of tokens in a serialized payload; payloads that are
+/// not UTF-8 are estimated from their lossy decoding.
+pub fn estimate_payload_tokens(payload: &[u8]) -> u64 {
+    match std::str::from_utf8(payload) {
+        Ok(text) => estimate_tokens(text),
+        Err(_) => estimate_tokens(&String::from_utf8_lossy(payload)),
+    }
+}
+
+/// Pre-tokenization classes of characters.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Letter,
+    Digit,
+    Punctuation,
+    Whitespace,
+    Other,
+}
+
+impl CharClass {
+    fn of(c: char) -> Self {
+        if c.is_ascii_alphabetic() {
+            Self::Letter
+        } else if c.is_ascii_digit() {
+            Self::Digit
+        } else if c.is_ascii_punctuation() {
+            Self::Punctuation
+        } else if c.is_whitespace() {
+            Self::Whitespace
+        } else {
+            Self::Other
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        // "Hello", " world", "!"
+        assert_eq!(estimate_tokens("Hello world!"), 3);
+        assert_eq!(estimate_tokens(r#"{"name":"read_file"}"#), 8);
+        assert_eq!(estimate_tokens("Search GitHub issues by query"), 5);
+        assert_eq!(estimate_tokens("1234567"), 3);
+        assert_eq!(estimate_tokens("日本語"), 3);
+        assert_eq!(estimate_tokens("a\n\n  b"), 3);
+    }
+
+    #[test]
+    fn test_estimate_scales_with_size() {
+        let tool = r#"{"name":"search_issues","description":"Search GitHub issues by query","inputSchema":{"type":"object"}},"#;
+        let one = estimate_payload_tokens(tool.as_bytes());
+        let many = estimate_payload_tokens(tool.repeat(100).as_bytes());
+        assert!(one > 10 && one < tool.len() as u64 / 2, "{}", one);
+        assert!(many >= one * 99 && many <= one * 101, "{} vs {}", many, one);
+    }
+}
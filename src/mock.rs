@@ -0,0 +1,216 @@
+//! Mock MCP server, used by `only1mcp mock-server`.
+//!
+//! Speaks MCP over STDIO (newline-delimited JSON-RPC) or HTTP and answers
+//! from the same fixtures as dry-run mode (see [`crate::proxy::dry_run`]):
+//! tool lists come from the fixture file and calls get their canned replies,
+//! while tools without canned replies echo their arguments back. Latency
+//! and random failures can be injected, globally or per tool, to exercise a
+//! proxy's timeouts, retries and health checks.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tracing::{debug, info};
+
+use crate::error::{Error, Result};
+use crate::proxy::dry_run::{FixtureTool, Fixtures};
+use crate::types::{McpRequest, Tool};
+
+/// Behaviour of a mock server.
+#[derive(Debug, Clone, Default)]
+pub struct MockOptions {
+    pub fixtures: Fixtures,
+    /// Delay before every reply, unless a tool sets its own
+    pub latency: Duration,
+    /// Share of tool calls, 0.0 to 1.0, failed with an error unless a tool
+    /// sets its own
+    pub fail_rate: f64,
+}
+
+/// Fixtures with a single `echo` tool, served when no file is given.
+pub fn echo_fixtures() -> Fixtures {
+    Fixtures {
+        tools: vec![FixtureTool {
+            tool: Tool {
+                name: "echo".to_string(),
+                description: Some("Echo the arguments back".to_string()),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {"message": {"type": "string"}}
+                }),
+                output_schema: None,
+            },
+            responses: Vec::new(),
+            latency_ms: None,
+            fail_rate: None,
+        }],
+        ..Default::default()
+    }
+}
+
+/// A mock MCP server answering from fixtures.
+#[derive(Debug, Clone)]
+pub struct MockServer {
+    options: Arc<MockOptions>,
+}
+
+impl MockServer {
+    pub fn new(options: MockOptions) -> Self {
+        Self {
+            options: Arc::new(options),
+        }
+    }
+
+    /// Answer one JSON-RPC message; notifications get `None`.
+    pub async fn handle(&self, message: Value) -> Option<Value> {
+        let request: McpRequest = match serde_json::from_value(message) {
+            Ok(request) => request,
+            Err(e) => {
+                return Some(json!({
+                    "jsonrpc": "2.0",
+                    "id": null,
+                    "error": {"code": -32600, "message": format!("Invalid request: {}", e)}
+                }))
+            },
+        };
+        debug!("Mock server received {}", request.method);
+
+        let tool = (request.method == "tools/call")
+            .then(|| request.get_tool_name())
+            .flatten()
+            .and_then(|name| self.options.fixtures.tools.iter().find(|t| t.tool.name == name));
+        let latency = tool
+            .and_then(|tool| tool.latency_ms)
+            .map_or(self.options.latency, Duration::from_millis);
+        if !latency.is_zero() {
+            tokio::time::sleep(latency).await;
+        }
+
+        // Notifications get no reply
+        request.id.as_ref()?;
+
+        let fail_rate = tool.and_then(|tool| tool.fail_rate).unwrap_or(self.options.fail_rate);
+        if request.method == "tools/call" && fail_rate > 0.0 && rand::random::<f64>() < fail_rate {
+            return Some(json!({
+                "jsonrpc": "2.0",
+                "id": request.id,
+                "error": {"code": -32000, "message": "Injected failure"}
+            }));
+        }
+
+        match tool {
+            Some(tool) if tool.responses.is_empty() => {
+                let arguments = request.params().get("arguments").cloned().unwrap_or(json!({}));
+                Some(json!({
+                    "jsonrpc": "2.0",
+                    "id": request.id,
+                    "result": {"content": [{"type": "text", "text": arguments.to_string()}]}
+                }))
+            },
+            _ => Some(self.options.fixtures.respond(&request)),
+        }
+    }
+
+    /// Serve newline-delimited JSON-RPC on stdin/stdout until stdin closes.
+    pub async fn serve_stdio(self) -> Result<()> {
+        info!("Mock MCP server listening on stdio");
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+        let mut stdout = tokio::io::stdout();
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let reply = match serde_json::from_str(&line) {
+                Ok(message) => self.handle(message).await,
+                Err(e) => Some(json!({
+                    "jsonrpc": "2.0",
+                    "id": null,
+                    "error": {"code": -32700, "message": format!("Parse error: {}", e)}
+                })),
+            };
+            if let Some(reply) = reply {
+                let mut reply = serde_json::to_vec(&reply)?;
+                reply.push(b'\n');
+                stdout.write_all(&reply).await?;
+                stdout.flush().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Serve JSON-RPC over HTTP on `addr` (POST `/` or `/mcp`, and
+    /// GET `/health` for health checks).
+    pub async fn serve_http(self, addr: SocketAddr) -> Result<()> {
+        let listener = tokio::net::TcpListener::bind(addr)
+            .await
+            .map_err(|e| Error::Server(format!("Failed to bind {}: {}", addr, e)))?;
+        info!(
+            "Mock MCP server listening on http://{}",
+            listener.local_addr()?
+        );
+        axum::serve(listener, self.router())
+            .await
+            .map_err(|e| Error::Server(format!("Mock server failed: {}", e)))
+    }
+
+    /// HTTP routes of the mock server.
+    pub fn router(self) -> Router {
+        Router::new()
+            .route("/", post(handle_http))
+            .route("/mcp", post(handle_http))
+            .route("/health", get(|| async { StatusCode::OK }))
+            .with_state(self)
+    }
+}
+
+async fn handle_http(State(server): State<MockServer>, Json(message): Json<Value>) -> Response {
+    match server.handle(message).await {
+        Some(reply) => Json(reply).into_response(),
+        None => StatusCode::ACCEPTED.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str) -> Value {
+        json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call",
+            "params": {"name": name, "arguments": {"message": "hi"}}})
+    }
+
+    #[tokio::test]
+    async fn test_mock_echoes_and_injects_failures() {
+        let server = MockServer::new(MockOptions {
+            fixtures: echo_fixtures(),
+            ..Default::default()
+        });
+        let reply = server.handle(call("echo")).await.unwrap();
+        assert_eq!(reply["result"]["content"][0]["text"], r#"{"message":"hi"}"#);
+        assert_eq!(
+            server.handle(call("missing")).await.unwrap()["error"]["code"],
+            -32602
+        );
+
+        let notification = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        assert!(server.handle(notification).await.is_none());
+
+        let failing = MockServer::new(MockOptions {
+            fixtures: echo_fixtures(),
+            fail_rate: 1.0,
+            ..Default::default()
+        });
+        let reply = failing.handle(call("echo")).await.unwrap();
+        assert_eq!(reply["error"]["message"], "Injected failure");
+    }
+}
@@ -0,0 +1,213 @@
+//! Admission control (`proxy.admission`).
+//!
+//! Every request takes a slot of the global limit, and every backend call a
+//! slot of its backend's limit. Requests over a limit wait in a FIFO queue
+//! for up to `queue_timeout_ms`; once the queue is full, or the wait runs
+//! out, they are shed with a "server busy" error (`-32007`, HTTP 503).
+//! Latency then grows with the queue instead of memory growing with
+//! unbounded pending work. Queue depths are exported as
+//! `only1mcp_admission_queue_depth` and shed requests counted in
+//! `only1mcp_shed_requests_total`.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::config::{AdmissionConfig, ConcurrencyLimit};
+use crate::error::ProxyError;
+
+/// Scope label of the global limit.
+const GLOBAL: &str = "global";
+
+/// Concurrency gates of the proxy and each backend, shared by all requests.
+#[derive(Debug, Default)]
+pub struct Admission {
+    global: Gate,
+    backends: DashMap<String, Arc<Gate>>,
+}
+
+impl Admission {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wait for a slot of the global limit.
+    pub async fn admit(&self, config: &AdmissionConfig) -> Result<Permit, ProxyError> {
+        self.global.enter(&config.global, config.queue_timeout(), GLOBAL).await
+    }
+
+    /// Wait for a slot of `server_id`'s limit.
+    pub async fn admit_backend(
+        &self,
+        config: &AdmissionConfig,
+        server_id: &str,
+    ) -> Result<Permit, ProxyError> {
+        let limit = config.backend_limit(server_id);
+        if limit.max_concurrent == 0 {
+            return Ok(Permit { _slot: None });
+        }
+        let gate = self.backends.entry(server_id.to_string()).or_default().clone();
+        gate.enter(limit, config.queue_timeout(), server_id).await
+    }
+
+    /// Requests waiting for a slot of `server_id`, or of the global limit
+    /// when `None`.
+    pub fn queued(&self, server_id: Option<&str>) -> usize {
+        match server_id {
+            None => self.global.queued.load(Ordering::Relaxed),
+            Some(id) => self
+                .backends
+                .get(id)
+                .map(|gate| gate.queued.load(Ordering::Relaxed))
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// A slot of a concurrency limit, released when dropped.
+#[derive(Debug)]
+pub struct Permit {
+    _slot: Option<OwnedSemaphorePermit>,
+}
+
+/// One concurrency limit and its queue.
+#[derive(Debug, Default)]
+struct Gate {
+    /// Semaphore with the limit it was sized for; replaced when a reload
+    /// changes the limit, while requests holding old slots finish
+    slots: Mutex<Option<(usize, Arc<Semaphore>)>>,
+    queued: AtomicUsize,
+}
+
+impl Gate {
+    async fn enter(
+        &self,
+        limit: &ConcurrencyLimit,
+        timeout: Duration,
+        scope: &str,
+    ) -> Result<Permit, ProxyError> {
+        if limit.max_concurrent == 0 {
+            return Ok(Permit { _slot: None });
+        }
+        let semaphore = {
+            let mut slots = self.slots.lock();
+            match &*slots {
+                Some((size, semaphore)) if *size == limit.max_concurrent => semaphore.clone(),
+                _ => {
+                    let semaphore = Arc::new(Semaphore::new(limit.max_concurrent));
+                    *slots = Some((limit.max_concurrent, semaphore.clone()));
+                    semaphore
+                },
+            }
+        };
+        if let Ok(permit) = semaphore.clone().try_acquire_owned() {
+            return Ok(Permit {
+                _slot: Some(permit),
+            });
+        }
+
+        if self.queued.fetch_add(1, Ordering::Relaxed) >= limit.max_queued {
+            self.queued.fetch_sub(1, Ordering::Relaxed);
+            return Err(shed(scope, "queue_full"));
+        }
+        let _waiting = Waiting { gate: self, scope };
+        crate::metrics::ADMISSION_QUEUE_DEPTH
+            .with_label_values(&[scope])
+            .set(self.queued.load(Ordering::Relaxed) as i64);
+        match tokio::time::timeout(timeout, semaphore.acquire_owned()).await {
+            Ok(Ok(permit)) => Ok(Permit {
+                _slot: Some(permit),
+            }),
+            _ => Err(shed(scope, "queue_timeout")),
+        }
+    }
+}
+
+/// Counts a request in its gate's queue until dropped.
+struct Waiting<'a> {
+    gate: &'a Gate,
+    scope: &'a str,
+}
+
+impl Drop for Waiting<'_> {
+    fn drop(&mut self) {
+        let queued = self.gate.queued.fetch_sub(1, Ordering::Relaxed) - 1;
+        crate::metrics::ADMISSION_QUEUE_DEPTH
+            .with_label_values(&[self.scope])
+            .set(queued as i64);
+    }
+}
+
+/// Count a shed request and build its error.
+fn shed(scope: &str, reason: &str) -> ProxyError {
+    crate::metrics::SHED_REQUESTS_TOTAL.with_label_values(&[scope, reason]).inc();
+    let what = match scope {
+        GLOBAL => "proxy".to_string(),
+        server_id => format!("backend {}", server_id),
+    };
+    match reason {
+        "queue_full" => ProxyError::Busy(format!("Too many requests queued for {}", what)),
+        _ => ProxyError::Busy(format!("Timed out waiting for {}", what)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(max_concurrent: usize, max_queued: usize, timeout_ms: u64) -> AdmissionConfig {
+        AdmissionConfig {
+            per_backend: ConcurrencyLimit {
+                max_concurrent,
+                max_queued,
+            },
+            queue_timeout_ms: timeout_ms,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_backend_requests_queue_then_shed() {
+        let admission = Arc::new(Admission::new());
+        let config = config(1, 1, 1000);
+
+        let first = admission.admit_backend(&config, "db").await.unwrap();
+
+        // A second request waits for the first one's slot
+        let waiter = {
+            let (admission, config) = (admission.clone(), config.clone());
+            tokio::spawn(async move { admission.admit_backend(&config, "db").await })
+        };
+        while admission.queued(Some("db")) == 0 {
+            tokio::task::yield_now().await;
+        }
+
+        // A third finds the queue full
+        let shed = admission.admit_backend(&config, "db").await.unwrap_err();
+        assert!(matches!(shed, ProxyError::Busy(_)));
+        assert_eq!(shed.jsonrpc_code(), crate::error::SERVER_BUSY);
+
+        // Other backends and the unlimited global gate are unaffected
+        admission.admit_backend(&config, "search").await.unwrap();
+        admission.admit(&config).await.unwrap();
+
+        drop(first);
+        assert!(waiter.await.unwrap().is_ok());
+        assert_eq!(admission.queued(Some("db")), 0);
+    }
+
+    #[tokio::test]
+    async fn test_queued_requests_time_out() {
+        let admission = Admission::new();
+        let config = config(1, 10, 20);
+
+        let _first = admission.admit_backend(&config, "db").await.unwrap();
+        let error = admission.admit_backend(&config, "db").await.unwrap_err();
+        assert!(error.to_string().contains("Timed out waiting for backend db"));
+        assert_eq!(admission.queued(Some("db")), 0);
+    }
+}
@@ -0,0 +1,111 @@
+//! Tool aliases (`proxy.tool_aliases`).
+//!
+//! An alias gives one name to equivalent tools of several backends, e.g.
+//! GitHub's `search_repositories` and GitLab's `search_projects`:
+//!
+//! - tools/list lists the alias, with the definition of its first target
+//!   tool that is listed, in place of all its target tools.
+//! - tools/call of the alias calls the first target whose server is
+//!   registered and healthy, under that target's tool name. Load balancing
+//!   and sticky sessions do not apply to it.
+//!
+//! Target tools are named as their server lists them, after any
+//! `servers[].transforms.rename_tools`.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::config::{AliasTargetConfig, ToolAliasConfig};
+
+/// The alias named `name`, if there is one.
+pub fn find<'a>(aliases: &'a [ToolAliasConfig], name: &str) -> Option<&'a ToolAliasConfig> {
+    aliases.iter().find(|alias| alias.name == name)
+}
+
+/// The first target of `alias` whose server is `available`.
+pub fn pick(
+    alias: &ToolAliasConfig,
+    available: impl Fn(&str) -> bool,
+) -> Option<&AliasTargetConfig> {
+    alias.targets.iter().find(|target| available(&target.server))
+}
+
+/// Replace the target tools of a tools/list by the aliases standing for
+/// them. An alias none of whose targets is listed is left out.
+pub fn list_aliases(aliases: &[ToolAliasConfig], tools: &mut Vec<Value>) {
+    if aliases.is_empty() {
+        return;
+    }
+    let is_target = |name: &str| {
+        aliases
+            .iter()
+            .any(|alias| alias.targets.iter().any(|target| target.tool == name))
+    };
+    let mut targets = HashMap::new();
+    tools.retain(|tool| {
+        let name = tool.get("name").and_then(Value::as_str).unwrap_or_default();
+        if !is_target(name) {
+            return true;
+        }
+        targets.insert(name.to_string(), tool.clone());
+        false
+    });
+
+    for alias in aliases {
+        let definition = alias.targets.iter().find_map(|target| targets.get(&target.tool));
+        if let Some(mut definition) = definition.cloned() {
+            definition["name"] = Value::String(alias.name.clone());
+            tools.push(definition);
+        }
+    }
+    tools.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn aliases() -> Vec<ToolAliasConfig> {
+        serde_yaml::from_str(
+            r#"
+            - name: search
+              targets:
+                - {server: github, tool: search_repositories}
+                - {server: gitlab, tool: search_projects}
+            - name: gh_issues
+              targets: [{server: github, tool: list_issues}]
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_targets_listed_under_their_alias() {
+        let mut tools = vec![
+            json!({"name": "fetch"}),
+            json!({"name": "search_projects", "description": "GitLab"}),
+            json!({"name": "search_repositories", "description": "GitHub"}),
+        ];
+        list_aliases(&aliases(), &mut tools);
+        assert_eq!(
+            tools,
+            [
+                json!({"name": "fetch"}),
+                json!({"name": "search", "description": "GitHub"}),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_first_available_target_picked() {
+        let aliases = aliases();
+        let alias = find(&aliases, "search").unwrap();
+        assert_eq!(pick(alias, |_| true).unwrap().server, "github");
+        let target = pick(alias, |server| server != "github").unwrap();
+        assert_eq!(target.tool, "search_projects");
+        assert!(pick(alias, |_| false).is_none());
+        assert!(find(&aliases, "search_projects").is_none());
+    }
+}
@@ -0,0 +1,108 @@
+//! Checking tools/call arguments against the called tool's `inputSchema`
+//! (`proxy.argument_validation`).
+//!
+//! Schemas come from the tools/list of the backend a call is routed to, so
+//! a call is only checked once that list has been fetched. Arguments are
+//! checked as the backend will receive them, after
+//! `servers[].transforms.default_arguments`. A schema that does not compile
+//! is not enforced.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use jsonschema::JSONSchema;
+use parking_lot::Mutex;
+use serde_json::Value;
+use tracing::debug;
+
+/// Most problems reported for one call.
+const MAX_REPORTED: usize = 5;
+
+/// Compiled schemas kept before the cache is cleared.
+const MAX_SCHEMAS: usize = 4096;
+
+/// Compiled tool schemas, keyed by a hash of the schema so a tool whose
+/// schema changes is compiled again. Also checks results against output
+/// schemas (see [`crate::proxy::sanitize`]).
+#[derive(Default)]
+pub struct ToolSchemas {
+    compiled: Mutex<HashMap<u64, Option<Arc<JSONSchema>>>>,
+}
+
+impl ToolSchemas {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check `value` against `schema`, returning what is wrong with it:
+    /// each problem with the path of the value it concerns.
+    pub fn check(&self, schema: &Value, value: &Value) -> Result<(), Vec<String>> {
+        let Some(validator) = self.compiled(schema) else {
+            return Ok(());
+        };
+        let problems: Vec<String> = match validator.validate(value) {
+            Ok(()) => return Ok(()),
+            Err(errors) => errors
+                .take(MAX_REPORTED)
+                .map(|error| match error.instance_path.to_string() {
+                    path if path.is_empty() => error.to_string(),
+                    path => format!("{}: {}", path, error),
+                })
+                .collect(),
+        };
+        Err(problems)
+    }
+
+    fn compiled(&self, schema: &Value) -> Option<Arc<JSONSchema>> {
+        let key = xxhash_rust::xxh3::xxh3_64(schema.to_string().as_bytes());
+        let mut compiled = self.compiled.lock();
+        if let Some(validator) = compiled.get(&key) {
+            return validator.clone();
+        }
+        if compiled.len() >= MAX_SCHEMAS {
+            compiled.clear();
+        }
+        let validator = match JSONSchema::compile(schema) {
+            Ok(validator) => Some(Arc::new(validator)),
+            Err(e) => {
+                debug!("Not enforcing invalid tool schema: {}", e);
+                None
+            },
+        };
+        compiled.insert(key, validator.clone());
+        validator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_arguments_checked_against_schema() {
+        let schema = json!({
+            "type": "object",
+            "properties": {"query": {"type": "string"}, "limit": {"type": "integer"}},
+            "required": ["query"]
+        });
+        let schemas = ToolSchemas::new();
+        assert!(schemas.check(&schema, &json!({"query": "rust", "limit": 5})).is_ok());
+
+        let problems = schemas.check(&schema, &json!({"limit": "ten"})).unwrap_err();
+        assert_eq!(problems.len(), 2, "{:?}", problems);
+        assert!(
+            problems.iter().any(|p| p.starts_with("/limit: ") && p.contains("integer")),
+            "{:?}",
+            problems
+        );
+        assert!(problems.iter().any(|p| p.contains("\"query\" is a required property")));
+    }
+
+    #[test]
+    fn test_invalid_schema_not_enforced() {
+        let schemas = ToolSchemas::new();
+        let schema = json!({"type": "no-such-type"});
+        assert!(schemas.check(&schema, &json!({"anything": 1})).is_ok());
+    }
+}
@@ -0,0 +1,114 @@
+//! Client-facing MCP `initialize` negotiation.
+//!
+//! The proxy answers `initialize` itself instead of forwarding it, advertising
+//! the union of its backends' capabilities: a capability is offered when any
+//! backend offers it, and boolean sub-flags such as `resources.subscribe` are
+//! set when any backend sets them.
+
+use serde_json::{json, Map, Value};
+
+use crate::transport::stdio::ServerCapabilities;
+
+/// Protocol revisions the proxy accepts from clients, oldest first.
+pub const SUPPORTED_PROTOCOL_VERSIONS: [&str; 3] = ["2024-11-05", "2025-03-26", "2025-06-18"];
+
+/// Accessor for one capability of a backend's set.
+type CapabilityField = fn(&ServerCapabilities) -> Option<&Value>;
+
+/// Version to answer a client's `initialize` with: the requested one if
+/// supported, otherwise the latest the proxy speaks.
+pub fn negotiate_protocol_version(requested: Option<&str>) -> &'static str {
+    SUPPORTED_PROTOCOL_VERSIONS
+        .iter()
+        .find(|version| Some(**version) == requested)
+        .or(SUPPORTED_PROTOCOL_VERSIONS.last())
+        .copied()
+        .unwrap_or("2024-11-05")
+}
+
+/// Union of backend capability sets, as the `capabilities` object of an
+/// `initialize` result.
+///
+/// With no known backend capabilities, tools, resources and prompts are
+/// advertised anyway: the proxy answers their list methods regardless and
+/// strict clients would otherwise never ask. `listChanged` is never set since
+/// the proxy has no channel to push notifications to clients on.
+pub fn merge_capabilities(backends: &[ServerCapabilities]) -> Value {
+    if backends.is_empty() {
+        return json!({"tools": {}, "resources": {}, "prompts": {}});
+    }
+
+    let fields: [(&str, CapabilityField); 4] = [
+        ("tools", |c| c.tools.as_ref()),
+        ("resources", |c| c.resources.as_ref()),
+        ("prompts", |c| c.prompts.as_ref()),
+        ("logging", |c| c.logging.as_ref()),
+    ];
+
+    let mut merged = Map::new();
+    for (name, select) in fields {
+        let offered: Vec<&Value> = backends.iter().filter_map(select).collect();
+        if !offered.is_empty() {
+            merged.insert(name.to_string(), merge_flags(&offered));
+        }
+    }
+    Value::Object(merged)
+}
+
+/// Merge capability objects, OR-ing boolean flags.
+fn merge_flags(values: &[&Value]) -> Value {
+    let mut flags = Map::new();
+    for (key, value) in values.iter().filter_map(|v| v.as_object()).flatten() {
+        if key == "listChanged" {
+            continue;
+        }
+        match (flags.get_mut(key), value) {
+            (Some(Value::Bool(set)), Value::Bool(other)) => *set |= other,
+            (Some(_), _) => {},
+            (None, value) => {
+                flags.insert(key.clone(), value.clone());
+            },
+        }
+    }
+    Value::Object(flags)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn caps(value: Value) -> ServerCapabilities {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_negotiate_protocol_version() {
+        assert_eq!(negotiate_protocol_version(Some("2024-11-05")), "2024-11-05");
+        assert_eq!(negotiate_protocol_version(Some("2025-03-26")), "2025-03-26");
+        assert_eq!(negotiate_protocol_version(Some("1999-01-01")), "2025-06-18");
+        assert_eq!(negotiate_protocol_version(None), "2025-06-18");
+    }
+
+    #[test]
+    fn test_merge_capabilities() {
+        let merged = merge_capabilities(&[
+            caps(json!({"tools": {"listChanged": true}})),
+            caps(json!({"resources": {"subscribe": false}, "logging": {}})),
+            caps(json!({"resources": {"subscribe": true}, "tools": {}})),
+        ]);
+
+        assert_eq!(
+            merged,
+            json!({"tools": {}, "resources": {"subscribe": true}, "logging": {}})
+        );
+    }
+
+    #[test]
+    fn test_merge_without_backends() {
+        let merged = merge_capabilities(&[]);
+        for capability in ["tools", "resources", "prompts"] {
+            assert!(merged.get(capability).is_some(), "{}", capability);
+        }
+        assert!(merged.get("logging").is_none());
+    }
+}
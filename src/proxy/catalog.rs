@@ -0,0 +1,230 @@
+//! Precomputed aggregated catalogs maintained in the background.
+//!
+//! Without a catalog, every `tools/list`, `resources/list` and `prompts/list`
+//! cache miss fans out to all backends, so clients occasionally pay the full
+//! aggregation latency. When enabled, a background refresher fetches every
+//! backend's lists into the server registry and rebuilds the merged catalogs
+//! on a fixed interval (and whenever a `list_changed` notification or config
+//! reload requests it), so list requests become a single atomic load. With
+//! `prefetch` alone it only keeps the registry and the tools/list response
+//! cache warm.
+//!
+//! Every catalog carries a version tag derived from its contents, which
+//! clients can echo back as `ifVersion` to skip unchanged lists.
+
+use crate::proxy::handler::{
+    aggregate_prompts, aggregate_resources, aggregate_tools, cache_tools_list,
+    refresh_server_catalog,
+};
+use crate::proxy::server::AppState;
+use crate::types::{McpRequest, Prompt, Resource, Tool};
+use arc_swap::ArcSwapOption;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, Notify};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// An aggregated catalog and its version tag.
+pub struct Catalog<T> {
+    pub items: Vec<T>,
+    /// Changes whenever `items` does; see [`catalog_version`]
+    pub version: String,
+}
+
+impl<T: Serialize> Catalog<T> {
+    /// Wrap `items`, computing their version.
+    pub fn new(items: Vec<T>) -> Self {
+        let version = catalog_version(&items);
+        Self { items, version }
+    }
+}
+
+/// Version tag of a list of catalog items: a hash of their JSON form.
+pub fn catalog_version<T: Serialize>(items: &[T]) -> String {
+    let serialized = serde_json::to_vec(items).unwrap_or_default();
+    format!("{:016x}", xxhash_rust::xxh3::xxh3_64(&serialized))
+}
+
+/// Latest aggregated catalogs, swapped atomically by the refresher.
+///
+/// Each catalog is `None` until its first successful refresh, in which case
+/// handlers fall back to on-demand aggregation.
+#[derive(Default)]
+pub struct CatalogStore {
+    tools: ArcSwapOption<Catalog<Tool>>,
+    resources: ArcSwapOption<Catalog<Resource>>,
+    prompts: ArcSwapOption<Catalog<Prompt>>,
+    /// Wakes the refresher ahead of its next scheduled run
+    refresh: Notify,
+}
+
+impl CatalogStore {
+    /// Create an empty catalog store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current aggregated tool catalog, if one has been built.
+    pub fn tools(&self) -> Option<Arc<Catalog<Tool>>> {
+        self.tools.load_full()
+    }
+
+    /// Current aggregated resource catalog, if one has been built.
+    pub fn resources(&self) -> Option<Arc<Catalog<Resource>>> {
+        self.resources.load_full()
+    }
+
+    /// Current aggregated prompt catalog, if one has been built.
+    pub fn prompts(&self) -> Option<Arc<Catalog<Prompt>>> {
+        self.prompts.load_full()
+    }
+
+    /// Replace the tool catalog.
+    pub fn set_tools(&self, tools: Vec<Tool>) {
+        self.tools.store(Some(Arc::new(Catalog::new(tools))));
+    }
+
+    /// Replace the resource catalog.
+    pub fn set_resources(&self, resources: Vec<Resource>) {
+        self.resources.store(Some(Arc::new(Catalog::new(resources))));
+    }
+
+    /// Replace the prompt catalog.
+    pub fn set_prompts(&self, prompts: Vec<Prompt>) {
+        self.prompts.store(Some(Arc::new(Catalog::new(prompts))));
+    }
+
+    /// Ask the background refresher to rebuild the catalogs now.
+    ///
+    /// Multiple requests made before the refresher wakes collapse into one run.
+    pub fn request_refresh(&self) {
+        self.refresh.notify_one();
+    }
+
+    /// Drop all catalogs so handlers aggregate on demand again.
+    pub fn clear(&self) {
+        self.tools.store(None);
+        self.resources.store(None);
+        self.prompts.store(None);
+    }
+}
+
+/// Refresh every healthy backend's lists, then rebuild the catalogs from them.
+///
+/// If no backend is available the tool catalog keeps its previous contents,
+/// so a transient outage does not empty the list clients see. Refreshes run
+/// in the background and wait for every backend rather than applying the
+/// aggregation budget. With only `prefetch` enabled, no catalogs are kept;
+/// the tools/list response is cached instead.
+pub async fn refresh_catalogs(state: &AppState) {
+    let start = Instant::now();
+    let request = |method: &str| McpRequest::new(method, serde_json::json!({}), None);
+
+    // Ask every backend again rather than reusing the lists it last returned
+    refresh_servers(state).await;
+
+    let keep_catalogs = state.config.load().context_optimization.catalog.enabled;
+    match aggregate_tools(state, &request("tools/list"), None).await {
+        Ok(tools) if keep_catalogs => state.catalog.set_tools(tools.items),
+        Ok(tools) => {
+            cache_tools_list(state, &tools).await;
+        },
+        Err(e) => warn!("Catalog refresh for tools failed, keeping previous: {}", e),
+    }
+
+    if keep_catalogs {
+        let resources = aggregate_resources(state, &request("resources/list"), None).await;
+        state.catalog.set_resources(resources.items);
+
+        let prompts = aggregate_prompts(state, &request("prompts/list"), None).await;
+        state.catalog.set_prompts(prompts.items);
+    }
+
+    debug!("Catalogs refreshed in {:?}", start.elapsed());
+}
+
+/// Fetch the tools, resources and prompts of every healthy backend into the
+/// server registry, concurrently.
+async fn refresh_servers(state: &AppState) {
+    let servers = state.registry.read().await.get_healthy_servers().await;
+    let refreshes = servers.iter().map(|server_id| async move {
+        (server_id, refresh_server_catalog(state, server_id).await)
+    });
+
+    for (server_id, result) in futures::future::join_all(refreshes).await {
+        if let Err(e) = result {
+            warn!("Catalog refresh for {} failed: {}", server_id, e);
+        }
+    }
+}
+
+/// Spawn the background refresher.
+///
+/// Runs an initial refresh immediately, then every `interval` or whenever
+/// [`CatalogStore::request_refresh`] is called, until `shutdown_rx` fires.
+pub fn spawn_refresher(
+    state: AppState,
+    interval: Duration,
+    mut shutdown_rx: broadcast::Receiver<()>,
+) -> JoinHandle<()> {
+    info!("Starting catalog refresher (interval: {:?})", interval);
+
+    tokio::spawn(async move {
+        loop {
+            refresh_catalogs(&state).await;
+
+            tokio::select! {
+                _ = tokio::time::sleep(interval) => {},
+                _ = state.catalog.refresh.notified() => {
+                    debug!("Catalog refresh requested");
+                },
+                _ = shutdown_rx.recv() => {
+                    debug!("Catalog refresher stopping");
+                    break;
+                },
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(name: &str) -> Tool {
+        Tool {
+            name: name.to_string(),
+            description: None,
+            input_schema: serde_json::json!({"type": "object"}),
+            output_schema: None,
+        }
+    }
+
+    #[test]
+    fn test_catalog_empty_until_set() {
+        let store = CatalogStore::new();
+        assert!(store.tools().is_none());
+
+        store.set_tools(vec![tool("a"), tool("b")]);
+        let catalog = store.tools().unwrap();
+        assert_eq!(catalog.items.len(), 2);
+        assert_eq!(catalog.version, catalog_version(&[tool("a"), tool("b")]));
+        assert_ne!(catalog.version, catalog_version(&[tool("a")]));
+
+        store.clear();
+        assert!(store.tools().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_request_refresh_wakes_waiter() {
+        let store = Arc::new(CatalogStore::new());
+        store.request_refresh();
+
+        // A permit stored before the waiter arrives must not be lost
+        tokio::time::timeout(Duration::from_millis(100), store.refresh.notified())
+            .await
+            .expect("refresh notification was lost");
+    }
+}
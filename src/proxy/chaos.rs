@@ -0,0 +1,160 @@
+//! Fault injection into backend requests (`proxy.chaos`).
+//!
+//! Each request to a backend is matched against the configured fault
+//! rules, which may delay it, fail it with a JSON-RPC error before it is
+//! sent, or send it and drop the reply so the request runs into its
+//! timeout. Rules marked `health_checks` affect health checks too, which
+//! makes it possible to watch circuits open and failover groups shift
+//! without breaking a real backend. Every injected fault is counted in
+//! `only1mcp_injected_faults_total`.
+
+use std::time::Duration;
+
+use rand::Rng;
+
+use crate::config::{ChaosConfig, FaultConfig};
+use crate::types::McpError;
+
+/// Failure injected into a request.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fault {
+    /// Answered with this error without reaching the backend
+    Error(McpError),
+    /// Sent, but the reply is never delivered
+    Drop,
+}
+
+/// What to do to one request.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Injection {
+    pub delay: Duration,
+    pub fault: Option<Fault>,
+}
+
+impl Injection {
+    /// Roll the faults for a `method` request to `server_id`.
+    pub fn for_request(config: &ChaosConfig, server_id: &str, method: &str) -> Self {
+        if !config.enabled {
+            return Self::default();
+        }
+        Self::roll(
+            config.faults.iter().filter(|fault| fault.matches(server_id, method)),
+            server_id,
+        )
+    }
+
+    /// Roll the faults for a health check of `server_id`.
+    pub fn for_health_check(config: &ChaosConfig, server_id: &str) -> Self {
+        if !config.enabled {
+            return Self::default();
+        }
+        Self::roll(
+            config
+                .faults
+                .iter()
+                .filter(|fault| fault.health_checks && fault.matches_server(server_id)),
+            server_id,
+        )
+    }
+
+    /// Delays of all matching rules add up; the first rule that fails the
+    /// request decides how.
+    fn roll<'a>(faults: impl Iterator<Item = &'a FaultConfig>, server_id: &str) -> Self {
+        let mut rng = rand::thread_rng();
+        let mut injection = Self::default();
+        for fault in faults {
+            if fault.latency_ms > 0 && rng.gen_range(0..100) < fault.latency_percent {
+                injection.delay += Duration::from_millis(fault.latency_ms);
+            }
+            if injection.fault.is_none() {
+                let roll = rng.gen_range(0..100);
+                if roll < fault.error_percent {
+                    injection.fault = Some(Fault::Error(McpError::new(
+                        fault.error.code,
+                        fault.error.message.clone(),
+                    )));
+                } else if roll < fault.error_percent.saturating_add(fault.drop_percent) {
+                    injection.fault = Some(Fault::Drop);
+                }
+            }
+        }
+
+        let count = |kind: &str| {
+            crate::metrics::INJECTED_FAULTS_TOTAL
+                .with_label_values(&[server_id, kind])
+                .inc();
+        };
+        if !injection.delay.is_zero() {
+            count("latency");
+        }
+        match injection.fault {
+            Some(Fault::Error(_)) => count("error"),
+            Some(Fault::Drop) => count("drop"),
+            None => {},
+        }
+        injection
+    }
+
+    /// Wait out the injected delay.
+    pub async fn delay(&self) {
+        if !self.delay.is_zero() {
+            tokio::time::sleep(self.delay).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chaos(yaml: &str) -> ChaosConfig {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_injection_matches_servers_and_methods() {
+        let config = chaos(
+            r#"
+enabled: true
+faults:
+  - servers: ["github*"]
+    methods: [tools/call]
+    latency_ms: 50
+    error_percent: 100
+    error: {code: -32000, message: boom}
+  - servers: [github]
+    latency_ms: 20
+    drop_percent: 100
+"#,
+        );
+
+        let call = Injection::for_request(&config, "github", "tools/call");
+        assert_eq!(call.delay, Duration::from_millis(70));
+        assert_eq!(
+            call.fault,
+            Some(Fault::Error(McpError::new(-32000, "boom")))
+        );
+
+        let list = Injection::for_request(&config, "github", "tools/list");
+        assert_eq!(list.delay, Duration::from_millis(20));
+        assert_eq!(list.fault, Some(Fault::Drop));
+
+        assert_eq!(
+            Injection::for_request(&config, "slack", "tools/call"),
+            Injection::default()
+        );
+        assert_eq!(
+            Injection::for_health_check(&config, "github"),
+            Injection::default()
+        );
+
+        let disabled = ChaosConfig {
+            enabled: false,
+            ..config
+        };
+        assert_eq!(
+            Injection::for_request(&disabled, "github", "tools/call"),
+            Injection::default()
+        );
+    }
+}
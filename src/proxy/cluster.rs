@@ -0,0 +1,419 @@
+//! Cluster mode (`cluster`): instances share backend health and sticky
+//! sessions by gossip.
+//!
+//! Every `gossip_interval_ms`, each instance POSTs a [`GossipMessage`] to
+//! `/api/v1/admin/cluster/gossip` of every configured peer and merges the
+//! reply, so one round trip updates both sides. A message carries what the
+//! sender's own health checkers and STDIO supervisor observed, and the
+//! sticky session pins it used since its previous exchange with that peer
+//! (all of them on first contact).
+//!
+//! Routing uses the worst state any live instance reports for a backend:
+//! a backend failing from one instance is taken out everywhere, and comes
+//! back once all of them see it recover. An instance silent for
+//! `peer_timeout_ms` is down; its reports stop counting, and the clients it
+//! served find their pins on the others, which received them by gossip.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use arc_swap::ArcSwap;
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::config::{ClusterConfig, Config};
+use crate::health::checker::{HealthHistory, HealthState, HealthStateMap, HealthTransition};
+use crate::routing::sticky::{SessionEntry, SessionTable};
+use crate::types::{ClusterPeer, ClusterStatus, ServerId};
+
+/// What one instance tells another in each exchange.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GossipMessage {
+    pub node_id: String,
+    /// The sender's own health observations
+    pub health: HashMap<ServerId, HealthState>,
+    /// Sticky session pins used since the previous exchange
+    pub sessions: Vec<SessionEntry>,
+}
+
+/// Another instance that has been heard from.
+#[derive(Debug)]
+struct Member {
+    health: HashMap<ServerId, HealthState>,
+    last_seen: Instant,
+    /// When pins were last sent to it in a reply
+    replied: Option<Instant>,
+}
+
+/// A configured peer URL.
+#[derive(Debug, Default)]
+struct Link {
+    node_id: Option<String>,
+    /// When pins were last pushed to it
+    pushed: Option<Instant>,
+    error: Option<String>,
+}
+
+/// This instance's view of the cluster.
+#[derive(Debug)]
+pub struct Cluster {
+    node_id: String,
+    settings: ClusterConfig,
+    config: Arc<ArcSwap<Config>>,
+    client: reqwest::Client,
+    /// Health seen by this instance, written by its checkers and supervisor
+    observed: HealthStateMap,
+    /// Merged health that routing reads (the registry's map)
+    merged: HealthStateMap,
+    history: HealthHistory,
+    sessions: Arc<SessionTable>,
+    members: DashMap<String, Member>,
+    links: DashMap<String, Link>,
+    /// Servers held at a state worse than observed here, and the peer
+    /// reporting it
+    imposed: DashMap<ServerId, String>,
+}
+
+impl Cluster {
+    pub fn new(
+        config: Arc<ArcSwap<Config>>,
+        merged: HealthStateMap,
+        history: HealthHistory,
+        sessions: Arc<SessionTable>,
+    ) -> Self {
+        let current = config.load_full();
+        let settings = current.cluster.clone();
+        let node_id = settings.node_id.clone().unwrap_or_else(|| {
+            std::env::var("HOSTNAME")
+                .ok()
+                .filter(|host| !host.is_empty())
+                .map(|host| format!("{}:{}", host, current.server.port))
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+        });
+        let client = reqwest::Client::builder()
+            .timeout(settings.peer_timeout())
+            .build()
+            .unwrap_or_default();
+        let links = settings.peers.iter().map(|url| (url.clone(), Link::default())).collect();
+
+        Self {
+            node_id,
+            settings,
+            config,
+            client,
+            observed: Arc::new(DashMap::new()),
+            merged,
+            history,
+            sessions,
+            members: DashMap::new(),
+            links,
+            imposed: DashMap::new(),
+        }
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    /// Map this instance's health checkers and STDIO supervisor write to.
+    pub fn observed_health(&self) -> HealthStateMap {
+        self.observed.clone()
+    }
+
+    /// Exchange state with every peer each `gossip_interval_ms` until
+    /// shutdown.
+    pub async fn run(self: Arc<Self>, mut shutdown: tokio::sync::broadcast::Receiver<()>) {
+        info!(
+            "Cluster node {} gossiping with {} peer(s)",
+            self.node_id,
+            self.links.len()
+        );
+        loop {
+            let urls: Vec<String> = self.links.iter().map(|link| link.key().clone()).collect();
+            futures::future::join_all(urls.iter().map(|url| self.exchange(url))).await;
+            self.merge_health();
+
+            tokio::select! {
+                _ = tokio::time::sleep(self.settings.gossip_interval()) => {},
+                _ = shutdown.recv() => break,
+            }
+        }
+    }
+
+    /// Push our state to the peer at `url` and take in its reply.
+    async fn exchange(&self, url: &str) {
+        let started = Instant::now();
+        let pushed = self.links.get(url).and_then(|link| link.pushed);
+        let message = self.message(pushed);
+
+        let mut request = self
+            .client
+            .post(format!(
+                "{}/api/v1/admin/cluster/gossip",
+                url.trim_end_matches('/')
+            ))
+            .json(&message);
+        if let Some(token) = &self.settings.token {
+            request = request.bearer_auth(token);
+        }
+        let reply = match request.send().await {
+            Ok(response) if response.status().is_success() => {
+                response.json::<GossipMessage>().await.map_err(|e| e.to_string())
+            },
+            Ok(response) => Err(format!(
+                "HTTP {}: {}",
+                response.status(),
+                response.text().await.unwrap_or_default()
+            )),
+            Err(e) => Err(e.to_string()),
+        };
+
+        let mut link = self.links.entry(url.to_string()).or_default();
+        match reply {
+            Ok(reply) => {
+                if link.error.take().is_some() || link.node_id.is_none() {
+                    info!("Cluster peer {} at {} is reachable", reply.node_id, url);
+                }
+                link.node_id = Some(reply.node_id.clone());
+                link.pushed = Some(started);
+                drop(link);
+                self.accept(reply);
+            },
+            Err(e) => {
+                if link.error.is_none() {
+                    warn!("Cluster peer {} is unreachable: {}", url, e);
+                }
+                link.error = Some(e);
+            },
+        }
+    }
+
+    /// Take in a peer's message (`POST /api/v1/admin/cluster/gossip`) and
+    /// answer with our own state.
+    pub fn receive(&self, message: GossipMessage) -> Result<GossipMessage, String> {
+        if message.node_id == self.node_id {
+            return Err(format!(
+                "Node ID {} is this instance's own; is it listed in its own peers?",
+                self.node_id
+            ));
+        }
+        let started = Instant::now();
+        let replied = self.members.get(&message.node_id).and_then(|member| member.replied);
+        let reply = self.message(replied);
+        let node_id = message.node_id.clone();
+        self.accept(message);
+        if let Some(mut member) = self.members.get_mut(&node_id) {
+            member.replied = Some(started);
+        }
+        self.merge_health();
+        Ok(reply)
+    }
+
+    fn message(&self, since: Option<Instant>) -> GossipMessage {
+        GossipMessage {
+            node_id: self.node_id.clone(),
+            health: self
+                .observed
+                .iter()
+                .map(|entry| (entry.key().clone(), entry.value().clone()))
+                .collect(),
+            sessions: self.sessions.used_since(since),
+        }
+    }
+
+    fn accept(&self, message: GossipMessage) {
+        let ttl = self.config.load().proxy.routing.session_ttl();
+        self.sessions.merge(message.sessions, ttl);
+
+        let mut member = self.members.entry(message.node_id.clone()).or_insert_with(|| {
+            info!("Cluster peer {} joined", message.node_id);
+            Member {
+                health: HashMap::new(),
+                last_seen: Instant::now(),
+                replied: None,
+            }
+        });
+        member.health = message.health;
+        member.last_seen = Instant::now();
+    }
+
+    /// Whether `member` was heard from within `peer_timeout_ms`.
+    fn is_up(&self, member: &Member) -> bool {
+        member.last_seen.elapsed() < self.settings.peer_timeout()
+    }
+
+    /// Publish, per server, the worst state reported by this instance or
+    /// any live peer.
+    fn merge_health(&self) {
+        let mut worst: HashMap<ServerId, (HealthState, Option<String>)> = self
+            .observed
+            .iter()
+            .map(|entry| (entry.key().clone(), (entry.value().clone(), None)))
+            .collect();
+        let mut up = 0;
+        for member in self.members.iter() {
+            if !self.is_up(&member) {
+                continue;
+            }
+            up += 1;
+            for (server_id, state) in &member.health {
+                let current =
+                    worst.entry(server_id.clone()).or_insert((HealthState::Unknown, None));
+                if severity(state) > severity(&current.0) {
+                    *current = (state.clone(), Some(member.key().clone()));
+                }
+            }
+        }
+        crate::metrics::CLUSTER_PEERS_UP.set(up);
+
+        for (server_id, (state, reporter)) in worst {
+            let previous = self.merged.insert(server_id.clone(), state.clone());
+            if previous.as_ref() == Some(&state) {
+                continue;
+            }
+            let from = format!("{:?}", previous.unwrap_or(HealthState::Unknown));
+            let reason = match reporter {
+                Some(peer) => {
+                    self.imposed.insert(server_id.clone(), peer.clone());
+                    format!("reported by cluster peer {}", peer)
+                },
+                None => match self.imposed.remove(&server_id) {
+                    Some((_, peer)) => format!("cluster peer {} no longer reports it", peer),
+                    // A transition of our own, logged by the checker
+                    None => continue,
+                },
+            };
+            debug!("{} is {:?}: {}", server_id, state, reason);
+            self.history.record(HealthTransition {
+                server_id,
+                from,
+                to: format!("{:?}", state),
+                at: chrono::Utc::now(),
+                reason: Some(reason),
+            });
+        }
+    }
+
+    /// Membership for `GET /api/v1/admin/cluster`.
+    pub fn status(&self) -> ClusterStatus {
+        let mut peers: Vec<ClusterPeer> = self
+            .links
+            .iter()
+            .map(|link| ClusterPeer {
+                node_id: link.node_id.clone(),
+                url: Some(link.key().clone()),
+                up: false,
+                last_seen_ms: None,
+                error: link.error.clone(),
+            })
+            .collect();
+        for member in self.members.iter() {
+            let index = match peers.iter().position(|p| p.node_id.as_ref() == Some(member.key())) {
+                Some(index) => index,
+                None => {
+                    peers.push(ClusterPeer {
+                        node_id: Some(member.key().clone()),
+                        url: None,
+                        up: false,
+                        last_seen_ms: None,
+                        error: None,
+                    });
+                    peers.len() - 1
+                },
+            };
+            peers[index].up = self.is_up(&member);
+            peers[index].last_seen_ms = Some(member.last_seen.elapsed().as_millis() as u64);
+        }
+
+        ClusterStatus {
+            node_id: self.node_id.clone(),
+            peers,
+            sessions: self.sessions.len(),
+        }
+    }
+}
+
+/// Order in which reports win: any report beats no check yet, and the
+/// worse of two reports wins.
+fn severity(state: &HealthState) -> u8 {
+    match state {
+        HealthState::Unknown => 0,
+        HealthState::Healthy => 1,
+        HealthState::Degraded => 2,
+        HealthState::Unhealthy => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cluster(node_id: &str) -> Cluster {
+        let mut config = Config::default();
+        config.cluster.enabled = true;
+        config.cluster.node_id = Some(node_id.to_string());
+        Cluster::new(
+            Arc::new(ArcSwap::from_pointee(config)),
+            Arc::new(DashMap::new()),
+            HealthHistory::default(),
+            Arc::new(SessionTable::new()),
+        )
+    }
+
+    #[test]
+    fn test_gossip_merges_health_and_sessions() {
+        let a = cluster("a");
+        let b = cluster("b");
+        a.observed_health().insert("github".to_string(), HealthState::Healthy);
+        b.observed_health().insert("github".to_string(), HealthState::Unhealthy);
+        a.sessions.pin("client-1", &"github".to_string());
+
+        // One exchange from a to b updates both
+        let reply = b.receive(a.message(None)).unwrap();
+        a.accept(reply);
+        a.merge_health();
+
+        for node in [&a, &b] {
+            assert_eq!(
+                node.merged.get("github").map(|state| state.clone()),
+                Some(HealthState::Unhealthy)
+            );
+        }
+        assert_eq!(b.sessions.len(), 1);
+        assert_eq!(a.status().peers[0].node_id.as_deref(), Some("b"));
+        assert!(a.status().peers[0].up);
+
+        // Once b recovers it no longer holds github out on a
+        b.observed_health().insert("github".to_string(), HealthState::Healthy);
+        a.accept(b.message(None));
+        a.merge_health();
+        assert_eq!(
+            a.merged.get("github").map(|state| state.clone()),
+            Some(HealthState::Healthy)
+        );
+        assert!(a.imposed.is_empty());
+
+        assert!(a.receive(a.message(None)).is_err());
+    }
+
+    #[test]
+    fn test_down_peer_reports_are_ignored() {
+        let a = cluster("a");
+        a.observed_health().insert("github".to_string(), HealthState::Healthy);
+        a.accept(GossipMessage {
+            node_id: "b".to_string(),
+            health: HashMap::from([("github".to_string(), HealthState::Unhealthy)]),
+            sessions: Vec::new(),
+        });
+        a.members.get_mut("b").unwrap().last_seen -= a.settings.peer_timeout();
+        a.merge_health();
+
+        assert_eq!(
+            a.merged.get("github").map(|state| state.clone()),
+            Some(HealthState::Healthy)
+        );
+        assert!(!a.status().peers[0].up);
+    }
+}
@@ -0,0 +1,140 @@
+//! Single-flight request coalescing for identical in-flight backend calls.
+//!
+//! Time-window batching (see [`crate::batching`]) only merges requests that
+//! arrive inside the same window. Coalescing complements it: while a request
+//! for a given key is in flight, any identical request awaits the same future
+//! instead of issuing a second backend call.
+//!
+//! # How It Works
+//!
+//! 1. The first caller for a key (the leader) registers a shared future
+//! 2. Callers arriving while it is pending (followers) clone and await it
+//! 3. When the future resolves, the key is removed so the next call goes
+//!    to the backend again (caching is handled separately by the handler)
+
+use crate::error::Result;
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use futures::future::{BoxFuture, FutureExt, Shared};
+use std::future::Future;
+use std::sync::Arc;
+use tracing::debug;
+
+type SharedResult<T> = Shared<BoxFuture<'static, Result<T>>>;
+
+/// Deduplicates identical in-flight requests keyed by cache key.
+pub struct RequestCoalescer<T: Clone + Send + Sync + 'static> {
+    /// In-flight futures indexed by coalescing key
+    in_flight: Arc<DashMap<String, SharedResult<T>>>,
+}
+
+impl<T: Clone + Send + Sync + 'static> Default for RequestCoalescer<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> RequestCoalescer<T> {
+    /// Create an empty coalescer.
+    pub fn new() -> Self {
+        Self {
+            in_flight: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Run `fut` unless an identical request (same `key`) is already in flight,
+    /// in which case the pending result is awaited and shared.
+    ///
+    /// `method` is only used to label the coalescing metric.
+    pub async fn run<F>(&self, key: String, method: &str, fut: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>> + Send + 'static,
+    {
+        self.run_shared(key, method, fut).await.0
+    }
+
+    /// Like [`run`](Self::run), also reporting whether the result was shared
+    /// from another caller's in-flight request rather than produced by `fut`.
+    pub async fn run_shared<F>(&self, key: String, method: &str, fut: F) -> (Result<T>, bool)
+    where
+        F: Future<Output = Result<T>> + Send + 'static,
+    {
+        let (shared, follower) = match self.in_flight.entry(key.clone()) {
+            Entry::Occupied(entry) => {
+                debug!("Coalescing in-flight request: {}", key);
+                crate::metrics::COALESCED_REQUESTS_TOTAL.with_label_values(&[method]).inc();
+                (entry.get().clone(), true)
+            },
+            Entry::Vacant(entry) => {
+                let in_flight = self.in_flight.clone();
+                let leader_key = key.clone();
+                let shared = async move {
+                    let result = fut.await;
+                    // Release the slot before waking followers so that requests
+                    // issued after completion start a fresh backend call.
+                    in_flight.remove(&leader_key);
+                    result
+                }
+                .boxed()
+                .shared();
+                entry.insert(shared.clone());
+                (shared, false)
+            },
+        };
+
+        (shared.await, follower)
+    }
+
+    /// Number of distinct requests currently in flight (for monitoring).
+    pub fn in_flight_count(&self) -> usize {
+        self.in_flight.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_identical_requests_share_one_call() {
+        let coalescer = Arc::new(RequestCoalescer::<u32>::new());
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let mut handles = Vec::new();
+        for _ in 0..5 {
+            let coalescer = coalescer.clone();
+            let calls = calls.clone();
+            let call = async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                Ok(42)
+            };
+            handles.push(tokio::spawn(async move {
+                coalescer.run_shared("server1:tools/list".to_string(), "tools/list", call).await
+            }));
+        }
+
+        let mut followers = 0;
+        for handle in handles {
+            let (result, shared) = handle.await.unwrap();
+            assert_eq!(result.unwrap(), 42);
+            followers += usize::from(shared);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(followers, 4);
+        assert_eq!(coalescer.in_flight_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_distinct_keys_not_coalesced() {
+        let coalescer = RequestCoalescer::<u32>::new();
+        let a = coalescer.run("a".to_string(), "tools/list", async { Ok(1) });
+        let b = coalescer.run("b".to_string(), "tools/list", async { Ok(2) });
+
+        let (a, b) = tokio::join!(a, b);
+        assert_eq!(a.unwrap(), 1);
+        assert_eq!(b.unwrap(), 2);
+    }
+}
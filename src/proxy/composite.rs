@@ -0,0 +1,364 @@
+//! Composite tools (`proxy.composite_tools`).
+//!
+//! A composite tool is listed in tools/list like any backend tool, but a
+//! call to it is run by the proxy as a pipeline of backend tools/call
+//! requests. The arguments of each step, and the tool's output, are JSON
+//! templates filled in from what is known so far:
+//!
+//! ```yaml
+//! proxy:
+//!   composite_tools:
+//!     - name: issue_summary
+//!       steps:
+//!         - id: search
+//!           tool: search_issues
+//!           arguments: {query: "{{ $.arguments.query }}", limit: 1}
+//!         - id: issue
+//!           tool: get_issue
+//!           arguments: {number: "{{ $.steps.search.content[0].text.items[0].number }}"}
+//! ```
+//!
+//! A placeholder `{{ path }}` holds a path into `$.arguments` (the
+//! arguments the composite tool was called with) or `$.steps.<id>` (the
+//! tools/call result of an earlier step), made of `.field` and `[index]`
+//! segments. A string that is a single placeholder is replaced by the value
+//! itself, keeping its type; placeholders within other text are replaced by
+//! the value's text. A path that continues past a string holding JSON, such
+//! as a result's `content[0].text`, looks into that JSON.
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use serde_json::{json, Map, Value};
+
+use crate::config::{CompositeStepConfig, CompositeToolConfig};
+use crate::types::Tool;
+
+/// The tools/list definition of a composite tool.
+pub fn definition(tool: &CompositeToolConfig) -> Tool {
+    let description = tool.description.clone().unwrap_or_else(|| {
+        let steps: Vec<&str> = tool.steps.iter().map(|step| step.tool.as_str()).collect();
+        format!("Calls {}", steps.join(", then "))
+    });
+    Tool {
+        name: tool.name.clone(),
+        description: Some(description),
+        input_schema: tool.input_schema.clone(),
+        output_schema: None,
+    }
+}
+
+/// Check that a composite tool has steps with distinct IDs and that its
+/// templates only refer to its arguments and to earlier steps.
+pub fn check(tool: &CompositeToolConfig) -> Result<(), String> {
+    if tool.steps.is_empty() {
+        return Err("has no steps".to_string());
+    }
+    let mut earlier = HashSet::new();
+    for step in &tool.steps {
+        if step.id.is_empty() {
+            return Err(format!("step calling {} has no id", step.tool));
+        }
+        check_template(&Value::Object(step.arguments.clone()), &earlier)
+            .map_err(|e| format!("step {}: {}", step.id, e))?;
+        if !earlier.insert(step.id.as_str()) {
+            return Err(format!("step id {} is used twice", step.id));
+        }
+    }
+    if let Some(output) = &tool.output {
+        check_template(output, &earlier).map_err(|e| format!("output: {}", e))?;
+    }
+    Ok(())
+}
+
+/// A composite tool call in progress: its arguments and the results of the
+/// steps run so far.
+pub struct Pipeline {
+    context: Value,
+}
+
+impl Pipeline {
+    pub fn new(arguments: Value) -> Self {
+        Self {
+            context: json!({"arguments": arguments, "steps": {}}),
+        }
+    }
+
+    /// The arguments to call `step` with.
+    pub fn arguments(&self, step: &CompositeStepConfig) -> Result<Value, String> {
+        render(&Value::Object(step.arguments.clone()), &self.context)
+    }
+
+    /// Keep the tools/call result of `step` for the steps after it.
+    pub fn record(&mut self, step: &CompositeStepConfig, result: Value) {
+        self.context["steps"][step.id.as_str()] = result;
+    }
+
+    /// The tools/call result of the composite tool: its output template
+    /// filled in, or else the last step's result. An output that is not a
+    /// tools/call result is returned as text content.
+    pub fn output(self, tool: &CompositeToolConfig) -> Result<Value, String> {
+        let output = match &tool.output {
+            Some(template) => render(template, &self.context)?,
+            None => {
+                let last = tool.steps.last().map(|step| step.id.as_str()).unwrap_or_default();
+                self.context["steps"][last].clone()
+            },
+        };
+        if output.get("content").is_some_and(Value::is_array) {
+            return Ok(output);
+        }
+        Ok(json!({"content": [{"type": "text", "text": text_of(&output)}]}))
+    }
+}
+
+/// Fill in the placeholders of `template` from `context`.
+fn render(template: &Value, context: &Value) -> Result<Value, String> {
+    match template {
+        Value::String(text) => render_string(text, context),
+        Value::Array(items) => items.iter().map(|item| render(item, context)).collect(),
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(name, value)| Ok((name.clone(), render(value, context)?)))
+            .collect::<Result<Map<_, _>, String>>()
+            .map(Value::Object),
+        value => Ok(value.clone()),
+    }
+}
+
+fn render_string(text: &str, context: &Value) -> Result<Value, String> {
+    let parts = split_placeholders(text)?;
+    if let [Part::Path(path)] = parts.as_slice() {
+        return lookup(path, context);
+    }
+    let mut rendered = String::new();
+    for part in parts {
+        match part {
+            Part::Text(text) => rendered.push_str(text),
+            Part::Path(path) => rendered.push_str(&text_of(&lookup(path, context)?)),
+        }
+    }
+    Ok(Value::String(rendered))
+}
+
+enum Part<'a> {
+    Text(&'a str),
+    Path(&'a str),
+}
+
+/// Split a template string into literal text and placeholder paths.
+fn split_placeholders(mut text: &str) -> Result<Vec<Part<'_>>, String> {
+    let mut parts = Vec::new();
+    while let Some(start) = text.find("{{") {
+        let end = text[start..]
+            .find("}}")
+            .ok_or_else(|| format!("unclosed placeholder in {:?}", text))?;
+        if start > 0 {
+            parts.push(Part::Text(&text[..start]));
+        }
+        parts.push(Part::Path(text[start + 2..start + end].trim()));
+        text = &text[start + end + 2..];
+    }
+    if !text.is_empty() {
+        parts.push(Part::Text(text));
+    }
+    Ok(parts)
+}
+
+#[derive(Debug, PartialEq)]
+enum Segment<'a> {
+    Field(&'a str),
+    Index(usize),
+}
+
+impl Segment<'_> {
+    fn get<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        match *self {
+            Segment::Field(field) => value.get(field),
+            Segment::Index(index) => value.get(index),
+        }
+    }
+
+    fn get_mut<'v>(&self, value: &'v mut Value) -> Option<&'v mut Value> {
+        match *self {
+            Segment::Field(field) => value.get_mut(field),
+            Segment::Index(index) => value.get_mut(index),
+        }
+    }
+}
+
+/// Parse a `$.field[0].field` path.
+fn parse_path(path: &str) -> Result<Vec<Segment<'_>>, String> {
+    let invalid = || format!("invalid path {:?}", path);
+    let mut rest = path.strip_prefix('$').ok_or_else(invalid)?;
+    let mut segments = Vec::new();
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('.') {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            if end == 0 {
+                return Err(invalid());
+            }
+            segments.push(Segment::Field(&after[..end]));
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']').ok_or_else(invalid)?;
+            let index = after[..end].trim().parse().map_err(|_| invalid())?;
+            segments.push(Segment::Index(index));
+            rest = &after[end + 1..];
+        } else {
+            return Err(invalid());
+        }
+    }
+    Ok(segments)
+}
+
+/// The value at `path` in `context`, looking into strings holding JSON.
+fn lookup(path: &str, context: &Value) -> Result<Value, String> {
+    let mut value = Cow::Borrowed(context);
+    for segment in parse_path(path)? {
+        if let Value::String(text) = value.as_ref() {
+            let parsed = serde_json::from_str(text)
+                .map_err(|_| format!("{} passes through text that is not JSON", path))?;
+            value = Cow::Owned(parsed);
+        }
+        let next = match value {
+            Cow::Borrowed(value) => segment.get(value).map(Cow::Borrowed),
+            Cow::Owned(mut value) => segment.get_mut(&mut value).map(Value::take).map(Cow::Owned),
+        };
+        value = next.ok_or_else(|| format!("{} not found", path))?;
+    }
+    Ok(value.into_owned())
+}
+
+/// Check that every placeholder of `template` refers to the arguments or to
+/// one of the `steps` run before it.
+fn check_template(template: &Value, steps: &HashSet<&str>) -> Result<(), String> {
+    match template {
+        Value::String(text) => {
+            for part in split_placeholders(text)? {
+                let Part::Path(path) = part else {
+                    continue;
+                };
+                match parse_path(path)?.as_slice() {
+                    [Segment::Field("arguments"), ..] => {},
+                    [Segment::Field("steps"), Segment::Field(step), ..] if steps.contains(step) => {
+                    },
+                    _ => {
+                        return Err(format!(
+                            "{} refers to neither $.arguments nor an earlier step",
+                            path
+                        ))
+                    },
+                }
+            }
+            Ok(())
+        },
+        Value::Array(items) => items.iter().try_for_each(|item| check_template(item, steps)),
+        Value::Object(fields) => fields.values().try_for_each(|value| check_template(value, steps)),
+        _ => Ok(()),
+    }
+}
+
+/// A value as text: strings as they are, anything else as JSON.
+fn text_of(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(yaml: &str) -> CompositeToolConfig {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_pipeline_maps_results_into_arguments() {
+        let tool = tool(
+            r#"
+            name: issue_summary
+            steps:
+              - id: search
+                tool: search_issues
+                arguments: {query: "repo:{{ $.arguments.repo }} {{ $.arguments.query }}"}
+              - id: issue
+                tool: get_issue
+                arguments: {number: "{{ $.steps.search.content[0].text.items[0].number }}"}
+            output: {title: "{{ $.steps.issue.structuredContent.title }}"}
+            "#,
+        );
+        check(&tool).unwrap();
+
+        let mut pipeline = Pipeline::new(json!({"repo": "a/b", "query": "crash"}));
+        let arguments = pipeline.arguments(&tool.steps[0]).unwrap();
+        assert_eq!(arguments, json!({"query": "repo:a/b crash"}));
+        pipeline.record(
+            &tool.steps[0],
+            json!({"content": [{"type": "text", "text": r#"{"items": [{"number": 7}]}"#}]}),
+        );
+        // A single placeholder keeps the value's type
+        let arguments = pipeline.arguments(&tool.steps[1]).unwrap();
+        assert_eq!(arguments, json!({"number": 7}));
+        pipeline.record(
+            &tool.steps[1],
+            json!({"content": [], "structuredContent": {"title": "Crash on start"}}),
+        );
+        assert_eq!(
+            pipeline.output(&tool).unwrap(),
+            json!({"content": [{"type": "text", "text": r#"{"title":"Crash on start"}"#}]})
+        );
+    }
+
+    #[test]
+    fn test_missing_values_fail_the_step() {
+        let tool = tool(
+            r#"
+            name: lookup
+            steps:
+              - {id: get, tool: get, arguments: {id: "{{ $.arguments.id }}"}}
+            "#,
+        );
+        let pipeline = Pipeline::new(json!({}));
+        let error = pipeline.arguments(&tool.steps[0]).unwrap_err();
+        assert!(error.contains("$.arguments.id not found"), "{}", error);
+
+        // Without an output template the last step's result is returned
+        let mut pipeline = Pipeline::new(json!({"id": 1}));
+        let result = json!({"content": [{"type": "text", "text": "found"}]});
+        pipeline.record(&tool.steps[0], result.clone());
+        assert_eq!(pipeline.output(&tool).unwrap(), result);
+    }
+
+    #[test]
+    fn test_check_rejects_bad_references() {
+        let later = tool(
+            r#"
+            name: backwards
+            steps:
+              - {id: a, tool: a, arguments: {x: "{{ $.steps.b.content }}"}}
+              - {id: b, tool: b}
+            "#,
+        );
+        assert!(check(&later).unwrap_err().contains("earlier step"));
+
+        let duplicate = tool(
+            r#"
+            name: twice
+            steps: [{id: a, tool: a}, {id: a, tool: b}]
+            "#,
+        );
+        assert!(check(&duplicate).unwrap_err().contains("used twice"));
+
+        let malformed = tool(
+            r#"
+            name: malformed
+            steps: [{id: a, tool: a, arguments: {x: "{{ arguments.x }}"}}]
+            "#,
+        );
+        assert!(check(&malformed).unwrap_err().contains("invalid path"));
+        assert!(check(&tool("{name: empty, steps: []}")).is_err());
+    }
+}
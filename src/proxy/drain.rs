@@ -0,0 +1,140 @@
+//! Connection draining for graceful shutdown.
+//!
+//! Every MCP request holds an [`InFlightGuard`] while it is being handled.
+//! Once draining starts, new requests are refused with 503 and shutdown
+//! waits (up to the configured drain timeout) for the remaining guards to be
+//! dropped before backends are torn down.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+use tokio::sync::Notify;
+
+/// Tracks in-flight requests and whether the server is draining.
+#[derive(Debug, Default)]
+pub struct DrainState {
+    draining: AtomicBool,
+    in_flight: AtomicUsize,
+    idle: Notify,
+}
+
+impl DrainState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop admitting new requests.
+    pub fn begin(&self) {
+        self.draining.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether draining has started.
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Number of requests currently being handled.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Admit a request, or return `None` if draining has started.
+    pub fn enter(self: &Arc<Self>) -> Option<InFlightGuard> {
+        if self.is_draining() {
+            return None;
+        }
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+
+        // Re-check so a request racing with `begin` cannot slip past `wait_idle`
+        if self.is_draining() {
+            self.leave();
+            return None;
+        }
+        Some(InFlightGuard(self.clone()))
+    }
+
+    /// Wait until no requests are in flight, or `timeout` elapses.
+    ///
+    /// Returns `true` if the server went idle in time.
+    pub async fn wait_idle(&self, timeout: Duration) -> bool {
+        let wait = async {
+            loop {
+                let notified = self.idle.notified();
+                if self.in_flight() == 0 {
+                    return;
+                }
+                notified.await;
+            }
+        };
+        tokio::time::timeout(timeout, wait).await.is_ok()
+    }
+
+    fn leave(&self) {
+        if self.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.idle.notify_waiters();
+        }
+    }
+}
+
+/// Marks one request as in flight until dropped.
+#[derive(Debug)]
+pub struct InFlightGuard(Arc<DrainState>);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0.leave();
+    }
+}
+
+/// Middleware that refuses requests while draining and tracks the rest.
+pub async fn track_in_flight(
+    State(drain): State<Arc<DrainState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match drain.enter() {
+        Some(_guard) => next.run(request).await,
+        None => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [("connection", "close")],
+            Json(json!({
+                "jsonrpc": "2.0",
+                "id": null,
+                "error": {"code": -32000, "message": "Server is shutting down"}
+            })),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_drain_waits_for_in_flight() {
+        let drain = Arc::new(DrainState::new());
+        let guard = drain.enter().unwrap();
+        assert_eq!(drain.in_flight(), 1);
+
+        drain.begin();
+        assert!(drain.enter().is_none());
+        assert!(!drain.wait_idle(Duration::from_millis(20)).await);
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            drop(guard);
+        });
+        assert!(drain.wait_idle(Duration::from_secs(1)).await);
+        assert_eq!(drain.in_flight(), 0);
+    }
+}
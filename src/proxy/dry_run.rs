@@ -0,0 +1,320 @@
+//! Dry-run mode (`only1mcp start --dry-run`).
+//!
+//! Instead of forwarding to backends, the proxy answers from a fixture
+//! catalog: `tools/list`, `resources/list` and `prompts/list` return the
+//! fixture lists, and `tools/call` returns the canned reply whose arguments
+//! match the call. Fixtures are either written by hand (YAML or JSON) or
+//! taken from a recording (`observability.recording`), so client
+//! integrations can be developed and tested in CI without any real backend.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+use crate::proxy::capabilities::negotiate_protocol_version;
+use crate::proxy::recording::read_recording;
+use crate::types::{McpRequest, Prompt, Resource, Tool};
+
+/// Catalog and canned replies served in dry-run mode.
+///
+/// ```yaml
+/// tools:
+///   - name: search_repos
+///     inputSchema: {type: object}
+///     responses:
+///       - arguments: {query: rust}   # matched exactly
+///         result: {content: [{type: text, text: "3 repositories"}]}
+///       - result: {content: [{type: text, text: "no repositories"}]}
+/// resources: []
+/// prompts: []
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Fixtures {
+    #[serde(default)]
+    pub tools: Vec<FixtureTool>,
+    #[serde(default)]
+    pub resources: Vec<Resource>,
+    #[serde(default)]
+    pub prompts: Vec<Prompt>,
+}
+
+/// A tool and the replies its calls get.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixtureTool {
+    #[serde(flatten)]
+    pub tool: Tool,
+    /// Tried in order; the first whose `arguments` match answers the call
+    #[serde(default)]
+    pub responses: Vec<CannedResponse>,
+    /// Delay before each call is answered (`only1mcp mock-server` only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// Share of calls, 0.0 to 1.0, failed with an error
+    /// (`only1mcp mock-server` only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fail_rate: Option<f64>,
+}
+
+/// The reply to calls with given arguments.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CannedResponse {
+    /// Arguments the call must have; any call matches when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<Value>,
+    /// JSON-RPC `result` of the reply
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    /// JSON-RPC `error` of the reply, used when there is no `result`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<Value>,
+}
+
+impl Fixtures {
+    /// Load fixtures from `path`: a recording when it ends in `.jsonl`,
+    /// otherwise a YAML or JSON fixture file.
+    pub fn load(path: &Path) -> Result<Self> {
+        if path.extension().is_some_and(|extension| extension == "jsonl") {
+            return Self::from_recording(path);
+        }
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            Error::Config(format!("Failed to read fixtures {}: {}", path.display(), e))
+        })?;
+        serde_yaml::from_str(&content)
+            .map_err(|e| Error::Config(format!("Invalid fixtures {}: {}", path.display(), e)))
+    }
+
+    /// Build fixtures from a recorded session: the last recorded lists, and
+    /// every recorded tool call as a canned reply for its arguments.
+    pub fn from_recording(path: &Path) -> Result<Self> {
+        let mut fixtures = Self::default();
+        let mut calls = Vec::new();
+        for exchange in read_recording(path)? {
+            let Some(response) = exchange.response else {
+                continue;
+            };
+            let result = response.get("result").filter(|result| !result.is_null());
+            match exchange.method.as_str() {
+                "tools/list" => {
+                    if let Some(tools) = list(result, "tools") {
+                        fixtures.tools = tools
+                            .into_iter()
+                            .map(|tool| FixtureTool {
+                                tool,
+                                responses: Vec::new(),
+                                latency_ms: None,
+                                fail_rate: None,
+                            })
+                            .collect();
+                    }
+                },
+                "resources/list" => {
+                    if let Some(resources) = list(result, "resources") {
+                        fixtures.resources = resources;
+                    }
+                },
+                "prompts/list" => {
+                    if let Some(prompts) = list(result, "prompts") {
+                        fixtures.prompts = prompts;
+                    }
+                },
+                "tools/call" => {
+                    let params = &exchange.request["params"];
+                    let Some(name) = params.get("name").and_then(Value::as_str) else {
+                        continue;
+                    };
+                    calls.push((
+                        name.to_string(),
+                        CannedResponse {
+                            arguments: Some(params.get("arguments").cloned().unwrap_or(json!({}))),
+                            result: result.cloned(),
+                            error: response.get("error").cloned(),
+                        },
+                    ));
+                },
+                _ => {},
+            }
+        }
+
+        // Calls to tools missing from the recorded lists are still answered
+        for (name, response) in calls {
+            match fixtures.tools.iter_mut().find(|fixture| fixture.tool.name == name) {
+                Some(fixture) => fixture.responses.push(response),
+                None => fixtures.tools.push(FixtureTool {
+                    tool: Tool {
+                        name,
+                        description: None,
+                        input_schema: json!({"type": "object"}),
+                        output_schema: None,
+                    },
+                    responses: vec![response],
+                    latency_ms: None,
+                    fail_rate: None,
+                }),
+            }
+        }
+        Ok(fixtures)
+    }
+
+    /// Answer `request` from the fixtures.
+    pub fn respond(&self, request: &McpRequest) -> Value {
+        let params = request.params();
+        let outcome = match request.method().as_str() {
+            "initialize" => Ok(json!({
+                "protocolVersion": negotiate_protocol_version(
+                    params.get("protocolVersion").and_then(Value::as_str)
+                ),
+                "capabilities": {"tools": {}, "resources": {}, "prompts": {}},
+                "serverInfo": {
+                    "name": "Only1MCP (dry run)",
+                    "version": env!("CARGO_PKG_VERSION")
+                }
+            })),
+            "ping" => Ok(json!({})),
+            "tools/list" => {
+                let tools: Vec<&Tool> = self.tools.iter().map(|fixture| &fixture.tool).collect();
+                Ok(json!({"tools": tools}))
+            },
+            "resources/list" => Ok(json!({"resources": self.resources})),
+            "prompts/list" => Ok(json!({"prompts": self.prompts})),
+            "tools/call" => self.call(&params),
+            method if method.starts_with("notifications/") => Ok(json!({})),
+            method => Err(json!({
+                "code": -32601,
+                "message": format!("{} is not available in dry-run mode", method)
+            })),
+        };
+
+        match outcome {
+            Ok(result) => json!({"jsonrpc": "2.0", "id": request.id(), "result": result}),
+            Err(error) => json!({"jsonrpc": "2.0", "id": request.id(), "error": error}),
+        }
+    }
+
+    fn call(&self, params: &Value) -> std::result::Result<Value, Value> {
+        let name = params.get("name").and_then(Value::as_str).unwrap_or_default();
+        let Some(fixture) = self.tools.iter().find(|fixture| fixture.tool.name == name) else {
+            return Err(json!({"code": -32602, "message": format!("Unknown tool: {}", name)}));
+        };
+
+        let arguments = params.get("arguments").cloned().unwrap_or(json!({}));
+        let canned = fixture.responses.iter().find(|canned| {
+            canned.arguments.as_ref().map_or(true, |expected| *expected == arguments)
+        });
+        match canned {
+            Some(CannedResponse {
+                result: Some(result),
+                ..
+            }) => Ok(result.clone()),
+            Some(CannedResponse {
+                error: Some(error), ..
+            }) => Err(error.clone()),
+            _ => Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": format!("No canned response for {} with these arguments", name)
+                }],
+                "isError": true
+            })),
+        }
+    }
+}
+
+/// Parse the `field` list of a list result.
+fn list<T: serde::de::DeserializeOwned>(result: Option<&Value>, field: &str) -> Option<Vec<T>> {
+    result
+        .and_then(|result| result.get(field))
+        .and_then(|items| serde_json::from_value(items.clone()).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(fixtures: &Fixtures, arguments: Value) -> Value {
+        let request = McpRequest::new(
+            "tools/call",
+            json!({"name": "search_repos", "arguments": arguments}),
+            Some(json!(1)),
+        );
+        fixtures.respond(&request)
+    }
+
+    #[test]
+    fn test_canned_responses_match_arguments() {
+        let fixtures: Fixtures = serde_yaml::from_str(
+            r#"
+tools:
+  - name: search_repos
+    inputSchema: {type: object}
+    responses:
+      - arguments: {query: rust}
+        result: {content: [{type: text, text: "3 repositories"}]}
+      - arguments: {query: private}
+        error: {code: -32000, message: forbidden}
+"#,
+        )
+        .unwrap();
+
+        let list = fixtures.respond(&McpRequest::new("tools/list", json!({}), Some(json!(1))));
+        assert_eq!(list["result"]["tools"][0]["name"], "search_repos");
+        assert!(list["result"]["tools"][0].get("responses").is_none());
+
+        let reply = call(&fixtures, json!({"query": "rust"}));
+        assert_eq!(reply["result"]["content"][0]["text"], "3 repositories");
+        assert_eq!(
+            call(&fixtures, json!({"query": "private"}))["error"]["message"],
+            "forbidden"
+        );
+        assert_eq!(
+            call(&fixtures, json!({"query": "go"}))["result"]["isError"],
+            true
+        );
+    }
+
+    #[test]
+    fn test_fixtures_from_recording() {
+        use crate::config::RecordingConfig;
+        use crate::proxy::recording::{Exchange, Recorder};
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = RecordingConfig {
+            enabled: true,
+            path: dir.path().join("session.jsonl"),
+            ..Default::default()
+        };
+        let recorder = Recorder::new();
+        for (method, params, result) in [
+            (
+                "tools/list",
+                json!({}),
+                json!({"tools": [{"name": "search_repos", "inputSchema": {"type": "object"}}]}),
+            ),
+            (
+                "tools/call",
+                json!({"name": "search_repos", "arguments": {"query": "rust"}}),
+                json!({"content": [{"type": "text", "text": "3 repositories"}]}),
+            ),
+        ] {
+            let request = json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params});
+            recorder.record(
+                &config,
+                Exchange {
+                    at: chrono::Utc::now(),
+                    method: method.to_string(),
+                    request,
+                    response: Some(json!({"jsonrpc": "2.0", "id": 1, "result": result})),
+                    latency_ms: 1.0,
+                    client: None,
+                    session: None,
+                },
+            );
+        }
+
+        let fixtures = Fixtures::load(&config.path).unwrap();
+        assert_eq!(fixtures.tools.len(), 1);
+        let reply = call(&fixtures, json!({"query": "rust"}));
+        assert_eq!(reply["result"]["content"][0]["text"], "3 repositories");
+    }
+}
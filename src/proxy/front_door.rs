@@ -0,0 +1,71 @@
+//! Front-door mode (`proxy.routing.front_door`).
+//!
+//! A front door dispatches over other Only1MCP instances configured as
+//! `http` servers; the router shards clients between them by consistent
+//! hash of the client id. Requests to an instance carry the caller's id in
+//! the `client_id_header`, set from a task-local scope around dispatch, so
+//! the instance sees the original client rather than the front door, and
+//! [`FORWARDED_HEADER`], so an instance that is itself a front door refuses
+//! them instead of dispatching in a loop.
+
+use std::collections::HashMap;
+use std::future::Future;
+
+use crate::config::RoutingAlgorithmConfig;
+
+/// Header marking a request forwarded by a front door.
+pub const FORWARDED_HEADER: &str = "x-only1mcp-front-door";
+
+tokio::task_local! {
+    static CLIENT: Option<String>;
+}
+
+/// Run `future` on behalf of `client`, whose id is forwarded to instances.
+pub async fn forwarding_client<F: Future>(client: Option<String>, future: F) -> F::Output {
+    CLIENT.scope(client, future).await
+}
+
+/// Add the front-door headers to a request for `server_id` if it is an
+/// instance. Outside [`forwarding_client`], e.g. in background refreshes,
+/// only [`FORWARDED_HEADER`] is added.
+pub fn instance_headers(
+    routing: &RoutingAlgorithmConfig,
+    server_id: &str,
+    mut headers: HashMap<String, String>,
+) -> HashMap<String, String> {
+    if !routing.front_door.is_instance(server_id) {
+        return headers;
+    }
+    headers.insert(FORWARDED_HEADER.to_string(), "1".to_string());
+    if let Some(client) = CLIENT.try_with(|client| client.clone()).ok().flatten() {
+        headers.insert(routing.client_id_header.clone(), client);
+    }
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_instance_headers_forward_client() {
+        let routing = RoutingAlgorithmConfig {
+            front_door: serde_yaml::from_str("{enabled: true, instances: [shard-a]}").unwrap(),
+            ..Default::default()
+        };
+
+        let headers = forwarding_client(Some("alice".into()), async {
+            (
+                instance_headers(&routing, "shard-a", HashMap::new()),
+                instance_headers(&routing, "local", HashMap::new()),
+            )
+        })
+        .await;
+        assert_eq!(headers.0[&routing.client_id_header], "alice");
+        assert_eq!(headers.0[FORWARDED_HEADER], "1");
+        assert!(headers.1.is_empty());
+
+        let background = instance_headers(&routing, "shard-a", HashMap::new());
+        assert_eq!(background.len(), 1);
+    }
+}
@@ -3,158 +3,1150 @@
 //! Handles JSON-RPC requests, tool discovery, resource management,
 //! and WebSocket upgrades for the MCP protocol.
 
+use crate::cache::{ResponseCache, TokenSources};
+use crate::config::{CatalogMode, CompositeToolConfig, Config, RetryConfig};
 use crate::error::{Error, ProxyError, Result};
+use crate::health::checker::HealthState;
+use crate::proxy::aliases;
+use crate::proxy::capabilities::{merge_capabilities, negotiate_protocol_version};
+use crate::proxy::catalog::catalog_version;
+use crate::proxy::chaos::{Fault, Injection};
+use crate::proxy::composite::{self, Pipeline};
+use crate::proxy::front_door::{forwarding_client, instance_headers, FORWARDED_HEADER};
+use crate::proxy::history::{note_backend, tracking_backends};
+use crate::proxy::listener::ClientPrincipal;
+use crate::proxy::pagination::{fill_page, PageCursor};
+use crate::proxy::recording::Exchange;
 use crate::proxy::router::RequestRouter;
+use crate::proxy::sanitize::{check_output, sanitize_result};
+use crate::proxy::scripts::{Hook, ScriptError, ToolCall};
 use crate::proxy::server::AppState;
-use crate::types::{McpRequest, Prompt, Resource, Tool};
+use crate::proxy::slim::slim_tools;
+use crate::proxy::transform::{
+    backend_headers, rewrites_results, transform_request, transform_response,
+};
+use crate::proxy::truncate::truncate_result;
+use crate::routing::tool_matcher::glob_match;
+use crate::transport::http::BackendRequestOptions;
+use crate::transport::stdio::{ServerCapabilities, StdioConfig};
+use crate::transport::streaming::{metered_body, BackendReply, StreamedSize};
+use crate::types::{
+    McpRequest, McpResponse, Prompt, RequestRecord, Resource, StartupState, StartupStatus, Tool,
+};
 use axum::{
+    body::{Body, Bytes},
     extract::{ws::WebSocketUpgrade, State},
-    response::Response,
-    Json,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
 };
+use bytes::BytesMut;
+use futures::StreamExt;
+use serde::{de::DeserializeOwned, Serialize};
 use serde_json::{json, Value};
+use std::future::Future;
 use std::time::{Duration, Instant};
 use tracing::{debug, error, info, instrument, warn};
 
 /// Handle generic JSON-RPC requests.
-#[instrument(skip(state, payload))]
+///
+/// Accepts either a single request object or a JSON-RPC 2.0 batch array.
+/// Bodies over `proxy.limits.max_request_bytes` are rejected with 413.
+#[instrument(skip(state, headers, principal, body))]
 pub async fn handle_jsonrpc_request(
     State(state): State<AppState>,
-    Json(payload): Json<Value>,
-) -> std::result::Result<Json<Value>, ProxyError> {
+    headers: HeaderMap,
+    principal: Option<Extension<ClientPrincipal>>,
+    body: Body,
+) -> std::result::Result<Response, ProxyError> {
+    let limit = state.config.load().proxy.limits.max_request_bytes;
+    let body = read_request_body(&headers, body, limit).await?;
+    let payload: Value =
+        crate::json::from_slice(&body).map_err(|e| ProxyError::InvalidRequest(e.to_string()))?;
+    let caller = Caller::new(&state, &headers, principal.map(|Extension(p)| p));
+
+    // A front door never serves another, or one listing itself would loop
+    if caller.forwarded && state.config.load().proxy.routing.front_door.enabled {
+        return Err(ProxyError::InvalidRequest(
+            "Request was already dispatched by a front door".into(),
+        ));
+    }
+    let client = caller.client_id.clone();
+    // Boxed: inline, the whole request future overflows the stack of debug
+    // builds
+    let response = Box::pin(respond(state, headers, payload, caller));
+    forwarding_client(client, response).await
+}
+
+/// Answer a parsed JSON-RPC request or batch.
+async fn respond(
+    state: AppState,
+    headers: HeaderMap,
+    payload: Value,
+    caller: Caller,
+) -> std::result::Result<Response, ProxyError> {
+    match payload {
+        Value::Array(entries) => Ok(handle_batch(state, entries, caller).await),
+        // Notifications get no reply, matching batch handling
+        payload if payload.is_object() && payload.get("id").is_none() => {
+            dispatch_request(state, payload, caller).await?;
+            Ok(StatusCode::ACCEPTED.into_response())
+        },
+        payload if streams_tool_call(&state, &payload) => {
+            let log = RequestLog::begin(&caller, &payload);
+            let request: McpRequest = serde_json::from_value(payload)
+                .map_err(|e| ProxyError::InvalidRequest(e.to_string()))?;
+            let admission = state.config.load().proxy.admission.clone();
+            let response = match state.admission.admit(&admission).await {
+                Ok(_admitted) => {
+                    handle_tools_call_streaming(state.clone(), request, caller, log.clone()).await
+                },
+                Err(e) => Err(e),
+            };
+            // Replies are recorded once their body has been forwarded
+            if let Err(e) = &response {
+                log.finish(&state, Vec::new(), "error", Some(e.to_string()));
+            }
+            response
+        },
+        payload if is_polled_list(&payload) => {
+            let method = payload["method"].as_str().unwrap_or_default().to_string();
+            let response = dispatch_request(state.clone(), payload, caller).await?;
+            Ok(conditional_list_response(
+                &state, &headers, &method, &response,
+            ))
+        },
+        payload => Ok(json_response(
+            &dispatch_request(state, payload, caller).await?,
+        )),
+    }
+}
+
+/// Whether `payload` is a list request clients commonly poll, answered
+/// with HTTP caching headers (see [`conditional_list_response`]).
+fn is_polled_list(payload: &Value) -> bool {
+    matches!(
+        payload.get("method").and_then(Value::as_str),
+        Some("tools/list" | "resources/list")
+    )
+}
+
+/// Whether `payload` is a tools/call whose result may be streamed
+/// (`proxy.streaming`). Batched calls and composite tools are always
+/// buffered, and so are all calls while results may be truncated,
+/// sanitized, redacted, rewritten by a script or are being recorded.
+fn streams_tool_call(state: &AppState, payload: &Value) -> bool {
+    let config = state.config.load();
+    state.dry_run.is_none()
+        && config.proxy.streaming.enabled
+        && !config.context_optimization.truncation.enabled
+        && !config.observability.recording.enabled
+        && !config.proxy.chaos.enabled
+        && !config.proxy.sanitization.enabled
+        && !(config.proxy.redaction.enabled && config.proxy.redaction.results)
+        && config.proxy.scripts.on_response.is_none()
+        && payload.get("method").and_then(Value::as_str) == Some("tools/call")
+        && composite_tool(&config, payload.pointer("/params/name")).is_none()
+}
+
+/// Read a request body, rejecting it once it passes `limit` bytes.
+async fn read_request_body(
+    headers: &HeaderMap,
+    body: Body,
+    limit: usize,
+) -> std::result::Result<Bytes, ProxyError> {
+    let too_large =
+        || ProxyError::PayloadTooLarge(format!("Request body exceeds {} byte limit", limit));
+    let declared = headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+    if declared.is_some_and(|length| length > limit as u64) {
+        return Err(too_large());
+    }
+
+    let mut chunks = body.into_data_stream();
+    let mut body = BytesMut::new();
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk.map_err(|e| {
+            ProxyError::InvalidRequest(format!("Failed to read request body: {}", e))
+        })?;
+        if body.len() + chunk.len() > limit {
+            return Err(too_large());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body.freeze())
+}
+
+/// Who sent a request, as far as the proxy knows.
+#[derive(Debug, Clone, Default)]
+struct Caller {
+    /// Sticky session identity: the configured client id header, falling
+    /// back to the authenticated principal
+    client_id: Option<String>,
+    /// Set when the listener requires credentials
+    principal: Option<ClientPrincipal>,
+    /// How much of each tool definition tools/list returns
+    /// (`context_optimization.slim`)
+    catalog_mode: CatalogMode,
+    /// The client's `Mcp-Session-Id`, kept in the request history
+    session_id: Option<String>,
+    /// Sent by a front door (`proxy.routing.front_door`)
+    forwarded: bool,
+}
+
+impl Caller {
+    fn new(state: &AppState, headers: &HeaderMap, principal: Option<ClientPrincipal>) -> Self {
+        let config = state.config.load();
+        let client_id = headers
+            .get(config.proxy.routing.client_id_header.as_str())
+            .and_then(|value| value.to_str().ok())
+            .filter(|value| !value.is_empty())
+            .map(|value| value.to_string())
+            .or_else(|| principal.as_ref().map(|p| p.id.clone()));
+
+        // The header wins over the client's configured mode
+        let slim = &config.context_optimization.slim;
+        let catalog_mode = headers
+            .get(slim.header.as_str())
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| match value.parse() {
+                Ok(mode) => Some(mode),
+                Err(e) => {
+                    debug!("Ignoring {} header: {}", slim.header, e);
+                    None
+                },
+            })
+            .or_else(|| client_id.as_ref().and_then(|id| slim.clients.get(id).copied()))
+            .unwrap_or(slim.mode);
+
+        let session_id = headers
+            .get("mcp-session-id")
+            .and_then(|value| value.to_str().ok())
+            .filter(|value| !value.is_empty())
+            .map(|value| value.to_string());
+
+        Self {
+            client_id,
+            principal,
+            catalog_mode,
+            session_id,
+            forwarded: headers.contains_key(FORWARDED_HEADER),
+        }
+    }
+
+    /// Roles granted to the caller, for `auth.policies`.
+    fn roles(&self) -> &[String] {
+        self.principal.as_ref().map(|p| p.roles.as_slice()).unwrap_or_default()
+    }
+}
+
+/// A request being handled, recorded in the request metrics and history
+/// (`observability.request_history`) once it has been answered.
+#[derive(Debug, Clone)]
+struct RequestLog {
+    method: String,
+    tool: Option<String>,
+    client: Option<String>,
+    session: Option<String>,
+    at: chrono::DateTime<chrono::Utc>,
+    start: Instant,
+}
+
+impl RequestLog {
+    fn begin(caller: &Caller, payload: &Value) -> Self {
+        let method = payload.get("method").and_then(Value::as_str).unwrap_or_default();
+        let tool = (method == "tools/call")
+            .then(|| payload.pointer("/params/name").and_then(Value::as_str))
+            .flatten();
+        Self {
+            method: method.to_string(),
+            tool: tool.map(str::to_string),
+            client: caller.client_id.clone(),
+            session: caller.session_id.clone(),
+            at: chrono::Utc::now(),
+            start: Instant::now(),
+        }
+    }
+
+    /// Record `request` and the reply it got for `only1mcp replay`
+    /// (`observability.recording`).
+    fn record_exchange(
+        &self,
+        state: &AppState,
+        request: Value,
+        response: &std::result::Result<Value, ProxyError>,
+    ) {
+        // Notifications get no reply
+        let response = request.get("id").map(|id| match response {
+            Ok(response) => response.clone(),
+            Err(e) => e.to_jsonrpc(id.clone()),
+        });
+        let exchange = Exchange {
+            at: self.at,
+            method: self.method.clone(),
+            request,
+            response,
+            latency_ms: self.start.elapsed().as_secs_f64() * 1000.0,
+            client: self.client.clone(),
+            session: self.session.clone(),
+        };
+        let config = state.config.load();
+        state.recorder.record(&config.observability.recording, exchange);
+    }
+
+    /// Record the request as answered by `servers` with `status`.
+    fn finish(
+        self,
+        state: &AppState,
+        servers: Vec<String>,
+        status: &'static str,
+        error: Option<String>,
+    ) {
+        let elapsed = self.start.elapsed();
+        crate::metrics::record_mcp_request(
+            "proxy",
+            crate::metrics::method_label(&self.method),
+            status,
+            elapsed,
+        );
+        let capacity = state.config.load().observability.request_history.capacity;
+        state.history.record(
+            capacity,
+            RequestRecord {
+                id: 0,
+                at: self.at,
+                method: self.method,
+                tool: self.tool,
+                servers,
+                status: status.to_string(),
+                error,
+                latency_ms: elapsed.as_secs_f64() * 1000.0,
+                client: self.client,
+                session: self.session,
+            },
+        );
+    }
+}
+
+/// Build a router that shares the proxy's sticky session table, live
+/// backend connection counts and failover group state.
+fn request_router(state: &AppState) -> RequestRouter {
+    RequestRouter::new(state.config.load().proxy.routing.clone())
+        .with_sessions(state.sessions.clone())
+        .with_connections(state.connections.clone())
+        .with_failover(state.failover.clone())
+}
+
+/// Serialize a JSON-RPC reply through the pooled buffer into a response body.
+fn json_response(value: &Value) -> Response {
+    match crate::json::to_bytes(value) {
+        Ok(body) => ([(header::CONTENT_TYPE, "application/json")], body).into_response(),
+        Err(e) => {
+            error!("Failed to serialize response: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        },
+    }
+}
+
+/// Serialize a list response with a strong `ETag` over its `result`, so
+/// the JSON-RPC id of each poll does not change it, and a `Cache-Control`
+/// max-age of the list's cache TTL. A request whose `If-None-Match` names
+/// the current tag gets `304 Not Modified` without a body.
+///
+/// Errors and degraded lists, which may change on the next poll even if
+/// the backends do not, are sent with `Cache-Control: no-cache`.
+fn conditional_list_response(
+    state: &AppState,
+    headers: &HeaderMap,
+    method: &str,
+    response: &Value,
+) -> Response {
+    let Some(result) = response.get("result") else {
+        return json_response(response);
+    };
+    let Ok(serialized) = crate::json::to_bytes(result) else {
+        return json_response(response);
+    };
+    let etag = format!("\"{}\"", &blake3::hash(&serialized).to_hex()[..32]);
+
+    let cache = state.cache.config();
+    let degraded = result.pointer("/_meta/degraded").is_some();
+    let cache_control = if cache.enabled && !degraded {
+        format!("private, max-age={}", cache.ttl_for(method).as_secs())
+    } else {
+        "no-cache".to_string()
+    };
+
+    let not_modified = headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim().trim_start_matches("W/"))
+        .any(|tag| tag == etag || tag == "*");
+    let mut reply = if not_modified {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        json_response(response)
+    };
+    for (name, value) in [(header::ETAG, etag), (header::CACHE_CONTROL, cache_control)] {
+        if let Ok(value) = value.parse() {
+            reply.headers_mut().insert(name, value);
+        }
+    }
+    reply
+}
+
+/// Handle a JSON-RPC batch by dispatching every entry concurrently.
+///
+/// Responses are returned in request order. Notifications (entries without an
+/// `id`) are executed but produce no response entry; a batch made only of
+/// notifications returns `202 Accepted` with an empty body.
+async fn handle_batch(state: AppState, entries: Vec<Value>, caller: Caller) -> Response {
+    if entries.is_empty() {
+        let error = ProxyError::InvalidRequest("Empty batch".into());
+        return (error.status_code(), Json(error.to_jsonrpc(Value::Null))).into_response();
+    }
+
+    debug!("Dispatching JSON-RPC batch of {} requests", entries.len());
+
+    let tasks = entries.into_iter().map(|entry| {
+        let state = state.clone();
+        let caller = caller.clone();
+        async move {
+            let is_notification = entry.is_object() && entry.get("id").is_none();
+            let id = entry.get("id").cloned().unwrap_or(Value::Null);
+
+            let result = dispatch_request(state, entry, caller).await;
+            if is_notification {
+                if let Err(e) = result {
+                    warn!("Notification in batch failed: {}", e);
+                }
+                return None;
+            }
+
+            Some(result.unwrap_or_else(|e| e.to_jsonrpc(id)))
+        }
+    });
+
+    let responses: Vec<Value> =
+        futures::future::join_all(tasks).await.into_iter().flatten().collect();
+
+    if responses.is_empty() {
+        StatusCode::ACCEPTED.into_response()
+    } else {
+        json_response(&Value::Array(responses))
+    }
+}
+
+/// Parse a single JSON-RPC request and route it to its method handler.
+async fn dispatch_request(
+    state: AppState,
+    payload: Value,
+    caller: Caller,
+) -> std::result::Result<Value, ProxyError> {
+    let log = RequestLog::begin(&caller, &payload);
+    let recorded = state.config.load().observability.recording.enabled.then(|| payload.clone());
+    let admission = state.config.load().proxy.admission.clone();
+    let (response, servers) = tracking_backends(async {
+        let _admitted = state.admission.admit(&admission).await?;
+        dispatch_method(state.clone(), payload, caller).await
+    })
+    .await;
+    if let Some(request) = recorded {
+        log.record_exchange(&state, request, &response);
+    }
+    let (status, error) = match &response {
+        Ok(response) => (
+            crate::metrics::response_status(response),
+            reply_error(response),
+        ),
+        Err(e) => ("error", Some(e.to_string())),
+    };
+    log.finish(&state, servers, status, error);
+    response
+}
+
+/// Message of a JSON-RPC error reply.
+fn reply_error(response: &Value) -> Option<String> {
+    response.pointer("/error/message").and_then(Value::as_str).map(str::to_string)
+}
+
+/// Handle one request by method, without recording it.
+async fn dispatch_method(
+    state: AppState,
+    payload: Value,
+    caller: Caller,
+) -> std::result::Result<Value, ProxyError> {
     // Parse request
     let request: McpRequest =
         serde_json::from_value(payload).map_err(|e| ProxyError::InvalidRequest(e.to_string()))?;
 
+    if let Some(fixtures) = &state.dry_run {
+        return Ok(fixtures.respond(&request));
+    }
+
     // Route to appropriate handler based on method
     let response = match request.method().as_str() {
-        "tools/list" => handle_tools_list_impl(state, request).await?,
-        "tools/call" => handle_tools_call_impl(state, request).await?,
+        "initialize" => handle_initialize_impl(state, request).await?,
+        "notifications/initialized" => {
+            debug!("Client completed initialization");
+            json!({"jsonrpc": "2.0", "id": request.id(), "result": {}})
+        },
+        "tools/list" => handle_tools_list_impl(state, request, caller).await?,
+        "tools/describe" => handle_tools_describe_impl(state, request, caller).await?,
+        "tools/call" => call_tool(state, request, caller).await?,
         "resources/list" => handle_resources_list_impl(state, request).await?,
-        "resources/read" => handle_resources_read_impl(state, request).await?,
+        "resources/read" => handle_resources_read_impl(state, request, caller).await?,
         "resources/subscribe" => handle_resources_subscribe_impl(state, request).await?,
         "prompts/list" => handle_prompts_list_impl(state, request).await?,
-        "prompts/get" => handle_prompts_get_impl(state, request).await?,
-        "sampling/createMessage" => handle_sampling_create_impl(state, request).await?,
+        "prompts/get" => handle_prompts_get_impl(state, request, caller).await?,
+        "sampling/createMessage" => handle_sampling_create_impl(state, request, caller).await?,
+        "notifications/tools/list_changed"
+        | "notifications/resources/list_changed"
+        | "notifications/prompts/list_changed" => handle_list_changed(state, request).await,
+        "notifications/resources/updated" => handle_resource_updated(state, request),
         _ => {
             // Unknown method, try to route to a backend
-            route_generic_request(state, request).await?
+            route_generic_request(state, request, caller).await?
         },
     };
 
-    Ok(Json(response))
+    Ok(response)
+}
+
+/// Answer `initialize` with the proxy's own identity and the union of the
+/// healthy backends' capabilities. It is never forwarded: each backend
+/// already has its own session with the proxy.
+async fn handle_initialize_impl(
+    state: AppState,
+    request: McpRequest,
+) -> std::result::Result<Value, ProxyError> {
+    let params = request.params();
+    let requested = params.get("protocolVersion").and_then(|v| v.as_str());
+    if let Some(client) = params.get("clientInfo") {
+        debug!("Initializing client session for {}", client);
+    }
+
+    let servers = state.registry.read().await.get_healthy_servers().await;
+    let budget = aggregation_budget(&state);
+    let backends = aggregate_list(
+        &state,
+        "initialize",
+        servers,
+        budget,
+        fetch_capabilities_from_server,
+    )
+    .await;
+
+    Ok(json!({
+        "jsonrpc": "2.0",
+        "id": request.id(),
+        "result": {
+            "protocolVersion": negotiate_protocol_version(requested),
+            "capabilities": merge_capabilities(&backends.items),
+            "serverInfo": {
+                "name": "Only1MCP",
+                "version": env!("CARGO_PKG_VERSION")
+            }
+        }
+    }))
+}
+
+/// Handle a `list_changed` notification by forgetting the lists discovered
+/// from backends and rebuilding the aggregated catalogs. A changed prompt
+/// list also drops cached prompts/get responses.
+async fn handle_list_changed(state: AppState, request: McpRequest) -> Value {
+    debug!("Received {}, refreshing catalogs", request.method);
+    state.registry.read().await.invalidate_catalog(None);
+    state.catalog.request_refresh();
+    if request.method == "notifications/prompts/list_changed" {
+        // Prompt definitions may have changed with the list
+        state.cache.invalidate_prefix("prompts/get:");
+    }
+
+    json!({
+        "jsonrpc": "2.0",
+        "id": request.id(),
+        "result": {}
+    })
+}
+
+/// Handle a `resources/updated` notification by dropping the cached reads
+/// of that resource.
+fn handle_resource_updated(state: AppState, request: McpRequest) -> Value {
+    match request.params().get("uri").and_then(Value::as_str) {
+        Some(uri) => {
+            debug!("Resource {} updated, dropping cached reads", uri);
+            state.cache.invalidate_prefix(&format!("resources/read:{}#", uri));
+        },
+        None => warn!("Ignoring resources/updated without a uri"),
+    }
+
+    json!({
+        "jsonrpc": "2.0",
+        "id": request.id(),
+        "result": {}
+    })
 }
 
 /// Handle tools/list request with aggregation.
 async fn handle_tools_list_impl(
     state: AppState,
     request: McpRequest,
+    caller: Caller,
 ) -> std::result::Result<Value, ProxyError> {
-    let start = Instant::now();
-
-    // Check cache
-    let cache_key = format!("tools:list:{}", state.config.server.port);
-    if let Some(cached) = state.cache.get(&cache_key).await {
-        state.metrics.cache_hits().inc();
-        debug!("Cache hit for tools/list");
-        return Ok(serde_json::from_slice(&cached)?);
+    let page_size = state.config.load().context_optimization.aggregation.page_size;
+    if page_size > 0 {
+        return tools_list_page(&state, &request, &caller, page_size).await;
     }
 
-    // Get all healthy servers
-    let registry = state.registry.read().await;
-    let servers = registry.get_healthy_servers().await;
+    let mut result = tools_list_result(&state, &request, &caller).await?;
+    slim_tools_list(&state, &caller, &mut result);
+    Ok(list_response(&request, result))
+}
 
+/// One page of the tools/list, starting where the request's `cursor`
+/// left off (see [`crate::proxy::pagination`]). Pages bypass the catalog
+/// and the response cache, since each covers only part of the list.
+async fn tools_list_page(
+    state: &AppState,
+    request: &McpRequest,
+    caller: &Caller,
+    page_size: usize,
+) -> std::result::Result<Value, ProxyError> {
+    let token = request.params().get("cursor").and_then(Value::as_str).map(str::to_string);
+    let mut cursor = match &token {
+        Some(token) => PageCursor::decode(token)?,
+        None => PageCursor::default(),
+    };
+    let mut servers = capable_servers(state, "tools/list").await;
     if servers.is_empty() {
         return Err(ProxyError::NoBackendAvailable("No healthy servers".into()));
     }
+    servers.sort();
 
-    // Parallel fetch from all servers (with optional batching)
-    let mut tasks = Vec::new();
-    for server in servers {
-        let state = state.clone();
-        let request = request.clone();
+    let page = fill_page(
+        &mut cursor,
+        &servers,
+        page_size,
+        |server, backend_cursor| fetch_tools_page(state, server, backend_cursor, request.id()),
+    )
+    .await;
+    let version = catalog_version(&page.items);
+    let mut result = list_result("tools", &page.items, &version, &page.degraded);
+    add_proxy_tools(state, &mut result, token.is_none());
+    filter_tools_by_policy(state, caller, &mut result).await;
+    annotate_tool_health(state, &mut result).await;
+    slim_tools_list(state, caller, &mut result);
+
+    let mut response = list_response(request, result);
+    if page.more {
+        response["result"]["nextCursor"] = json!(cursor.encode());
+    }
+    Ok(response)
+}
 
-        tasks.push(tokio::spawn(async move {
-            // Check if batching is enabled for this method
-            if state.config.context_optimization.batching.enabled
-                && state.config.context_optimization.batching.methods.contains(&request.method)
-            {
-                // Route through BatchAggregator
-                debug!(
-                    "Routing tools/list through batch aggregator for server: {}",
-                    server
-                );
-                state.batch_aggregator.submit_request(server.clone(), request).await.and_then(
-                    |response| {
-                        // Parse response and extract tools array
-                        // Nesting required for: async response → result extraction → error handling
-                        #[allow(clippy::excessive_nesting)]
-                        let result = response.result.ok_or_else(|| {
-                            Error::Server("No result in tools/list response".into())
-                        })?;
-
-                        let tools_value = result
-                            .get("tools")
-                            .ok_or_else(|| Error::Server("No tools field in response".into()))?;
-
-                        // Nesting required for: JSON parsing → error mapping
-                        #[allow(clippy::excessive_nesting)]
-                        let tools: Vec<Tool> = serde_json::from_value(tools_value.clone())
-                            .map_err(|e| {
-                                Error::Serialization(format!("Failed to parse tools: {}", e))
-                            })?;
-
-                        Ok(tools)
+/// Handle tools/describe: the full definitions of the tools named in the
+/// `names` param, for clients served a slim or names-only catalog. Unknown
+/// tools and tools the caller may not use are left out.
+async fn handle_tools_describe_impl(
+    state: AppState,
+    request: McpRequest,
+    caller: Caller,
+) -> std::result::Result<Value, ProxyError> {
+    let names: Vec<String> = request
+        .params()
+        .get("names")
+        .and_then(|names| serde_json::from_value(names.clone()).ok())
+        .ok_or_else(|| {
+            ProxyError::InvalidRequest("tools/describe requires a names array".into())
+        })?;
+
+    let list_request = McpRequest::new("tools/list", json!({}), request.id());
+    let mut result = tools_list_result(&state, &list_request, &caller).await?;
+    let mut tools = match result.get_mut("tools") {
+        Some(Value::Array(tools)) => std::mem::take(tools),
+        _ => Vec::new(),
+    };
+    tools.retain(|tool| {
+        tool.get("name")
+            .and_then(Value::as_str)
+            .is_some_and(|name| names.iter().any(|n| n == name))
+    });
+
+    Ok(json!({
+        "jsonrpc": "2.0",
+        "id": request.id(),
+        "result": { "tools": tools }
+    }))
+}
+
+/// Full tools/list result the caller may see: served from the catalog, the
+/// cache or a fresh aggregation, filtered by policy and annotated.
+async fn tools_list_result(
+    state: &AppState,
+    request: &McpRequest,
+    caller: &Caller,
+) -> std::result::Result<Value, ProxyError> {
+    // Serve the precomputed catalog when the background refresher maintains one
+    if let Some(tools) = state.catalog.tools() {
+        let mut result = list_result("tools", &tools.items, &tools.version, &[]);
+        add_proxy_tools(state, &mut result, true);
+        filter_tools_by_policy(state, caller, &mut result).await;
+        annotate_tool_health(state, &mut result).await;
+        return Ok(result);
+    }
+
+    // Check cache, refreshing a stale list in the background
+    let cached = state
+        .cache
+        .get_or_revalidate(&tools_list_cache_key(state), || {
+            let (state, request) = (state.clone(), request.clone());
+            async move {
+                match aggregate_tools(&state, &request, None).await {
+                    Ok(aggregated) => {
+                        cache_tools_list(&state, &aggregated).await;
                     },
-                )
-            } else {
-                // Direct backend call (existing path)
-                fetch_tools_from_server(state, server, request).await
+                    Err(e) => warn!("Failed to refresh cached tools/list: {}", e),
+                }
             }
-        }));
+        })
+        .await;
+    if let Some((cached, sources)) = cached {
+        debug!("Cache hit for tools/list");
+        sources.record("cache_hit");
+        let mut result = crate::json::from_slice(&cached)?;
+        add_proxy_tools(state, &mut result, true);
+        filter_tools_by_policy(state, caller, &mut result).await;
+        annotate_tool_health(state, &mut result).await;
+        return Ok(result);
     }
 
-    // Wait for all responses
-    let results = futures::future::join_all(tasks).await;
+    let aggregated = aggregate_tools(state, request, aggregation_budget(state)).await?;
+    let mut result = cache_tools_list(state, &aggregated).await;
+    add_proxy_tools(state, &mut result, true);
+    filter_tools_by_policy(state, caller, &mut result).await;
+    annotate_tool_health(state, &mut result).await;
+    Ok(result)
+}
+
+/// Apply the tools the proxy adds to a tools/list result: list aliased
+/// tools under their alias (`proxy.tool_aliases`) and hide backend tools
+/// named like a composite tool (`proxy.composite_tools`), adding the
+/// composite tools in their place if `first_page` is set. Later pages
+/// leave them out, so each is listed once.
+fn add_proxy_tools(state: &AppState, result: &mut Value, first_page: bool) {
+    let config = state.config.load();
+    let (aliases, composites) = (&config.proxy.tool_aliases, &config.proxy.composite_tools);
+    if aliases.is_empty() && composites.is_empty() {
+        return;
+    }
+    let Some(tools) = result.get_mut("tools").and_then(Value::as_array_mut) else {
+        return;
+    };
+
+    aliases::list_aliases(aliases, tools);
+    tools.retain(|tool| {
+        let name = tool.get("name").and_then(Value::as_str).unwrap_or_default();
+        !composites.iter().any(|composite| composite.name == name)
+    });
+    if first_page && !composites.is_empty() {
+        tools.extend(composites.iter().map(|tool| json!(composite::definition(tool))));
+        tools.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+    }
+
+    let version = catalog_version(tools);
+    result["catalogVersion"] = json!(version);
+}
+
+/// Reduce a tools/list result to the caller's catalog mode
+/// (`context_optimization.slim`), marking it in `_meta.catalogMode`.
+///
+/// The catalog version is recomputed over the reduced tools, so each mode
+/// has its own version for `ifVersion`.
+fn slim_tools_list(state: &AppState, caller: &Caller, result: &mut Value) {
+    if caller.catalog_mode == CatalogMode::Full {
+        return;
+    }
+    let Some(tools) = result.get_mut("tools").and_then(Value::as_array_mut) else {
+        return;
+    };
+
+    let full_tokens: u64 = tools.iter().map(estimate_tokens).sum();
+    slim_tools(
+        tools,
+        caller.catalog_mode,
+        &state.config.load().context_optimization.slim,
+    );
+    let slim_tokens: u64 = tools.iter().map(estimate_tokens).sum();
+    crate::metrics::record_context_optimization(
+        "proxy",
+        "slim_catalog",
+        full_tokens.saturating_sub(slim_tokens),
+    );
+
+    let version = catalog_version(tools);
+    result["catalogVersion"] = json!(version);
+    if !result.get("_meta").is_some_and(Value::is_object) {
+        result["_meta"] = json!({});
+    }
+    result["_meta"]["catalogMode"] = json!(caller.catalog_mode);
+}
+
+/// Response-cache key of the aggregated tools/list result.
+fn tools_list_cache_key(state: &AppState) -> String {
+    format!("tools:list:{}", state.config.load().server.port)
+}
+
+/// Build the tools/list result for `aggregated` and cache it (5 minute
+/// TTL), unless some backends were skipped. Each tool's tokens are
+/// attributed to the server that provides it.
+pub(crate) async fn cache_tools_list(state: &AppState, aggregated: &Aggregated<Tool>) -> Value {
+    let version = catalog_version(&aggregated.items);
+    let result = list_result("tools", &aggregated.items, &version, &aggregated.degraded);
+
+    if aggregated.degraded.is_empty() {
+        if let Ok(serialized) = crate::json::to_bytes(&result) {
+            let owners = state.registry.read().await.tool_owners();
+            let sources = aggregated
+                .items
+                .iter()
+                .map(|tool| {
+                    let server = owners.get(&tool.name).map(String::as_str).unwrap_or("proxy");
+                    let tokens = crate::json::to_bytes(tool)
+                        .map(|bytes| crate::metrics::tokens::estimate_payload_tokens(&bytes))
+                        .unwrap_or(0);
+                    (server.to_string(), tokens)
+                })
+                .collect();
+            state
+                .cache
+                .set_from(
+                    tools_list_cache_key(state),
+                    serialized,
+                    "tools/list",
+                    sources,
+                )
+                .await;
+        }
+    }
+    result
+}
+
+/// Items merged from every backend by a list aggregation.
+pub(crate) struct Aggregated<T> {
+    pub items: Vec<T>,
+    /// Backends that missed the aggregation budget; their items, if any,
+    /// come from the last cached response
+    pub degraded: Vec<String>,
+}
+
+/// Time budget for an on-demand list aggregation, or `None` if unbounded.
+fn aggregation_budget(state: &AppState) -> Option<Duration> {
+    match state.config.load().context_optimization.aggregation.timeout_ms {
+        0 => None,
+        ms => Some(Duration::from_millis(ms)),
+    }
+}
+
+/// Cache key for one backend's contribution to a list method.
+fn server_list_cache_key(method: &str, server_id: &str) -> String {
+    format!("{}:server:{}", method, server_id)
+}
+
+/// Whether list requests for `method` are routed through the batch aggregator.
+fn batching_enabled(state: &AppState, method: &str) -> bool {
+    let config = state.config.load();
+    let batching = &config.context_optimization.batching;
+    batching.enabled && batching.methods.iter().any(|m| m == method)
+}
+
+/// Submit a list request through the batch aggregator and extract `field`
+/// from the result.
+async fn submit_batched_list<T: DeserializeOwned>(
+    state: &AppState,
+    server_id: String,
+    request: McpRequest,
+    field: &str,
+) -> Result<Vec<T>> {
+    debug!(
+        "Routing {} through batch aggregator for server: {}",
+        request.method, server_id
+    );
+    let method = request.method.clone();
+    let response = state.batch_aggregator.submit_request(server_id, request).await?;
 
-    // Store count before consuming results
-    let server_count = results.len();
+    let result = response
+        .result
+        .ok_or_else(|| Error::Server(format!("No result in {} response", method)))?;
+    let value = result
+        .get(field)
+        .ok_or_else(|| Error::Server(format!("No {} field in response", field)))?;
+
+    serde_json::from_value(value.clone())
+        .map_err(|e| Error::Serialization(format!("Failed to parse {}: {}", field, e)))
+}
+
+/// Healthy servers that advertise the surface `method` belongs to.
+///
+/// Servers whose capabilities are not known yet are included, and discovery
+/// is started for them in the background so later requests can skip them.
+async fn capable_servers(state: &AppState, method: &str) -> Vec<String> {
+    let registry = state.registry.read().await;
+    let mut servers = registry.get_healthy_servers().await;
+
+    for server in &servers {
+        if registry.begin_capability_discovery(server) {
+            let (state, server) = (state.clone(), server.clone());
+            tokio::spawn(async move {
+                if let Err(e) = fetch_capabilities_from_server(state, server.clone()).await {
+                    debug!("Capabilities of {} unknown: {}", server, e);
+                }
+            });
+        }
+    }
+
+    servers.retain(|server| {
+        let supported = registry.supports_method(server, method);
+        if !supported {
+            debug!(
+                "Skipping {} for {}: not advertised in capabilities",
+                server, method
+            );
+        }
+        supported
+    });
+    servers
+}
+
+/// Fetch a list from every server in parallel and merge the results.
+///
+/// With a `budget`, servers that have not answered when it runs out are
+/// reported as degraded and their last cached list is used instead. Their
+/// fetches keep running in the background so the cache is warm next time.
+async fn aggregate_list<T, F, Fut>(
+    state: &AppState,
+    method: &'static str,
+    servers: Vec<String>,
+    budget: Option<Duration>,
+    fetch: F,
+) -> Aggregated<T>
+where
+    T: Serialize + DeserializeOwned + Send + 'static,
+    F: Fn(AppState, String) -> Fut,
+    Fut: Future<Output = Result<Vec<T>>> + Send + 'static,
+{
+    let deadline = budget.map(|budget| tokio::time::Instant::now() + budget);
+
+    let tasks = servers.into_iter().map(|server| {
+        // Fetches run on their own tasks, outside the request's scope
+        note_backend(&server);
+        let fetch = fetch(state.clone(), server.clone());
+        let cache_state = state.clone();
+        let key = server_list_cache_key(method, &server);
+        let server_id = server.clone();
+        let handle = tokio::spawn(async move {
+            let items = fetch.await?;
+            if let Ok(serialized) = crate::json::to_bytes(&items) {
+                let sources = TokenSources::server(&server_id, &serialized);
+                cache_state.cache.set_from(key, serialized, method, sources).await;
+            }
+            Ok::<_, Error>(items)
+        });
 
-    // Aggregate tools
-    let mut all_tools = Vec::new();
-    for result in results {
+        async move {
+            let result = match deadline {
+                Some(deadline) => tokio::time::timeout_at(deadline, handle).await.ok(),
+                None => Some(handle.await),
+            };
+            (server, result)
+        }
+    });
+    let results = futures::future::join_all(tasks).await;
+
+    let mut aggregated = Aggregated {
+        items: Vec::new(),
+        degraded: Vec::new(),
+    };
+    for (server, result) in results {
         match result {
-            Ok(Ok(tools)) => all_tools.extend(tools),
-            Ok(Err(e)) => warn!("Failed to fetch tools: {}", e),
-            Err(e) => error!("Task panic: {}", e),
+            Some(Ok(Ok(items))) => aggregated.items.extend(items),
+            Some(Ok(Err(e))) => warn!("Failed to fetch {} from {}: {}", method, server, e),
+            Some(Err(e)) => error!("Task panic: {}", e),
+            None => {
+                warn!("{} from {} exceeded the aggregation budget", method, server);
+                let cached = state.cache.get(&server_list_cache_key(method, &server)).await;
+                if let Some(items) = cached.and_then(|c| crate::json::from_slice::<Vec<T>>(&c).ok())
+                {
+                    aggregated.items.extend(items);
+                }
+                aggregated.degraded.push(server);
+            },
+        }
+    }
+    aggregated
+}
+
+/// Full `result` of a list request: the items under `field`, the catalog
+/// version, and `_meta.degraded` when some backends were skipped.
+fn list_result<T: Serialize>(
+    field: &str,
+    items: &[T],
+    version: &str,
+    degraded: &[String],
+) -> Value {
+    let mut result = json!({ field: items, "catalogVersion": version });
+    if !degraded.is_empty() {
+        result["_meta"] = json!({ "degraded": degraded });
+    }
+    result
+}
+
+/// Drop the tools the caller's roles may not use (`auth.policies`) from a
+/// tools/list result, so each caller's catalog version covers only what it
+/// sees.
+async fn filter_tools_by_policy(state: &AppState, caller: &Caller, result: &mut Value) {
+    let config = state.config.load();
+    if config.auth.policies.is_empty() {
+        return;
+    }
+    let Some(tools) = result.get_mut("tools").and_then(Value::as_array_mut) else {
+        return;
+    };
+
+    let owners = state.registry.read().await.tool_owners();
+    tools.retain(|tool| {
+        let name = tool.get("name").and_then(Value::as_str).unwrap_or_default();
+        let server = owners.get(name).and_then(|id| config.servers.iter().find(|s| &s.id == id));
+        crate::auth::policy::tool_allowed(&config.auth, caller.roles(), server, name)
+    });
+
+    let version = catalog_version(tools);
+    result["catalogVersion"] = json!(version);
+}
+
+/// Add `_meta.health` to each tool of a tools/list result from the health
+/// of the server that provides it.
+///
+/// The annotations are part of what the client sees, so the catalog version
+/// is recomputed over the annotated tools: a health change then reads as a
+/// catalog change to clients using `ifVersion`.
+async fn annotate_tool_health(state: &AppState, result: &mut Value) {
+    if !state.config.load().context_optimization.aggregation.annotate_health {
+        return;
+    }
+    let Some(tools) = result.get_mut("tools").and_then(Value::as_array_mut) else {
+        return;
+    };
+
+    let registry = state.registry.read().await;
+    let owners = registry.tool_owners();
+    for tool in tools.iter_mut() {
+        let health = tool
+            .get("name")
+            .and_then(Value::as_str)
+            .and_then(|name| owners.get(name))
+            .and_then(|server| health_label(&registry.health_state(server)));
+        if let (Some(health), Some(fields)) = (health, tool.as_object_mut()) {
+            fields.insert("_meta".into(), json!({ "health": health }));
         }
     }
+    drop(registry);
+
+    let version = catalog_version(tools);
+    result["catalogVersion"] = json!(version);
+}
+
+/// Label for a server health state in `_meta.health`; `None` until the
+/// server has been checked.
+fn health_label(health: &HealthState) -> Option<&'static str> {
+    match health {
+        HealthState::Healthy => Some("healthy"),
+        HealthState::Degraded => Some("degraded"),
+        // Unhealthy servers receive no traffic until they recover
+        HealthState::Unhealthy => Some("circuit-open"),
+        HealthState::Unknown => None,
+    }
+}
+
+/// Reply to a list request with `result`, or with a small "not modified"
+/// result if the client's `ifVersion` param matches its catalog version.
+fn list_response(request: &McpRequest, result: Value) -> Value {
+    let if_version = request.params.as_ref().and_then(|params| params.get("ifVersion"));
+    let result = match result.get("catalogVersion") {
+        Some(version) if if_version == Some(version) => {
+            json!({ "notModified": true, "catalogVersion": version })
+        },
+        _ => result,
+    };
+
+    json!({
+        "jsonrpc": "2.0",
+        "id": request.id(),
+        "result": result
+    })
+}
+
+/// Reply to a list request from a cached list result, recording the
+/// tokens it saved the servers it came from.
+fn cached_list_response(
+    request: &McpRequest,
+    (cached, sources): (Bytes, TokenSources),
+) -> std::result::Result<Value, ProxyError> {
+    sources.record("cache_hit");
+    Ok(list_response(request, crate::json::from_slice(&cached)?))
+}
+
+/// Fetch and merge tools from all healthy servers, deduplicated by name.
+pub(crate) async fn aggregate_tools(
+    state: &AppState,
+    request: &McpRequest,
+    budget: Option<Duration>,
+) -> std::result::Result<Aggregated<Tool>, ProxyError> {
+    // Get all healthy servers offering tools
+    let servers = capable_servers(state, "tools/list").await;
+
+    if servers.is_empty() {
+        return Err(ProxyError::NoBackendAvailable("No healthy servers".into()));
+    }
+    let server_count = servers.len();
+
+    let mut aggregated = aggregate_list(state, "tools/list", servers, budget, |state, server| {
+        let request = request.clone();
+        async move {
+            let ttl = server_catalog_ttl(&state);
+            if let Some(tools) = state.registry.read().await.fresh_tools(&server, ttl) {
+                return Ok(tools);
+            }
 
-    // Deduplicate tools by name
-    all_tools.sort_by(|a, b| a.name.cmp(&b.name));
-    all_tools.dedup_by(|a, b| a.name == b.name);
+            let tools = if batching_enabled(&state, &request.method) {
+                submit_batched_list(&state, server.clone(), request, "tools").await
+            } else {
+                fetch_tools_from_server(state.clone(), server.clone(), request).await
+            };
 
-    // Build response
-    let response = json!({
-        "jsonrpc": "2.0",
-        "id": request.id(),
-        "result": {
-            "tools": all_tools
+            // Remember which server offers which tool
+            if let Ok(tools) = &tools {
+                state.registry.read().await.set_server_tools(&server, tools.clone());
+            }
+            tools
         }
-    });
+    })
+    .await;
 
-    // Cache response (5 minute TTL)
-    if let Ok(serialized) = serde_json::to_vec(&response) {
-        state.cache.set(cache_key, serialized, "tools/list").await;
-    }
+    // Deduplicate tools by name
+    aggregated.items.sort_by(|a, b| a.name.cmp(&b.name));
+    aggregated.items.dedup_by(|a, b| a.name == b.name);
 
-    state.metrics.tools_list_duration().record(start.elapsed().as_secs_f64());
     info!(
         "Aggregated {} tools from {} servers",
-        all_tools.len(),
+        aggregated.items.len(),
         server_count
     );
-    Ok(response)
+    Ok(aggregated)
 }
 
 /// Handle tools/call with routing and retries.
@@ -163,15 +1155,258 @@ pub async fn handle_tools_call(
     Json(payload): Json<Value>,
 ) -> std::result::Result<Json<Value>, ProxyError> {
     let request: McpRequest = serde_json::from_value(payload)?;
-    handle_tools_call_impl(state, request).await.map(Json)
+    call_tool(state, request, Caller::default()).await.map(Json)
 }
 
-async fn handle_tools_call_impl(
+/// Handle tools/call, running a composite tool's pipeline when it calls one.
+async fn call_tool(
+    state: AppState,
+    request: McpRequest,
+    caller: Caller,
+) -> std::result::Result<Value, ProxyError> {
+    let name = request.params.as_ref().and_then(|params| params.get("name"));
+    match composite_tool(&state.config.load(), name).cloned() {
+        Some(tool) => handle_composite_call(state, request, caller, tool).await,
+        None => handle_tools_call_impl(state, request, caller).await,
+    }
+}
+
+/// The composite tool (`proxy.composite_tools`) a tools/call names.
+fn composite_tool<'a>(config: &'a Config, name: Option<&Value>) -> Option<&'a CompositeToolConfig> {
+    let name = name.and_then(Value::as_str)?;
+    config.proxy.composite_tools.iter().find(|tool| tool.name == name)
+}
+
+/// Run a composite tool (`proxy.composite_tools`). Each step is an ordinary
+/// tools/call, so routing, policies, scripts and retries apply to it. A step
+/// that fails ends the call with its error; a template that cannot be
+/// filled in ends it with an error result.
+async fn handle_composite_call(
     state: AppState,
     request: McpRequest,
+    caller: Caller,
+    tool: CompositeToolConfig,
+) -> std::result::Result<Value, ProxyError> {
+    let start = Instant::now();
+    let config = state.config.load();
+    if !crate::auth::policy::tool_allowed(&config.auth, caller.roles(), None, &tool.name) {
+        warn!("Policy denied composite tool {}", tool.name);
+        return Err(ProxyError::Forbidden(format!(
+            "Not allowed to call tool: {}",
+            tool.name
+        )));
+    }
+    drop(config);
+
+    let reply = |result: Value| json!({"jsonrpc": "2.0", "id": request.id(), "result": result});
+    let failed = |message: String| {
+        warn!("Composite tool {} failed: {}", tool.name, message);
+        reply(json!({"content": [{"type": "text", "text": message}], "isError": true}))
+    };
+    let arguments = request.params().get("arguments").cloned().unwrap_or_else(|| json!({}));
+    let mut pipeline = Pipeline::new(arguments);
+    for step in &tool.steps {
+        let arguments = match pipeline.arguments(step) {
+            Ok(arguments) => arguments,
+            Err(e) => return Ok(failed(format!("Step {}: {}", step.id, e))),
+        };
+        let call = McpRequest::new(
+            "tools/call",
+            json!({"name": step.tool, "arguments": arguments}),
+            request.id(),
+        );
+        let mut response = handle_tools_call_impl(state.clone(), call, caller.clone()).await?;
+        if response.get("error").is_some()
+            || response.pointer("/result/isError") == Some(&json!(true))
+        {
+            debug!("Step {} of composite tool {} failed", step.id, tool.name);
+            return Ok(response);
+        }
+        let result = response.get_mut("result").map(Value::take).unwrap_or_default();
+        pipeline.record(step, result);
+    }
+
+    let response = match pipeline.output(&tool) {
+        Ok(result) => reply(result),
+        Err(e) => failed(format!("Output: {}", e)),
+    };
+    info!(
+        "Composite tool {} ran {} steps in {:?}",
+        tool.name,
+        tool.steps.len(),
+        start.elapsed()
+    );
+    Ok(response)
+}
+
+async fn handle_tools_call_impl(
+    state: AppState,
+    mut request: McpRequest,
+    caller: Caller,
 ) -> std::result::Result<Value, ProxyError> {
     let start = Instant::now();
+    let (server_id, tool_name) = route_tool_call(&state, &mut request, &caller).await?;
+    mirror_tool_call(&state, &server_id, &tool_name, &request);
+
+    // Execute with the backend's retry policy
+    let retry = retry_policy(&state, &server_id);
+    let mut response =
+        execute_with_retry(|| call_backend(&state, &server_id, request.clone()), &retry).await?;
+    sanitize_tool_result(&state, &server_id, &tool_name, &mut response).await;
+    if let Some(result) = response.get_mut("result") {
+        redact(&state, &tool_name, Redacted::Result, result);
+    }
+    truncate_tool_result(&state, &server_id, &tool_name, &mut response);
+    script_tool_result(&state, &caller, &server_id, &tool_name, &mut response)?;
+
+    record_tool_cost(&state, &server_id, &tool_name, &request, &response);
+    info!("Tool {} executed in {:?}", tool_name, start.elapsed());
+    Ok(response)
+}
+
+/// Handle a single tools/call, passing a large JSON result from an HTTP or
+/// Streamable HTTP backend through to the client as it arrives rather than
+/// parsing it (`proxy.streaming`). Cost and duration are recorded once the
+/// body has been forwarded.
+async fn handle_tools_call_streaming(
+    state: AppState,
+    mut request: McpRequest,
+    caller: Caller,
+    log: RequestLog,
+) -> std::result::Result<Response, ProxyError> {
+    let start = Instant::now();
+    let (server_id, tool_name) = route_tool_call(&state, &mut request, &caller).await?;
+    mirror_tool_call(&state, &server_id, &tool_name, &request);
+
+    // The backend stays in flight, and keeps its admission slot, until the
+    // body has been streamed
+    let admission = state.config.load().proxy.admission.clone();
+    let admitted = state.admission.admit_backend(&admission, &server_id).await?;
+    let in_flight = state.connections.acquire(&server_id);
+    let retry = retry_policy(&state, &server_id);
+    let reply = execute_with_retry(
+        || call_backend_streaming(&state, &server_id, request.clone()),
+        &retry,
+    )
+    .await?;
+
+    let response = match reply {
+        BackendReply::Parsed(response) => serde_json::to_value(response)?,
+        BackendReply::Streaming(response) => {
+            let limit = state.config.load().proxy.limits.max_response_bytes;
+            let params_tokens = estimate_tokens(&request.params());
+            let on_end = move |size: StreamedSize| {
+                drop((in_flight, admitted));
+                if !size.complete {
+                    warn!(
+                        "Streamed result of tool {} cut off after {} bytes",
+                        tool_name, size.bytes
+                    );
+                }
+                crate::metrics::STREAMED_RESPONSE_BYTES_TOTAL
+                    .with_label_values(&[&server_id])
+                    .inc_by(size.bytes as f64);
+                charge_tool_cost(&state, &server_id, &tool_name, params_tokens + size.tokens);
+                let (status, error) = if size.complete {
+                    ("success", None)
+                } else {
+                    (
+                        "error",
+                        Some(format!("Result cut off after {} bytes", size.bytes)),
+                    )
+                };
+                log.finish(&state, vec![server_id.clone()], status, error);
+                info!(
+                    "Tool {} streamed {} bytes in {:?}",
+                    tool_name,
+                    size.bytes,
+                    start.elapsed()
+                );
+            };
+            let body = Body::from_stream(metered_body(response, limit, on_end));
+            return Ok(([(header::CONTENT_TYPE, "application/json")], body).into_response());
+        },
+    };
+
+    record_tool_cost(&state, &server_id, &tool_name, &request, &response);
+    let status = crate::metrics::response_status(&response);
+    log.finish(&state, vec![server_id], status, reply_error(&response));
+    info!("Tool {} executed in {:?}", tool_name, start.elapsed());
+    Ok(json_response(&response))
+}
+
+/// Clean up a tools/call result (`proxy.sanitization`), replacing it with
+/// an error result if it does not match the tool's outputSchema.
+async fn sanitize_tool_result(state: &AppState, server_id: &str, tool: &str, response: &mut Value) {
+    let config = state.config.load();
+    let sanitization = &config.proxy.sanitization;
+    let Some(result) = response.get_mut("result").filter(|_| sanitization.enabled) else {
+        return;
+    };
+    let fixes = sanitize_result(result, sanitization);
+    for fix in fixes.labels() {
+        debug!("Sanitized result of tool {}: {}", tool, fix);
+        crate::metrics::SANITIZED_RESULTS_TOTAL
+            .with_label_values(&[server_id, fix])
+            .inc();
+    }
+
+    if !sanitization.validate_output {
+        return;
+    }
+    let listed = state.registry.read().await.listed_tool(server_id, tool);
+    let Some(schema) = listed.and_then(|listed| listed.output_schema) else {
+        return;
+    };
+    if let Err(problem) = check_output(&state.tool_schemas, &schema, result) {
+        warn!(
+            "Result of tool {} does not match its outputSchema: {}",
+            tool, problem
+        );
+        crate::metrics::SANITIZED_RESULTS_TOTAL
+            .with_label_values(&[server_id, "output_schema"])
+            .inc();
+        *result = json!({
+            "content": [{
+                "type": "text",
+                "text": format!("Tool {} returned a result that does not match its outputSchema: {}", tool, problem)
+            }],
+            "isError": true
+        });
+    }
+}
 
+/// Cut an oversized tools/call result down to the limits of its tool
+/// (`context_optimization.truncation`).
+fn truncate_tool_result(state: &AppState, server_id: &str, tool: &str, response: &mut Value) {
+    let config = state.config.load();
+    let truncation = &config.context_optimization.truncation;
+    let Some(result) = response.get_mut("result").filter(|_| truncation.enabled) else {
+        return;
+    };
+    let (max_bytes, max_tokens) = truncation.limits_for(tool);
+    let Some(truncated) = truncate_result(result, max_bytes, max_tokens) else {
+        return;
+    };
+    debug!(
+        "Truncated result of tool {} from {} to {} bytes",
+        tool, truncated.original_bytes, truncated.returned_bytes
+    );
+    crate::metrics::TRUNCATED_RESULTS_TOTAL
+        .with_label_values(&[server_id, tool])
+        .inc();
+    crate::metrics::record_context_optimization(server_id, "truncation", truncated.omitted_tokens);
+}
+
+/// Route a tools/call to its backend and check the caller may use the
+/// tool, running the `on_request` and `on_route` scripts
+/// (`proxy.scripts`) and resolving tool aliases (`proxy.tool_aliases`).
+/// Returns the backend and the tool name it is called under.
+async fn route_tool_call(
+    state: &AppState,
+    request: &mut McpRequest,
+    caller: &Caller,
+) -> std::result::Result<(String, String), ProxyError> {
     // Extract tool name
     let tool_name = request
         .params()
@@ -181,29 +1416,395 @@ async fn handle_tools_call_impl(
         .ok_or_else(|| ProxyError::InvalidRequest("Missing tool name".into()))?;
 
     debug!("Calling tool: {}", tool_name);
+    let config = state.config.load();
+    let scripts = &config.proxy.scripts;
+    let call = ToolCall {
+        tool: &tool_name,
+        client: caller.client_id.as_deref(),
+    };
+    if scripts.on_request.is_some() {
+        let params = request.params.get_or_insert_with(|| json!({}));
+        let mut arguments = params.get("arguments").cloned().unwrap_or_else(|| json!({}));
+        state
+            .scripts
+            .on_request(scripts, call, &mut arguments)
+            .map_err(|e| script_error(Hook::Request, e))?;
+        params["arguments"] = arguments;
+    }
 
-    // Route request
-    let router = RequestRouter::new(state.config.proxy.routing.clone());
-    let (server_id, _) = router
-        .route_request(&request, &*state.registry.read().await, &state.cache)
-        .await?;
+    // An alias calls its first available target rather than being routed
+    let target = match aliases::find(&config.proxy.tool_aliases, &tool_name) {
+        Some(alias) => {
+            let registry = state.registry.read().await;
+            let target = aliases::pick(alias, |server| {
+                registry.contains(server) && registry.is_available(server)
+            })
+            .ok_or_else(|| {
+                ProxyError::NoBackendAvailable(format!(
+                    "No target of tool alias {} is available",
+                    tool_name
+                ))
+            })?;
+            Some(target.clone())
+        },
+        None => None,
+    };
+    let (mut server_id, tool) = match target {
+        Some(target) => {
+            debug!(
+                "Tool alias {} calls {} on {}",
+                tool_name, target.tool, target.server
+            );
+            request.params.get_or_insert_with(|| json!({}))["name"] = json!(target.tool);
+            (target.server, target.tool)
+        },
+        None => {
+            let (server_id, _) = request_router(state)
+                .route_request(
+                    request,
+                    caller.client_id.as_deref(),
+                    &*state.registry.read().await,
+                    &state.cache,
+                )
+                .await?;
+            (server_id, tool_name.clone())
+        },
+    };
+    let arguments = request.params().get("arguments").cloned().unwrap_or(Value::Null);
+    let picked = state
+        .scripts
+        .on_route(scripts, call, &arguments, &server_id)
+        .map_err(|e| script_error(Hook::Route, e))?;
+    if let Some(picked) = picked {
+        if !config.servers.iter().any(|s| s.id == picked && s.enabled) {
+            return Err(ProxyError::NoBackendAvailable(format!(
+                "on_route script picked unknown server: {}",
+                picked
+            )));
+        }
+        debug!("on_route script sent tool {} to {}", tool_name, picked);
+        server_id = picked;
+    }
+    authorize_tool(state, caller, &server_id, &tool)?;
+    check_arguments(state, &server_id, &tool, request).await?;
+    if let Some(arguments) = request.params.as_mut().and_then(|p| p.get_mut("arguments")) {
+        redact(state, &tool, Redacted::Arguments, arguments);
+    }
+    Ok((server_id, tool))
+}
 
-    // Get server configuration
-    let registry = state.registry.read().await;
-    let server = registry
-        .get_server(&server_id)
-        .ok_or_else(|| ProxyError::NoBackendAvailable(tool_name.to_string()))?;
-
-    // Execute with retry
-    let response = execute_with_retry(
-        || send_request_to_backend(state.clone(), server.clone(), request.clone()),
-        3,
-    )
-    .await?;
+/// What part of a tools/call is redacted.
+#[derive(Clone, Copy)]
+enum Redacted {
+    Arguments,
+    Result,
+}
 
-    state.metrics.tools_call_duration().record(start.elapsed().as_secs_f64());
-    info!("Tool {} executed in {:?}", tool_name, start.elapsed());
-    Ok(response)
+/// Redact personal data and secrets in the arguments or result of a
+/// tools/call (`proxy.redaction`).
+fn redact(state: &AppState, tool: &str, part: Redacted, value: &mut Value) {
+    let config = state.config.load();
+    let redaction = &config.proxy.redaction;
+    let (applies, target) = match part {
+        Redacted::Arguments => (redaction.arguments, "arguments"),
+        Redacted::Result => (redaction.results, "results"),
+    };
+    if !redaction.enabled
+        || !applies
+        || redaction.skip_tools.iter().any(|pattern| glob_match(pattern, tool))
+    {
+        return;
+    }
+    for (pattern, count) in state.redaction.redactor(redaction).redact(value) {
+        debug!(
+            "Redacted {} {} match(es) in {} of tool {}",
+            count, pattern, target, tool
+        );
+        crate::metrics::REDACTIONS_TOTAL
+            .with_label_values(&[&pattern, target])
+            .inc_by(count as f64);
+    }
+}
+
+/// Reject a tools/call whose arguments do not match the tool's inputSchema
+/// (`proxy.argument_validation`), as last listed by `server_id`. Calls are
+/// let through while the schema is not known.
+async fn check_arguments(
+    state: &AppState,
+    server_id: &str,
+    tool: &str,
+    request: &McpRequest,
+) -> std::result::Result<(), ProxyError> {
+    let config = state.config.load();
+    let validation = &config.proxy.argument_validation;
+    if !validation.enabled || validation.skip_tools.iter().any(|pattern| glob_match(pattern, tool))
+    {
+        return Ok(());
+    }
+    let Some(listed) = state.registry.read().await.listed_tool(server_id, tool) else {
+        return Ok(());
+    };
+
+    // Check the arguments the backend will receive
+    let request = match config.servers.iter().find(|s| s.id == server_id) {
+        Some(server) => transform_request(&server.transforms, request.clone()),
+        None => request.clone(),
+    };
+    let arguments = request.params().get("arguments").cloned().unwrap_or_else(|| json!({}));
+    let Err(problems) = state.tool_schemas.check(&listed.input_schema, &arguments) else {
+        return Ok(());
+    };
+    debug!("Invalid arguments for tool {}: {:?}", tool, problems);
+    crate::metrics::INVALID_ARGUMENTS_TOTAL
+        .with_label_values(&[server_id, tool])
+        .inc();
+    Err(ProxyError::InvalidParams(format!(
+        "Invalid arguments for tool {}: {}",
+        tool,
+        problems.join("; ")
+    )))
+}
+
+/// Run the `on_response` script on a tools/call result (`proxy.scripts`).
+fn script_tool_result(
+    state: &AppState,
+    caller: &Caller,
+    server_id: &str,
+    tool: &str,
+    response: &mut Value,
+) -> std::result::Result<(), ProxyError> {
+    let config = state.config.load();
+    let Some(result) = response.get_mut("result") else {
+        return Ok(());
+    };
+    let call = ToolCall {
+        tool,
+        client: caller.client_id.as_deref(),
+    };
+    state
+        .scripts
+        .on_response(&config.proxy.scripts, call, server_id, result)
+        .map_err(|e| script_error(Hook::Response, e))
+}
+
+fn script_error(hook: Hook, error: ScriptError) -> ProxyError {
+    match error {
+        ScriptError::Rejected(reason) => {
+            warn!("{} script rejected a call: {}", hook.name(), reason);
+            ProxyError::Forbidden(format!("Rejected by {} script: {}", hook.name(), reason))
+        },
+        ScriptError::Failed(e) => {
+            error!("{} script failed: {}", hook.name(), e);
+            ProxyError::Internal(format!("{} script failed", hook.name()))
+        },
+    }
+}
+
+/// Send a copy of a tools/call routed to `server_id` to its shadow backend
+/// in the background, if the server mirrors `tool` (`servers[].mirror`).
+fn mirror_tool_call(state: &AppState, server_id: &str, tool: &str, request: &McpRequest) {
+    use rand::Rng;
+
+    let config = state.config.load();
+    let Some(mirror) = config
+        .servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .and_then(|s| s.mirror.as_ref())
+    else {
+        return;
+    };
+    if !mirror.mirrors_tool(tool) || rand::thread_rng().gen_range(0..100) >= mirror.percent {
+        return;
+    }
+
+    let (state, request) = (state.clone(), request.clone());
+    let (source, target) = (server_id.to_string(), mirror.server.clone());
+    tokio::spawn(async move { send_mirrored(&state, &source, &target, request).await });
+}
+
+/// Send a mirrored call to `target`, discarding the reply and counting its
+/// outcome.
+async fn send_mirrored(state: &AppState, source: &str, target: &str, request: McpRequest) {
+    let outcome = match send_to_server(state, target, request).await {
+        Ok(response) if response.error.is_none() => "success",
+        Ok(response) => {
+            let reason = response.error.map(|e| e.message).unwrap_or_default();
+            debug!(
+                "Mirror {} of {} returned an error: {}",
+                target, source, reason
+            );
+            "error"
+        },
+        Err(e) => {
+            debug!("Mirror {} of {} failed: {}", target, source, e);
+            "error"
+        },
+    };
+    crate::metrics::MIRRORED_REQUESTS_TOTAL
+        .with_label_values(&[source, target, outcome])
+        .inc();
+}
+
+/// Send a request to a configured backend, counting it as in flight.
+async fn call_backend(
+    state: &AppState,
+    server_id: &str,
+    request: McpRequest,
+) -> std::result::Result<Value, ProxyError> {
+    let admission = state.config.load().proxy.admission.clone();
+    let _admitted = state.admission.admit_backend(&admission, server_id).await?;
+    let _in_flight = state.connections.acquire(server_id);
+    let start = Instant::now();
+    let response = send_to_server(state, server_id, request).await;
+    let success = response.as_ref().is_ok_and(|response| response.error.is_none());
+    record_split_outcome(state, server_id, success, start);
+    Ok(serde_json::to_value(response.map_err(backend_error)?)?)
+}
+
+/// Count a call in the per-variant metrics of the server's traffic split.
+fn record_split_outcome(state: &AppState, server_id: &str, success: bool, start: Instant) {
+    let config = state.config.load();
+    crate::routing::split::record_outcome(
+        &config.proxy.routing.traffic_splits,
+        server_id,
+        success,
+        start.elapsed(),
+    );
+}
+
+/// Send a request to a configured backend, leaving a large JSON result from
+/// an HTTP or Streamable HTTP backend unread for the caller to stream.
+async fn call_backend_streaming(
+    state: &AppState,
+    server_id: &str,
+    request: McpRequest,
+) -> std::result::Result<BackendReply, ProxyError> {
+    let config = state.config.load_full();
+    let server_config = config
+        .servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .ok_or_else(|| ProxyError::Core(Error::ServerNotFound(server_id.to_string())))?;
+    let stream_from = config.proxy.streaming.min_bytes;
+    let timeout = server_config.request_timeout();
+    let transforms = &server_config.transforms;
+
+    let send = async {
+        match &server_config.transport {
+            // Rewritten results are parsed, so there is nothing to stream
+            _ if rewrites_results(transforms) => send_via_transport(state, server_config, request)
+                .await
+                .map(BackendReply::Parsed),
+            crate::config::TransportConfig::Http { url, headers, .. } => {
+                let http_transport = state
+                    .http_transport
+                    .as_ref()
+                    .ok_or_else(|| Error::Transport("HTTP transport not initialized".into()))?;
+                Ok(http_transport
+                    .send_request_streaming(
+                        url,
+                        transform_request(transforms, request),
+                        instance_headers(
+                            &config.proxy.routing,
+                            server_id,
+                            backend_headers(transforms, headers),
+                        ),
+                        BackendRequestOptions::for_server(server_config, &config),
+                        stream_from,
+                    )
+                    .await?)
+            },
+            crate::config::TransportConfig::StreamableHttp { .. } => {
+                Ok(streamable_http_transport(state, server_config)?
+                    .send_request_streaming(transform_request(transforms, request), stream_from)
+                    .await?)
+            },
+            _ => send_via_transport(state, server_config, request)
+                .await
+                .map(BackendReply::Parsed),
+        }
+    };
+
+    // The timeout bounds the exchange up to the start of the body
+    let start = Instant::now();
+    let reply = tokio::time::timeout(timeout, send)
+        .await
+        .map_err(|_| Error::BackendTimeout(server_config.request_timeout_ms()))
+        .and_then(|reply| reply);
+    let (success, status) = match &reply {
+        Ok(BackendReply::Parsed(response)) if response.error.is_some() => (false, "error"),
+        Ok(_) => (true, "success"),
+        Err(Error::BackendTimeout(_)) => (false, "timeout"),
+        Err(_) => (false, "failure"),
+    };
+    record_split_outcome(state, server_id, success, start);
+    note_backend(server_id);
+    crate::metrics::record_backend_request(server_id, "tools/call", status, start.elapsed());
+    reply.map_err(backend_error)
+}
+
+/// Map a backend failure to the error reported to the client.
+fn backend_error(error: Error) -> ProxyError {
+    match error {
+        Error::BackendTimeout(ms) => ProxyError::Timeout(format!("{}ms", ms)),
+        Error::Transport(e) => ProxyError::Transport(e),
+        e => e.into(),
+    }
+}
+
+/// Estimated tokens of a JSON value, see
+/// [`estimate_payload_tokens`](crate::metrics::tokens::estimate_payload_tokens).
+fn estimate_tokens(value: &Value) -> u64 {
+    crate::json::to_bytes(value)
+        .map(|bytes| crate::metrics::tokens::estimate_payload_tokens(&bytes))
+        .unwrap_or(0)
+}
+
+/// Charge a completed tools/call with its backend's cost model; the tokens
+/// are estimated over the call's params and the backend's response.
+fn record_tool_cost(
+    state: &AppState,
+    server_id: &str,
+    tool: &str,
+    request: &McpRequest,
+    response: &Value,
+) {
+    let tokens = estimate_tokens(&request.params()) + estimate_tokens(response);
+    charge_tool_cost(state, server_id, tool, tokens);
+}
+
+/// Charge a tools/call that moved `tokens` estimated tokens.
+fn charge_tool_cost(state: &AppState, server_id: &str, tool: &str, tokens: u64) {
+    let config = state.config.load();
+    let model = config.servers.iter().find(|s| s.id == server_id).and_then(|s| s.cost.as_ref());
+    let cost = state.costs.record(server_id, tool, model, tokens);
+    debug!(
+        "Tool {} on {} cost ${:.6} ({} tokens)",
+        tool, server_id, cost, tokens
+    );
+}
+
+/// Reject a call to `tool` on `server_id` unless the caller's roles allow it
+/// (`auth.policies`).
+fn authorize_tool(
+    state: &AppState,
+    caller: &Caller,
+    server_id: &str,
+    tool: &str,
+) -> std::result::Result<(), ProxyError> {
+    let config = state.config.load();
+    let server = config.servers.iter().find(|s| s.id == server_id);
+    if crate::auth::policy::tool_allowed(&config.auth, caller.roles(), server, tool) {
+        return Ok(());
+    }
+
+    let who = caller.principal.as_ref().map(|p| p.id.as_str()).unwrap_or("anonymous caller");
+    warn!("Policy denied tool {} to {}", tool, who);
+    Err(ProxyError::Forbidden(format!(
+        "Not allowed to call tool: {}",
+        tool
+    )))
 }
 
 /// Handle resources/list request.
@@ -219,77 +1820,70 @@ async fn handle_resources_list_impl(
     state: AppState,
     request: McpRequest,
 ) -> std::result::Result<Value, ProxyError> {
-    // Similar to tools/list but for resources
-    let start = Instant::now();
+    // Serve the precomputed catalog when the background refresher maintains one
+    if let Some(resources) = state.catalog.resources() {
+        return Ok(list_response(
+            &request,
+            list_result("resources", &resources.items, &resources.version, &[]),
+        ));
+    }
 
     // Check cache
-    let cache_key = format!("resources:list:{}", state.config.server.port);
-    if let Some(cached) = state.cache.get(&cache_key).await {
-        return Ok(serde_json::from_slice(&cached)?);
+    let cache_key = format!("resources:list:{}", state.config.load().server.port);
+    if let Some(cached) = state.cache.get_with_sources(&cache_key).await {
+        return cached_list_response(&request, cached);
     }
 
-    // Get all healthy servers and aggregate resources
-    let registry = state.registry.read().await;
-    let servers = registry.get_healthy_servers().await;
-
-    let mut all_resources = Vec::new();
-    for server in servers {
-        // Check if batching is enabled for this method
-        if state.config.context_optimization.batching.enabled
-            && state.config.context_optimization.batching.methods.contains(&request.method)
-        {
-            // Route through BatchAggregator
-            debug!(
-                "Routing resources/list through batch aggregator for server: {}",
-                server
-            );
-            match state
-                .batch_aggregator
-                .submit_request(server.clone(), request.clone())
-                .await
-                .and_then(|response| {
-                    // Parse response and extract resources array
-                    let result = response.result.ok_or_else(|| {
-                        Error::Server("No result in resources/list response".into())
-                    })?;
-
-                    let resources_value = result
-                        .get("resources")
-                        .ok_or_else(|| Error::Server("No resources field in response".into()))?;
-
-                    let resources: Vec<Resource> = serde_json::from_value(resources_value.clone())
-                        .map_err(|e| {
-                            Error::Serialization(format!("Failed to parse resources: {}", e))
-                        })?;
+    let aggregated = aggregate_resources(&state, &request, aggregation_budget(&state)).await;
+
+    let version = catalog_version(&aggregated.items);
+    let response = list_response(
+        &request,
+        list_result(
+            "resources",
+            &aggregated.items,
+            &version,
+            &aggregated.degraded,
+        ),
+    );
+    Ok(response)
+}
 
-                    Ok(resources)
-                }) {
-                Ok(resources) => all_resources.extend(resources),
-                Err(e) => warn!("Failed to fetch resources: {}", e),
-            }
-        } else {
-            // Direct backend call (existing path)
-            match fetch_resources_from_server(&state, server, request.clone()).await {
-                Ok(resources) => all_resources.extend(resources),
-                Err(e) => warn!("Failed to fetch resources: {}", e),
+/// Fetch and merge resources from all healthy servers, deduplicated by URI.
+pub(crate) async fn aggregate_resources(
+    state: &AppState,
+    request: &McpRequest,
+    budget: Option<Duration>,
+) -> Aggregated<Resource> {
+    let servers = capable_servers(state, "resources/list").await;
+
+    let mut aggregated =
+        aggregate_list(state, "resources/list", servers, budget, |state, server| {
+            let request = request.clone();
+            async move {
+                let ttl = server_catalog_ttl(&state);
+                if let Some(resources) = state.registry.read().await.fresh_resources(&server, ttl) {
+                    return Ok(resources);
+                }
+
+                let resources = if batching_enabled(&state, &request.method) {
+                    submit_batched_list(&state, server.clone(), request, "resources").await
+                } else {
+                    fetch_resources_from_server(&state, server.clone(), request).await
+                };
+                if let Ok(resources) = &resources {
+                    state.registry.read().await.set_server_resources(&server, resources.clone());
+                }
+                resources
             }
-        }
-    }
+        })
+        .await;
 
     // Deduplicate by URI
-    all_resources.sort_by(|a, b| a.uri.cmp(&b.uri));
-    all_resources.dedup_by(|a, b| a.uri == b.uri);
-
-    let response = json!({
-        "jsonrpc": "2.0",
-        "id": request.id(),
-        "result": {
-            "resources": all_resources
-        }
-    });
+    aggregated.items.sort_by(|a, b| a.uri.cmp(&b.uri));
+    aggregated.items.dedup_by(|a, b| a.uri == b.uri);
 
-    state.metrics.resources_list_duration().record(start.elapsed().as_secs_f64());
-    Ok(response)
+    aggregated
 }
 
 /// Handle resources/read request.
@@ -298,12 +1892,13 @@ pub async fn handle_resources_read(
     Json(payload): Json<Value>,
 ) -> std::result::Result<Json<Value>, ProxyError> {
     let request: McpRequest = serde_json::from_value(payload)?;
-    handle_resources_read_impl(state, request).await.map(Json)
+    handle_resources_read_impl(state, request, Caller::default()).await.map(Json)
 }
 
 async fn handle_resources_read_impl(
     state: AppState,
     request: McpRequest,
+    caller: Caller,
 ) -> std::result::Result<Value, ProxyError> {
     let uri = request
         .params()
@@ -313,22 +1908,7 @@ async fn handle_resources_read_impl(
         .ok_or_else(|| ProxyError::InvalidRequest("Missing resource URI".into()))?;
 
     debug!("Reading resource: {}", uri);
-
-    // Route to server that has this resource
-    let router = RequestRouter::new(state.config.proxy.routing.clone());
-    let (server_id, _) = router
-        .route_request(&request, &*state.registry.read().await, &state.cache)
-        .await?;
-
-    let server = {
-        let registry = state.registry.read().await;
-        registry
-            .get_server(&server_id)
-            .ok_or_else(|| ProxyError::NoBackendAvailable(uri.to_string()))?
-            .clone()
-    };
-
-    send_request_to_backend(state, server, request).await
+    cached_item_request(state, request, &uri, caller).await
 }
 
 /// Handle resources/subscribe for real-time updates.
@@ -381,69 +1961,62 @@ async fn handle_prompts_list_impl(
     state: AppState,
     request: McpRequest,
 ) -> std::result::Result<Value, ProxyError> {
-    // Similar aggregation pattern as tools/list
-    let cache_key = format!("prompts:list:{}", state.config.server.port);
-    if let Some(cached) = state.cache.get(&cache_key).await {
-        return Ok(serde_json::from_slice(&cached)?);
+    // Serve the precomputed catalog when the background refresher maintains one
+    if let Some(prompts) = state.catalog.prompts() {
+        return Ok(list_response(
+            &request,
+            list_result("prompts", &prompts.items, &prompts.version, &[]),
+        ));
     }
 
-    let registry = state.registry.read().await;
-    let servers = registry.get_healthy_servers().await;
+    // Similar aggregation pattern as tools/list
+    let cache_key = format!("prompts:list:{}", state.config.load().server.port);
+    if let Some(cached) = state.cache.get_with_sources(&cache_key).await {
+        return cached_list_response(&request, cached);
+    }
 
-    let mut all_prompts = Vec::new();
-    for server in servers {
-        // Check if batching is enabled for this method
-        if state.config.context_optimization.batching.enabled
-            && state.config.context_optimization.batching.methods.contains(&request.method)
-        {
-            // Route through BatchAggregator
-            debug!(
-                "Routing prompts/list through batch aggregator for server: {}",
-                server
-            );
-            match state
-                .batch_aggregator
-                .submit_request(server.clone(), request.clone())
-                .await
-                .and_then(|response| {
-                    // Parse response and extract prompts array
-                    let result = response.result.ok_or_else(|| {
-                        Error::Server("No result in prompts/list response".into())
-                    })?;
+    let aggregated = aggregate_prompts(&state, &request, aggregation_budget(&state)).await;
 
-                    let prompts_value = result
-                        .get("prompts")
-                        .ok_or_else(|| Error::Server("No prompts field in response".into()))?;
+    let version = catalog_version(&aggregated.items);
+    Ok(list_response(
+        &request,
+        list_result("prompts", &aggregated.items, &version, &aggregated.degraded),
+    ))
+}
 
-                    let prompts: Vec<Prompt> = serde_json::from_value(prompts_value.clone())
-                        .map_err(|e| {
-                            Error::Serialization(format!("Failed to parse prompts: {}", e))
-                        })?;
+/// Fetch and merge prompts from all healthy servers, deduplicated by name.
+pub(crate) async fn aggregate_prompts(
+    state: &AppState,
+    request: &McpRequest,
+    budget: Option<Duration>,
+) -> Aggregated<Prompt> {
+    let servers = capable_servers(state, "prompts/list").await;
 
-                    Ok(prompts)
-                }) {
-                Ok(prompts) => all_prompts.extend(prompts),
-                Err(e) => warn!("Failed to fetch prompts: {}", e),
+    let mut aggregated = aggregate_list(state, "prompts/list", servers, budget, |state, server| {
+        let request = request.clone();
+        async move {
+            let ttl = server_catalog_ttl(&state);
+            if let Some(prompts) = state.registry.read().await.fresh_prompts(&server, ttl) {
+                return Ok(prompts);
             }
-        } else {
-            // Direct backend call (existing path)
-            match fetch_prompts_from_server(&state, server, request.clone()).await {
-                Ok(prompts) => all_prompts.extend(prompts),
-                Err(e) => warn!("Failed to fetch prompts: {}", e),
+
+            let prompts = if batching_enabled(&state, &request.method) {
+                submit_batched_list(&state, server.clone(), request, "prompts").await
+            } else {
+                fetch_prompts_from_server(&state, server.clone(), request).await
+            };
+            if let Ok(prompts) = &prompts {
+                state.registry.read().await.set_server_prompts(&server, prompts.clone());
             }
+            prompts
         }
-    }
+    })
+    .await;
 
-    all_prompts.sort_by(|a, b| a.name.cmp(&b.name));
-    all_prompts.dedup_by(|a, b| a.name == b.name);
+    aggregated.items.sort_by(|a, b| a.name.cmp(&b.name));
+    aggregated.items.dedup_by(|a, b| a.name == b.name);
 
-    Ok(json!({
-        "jsonrpc": "2.0",
-        "id": request.id(),
-        "result": {
-            "prompts": all_prompts
-        }
-    }))
+    aggregated
 }
 
 /// Handle prompts/get request.
@@ -452,12 +2025,13 @@ pub async fn handle_prompts_get(
     Json(payload): Json<Value>,
 ) -> std::result::Result<Json<Value>, ProxyError> {
     let request: McpRequest = serde_json::from_value(payload)?;
-    handle_prompts_get_impl(state, request).await.map(Json)
+    handle_prompts_get_impl(state, request, Caller::default()).await.map(Json)
 }
 
 async fn handle_prompts_get_impl(
     state: AppState,
     request: McpRequest,
+    caller: Caller,
 ) -> std::result::Result<Value, ProxyError> {
     let name = request
         .params()
@@ -467,22 +2041,74 @@ async fn handle_prompts_get_impl(
         .ok_or_else(|| ProxyError::InvalidRequest("Missing prompt name".into()))?;
 
     debug!("Getting prompt: {}", name);
+    cached_item_request(state, request, &name, caller).await
+}
 
-    // Route to appropriate server
-    let router = RequestRouter::new(state.config.proxy.routing.clone());
-    let (server_id, _) = router
-        .route_request(&request, &*state.registry.read().await, &state.cache)
-        .await?;
+/// Answer a request for one item (a resource URI or a prompt name) from the
+/// response cache, or forward it to the backend it routes to and cache a
+/// successful result under a key covering the item and all params.
+async fn cached_item_request(
+    state: AppState,
+    request: McpRequest,
+    item: &str,
+    caller: Caller,
+) -> std::result::Result<Value, ProxyError> {
+    let cache_key = ResponseCache::content_key(&request.method, item, &request.params());
+    let cached = state
+        .cache
+        .get_or_revalidate(&cache_key, || {
+            let (state, request, key, caller) = (
+                state.clone(),
+                request.clone(),
+                cache_key.clone(),
+                caller.clone(),
+            );
+            async move {
+                if let Err(e) = fetch_item(&state, request, key, &caller).await {
+                    warn!("Failed to refresh cached item: {}", e);
+                }
+            }
+        })
+        .await;
+    if let Some((cached, sources)) = cached {
+        debug!("Cache hit for {} {}", request.method, item);
+        sources.record("cache_hit");
+        return Ok(json!({
+            "jsonrpc": "2.0",
+            "id": request.id(),
+            "result": crate::json::from_slice::<Value>(&cached)?
+        }));
+    }
 
-    let server = {
-        let registry = state.registry.read().await;
-        registry
-            .get_server(&server_id)
-            .ok_or_else(|| ProxyError::NoBackendAvailable(name.to_string()))?
-            .clone()
-    };
+    fetch_item(&state, request, cache_key, &caller).await
+}
 
-    send_request_to_backend(state, server, request).await
+/// Forward an item request to the backend it routes to and cache a
+/// successful result under `cache_key`.
+async fn fetch_item(
+    state: &AppState,
+    request: McpRequest,
+    cache_key: String,
+    caller: &Caller,
+) -> std::result::Result<Value, ProxyError> {
+    let (server_id, _) = request_router(state)
+        .route_request(
+            &request,
+            caller.client_id.as_deref(),
+            &*state.registry.read().await,
+            &state.cache,
+        )
+        .await?;
+
+    let method = request.method.clone();
+    let response = call_backend(state, &server_id, request).await?;
+    if let (Some(result), None) = (response.get("result"), response.get("error")) {
+        if let Ok(serialized) = crate::json::to_bytes(result) {
+            let sources = TokenSources::server(&server_id, &serialized);
+            state.cache.set_from(cache_key, serialized, &method, sources).await;
+        }
+    }
+    Ok(response)
 }
 
 /// Handle sampling/createMessage request.
@@ -491,17 +2117,22 @@ pub async fn handle_sampling_create(
     Json(payload): Json<Value>,
 ) -> std::result::Result<Json<Value>, ProxyError> {
     let request: McpRequest = serde_json::from_value(payload)?;
-    handle_sampling_create_impl(state, request).await.map(Json)
+    handle_sampling_create_impl(state, request, Caller::default()).await.map(Json)
 }
 
 async fn handle_sampling_create_impl(
     state: AppState,
     request: McpRequest,
+    caller: Caller,
 ) -> std::result::Result<Value, ProxyError> {
     // Route to a capable server
-    let router = RequestRouter::new(state.config.proxy.routing.clone());
-    let (server_id, _) = router
-        .route_request(&request, &*state.registry.read().await, &state.cache)
+    let (server_id, _) = request_router(&state)
+        .route_request(
+            &request,
+            caller.client_id.as_deref(),
+            &*state.registry.read().await,
+            &state.cache,
+        )
         .await?;
 
     let server = {
@@ -540,10 +2171,15 @@ pub async fn handle_sse_stream(
 async fn route_generic_request(
     state: AppState,
     request: McpRequest,
+    caller: Caller,
 ) -> std::result::Result<Value, ProxyError> {
-    let router = RequestRouter::new(state.config.proxy.routing.clone());
-    let (server_id, _) = router
-        .route_request(&request, &*state.registry.read().await, &state.cache)
+    let (server_id, _) = request_router(&state)
+        .route_request(
+            &request,
+            caller.client_id.as_deref(),
+            &*state.registry.read().await,
+            &state.cache,
+        )
         .await?;
 
     let server = {
@@ -559,95 +2195,181 @@ async fn route_generic_request(
 
 // Helper functions
 
-async fn fetch_tools_from_server(
+/// Fetch a backend's capabilities: those already in the registry, those
+/// reported in its handshake for STDIO, or else by sending it an
+/// `initialize` request. Newly learned capabilities are recorded in the
+/// registry.
+async fn fetch_capabilities_from_server(
     state: AppState,
     server_id: String,
-    request: McpRequest,
-) -> Result<Vec<Tool>> {
-    // Get server config from the config (not registry, as registry only has ServerInfo)
-    let server_config = state
-        .config
+) -> Result<Vec<ServerCapabilities>> {
+    if let Some(capabilities) = state.registry.read().await.server_capabilities(&server_id) {
+        return Ok(vec![capabilities]);
+    }
+
+    let config = state.config.load_full();
+    let server_config = config
         .servers
         .iter()
         .find(|s| s.id == server_id)
         .ok_or_else(|| Error::ServerNotFound(server_id.clone()))?;
 
-    // Create tools/list JSON-RPC request
-    let tools_request = McpRequest::new("tools/list", serde_json::json!({}), request.id());
+    if let Some(stdio_config) = stdio_config(&state, server_config) {
+        let stdio_transport = state
+            .stdio_transport
+            .as_ref()
+            .ok_or_else(|| Error::Transport("STDIO transport not initialized".into()))?;
+        let capabilities = stdio_transport
+            .capabilities_with_config(server_id.clone(), &stdio_config)
+            .await
+            .map_err(|e| Error::Transport(e.to_string()))?;
+        state
+            .registry
+            .read()
+            .await
+            .set_server_capabilities(&server_id, capabilities.clone());
+        return Ok(vec![capabilities]);
+    }
 
-    // Send via appropriate transport
-    let response = match &server_config.transport {
-        crate::config::TransportConfig::Http { url, headers } => {
-            let http_transport = state
-                .http_transport
-                .as_ref()
-                .ok_or_else(|| Error::Transport("HTTP transport not initialized".into()))?;
+    let init_request = McpRequest::new(
+        "initialize",
+        json!({
+            "protocolVersion": "2024-11-05",
+            "capabilities": {},
+            "clientInfo": {
+                "name": "Only1MCP",
+                "version": env!("CARGO_PKG_VERSION")
+            }
+        }),
+        Some(json!(0)),
+    );
+    let response = send_to_server(&state, &server_id, init_request).await?;
 
-            http_transport
-                .send_request_with_headers(url, tools_request, headers.clone())
-                .await
-                .map_err(|e| Error::Transport(e.to_string()))?
-        },
-        crate::config::TransportConfig::Stdio { command, args, env } => {
-            let stdio_transport = state
-                .stdio_transport
-                .as_ref()
-                .ok_or_else(|| Error::Transport("STDIO transport not initialized".into()))?;
+    let capabilities = response
+        .result()
+        .and_then(|result| result.get("capabilities").cloned())
+        .ok_or_else(|| Error::Server("No capabilities in initialize response".into()))?;
+    let capabilities: ServerCapabilities = serde_json::from_value(capabilities)
+        .map_err(|e| Error::Serialization(format!("Failed to parse capabilities: {}", e)))?;
+    state
+        .registry
+        .read()
+        .await
+        .set_server_capabilities(&server_id, capabilities.clone());
+    Ok(vec![capabilities])
+}
 
-            // Create STDIO config
-            let stdio_config = crate::transport::stdio::StdioConfig {
-                command: command.clone(),
-                args: args.clone(),
-                env: env.clone(),
-                cwd: None,
-                timeout_ms: 30000,
-                max_memory_mb: Some(512),
-                max_cpu_percent: Some(50),
-                sandbox: true,
-            };
+/// How long a server's discovered lists are reused
+/// (`context_optimization.aggregation.server_catalog_ttl_seconds`).
+fn server_catalog_ttl(state: &AppState) -> Duration {
+    state.config.load().context_optimization.aggregation.server_catalog_ttl()
+}
 
-            stdio_transport
-                .send_request_with_config(server_id.clone(), &stdio_config, tools_request)
-                .await
-                .map_err(|e| Error::Transport(e.to_string()))?
-        },
-        crate::config::TransportConfig::Sse { url, headers } => {
-            let sse_transport = state
-                .sse_transport
-                .as_ref()
-                .ok_or_else(|| Error::Transport("SSE transport not initialized".into()))?;
+/// A server's tools, from the registry while fresh, otherwise fetched from
+/// the backend and recorded in the registry.
+pub(crate) async fn server_tools(state: &AppState, server_id: &str) -> Result<Vec<Tool>> {
+    let ttl = server_catalog_ttl(state);
+    if let Some(tools) = state.registry.read().await.fresh_tools(server_id, ttl) {
+        return Ok(tools);
+    }
 
-            sse_transport
-                .send_request_with_headers(url, tools_request, headers.clone())
-                .await
-                .map_err(|e| Error::Transport(e.to_string()))?
-        },
-        crate::config::TransportConfig::StreamableHttp {
-            url,
-            headers,
-            timeout_ms,
-        } => {
-            let streamable_http_transport =
-                state.streamable_http_transport.as_ref().ok_or_else(|| {
-                    Error::Transport("Streamable HTTP transport not initialized".into())
-                })?;
-
-            // Create transport config
-            let transport_config = crate::transport::streamable_http::StreamableHttpConfig {
-                url: url.clone(),
-                headers: headers.clone(),
-                timeout_ms: *timeout_ms,
-            };
+    let request = McpRequest::new("tools/list", json!({}), Some(json!(1)));
+    let tools = fetch_tools_from_server(state.clone(), server_id.to_string(), request).await?;
+    state.registry.read().await.set_server_tools(server_id, tools.clone());
+    Ok(tools)
+}
 
-            // Get or create transport (maintains session)
-            let transport = streamable_http_transport.get_or_create(transport_config);
+/// Fetch a server's tools, resources and prompts again, whatever the age of
+/// what the registry holds. Lists the server does not advertise are skipped,
+/// and lists it fails to return stay unknown; it is an error only if none
+/// could be fetched.
+pub(crate) async fn refresh_server_catalog(state: &AppState, server_id: &str) -> Result<()> {
+    state.registry.read().await.invalidate_catalog(Some(server_id));
 
-            transport
-                .send_request(tools_request)
-                .await
-                .map_err(|e| Error::Transport(e.to_string()))?
+    let request = |method: &str| McpRequest::new(method, json!({}), Some(json!(1)));
+    let supports = |method: &str| {
+        let state = state.clone();
+        let method = method.to_string();
+        async move { state.registry.read().await.supports_method(server_id, &method) }
+    };
+    let mut last_error = None;
+    let mut refreshed = false;
+
+    match fetch_tools_from_server(state.clone(), server_id.to_string(), request("tools/list")).await
+    {
+        Ok(tools) => {
+            state.registry.read().await.set_server_tools(server_id, tools);
+            refreshed = true;
+        },
+        Err(e) => last_error = Some(e),
+    }
+    if supports("resources/list").await {
+        match fetch_resources_from_server(state, server_id.to_string(), request("resources/list"))
+            .await
+        {
+            Ok(resources) => {
+                state.registry.read().await.set_server_resources(server_id, resources);
+                refreshed = true;
+            },
+            Err(e) => last_error = Some(e),
+        }
+    }
+    if supports("prompts/list").await {
+        match fetch_prompts_from_server(state, server_id.to_string(), request("prompts/list")).await
+        {
+            Ok(prompts) => {
+                state.registry.read().await.set_server_prompts(server_id, prompts);
+                refreshed = true;
+            },
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    match last_error {
+        Some(e) if !refreshed => Err(e),
+        Some(e) => {
+            debug!("Partial catalog refresh of {}: {}", server_id, e);
+            Ok(())
         },
+        None => Ok(()),
+    }
+}
+
+/// Fetch one page of a server's tool list and its `nextCursor`.
+async fn fetch_tools_page(
+    state: &AppState,
+    server_id: String,
+    cursor: Option<String>,
+    id: Option<Value>,
+) -> Result<(Vec<Tool>, Option<String>)> {
+    let params = match cursor {
+        Some(cursor) => json!({ "cursor": cursor }),
+        None => json!({}),
     };
+    let response =
+        send_coalesced(state, server_id, McpRequest::new("tools/list", params, id)).await?;
+    let result = response
+        .result()
+        .ok_or_else(|| Error::Server("No result in tools/list response".into()))?;
+
+    let tools = result
+        .get("tools")
+        .cloned()
+        .ok_or_else(|| Error::Server("No tools field in response".into()))?;
+    let tools: Vec<Tool> = serde_json::from_value(tools)
+        .map_err(|e| Error::Serialization(format!("Failed to parse tools: {}", e)))?;
+    let next = result.get("nextCursor").and_then(Value::as_str).map(String::from);
+    Ok((tools, next))
+}
+
+/// Fetch a server's tool list, coalescing identical in-flight requests.
+async fn fetch_tools_from_server(
+    state: AppState,
+    server_id: String,
+    request: McpRequest,
+) -> Result<Vec<Tool>> {
+    let tools_request = McpRequest::new("tools/list", serde_json::json!({}), request.id());
+    let response = send_coalesced(&state, server_id, tools_request).await?;
 
     // Parse response and extract tools array
     let result = response
@@ -664,92 +2386,14 @@ async fn fetch_tools_from_server(
     Ok(tools)
 }
 
+/// Fetch a server's resource list, coalescing identical in-flight requests.
 async fn fetch_resources_from_server(
     state: &AppState,
     server_id: String,
     request: McpRequest,
 ) -> Result<Vec<Resource>> {
-    // Get server config
-    let server_config = state
-        .config
-        .servers
-        .iter()
-        .find(|s| s.id == server_id)
-        .ok_or_else(|| Error::ServerNotFound(server_id.clone()))?;
-
-    // Create resources/list JSON-RPC request
     let resources_request = McpRequest::new("resources/list", serde_json::json!({}), request.id());
-
-    // Send via appropriate transport
-    let response = match &server_config.transport {
-        crate::config::TransportConfig::Http { url, headers } => {
-            let http_transport = state
-                .http_transport
-                .as_ref()
-                .ok_or_else(|| Error::Transport("HTTP transport not initialized".into()))?;
-
-            http_transport
-                .send_request_with_headers(url, resources_request, headers.clone())
-                .await
-                .map_err(|e| Error::Transport(e.to_string()))?
-        },
-        crate::config::TransportConfig::Stdio { command, args, env } => {
-            let stdio_transport = state
-                .stdio_transport
-                .as_ref()
-                .ok_or_else(|| Error::Transport("STDIO transport not initialized".into()))?;
-
-            let stdio_config = crate::transport::stdio::StdioConfig {
-                command: command.clone(),
-                args: args.clone(),
-                env: env.clone(),
-                cwd: None,
-                timeout_ms: 30000,
-                max_memory_mb: Some(512),
-                max_cpu_percent: Some(50),
-                sandbox: true,
-            };
-
-            stdio_transport
-                .send_request_with_config(server_id.clone(), &stdio_config, resources_request)
-                .await
-                .map_err(|e| Error::Transport(e.to_string()))?
-        },
-        crate::config::TransportConfig::Sse { url, headers } => {
-            let sse_transport = state
-                .sse_transport
-                .as_ref()
-                .ok_or_else(|| Error::Transport("SSE transport not initialized".into()))?;
-
-            sse_transport
-                .send_request_with_headers(url, resources_request, headers.clone())
-                .await
-                .map_err(|e| Error::Transport(e.to_string()))?
-        },
-        crate::config::TransportConfig::StreamableHttp {
-            url,
-            headers,
-            timeout_ms,
-        } => {
-            let streamable_http_transport =
-                state.streamable_http_transport.as_ref().ok_or_else(|| {
-                    Error::Transport("Streamable HTTP transport not initialized".into())
-                })?;
-
-            let transport_config = crate::transport::streamable_http::StreamableHttpConfig {
-                url: url.clone(),
-                headers: headers.clone(),
-                timeout_ms: *timeout_ms,
-            };
-
-            let transport = streamable_http_transport.get_or_create(transport_config);
-
-            transport
-                .send_request(resources_request)
-                .await
-                .map_err(|e| Error::Transport(e.to_string()))?
-        },
-    };
+    let response = send_coalesced(state, server_id, resources_request).await?;
 
     // Parse response and extract resources array
     let result = response
@@ -766,106 +2410,393 @@ async fn fetch_resources_from_server(
     Ok(resources)
 }
 
+/// Fetch a server's prompt list, coalescing identical in-flight requests.
 async fn fetch_prompts_from_server(
     state: &AppState,
     server_id: String,
     request: McpRequest,
 ) -> Result<Vec<Prompt>> {
-    // Get server config
-    let server_config = state
-        .config
+    let prompts_request = McpRequest::new("prompts/list", serde_json::json!({}), request.id());
+    let response = send_coalesced(state, server_id, prompts_request).await?;
+
+    // Parse response and extract prompts array
+    let result = response
+        .result()
+        .ok_or_else(|| Error::Server("No result in prompts/list response".into()))?;
+
+    let prompts_value = result
+        .get("prompts")
+        .ok_or_else(|| Error::Server("No prompts field in response".into()))?;
+
+    let prompts: Vec<Prompt> = serde_json::from_value(prompts_value.clone())
+        .map_err(|e| Error::Serialization(format!("Failed to parse prompts: {}", e)))?;
+
+    Ok(prompts)
+}
+
+/// Send a request to a backend, sharing the response with any identical
+/// request to the same server that is already in flight.
+///
+/// The coalescing key is the server ID plus the cache key of the method and
+/// params, so the JSON-RPC id of each caller does not prevent deduplication.
+async fn send_coalesced(
+    state: &AppState,
+    server_id: String,
+    request: McpRequest,
+) -> Result<McpResponse> {
+    let method = request.method();
+    let key = format!(
+        "{}:{}",
+        server_id,
+        ResponseCache::cache_key(&method, &request.params())
+    );
+
+    let leader_state = state.clone();
+    let leader_server = server_id.clone();
+    let (response, shared) = state
+        .coalescer
+        .run_shared(key, &method, async move {
+            send_to_server(&leader_state, &leader_server, request).await
+        })
+        .await;
+    if let (true, Ok(response)) = (shared, &response) {
+        if let Ok(serialized) = crate::json::to_bytes(response) {
+            crate::metrics::record_tokens_saved(&server_id, "deduplication", &serialized);
+        }
+    }
+    response
+}
+
+/// Send a request to a configured backend via its transport, recording it
+/// in the backend's request metrics.
+pub(crate) async fn send_to_server(
+    state: &AppState,
+    server_id: &str,
+    request: McpRequest,
+) -> Result<McpResponse> {
+    let method = request.method.clone();
+    let start = Instant::now();
+    let response = send_with_timeout(state, server_id, request).await;
+    record_backend_outcome(server_id, &method, &response, start);
+    response
+}
+
+/// Record a backend's reply (or failure) in the request metrics.
+fn record_backend_outcome(
+    server_id: &str,
+    method: &str,
+    response: &Result<McpResponse>,
+    start: Instant,
+) {
+    let status = match response {
+        Ok(response) if response.error.is_some() => "error",
+        Ok(_) => "success",
+        Err(Error::BackendTimeout(_)) => "timeout",
+        Err(_) => "failure",
+    };
+    note_backend(server_id);
+    crate::metrics::record_backend_request(server_id, method, status, start.elapsed());
+}
+
+/// Send a request to a configured backend within its request timeout.
+async fn send_with_timeout(
+    state: &AppState,
+    server_id: &str,
+    request: McpRequest,
+) -> Result<McpResponse> {
+    // Get server config from the live config (not registry, as registry only has ServerInfo)
+    let config = state.config.load_full();
+    let server_config = config
         .servers
         .iter()
         .find(|s| s.id == server_id)
-        .ok_or_else(|| Error::ServerNotFound(server_id.clone()))?;
+        .ok_or_else(|| Error::ServerNotFound(server_id.to_string()))?;
+
+    // The timeout bounds the whole exchange, including pool checkout and
+    // injected delays
+    let timeout = server_config.request_timeout();
+    let injection = Injection::for_request(&config.proxy.chaos, server_id, &request.method);
+    let send = async {
+        injection.delay().await;
+        match injection.fault {
+            Some(Fault::Error(error)) => Ok(McpResponse::error(request.id, error)),
+            Some(Fault::Drop) => {
+                let _ = send_via_transport(state, server_config, request).await;
+                std::future::pending().await
+            },
+            None => send_via_transport(state, server_config, request).await,
+        }
+    };
+    tokio::time::timeout(timeout, send)
+        .await
+        .map_err(|_| Error::BackendTimeout(server_config.request_timeout_ms()))?
+}
 
-    // Create prompts/list JSON-RPC request
-    let prompts_request = McpRequest::new("prompts/list", serde_json::json!({}), request.id());
+/// Dispatch a request over the transport configured for `server_config`.
+async fn send_via_transport(
+    state: &AppState,
+    server_config: &crate::config::McpServerConfig,
+    request: McpRequest,
+) -> Result<McpResponse> {
+    let server_id = server_config.id.as_str();
+    let timeout = server_config.request_timeout();
+    let transforms = &server_config.transforms;
+    let method = request.method.clone();
+    let request = transform_request(transforms, request);
+    let config = state.config.load();
+    let proxy = config.outbound_proxy(&server_config.transport);
 
     // Send via appropriate transport
     let response = match &server_config.transport {
-        crate::config::TransportConfig::Http { url, headers } => {
+        crate::config::TransportConfig::Http { url, headers, .. } => {
             let http_transport = state
                 .http_transport
                 .as_ref()
                 .ok_or_else(|| Error::Transport("HTTP transport not initialized".into()))?;
 
             http_transport
-                .send_request_with_headers(url, prompts_request, headers.clone())
-                .await
-                .map_err(|e| Error::Transport(e.to_string()))?
+                .send_request_with_auth(
+                    url,
+                    request,
+                    instance_headers(
+                        &config.proxy.routing,
+                        server_id,
+                        backend_headers(transforms, headers),
+                    ),
+                    BackendRequestOptions::for_server(server_config, &config),
+                )
+                .await?
         },
-        crate::config::TransportConfig::Stdio { command, args, env } => {
+        crate::config::TransportConfig::Stdio { .. }
+        | crate::config::TransportConfig::Docker { .. } => {
             let stdio_transport = state
                 .stdio_transport
                 .as_ref()
                 .ok_or_else(|| Error::Transport("STDIO transport not initialized".into()))?;
-
-            let stdio_config = crate::transport::stdio::StdioConfig {
-                command: command.clone(),
-                args: args.clone(),
-                env: env.clone(),
-                cwd: None,
-                timeout_ms: 30000,
-                max_memory_mb: Some(512),
-                max_cpu_percent: Some(50),
-                sandbox: true,
-            };
+            let stdio_config = stdio_config(state, server_config)
+                .ok_or_else(|| Error::Transport("Not a STDIO backend".into()))?;
 
             stdio_transport
-                .send_request_with_config(server_id.clone(), &stdio_config, prompts_request)
+                .send_request_with_config(server_id.to_string(), &stdio_config, request)
                 .await
                 .map_err(|e| Error::Transport(e.to_string()))?
         },
-        crate::config::TransportConfig::Sse { url, headers } => {
+        crate::config::TransportConfig::Sse { url, headers, .. } => {
             let sse_transport = state
                 .sse_transport
                 .as_ref()
                 .ok_or_else(|| Error::Transport("SSE transport not initialized".into()))?;
 
             sse_transport
-                .send_request_with_headers(url, prompts_request, headers.clone())
-                .await
-                .map_err(|e| Error::Transport(e.to_string()))?
+                .send_request_with_options(
+                    url,
+                    request,
+                    backend_headers(transforms, headers),
+                    Some(timeout),
+                    proxy,
+                )
+                .await?
+        },
+        crate::config::TransportConfig::StreamableHttp { .. } => {
+            streamable_http_transport(state, server_config)?.send_request(request).await?
         },
-        crate::config::TransportConfig::StreamableHttp {
-            url,
-            headers,
-            timeout_ms,
+        crate::config::TransportConfig::Grpc {
+            endpoint,
+            metadata,
+            tls,
         } => {
-            let streamable_http_transport =
-                state.streamable_http_transport.as_ref().ok_or_else(|| {
-                    Error::Transport("Streamable HTTP transport not initialized".into())
-                })?;
-
-            let transport_config = crate::transport::streamable_http::StreamableHttpConfig {
-                url: url.clone(),
-                headers: headers.clone(),
-                timeout_ms: *timeout_ms,
-            };
+            let grpc_transport = state
+                .grpc_transport
+                .as_ref()
+                .ok_or_else(|| Error::Transport("gRPC transport not initialized".into()))?;
 
-            let transport = streamable_http_transport.get_or_create(transport_config);
+            grpc_transport
+                .send_request(endpoint, request, metadata, Some(timeout), tls.as_ref())
+                .await?
+        },
+        crate::config::TransportConfig::Unix { path } => {
+            let unix_transport = state
+                .unix_transport
+                .as_ref()
+                .ok_or_else(|| Error::Transport("Unix socket transport not initialized".into()))?;
 
-            transport
-                .send_request(prompts_request)
+            unix_transport
+                .send_request(path, request, timeout)
                 .await
                 .map_err(|e| Error::Transport(e.to_string()))?
         },
     };
 
-    // Parse response and extract prompts array
-    let result = response
-        .result()
-        .ok_or_else(|| Error::Server("No result in prompts/list response".into()))?;
+    Ok(transform_response(transforms, &method, response))
+}
 
-    let prompts_value = result
-        .get("prompts")
-        .ok_or_else(|| Error::Server("No prompts field in response".into()))?;
+/// The Streamable HTTP transport, with its session, for `server_config`.
+fn streamable_http_transport(
+    state: &AppState,
+    server_config: &crate::config::McpServerConfig,
+) -> Result<std::sync::Arc<crate::transport::streamable_http::StreamableHttpTransport>> {
+    let crate::config::TransportConfig::StreamableHttp {
+        url,
+        headers,
+        oauth,
+        tls,
+        signing,
+        ..
+    } = &server_config.transport
+    else {
+        return Err(Error::Transport("Not a Streamable HTTP backend".into()));
+    };
+    let pool = state
+        .streamable_http_transport
+        .as_ref()
+        .ok_or_else(|| Error::Transport("Streamable HTTP transport not initialized".into()))?;
+
+    // Create transport config
+    let transport_config = crate::transport::streamable_http::StreamableHttpConfig {
+        url: url.clone(),
+        headers: backend_headers(&server_config.transforms, headers),
+        timeout_ms: server_config.request_timeout_ms(),
+        oauth: oauth.clone(),
+        tls: tls.clone(),
+        signing: signing.clone(),
+        proxy: state.config.load().outbound_proxy(&server_config.transport).cloned(),
+    };
 
-    let prompts: Vec<Prompt> = serde_json::from_value(prompts_value.clone())
-        .map_err(|e| Error::Serialization(format!("Failed to parse prompts: {}", e)))?;
+    // Get or create transport (maintains session)
+    Ok(pool.get_or_create(transport_config))
+}
 
-    Ok(prompts)
+/// Start a STDIO server's processes ahead of its first request, recording
+/// progress in [`AppState::startup`]. Failures are logged; requests start
+/// the processes on demand as usual.
+pub(crate) async fn start_stdio_server(state: &AppState, server_id: &str) {
+    let config = state.config.load();
+    let Some(server_config) = config.servers.iter().find(|s| s.id == server_id) else {
+        return;
+    };
+    let (Some(stdio), Some(stdio_config)) =
+        (&state.stdio_transport, stdio_config(state, server_config))
+    else {
+        return;
+    };
+    let status = |state: StartupState, error: Option<String>| StartupStatus {
+        policy: server_config.startup.as_str().to_string(),
+        state,
+        error,
+    };
+
+    info!(
+        "Starting STDIO server {} ({})",
+        server_id,
+        server_config.startup.as_str()
+    );
+    state
+        .startup
+        .insert(server_id.to_string(), status(StartupState::Starting, None));
+    let start = Instant::now();
+    let result = stdio.prestart_with_config(server_id.to_string(), &stdio_config).await;
+
+    let status = match result {
+        Ok(()) => {
+            info!("STDIO server {} ready in {:?}", server_id, start.elapsed());
+            status(StartupState::Ready, None)
+        },
+        Err(e) => {
+            let error = crate::config::redact::redact_values(
+                &e.to_string(),
+                &server_config.redacted_env_values(),
+            );
+            warn!("Failed to start STDIO server {}: {}", server_id, error);
+            status(StartupState::Failed, Some(error))
+        },
+    };
+    state.startup.insert(server_id.to_string(), status);
+}
+
+/// Connect to an HTTP, Streamable HTTP, gRPC or Unix socket backend ahead
+/// of its first request (`proxy.connection_pool.prewarm`); a Streamable HTTP backend also
+/// gets its session. Failures are logged; requests connect on demand as usual.
+pub(crate) async fn prewarm_server(state: &AppState, server_id: &str) {
+    let config = state.config.load_full();
+    let Some(server_config) = config.servers.iter().find(|s| s.id == server_id) else {
+        return;
+    };
+
+    let start = Instant::now();
+    let warm = async {
+        match &server_config.transport {
+            crate::config::TransportConfig::Http { url, tls, .. } => {
+                let Some(http_transport) = &state.http_transport else {
+                    return Ok(());
+                };
+                http_transport
+                    .prewarm(
+                        url,
+                        tls.as_ref(),
+                        config.outbound_proxy(&server_config.transport),
+                    )
+                    .await
+                    .map_err(Error::from)?;
+                report_pool_stats(state, server_config);
+                Ok(())
+            },
+            crate::config::TransportConfig::StreamableHttp { .. } => {
+                Ok(streamable_http_transport(state, server_config)?.ensure_session().await?)
+            },
+            crate::config::TransportConfig::Grpc { endpoint, tls, .. } => {
+                let Some(grpc_transport) = &state.grpc_transport else {
+                    return Ok(());
+                };
+                Ok(grpc_transport.prewarm(endpoint, tls.as_ref()).await?)
+            },
+            crate::config::TransportConfig::Unix { path } => {
+                let Some(unix_transport) = &state.unix_transport else {
+                    return Ok(());
+                };
+                unix_transport.prewarm(path).await.map_err(|e| Error::Transport(e.to_string()))
+            },
+            _ => Ok(()),
+        }
+    };
+    let result = tokio::time::timeout(server_config.request_timeout(), warm)
+        .await
+        .unwrap_or_else(|_| Err(Error::BackendTimeout(server_config.request_timeout_ms())));
+
+    match result {
+        Ok(()) => debug!("Prewarmed {} in {:?}", server_id, start.elapsed()),
+        Err(e) => warn!("Failed to prewarm {}: {}", server_id, e),
+    }
+}
+
+/// Publish the connection pool of an HTTP backend in
+/// `only1mcp_connection_pool_size`.
+pub(crate) fn report_pool_stats(state: &AppState, server_config: &crate::config::McpServerConfig) {
+    let crate::config::TransportConfig::Http { url, tls, .. } = &server_config.transport else {
+        return;
+    };
+    let config = state.config.load();
+    let proxy = config.outbound_proxy(&server_config.transport);
+    let stats = state
+        .http_transport
+        .as_ref()
+        .and_then(|pool| pool.pool_stats(url, tls.as_ref(), proxy));
+    if let Some(stats) = stats {
+        crate::metrics::update_connection_pool(
+            &server_config.id,
+            stats.connections.saturating_sub(stats.idle_connections) as usize,
+            stats.idle_connections as usize,
+            stats.pending_connections as usize,
+        );
+    }
+}
+
+/// Process settings for a backend using the STDIO transport, `None` for
+/// other transports.
+pub(crate) fn stdio_config(
+    state: &AppState,
+    server_config: &crate::config::McpServerConfig,
+) -> Option<StdioConfig> {
+    StdioConfig::for_server(server_config, &state.config.load())
 }
 
 async fn send_request_to_backend(
@@ -875,47 +2806,62 @@ async fn send_request_to_backend(
 ) -> std::result::Result<Value, ProxyError> {
     use crate::proxy::registry::TransportType;
 
+    // Count the request against the backend until it completes or fails
+    let admission = state.config.load().proxy.admission.clone();
+    let _admitted = state.admission.admit_backend(&admission, &server.id).await?;
+    let _in_flight = state.connections.acquire(&server.id);
+    let method = request.method.clone();
     let start = Instant::now();
 
     // Route based on transport type
-    let response = match server.transport {
-        TransportType::Http => {
-            let http_transport = state
-                .http_transport
-                .as_ref()
-                .ok_or_else(|| ProxyError::Transport("HTTP transport not available".into()))?;
-            http_transport
-                .send_request(&server.endpoint, request)
-                .await
-                .map_err(|e| ProxyError::Transport(e.to_string()))?
-        },
-        TransportType::Stdio => {
-            let stdio_transport = state
-                .stdio_transport
-                .as_ref()
-                .ok_or_else(|| ProxyError::Transport("STDIO transport not available".into()))?;
-            stdio_transport
-                .send_request(&server.id, request)
-                .await
-                .map_err(|e| ProxyError::Transport(e.to_string()))?
-        },
-        TransportType::WebSocket => {
-            return Err(ProxyError::Transport(
-                "WebSocket not yet implemented".into(),
-            ));
-        },
-        TransportType::Sse => {
-            return Err(ProxyError::Transport("SSE not yet implemented".into()));
-        },
-        TransportType::StreamableHttp => {
-            return Err(ProxyError::Transport(
-                "StreamableHttp in route_generic_request not yet implemented".into(),
-            ));
-        },
-    };
+    let response =
+        async {
+            match server.transport {
+                TransportType::Http => {
+                    let http_transport = state.http_transport.as_ref().ok_or_else(|| {
+                        ProxyError::Transport("HTTP transport not available".into())
+                    })?;
+                    http_transport
+                        .send_request(&server.endpoint, request)
+                        .await
+                        .map_err(|e| ProxyError::Transport(e.to_string()))
+                },
+                TransportType::Stdio => {
+                    let stdio_transport = state.stdio_transport.as_ref().ok_or_else(|| {
+                        ProxyError::Transport("STDIO transport not available".into())
+                    })?;
+                    stdio_transport
+                        .send_request(&server.id, request)
+                        .await
+                        .map_err(|e| ProxyError::Transport(e.to_string()))
+                },
+                TransportType::WebSocket => Err(ProxyError::Transport(
+                    "WebSocket not yet implemented".into(),
+                )),
+                TransportType::Sse => Err(ProxyError::Transport("SSE not yet implemented".into())),
+                // Metadata, TLS, socket paths and container settings come from the
+                // live config
+                TransportType::Grpc | TransportType::Unix | TransportType::Docker => {
+                    send_with_timeout(&state, &server.id, request)
+                        .await
+                        .map_err(|e| ProxyError::Transport(e.to_string()))
+                },
+                TransportType::StreamableHttp => Err(ProxyError::Transport(
+                    "StreamableHttp in route_generic_request not yet implemented".into(),
+                )),
+            }
+        }
+        .await;
 
-    // Record metrics
     let duration = start.elapsed();
+    let status = match &response {
+        Ok(response) if response.error.is_some() => "error",
+        Ok(_) => "success",
+        Err(_) => "failure",
+    };
+    note_backend(&server.id);
+    crate::metrics::record_backend_request(&server.id, &method, status, duration);
+    let response = response?;
     info!(
         "Backend request to {} completed in {:?}",
         server.id, duration
@@ -925,22 +2871,36 @@ async fn send_request_to_backend(
     Ok(serde_json::to_value(response)?)
 }
 
-async fn execute_with_retry<F, Fut>(
+/// Retry policy configured for a backend, or the default if it is unknown.
+fn retry_policy(state: &AppState, server_id: &str) -> RetryConfig {
+    state
+        .config
+        .load()
+        .servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .map(|s| s.retry.clone())
+        .unwrap_or_default()
+}
+
+/// Run `f` until it succeeds, fails with a non-retryable error, or the
+/// policy's `max_attempts` is exhausted, sleeping per its backoff in between.
+async fn execute_with_retry<T, F, Fut>(
     f: F,
-    max_retries: u32,
-) -> std::result::Result<Value, ProxyError>
+    policy: &RetryConfig,
+) -> std::result::Result<T, ProxyError>
 where
     F: Fn() -> Fut,
-    Fut: std::future::Future<Output = std::result::Result<Value, ProxyError>>,
+    Fut: std::future::Future<Output = std::result::Result<T, ProxyError>>,
 {
-    let mut attempts = 0;
+    let mut attempts = 1;
     loop {
         match f().await {
             Ok(result) => return Ok(result),
-            Err(e) if e.is_retryable() && attempts < max_retries => {
-                attempts += 1;
+            Err(e) if e.is_retryable() && attempts < policy.max_attempts => {
                 warn!("Retry attempt {} after error: {}", attempts, e);
-                tokio::time::sleep(Duration::from_millis(100 * attempts as u64)).await;
+                tokio::time::sleep(policy.delay_for(attempts)).await;
+                attempts += 1;
             },
             Err(e) => return Err(e),
         }
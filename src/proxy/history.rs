@@ -0,0 +1,202 @@
+//! History of recently answered requests (`observability.request_history`).
+//!
+//! Every MCP request handled by the proxy is kept in a bounded ring buffer
+//! with its method, tool, backends, outcome, latency and caller, so the
+//! admin API and TUI can show what the proxy has been doing without a
+//! tracing backend. The backends a request touched are collected through a
+//! task-local scope around its dispatch.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use parking_lot::Mutex;
+use serde::Deserialize;
+
+use crate::types::RequestRecord;
+
+tokio::task_local! {
+    static BACKENDS: RefCell<Vec<String>>;
+}
+
+/// Run `future`, returning its output and the backends it sent requests to
+/// (as reported by [`note_backend`]), in first-use order.
+pub async fn tracking_backends<F: Future>(future: F) -> (F::Output, Vec<String>) {
+    BACKENDS
+        .scope(RefCell::new(Vec::new()), async move {
+            let output = future.await;
+            (output, BACKENDS.with(|backends| backends.take()))
+        })
+        .await
+}
+
+/// Note that the current request was sent to `server_id`. Does nothing
+/// outside [`tracking_backends`], e.g. in background refreshes.
+pub fn note_backend(server_id: &str) {
+    let _ = BACKENDS.try_with(|backends| {
+        let mut backends = backends.borrow_mut();
+        if !backends.iter().any(|id| id == server_id) {
+            backends.push(server_id.to_string());
+        }
+    });
+}
+
+/// Which requests `GET /api/v1/admin/requests` returns.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RequestFilter {
+    /// Most requests returned, newest first (default: 100)
+    pub limit: Option<usize>,
+    pub method: Option<String>,
+    pub tool: Option<String>,
+    /// Requests sent to this backend
+    pub server: Option<String>,
+    pub status: Option<String>,
+    pub client: Option<String>,
+    pub session: Option<String>,
+    /// Only requests recorded after the one with this id
+    pub after: Option<u64>,
+}
+
+const DEFAULT_LIMIT: usize = 100;
+
+impl RequestFilter {
+    fn matches(&self, record: &RequestRecord) -> bool {
+        fn same(wanted: &Option<String>, value: Option<&str>) -> bool {
+            wanted.as_deref().map_or(true, |wanted| value == Some(wanted))
+        }
+
+        same(&self.method, Some(&record.method))
+            && same(&self.tool, record.tool.as_deref())
+            && self.server.as_ref().map_or(true, |server| record.servers.contains(server))
+            && same(&self.status, Some(&record.status))
+            && same(&self.client, record.client.as_deref())
+            && same(&self.session, record.session.as_deref())
+            && self.after.map_or(true, |after| record.id > after)
+    }
+}
+
+/// Ring buffer of the most recent requests.
+#[derive(Debug)]
+pub struct RequestHistory {
+    entries: Mutex<VecDeque<RequestRecord>>,
+    next_id: AtomicU64,
+}
+
+impl Default for RequestHistory {
+    fn default() -> Self {
+        Self {
+            entries: Mutex::new(VecDeque::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+}
+
+impl RequestHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep `record`, numbered after the previous one, dropping the oldest
+    /// entries beyond `capacity`. With a capacity of 0 the history is
+    /// cleared and nothing is kept.
+    pub fn record(&self, capacity: usize, mut record: RequestRecord) {
+        let mut entries = self.entries.lock();
+        if capacity > 0 {
+            record.id = self.next_id.fetch_add(1, Ordering::Relaxed);
+            entries.push_back(record);
+        }
+        while entries.len() > capacity {
+            entries.pop_front();
+        }
+    }
+
+    /// Kept requests matching `filter`, newest first.
+    pub fn query(&self, filter: &RequestFilter) -> Vec<RequestRecord> {
+        let limit = filter.limit.unwrap_or(DEFAULT_LIMIT);
+        self.entries
+            .lock()
+            .iter()
+            .rev()
+            .filter(|record| filter.matches(record))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, servers: &[&str]) -> RequestRecord {
+        RequestRecord {
+            id: 0,
+            at: chrono::Utc::now(),
+            method: method.to_string(),
+            tool: None,
+            servers: servers.iter().map(|s| s.to_string()).collect(),
+            status: "success".to_string(),
+            error: None,
+            latency_ms: 1.0,
+            client: None,
+            session: None,
+        }
+    }
+
+    #[test]
+    fn test_history_keeps_newest_within_capacity() {
+        let history = RequestHistory::new();
+        for method in ["initialize", "tools/list", "tools/call"] {
+            history.record(2, request(method, &["a"]));
+        }
+
+        let kept = history.query(&RequestFilter::default());
+        let methods: Vec<_> = kept.iter().map(|r| r.method.as_str()).collect();
+        assert_eq!(methods, ["tools/call", "tools/list"]);
+        assert_eq!(kept[0].id, 3);
+
+        let after = RequestFilter {
+            after: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(history.query(&after).len(), 1);
+
+        history.record(0, request("ping", &[]));
+        assert!(history.query(&RequestFilter::default()).is_empty());
+    }
+
+    #[test]
+    fn test_history_filters() {
+        let history = RequestHistory::new();
+        history.record(10, request("tools/list", &["a", "b"]));
+        history.record(10, request("tools/call", &["b"]));
+
+        let by_server = RequestFilter {
+            server: Some("a".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(history.query(&by_server)[0].method, "tools/list");
+
+        let by_method = RequestFilter {
+            method: Some("tools/call".to_string()),
+            limit: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(history.query(&by_method).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_tracking_backends() {
+        let ((), backends) = tracking_backends(async {
+            note_backend("b");
+            note_backend("a");
+            note_backend("b");
+        })
+        .await;
+        assert_eq!(backends, ["b", "a"]);
+
+        // Outside a scope nothing is tracked
+        note_backend("c");
+    }
+}
@@ -0,0 +1,798 @@
+//! Listening sockets (`server.listeners`).
+//!
+//! Each listener binds a TCP address or a Unix domain socket, optionally
+//! terminates TLS, and serves a subset of the proxy's routes, so the admin
+//! API and metrics can be kept off the network that AI clients reach. An
+//! IPv6 address also accepts IPv4 connections unless `ipv6_only` is set,
+//! whatever the system default (`net.ipv6.bindv6only` on Linux).
+//! Plain TCP is served through `axum::serve`; TLS and Unix sockets go
+//! through hyper's connection builder directly.
+//!
+//! TLS certificates are reloaded without a restart when the certificate or
+//! key file changes on disk, or on `SIGHUP`. A pair that fails to load is
+//! logged and the previous one keeps serving. With `tls.client_ca_path`,
+//! clients authenticate with certificates (mutual TLS) and each connection
+//! carries the [`ClientPrincipal`] named by its certificate.
+//!
+//! A process started by a binary upgrade (see [`crate::daemon::upgrade`])
+//! adopts the sockets of the process it replaces instead of binding.
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use arc_swap::ArcSwap;
+
+use axum::{
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Router,
+};
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo},
+    server::{conn::auto, graceful::GracefulShutdown},
+    service::TowerToHyperService,
+};
+use rustls::pki_types::CertificateDer;
+use rustls::server::{ClientHello, ResolvesServerCert, WebPkiClientVerifier};
+use rustls::sign::CertifiedKey;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
+use tracing::{debug, info, warn};
+
+use crate::auth::jwt::{JwtConfig, JwtManager};
+use crate::config::{
+    ApiKeyConfig, ClientAuthMode, ListenAddress, ListenerAuthConfig, ListenerConfig, TlsConfig,
+};
+use crate::error::{Error, Result};
+
+/// Connections waiting to be accepted, as in `TcpListener::bind`.
+const LISTEN_BACKLOG: i32 = 1024;
+
+/// Pause after a failed `accept`, e.g. when out of file descriptors.
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_millis(100);
+
+/// How often TLS certificate files are checked for changes.
+const CERT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A listener whose socket is bound and ready to serve.
+pub struct BoundListener {
+    pub name: String,
+    socket: Socket,
+}
+
+enum Socket {
+    Tcp(TcpListener),
+    Tls(TcpListener, TlsListener),
+    #[cfg(unix)]
+    Unix(tokio::net::UnixListener, PathBuf),
+}
+
+/// Bind the socket described by `config`, loading its TLS material.
+pub async fn bind(config: &ListenerConfig) -> Result<BoundListener> {
+    let socket = match config.address()? {
+        ListenAddress::Tcp(addr) => {
+            let listener = match adopt_tcp(&config.name, addr) {
+                Some(listener) => listener,
+                None => bind_tcp(addr, config.ipv6_only).map_err(|e| {
+                    Error::Server(format!("Failed to bind {} on {}: {}", config.name, addr, e))
+                })?,
+            };
+            if config.tls.enabled {
+                Socket::Tls(listener, TlsListener::new(&config.tls)?)
+            } else {
+                Socket::Tcp(listener)
+            }
+        },
+        #[cfg(unix)]
+        ListenAddress::Unix(path) => {
+            let listener = match adopt_unix(&config.name, &path) {
+                Some(listener) => listener,
+                None => bind_unix(&config.name, &path)?,
+            };
+            Socket::Unix(listener, path)
+        },
+        #[cfg(not(unix))]
+        ListenAddress::Unix(_) => {
+            return Err(Error::Config(format!(
+                "Listener {}: Unix sockets are not supported on this platform",
+                config.name
+            )))
+        },
+    };
+    Ok(BoundListener {
+        name: config.name.clone(),
+        socket,
+    })
+}
+
+/// Bind a TCP listener on `addr`, dual-stack for IPv6 addresses unless
+/// `ipv6_only`.
+fn bind_tcp(addr: SocketAddr, ipv6_only: bool) -> std::io::Result<TcpListener> {
+    let socket = socket2::Socket::new(
+        socket2::Domain::for_address(addr),
+        socket2::Type::STREAM,
+        Some(socket2::Protocol::TCP),
+    )?;
+    // Like `TcpListener::bind`, so a restart can rebind at once
+    #[cfg(unix)]
+    socket.set_reuse_address(true)?;
+    if addr.is_ipv6() {
+        socket.set_only_v6(ipv6_only)?;
+    }
+    socket.set_nonblocking(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(LISTEN_BACKLOG)?;
+    TcpListener::from_std(socket.into())
+}
+
+/// Take over the socket a previous process handed down for listener `name`
+/// during a binary upgrade, if it is still bound to `addr`.
+#[cfg(unix)]
+fn adopt_tcp(name: &str, addr: SocketAddr) -> Option<TcpListener> {
+    let listener = std::net::TcpListener::from(crate::daemon::upgrade::take_listener(name)?);
+    let local = listener.local_addr().ok()?;
+    if local.ip() != addr.ip() || (addr.port() != 0 && local.port() != addr.port()) {
+        info!("Listener {} moved from {}, binding {}", name, local, addr);
+        return None;
+    }
+    listener.set_nonblocking(true).ok()?;
+    let listener = TcpListener::from_std(listener).ok()?;
+    info!(
+        "Listener {} adopted {} from the previous process",
+        name, local
+    );
+    Some(listener)
+}
+
+#[cfg(not(unix))]
+fn adopt_tcp(_name: &str, _addr: SocketAddr) -> Option<TcpListener> {
+    None
+}
+
+/// Take over the Unix socket a previous process handed down for listener
+/// `name`, if it is still bound to `path`.
+#[cfg(unix)]
+fn adopt_unix(name: &str, path: &std::path::Path) -> Option<tokio::net::UnixListener> {
+    let listener =
+        std::os::unix::net::UnixListener::from(crate::daemon::upgrade::take_listener(name)?);
+    if listener.local_addr().ok()?.as_pathname() != Some(path) {
+        info!("Listener {} moved, binding {}", name, path.display());
+        return None;
+    }
+    listener.set_nonblocking(true).ok()?;
+    let listener = tokio::net::UnixListener::from_std(listener).ok()?;
+    info!(
+        "Listener {} adopted {} from the previous process",
+        name,
+        path.display()
+    );
+    Some(listener)
+}
+
+impl BoundListener {
+    /// Listening socket, handed to the new process on a binary upgrade.
+    #[cfg(unix)]
+    pub fn raw_fd(&self) -> std::os::fd::RawFd {
+        use std::os::fd::AsRawFd;
+
+        match &self.socket {
+            Socket::Tcp(listener) | Socket::Tls(listener, ..) => listener.as_raw_fd(),
+            Socket::Unix(listener, _) => listener.as_raw_fd(),
+        }
+    }
+
+    /// Address actually bound, with the OS-assigned port for `:0` binds.
+    pub fn local_address(&self) -> String {
+        match &self.socket {
+            Socket::Tcp(listener) | Socket::Tls(listener, ..) => listener
+                .local_addr()
+                .map(|addr| addr.to_string())
+                .unwrap_or_else(|_| "<unknown>".to_string()),
+            #[cfg(unix)]
+            Socket::Unix(_, path) => format!("unix:{}", path.display()),
+        }
+    }
+
+    /// Whether connections are TLS-encrypted.
+    pub fn is_tls(&self) -> bool {
+        matches!(self.socket, Socket::Tls(..))
+    }
+
+    /// Serve `router` until `shutdown` resolves, then stop accepting and wait
+    /// for open connections to finish.
+    pub async fn serve<F>(self, router: Router, shutdown: F) -> Result<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let name = self.name;
+        match self.socket {
+            Socket::Tcp(listener) => axum::serve(listener, router)
+                .with_graceful_shutdown(shutdown)
+                .await
+                .map_err(|e| Error::Server(format!("Listener {} failed: {}", name, e))),
+            Socket::Tls(listener, tls) => {
+                let reloader =
+                    tokio::spawn(watch_certificate(name.clone(), tls.certificate.clone()));
+                let graceful = GracefulShutdown::new();
+                tokio::pin!(shutdown);
+                loop {
+                    let stream = tokio::select! {
+                        accepted = listener.accept() => match accepted {
+                            Ok((stream, _)) => stream,
+                            Err(e) => {
+                                warn!("Listener {} failed to accept: {}", name, e);
+                                tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                                continue;
+                            },
+                        },
+                        _ = &mut shutdown => break,
+                    };
+                    tokio::spawn(serve_tls_connection(
+                        tls.acceptor.clone(),
+                        stream,
+                        router.clone(),
+                        tls.client_roles.clone(),
+                        graceful.watcher(),
+                    ));
+                }
+                reloader.abort();
+                graceful.shutdown().await;
+                Ok(())
+            },
+            #[cfg(unix)]
+            Socket::Unix(listener, path) => {
+                let graceful = GracefulShutdown::new();
+                tokio::pin!(shutdown);
+                loop {
+                    let stream = tokio::select! {
+                        accepted = listener.accept() => match accepted {
+                            Ok((stream, _)) => stream,
+                            Err(e) => {
+                                warn!("Listener {} failed to accept: {}", name, e);
+                                tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                                continue;
+                            },
+                        },
+                        _ = &mut shutdown => break,
+                    };
+                    tokio::spawn(serve_connection(stream, router.clone(), graceful.watcher()));
+                }
+                graceful.shutdown().await;
+                // After an upgrade the socket file belongs to the new process
+                if !crate::daemon::upgrade::handed_over() {
+                    let _ = std::fs::remove_file(&path);
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+/// Complete the TLS handshake on `stream`, then serve it as the principal
+/// named by its client certificate, if any.
+async fn serve_tls_connection(
+    acceptor: TlsAcceptor,
+    stream: tokio::net::TcpStream,
+    router: Router,
+    client_roles: Arc<BTreeMap<String, Vec<String>>>,
+    watcher: hyper_util::server::graceful::Watcher,
+) {
+    let stream = match acceptor.accept(stream).await {
+        Ok(stream) => stream,
+        Err(e) => {
+            debug!("TLS handshake failed: {}", e);
+            return;
+        },
+    };
+    let router = match peer_principal(&stream, &client_roles) {
+        Some(principal) => router.layer(axum::Extension(principal)),
+        None => router,
+    };
+    serve_connection(stream, router, watcher).await
+}
+
+/// Serve HTTP/1 or HTTP/2 (with WebSocket upgrades) on one connection.
+async fn serve_connection<S>(
+    stream: S,
+    router: Router,
+    watcher: hyper_util::server::graceful::Watcher,
+) where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let builder = auto::Builder::new(TokioExecutor::new());
+    let connection = builder
+        .serve_connection_with_upgrades(TokioIo::new(stream), TowerToHyperService::new(router))
+        .into_owned();
+    if let Err(e) = watcher.watch(connection).await {
+        debug!("Connection closed with error: {}", e);
+    }
+}
+
+/// TLS state of a listener.
+struct TlsListener {
+    acceptor: TlsAcceptor,
+    certificate: Arc<TlsCertificate>,
+    /// Roles for client certificate names (`tls.client_roles`)
+    client_roles: Arc<BTreeMap<String, Vec<String>>>,
+}
+
+impl TlsListener {
+    fn new(tls: &TlsConfig) -> Result<Self> {
+        let certificate = Arc::new(TlsCertificate::load(tls)?);
+        Ok(Self {
+            acceptor: tls_acceptor(tls, certificate.clone())?,
+            certificate,
+            client_roles: Arc::new(tls.client_roles.clone()),
+        })
+    }
+}
+
+/// Principal named by the verified client certificate of a TLS connection.
+fn peer_principal(
+    stream: &tokio_rustls::server::TlsStream<tokio::net::TcpStream>,
+    client_roles: &BTreeMap<String, Vec<String>>,
+) -> Option<ClientPrincipal> {
+    let leaf = stream.get_ref().1.peer_certificates()?.first()?;
+    ClientPrincipal::from_certificate(&crate::auth::mtls::certificate_names(leaf), client_roles)
+}
+
+/// A TLS listener's certificate chain and private key, swappable while the
+/// listener serves.
+#[derive(Debug)]
+pub struct TlsCertificate {
+    cert_path: PathBuf,
+    key_path: PathBuf,
+    current: ArcSwap<CertifiedKey>,
+}
+
+impl TlsCertificate {
+    /// Load the certificate chain and key named in `tls`.
+    pub fn load(tls: &TlsConfig) -> Result<Self> {
+        let (Some(cert_path), Some(key_path)) = (&tls.cert_path, &tls.key_path) else {
+            return Err(Error::Config(
+                "TLS enabled but cert_path or key_path not specified".to_string(),
+            ));
+        };
+        let current = load_certified_key(cert_path, key_path)?;
+        Ok(Self {
+            cert_path: cert_path.clone(),
+            key_path: key_path.clone(),
+            current: ArcSwap::from_pointee(current),
+        })
+    }
+
+    /// Re-read the certificate and key from disk. The current pair is kept
+    /// when the new one is missing or invalid.
+    pub fn reload(&self) -> Result<()> {
+        let reloaded = load_certified_key(&self.cert_path, &self.key_path)?;
+        self.current.store(Arc::new(reloaded));
+        Ok(())
+    }
+
+    /// Certificate chain currently served, leaf first.
+    pub fn chain(&self) -> Vec<CertificateDer<'static>> {
+        self.current.load().cert.clone()
+    }
+
+    /// Modification times of the certificate and key files.
+    fn modified(&self) -> (Option<SystemTime>, Option<SystemTime>) {
+        let modified = |path: &PathBuf| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        (modified(&self.cert_path), modified(&self.key_path))
+    }
+}
+
+impl ResolvesServerCert for TlsCertificate {
+    fn resolve(&self, _client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        Some(self.current.load_full())
+    }
+}
+
+/// Reload `certificate` whenever its files change or the process receives
+/// `SIGHUP`. Runs until aborted.
+async fn watch_certificate(name: String, certificate: Arc<TlsCertificate>) {
+    #[cfg(unix)]
+    let mut hangup = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())
+        .map_err(|e| warn!("Listener {}: cannot watch for SIGHUP: {}", name, e))
+        .ok();
+    let mut last_modified = certificate.modified();
+    let mut poll = tokio::time::interval(CERT_POLL_INTERVAL);
+
+    loop {
+        let hangup_received = async {
+            #[cfg(unix)]
+            if let Some(hangup) = hangup.as_mut() {
+                hangup.recv().await;
+                return;
+            }
+            std::future::pending::<()>().await
+        };
+        let trigger = tokio::select! {
+            _ = poll.tick() => {
+                let modified = certificate.modified();
+                if modified == last_modified {
+                    continue;
+                }
+                last_modified = modified;
+                "files changed"
+            },
+            _ = hangup_received => "SIGHUP",
+        };
+
+        match certificate.reload() {
+            Ok(()) => {
+                info!("Listener {}: reloaded TLS certificate ({})", name, trigger);
+                crate::metrics::TLS_CERTIFICATE_RELOADS_TOTAL
+                    .with_label_values(&[name.as_str(), "success"])
+                    .inc();
+            },
+            Err(e) => {
+                warn!(
+                    "Listener {}: keeping the current TLS certificate ({}): {}",
+                    name, trigger, e
+                );
+                crate::metrics::TLS_CERTIFICATE_RELOADS_TOTAL
+                    .with_label_values(&[name.as_str(), "failure"])
+                    .inc();
+            },
+        }
+    }
+}
+
+/// Build a TLS acceptor that serves whatever `certificate` currently holds
+/// and, with `tls.client_ca_path`, verifies client certificates.
+fn tls_acceptor(tls: &TlsConfig, certificate: Arc<TlsCertificate>) -> Result<TlsAcceptor> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+
+    let verifier = match &tls.client_ca_path {
+        Some(ca_path) => {
+            let mut roots = rustls::RootCertStore::empty();
+            for ca in read_certs(ca_path)? {
+                roots.add(ca).map_err(|e| {
+                    Error::Config(format!("Invalid client CA {}: {}", ca_path.display(), e))
+                })?;
+            }
+            let builder =
+                WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider.clone());
+            let builder = match tls.client_auth {
+                ClientAuthMode::Required => builder,
+                ClientAuthMode::Optional => builder.allow_unauthenticated(),
+            };
+            builder
+                .build()
+                .map_err(|e| Error::Config(format!("Invalid client CA configuration: {}", e)))?
+        },
+        None => WebPkiClientVerifier::no_client_auth(),
+    };
+
+    let mut config = rustls::ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .expect("ring supports the default protocol versions")
+        .with_client_cert_verifier(verifier)
+        .with_cert_resolver(certificate);
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+fn read_pem(path: &PathBuf) -> Result<Vec<u8>> {
+    std::fs::read(path)
+        .map_err(|e| Error::Config(format!("Failed to read {}: {}", path.display(), e)))
+}
+
+/// Read the certificates of a PEM file, failing if there are none.
+fn read_certs(path: &PathBuf) -> Result<Vec<CertificateDer<'static>>> {
+    let certs = rustls_pemfile::certs(&mut read_pem(path)?.as_slice())
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| Error::Config(format!("Invalid certificate {}: {}", path.display(), e)))?;
+    if certs.is_empty() {
+        return Err(Error::Config(format!(
+            "No certificates found in {}",
+            path.display()
+        )));
+    }
+    Ok(certs)
+}
+
+/// Read a PEM certificate chain and private key, checking that they match.
+fn load_certified_key(cert_path: &PathBuf, key_path: &PathBuf) -> Result<CertifiedKey> {
+    let certs = read_certs(cert_path)?;
+    let key = rustls_pemfile::private_key(&mut read_pem(key_path)?.as_slice())
+        .map_err(|e| Error::Config(format!("Invalid private key {}: {}", key_path.display(), e)))?
+        .ok_or_else(|| Error::Config(format!("No private key found in {}", key_path.display())))?;
+
+    CertifiedKey::from_der(certs, key, &rustls::crypto::ring::default_provider())
+        .map_err(|e| Error::Config(format!("Invalid TLS configuration: {}", e)))
+}
+
+/// Bind a Unix socket, replacing a stale socket file left by a previous run.
+#[cfg(unix)]
+fn bind_unix(name: &str, path: &std::path::Path) -> Result<tokio::net::UnixListener> {
+    use std::os::unix::fs::FileTypeExt;
+
+    if let Ok(metadata) = std::fs::symlink_metadata(path) {
+        if !metadata.file_type().is_socket() {
+            return Err(Error::Config(format!(
+                "Listener {}: {} exists and is not a socket",
+                name,
+                path.display()
+            )));
+        }
+        std::fs::remove_file(path)
+            .map_err(|e| Error::Server(format!("Failed to remove stale socket: {}", e)))?;
+    }
+    tokio::net::UnixListener::bind(path).map_err(|e| {
+        Error::Server(format!(
+            "Failed to bind {} on {}: {}",
+            name,
+            path.display(),
+            e
+        ))
+    })
+}
+
+/// Authenticated caller, attached as a request extension by
+/// [`require_auth`] or, for client certificates, by the TLS listener.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientPrincipal {
+    /// `key:<fingerprint>` for API keys (never the key itself), `jwt:<sub>`
+    /// for JWTs or `cert:<name>` for client certificates
+    pub id: String,
+    /// Roles used by `auth.policies`
+    pub roles: Vec<String>,
+}
+
+impl ClientPrincipal {
+    /// Principal for an API key: `key:` followed by a short SHA-256 prefix.
+    pub fn from_api_key(key: &str, roles: &[String]) -> Self {
+        use sha2::{Digest, Sha256};
+        let digest = Sha256::digest(key.as_bytes());
+        let fingerprint: String = digest[..8].iter().map(|b| format!("{:02x}", b)).collect();
+        Self {
+            id: format!("key:{}", fingerprint),
+            roles: roles.to_vec(),
+        }
+    }
+
+    /// Principal for a client certificate with the given subject names (CN
+    /// first), holding the `client_roles` of every name. `None` when the
+    /// certificate names nobody.
+    pub fn from_certificate(
+        names: &[String],
+        client_roles: &BTreeMap<String, Vec<String>>,
+    ) -> Option<Self> {
+        let mut roles: Vec<String> = names
+            .iter()
+            .filter_map(|name| client_roles.get(name))
+            .flatten()
+            .cloned()
+            .collect();
+        roles.sort();
+        roles.dedup();
+        Some(Self {
+            id: format!("cert:{}", names.first()?),
+            roles,
+        })
+    }
+}
+
+/// Credentials accepted by a listener (`server.listeners[].auth`).
+pub struct ListenerAuth {
+    api_keys: Vec<ApiKeyConfig>,
+    jwt: Option<JwtManager>,
+}
+
+impl ListenerAuth {
+    pub fn new(config: &ListenerAuthConfig) -> Self {
+        let jwt = config.jwt.as_ref().and_then(|jwt| {
+            let Some(secret) = jwt.secret() else {
+                warn!("JWT secret is not set; JWTs will be rejected");
+                return None;
+            };
+            let jwt_config = JwtConfig {
+                issuer: jwt.issuer.clone(),
+                audience: vec![jwt.audience.clone()],
+                ..Default::default()
+            };
+            JwtManager::new(jwt_config, secret.as_bytes()).ok()
+        });
+
+        Self {
+            api_keys: config.api_keys.clone(),
+            jwt,
+        }
+    }
+
+    /// The caller presenting `credential`, if it is an accepted API key or a
+    /// valid JWT.
+    pub async fn authenticate(&self, credential: &str) -> Option<ClientPrincipal> {
+        let key = self
+            .api_keys
+            .iter()
+            .find(|k| constant_time_eq(k.key().as_bytes(), credential.as_bytes()));
+        if let Some(key) = key {
+            return Some(ClientPrincipal::from_api_key(credential, key.roles()));
+        }
+
+        let claims = self.jwt.as_ref()?.validate_token(credential).await.ok()?;
+        Some(ClientPrincipal {
+            id: format!("jwt:{}", claims.sub),
+            roles: claims.roles,
+        })
+    }
+}
+
+/// Reject requests without an accepted API key or JWT, given as
+/// `Authorization: Bearer <credential>` or `X-API-Key: <key>`, and attach
+/// the caller's [`ClientPrincipal`] to the rest. Requests without such a
+/// credential on a connection with a verified client certificate are
+/// accepted as the certificate's principal.
+pub async fn require_auth(
+    State(auth): State<Arc<ListenerAuth>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let headers = request.headers();
+    let presented = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .or_else(|| headers.get("x-api-key").and_then(|value| value.to_str().ok()));
+
+    let principal = match presented {
+        Some(credential) => auth.authenticate(credential).await,
+        None => request.extensions().get::<ClientPrincipal>().cloned(),
+    };
+    match principal {
+        Some(principal) => {
+            request.extensions_mut().insert(principal);
+            next.run(request).await
+        },
+        None => (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+            "Missing or invalid credentials",
+        )
+            .into_response(),
+    }
+}
+
+/// Compare without short-circuiting on the first differing byte.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+    }
+
+    #[test]
+    fn test_client_principal_hides_key() {
+        let principal = ClientPrincipal::from_api_key("secret", &[]);
+        assert!(principal.id.starts_with("key:"));
+        assert!(!principal.id.contains("secret"));
+        assert_eq!(principal, ClientPrincipal::from_api_key("secret", &[]));
+        assert_ne!(principal, ClientPrincipal::from_api_key("other", &[]));
+    }
+
+    #[test]
+    fn test_client_principal_from_certificate() {
+        let names = vec!["analyst-bot".to_string(), "bot.example.com".to_string()];
+        let client_roles = BTreeMap::from([
+            ("analyst-bot".to_string(), vec!["analyst".to_string()]),
+            (
+                "bot.example.com".to_string(),
+                vec!["analyst".to_string(), "bots".to_string()],
+            ),
+        ]);
+
+        let principal = ClientPrincipal::from_certificate(&names, &client_roles).unwrap();
+        assert_eq!(principal.id, "cert:analyst-bot");
+        assert_eq!(principal.roles, vec!["analyst", "bots"]);
+        assert!(ClientPrincipal::from_certificate(&[], &client_roles).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_listener_auth_roles() {
+        let config: ListenerAuthConfig = serde_yaml::from_str(
+            r#"
+api_keys:
+  - plain-key
+  - key: analyst-key
+    roles: [analyst]
+jwt:
+  secret: jwt-secret
+"#,
+        )
+        .unwrap();
+        let auth = ListenerAuth::new(&config);
+
+        assert!(auth.authenticate("plain-key").await.unwrap().roles.is_empty());
+        assert_eq!(
+            auth.authenticate("analyst-key").await.unwrap().roles,
+            vec!["analyst"]
+        );
+        assert!(auth.authenticate("wrong").await.is_none());
+
+        let issuer = JwtManager::new(JwtConfig::default(), b"jwt-secret").unwrap();
+        let token = issuer
+            .create_access_token(&crate::auth::jwt::Identity {
+                id: "alice".to_string(),
+                username: "alice".to_string(),
+                email: None,
+                roles: vec!["admin".to_string()],
+                mfa_verified: false,
+                session_id: None,
+                client_id: None,
+            })
+            .await
+            .unwrap();
+        let principal = auth.authenticate(&token).await.unwrap();
+        assert_eq!(principal.id, "jwt:alice");
+        assert_eq!(principal.roles, vec!["admin"]);
+    }
+
+    #[test]
+    fn test_tls_requires_cert_material() {
+        let tls = TlsConfig {
+            enabled: true,
+            cert_path: None,
+            key_path: None,
+            ..Default::default()
+        };
+        assert!(TlsCertificate::load(&tls).is_err());
+
+        // A key that does not belong to the certificate is rejected too
+        let tls = TlsConfig {
+            enabled: true,
+            cert_path: Some(fixture("cert-a.pem")),
+            key_path: Some(fixture("key-b.pem")),
+            ..Default::default()
+        };
+        assert!(TlsCertificate::load(&tls).is_err());
+    }
+
+    #[test]
+    fn test_tls_certificate_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let (cert_path, key_path) = (dir.path().join("cert.pem"), dir.path().join("key.pem"));
+        let install = |name: &str| {
+            std::fs::copy(fixture(&format!("cert-{}.pem", name)), &cert_path).unwrap();
+            std::fs::copy(fixture(&format!("key-{}.pem", name)), &key_path).unwrap();
+        };
+        install("a");
+        let certificate = TlsCertificate::load(&TlsConfig {
+            enabled: true,
+            cert_path: Some(cert_path.clone()),
+            key_path: Some(key_path.clone()),
+            ..Default::default()
+        })
+        .unwrap();
+        let original = certificate.chain();
+
+        // A broken pair leaves the current certificate in place
+        std::fs::write(&key_path, "not a key").unwrap();
+        assert!(certificate.reload().is_err());
+        assert_eq!(certificate.chain(), original);
+
+        install("b");
+        certificate.reload().unwrap();
+        assert_ne!(certificate.chain(), original);
+    }
+
+    fn fixture(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/tls").join(name)
+    }
+}
@@ -5,10 +5,33 @@
 
 use crate::{config::Config, error::Result};
 
+pub mod admission;
+pub mod aliases;
+pub mod arguments;
+pub mod capabilities;
+pub mod catalog;
+pub mod chaos;
+pub mod cluster;
+pub mod coalescing;
+pub mod composite;
+pub mod drain;
+pub mod dry_run;
+pub mod front_door;
 pub mod handler;
+pub mod history;
+pub mod listener;
+pub mod pagination;
+pub mod recording;
+pub mod redact;
 pub mod registry;
 pub mod router;
+pub mod sanitize;
+pub mod scripts;
 pub mod server;
+pub mod slim;
+pub mod snapshot;
+pub mod transform;
+pub mod truncate;
 
 pub use server::ProxyServer;
 
@@ -0,0 +1,181 @@
+//! Cursor pagination of aggregated tools/list results
+//! (`context_optimization.aggregation.page_size`).
+//!
+//! Instead of fetching every backend's full list for each request, a page
+//! is filled from the backends in server order, fetching only the backend
+//! pages it needs. The `nextCursor` returned to the client encodes the
+//! position reached in each backend: the backend's own cursor, how many
+//! items of that backend page were already returned, and whether the
+//! backend is exhausted. The next tools/list with that cursor resumes from
+//! there.
+
+use std::collections::BTreeMap;
+use std::future::Future;
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::error::{ProxyError, Result};
+
+/// Position reached in every backend, sent to clients as `nextCursor`.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct PageCursor {
+    #[serde(default)]
+    servers: BTreeMap<String, ServerPosition>,
+}
+
+/// Position reached in one backend's list.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+struct ServerPosition {
+    /// Backend cursor of the page being read; `None` for the first page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cursor: Option<String>,
+    /// Items of that page already returned
+    #[serde(default, skip_serializing_if = "is_zero")]
+    offset: usize,
+    /// Whether every item has been returned
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    done: bool,
+}
+
+fn is_zero(offset: &usize) -> bool {
+    *offset == 0
+}
+
+impl PageCursor {
+    /// Parse a cursor sent back by a client.
+    pub(crate) fn decode(token: &str) -> std::result::Result<Self, ProxyError> {
+        let invalid = || ProxyError::InvalidRequest(format!("Invalid cursor: {}", token));
+        let json = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(token)
+            .map_err(|_| invalid())?;
+        serde_json::from_slice(&json).map_err(|_| invalid())
+    }
+
+    /// Opaque token for the client.
+    pub(crate) fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(json)
+    }
+}
+
+/// One page of an aggregated list.
+pub(crate) struct Page<T> {
+    pub items: Vec<T>,
+    /// Backends whose pages failed; they are skipped from then on
+    pub degraded: Vec<String>,
+    /// Whether some backend has items left
+    pub more: bool,
+}
+
+/// Fill a page of up to `page_size` items from `servers`, in order,
+/// starting at and advancing `cursor`.
+///
+/// `fetch` returns the items of one backend page and the backend's
+/// `nextCursor`.
+pub(crate) async fn fill_page<T, F, Fut>(
+    cursor: &mut PageCursor,
+    servers: &[String],
+    page_size: usize,
+    fetch: F,
+) -> Page<T>
+where
+    F: Fn(String, Option<String>) -> Fut,
+    Fut: Future<Output = Result<(Vec<T>, Option<String>)>>,
+{
+    let mut page = Page {
+        items: Vec::new(),
+        degraded: Vec::new(),
+        more: false,
+    };
+    for server in servers {
+        let position = cursor.servers.entry(server.clone()).or_default();
+        while page.items.len() < page_size && !position.done {
+            let (items, next) = match fetch(server.clone(), position.cursor.clone()).await {
+                Ok(backend_page) => backend_page,
+                Err(e) => {
+                    warn!("Failed to fetch a tools/list page from {}: {}", server, e);
+                    page.degraded.push(server.clone());
+                    position.done = true;
+                    break;
+                },
+            };
+
+            let room = page_size - page.items.len();
+            let unread = items.len().saturating_sub(position.offset);
+            if unread > room {
+                page.items.extend(items.into_iter().skip(position.offset).take(room));
+                position.offset += room;
+            } else {
+                page.items.extend(items.into_iter().skip(position.offset));
+                position.offset = 0;
+                // A backend repeating its cursor would be asked forever
+                position.done = next.is_none() || next == position.cursor;
+                position.cursor = next;
+            }
+        }
+        if page.items.len() >= page_size {
+            break;
+        }
+    }
+
+    page.more = servers
+        .iter()
+        .any(|server| cursor.servers.get(server).map_or(true, |position| !position.done));
+    page
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Backend `a` has items a0..a4 in pages of 2, `b` has b0 and `c` fails.
+    async fn fetch(
+        server: String,
+        cursor: Option<String>,
+    ) -> Result<(Vec<String>, Option<String>)> {
+        let start: usize = cursor.as_deref().map_or(0, |c| c.parse().unwrap());
+        match server.as_str() {
+            "a" => {
+                let end = (start + 2).min(5);
+                let items = (start..end).map(|i| format!("a{}", i)).collect();
+                Ok((items, (end < 5).then(|| end.to_string())))
+            },
+            "b" => Ok((vec!["b0".to_string()], None)),
+            _ => Err(crate::error::Error::Transport("down".into())),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pages_resume_from_cursor() {
+        let servers = ["a", "b", "c"].map(String::from).to_vec();
+        let mut cursor = PageCursor::default();
+        let mut pages = Vec::new();
+        loop {
+            let page = fill_page(&mut cursor, &servers, 3, fetch).await;
+            pages.push((page.items, page.degraded));
+            if !page.more {
+                break;
+            }
+            // Clients carry the cursor between requests
+            cursor = PageCursor::decode(&cursor.encode()).unwrap();
+        }
+
+        assert_eq!(
+            pages,
+            vec![
+                (
+                    vec!["a0", "a1", "a2"].into_iter().map(String::from).collect(),
+                    vec![]
+                ),
+                (
+                    vec!["a3", "a4", "b0"].into_iter().map(String::from).collect(),
+                    vec![]
+                ),
+                (vec![], vec!["c".to_string()]),
+            ]
+        );
+        assert!(PageCursor::decode("not a cursor").is_err());
+    }
+}
@@ -0,0 +1,195 @@
+//! Recording of request/response pairs (`observability.recording`).
+//!
+//! Each MCP request is appended to the recording file as one JSON
+//! line holding the request exactly as the client sent it and the reply the
+//! proxy gave, after redacting configured fields. `only1mcp replay` re-sends
+//! a recording and compares the replies, which makes backend regressions
+//! and configuration changes reproducible.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::warn;
+
+use crate::config::redact::REDACTED;
+use crate::config::RecordingConfig;
+use crate::error::{Error, Result};
+
+/// One recorded request and the reply it got.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Exchange {
+    pub at: chrono::DateTime<chrono::Utc>,
+    pub method: String,
+    /// JSON-RPC request as sent by the client
+    pub request: Value,
+    /// JSON-RPC reply; absent for notifications
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<Value>,
+    pub latency_ms: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+}
+
+/// Replace the value of every object key in `fields` (case-insensitive),
+/// at any depth, with [`REDACTED`].
+pub fn redact(value: &mut Value, fields: &[String]) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if fields.iter().any(|field| field.eq_ignore_ascii_case(key)) {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact(value, fields);
+                }
+            }
+        },
+        Value::Array(items) => items.iter_mut().for_each(|item| redact(item, fields)),
+        _ => {},
+    }
+}
+
+/// Appends exchanges to the configured recording file, reopening it when
+/// the path changes on reload.
+#[derive(Debug, Default)]
+pub struct Recorder {
+    file: Mutex<Option<(PathBuf, File)>>,
+    /// Path that could not be opened, so the failure is logged once
+    failed: Mutex<Option<PathBuf>>,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Redact `exchange` and append it to `config.path`.
+    pub fn record(&self, config: &RecordingConfig, mut exchange: Exchange) {
+        redact(&mut exchange.request, &config.redact_fields);
+        if let Some(response) = &mut exchange.response {
+            redact(response, &config.redact_fields);
+        }
+        let mut line = match serde_json::to_vec(&exchange) {
+            Ok(line) => line,
+            Err(e) => {
+                warn!("Failed to serialize recorded {}: {}", exchange.method, e);
+                return;
+            },
+        };
+        line.push(b'\n');
+
+        let mut file = self.file.lock();
+        if file.as_ref().map_or(true, |(path, _)| *path != config.path) {
+            *file = self.open(&config.path).map(|opened| (config.path.clone(), opened));
+        }
+        if let Some((path, opened)) = file.as_mut() {
+            if let Err(e) = opened.write_all(&line) {
+                warn!("Failed to write recording {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    fn open(&self, path: &Path) -> Option<File> {
+        let mut failed = self.failed.lock();
+        match OpenOptions::new().create(true).append(true).open(path) {
+            Ok(file) => {
+                *failed = None;
+                Some(file)
+            },
+            Err(e) => {
+                if failed.as_deref() != Some(path) {
+                    warn!("Failed to open recording {}: {}", path.display(), e);
+                    *failed = Some(path.to_path_buf());
+                }
+                None
+            },
+        }
+    }
+}
+
+/// Read the exchanges of a recording file, in recorded order.
+pub fn read_recording(path: &Path) -> Result<Vec<Exchange>> {
+    let file = File::open(path).map_err(|e| {
+        Error::Config(format!(
+            "Failed to open recording {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+
+    let mut exchanges = Vec::new();
+    for (number, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let exchange = serde_json::from_str(&line).map_err(|e| {
+            Error::Config(format!(
+                "{} line {}: not a recorded exchange: {}",
+                path.display(),
+                number + 1,
+                e
+            ))
+        })?;
+        exchanges.push(exchange);
+    }
+    Ok(exchanges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_redact_nested_fields() {
+        let mut value = json!({
+            "params": {
+                "name": "login",
+                "arguments": {"user": "ada", "Password": "hunter2", "items": [{"token": "t"}]}
+            }
+        });
+        redact(&mut value, &RecordingConfig::default().redact_fields);
+        assert_eq!(value["params"]["name"], "login");
+        assert_eq!(value["params"]["arguments"]["user"], "ada");
+        assert_eq!(value["params"]["arguments"]["Password"], REDACTED);
+        assert_eq!(value["params"]["arguments"]["items"][0]["token"], REDACTED);
+    }
+
+    #[test]
+    fn test_recording_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = RecordingConfig {
+            enabled: true,
+            path: dir.path().join("session.jsonl"),
+            ..Default::default()
+        };
+        let exchange = Exchange {
+            at: chrono::Utc::now(),
+            method: "tools/call".to_string(),
+            request: json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call",
+                "params": {"name": "echo", "arguments": {"api_key": "k"}}}),
+            response: Some(json!({"jsonrpc": "2.0", "id": 1, "result": {}})),
+            latency_ms: 2.5,
+            client: Some("ide".to_string()),
+            session: None,
+        };
+
+        let recorder = Recorder::new();
+        recorder.record(&config, exchange.clone());
+        recorder.record(&config, exchange);
+
+        let recorded = read_recording(&config.path).unwrap();
+        assert_eq!(recorded.len(), 2);
+        assert_eq!(
+            recorded[0].request["params"]["arguments"]["api_key"],
+            REDACTED
+        );
+        assert_eq!(recorded[1].client.as_deref(), Some("ide"));
+    }
+}
@@ -81,6 +81,9 @@ pub enum TransportType {
     Sse,
     StreamableHttp,
     WebSocket,
+    Grpc,
+    Unix,
+    Docker,
 }
 
 /// Health check configuration
@@ -357,6 +360,7 @@ impl RegistryInner {
                 command: cmd,
                 args,
                 env: e,
+                cwd,
             } => {
                 let mut full_command = vec![cmd.clone()];
                 full_command.extend(args.clone());
@@ -365,7 +369,7 @@ impl RegistryInner {
                     cmd.clone(),
                     Some(full_command),
                     Some(e.clone()),
-                    None,
+                    cwd.clone(),
                 )
             },
             TransportConfig::Http { url, .. } => {
@@ -375,6 +379,29 @@ impl RegistryInner {
             TransportConfig::StreamableHttp { url, .. } => {
                 (TransportType::StreamableHttp, url.clone(), None, None, None)
             },
+            TransportConfig::Grpc { endpoint, .. } => {
+                (TransportType::Grpc, endpoint.clone(), None, None, None)
+            },
+            TransportConfig::Unix { path } => (
+                TransportType::Unix,
+                format!("unix:{}", path.display()),
+                None,
+                None,
+                None,
+            ),
+            TransportConfig::Docker { image, env, .. } => {
+                let full_command = mcp
+                    .transport
+                    .process_command()
+                    .map(|process| std::iter::once(process.command).chain(process.args).collect());
+                (
+                    TransportType::Docker,
+                    image.clone(),
+                    full_command,
+                    Some(env.clone()),
+                    None,
+                )
+            },
         };
 
         let health_check = if mcp.health_check.enabled {
@@ -446,10 +473,27 @@ mod tests {
                 transport: TransportConfig::Http {
                     url: "http://localhost:8001".to_string(),
                     headers: Default::default(),
+                    oauth: None,
+                    tls: None,
+                    signing: None,
+                    proxy: None,
                 },
                 health_check: Default::default(),
                 routing: Default::default(),
                 weight: 1,
+                timeout_ms: 30000,
+                retry: Default::default(),
+                tags: Vec::new(),
+                notes: None,
+                tool_metadata: Default::default(),
+                cost: None,
+                startup: Default::default(),
+                restart: Default::default(),
+                resources: Default::default(),
+                sandbox: Default::default(),
+                env_policy: Default::default(),
+                mirror: None,
+                transforms: Default::default(),
             }],
             ..Default::default()
         };
@@ -6,6 +6,9 @@
 //! - Round-robin for simple fairness
 //! - Health-aware routing with automatic failover
 //!
+//! All algorithms weigh servers by their configured `weight`, and only the
+//! highest `routing.priority` tier with a healthy server is considered.
+//!
 //! # Routing Decision Flow
 //!
 //! 1. Extract tool/method from request
@@ -18,11 +21,22 @@
 use crate::cache::ResponseCache;
 use crate::config::RoutingAlgorithmConfig;
 use crate::error::Error;
+use crate::health::checker::{HealthHistory, HealthState as BackendHealth, HealthStateMap};
 use crate::health::circuit_breaker::CircuitBreaker;
-use crate::routing::load_balancer::ConsistentHashRing;
-use crate::types::{McpRequest, ServerId};
+use crate::routing::connections::ConnectionTracker;
+use crate::routing::failover::FailoverState;
+use crate::routing::load_balancer::{
+    compare_weighted_load, highest_priority_tier, weighted_round_robin_index, ConsistentHashRing,
+    DEFAULT_PRIORITY,
+};
+use crate::routing::split::pick_variants;
+use crate::routing::sticky::SessionTable;
+use crate::routing::tool_matcher::ToolMatcher;
+use crate::transport::stdio::ServerCapabilities;
+use crate::types::{McpRequest, Prompt, Resource, ServerId, Tool};
 use arc_swap::ArcSwap;
 use dashmap::DashMap;
+use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -135,12 +149,16 @@ pub struct RequestRouter {
     hash_ring: Arc<ArcSwap<ConsistentHashRing>>,
     /// Active server health states
     health_states: Arc<DashMap<ServerId, HealthState>>,
-    /// Per-server connection counts
-    connection_counts: Arc<DashMap<ServerId, AtomicUsize>>,
+    /// Live in-flight request counts per server
+    connections: Arc<ConnectionTracker>,
     /// Routing configuration
     config: RoutingAlgorithmConfig,
     /// Circuit breakers per backend
     circuit_breakers: Arc<DashMap<ServerId, CircuitBreaker>>,
+    /// Client-to-backend pins, consulted when sticky sessions are enabled
+    sessions: Option<Arc<SessionTable>>,
+    /// Server serving each failover group
+    failover: Arc<FailoverState>,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -175,17 +193,38 @@ impl RequestRouter {
         Self {
             hash_ring: Arc::new(ArcSwap::new(Arc::new(hash_ring))),
             health_states: Arc::new(DashMap::new()),
-            connection_counts: Arc::new(DashMap::new()),
+            connections: Arc::new(ConnectionTracker::new()),
             config,
             circuit_breakers: Arc::new(DashMap::new()),
+            sessions: None,
+            failover: Arc::new(FailoverState::new()),
         }
     }
 
+    /// Share live in-flight request counts across routers.
+    pub fn with_connections(mut self, connections: Arc<ConnectionTracker>) -> Self {
+        self.connections = connections;
+        self
+    }
+
+    /// Share failover group state across routers.
+    pub fn with_failover(mut self, failover: Arc<FailoverState>) -> Self {
+        self.failover = failover;
+        self
+    }
+
+    /// Share a sticky session table across routers.
+    pub fn with_sessions(mut self, sessions: Arc<SessionTable>) -> Self {
+        self.sessions = Some(sessions);
+        self
+    }
+
     /// Route an incoming MCP request to the appropriate backend server.
     ///
     /// # Arguments
     ///
     /// * `request` - The MCP JSON-RPC request to route
+    /// * `client` - Caller identity used for sticky sessions, if known
     /// * `registry` - Current registry of available servers
     /// * `cache` - Response cache for memoization
     ///
@@ -193,19 +232,25 @@ impl RequestRouter {
     ///
     /// * `Ok((ServerId, Duration))` - Selected server and expected latency
     /// * `Err(RoutingError)` - No available backend or routing failure
-    #[instrument(skip(self, request, registry, _cache))]
+    #[instrument(skip(self, request, client, registry, _cache))]
     pub async fn route_request(
         &self,
         request: &McpRequest,
+        client: Option<&str>,
         registry: &ServerRegistry,
         _cache: &ResponseCache,
     ) -> std::result::Result<(ServerId, Duration), RoutingError> {
-        let _method = request.method();
+        let method = request.method();
         let tool_name = extract_tool_name(request)?;
 
-        // Find servers that support this tool
+        // Find servers that support this tool and advertise the method's surface
         // Note: Response caching is handled at the handler level, not here
-        let eligible_servers = registry.find_servers_for_tool(&tool_name).await?;
+        let eligible_servers: Vec<ServerId> = registry
+            .find_servers_for_tool(&tool_name)
+            .await?
+            .into_iter()
+            .filter(|id| registry.supports_method(id, &method))
+            .collect();
 
         if eligible_servers.is_empty() {
             error!("No servers available for tool: {}", tool_name);
@@ -214,11 +259,11 @@ impl RequestRouter {
 
         // Step 3: Filter by health status and circuit breaker state
         let healthy_servers: Vec<ServerId> = eligible_servers
-            .into_iter()
-            .filter(|id| {
-                // Check health state
-                let is_healthy =
-                    self.health_states.get(id).map(|state| state.is_healthy()).unwrap_or(false);
+            .iter()
+            .filter(|&id| {
+                // Check active health checks and locally observed request outcomes
+                let is_healthy = registry.is_available(id)
+                    && self.health_states.get(id).map(|state| state.is_healthy()).unwrap_or(true);
 
                 // Check circuit breaker
                 let circuit_open =
@@ -226,34 +271,50 @@ impl RequestRouter {
 
                 is_healthy && !circuit_open
             })
+            .cloned()
             .collect();
 
+        // Standbys of a failover group only serve while its primary cannot
+        let healthy_servers = self.failover.filter(
+            &self.config.failover_groups,
+            &eligible_servers,
+            healthy_servers,
+        );
+
         if healthy_servers.is_empty() {
             warn!("All backends unhealthy for tool: {}", tool_name);
             return Err(RoutingError::AllBackendsUnhealthy(tool_name));
         }
 
-        // Step 4: Apply routing algorithm
-        let algorithm = RoutingAlgorithm::from_str(&self.config.algorithm).unwrap();
-        let selected_server = match algorithm {
-            RoutingAlgorithm::ConsistentHash => {
-                self.route_consistent_hash(&tool_name, &healthy_servers)
+        // Sticky sessions: reuse the client's pinned backend while it is
+        // still eligible and healthy, otherwise rebalance below
+        let sticky = match (&self.sessions, client) {
+            (Some(sessions), Some(client)) if self.config.sticky_sessions => {
+                Some((sessions, client))
             },
-            RoutingAlgorithm::LeastConnections => self.route_least_connections(&healthy_servers),
-            RoutingAlgorithm::RoundRobin => self.route_round_robin(&healthy_servers),
-            RoutingAlgorithm::Random => self.route_random(&healthy_servers),
-            RoutingAlgorithm::WeightedRandom => {
-                self.route_weighted_random(&healthy_servers, registry).await
+            _ => None,
+        };
+        let pinned = sticky.and_then(|(sessions, client)| {
+            sessions.lookup(client, self.config.session_ttl(), |id| {
+                healthy_servers.contains(id)
+            })
+        });
+
+        let selected_server = match pinned {
+            Some(server_id) => server_id,
+            None => {
+                // Versions of a split service share its traffic by weight
+                let healthy_servers = pick_variants(&self.config.traffic_splits, healthy_servers);
+                let server_id =
+                    self.select_server(&tool_name, client, &healthy_servers, registry)?;
+                if let Some((sessions, client)) = sticky {
+                    sessions.pin(client, &server_id);
+                }
+                server_id
             },
-        }?;
-
-        // Step 5: Update connection count
-        self.connection_counts
-            .entry(selected_server.clone())
-            .or_insert_with(|| AtomicUsize::new(0))
-            .fetch_add(1, Ordering::Relaxed);
+        };
 
-        // Step 6: Estimate latency based on historical data
+        // Step 5: Estimate latency based on historical data
         let estimated_latency = self.estimate_latency(&selected_server).await;
 
         info!(
@@ -264,15 +325,60 @@ impl RequestRouter {
         Ok((selected_server, estimated_latency))
     }
 
+    /// Pick a backend from `healthy_servers` with the configured algorithm,
+    /// or, among front-door instances, by consistent hash of the client.
+    fn select_server(
+        &self,
+        tool_name: &str,
+        client: Option<&str>,
+        healthy_servers: &[ServerId],
+        registry: &ServerRegistry,
+    ) -> std::result::Result<ServerId, RoutingError> {
+        // Prefer the highest priority tier; lower tiers only serve while
+        // every server above them is unavailable
+        let tier = highest_priority_tier(healthy_servers, |id| registry.server_priority(id));
+        let weights: Vec<u32> = tier.iter().map(|id| registry.server_weight(id)).collect();
+
+        // A front door shards clients across its instances
+        let front_door = &self.config.front_door;
+        if let Some(client) = client.filter(|_| tier.iter().all(|id| front_door.is_instance(id))) {
+            return self.route_consistent_hash(client, &tier, &weights);
+        }
+
+        // Step 4: Apply routing algorithm
+        let algorithm = RoutingAlgorithm::from_str(&self.config.algorithm).unwrap();
+        match algorithm {
+            RoutingAlgorithm::ConsistentHash => {
+                self.route_consistent_hash(tool_name, &tier, &weights)
+            },
+            RoutingAlgorithm::LeastConnections => self.route_least_connections(&tier, &weights),
+            RoutingAlgorithm::RoundRobin => self.route_round_robin(&tier, &weights),
+            RoutingAlgorithm::Random | RoutingAlgorithm::WeightedRandom => {
+                self.route_weighted_random(&tier, &weights)
+            },
+        }
+    }
+
     /// Consistent hashing implementation with virtual nodes.
     ///
     /// Provides session affinity while maintaining good load distribution
-    /// even when servers are added or removed from the pool.
+    /// even when servers are added or removed from the pool. Servers missing
+    /// from the ring are added with virtual nodes scaled by their weight.
     fn route_consistent_hash(
         &self,
         key: &str,
         servers: &[ServerId],
+        weights: &[u32],
     ) -> std::result::Result<ServerId, RoutingError> {
+        if servers.iter().any(|id| !self.hash_ring.load().contains(id)) {
+            let mut ring = (**self.hash_ring.load()).clone();
+            for (id, &weight) in servers.iter().zip(weights) {
+                if !ring.contains(id) {
+                    ring.add_weighted_server(id, weight);
+                }
+            }
+            self.hash_ring.store(Arc::new(ring));
+        }
         let hash_ring = self.hash_ring.load();
 
         // Hash the routing key
@@ -290,10 +396,12 @@ impl RequestRouter {
     /// Least connections routing using Power of Two Choices.
     ///
     /// Randomly selects two servers and routes to the one with fewer
-    /// active connections. O(1) complexity with near-optimal distribution.
+    /// active connections per unit of weight. O(1) complexity with
+    /// near-optimal distribution.
     fn route_least_connections(
         &self,
         servers: &[ServerId],
+        weights: &[u32],
     ) -> std::result::Result<ServerId, RoutingError> {
         use rand::seq::SliceRandom;
 
@@ -303,62 +411,46 @@ impl RequestRouter {
 
         // Power of Two Choices algorithm
         let mut rng = rand::thread_rng();
-        let candidates: Vec<_> = servers.choose_multiple(&mut rng, 2.min(servers.len())).collect();
+        let indices: Vec<usize> = (0..servers.len()).collect();
+        let candidates: Vec<usize> =
+            indices.choose_multiple(&mut rng, 2.min(servers.len())).copied().collect();
 
-        // Select server with minimum connections
+        // Select server with minimum connections per unit of weight
+        let load = |index: usize| (self.connections.in_flight(&servers[index]), weights[index]);
         let selected = candidates
             .into_iter()
-            .min_by_key(|&id| {
-                self.connection_counts
-                    .get(id)
-                    .map(|count| count.load(Ordering::Relaxed))
-                    .unwrap_or(0)
-            })
+            .min_by(|&a, &b| compare_weighted_load(load(a), load(b)))
+            .map(|index| &servers[index])
             .ok_or(RoutingError::NoServerSelected)?;
 
         debug!("Least connections selected: {}", selected);
         Ok(selected.clone())
     }
 
-    /// Simple round-robin routing for fairness.
+    /// Weighted round-robin: each server gets `weight` turns per cycle.
     fn route_round_robin(
         &self,
         servers: &[ServerId],
+        weights: &[u32],
     ) -> std::result::Result<ServerId, RoutingError> {
         static COUNTER: AtomicUsize = AtomicUsize::new(0);
 
-        let index = COUNTER.fetch_add(1, Ordering::Relaxed) % servers.len();
-        let selected = &servers[index];
+        let turn = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let selected = &servers[weighted_round_robin_index(weights, turn)];
 
         debug!("Round-robin selected: {}", selected);
         Ok(selected.clone())
     }
 
-    /// Random server selection for simplicity.
-    fn route_random(&self, servers: &[ServerId]) -> std::result::Result<ServerId, RoutingError> {
-        use rand::seq::SliceRandom;
-
-        let mut rng = rand::thread_rng();
-        let selected = servers.choose(&mut rng).ok_or(RoutingError::NoServerSelected)?;
-
-        debug!("Random selected: {}", selected);
-        Ok(selected.clone())
-    }
-
     /// Weighted random selection based on server weights.
-    async fn route_weighted_random(
+    fn route_weighted_random(
         &self,
         servers: &[ServerId],
-        registry: &ServerRegistry,
+        weights: &[u32],
     ) -> std::result::Result<ServerId, RoutingError> {
         use rand::distributions::{Distribution, WeightedIndex};
 
-        // Get weights for each server
-        let weights: Vec<u32> =
-            futures::future::join_all(servers.iter().map(|id| registry.get_server_weight(id)))
-                .await;
-
-        let dist = WeightedIndex::new(&weights).map_err(|_| RoutingError::NoServerSelected)?;
+        let dist = WeightedIndex::new(weights).map_err(|_| RoutingError::NoServerSelected)?;
 
         let mut rng = rand::thread_rng();
         let index = dist.sample(&mut rng);
@@ -432,7 +524,6 @@ impl RequestRouter {
         // Clean up state
         self.health_states.remove(server_id);
         self.circuit_breakers.remove(server_id);
-        self.connection_counts.remove(server_id);
 
         info!("Removed server {} from router", server_id);
     }
@@ -452,6 +543,12 @@ fn extract_tool_name(request: &McpRequest) -> std::result::Result<String, Routin
 /// Server registry for tracking available backends.
 pub struct ServerRegistry {
     servers: DashMap<ServerId, ServerInfo>,
+    /// Latest active health check state per server (absent = not yet checked)
+    health: HealthStateMap,
+    /// Recent health transitions (kept across hot reloads)
+    history: HealthHistory,
+    /// Tool routing rules compiled from all servers' `routing.tools` patterns
+    tool_matcher: ToolMatcher,
 }
 
 impl Default for ServerRegistry {
@@ -465,31 +562,97 @@ impl ServerRegistry {
     pub fn new() -> Self {
         Self {
             servers: DashMap::new(),
+            health: Arc::new(DashMap::new()),
+            history: HealthHistory::default(),
+            tool_matcher: ToolMatcher::new(),
         }
     }
 
     /// Create server registry from config
     pub async fn from_config(config: &crate::config::Config) -> std::result::Result<Self, Error> {
-        let registry = Self::new();
-
-        // Populate registry from config servers
-        for server in &config.servers {
-            if server.enabled {
-                let info = ServerInfo {
-                    id: server.id.clone(),
-                    weight: server.weight,
-                    tools: Vec::new(), // Would be discovered from server capabilities
-                };
-                registry.servers.insert(server.id.clone(), info);
-            }
+        let mut registry = Self::new();
+
+        // Populate registry from config servers; mirror targets are only
+        // reached through mirroring
+        for server in config.routed_servers() {
+            let info = ServerInfo {
+                id: server.id.clone(),
+                weight: server.weight,
+                priority: server.routing.priority,
+                tools: Vec::new(), // Would be discovered from server capabilities
+                rules: server.routing.tools.clone(),
+                capabilities: None,
+                capabilities_requested: false,
+                catalog: ServerCatalog::default(),
+            };
+            registry.servers.insert(server.id.clone(), info);
         }
 
+        registry.rebuild_tool_matcher();
         Ok(registry)
     }
 
+    /// Recompile the tool routing rules after the server set changed.
+    fn rebuild_tool_matcher(&mut self) {
+        let mut servers: Vec<(ServerId, Vec<String>)> = self
+            .servers
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().rules.clone()))
+            .collect();
+        servers.sort_by(|a, b| a.0.cmp(&b.0));
+
+        self.tool_matcher =
+            ToolMatcher::compile(servers.iter().map(|(id, rules)| (id.as_str(), rules)));
+    }
+
     /// Get list of healthy server IDs
+    ///
+    /// Servers that have not been checked yet (or have health checks disabled)
+    /// are considered available; only servers marked unhealthy are excluded.
     pub async fn get_healthy_servers(&self) -> Vec<String> {
-        self.servers.iter().map(|entry| entry.key().clone()).collect()
+        let mut servers: Vec<String> = self
+            .servers
+            .iter()
+            .filter(|entry| self.is_available(entry.key()))
+            .map(|entry| entry.key().clone())
+            .collect();
+        servers.sort();
+        servers
+    }
+
+    /// Shared health state map, handed to health checkers so their results
+    /// drive routing.
+    pub fn health_states(&self) -> HealthStateMap {
+        self.health.clone()
+    }
+
+    /// Shared health transition log, appended to by health checkers.
+    pub fn health_history(&self) -> HealthHistory {
+        self.history.clone()
+    }
+
+    /// Tools each server advertised in its latest tools/list, sorted by server ID.
+    pub fn server_tools(&self) -> Vec<(ServerId, Vec<String>)> {
+        let mut servers: Vec<_> = self
+            .servers
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().tools.clone()))
+            .collect();
+        servers.sort_by(|a, b| a.0.cmp(&b.0));
+        servers
+    }
+
+    /// Current health state of a server.
+    pub fn health_state(&self, server_id: &str) -> BackendHealth {
+        self.health
+            .get(server_id)
+            .map(|state| state.clone())
+            .unwrap_or(BackendHealth::Unknown)
+    }
+
+    /// Whether a server may receive traffic based on its health state.
+    pub fn is_available(&self, server_id: &str) -> bool {
+        self.health_state(server_id) != BackendHealth::Unhealthy
     }
 
     /// Get server configuration by ID
@@ -499,31 +662,154 @@ impl ServerRegistry {
         None
     }
 
+    /// Record the tools a server advertised in its latest tools/list.
+    pub fn set_server_tools(&self, server_id: &str, tools: Vec<Tool>) {
+        if let Some(mut info) = self.servers.get_mut(server_id) {
+            info.tools = tools.iter().map(|tool| tool.name.clone()).collect();
+            info.catalog.tools = Some(CatalogEntry::new(tools));
+        }
+    }
+
+    /// Record the resources a server advertised in its latest resources/list.
+    pub fn set_server_resources(&self, server_id: &str, resources: Vec<Resource>) {
+        if let Some(mut info) = self.servers.get_mut(server_id) {
+            info.catalog.resources = Some(CatalogEntry::new(resources));
+        }
+    }
+
+    /// Record the prompts a server advertised in its latest prompts/list.
+    pub fn set_server_prompts(&self, server_id: &str, prompts: Vec<Prompt>) {
+        if let Some(mut info) = self.servers.get_mut(server_id) {
+            info.catalog.prompts = Some(CatalogEntry::new(prompts));
+        }
+    }
+
+    /// Everything discovered about a server's tools, resources and prompts.
+    pub fn server_catalog(&self, server_id: &str) -> Option<ServerCatalog> {
+        self.servers.get(server_id).map(|info| info.catalog.clone())
+    }
+
+    /// Tools of a server discovered less than `max_age` ago.
+    pub fn fresh_tools(&self, server_id: &str, max_age: Duration) -> Option<Vec<Tool>> {
+        self.servers.get(server_id)?.catalog.tools.as_ref()?.fresh(max_age)
+    }
+
+    /// Definition of a tool as its server last listed it.
+    pub fn listed_tool(&self, server_id: &str, tool: &str) -> Option<Tool> {
+        let info = self.servers.get(server_id)?;
+        let tools = &info.catalog.tools.as_ref()?.items;
+        tools.iter().find(|t| t.name == tool).cloned()
+    }
+
+    /// Resources of a server discovered less than `max_age` ago.
+    pub fn fresh_resources(&self, server_id: &str, max_age: Duration) -> Option<Vec<Resource>> {
+        self.servers.get(server_id)?.catalog.resources.as_ref()?.fresh(max_age)
+    }
+
+    /// Prompts of a server discovered less than `max_age` ago.
+    pub fn fresh_prompts(&self, server_id: &str, max_age: Duration) -> Option<Vec<Prompt>> {
+        self.servers.get(server_id)?.catalog.prompts.as_ref()?.fresh(max_age)
+    }
+
+    /// Forget what was discovered about a server (all servers when `None`),
+    /// so the next lookup asks the backend again. Tool routing keeps the
+    /// last known tool names until then.
+    pub fn invalidate_catalog(&self, server_id: Option<&str>) {
+        for mut info in self.servers.iter_mut() {
+            if server_id.map_or(true, |id| id == info.key()) {
+                info.catalog = ServerCatalog::default();
+            }
+        }
+    }
+
+    /// Record the capabilities a server reported in its `initialize` result.
+    pub fn set_server_capabilities(&self, server_id: &str, capabilities: ServerCapabilities) {
+        if let Some(mut info) = self.servers.get_mut(server_id) {
+            info.capabilities = Some(capabilities);
+        }
+    }
+
+    /// Capabilities a server reported, if known.
+    pub fn server_capabilities(&self, server_id: &str) -> Option<ServerCapabilities> {
+        self.servers.get(server_id).and_then(|info| info.capabilities.clone())
+    }
+
+    /// Mark capability discovery as started for a server. Returns `false` if
+    /// it already was, so each registration is probed at most once.
+    pub fn begin_capability_discovery(&self, server_id: &str) -> bool {
+        match self.servers.get_mut(server_id) {
+            Some(mut info) if !info.capabilities_requested => {
+                info.capabilities_requested = true;
+                true
+            },
+            _ => false,
+        }
+    }
+
+    /// Whether a server may serve `method` according to its capabilities.
+    ///
+    /// Servers whose capabilities are not known yet are given the benefit of
+    /// the doubt.
+    pub fn supports_method(&self, server_id: &str, method: &str) -> bool {
+        self.servers
+            .get(server_id)
+            .and_then(|info| info.capabilities.as_ref().map(|c| c.supports_method(method)))
+            .unwrap_or(true)
+    }
+
+    /// Map of tool name to the server providing it, as last advertised.
+    ///
+    /// When several servers offer the same tool, the one with the lowest ID
+    /// wins, matching the deduplication of the aggregated catalog.
+    pub fn tool_owners(&self) -> HashMap<String, ServerId> {
+        let mut servers: Vec<_> = self.servers.iter().collect();
+        servers.sort_by(|a, b| b.key().cmp(a.key()));
+
+        let mut owners = HashMap::new();
+        for server in servers {
+            for tool in &server.tools {
+                owners.insert(tool.clone(), server.key().clone());
+            }
+        }
+        owners
+    }
+
     /// Find servers that support a specific tool.
     pub async fn find_servers_for_tool(
         &self,
         tool: &str,
     ) -> std::result::Result<Vec<ServerId>, Error> {
-        let servers: Vec<ServerId> = self
-            .servers
-            .iter()
-            .filter(|entry| entry.value().supports_tool(tool))
-            .map(|entry| entry.key().clone())
-            .collect();
-
-        Ok(servers)
+        Ok(self.tool_matcher.servers_for(tool))
     }
 
     /// Get the weight of a server for weighted routing.
     pub async fn get_server_weight(&self, server_id: &ServerId) -> u32 {
+        self.server_weight(server_id)
+    }
+
+    /// Weight of a server (1 if unknown).
+    pub fn server_weight(&self, server_id: &str) -> u32 {
         self.servers.get(server_id).map(|info| info.weight).unwrap_or(1)
     }
 
+    /// Routing priority of a server; higher values are preferred.
+    pub fn server_priority(&self, server_id: &str) -> u32 {
+        self.servers
+            .get(server_id)
+            .map(|info| info.priority)
+            .unwrap_or(DEFAULT_PRIORITY)
+    }
+
     /// Get the number of registered servers
     pub fn len(&self) -> usize {
         self.servers.len()
     }
 
+    /// Whether a server is registered for routing.
+    pub fn contains(&self, server_id: &str) -> bool {
+        self.servers.contains_key(server_id)
+    }
+
     /// Check if the registry is empty
     pub fn is_empty(&self) -> bool {
         self.servers.is_empty()
@@ -532,6 +818,8 @@ impl ServerRegistry {
     /// Clear all servers from the registry (used during hot-reload)
     pub fn clear(&mut self) {
         self.servers.clear();
+        self.health.clear();
+        self.tool_matcher = ToolMatcher::new();
     }
 
     /// Add a server to the registry (used during hot-reload)
@@ -542,18 +830,80 @@ impl ServerRegistry {
         let info = ServerInfo {
             id: server_config.id.clone(),
             weight: server_config.weight,
+            priority: server_config.routing.priority,
             tools: Vec::new(), // Would be discovered from server capabilities
+            rules: server_config.routing.tools.clone(),
+            capabilities: None,
+            capabilities_requested: false,
+            catalog: ServerCatalog::default(),
         };
         self.servers.insert(server_config.id, info);
+        self.rebuild_tool_matcher();
         Ok(())
     }
 }
 
+/// A list discovered from a server and when it was fetched.
+#[derive(Debug, Clone)]
+pub struct CatalogEntry<T> {
+    pub items: Vec<T>,
+    pub refreshed_at: chrono::DateTime<chrono::Utc>,
+    fetched: Instant,
+}
+
+impl<T: Clone> CatalogEntry<T> {
+    fn new(items: Vec<T>) -> Self {
+        Self {
+            items,
+            refreshed_at: chrono::Utc::now(),
+            fetched: Instant::now(),
+        }
+    }
+
+    /// The items, if fetched less than `max_age` ago.
+    fn fresh(&self, max_age: Duration) -> Option<Vec<T>> {
+        (self.fetched.elapsed() < max_age).then(|| self.items.clone())
+    }
+}
+
+/// Tools, resources and prompts a server advertised; `None` until the
+/// corresponding list has been fetched (or after an invalidation).
+#[derive(Debug, Clone, Default)]
+pub struct ServerCatalog {
+    pub tools: Option<CatalogEntry<Tool>>,
+    pub resources: Option<CatalogEntry<Resource>>,
+    pub prompts: Option<CatalogEntry<Prompt>>,
+}
+
+impl ServerCatalog {
+    /// When any of the lists was last fetched.
+    pub fn last_refreshed(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        [
+            self.tools.as_ref().map(|e| e.refreshed_at),
+            self.resources.as_ref().map(|e| e.refreshed_at),
+            self.prompts.as_ref().map(|e| e.refreshed_at),
+        ]
+        .into_iter()
+        .flatten()
+        .max()
+    }
+}
+
 /// Server information for routing decisions.
 pub struct ServerInfo {
     pub id: ServerId,
     pub weight: u32,
+    /// Routing priority (`routing.priority`); higher tiers are preferred
+    pub priority: u32,
     pub tools: Vec<String>,
+    /// Tool routing patterns from config (`routing.tools`)
+    pub rules: Vec<String>,
+    /// Capabilities from the server's `initialize` result, once discovered
+    pub capabilities: Option<ServerCapabilities>,
+    /// Whether capability discovery has been started
+    pub capabilities_requested: bool,
+    /// Lists discovered from the server, reused until they go stale
+    pub catalog: ServerCatalog,
 }
 
 impl ServerInfo {
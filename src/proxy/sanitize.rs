@@ -0,0 +1,268 @@
+//! Sanitization of tools/call results (`proxy.sanitization`).
+//!
+//! Backend output reaches the model's context verbatim, so a result is
+//! cleaned up before it is returned:
+//!
+//! - ANSI escape sequences and other control characters (except newlines
+//!   and tabs) are removed from every string.
+//! - Arrays and objects nested deeper than `max_depth` are replaced by a
+//!   notice.
+//! - Images, audio and binary resources whose MIME type is not allowed, or
+//!   whose payload is not base64, are replaced by a text notice.
+//!
+//! With `validate_output`, the `structuredContent` of a tool that declares
+//! an `outputSchema` is also checked against it; see [`check_output`].
+
+use base64::Engine;
+use serde_json::{json, Value};
+
+use crate::config::SanitizationConfig;
+use crate::proxy::arguments::ToolSchemas;
+use crate::routing::tool_matcher::glob_match;
+
+/// Replaces values nested deeper than `max_depth`.
+const TOO_DEEP: &str = "[Nested too deep]";
+
+/// What sanitizing changed in a result.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Fixes {
+    /// Control characters were removed from strings
+    pub control: bool,
+    /// Deeply nested values were replaced
+    pub depth: bool,
+    /// Binary content was replaced
+    pub binary: bool,
+}
+
+impl Fixes {
+    /// Labels of the fixes made, for `only1mcp_sanitized_results_total`.
+    pub fn labels(self) -> impl Iterator<Item = &'static str> {
+        [
+            (self.control, "control"),
+            (self.depth, "depth"),
+            (self.binary, "binary"),
+        ]
+        .into_iter()
+        .filter_map(|(made, label)| made.then_some(label))
+    }
+}
+
+/// Clean up a tools/call `result` in place.
+pub fn sanitize_result(result: &mut Value, settings: &SanitizationConfig) -> Fixes {
+    Fixes {
+        depth: limit_depth(result, settings.max_depth),
+        control: settings.strip_ansi && strip_strings(result),
+        binary: drop_binary(result, &settings.binary_mime_types),
+    }
+}
+
+/// Check the `structuredContent` of a successful tools/call `result`
+/// against the tool's `outputSchema`, describing why it does not match.
+pub fn check_output(schemas: &ToolSchemas, schema: &Value, result: &Value) -> Result<(), String> {
+    if result.get("isError") == Some(&Value::Bool(true)) {
+        return Ok(());
+    }
+    let Some(structured) = result.get("structuredContent") else {
+        return Err("structuredContent is missing".to_string());
+    };
+    schemas.check(schema, structured).map_err(|problems| problems.join("; "))
+}
+
+/// Replace the arrays and objects below `depth` levels of nesting,
+/// returning whether any was.
+fn limit_depth(value: &mut Value, depth: usize) -> bool {
+    match value {
+        Value::Array(_) | Value::Object(_) if depth == 0 => {
+            *value = json!(TOO_DEEP);
+            true
+        },
+        Value::Array(items) => {
+            items.iter_mut().fold(false, |cut, item| limit_depth(item, depth - 1) | cut)
+        },
+        Value::Object(fields) => fields
+            .values_mut()
+            .fold(false, |cut, field| limit_depth(field, depth - 1) | cut),
+        _ => false,
+    }
+}
+
+/// Remove control characters from every string in `value`, returning
+/// whether any string changed.
+fn strip_strings(value: &mut Value) -> bool {
+    match value {
+        Value::String(text) => match strip_control(text) {
+            Some(stripped) => {
+                *text = stripped;
+                true
+            },
+            None => false,
+        },
+        Value::Array(items) => {
+            items.iter_mut().fold(false, |changed, item| strip_strings(item) | changed)
+        },
+        Value::Object(fields) => {
+            fields.values_mut().fold(false, |changed, field| strip_strings(field) | changed)
+        },
+        _ => false,
+    }
+}
+
+/// `text` without ANSI escape sequences and control characters other than
+/// newlines and tabs, or `None` if it has none.
+fn strip_control(text: &str) -> Option<String> {
+    let stripped = |c: char| c.is_control() && !matches!(c, '\n' | '\r' | '\t');
+    if !text.chars().any(stripped) {
+        return None;
+    }
+
+    let mut clean = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\u{1b}' => match chars.next() {
+                Some('[') => skip_csi(&mut chars),
+                // OSC, DCS, SOS, PM and APC strings end with BEL or ESC \
+                Some(']' | 'P' | 'X' | '^' | '_') => {
+                    while let Some(c) = chars.next() {
+                        if c == '\u{7}' {
+                            break;
+                        }
+                        if c == '\u{1b}' {
+                            chars.next_if_eq(&'\\');
+                            break;
+                        }
+                    }
+                },
+                // Two-character sequences
+                _ => {},
+            },
+            '\u{9b}' => skip_csi(&mut chars),
+            c if stripped(c) => {},
+            c => clean.push(c),
+        }
+    }
+    Some(clean)
+}
+
+/// Skip the parameters of a control sequence up to its final byte.
+fn skip_csi(chars: &mut impl Iterator<Item = char>) {
+    for c in chars.by_ref() {
+        if ('\u{40}'..='\u{7e}').contains(&c) {
+            break;
+        }
+    }
+}
+
+/// Replace the unexpected binary items of a result's content, returning
+/// whether any was.
+fn drop_binary(result: &mut Value, allowed: &[String]) -> bool {
+    let Some(content) = result.get_mut("content").and_then(Value::as_array_mut) else {
+        return false;
+    };
+    let mut dropped = false;
+    for item in content.iter_mut() {
+        if let Some(notice) = unexpected_binary(item, allowed) {
+            *item = json!({"type": "text", "text": notice});
+            dropped = true;
+        }
+    }
+    dropped
+}
+
+/// A notice replacing a content item carrying binary data of a type that
+/// is not allowed, or that is not base64.
+fn unexpected_binary(item: &Value, allowed: &[String]) -> Option<String> {
+    let payload = item.get("resource").unwrap_or(item);
+    let data = payload.get("data").or_else(|| payload.get("blob"))?.as_str()?;
+    let mime = payload.get("mimeType").and_then(Value::as_str).unwrap_or_default();
+    let expected = allowed.iter().any(|pattern| glob_match(pattern, mime))
+        && base64::engine::general_purpose::STANDARD.decode(data).is_ok();
+    (!expected).then(|| {
+        format!(
+            "[Binary content removed: {} bytes of {}]",
+            data.len(),
+            if mime.is_empty() { "unknown type" } else { mime }
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> SanitizationConfig {
+        SanitizationConfig {
+            enabled: true,
+            max_depth: 4,
+            ..SanitizationConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_control_characters_stripped() {
+        assert_eq!(strip_control("plain\ttext\n"), None);
+        assert_eq!(
+            strip_control("\u{1b}[1;31merror\u{1b}[0m: \u{1b}]0;title\u{7}done\u{0}").as_deref(),
+            Some("error: done")
+        );
+
+        let mut result = json!({
+            "content": [{"type": "text", "text": "\u{1b}[32mok\u{1b}[0m"}],
+            "structuredContent": {"status": "\u{1b}[32mok"}
+        });
+        let fixes = sanitize_result(&mut result, &settings());
+        assert_eq!(fixes.labels().collect::<Vec<_>>(), ["control"]);
+        assert_eq!(result["content"][0]["text"], "ok");
+        assert_eq!(result["structuredContent"]["status"], "ok");
+    }
+
+    #[test]
+    fn test_deep_nesting_and_unexpected_binary_replaced() {
+        let mut result = json!({
+            "content": [
+                {"type": "image", "data": "aGVsbG8=", "mimeType": "image/png"},
+                {"type": "image", "data": "not base64!", "mimeType": "image/png"},
+                {"type": "resource", "resource": {"uri": "file:///a.exe", "blob": "aGVsbG8=",
+                                                  "mimeType": "application/x-msdownload"}}
+            ],
+            "structuredContent": {"a": {"b": {"c": {"d": 1}}, "kept": [1]}}
+        });
+        let fixes = sanitize_result(&mut result, &settings());
+        assert_eq!(fixes.labels().collect::<Vec<_>>(), ["depth", "binary"]);
+        assert_eq!(result["content"][0]["mimeType"], "image/png");
+        assert_eq!(
+            result["content"][1]["text"],
+            "[Binary content removed: 11 bytes of image/png]"
+        );
+        assert_eq!(
+            result["content"][2]["text"],
+            "[Binary content removed: 8 bytes of application/x-msdownload]"
+        );
+        assert_eq!(
+            result["structuredContent"],
+            json!({"a": {"b": {"c": TOO_DEEP}, "kept": [1]}})
+        );
+    }
+
+    #[test]
+    fn test_output_checked_against_schema() {
+        let schemas = ToolSchemas::new();
+        let schema = json!({"type": "object", "required": ["count"]});
+        let valid = json!({"content": [], "structuredContent": {"count": 1}});
+        assert!(check_output(&schemas, &schema, &valid).is_ok());
+
+        let invalid = json!({"content": [], "structuredContent": {}});
+        let error = check_output(&schemas, &schema, &invalid).unwrap_err();
+        assert!(
+            error.contains("\"count\" is a required property"),
+            "{}",
+            error
+        );
+        let missing = json!({"content": []});
+        assert!(check_output(&schemas, &schema, &missing).is_err());
+
+        // Error results carry no structured content
+        let failed = json!({"content": [], "isError": true});
+        assert!(check_output(&schemas, &schema, &failed).is_ok());
+    }
+}
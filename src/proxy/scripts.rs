@@ -0,0 +1,393 @@
+//! Request policy scripts (`proxy.scripts`).
+//!
+//! Each hook is a [Rhai](https://rhai.rs) script run on every tools/call:
+//!
+//! | Hook | Runs | Variables | Effect |
+//! |------|------|-----------|--------|
+//! | `on_request` | before routing | `tool`, `client`, `arguments` | changes to `arguments` are sent |
+//! | `on_route` | after routing | also `server` | a returned string is the server ID to call |
+//! | `on_response` | on the backend's result | also `result` | changes to `result` are returned |
+//!
+//! `client` is `()` for anonymous callers. A hook fails the call with
+//! `reject("reason")` (or `throw`). Scripts cannot reach files, the network
+//! or processes, and are stopped after `max_operations`. A script whose file
+//! changes is compiled again; while the new version does not compile, the
+//! previous one keeps running. A script that fails fails the call rather
+//! than letting it through.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use parking_lot::Mutex;
+use rhai::{Dynamic, Engine, EvalAltResult, Position, Scope, AST};
+use serde_json::Value;
+use tracing::{debug, error, info};
+
+use crate::config::ScriptsConfig;
+
+/// How often a script's file is checked for changes.
+const RELOAD_CHECK: Duration = Duration::from_secs(1);
+
+/// Longest string and largest array or map a script may build.
+const MAX_STRING_BYTES: usize = 16 * 1024 * 1024;
+const MAX_COLLECTION_SIZE: usize = 1_000_000;
+
+/// A script hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    Request,
+    Route,
+    Response,
+}
+
+impl Hook {
+    pub fn name(self) -> &'static str {
+        match self {
+            Hook::Request => "on_request",
+            Hook::Route => "on_route",
+            Hook::Response => "on_response",
+        }
+    }
+}
+
+/// Why a hook did not let a call through.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptError {
+    /// The script rejected the call with this reason
+    Rejected(String),
+    /// The script could not be compiled or run
+    Failed(String),
+}
+
+/// The tools/call a hook runs for.
+#[derive(Debug, Clone, Copy)]
+pub struct ToolCall<'a> {
+    pub tool: &'a str,
+    pub client: Option<&'a str>,
+}
+
+/// Compiles, caches and runs the configured scripts.
+pub struct ScriptHooks {
+    engine: Engine,
+    /// `max_operations` of the running configuration
+    max_operations: Arc<AtomicU64>,
+    compiled: Mutex<HashMap<PathBuf, Compiled>>,
+}
+
+struct Compiled {
+    ast: Arc<AST>,
+    modified: Option<SystemTime>,
+    checked: Instant,
+}
+
+impl Default for ScriptHooks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScriptHooks {
+    pub fn new() -> Self {
+        let max_operations = Arc::new(AtomicU64::new(u64::MAX));
+        let mut engine = sandboxed_engine();
+        let limit = max_operations.clone();
+        engine.on_progress(move |operations| {
+            (operations > limit.load(Ordering::Relaxed))
+                .then(|| Dynamic::from("too many operations"))
+        });
+        engine.on_print(|text| info!("Script: {}", text));
+        engine.on_debug(|text, _, position| debug!("Script at {}: {}", position, text));
+        engine.register_fn(
+            "reject",
+            |reason: &str| -> Result<Dynamic, Box<EvalAltResult>> {
+                Err(EvalAltResult::ErrorRuntime(reason.into(), Position::NONE).into())
+            },
+        );
+        Self {
+            engine,
+            max_operations,
+            compiled: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Run `on_request`, which may change `arguments`.
+    pub fn on_request(
+        &self,
+        settings: &ScriptsConfig,
+        call: ToolCall<'_>,
+        arguments: &mut Value,
+    ) -> Result<(), ScriptError> {
+        let Some(path) = &settings.on_request else {
+            return Ok(());
+        };
+        let mut scope = call_scope(call);
+        scope.push_dynamic("arguments", to_dynamic(arguments)?);
+        self.run(settings, path, &mut scope).map(drop)?;
+        *arguments = read_back(&scope, "arguments")?;
+        Ok(())
+    }
+
+    /// Run `on_route`, returning the server it picked instead of `server`.
+    pub fn on_route(
+        &self,
+        settings: &ScriptsConfig,
+        call: ToolCall<'_>,
+        arguments: &Value,
+        server: &str,
+    ) -> Result<Option<String>, ScriptError> {
+        let Some(path) = &settings.on_route else {
+            return Ok(None);
+        };
+        let mut scope = call_scope(call);
+        scope.push_dynamic("arguments", to_dynamic(arguments)?);
+        scope.push("server", server.to_string());
+        let picked = self.run(settings, path, &mut scope)?;
+        if picked.is_unit() {
+            return Ok(None);
+        }
+        picked.into_string().map(Some).map_err(|kind| {
+            ScriptError::Failed(format!(
+                "on_route must return a server ID or nothing, not {}",
+                kind
+            ))
+        })
+    }
+
+    /// Run `on_response`, which may change `result`.
+    pub fn on_response(
+        &self,
+        settings: &ScriptsConfig,
+        call: ToolCall<'_>,
+        server: &str,
+        result: &mut Value,
+    ) -> Result<(), ScriptError> {
+        let Some(path) = &settings.on_response else {
+            return Ok(());
+        };
+        let mut scope = call_scope(call);
+        scope.push("server", server.to_string());
+        scope.push_dynamic("result", to_dynamic(result)?);
+        self.run(settings, path, &mut scope).map(drop)?;
+        *result = read_back(&scope, "result")?;
+        Ok(())
+    }
+
+    fn run(
+        &self,
+        settings: &ScriptsConfig,
+        path: &Path,
+        scope: &mut Scope,
+    ) -> Result<Dynamic, ScriptError> {
+        let ast = self.script(path)?;
+        self.max_operations.store(settings.max_operations, Ordering::Relaxed);
+        self.engine
+            .eval_ast_with_scope::<Dynamic>(scope, &ast)
+            .map_err(|e| match *thrown(e) {
+                EvalAltResult::ErrorRuntime(reason, _) => ScriptError::Rejected(
+                    reason.into_string().unwrap_or_else(|reason| reason.to_string()),
+                ),
+                e => ScriptError::Failed(format!("{}: {}", path.display(), e)),
+            })
+    }
+
+    /// The compiled script at `path`, compiled again if the file changed.
+    fn script(&self, path: &Path) -> Result<Arc<AST>, ScriptError> {
+        let mut compiled = self.compiled.lock();
+        let now = Instant::now();
+        if let Some(entry) = compiled.get(path) {
+            if now.duration_since(entry.checked) < RELOAD_CHECK {
+                return Ok(entry.ast.clone());
+            }
+        }
+
+        let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        if let Some(entry) = compiled.get_mut(path) {
+            entry.checked = now;
+            if entry.modified == modified {
+                return Ok(entry.ast.clone());
+            }
+            entry.modified = modified;
+        }
+        match self.engine.compile_file(path.to_path_buf()) {
+            Ok(ast) => {
+                let ast = Arc::new(ast);
+                let previous = compiled.insert(
+                    path.to_path_buf(),
+                    Compiled {
+                        ast: ast.clone(),
+                        modified,
+                        checked: now,
+                    },
+                );
+                if previous.is_some() {
+                    info!("Reloaded script {}", path.display());
+                }
+                Ok(ast)
+            },
+            Err(e) => match compiled.get(path) {
+                Some(entry) => {
+                    error!(
+                        "Script {} does not compile, keeping the previous version: {}",
+                        path.display(),
+                        e
+                    );
+                    Ok(entry.ast.clone())
+                },
+                None => Err(ScriptError::Failed(format!("{}: {}", path.display(), e))),
+            },
+        }
+    }
+}
+
+/// Check that the script at `path` compiles.
+pub fn check(path: &Path) -> Result<(), String> {
+    sandboxed_engine()
+        .compile_file(path.to_path_buf())
+        .map(|_| ())
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// An engine without module imports and with bounded data and nesting.
+fn sandboxed_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_module_resolver(rhai::module_resolvers::DummyModuleResolver::new())
+        .set_max_string_size(MAX_STRING_BYTES)
+        .set_max_array_size(MAX_COLLECTION_SIZE)
+        .set_max_map_size(MAX_COLLECTION_SIZE)
+        .set_max_call_levels(32)
+        .set_max_expr_depths(64, 32);
+    engine
+}
+
+fn call_scope(call: ToolCall<'_>) -> Scope<'static> {
+    let mut scope = Scope::new();
+    scope.push("tool", call.tool.to_string());
+    scope.push_dynamic(
+        "client",
+        call.client.map_or(Dynamic::UNIT, |client| client.to_string().into()),
+    );
+    scope
+}
+
+fn to_dynamic(value: &Value) -> Result<Dynamic, ScriptError> {
+    rhai::serde::to_dynamic(value).map_err(|e| ScriptError::Failed(e.to_string()))
+}
+
+/// The JSON value of a scope variable after a script ran.
+fn read_back(scope: &Scope, name: &str) -> Result<Value, ScriptError> {
+    let value = scope.get_value::<Dynamic>(name).unwrap_or(Dynamic::UNIT);
+    rhai::serde::from_dynamic(&value)
+        .map_err(|e| ScriptError::Failed(format!("`{}` is not valid JSON: {}", name, e)))
+}
+
+/// The error a script threw, looking through the functions it was thrown
+/// from.
+fn thrown(mut error: Box<EvalAltResult>) -> Box<EvalAltResult> {
+    while let EvalAltResult::ErrorInFunctionCall(_, _, inner, _) = *error {
+        error = inner;
+    }
+    error
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn settings(dir: &Path, hooks: &[(Hook, &str)]) -> ScriptsConfig {
+        let mut settings = ScriptsConfig::default();
+        for (hook, source) in hooks {
+            let path = dir.join(format!("{}.rhai", hook.name()));
+            std::fs::write(&path, source).unwrap();
+            match hook {
+                Hook::Request => settings.on_request = Some(path),
+                Hook::Route => settings.on_route = Some(path),
+                Hook::Response => settings.on_response = Some(path),
+            }
+        }
+        settings
+    }
+
+    const CALL: ToolCall<'static> = ToolCall {
+        tool: "search",
+        client: Some("ide"),
+    };
+
+    #[test]
+    fn test_hooks_change_reject_and_route_calls() {
+        let dir = tempfile::tempdir().unwrap();
+        let settings = settings(
+            dir.path(),
+            &[
+                (
+                    Hook::Request,
+                    r#"
+                    if arguments.query == "" { reject("empty query") }
+                    arguments.limit = 10;
+                    "#,
+                ),
+                (Hook::Route, r#"if client == "ide" { "replica" }"#),
+                (
+                    Hook::Response,
+                    r#"result.content[0].text = `${server}: ${tool}`;"#,
+                ),
+            ],
+        );
+        let hooks = ScriptHooks::new();
+
+        let mut arguments = json!({"query": "rust"});
+        hooks.on_request(&settings, CALL, &mut arguments).unwrap();
+        assert_eq!(arguments, json!({"query": "rust", "limit": 10}));
+        let mut empty = json!({"query": ""});
+        assert_eq!(
+            hooks.on_request(&settings, CALL, &mut empty),
+            Err(ScriptError::Rejected("empty query".to_string()))
+        );
+
+        let picked = hooks.on_route(&settings, CALL, &arguments, "primary").unwrap();
+        assert_eq!(picked.as_deref(), Some("replica"));
+        let anonymous = ToolCall {
+            client: None,
+            ..CALL
+        };
+        assert_eq!(
+            hooks.on_route(&settings, anonymous, &arguments, "primary"),
+            Ok(None)
+        );
+
+        let mut result = json!({"content": [{"type": "text", "text": "found"}]});
+        hooks.on_response(&settings, CALL, "replica", &mut result).unwrap();
+        assert_eq!(result["content"][0]["text"], "replica: search");
+    }
+
+    #[test]
+    fn test_runaway_and_broken_scripts_fail() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut settings = settings(dir.path(), &[(Hook::Request, "loop {}")]);
+        settings.max_operations = 1_000;
+        let hooks = ScriptHooks::new();
+        let result = hooks.on_request(&settings, CALL, &mut json!({}));
+        assert!(
+            matches!(result, Err(ScriptError::Failed(_))),
+            "{:?}",
+            result
+        );
+
+        // A script that stops compiling keeps its previous version
+        let path = settings.on_request.clone().unwrap();
+        std::fs::write(&path, "arguments.checked = true;").unwrap();
+        let mut arguments = json!({});
+        hooks.compiled.lock().clear();
+        hooks.on_request(&settings, CALL, &mut arguments).unwrap();
+        std::fs::write(&path, "arguments.checked = ").unwrap();
+        hooks.compiled.lock().get_mut(&path).unwrap().checked -= RELOAD_CHECK;
+        let mut arguments = json!({});
+        hooks.on_request(&settings, CALL, &mut arguments).unwrap();
+        assert_eq!(arguments, json!({"checked": true}));
+        assert!(check(&path).is_err());
+    }
+}
@@ -12,54 +12,119 @@
 //! - TLS 1.3 support with Rustls
 //! - Prometheus metrics and OpenTelemetry tracing
 
+use arc_swap::ArcSwap;
 use axum::{
-    extract::State,
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
-    routing::{get, post},
+    routing::{get, post, put},
     Json, Router,
 };
-use std::{net::SocketAddr, sync::Arc};
+use dashmap::DashMap;
+use futures::StreamExt;
+use std::{
+    sync::{Arc, OnceLock},
+    time::{Duration, Instant},
+};
 use tokio::sync::RwLock;
 use tower_http::{compression::CompressionLayer, cors::CorsLayer, trace::TraceLayer};
-use tracing::{info, warn};
+use tracing::{debug, error, info, warn};
 
 use crate::{
     batching::BatchAggregator,
     cache::ResponseCache,
-    config::{Config, TransportConfig},
+    config::{
+        Config, ConfigDiff, ConfigLoader, ListenerAuthConfig, McpServerConfig, Metadata,
+        RouteGroup, StartupPolicy, TransportConfig,
+    },
     error::{Error, Result},
+    health::checker::{
+        HealthCheckTransport, HealthChecker, HealthHistory, HealthState, HealthStateMap,
+        HealthTransition,
+    },
     metrics::Metrics,
     proxy::{
-        handler::{handle_jsonrpc_request, handle_websocket_upgrade},
+        catalog::CatalogStore,
+        coalescing::RequestCoalescer,
+        drain::{track_in_flight, DrainState},
+        handler::{self, handle_jsonrpc_request, handle_websocket_upgrade},
+        listener::{self, require_auth, ListenerAuth},
         router::ServerRegistry,
+        transform::{backend_headers, transform_request, transform_response},
+    },
+    transport::{
+        http::BackendRequestOptions,
+        resources::ResourceMonitor,
+        supervisor::{ProcessExit, RestartDecision, RestartSupervisor},
     },
-    types::{McpRequest, Tool},
+    types::{ActionResult, McpRequest, McpResponse, StartupState, StartupStatus, Tool},
 };
 
+/// How often exited STDIO processes are collected for restart.
+const STDIO_SUPERVISOR_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A configuration to apply and the channel its result is reported on.
+type ConfigUpdate = (Config, tokio::sync::oneshot::Sender<Result<ConfigDiff>>);
+
+/// Sending half of the config update channel held by [`AppState`].
+pub type ConfigUpdateSender = tokio::sync::mpsc::UnboundedSender<ConfigUpdate>;
+
 /// Main proxy server structure containing all shared state and configuration.
 #[derive(Clone)]
 pub struct ProxyServer {
-    /// Server configuration loaded from YAML/TOML
-    config: Arc<Config>,
+    /// Live server configuration, swapped atomically on hot reload
+    config: Arc<ArcSwap<Config>>,
     /// Registry of backend MCP servers
     registry: Arc<RwLock<ServerRegistry>>,
     /// LRU cache for response memoization
     cache: Arc<ResponseCache>,
     /// Metrics collector (Prometheus)
     metrics: Arc<Metrics>,
+    /// Precomputed aggregated catalogs shared with handlers
+    catalog: Arc<CatalogStore>,
+    /// Running active health check tasks, keyed by server ID
+    health_tasks: Arc<DashMap<String, tokio::task::JoinHandle<()>>>,
+    /// Running active health checkers, keyed by server ID
+    health_checkers: Arc<DashMap<String, Arc<HealthChecker>>>,
+    /// Health observed by this instance's checkers and STDIO supervisor: the
+    /// registry's map, or with clustering the local input merged into it
+    health_states: HealthStateMap,
+    /// Health transition log shared between health checkers and the registry
+    health_history: HealthHistory,
+    /// Sticky session pins, shared with handlers and the cluster
+    sessions: Arc<crate::routing::SessionTable>,
+    /// Gossip with other instances (`cluster.enabled`)
+    cluster: Option<Arc<crate::proxy::cluster::Cluster>>,
+    /// Backends found by discovery, added to every applied configuration
+    discovered: Arc<crate::discovery::DiscoveredServers>,
+    /// Sync of the configuration file from git (`gitops.enabled`)
+    gitops: Option<Arc<crate::config::gitops::GitOpsSync>>,
+    /// Restart decisions for exited STDIO processes, shared with handlers
+    restarts: Arc<RestartSupervisor>,
+    /// Resource usage and limit violations of STDIO processes
+    resources: Arc<ResourceMonitor>,
     /// Graceful shutdown handle
     shutdown_tx: tokio::sync::broadcast::Sender<()>,
     /// Server start time (for uptime calculation)
     start_time: std::time::Instant,
     /// Path to configuration file (for Admin API)
     config_path: std::path::PathBuf,
+    /// In-flight request tracking for graceful shutdown
+    drain: Arc<DrainState>,
+    /// Backend response size limit shared by the transport pools
+    response_limit: crate::transport::ResponseLimit,
+    /// Handler state, created once so the router, CLI display and shutdown
+    /// share the same transports (and STDIO child processes)
+    app_state: Arc<OnceLock<AppState>>,
+    /// Fixtures answered from instead of backends (`start --dry-run`)
+    dry_run: Option<Arc<crate::proxy::dry_run::Fixtures>>,
 }
 
 /// Shared application state passed to all handlers
 #[derive(Clone)]
 pub struct AppState {
-    pub config: Arc<Config>,
+    /// Live configuration; load it per request to observe hot reloads
+    pub config: Arc<ArcSwap<Config>>,
     pub registry: Arc<RwLock<ServerRegistry>>,
     pub cache: Arc<ResponseCache>,
     pub metrics: Arc<Metrics>,
@@ -68,7 +133,55 @@ pub struct AppState {
     pub sse_transport: Option<Arc<crate::transport::sse::SseTransportPool>>,
     pub streamable_http_transport:
         Option<Arc<crate::transport::streamable_http::StreamableHttpTransportPool>>,
+    pub grpc_transport: Option<Arc<crate::transport::grpc::GrpcTransportPool>>,
+    pub unix_transport: Option<Arc<crate::transport::unix::UnixTransportPool>>,
     pub batch_aggregator: Arc<BatchAggregator>,
+    /// Precomputed aggregated catalogs (populated when catalog refresh is enabled)
+    pub catalog: Arc<CatalogStore>,
+    /// Single-flight deduplication of identical in-flight backend requests
+    pub coalescer: Arc<RequestCoalescer<McpResponse>>,
+    /// Sticky session pins (used when `proxy.routing.sticky_sessions` is set)
+    pub sessions: Arc<crate::routing::SessionTable>,
+    /// Gossip with other instances (`cluster.enabled`)
+    pub cluster: Option<Arc<crate::proxy::cluster::Cluster>>,
+    /// Server serving each failover group (`proxy.routing.failover_groups`)
+    pub failover: Arc<crate::routing::FailoverState>,
+    /// Requests currently outstanding per backend, for least-connections routing
+    pub connections: Arc<crate::routing::ConnectionTracker>,
+    /// Concurrency limits and queues (`proxy.admission`)
+    pub admission: Arc<crate::proxy::admission::Admission>,
+    /// Cost of tool calls per backend and tool (`servers[].cost`)
+    pub costs: Arc<crate::metrics::cost::CostTracker>,
+    /// Alerts firing under `observability.alerts`
+    pub alerts: Arc<crate::metrics::alerts::AlertEngine>,
+    /// Recently answered requests (`observability.request_history`)
+    pub history: Arc<crate::proxy::history::RequestHistory>,
+    /// Request/response capture (`observability.recording`)
+    pub recorder: Arc<crate::proxy::recording::Recorder>,
+    /// Fixtures answered from instead of backends (`start --dry-run`)
+    pub dry_run: Option<Arc<crate::proxy::dry_run::Fixtures>>,
+    /// Running active health checkers, keyed by server ID
+    pub health_checkers: Arc<DashMap<String, Arc<HealthChecker>>>,
+    /// Progress of eager and preinitialized STDIO servers, keyed by server ID
+    pub startup: Arc<DashMap<String, crate::types::StartupStatus>>,
+    /// Restart history and crash loops of STDIO servers
+    pub restarts: Arc<RestartSupervisor>,
+    /// Resource usage and limit violations of STDIO processes
+    pub resources: Arc<ResourceMonitor>,
+    /// Applies an edited configuration to the running server (see
+    /// `ProxyServer::update_config`)
+    pub config_updates: ConfigUpdateSender,
+    /// Backends found at runtime, added to each applied configuration
+    pub discovered: Arc<crate::discovery::DiscoveredServers>,
+    /// Sync of the configuration file from git (`gitops.enabled`)
+    pub gitops: Option<Arc<crate::config::gitops::GitOpsSync>>,
+    /// Compiled request policy scripts (`proxy.scripts`)
+    pub scripts: Arc<crate::proxy::scripts::ScriptHooks>,
+    /// Compiled tool input and output schemas (`proxy.argument_validation`,
+    /// `proxy.sanitization.validate_output`)
+    pub tool_schemas: Arc<crate::proxy::arguments::ToolSchemas>,
+    /// Compiled redaction patterns (`proxy.redaction`)
+    pub redaction: Arc<crate::proxy::redact::Redaction>,
     pub start_time: std::time::Instant,
     pub config_path: std::path::PathBuf,
 }
@@ -87,100 +200,162 @@ impl ProxyServer {
     /// * `Err(Error)` - Configuration or initialization error
     pub async fn new(config: Config, config_path: std::path::PathBuf) -> Result<Self> {
         info!("Initializing Only1MCP proxy server");
+        record_applied(&config_path, &config);
 
         // Initialize shared application state
-        let registry = Arc::new(RwLock::new(ServerRegistry::from_config(&config).await?));
+        let registry = ServerRegistry::from_config(&config).await?;
+        let mut health_states = registry.health_states();
+        let health_history = registry.health_history();
+        let registry = Arc::new(RwLock::new(registry));
 
-        let cache = Arc::new(ResponseCache::new(crate::cache::CacheConfig::default()));
+        let cache = Arc::new(ResponseCache::new(crate::cache::CacheConfig::from(
+            &config.context_optimization.cache,
+        )));
 
         let metrics = Arc::new(Metrics::new());
 
         let (shutdown_tx, _) = tokio::sync::broadcast::channel(1);
 
+        let response_limit =
+            crate::transport::ResponseLimit::new(config.proxy.limits.max_response_bytes);
+
+        let config = Arc::new(ArcSwap::from_pointee(config));
+
+        // In a cluster, this instance's observations are merged with its
+        // peers' before routing sees them
+        let sessions = Arc::new(crate::routing::SessionTable::new());
+        let cluster = config.load().cluster.enabled.then(|| {
+            Arc::new(crate::proxy::cluster::Cluster::new(
+                config.clone(),
+                health_states.clone(),
+                health_history.clone(),
+                sessions.clone(),
+            ))
+        });
+        if let Some(cluster) = &cluster {
+            health_states = cluster.observed_health();
+        }
+        let gitops = config.load().gitops.enabled.then(|| {
+            Arc::new(crate::config::gitops::GitOpsSync::new(
+                config.load().gitops.clone(),
+                config_path.clone(),
+            ))
+        });
+
         Ok(Self {
-            config: Arc::new(config),
+            config,
             registry,
             cache,
             metrics,
+            catalog: Arc::new(CatalogStore::new()),
+            health_tasks: Arc::new(DashMap::new()),
+            health_checkers: Arc::new(DashMap::new()),
+            health_states,
+            health_history,
+            sessions,
+            cluster,
+            discovered: Arc::new(crate::discovery::DiscoveredServers::new()),
+            gitops,
+            restarts: Arc::new(RestartSupervisor::new()),
+            resources: Arc::new(ResourceMonitor::new()),
             shutdown_tx,
             start_time: std::time::Instant::now(),
             config_path,
+            drain: Arc::new(DrainState::new()),
+            response_limit,
+            app_state: Arc::new(OnceLock::new()),
+            dry_run: None,
         })
     }
 
+    /// Answer every request from `fixtures` instead of the backends
+    /// (`start --dry-run`). Must be called before the server is run.
+    pub fn with_dry_run(mut self, fixtures: crate::proxy::dry_run::Fixtures) -> Self {
+        self.dry_run = Some(Arc::new(fixtures));
+        self
+    }
+
+    /// Snapshot of the configuration currently in effect.
+    pub fn config(&self) -> Arc<Config> {
+        self.config.load_full()
+    }
+
     /// Build the Axum router with all routes and middleware (public for CLI).
     pub fn build_router_public(&self) -> Router {
         self.build_router()
     }
 
-    /// Build the Axum router with all routes and middleware.
-    fn build_router(&self) -> Router {
+    /// Shared handler state, created on first use.
+    fn state(&self) -> AppState {
+        self.app_state.get_or_init(|| self.create_app_state()).clone()
+    }
+
+    /// Create transports, the batch aggregator and the handler state.
+    fn create_app_state(&self) -> AppState {
         // Initialize HTTP transport pool manager
         // Note: We use a shared pool that can handle connections to multiple backends
-        let http_transport = Some(Arc::new(crate::transport::http::HttpTransportPool::new()));
-
-        // Initialize STDIO transport if any STDIO servers are configured
-        let stdio_transport = if self
-            .config
-            .servers
-            .iter()
-            .any(|s| matches!(s.transport, crate::config::TransportConfig::Stdio { .. }))
-        {
-            Some(Arc::new(crate::transport::stdio::StdioTransport::new()))
-        } else {
-            None
-        };
-
-        // Initialize SSE transport if any SSE servers are configured
-        let sse_transport = if self
-            .config
-            .servers
-            .iter()
-            .any(|s| matches!(s.transport, crate::config::TransportConfig::Sse { .. }))
-        {
-            let sse_config = crate::transport::sse::SseTransportConfig::default();
-            Some(Arc::new(crate::transport::sse::SseTransportPool::new(
-                sse_config,
-            )))
-        } else {
-            None
-        };
-
-        // Initialize Streamable HTTP transport if any streamable_http servers are configured
-        let streamable_http_transport = if self.config.servers.iter().any(|s| {
-            matches!(
-                s.transport,
-                crate::config::TransportConfig::StreamableHttp { .. }
-            )
-        }) {
-            Some(Arc::new(
-                crate::transport::streamable_http::StreamableHttpTransportPool::new(),
-            ))
-        } else {
-            None
-        };
+        let pool_config = self.config.load().proxy.connection_pool.clone();
+        let http_transport = Some(Arc::new(
+            crate::transport::http::HttpTransportPool::new()
+                .with_response_limit(self.response_limit.clone())
+                .with_pool_config(&pool_config),
+        ));
+
+        // Every transport is created up front (they are lazy and cheap) so
+        // backends added by a config reload can be reached without a restart
+        let stdio_transport = Some(Arc::new(crate::transport::stdio::StdioTransport::new()));
+        let sse_transport = Some(Arc::new(crate::transport::sse::SseTransportPool::new(
+            crate::transport::sse::SseTransportConfig {
+                max_response_bytes: self.response_limit.clone(),
+                ..Default::default()
+            },
+        )));
+        let streamable_http_transport = Some(Arc::new(
+            crate::transport::streamable_http::StreamableHttpTransportPool::new()
+                .with_response_limit(self.response_limit.clone())
+                .with_pool_config(&pool_config),
+        ));
+        let grpc_transport = Some(Arc::new(
+            crate::transport::grpc::GrpcTransportPool::new()
+                .with_response_limit(self.response_limit.clone())
+                .with_pool_config(&pool_config),
+        ));
+        let unix_transport = Some(Arc::new(
+            crate::transport::unix::UnixTransportPool::new()
+                .with_response_limit(self.response_limit.clone()),
+        ));
 
         // Initialize BatchAggregator with backend caller
-        let batch_config = self.config.context_optimization.batching.clone();
+        let config = self.config.load_full();
+        let batch_config = config.context_optimization.batching.clone();
         let batch_aggregator = {
             let http_transport_clone = http_transport.clone();
             let stdio_transport_clone = stdio_transport.clone();
             let sse_transport_clone = sse_transport.clone();
             let streamable_http_transport_clone = streamable_http_transport.clone();
+            let grpc_transport_clone = grpc_transport.clone();
+            let unix_transport_clone = unix_transport.clone();
             let config_clone = self.config.clone();
 
             Arc::new(BatchAggregator::new(batch_config).with_backend_caller(
                 move |server_id: String, request: McpRequest| {
-                    // Find server config
-                    let server_config = config_clone
+                    // Find server config in the live configuration
+                    let config = config_clone.load_full();
+                    let server_config = config
                         .servers
                         .iter()
                         .find(|s| s.id == server_id)
                         .ok_or_else(|| Error::ServerNotFound(server_id.clone()))?;
 
+                    let timeout = server_config.request_timeout();
+                    let transforms = &server_config.transforms;
+                    let method = request.method.clone();
+                    let request = transform_request(transforms, request);
+                    let proxy = config.outbound_proxy(&server_config.transport);
+
                     // Send via appropriate transport (synchronous wrapper around async)
                     let response = match &server_config.transport {
-                        crate::config::TransportConfig::Http { url, headers } => {
+                        crate::config::TransportConfig::Http { url, headers, .. } => {
                             // Nesting required for: transport extraction → error handling
                             #[allow(clippy::excessive_nesting)]
                             let http_transport =
@@ -194,17 +369,26 @@ impl ProxyServer {
                             tokio::task::block_in_place(|| {
                                 tokio::runtime::Handle::current().block_on(async {
                                     http_transport
-                                        .send_request_with_headers(
+                                        .send_request_with_auth(
                                             url,
                                             request.clone(),
-                                            headers.clone(),
+                                            crate::proxy::front_door::instance_headers(
+                                                &config.proxy.routing,
+                                                &server_id,
+                                                backend_headers(transforms, headers),
+                                            ),
+                                            BackendRequestOptions::for_server(
+                                                server_config,
+                                                &config,
+                                            ),
                                         )
                                         .await
-                                        .map_err(|e| Error::Transport(e.to_string()))
+                                        .map_err(Error::from)
                                 })
                             })?
                         },
-                        crate::config::TransportConfig::Stdio { command, args, env } => {
+                        crate::config::TransportConfig::Stdio { .. }
+                        | crate::config::TransportConfig::Docker { .. } => {
                             // Nesting required for: transport extraction → error handling
                             #[allow(clippy::excessive_nesting)]
                             let stdio_transport =
@@ -212,16 +396,11 @@ impl ProxyServer {
                                     Error::Transport("STDIO transport not initialized".into())
                                 })?;
 
-                            let stdio_config = crate::transport::stdio::StdioConfig {
-                                command: command.clone(),
-                                args: args.clone(),
-                                env: env.clone(),
-                                cwd: None,
-                                timeout_ms: 30000,
-                                max_memory_mb: Some(512),
-                                max_cpu_percent: Some(50),
-                                sandbox: true,
-                            };
+                            let stdio_config = crate::transport::stdio::StdioConfig::for_server(
+                                server_config,
+                                &config,
+                            )
+                            .ok_or_else(|| Error::Transport("Not a process backend".into()))?;
 
                             // Nesting required for: block_in_place → block_on async runtime bridge
                             #[allow(clippy::excessive_nesting)]
@@ -238,7 +417,7 @@ impl ProxyServer {
                                 })
                             })?
                         },
-                        crate::config::TransportConfig::Sse { url, headers } => {
+                        crate::config::TransportConfig::Sse { url, headers, .. } => {
                             // Nesting required for: transport extraction → error handling
                             #[allow(clippy::excessive_nesting)]
                             let sse_transport = sse_transport_clone.as_ref().ok_or_else(|| {
@@ -250,20 +429,25 @@ impl ProxyServer {
                             tokio::task::block_in_place(|| {
                                 tokio::runtime::Handle::current().block_on(async {
                                     sse_transport
-                                        .send_request_with_headers(
+                                        .send_request_with_options(
                                             url,
                                             request.clone(),
-                                            headers.clone(),
+                                            backend_headers(transforms, headers),
+                                            Some(timeout),
+                                            proxy,
                                         )
                                         .await
-                                        .map_err(|e| Error::Transport(e.to_string()))
+                                        .map_err(Error::from)
                                 })
                             })?
                         },
                         crate::config::TransportConfig::StreamableHttp {
                             url,
                             headers,
-                            timeout_ms,
+                            oauth,
+                            tls,
+                            signing,
+                            ..
                         } => {
                             // Nesting required for: transport extraction → error handling
                             #[allow(clippy::excessive_nesting)]
@@ -278,8 +462,12 @@ impl ProxyServer {
                             let transport_config =
                                 crate::transport::streamable_http::StreamableHttpConfig {
                                     url: url.clone(),
-                                    headers: headers.clone(),
-                                    timeout_ms: *timeout_ms,
+                                    headers: backend_headers(transforms, headers),
+                                    timeout_ms: server_config.request_timeout_ms(),
+                                    oauth: oauth.clone(),
+                                    tls: tls.clone(),
+                                    signing: signing.clone(),
+                                    proxy: proxy.cloned(),
                                 };
 
                             // Get or create transport (maintains session)
@@ -293,19 +481,66 @@ impl ProxyServer {
                                     transport
                                         .send_request(request.clone())
                                         .await
+                                        .map_err(Error::from)
+                                })
+                            })?
+                        },
+                        crate::config::TransportConfig::Grpc {
+                            endpoint,
+                            metadata,
+                            tls,
+                        } => {
+                            // Nesting required for: transport extraction → error handling
+                            #[allow(clippy::excessive_nesting)]
+                            let grpc_transport =
+                                grpc_transport_clone.as_ref().ok_or_else(|| {
+                                    Error::Transport("gRPC transport not initialized".into())
+                                })?;
+
+                            // Nesting required for: block_in_place → block_on async runtime bridge
+                            #[allow(clippy::excessive_nesting)]
+                            tokio::task::block_in_place(|| {
+                                tokio::runtime::Handle::current().block_on(async {
+                                    grpc_transport
+                                        .send_request(
+                                            endpoint,
+                                            request.clone(),
+                                            metadata,
+                                            Some(timeout),
+                                            tls.as_ref(),
+                                        )
+                                        .await
+                                        .map_err(Error::from)
+                                })
+                            })?
+                        },
+                        crate::config::TransportConfig::Unix { path } => {
+                            // Nesting required for: transport extraction → error handling
+                            #[allow(clippy::excessive_nesting)]
+                            let unix_transport =
+                                unix_transport_clone.as_ref().ok_or_else(|| {
+                                    Error::Transport("Unix socket transport not initialized".into())
+                                })?;
+
+                            // Nesting required for: block_in_place → block_on async runtime bridge
+                            #[allow(clippy::excessive_nesting)]
+                            tokio::task::block_in_place(|| {
+                                tokio::runtime::Handle::current().block_on(async {
+                                    unix_transport
+                                        .send_request(path, request.clone(), timeout)
+                                        .await
                                         .map_err(|e| Error::Transport(e.to_string()))
                                 })
                             })?
                         },
                     };
 
-                    Ok(response)
+                    Ok(transform_response(transforms, &method, response))
                 },
             ))
         };
 
-        // Create shared application state
-        let app_state = AppState {
+        AppState {
             config: self.config.clone(),
             registry: self.registry.clone(),
             cache: self.cache.clone(),
@@ -314,37 +549,221 @@ impl ProxyServer {
             stdio_transport,
             sse_transport,
             streamable_http_transport,
+            grpc_transport,
+            unix_transport,
             batch_aggregator,
+            catalog: self.catalog.clone(),
+            coalescer: Arc::new(RequestCoalescer::new()),
+            sessions: self.sessions.clone(),
+            cluster: self.cluster.clone(),
+            failover: Arc::new(crate::routing::FailoverState::new()),
+            connections: Arc::new(crate::routing::ConnectionTracker::new()),
+            admission: Arc::new(crate::proxy::admission::Admission::new()),
+            costs: Arc::new(crate::metrics::cost::CostTracker::new()),
+            alerts: Arc::new(crate::metrics::alerts::AlertEngine::new()),
+            history: Arc::new(crate::proxy::history::RequestHistory::new()),
+            recorder: Arc::new(crate::proxy::recording::Recorder::new()),
+            dry_run: self.dry_run.clone(),
+            health_checkers: self.health_checkers.clone(),
+            startup: Arc::new(DashMap::new()),
+            restarts: self.restarts.clone(),
+            resources: self.resources.clone(),
+            config_updates: self.spawn_config_updater(),
+            discovered: self.discovered.clone(),
+            gitops: self.gitops.clone(),
+            scripts: Arc::new(crate::proxy::scripts::ScriptHooks::new()),
+            tool_schemas: Arc::new(crate::proxy::arguments::ToolSchemas::new()),
+            redaction: Arc::new(crate::proxy::redact::Redaction::new()),
             start_time: self.start_time,
             config_path: self.config_path.clone(),
-        };
+        }
+    }
+
+    /// Build the Axum router with all routes and middleware.
+    fn build_router(&self) -> Router {
+        self.start_background_tasks(&self.config.load());
+        self.routes(&RouteGroup::ALL, None)
+    }
+
+    /// Start the processes of STDIO servers that are not started lazily:
+    /// eager ones before returning, preinitialized ones in the background.
+    /// HTTP, Streamable HTTP, gRPC and Unix socket backends are connected to
+    /// in the background when `proxy.connection_pool.prewarm` is set.
+    async fn start_backends(&self, config: &Config) {
+        let state = self.state();
+        let mut eager = tokio::task::JoinSet::new();
+
+        for server in config.servers.iter().filter(|s| s.enabled) {
+            let (state, id) = (state.clone(), server.id.clone());
+            if matches!(
+                server.transport,
+                TransportConfig::Http { .. }
+                    | TransportConfig::StreamableHttp { .. }
+                    | TransportConfig::Grpc { .. }
+                    | TransportConfig::Unix { .. }
+            ) && config.proxy.connection_pool.prewarm
+            {
+                tokio::spawn(async move { handler::prewarm_server(&state, &id).await });
+                continue;
+            }
+            if !server.transport.is_process() {
+                continue;
+            }
+            match server.startup {
+                StartupPolicy::Lazy => {},
+                StartupPolicy::Eager => {
+                    eager.spawn(async move { handler::start_stdio_server(&state, &id).await });
+                },
+                StartupPolicy::OnDemandWithPreinit => {
+                    tokio::spawn(async move { handler::start_stdio_server(&state, &id).await });
+                },
+            }
+        }
+
+        if !eager.is_empty() {
+            info!("Starting {} eager STDIO server(s)", eager.len());
+        }
+        while eager.join_next().await.is_some() {}
+    }
+
+    /// Start health checks, the catalog refresher, the STDIO reaper, the
+    /// sticky session purger and the connection pool reporter.
+    fn start_background_tasks(&self, config: &Config) {
+        let app_state = self.state();
+
+        // Start active health checks so routing reflects real backend status
+        self.start_health_checkers(config);
+
+        // Keep aggregated catalogs warm in the background
+        let catalog_config = &config.context_optimization.catalog;
+        if catalog_config.refresher_enabled() {
+            crate::proxy::catalog::spawn_refresher(
+                app_state.clone(),
+                std::time::Duration::from_secs(catalog_config.refresh_interval_seconds),
+                self.shutdown_tx.subscribe(),
+            );
+        }
+
+        // Stop STDIO processes that sit idle for too long, and restart the
+        // ones that exit on their own
+        if let Some(stdio) = app_state.stdio_transport.clone() {
+            self.start_stdio_reaper(stdio.clone());
+            self.start_stdio_supervisor(app_state.clone(), stdio);
+        }
+
+        // Forget sticky sessions that outlive their TTL
+        self.start_session_purger(app_state.sessions.clone());
+
+        // Share health and sticky sessions with the other instances
+        if let Some(cluster) = app_state.cluster.clone() {
+            tokio::spawn(cluster.run(self.shutdown_tx.subscribe()));
+        }
+
+        // Register and deregister labelled containers as they come and go
+        if config.discovery.docker.enabled {
+            self.start_docker_discovery(config.discovery.docker.clone());
+        }
+
+        // Follow the configuration file in git
+        if let Some(gitops) = self.gitops.clone() {
+            self.start_gitops_sync(gitops);
+        }
+
+        // Publish HTTP connection pool sizes
+        self.start_pool_reporter(app_state.clone());
 
-        // Build main MCP protocol routes
-        let mcp_routes = Router::new()
-            // Core MCP endpoints (JSON-RPC 2.0 over HTTP)
-            .route("/", post(handle_jsonrpc_request))
-            .route("/mcp", post(handle_jsonrpc_request))
-
-            // WebSocket for streaming
-            .route("/ws", get(handle_websocket_upgrade))
-
-            // Health check
-            .route("/health", get(health_check_handler));
-
-        // Management API routes
-        let admin_routes = Router::new()
-            .route("/health", get(admin_health))
-            .route("/metrics", get(crate::metrics::metrics_handler))
-            .route("/servers", get(admin_get_servers))
-            .route("/tools", get(admin_get_tools))
-            .route("/system", get(admin_system_info));
-
-        // Combine routes with middleware stack
-        Router::new()
-            .nest("/", mcp_routes)
-            .nest("/api/v1/admin", admin_routes)
-            .with_state(app_state)
-            // Apply middleware in reverse order (innermost first)
+        // Publish CPU, memory and descriptor usage of the proxy and its
+        // STDIO backends
+        self.start_resource_reporter(app_state.clone());
+
+        // Evaluate alert rules against the proxy's own metrics
+        self.start_alert_evaluator(app_state);
+    }
+
+    /// Router serving the given route groups, requiring one of the
+    /// configured API keys when `auth` is set.
+    fn routes(&self, groups: &[RouteGroup], auth: Option<&ListenerAuthConfig>) -> Router {
+        let mut router = Router::new();
+
+        if groups.contains(&RouteGroup::Mcp) {
+            // Build main MCP protocol routes
+            let mcp_routes = Router::new()
+                // Core MCP endpoints (JSON-RPC 2.0 over HTTP)
+                .route("/", post(handle_jsonrpc_request))
+                .route("/mcp", post(handle_jsonrpc_request))
+
+                // WebSocket for streaming
+                .route("/ws", get(handle_websocket_upgrade))
+
+                // Refuse new MCP requests once shutdown starts and track the
+                // in-flight ones so shutdown can wait for them
+                .route_layer(axum::middleware::from_fn_with_state(
+                    self.drain.clone(),
+                    track_in_flight,
+                ))
+
+                // Health checks, including Kubernetes-style probes
+                .route("/health", get(health_check_handler))
+                .route("/health/live", get(health_live_handler))
+                .route("/health/ready", get({
+                    let drain = self.drain.clone();
+                    move |state: State<AppState>| health_ready_handler(state, drain)
+                }))
+                .route("/health/detail", get({
+                    let drain = self.drain.clone();
+                    move |state: State<AppState>| health_detail_handler(state, drain)
+                }));
+            router = router.merge(mcp_routes);
+        }
+
+        if groups.contains(&RouteGroup::Admin) {
+            // Management API routes
+            let admin_routes = Router::new()
+                .route("/health", get(admin_health))
+                .route("/metrics", get(crate::metrics::metrics_handler))
+                .route("/servers", get(admin_get_servers))
+                .route("/servers/:id/metadata", put(admin_set_server_metadata))
+                .route("/servers/:id/enable", post(admin_enable_server))
+                .route("/servers/:id/disable", post(admin_disable_server))
+                .route("/servers/:id/health-check", post(admin_check_server))
+                .route("/servers/:id/restart", post(admin_restart_server))
+                .route("/servers/:id/refresh", post(admin_refresh_server))
+                .route("/servers/:id/logs", get(admin_get_server_logs))
+                .route(
+                    "/servers/:id/tools/:tool/metadata",
+                    put(admin_set_tool_metadata),
+                )
+                .route("/tools", get(admin_get_tools))
+                .route("/costs", get(admin_get_costs))
+                .route("/alerts", get(admin_get_alerts))
+                .route("/requests", get(admin_get_requests))
+                .route("/cache/clear", post(admin_clear_cache))
+                .route("/logs/rotate", post(admin_rotate_logs))
+                .route("/config/history", get(admin_config_history))
+                .route("/config/rollback", post(admin_config_rollback))
+                .route("/config/validate", post(admin_validate_config))
+                .route("/gitops/sync", post(admin_gitops_sync))
+                .route("/cluster", get(admin_cluster_status))
+                .route("/cluster/gossip", post(admin_cluster_gossip))
+                .route("/state", get(admin_export_state))
+                .route("/system", get(admin_system_info));
+            router = router.nest("/api/v1/admin", admin_routes);
+        }
+
+        if groups.contains(&RouteGroup::Metrics) {
+            router = router.route("/metrics", get(crate::metrics::metrics_handler));
+        }
+
+        let mut router = router.with_state(self.state());
+        if let Some(auth) = auth {
+            router = router.layer(axum::middleware::from_fn_with_state(
+                Arc::new(ListenerAuth::new(auth)),
+                require_auth,
+            ));
+        }
+
+        // Apply middleware in reverse order (innermost first)
+        router
             .layer(TraceLayer::new_for_http())
             // Note: TimeoutLayer commented out due to type incompatibility with Axum 0.7
             // Individual handlers should implement their own timeouts
@@ -353,277 +772,632 @@ impl ProxyServer {
             .layer(CorsLayer::permissive())
     }
 
-    /// Start the proxy server and begin accepting connections.
+    /// Start the proxy server and begin accepting connections on every
+    /// configured listener.
     pub async fn run(self) -> Result<()> {
-        let router = self.build_router();
-
-        // Bind to configured address
-        let addr = format!("{}:{}", self.config.server.host, self.config.server.port)
-            .parse::<SocketAddr>()
-            .map_err(|e| Error::Config(format!("Invalid address: {}", e)))?;
+        let config = self.config.load_full();
+
+        // Bind every listener before serving any, so a bad address fails
+        // startup instead of leaving a partial set of sockets open
+        let mut bound = Vec::new();
+        for listener in config.server.effective_listeners() {
+            let socket = listener::bind(&listener).await?;
+            info!(
+                "Listener {} on {}{} serving {:?}",
+                socket.name,
+                socket.local_address(),
+                if socket.is_tls() { " (TLS)" } else { "" },
+                listener.routes
+            );
+            bound.push((socket, listener));
+        }
 
-        info!("Starting Only1MCP proxy server on {}", addr);
+        self.start_backends(&config).await;
+        self.start_background_tasks(&config);
 
-        // Create TCP listener
-        let listener = tokio::net::TcpListener::bind(addr)
-            .await
-            .map_err(|e| Error::Server(format!("Failed to bind: {}", e)))?;
+        // Stop accepting on shutdown, then give in-flight requests up to the
+        // drain timeout before giving up on them
+        let mut deadline_rx = self.shutdown_tx.subscribe();
+        let drain_timeout = Duration::from_secs(config.server.drain_timeout_seconds);
+        #[cfg(unix)]
+        let upgrade_timeout = Duration::from_secs(config.server.upgrade_timeout_seconds);
+        drop(config);
 
-        info!("Server listening on {}", addr);
+        // On SIGUSR2, hand the sockets to a new process and drain this one
+        #[cfg(unix)]
+        {
+            let sockets = bound.iter().map(|(socket, _)| (socket.name.clone(), socket.raw_fd()));
+            let watch = crate::daemon::upgrade::watch(sockets.collect(), upgrade_timeout);
+            let mut shutdown_rx = self.shutdown_tx.subscribe();
+            let server = self.clone();
+            tokio::spawn(async move {
+                tokio::select! {
+                    _ = watch => server.shutdown(),
+                    _ = shutdown_rx.recv() => {},
+                }
+            });
+        }
 
-        // Run server with graceful shutdown
-        let mut shutdown_rx = self.shutdown_tx.subscribe();
-        axum::serve(listener, router)
-            .with_graceful_shutdown(async move {
+        let mut serving = tokio::task::JoinSet::new();
+        for (socket, listener) in bound {
+            let router = self.routes(&listener.routes, listener.auth.as_ref());
+            let mut shutdown_rx = self.shutdown_tx.subscribe();
+            serving.spawn(socket.serve(router, async move {
                 let _ = shutdown_rx.recv().await;
-                info!("Shutting down proxy server gracefully...");
-            })
-            .await
-            .map_err(|e| Error::Server(format!("Server error: {}", e)))?;
+            }));
+        }
+        #[cfg(unix)]
+        crate::daemon::upgrade::notify_ready();
+
+        let mut drain_rx = self.shutdown_tx.subscribe();
+        let server = self.clone();
+        tokio::spawn(async move {
+            let _ = drain_rx.recv().await;
+            info!("Shutting down proxy server gracefully...");
+            server.begin_drain();
+        });
+
+        let served = async {
+            let mut result = Ok(());
+            while let Some(joined) = serving.join_next().await {
+                let outcome = joined
+                    .map_err(|e| Error::Server(format!("Listener task failed: {}", e)))
+                    .and_then(|served| served);
+                // One listener failing takes the others down with it
+                if let (Err(e), true) = (outcome, result.is_ok()) {
+                    error!("{}", e);
+                    self.shutdown();
+                    result = Err(e);
+                }
+            }
+            result
+        };
+        let deadline = async move {
+            let _ = deadline_rx.recv().await;
+            tokio::time::sleep(drain_timeout).await;
+        };
+
+        let result = tokio::select! {
+            result = served => result,
+            _ = deadline => {
+                warn!(
+                    "Drain timeout of {:?} elapsed with {} request(s) in flight; forcing shutdown",
+                    drain_timeout,
+                    self.drain.in_flight()
+                );
+                Ok(())
+            },
+        };
 
+        self.stop_health_checkers();
+        if let Some(stdio) = self.state().stdio_transport {
+            if let Err(e) = stdio.kill_all().await {
+                warn!("Failed to stop STDIO backends: {}", e);
+            }
+        }
         info!("Proxy server stopped");
-        Ok(())
+        result
     }
 
-    /// Trigger graceful shutdown
-    pub fn shutdown(&self) {
-        let _ = self.shutdown_tx.send(());
+    /// Refuse new requests and flush batches waiting on their window, so
+    /// requests already queued complete instead of waiting out the timer.
+    fn begin_drain(&self) {
+        self.drain.begin();
+
+        let flushed = self.state().batch_aggregator.flush_all();
+        if flushed > 0 {
+            info!("Flushed {} pending batch(es)", flushed);
+        }
+        info!("Draining {} in-flight request(s)", self.drain.in_flight());
     }
 
-    /// Run server with configuration hot-reload support
-    ///
-    /// This method creates a ConfigLoader that watches the configuration file
-    /// for changes and automatically applies them without server restart.
-    ///
-    /// # Arguments
-    ///
-    /// * `config_path` - Path to the configuration file to watch
-    ///
-    /// # Example
+    /// Spawn active health checkers for all enabled backends, replacing any
+    /// that are already running.
     ///
-    /// ```no_run
-    /// use std::path::PathBuf;
-    /// use only1mcp::proxy::server::ProxyServer;
+    /// Check results are published into the registry's health state map, so
+    /// `get_healthy_servers()` and routing only consider live backends.
+    fn start_health_checkers(&self, config: &Config) {
+        self.stop_health_checkers();
+
+        for server in config.servers.iter().filter(|s| s.enabled && s.health_check.enabled) {
+            let checker = match HealthChecker::from_config(
+                server.id.clone(),
+                HealthCheckTransport::from_transport_config(
+                    &server.transport,
+                    config.outbound_proxy(&server.transport),
+                ),
+                server.health_check.clone(),
+            ) {
+                Ok(checker) => checker,
+                Err(e) => {
+                    error!("Failed to start health checker for {}: {}", server.id, e);
+                    continue;
+                },
+            };
+            let checker = checker
+                .with_state_sink(self.health_states.clone())
+                .with_history(self.health_history.clone())
+                .with_restart_supervisor(self.restarts.clone())
+                .with_transport_label(get_transport_name(&server.transport))
+                .with_faults(self.config.clone());
+
+            debug!("Starting health checker for {}", server.id);
+            let checker = Arc::new(checker);
+            let handle = tokio::spawn(checker.clone().start());
+            self.health_tasks.insert(server.id.clone(), handle);
+            self.health_checkers.insert(server.id.clone(), checker);
+        }
+    }
+
+    /// Periodically stop STDIO processes idle for longer than
+    /// `proxy.connection_pool.stdio_idle_timeout_seconds`.
     ///
-    /// #[tokio::main]
-    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    ///     ProxyServer::run_with_hot_reload(PathBuf::from("config.yaml")).await?;
-    ///     Ok(())
-    /// }
-    /// ```
-    pub async fn run_with_hot_reload(config_path: std::path::PathBuf) -> Result<()> {
-        use crate::config::ConfigLoader;
+    /// The timeout is re-read on every pass, so reloads apply without a
+    /// restart and 0 pauses reaping.
+    fn start_stdio_reaper(&self, stdio: Arc<crate::transport::stdio::StdioTransport>) {
+        let config = self.config.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+        let idle_timeout = move || {
+            Duration::from_secs(config.load().proxy.connection_pool.stdio_idle_timeout_seconds)
+        };
 
-        // Create config loader with hot-reload
-        info!(
-            "Enabling configuration hot-reload for: {}",
-            config_path.display()
-        );
-        let config_path_clone = config_path.clone();
-        let loader = ConfigLoader::new(config_path)?.watch()?;
+        tokio::spawn(async move {
+            loop {
+                // Check several times per timeout so processes stop close to it
+                let interval = match idle_timeout() {
+                    timeout if timeout.is_zero() => Duration::from_secs(30),
+                    timeout => (timeout / 4).clamp(Duration::from_secs(1), Duration::from_secs(30)),
+                };
+                tokio::select! {
+                    _ = tokio::time::sleep(interval) => {},
+                    _ = shutdown_rx.recv() => break,
+                }
 
-        let config = loader.get_config();
-        let mut reload_rx = loader.subscribe();
+                let timeout = idle_timeout();
+                if !timeout.is_zero() {
+                    stdio.reap_idle(timeout).await;
+                }
+            }
+        });
+    }
 
-        // Create server
-        let server = Arc::new(Self::new(config.as_ref().clone(), config_path_clone).await?);
+    /// Collect STDIO processes that exited on their own and restart them
+    /// according to their server's `restart` policy.
+    ///
+    /// A server exceeding its restart limit is marked unhealthy and its
+    /// processes are not restarted until an operator restarts it.
+    fn start_stdio_supervisor(
+        &self,
+        state: AppState,
+        stdio: Arc<crate::transport::stdio::StdioTransport>,
+    ) {
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+        let health_states = self.health_states.clone();
+        let health_history = self.health_history.clone();
 
-        // Spawn reload handler
-        let server_clone = server.clone();
         tokio::spawn(async move {
-            while reload_rx.changed().await.is_ok() {
-                let new_config = reload_rx.borrow().clone();
-                info!("Configuration change detected, applying new configuration...");
-
-                // Update server with new config
-                if let Err(e) = server_clone.update_config(new_config.as_ref()).await {
-                    tracing::error!("Failed to apply new config: {}", e);
-                } else {
-                    info!("Configuration successfully updated");
+            // Restarts waiting out their backoff: (due, server ID, pool slot)
+            let mut pending: Vec<(Instant, String, usize)> = Vec::new();
+            loop {
+                tokio::select! {
+                    _ = tokio::time::sleep(STDIO_SUPERVISOR_INTERVAL) => {},
+                    _ = shutdown_rx.recv() => break,
                 }
+
+                supervise_stdio(
+                    &state,
+                    &stdio,
+                    &health_states,
+                    &health_history,
+                    &mut pending,
+                )
+                .await;
             }
         });
-
-        // Run server (we need to extract it from Arc)
-        let server_owned = Arc::try_unwrap(server).unwrap_or_else(|arc| (*arc).clone());
-        server_owned.run().await
     }
 
-    /// Display loaded servers and tools (for foreground mode)
-    pub async fn display_loaded_servers(&self) -> Result<()> {
-        println!("\nMCP Servers Loaded:");
+    /// Periodically drop sticky sessions unused for longer than
+    /// `proxy.routing.session_ttl_seconds`, re-read on every pass.
+    fn start_session_purger(&self, sessions: Arc<crate::routing::SessionTable>) {
+        let config = self.config.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
 
-        let mut total_tools = 0;
-        let mut enabled_count = 0;
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = tokio::time::sleep(Duration::from_secs(60)) => {},
+                    _ = shutdown_rx.recv() => break,
+                }
 
-        for server_config in &self.config.servers {
-            if !server_config.enabled {
-                continue;
+                let purged = sessions.purge_expired(config.load().proxy.routing.session_ttl());
+                if purged > 0 {
+                    debug!("Purged {} expired sticky sessions", purged);
+                }
             }
+        });
+    }
 
-            enabled_count += 1;
-
-            // Fetch tools from this server
-            let tools = match self.fetch_tools_for_server(&server_config.id).await {
-                Ok(tools) => tools,
-                Err(e) => {
-                    println!("  ✗ {} - Error: {}", server_config.name, e);
-                    continue;
-                },
-            };
+    /// Poll Docker for labelled containers and apply each change in the
+    /// set of their backends. While Docker cannot be reached, the backends
+    /// found last are kept.
+    fn start_docker_discovery(&self, docker: crate::config::DockerDiscoveryConfig) {
+        let server = self.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+        let mut discovery = crate::discovery::docker::DockerDiscovery::new(docker.socket.clone());
 
-            total_tools += tools.len();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(docker.poll_interval());
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            let mut reachable = true;
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {},
+                    _ = shutdown_rx.recv() => break,
+                }
 
-            let transport_type = Self::get_transport_name(&server_config.transport);
+                let poll = discovery.poll().await;
+                server.apply_discovered(poll, &mut reachable).await;
+            }
+        });
+    }
 
-            println!(
-                "  ✓ {} ({}) - {} tool{}",
-                server_config.name,
-                transport_type,
-                tools.len(),
-                if tools.len() == 1 { "" } else { "s" }
-            );
+    /// Apply a poll of Docker discovery, warning once when it starts
+    /// failing.
+    async fn apply_discovered(&self, poll: Result<Vec<McpServerConfig>>, reachable: &mut bool) {
+        let servers = match poll {
+            Ok(servers) => servers,
+            Err(e) => {
+                if std::mem::replace(reachable, false) {
+                    warn!("Docker discovery failed: {}", e);
+                }
+                return;
+            },
+        };
+        *reachable = true;
+        if !self.discovered.set(servers) {
+            return;
+        }
+        let config = self.discovered.strip(&self.config.load());
+        if let Err(e) = self.update_config(&config).await {
+            error!("Failed to apply discovered servers: {}", e);
+        }
+    }
 
-            // Display tool names (wrapped at 80 chars)
-            if !tools.is_empty() {
-                let tool_names: Vec<String> = tools.iter().map(|t| t.name.clone()).collect();
+    /// Sync the configuration from git now, then at each poll or request.
+    fn start_gitops_sync(&self, gitops: Arc<crate::config::gitops::GitOpsSync>) {
+        let server = self.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
 
-                let formatted = Self::format_tool_list(&tool_names, 4);
-                println!("{}", formatted);
+        tokio::spawn(async move {
+            // Without polling, only requests wake the loop
+            let poll = gitops.settings().poll_interval().unwrap_or(Duration::MAX);
+            loop {
+                server.sync_gitops(&gitops).await;
+                tokio::select! {
+                    _ = tokio::time::sleep(poll) => {},
+                    _ = gitops.requested() => {},
+                    _ = shutdown_rx.recv() => break,
+                }
             }
-        }
+        });
+    }
 
-        println!(
-            "\nTotal: {} tools available across {} server{}",
-            total_tools,
-            enabled_count,
-            if enabled_count == 1 { "" } else { "s" }
-        );
+    /// Fetch the GitOps branch and apply its configuration if the commit
+    /// moved.
+    async fn sync_gitops(&self, gitops: &crate::config::gitops::GitOpsSync) {
+        use crate::config::gitops::short;
 
-        Ok(())
+        let revision = match gitops.fetch().await {
+            Ok(Some(revision)) => revision,
+            Ok(None) => return,
+            Err(e) => {
+                warn!("GitOps sync failed: {}", e);
+                return;
+            },
+        };
+        let applied = match gitops.install(&revision) {
+            Ok(config) => self.apply_file_config(&config).await,
+            Err(e) => Err(e),
+        };
+        match applied {
+            Ok(diff) => {
+                gitops.applied(&revision);
+                info!(
+                    "Applied commit {} from git: {}",
+                    short(&revision.commit),
+                    diff
+                );
+            },
+            Err(e) => {
+                gitops.rejected(&revision.commit, &e);
+                error!(
+                    "Commit {} from git was not applied: {}",
+                    short(&revision.commit),
+                    e
+                );
+            },
+        }
     }
 
-    /// Log loaded servers (for daemon mode)
-    pub async fn log_loaded_servers(&self) -> Result<()> {
-        let mut total_tools = 0;
+    /// Periodically publish the connection pools of HTTP backends in
+    /// `only1mcp_connection_pool_size`.
+    fn start_pool_reporter(&self, state: AppState) {
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
 
-        for server_config in &self.config.servers {
-            if !server_config.enabled {
-                continue;
-            }
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = tokio::time::sleep(Duration::from_secs(15)) => {},
+                    _ = shutdown_rx.recv() => break,
+                }
 
-            match self.fetch_tools_for_server(&server_config.id).await {
-                Ok(tools) => {
-                    total_tools += tools.len();
-                    info!(
-                        "Loaded server '{}' ({}) with {} tools",
-                        server_config.name,
-                        Self::get_transport_name(&server_config.transport),
-                        tools.len()
-                    );
-                },
-                Err(e) => {
-                    tracing::warn!("Failed to load server '{}': {}", server_config.name, e);
-                },
+                let config = state.config.load_full();
+                for server in &config.servers {
+                    handler::report_pool_stats(&state, server);
+                }
             }
-        }
-
-        info!("Total: {} tools available", total_tools);
-        Ok(())
+        });
     }
 
-    /// Fetch tools for a specific server (for display purposes)
-    async fn fetch_tools_for_server(&self, server_id: &str) -> Result<Vec<Tool>> {
-        let server_config = self
-            .config
-            .servers
-            .iter()
-            .find(|s| s.id == server_id)
-            .ok_or_else(|| Error::Config(format!("Server not found: {}", server_id)))?;
-
-        // Build app state for transport access
-        let app_state = self.build_app_state();
-
-        // Use existing handler logic to fetch tools
-        let request = McpRequest::new(
-            "tools/list",
-            serde_json::json!({}),
-            Some(serde_json::json!(1)),
-        );
+    /// Periodically sample resource usage of the proxy, the host's cores and
+    /// every running STDIO backend process, killing processes that stay over
+    /// their server's `resources` limits.
+    fn start_resource_reporter(&self, state: AppState) {
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
 
-        // Fetch from backend (reuse existing transport logic)
-        let response = match &server_config.transport {
-            TransportConfig::Http { url, headers } => {
-                let http_transport = app_state
-                    .http_transport
-                    .as_ref()
-                    .ok_or_else(|| Error::Transport("HTTP transport not initialized".into()))?;
-                http_transport
-                    .send_request_with_headers(url, request, headers.clone())
-                    .await
-                    .map_err(|e| Error::Transport(e.to_string()))?
-            },
-            TransportConfig::Sse { url, headers } => {
-                let sse_transport = app_state
-                    .sse_transport
-                    .as_ref()
-                    .ok_or_else(|| Error::Transport("SSE transport not initialized".into()))?;
-                sse_transport
-                    .send_request_with_headers(url, request, headers.clone())
+        tokio::spawn(async move {
+            let mut collector = crate::metrics::SystemMetricsCollector::new();
+            loop {
+                let samples = collector.collect(&running_stdio_processes(&state).await);
+                enforce_resource_limits(&state, &samples).await;
+
+                tokio::select! {
+                    _ = tokio::time::sleep(Duration::from_secs(10)) => {},
+                    _ = shutdown_rx.recv() => break,
+                }
+            }
+        });
+    }
+
+    /// Periodically evaluate `observability.alerts` and notify about alerts
+    /// that start or stop firing.
+    ///
+    /// Rules and the interval are re-read on every pass, so reloads apply
+    /// without a restart.
+    fn start_alert_evaluator(&self, state: AppState) {
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+        let client = reqwest::Client::new();
+
+        tokio::spawn(async move {
+            loop {
+                let config = state.config.load_full();
+                let interval =
+                    Duration::from_secs(config.observability.alerts.interval_seconds.max(1));
+                tokio::select! {
+                    _ = tokio::time::sleep(interval) => {},
+                    _ = shutdown_rx.recv() => break,
+                }
+
+                let config = state.config.load_full();
+                let alerts = &config.observability.alerts;
+                if alerts.rules.is_empty() && state.alerts.active().is_empty() {
+                    continue;
+                }
+                let observation = observe(&state).await;
+                for event in state.alerts.evaluate(alerts, observation) {
+                    crate::metrics::alerts::notify(&client, alerts, &event).await;
+                }
+            }
+        });
+    }
+
+    /// Stop all running health checkers.
+    fn stop_health_checkers(&self) {
+        self.health_tasks.retain(|_, handle| {
+            handle.abort();
+            false
+        });
+        self.health_checkers.clear();
+    }
+
+    /// Spawn the task that applies configurations edited through the Admin
+    /// API, so handlers can reconfigure the server without owning it.
+    fn spawn_config_updater(&self) -> ConfigUpdateSender {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<ConfigUpdate>();
+        let server = self.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    update = rx.recv() => match update {
+                        Some((config, reply)) => {
+                            let _ = reply.send(server.apply_file_config(&config).await);
+                        },
+                        None => break,
+                    },
+                    _ = shutdown_rx.recv() => break,
+                }
+            }
+        });
+
+        tx
+    }
+
+    /// Trigger graceful shutdown
+    pub fn shutdown(&self) {
+        let _ = self.shutdown_tx.send(());
+    }
+
+    /// Run server with configuration hot-reload support
+    ///
+    /// This method creates a ConfigLoader that watches the configuration file
+    /// for changes and automatically applies them without server restart.
+    ///
+    /// # Arguments
+    ///
+    /// * `config_path` - Path to the configuration file to watch
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use only1mcp::proxy::server::ProxyServer;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     ProxyServer::run_with_hot_reload(PathBuf::from("config.yaml")).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn run_with_hot_reload(config_path: std::path::PathBuf) -> Result<()> {
+        let config = Config::from_file(&config_path)?;
+        let server = Self::new(config, config_path).await?;
+
+        // Keep the loader alive for the lifetime of the server
+        let _loader = server.watch_config()?;
+        server.run().await
+    }
+
+    /// Watch the configuration file and apply changes as they are saved.
+    ///
+    /// Each successful reload is handed to
+    /// [`ProxyServer::apply_file_config`].
+    /// The returned loader owns the file watcher; reloading stops once it is
+    /// dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration file cannot be loaded or watched.
+    pub fn watch_config(&self) -> Result<ConfigLoader> {
+        info!(
+            "Enabling configuration hot-reload for: {}",
+            self.config_path.display()
+        );
+        let loader = ConfigLoader::new(self.config_path.clone())?.watch()?;
+        let mut reload_rx = loader.subscribe();
+
+        let server = self.clone();
+        tokio::spawn(async move {
+            while reload_rx.changed().await.is_ok() {
+                let new_config = reload_rx.borrow_and_update().clone();
+                if let Err(e) = server.apply_file_config(&new_config).await {
+                    error!("Failed to apply reloaded configuration: {}", e);
+                }
+            }
+        });
+
+        Ok(loader)
+    }
+
+    /// Initialize every enabled server and fetch its tools, up to
+    /// `proxy.initialization.max_parallel` at once and each within its
+    /// `timeout_ms`. `report` is called for each server as it finishes;
+    /// returns the number of servers.
+    async fn load_servers(
+        &self,
+        mut report: impl FnMut(&McpServerConfig, Result<Vec<Tool>>),
+    ) -> usize {
+        let config = self.config.load_full();
+        let initialization = &config.proxy.initialization;
+        let timeout = initialization.timeout();
+
+        let mut loads = futures::stream::iter(config.servers.iter().filter(|s| s.enabled))
+            .map(|server| async move {
+                let tools = tokio::time::timeout(timeout, self.fetch_tools_for_server(&server.id))
                     .await
-                    .map_err(|e| Error::Transport(e.to_string()))?
-            },
-            TransportConfig::StreamableHttp {
-                url,
-                headers,
-                timeout_ms,
-            } => {
-                let streamable_http_transport =
-                    app_state.streamable_http_transport.as_ref().ok_or_else(|| {
-                        Error::Transport("Streamable HTTP transport not initialized".into())
-                    })?;
-
-                let transport_config = crate::transport::streamable_http::StreamableHttpConfig {
-                    url: url.clone(),
-                    headers: headers.clone(),
-                    timeout_ms: *timeout_ms,
-                };
+                    .unwrap_or_else(|_| {
+                        Err(Error::Timeout(format!(
+                            "not initialized within {:?}",
+                            timeout
+                        )))
+                    });
+                (server, tools)
+            })
+            .buffer_unordered(initialization.max_parallel.max(1));
 
-                let transport = streamable_http_transport.get_or_create(transport_config);
-                transport.send_request(request).await?
-            },
-            TransportConfig::Stdio { command, args, env } => {
-                let stdio_transport = app_state
-                    .stdio_transport
-                    .as_ref()
-                    .ok_or_else(|| Error::Transport("STDIO transport not initialized".into()))?;
-
-                let stdio_config = crate::transport::stdio::StdioConfig {
-                    command: command.clone(),
-                    args: args.clone(),
-                    env: env.clone(),
-                    cwd: None,
-                    timeout_ms: 30000,
-                    max_memory_mb: Some(512),
-                    max_cpu_percent: Some(50),
-                    sandbox: true,
+        let mut count = 0;
+        while let Some((server, tools)) = loads.next().await {
+            count += 1;
+            report(server, tools);
+        }
+        count
+    }
+
+    /// Display loaded servers and tools (for foreground mode)
+    pub async fn display_loaded_servers(&self) -> Result<()> {
+        println!("\nMCP Servers Loaded:");
+
+        let mut total_tools = 0;
+        let enabled_count = self
+            .load_servers(|server_config, tools| {
+                let tools = match tools {
+                    Ok(tools) => tools,
+                    Err(e) => {
+                        println!("  ✗ {} - Error: {}", server_config.name, e);
+                        return;
+                    },
                 };
 
-                stdio_transport
-                    .send_request_with_config(server_id.to_string(), &stdio_config, request)
-                    .await
-                    .map_err(|e| Error::Transport(e.to_string()))?
+                total_tools += tools.len();
+
+                let transport_type = Self::get_transport_name(&server_config.transport);
+
+                println!(
+                    "  ✓ {} ({}) - {} tool{}",
+                    server_config.name,
+                    transport_type,
+                    tools.len(),
+                    if tools.len() == 1 { "" } else { "s" }
+                );
+
+                // Display tool names (wrapped at 80 chars)
+                if !tools.is_empty() {
+                    let tool_names: Vec<String> = tools.iter().map(|t| t.name.clone()).collect();
+
+                    let formatted = Self::format_tool_list(&tool_names, 4);
+                    println!("{}", formatted);
+                }
+            })
+            .await;
+
+        println!(
+            "\nTotal: {} tools available across {} server{}",
+            total_tools,
+            enabled_count,
+            if enabled_count == 1 { "" } else { "s" }
+        );
+
+        Ok(())
+    }
+
+    /// Log loaded servers (for daemon mode)
+    pub async fn log_loaded_servers(&self) -> Result<()> {
+        let mut total_tools = 0;
+
+        self.load_servers(|server_config, tools| match tools {
+            Ok(tools) => {
+                total_tools += tools.len();
+                info!(
+                    "Loaded server '{}' ({}) with {} tools",
+                    server_config.name,
+                    Self::get_transport_name(&server_config.transport),
+                    tools.len()
+                );
             },
-        };
+            Err(e) => {
+                tracing::warn!("Failed to load server '{}': {}", server_config.name, e);
+            },
+        })
+        .await;
 
-        // Parse tools from response
-        let tools: Vec<Tool> = if let Some(result) = response.result {
-            serde_json::from_value(result.get("tools").cloned().unwrap_or(serde_json::json!([])))?
-        } else {
-            Vec::new()
-        };
+        info!("Total: {} tools available", total_tools);
+        Ok(())
+    }
 
-        Ok(tools)
+    /// Fetch tools for a specific server (for display purposes)
+    async fn fetch_tools_for_server(&self, server_id: &str) -> Result<Vec<Tool>> {
+        fetch_tools_for_server_internal(&self.state(), server_id).await
     }
 
     /// Get human-readable transport name
@@ -633,6 +1407,9 @@ impl ProxyServer {
             TransportConfig::Sse { .. } => "SSE",
             TransportConfig::StreamableHttp { .. } => "Streamable HTTP",
             TransportConfig::Stdio { .. } => "STDIO",
+            TransportConfig::Grpc { .. } => "gRPC",
+            TransportConfig::Unix { .. } => "Unix socket",
+            TransportConfig::Docker { .. } => "Docker",
         }
     }
 
@@ -672,96 +1449,78 @@ impl ProxyServer {
         result
     }
 
-    /// Build AppState for internal use (needed for fetch_tools_for_server)
-    fn build_app_state(&self) -> AppState {
-        // Initialize transports (same logic as build_router)
-        let http_transport = Some(Arc::new(crate::transport::http::HttpTransportPool::new()));
+    /// Apply a configuration read from the configuration file, then record
+    /// the file in the history of applied versions.
+    pub async fn apply_file_config(&self, new_config: &Config) -> Result<ConfigDiff> {
+        let diff = self.update_config(new_config).await?;
+        record_applied(&self.config_path, new_config);
+        Ok(diff)
+    }
 
-        let stdio_transport = if self
-            .config
-            .servers
-            .iter()
-            .any(|s| matches!(s.transport, TransportConfig::Stdio { .. }))
-        {
-            Some(Arc::new(crate::transport::stdio::StdioTransport::new()))
-        } else {
-            None
-        };
+    /// Apply a reloaded configuration to the running server.
+    ///
+    /// Backends, response cache settings, proxy routing and payload limits
+    /// take effect immediately. The listener address is kept as is (it may have come from
+    /// CLI flags); other sections that only apply at startup are reported in
+    /// the returned diff's `restart_required`.
+    pub async fn update_config(&self, new_config: &Config) -> Result<ConfigDiff> {
+        let current = self.config.load_full();
+
+        let mut new_config = keep_listener(&current, new_config);
+        self.discovered.merge_into(&mut new_config);
+
+        let diff = ConfigDiff::between(&current, &new_config);
+        if diff.is_empty() {
+            info!("Configuration reloaded: no changes");
+            return Ok(diff);
+        }
+        info!("Configuration reloaded: {}", diff);
+        if !diff.restart_required.is_empty() {
+            warn!(
+                "Changes to {} take effect after a restart",
+                diff.restart_required.join(", ")
+            );
+        }
 
-        let sse_transport = if self
-            .config
-            .servers
-            .iter()
-            .any(|s| matches!(s.transport, TransportConfig::Sse { .. }))
-        {
-            let sse_config = crate::transport::sse::SseTransportConfig::default();
-            Some(Arc::new(crate::transport::sse::SseTransportPool::new(
-                sse_config,
-            )))
-        } else {
-            None
-        };
+        let new_config = Arc::new(new_config);
+        self.config.store(new_config.clone());
 
-        let streamable_http_transport = if self
-            .config
-            .servers
-            .iter()
-            .any(|s| matches!(s.transport, TransportConfig::StreamableHttp { .. }))
-        {
-            Some(Arc::new(
-                crate::transport::streamable_http::StreamableHttpTransportPool::new(),
-            ))
-        } else {
-            None
-        };
+        if diff.servers_changed() {
+            let mut registry = self.registry.write().await;
+            registry.clear();
+            for server_config in new_config.routed_servers() {
+                registry.add_server(server_config.clone()).await?;
+            }
+            drop(registry);
 
-        let batch_config = self.config.context_optimization.batching.clone();
-        let batch_aggregator = Arc::new(BatchAggregator::new(batch_config));
+            info!(
+                "{} backend servers registered",
+                new_config.routed_servers().count()
+            );
 
-        AppState {
-            config: self.config.clone(),
-            registry: self.registry.clone(),
-            cache: self.cache.clone(),
-            metrics: self.metrics.clone(),
-            http_transport,
-            stdio_transport,
-            sse_transport,
-            streamable_http_transport,
-            batch_aggregator,
-            start_time: self.start_time,
-            config_path: self.config_path.clone(),
+            // Restart health checks for the new backend set
+            self.start_health_checkers(&new_config);
         }
-    }
 
-    /// Update server configuration during hot-reload
-    ///
-    /// This method is called when a configuration change is detected.
-    /// It updates the server registry with new backend servers.
-    ///
-    /// Note: Some configuration changes (like server host/port, TLS settings)
-    /// require a server restart and cannot be hot-reloaded.
-    async fn update_config(&self, new_config: &Config) -> Result<()> {
-        info!("Updating server configuration...");
-
-        // Update registry with new backends
-        let mut registry = self.registry.write().await;
+        if diff.cache_changed {
+            self.cache
+                .reconfigure(crate::cache::CacheConfig::from(
+                    &new_config.context_optimization.cache,
+                ))
+                .await;
+        } else if diff.servers_changed() {
+            // Cached list responses may still name tools of removed backends
+            self.cache.clear().await;
+        }
 
-        // Clear existing servers
-        registry.clear();
+        self.response_limit.set(new_config.proxy.limits.max_response_bytes);
 
-        // Add new servers from updated config
-        for server_config in &new_config.servers {
-            if server_config.enabled {
-                registry.add_server(server_config.clone()).await?;
-            }
+        if diff.servers_changed() {
+            // Backends changed, so the precomputed catalogs are stale
+            self.catalog.request_refresh();
         }
 
-        info!(
-            "Configuration updated: {} backend servers registered",
-            new_config.servers.iter().filter(|s| s.enabled).count()
-        );
-
-        Ok(())
+        Ok(diff)
     }
 }
 
@@ -773,17 +1532,33 @@ impl ProxyServer {
 async fn admin_get_servers(
     State(state): State<AppState>,
 ) -> std::result::Result<Json<Vec<crate::types::ServerStatus>>, (StatusCode, String)> {
-    let config = state.config.as_ref();
+    let config = state.config.load();
 
     let mut servers = Vec::new();
 
     for server_config in &config.servers {
-        // Health tracking not yet implemented - always report as unknown
-        let health = Some("Unknown".to_string());
+        let health = Some(format!(
+            "{:?}",
+            state.registry.read().await.health_state(&server_config.id)
+        ));
+
+        // Before counting tools, which starts lazy STDIO servers
+        let startup = startup_status(&state, server_config).await;
+        let restart = server_config
+            .transport
+            .is_process()
+            .then(|| state.restarts.status(&server_config.id, &server_config.restart));
 
         // Fetch tool count (best effort, don't fail if server is down)
         let tool_count = fetch_tool_count_for_server(&state, &server_config.id).await.unwrap_or(0);
 
+        let catalog_refreshed_at = state
+            .registry
+            .read()
+            .await
+            .server_catalog(&server_config.id)
+            .and_then(|catalog| catalog.last_refreshed());
+
         servers.push(crate::types::ServerStatus {
             id: server_config.id.clone(),
             name: server_config.name.clone(),
@@ -791,17 +1566,108 @@ async fn admin_get_servers(
             transport: get_transport_name(&server_config.transport).to_string(),
             tool_count,
             health,
+            catalog_refreshed_at,
+            startup,
+            restart,
+            resources: server_config
+                .transport
+                .is_process()
+                .then(|| state.resources.usage(&server_config.id))
+                .flatten(),
+            tags: server_config.tags.clone(),
+            notes: server_config.notes.clone(),
         });
     }
 
     Ok(Json(servers))
 }
 
+/// Kill STDIO processes that stayed over their server's `resources` limits.
+/// The supervisor then collects them like any crashed process.
+async fn enforce_resource_limits(
+    state: &AppState,
+    samples: &[(
+        crate::metrics::system::ChildProcess,
+        crate::metrics::system::ProcessUsage,
+    )],
+) {
+    let config = state.config.load_full();
+    let limits = |id: &str| config.servers.iter().find(|s| s.id == id).map(|s| &s.resources);
+    let breaches = state.resources.record(samples, limits, Instant::now());
+    let Some(stdio) = &state.stdio_transport else {
+        return;
+    };
+    for breach in breaches {
+        let process = &breach.process;
+        warn!(
+            "Killing STDIO process {} of {} (pid {}): {}",
+            process.slot, process.server_id, process.pid, breach.reason
+        );
+        crate::metrics::STDIO_RESOURCE_KILLS_TOTAL
+            .with_label_values(&[&process.server_id, breach.resource])
+            .inc();
+        if let Err(e) = stdio.kill_slot(&process.server_id, process.slot, process.pid).await {
+            warn!(
+                "Failed to kill STDIO process {} of {}: {}",
+                process.slot, process.server_id, e
+            );
+        }
+    }
+}
+
+/// Every running STDIO backend process, for resource sampling.
+async fn running_stdio_processes(state: &AppState) -> Vec<crate::metrics::system::ChildProcess> {
+    let Some(stdio) = &state.stdio_transport else {
+        return Vec::new();
+    };
+    let config = state.config.load_full();
+    let mut children = Vec::new();
+    for server in config.servers.iter().filter(|s| s.transport.is_process()) {
+        for process in stdio.process_status(&server.id).await {
+            if let (true, Some(pid)) = (process.running, process.pid) {
+                children.push(crate::metrics::system::ChildProcess {
+                    server_id: server.id.clone(),
+                    slot: process.slot,
+                    pid,
+                });
+            }
+        }
+    }
+    children
+}
+
+/// Startup policy and progress of a STDIO server, `None` for other
+/// transports. A running process means ready, whatever started it.
+async fn startup_status(
+    state: &AppState,
+    server_config: &crate::config::McpServerConfig,
+) -> Option<StartupStatus> {
+    if !server_config.transport.is_process() {
+        return None;
+    }
+    let running = match &state.stdio_transport {
+        Some(stdio) => stdio.process_status(&server_config.id).await.iter().any(|p| p.running),
+        None => false,
+    };
+
+    let recorded = state.startup.get(&server_config.id).map(|status| status.clone());
+    let startup_state = match recorded {
+        _ if running => StartupState::Ready,
+        Some(status) if status.state != StartupState::Ready => return Some(status),
+        _ => StartupState::Deferred,
+    };
+    Some(StartupStatus {
+        policy: server_config.startup.as_str().to_string(),
+        state: startup_state,
+        error: None,
+    })
+}
+
 /// GET /api/v1/admin/tools - List all tools from all servers
 async fn admin_get_tools(
     State(state): State<AppState>,
 ) -> std::result::Result<Json<Vec<crate::types::ToolInfo>>, (StatusCode, String)> {
-    let config = state.config.as_ref();
+    let config = state.config.load();
     let mut all_tools = Vec::new();
 
     for server_config in &config.servers {
@@ -814,6 +1680,10 @@ async fn admin_get_tools(
             Ok(tools) => {
                 for tool in tools {
                     all_tools.push(crate::types::ToolInfo {
+                        tags: server_config.tool_tags(&tool.name).map(String::from).collect(),
+                        notes: server_config
+                            .tool_metadata(&tool.name)
+                            .and_then(|m| m.notes.clone()),
                         name: tool.name,
                         server: server_config.id.clone(),
                         description: tool.description,
@@ -829,29 +1699,452 @@ async fn admin_get_tools(
     Ok(Json(all_tools))
 }
 
+/// GET /api/v1/admin/costs - Cost of tool calls since startup
+async fn admin_get_costs(State(state): State<AppState>) -> Json<crate::metrics::cost::CostReport> {
+    Json(state.costs.report())
+}
+
+/// GET /api/v1/admin/alerts - Alerts currently firing
+async fn admin_get_alerts(
+    State(state): State<AppState>,
+) -> Json<Vec<crate::metrics::alerts::ActiveAlert>> {
+    Json(state.alerts.active())
+}
+
+/// GET /api/v1/admin/requests - Recently answered requests, newest first
+async fn admin_get_requests(
+    State(state): State<AppState>,
+    Query(filter): Query<crate::proxy::history::RequestFilter>,
+) -> Json<Vec<crate::types::RequestRecord>> {
+    Json(state.history.query(&filter))
+}
+
+/// The proxy's state as seen by alert rules.
+async fn observe(state: &AppState) -> crate::metrics::alerts::Observation {
+    let config = state.config.load();
+    let registry = state.registry.read().await;
+    let backends = config
+        .servers
+        .iter()
+        .filter(|server| server.enabled)
+        .map(|server| {
+            (
+                server.id.clone(),
+                registry.health_state(&server.id) == HealthState::Unhealthy,
+            )
+        })
+        .collect();
+    drop(registry);
+    let cache = state.cache.stats().await;
+
+    crate::metrics::alerts::Observation {
+        at: Instant::now(),
+        backends,
+        requests: crate::metrics::alerts::request_counts(),
+        cache_hits: cache.total_hits,
+        cache_misses: cache.total_misses,
+    }
+}
+
+/// PUT /api/v1/admin/servers/:id/metadata - Replace a server's tags and notes
+async fn admin_set_server_metadata(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(metadata): Json<Metadata>,
+) -> std::result::Result<Json<Metadata>, (StatusCode, String)> {
+    set_metadata(&state, &id, None, metadata).await
+}
+
+/// PUT /api/v1/admin/servers/:id/tools/:tool/metadata - Replace a tool's tags and notes
+async fn admin_set_tool_metadata(
+    State(state): State<AppState>,
+    Path((id, tool)): Path<(String, String)>,
+    Json(metadata): Json<Metadata>,
+) -> std::result::Result<Json<Metadata>, (StatusCode, String)> {
+    set_metadata(&state, &id, Some(&tool), metadata).await
+}
+
+/// POST /api/v1/admin/servers/:id/enable - Enable a server
+async fn admin_enable_server(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> std::result::Result<Json<ActionResult>, (StatusCode, String)> {
+    set_enabled(&state, &id, true).await
+}
+
+/// POST /api/v1/admin/servers/:id/disable - Disable a server
+async fn admin_disable_server(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> std::result::Result<Json<ActionResult>, (StatusCode, String)> {
+    set_enabled(&state, &id, false).await
+}
+
+/// POST /api/v1/admin/servers/:id/health-check - Run a health check now
+async fn admin_check_server(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> std::result::Result<Json<ActionResult>, (StatusCode, String)> {
+    find_server(&state, &id)?;
+
+    let checker = state.health_checkers.get(&id).map(|c| c.value().clone()).ok_or_else(|| {
+        (
+            StatusCode::CONFLICT,
+            format!("Server '{}' has no active health check", id),
+        )
+    })?;
+    let health = checker.check_now().await;
+
+    info!("Health check of {} requested: {:?}", id, health);
+    Ok(Json(ActionResult {
+        message: format!("{} is {:?}", id, health),
+    }))
+}
+
+/// One pass of the STDIO supervisor: start processes whose backoff is over,
+/// then decide what to do about processes that exited since the last pass.
+async fn supervise_stdio(
+    state: &AppState,
+    stdio: &crate::transport::stdio::StdioTransport,
+    health_states: &HealthStateMap,
+    health_history: &HealthHistory,
+    pending: &mut Vec<(Instant, String, usize)>,
+) {
+    let mut exits = stdio.take_exited().await;
+    let now = Instant::now();
+    let (due, waiting): (Vec<_>, Vec<_>) = pending.drain(..).partition(|(at, ..)| *at <= now);
+    *pending = waiting;
+
+    for (_, server_id, slot) in due {
+        if let Err(e) = restart_stdio_slot(state, stdio, &server_id, slot).await {
+            warn!(
+                "Failed to restart STDIO process {} of {}: {}",
+                slot, server_id, e
+            );
+            // Counts against the restart limit like a crash
+            exits.push(ProcessExit {
+                server_id,
+                slot,
+                status: None,
+            });
+        }
+    }
+
+    let config = state.config.load();
+    for exit in exits {
+        let Some(server) = config.servers.iter().find(|s| s.id == exit.server_id && s.enabled)
+        else {
+            continue;
+        };
+        match state.restarts.on_exit(&exit, &server.restart, Instant::now()) {
+            RestartDecision::Ignore => {
+                info!(
+                    "STDIO process {} of {} exited: {}",
+                    exit.slot,
+                    exit.server_id,
+                    exit.describe()
+                );
+            },
+            RestartDecision::RestartAfter(delay) => {
+                warn!(
+                    "STDIO process {} of {} exited ({}); restarting in {:?}",
+                    exit.slot,
+                    exit.server_id,
+                    exit.describe(),
+                    delay
+                );
+                pending.push((Instant::now() + delay, exit.server_id, exit.slot));
+            },
+            RestartDecision::CrashLoop => mark_crash_loop(server, health_states, health_history),
+        }
+    }
+}
+
+/// Start one exited process of a STDIO server again.
+async fn restart_stdio_slot(
+    state: &AppState,
+    stdio: &crate::transport::stdio::StdioTransport,
+    server_id: &str,
+    slot: usize,
+) -> std::result::Result<(), crate::transport::stdio::TransportError> {
+    let config = state.config.load();
+    let Some(stdio_config) = config
+        .servers
+        .iter()
+        .find(|s| s.id == server_id && s.enabled)
+        .and_then(|server| handler::stdio_config(state, server))
+    else {
+        // Removed, disabled or no longer a STDIO server
+        return Ok(());
+    };
+
+    stdio.restart_slot(server_id, slot, &stdio_config).await?;
+    info!("Restarted STDIO process {} of {}", slot, server_id);
+    crate::metrics::STDIO_PROCESS_RESTARTS_TOTAL
+        .with_label_values(&[server_id])
+        .inc();
+    Ok(())
+}
+
+/// Mark a STDIO server that exceeded its restart limit unhealthy.
+fn mark_crash_loop(
+    server: &crate::config::McpServerConfig,
+    health_states: &HealthStateMap,
+    health_history: &HealthHistory,
+) {
+    let reason = format!(
+        "crash loop: more than {} restarts within {}s",
+        server.restart.max_restarts, server.restart.window_seconds
+    );
+    error!("STDIO server {} is in a {}", server.id, reason);
+    crate::metrics::STDIO_CRASH_LOOPS_TOTAL.with_label_values(&[&server.id]).inc();
+
+    let from = health_states
+        .insert(server.id.clone(), HealthState::Unhealthy)
+        .unwrap_or(HealthState::Unknown);
+    health_history.record(HealthTransition {
+        server_id: server.id.clone(),
+        from: format!("{:?}", from),
+        to: format!("{:?}", HealthState::Unhealthy),
+        at: chrono::Utc::now(),
+        reason: Some(reason),
+    });
+}
+
+/// POST /api/v1/admin/servers/:id/restart - Restart a STDIO server's processes
+async fn admin_restart_server(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> std::result::Result<Json<ActionResult>, (StatusCode, String)> {
+    let server = find_server(&state, &id)?;
+    if !server.transport.is_process() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Server '{}' is not a STDIO server", id),
+        ));
+    }
+
+    // Processes are started again on the server's next request
+    if let Some(stdio) = &state.stdio_transport {
+        stdio
+            .kill_process(&id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    }
+    // A crash-looping server takes traffic again until its health check says otherwise
+    if state.restarts.reset(&id) {
+        let observed = match &state.cluster {
+            Some(cluster) => cluster.observed_health(),
+            None => state.registry.read().await.health_states(),
+        };
+        observed.insert(id.clone(), HealthState::Unknown);
+        info!("Cleared crash loop of {}", id);
+    }
+
+    info!("Restarted STDIO processes of {}", id);
+    Ok(Json(ActionResult {
+        message: format!("Restarted {}", id),
+    }))
+}
+
+/// POST /api/v1/admin/servers/:id/refresh - Fetch a server's lists again
+async fn admin_refresh_server(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> std::result::Result<Json<ActionResult>, (StatusCode, String)> {
+    let server = find_server(&state, &id)?;
+    if !server.enabled {
+        return Err((StatusCode::CONFLICT, format!("Server '{}' is disabled", id)));
+    }
+
+    crate::proxy::handler::refresh_server_catalog(&state, &id)
+        .await
+        .map_err(|e| (StatusCode::BAD_GATEWAY, e.to_string()))?;
+    // Aggregated lists may include what the server advertised before
+    state.catalog.request_refresh();
+
+    let catalog = state.registry.read().await.server_catalog(&id).unwrap_or_default();
+    let count = |len: Option<usize>| len.unwrap_or(0);
+    Ok(Json(ActionResult {
+        message: format!(
+            "Refreshed {}: {} tools, {} resources, {} prompts",
+            id,
+            count(catalog.tools.map(|e| e.items.len())),
+            count(catalog.resources.map(|e| e.items.len())),
+            count(catalog.prompts.map(|e| e.items.len())),
+        ),
+    }))
+}
+
+/// GET /api/v1/admin/servers/:id/logs - Latest captured output of a STDIO server
+async fn admin_get_server_logs(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<crate::transport::backend_logs::TailQuery>,
+) -> std::result::Result<Json<crate::types::ServerLogs>, (StatusCode, String)> {
+    find_server(&state, &id)?;
+
+    let config = state.config.load_full();
+    let lines = query.lines.unwrap_or(crate::transport::backend_logs::DEFAULT_TAIL_LINES);
+    let logs = &config.observability.backend_logs;
+    let lines = crate::transport::backend_logs::tail(logs, &id, lines).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to read the logs of {}: {}", id, e),
+        )
+    })?;
+    Ok(Json(crate::types::ServerLogs {
+        server_id: id,
+        lines,
+    }))
+}
+
+/// POST /api/v1/admin/cache/clear - Drop all cached responses
+async fn admin_clear_cache(State(state): State<AppState>) -> Json<ActionResult> {
+    let stats = state.cache.stats().await;
+    let entries = stats.l1_entries + stats.l2_entries + stats.l3_entries;
+    state.cache.clear().await;
+
+    info!("Cleared {} cached responses", entries);
+    Json(ActionResult {
+        message: format!("Cleared {} cached responses", entries),
+    })
+}
+
+/// GET /api/v1/admin/cluster - Cluster membership seen by this instance
+async fn admin_cluster_status(
+    State(state): State<AppState>,
+) -> std::result::Result<Json<crate::types::ClusterStatus>, (StatusCode, String)> {
+    let cluster = state.cluster.as_ref().ok_or_else(cluster_disabled)?;
+    Ok(Json(cluster.status()))
+}
+
+/// POST /api/v1/admin/cluster/gossip - Exchange state with a peer
+async fn admin_cluster_gossip(
+    State(state): State<AppState>,
+    Json(message): Json<crate::proxy::cluster::GossipMessage>,
+) -> std::result::Result<Json<crate::proxy::cluster::GossipMessage>, (StatusCode, String)> {
+    let cluster = state.cluster.as_ref().ok_or_else(cluster_disabled)?;
+    cluster.receive(message).map(Json).map_err(|e| (StatusCode::CONFLICT, e))
+}
+
+fn cluster_disabled() -> (StatusCode, String) {
+    (
+        StatusCode::NOT_FOUND,
+        "Cluster mode is not enabled".to_string(),
+    )
+}
+
+/// POST /api/v1/admin/gitops/sync - Fetch the GitOps branch now, e.g. from
+/// a push webhook
+async fn admin_gitops_sync(
+    State(state): State<AppState>,
+) -> std::result::Result<(StatusCode, Json<ActionResult>), (StatusCode, String)> {
+    let gitops = state.gitops.as_ref().ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            "GitOps sync is not enabled".to_string(),
+        )
+    })?;
+    gitops.request();
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ActionResult {
+            message: format!("Sync of {} requested", gitops.settings().branch),
+        }),
+    ))
+}
+
+/// POST /api/v1/admin/logs/rotate - Rotate the daemon log file now
+async fn admin_rotate_logs() -> std::result::Result<Json<ActionResult>, (StatusCode, String)> {
+    let Some(log) = crate::daemon::log_file::current() else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Not running as a daemon: logs go to stdout".to_string(),
+        ));
+    };
+    let rotated = log.rotate().map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to rotate logs: {}", e),
+        )
+    })?;
+
+    info!("Rotated daemon log to {}", rotated.display());
+    Ok(Json(ActionResult {
+        message: format!("Rotated {} to {}", log.path().display(), rotated.display()),
+    }))
+}
+
+/// GET /api/v1/admin/config/history - Applied versions of the configuration
+/// file, newest first
+async fn admin_config_history(
+    State(state): State<AppState>,
+) -> std::result::Result<Json<Vec<crate::types::ConfigVersion>>, (StatusCode, String)> {
+    let applied = crate::config::backup::list_applied(&state.config_path)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let newest = applied.len().saturating_sub(1);
+    let versions = applied
+        .into_iter()
+        .enumerate()
+        .rev()
+        .map(|(i, version)| crate::types::ConfigVersion {
+            timestamp: version.timestamp,
+            path: version.path.display().to_string(),
+            current: i == newest,
+        })
+        .collect();
+    Ok(Json(versions))
+}
+
+/// Body of `POST /api/v1/admin/config/rollback`
+#[derive(Debug, Default, serde::Deserialize)]
+struct RollbackRequest {
+    /// Timestamp (or a unique prefix) of the applied version to restore
+    to: Option<String>,
+}
+
+/// POST /api/v1/admin/config/rollback - Restore and apply the last good
+/// configuration, or the applied version given in the body
+async fn admin_config_rollback(
+    State(state): State<AppState>,
+    request: Option<Json<RollbackRequest>>,
+) -> std::result::Result<Json<ActionResult>, (StatusCode, String)> {
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+    let restored =
+        crate::config::backup::rollback_applied(&state.config_path, request.to.as_deref())
+            .map_err(|e| match e {
+                Error::Config(_) => (StatusCode::BAD_REQUEST, e.to_string()),
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+            })?;
+    let diff = apply_config_file(&state).await?;
+
+    info!(
+        "Rolled configuration back to {}: {}",
+        restored.timestamp, diff
+    );
+    Ok(Json(ActionResult {
+        message: format!("Rolled back to {}: {}", restored.timestamp, diff),
+    }))
+}
+
 /// GET /api/v1/admin/health - Overall system health
 async fn admin_health(
     State(state): State<AppState>,
 ) -> std::result::Result<Json<crate::types::HealthStatus>, (StatusCode, String)> {
-    let config = state.config.as_ref();
+    let config = state.config.load();
     let registry = state.registry.read().await;
 
-    let servers_total = config.servers.iter().filter(|s| s.enabled).count();
+    let servers_total = config.routed_servers().count();
 
-    // Health tracking not yet fully implemented - count registered servers as healthy
-    let servers_healthy = registry.len();
+    // Servers not marked unhealthy by active health checks
+    let servers_healthy = registry.get_healthy_servers().await.len();
+    drop(registry);
 
     // Count total tools (best effort)
     let tools_total = count_all_tools(&state).await.unwrap_or(0);
 
-    // Determine overall status - simplified until health tracking is implemented
-    let status = if servers_total > 0 && servers_healthy > 0 {
-        "healthy"
-    } else if servers_healthy > 0 {
-        "degraded"
-    } else {
-        "unhealthy"
-    };
+    let status = overall_status(servers_total, servers_healthy);
 
     let uptime_seconds = state.start_time.elapsed().as_secs();
 
@@ -864,6 +2157,25 @@ async fn admin_health(
     }))
 }
 
+/// `healthy` when every routed server is available, `degraded` when some
+/// are, `unhealthy` otherwise.
+fn overall_status(servers_total: usize, servers_healthy: usize) -> &'static str {
+    if servers_total > 0 && servers_healthy >= servers_total {
+        "healthy"
+    } else if servers_healthy > 0 {
+        "degraded"
+    } else {
+        "unhealthy"
+    }
+}
+
+/// GET /api/v1/admin/state - Sanitized runtime state bundle for support
+async fn admin_export_state(
+    State(state): State<AppState>,
+) -> Json<crate::proxy::snapshot::StateBundle> {
+    Json(crate::proxy::snapshot::StateBundle::capture(&state).await)
+}
+
 /// GET /api/v1/admin/system - System information
 async fn admin_system_info(
     State(state): State<AppState>,
@@ -873,6 +2185,7 @@ async fn admin_system_info(
         config_path: state.config_path.display().to_string(),
         pid: std::process::id(),
         uptime_seconds: state.start_time.elapsed().as_secs(),
+        gitops: state.gitops.as_ref().map(|gitops| gitops.status()),
     }))
 }
 
@@ -894,101 +2207,274 @@ async fn fetch_tools_for_server_internal(
     state: &AppState,
     server_id: &str,
 ) -> crate::error::Result<Vec<Tool>> {
-    let config = state.config.as_ref();
-    let server_config = config
+    // Served from the registry while fresh, otherwise fetched and recorded
+    crate::proxy::handler::server_tools(state, server_id).await
+}
+
+/// Persist tags and notes to the config file, then apply them to the live
+/// configuration (they need no reconnect, so nothing else is touched).
+async fn set_metadata(
+    state: &AppState,
+    server_id: &str,
+    tool: Option<&str>,
+    metadata: Metadata,
+) -> std::result::Result<Json<Metadata>, (StatusCode, String)> {
+    find_server(state, server_id)?;
+
+    crate::config::edit::set_metadata(&state.config_path, server_id, tool, &metadata).map_err(
+        |e| match e {
+            Error::Config(_) => (StatusCode::BAD_REQUEST, e.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        },
+    )?;
+
+    state.config.rcu(|current| {
+        let mut config = Config::clone(current);
+        if let Some(server) = config.servers.iter_mut().find(|s| s.id == server_id) {
+            match tool {
+                None => {
+                    server.tags = metadata.tags.clone();
+                    server.notes = metadata.notes.clone();
+                },
+                Some(tool) if metadata.is_empty() => {
+                    server.tool_metadata.remove(tool);
+                },
+                Some(tool) => {
+                    server.tool_metadata.insert(tool.to_string(), metadata.clone());
+                },
+            }
+        }
+        config
+    });
+
+    info!(
+        "Updated metadata for {}{}",
+        server_id,
+        tool.map(|t| format!(" tool {}", t)).unwrap_or_default()
+    );
+    Ok(Json(metadata))
+}
+
+/// Configuration of the server with the given ID, or 404.
+fn find_server(
+    state: &AppState,
+    server_id: &str,
+) -> std::result::Result<crate::config::McpServerConfig, (StatusCode, String)> {
+    state
+        .config
+        .load()
         .servers
         .iter()
         .find(|s| s.id == server_id)
-        .ok_or_else(|| Error::Config(format!("Server not found: {}", server_id)))?;
-
-    // Create tools/list request
-    let request = McpRequest::new(
-        "tools/list",
-        serde_json::json!({}),
-        Some(serde_json::json!(1)),
-    );
+        .cloned()
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                format!("Server '{}' not found", server_id),
+            )
+        })
+}
 
-    // Fetch based on transport type (reuse display_loaded_servers logic)
-    let response = match &server_config.transport {
-        TransportConfig::Http { url, headers } => {
-            let http_transport = state
-                .http_transport
-                .as_ref()
-                .ok_or_else(|| Error::Transport("HTTP transport not initialized".into()))?;
-            http_transport
-                .send_request_with_headers(url, request, headers.clone())
-                .await
-                .map_err(|e| Error::Transport(e.to_string()))?
-        },
-        TransportConfig::Sse { url, headers } => {
-            let sse_transport = state
-                .sse_transport
-                .as_ref()
-                .ok_or_else(|| Error::Transport("SSE transport not initialized".into()))?;
-            sse_transport
-                .send_request_with_headers(url, request, headers.clone())
-                .await
-                .map_err(|e| Error::Transport(e.to_string()))?
+/// Persist a server's `enabled` flag to the config file, then apply the
+/// edited configuration so the server is (dis)connected right away.
+async fn set_enabled(
+    state: &AppState,
+    server_id: &str,
+    enabled: bool,
+) -> std::result::Result<Json<ActionResult>, (StatusCode, String)> {
+    find_server(state, server_id)?;
+
+    crate::config::edit::set_enabled(&state.config_path, server_id, enabled).map_err(
+        |e| match e {
+            Error::Config(_) => (StatusCode::BAD_REQUEST, e.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
         },
-        TransportConfig::StreamableHttp {
-            url,
-            headers,
-            timeout_ms,
-        } => {
-            let streamable_http_transport =
-                state.streamable_http_transport.as_ref().ok_or_else(|| {
-                    Error::Transport("Streamable HTTP transport not initialized".into())
-                })?;
-
-            let transport_config = crate::transport::streamable_http::StreamableHttpConfig {
-                url: url.clone(),
-                headers: headers.clone(),
-                timeout_ms: *timeout_ms,
-            };
+    )?;
 
-            let transport = streamable_http_transport.get_or_create(transport_config);
-            transport.send_request(request).await?
-        },
-        TransportConfig::Stdio { command, args, env } => {
-            let stdio_transport = state
-                .stdio_transport
-                .as_ref()
-                .ok_or_else(|| Error::Transport("STDIO transport not initialized".into()))?;
-
-            let stdio_config = crate::transport::stdio::StdioConfig {
-                command: command.clone(),
-                args: args.clone(),
-                env: env.clone(),
-                cwd: None,
-                timeout_ms: 30000,
-                max_memory_mb: Some(512),
-                max_cpu_percent: Some(50),
-                sandbox: true,
-            };
+    apply_config_file(state).await?;
 
-            stdio_transport
-                .send_request_with_config(server_id.to_string(), &stdio_config, request)
-                .await
-                .map_err(|e| Error::Transport(e.to_string()))?
-        },
+    let action = if enabled { "Enabled" } else { "Disabled" };
+    info!("{} server {}", action, server_id);
+    Ok(Json(ActionResult {
+        message: format!("{} {}", action, server_id),
+    }))
+}
+
+/// Query of `POST /api/v1/admin/config/validate`
+#[derive(Debug, Default, serde::Deserialize)]
+struct ValidateQuery {
+    /// Report unknown keys as errors instead of warnings
+    #[serde(default)]
+    deny_unknown_fields: bool,
+    /// Do not probe the added and changed servers
+    #[serde(default)]
+    skip_reachability: bool,
+}
+
+/// POST /api/v1/admin/config/validate - Check a candidate configuration
+/// (YAML, or TOML when the content type says so) and report what applying
+/// it would change, without applying it. Responds 422 when it is not valid.
+async fn admin_validate_config(
+    State(state): State<AppState>,
+    Query(query): Query<ValidateQuery>,
+    headers: axum::http::HeaderMap,
+    body: String,
+) -> (StatusCode, Json<crate::types::ConfigValidation>) {
+    use crate::config::schema::{self, Diagnostic, Format, Severity};
+
+    let format = match headers.get(axum::http::header::CONTENT_TYPE).and_then(|v| v.to_str().ok()) {
+        Some(content_type) if content_type.contains("toml") => Format::Toml,
+        _ => Format::Yaml,
     };
+    let mut diagnostics = schema::check_document(&body, format, query.deny_unknown_fields);
+    let has_errors =
+        |diagnostics: &[Diagnostic]| diagnostics.iter().any(|d| d.severity == Severity::Error);
+
+    // Loading also resolves secret references, which the schema check skips
+    let mut candidate = None;
+    if !has_errors(&diagnostics) {
+        match Config::from_content(&body, format) {
+            Ok(config) => candidate = Some(config),
+            Err(e) => diagnostics.push(Diagnostic {
+                severity: Severity::Error,
+                path: String::new(),
+                line: None,
+                column: None,
+                message: e.to_string(),
+                suggestion: None,
+            }),
+        }
+    }
 
-    // Parse tools from response
-    let tools: Vec<Tool> = if let Some(result) = response.result {
-        serde_json::from_value(
-            result.get("tools").cloned().unwrap_or_else(|| serde_json::json!([])),
-        )
-        .map_err(|e| Error::Server(format!("Failed to parse tools: {}", e)))?
-    } else {
-        Vec::new()
+    let mut diff = None;
+    let mut reachability = Vec::new();
+    if let Some(candidate) = candidate {
+        let current = state.config.load_full();
+        let mut candidate = keep_listener(&current, &candidate);
+        state.discovered.preview(&mut candidate);
+        let changes = ConfigDiff::between(&current, &candidate);
+        if !query.skip_reachability {
+            let probed = candidate.servers.iter().filter(|server| {
+                server.enabled
+                    && (changes.servers_added.contains(&server.id)
+                        || changes.servers_changed.contains(&server.id))
+            });
+            let probes = probed.map(|server| probe_server(server, &candidate));
+            reachability = futures::future::join_all(probes).await;
+        }
+        diff = Some(config_changes(&changes));
+    }
+
+    let valid = !has_errors(&diagnostics) && reachability.iter().all(|r| r.reachable);
+    let status = match valid {
+        true => StatusCode::OK,
+        false => StatusCode::UNPROCESSABLE_ENTITY,
     };
+    let diagnostics = diagnostics
+        .into_iter()
+        .map(|d| crate::types::ConfigDiagnostic {
+            severity: match d.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            }
+            .to_string(),
+            path: d.path,
+            line: d.line,
+            column: d.column,
+            message: d.message,
+            suggestion: d.suggestion,
+        })
+        .collect();
+    (
+        status,
+        Json(crate::types::ConfigValidation {
+            valid,
+            diagnostics,
+            reachability,
+            diff,
+        }),
+    )
+}
 
-    Ok(tools)
+/// Run one health check of a server of `config` that is not registered,
+/// without recording it.
+async fn probe_server(
+    server: &McpServerConfig,
+    config: &Config,
+) -> crate::types::ServerReachability {
+    let checker = HealthChecker::from_config(
+        server.id.clone(),
+        HealthCheckTransport::from_transport_config(
+            &server.transport,
+            config.outbound_proxy(&server.transport),
+        ),
+        server.health_check.clone(),
+    );
+    let result = match checker {
+        Ok(checker) => checker.probe().await,
+        Err(e) => Err(e.to_string()),
+    };
+    crate::types::ServerReachability {
+        server_id: server.id.clone(),
+        reachable: result.is_ok(),
+        latency_ms: result.as_ref().ok().map(|latency| latency.as_millis() as u64),
+        error: result.err(),
+    }
+}
+
+fn config_changes(diff: &ConfigDiff) -> crate::types::ConfigChanges {
+    let names = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+    crate::types::ConfigChanges {
+        servers_added: diff.servers_added.clone(),
+        servers_removed: diff.servers_removed.clone(),
+        servers_changed: diff.servers_changed.clone(),
+        servers_annotated: diff.servers_annotated.clone(),
+        settings_changed: names(&diff.settings_changed()),
+        restart_required: names(&diff.restart_required),
+        summary: diff.to_string(),
+    }
+}
+
+/// `new_config` as applied over `current`: the listen address, which may
+/// come from `--host`/`--port`, is kept.
+fn keep_listener(current: &Config, new_config: &Config) -> Config {
+    let mut new_config = new_config.clone();
+    new_config.server.host = current.server.host.clone();
+    new_config.server.port = current.server.port;
+    new_config
+}
+
+/// Load the configuration file and apply it now, without waiting for hot
+/// reload to notice the change.
+async fn apply_config_file(
+    state: &AppState,
+) -> std::result::Result<ConfigDiff, (StatusCode, String)> {
+    let internal = |e: Error| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string());
+    let config = Config::from_file(&state.config_path).map_err(internal)?;
+    let (reply_tx, reply_rx) = tokio::sync::oneshot::channel();
+    state
+        .config_updates
+        .send((config, reply_tx))
+        .map_err(|_| internal(Error::Server("Server is shutting down".into())))?;
+    reply_rx
+        .await
+        .map_err(|_| internal(Error::Server("Server is shutting down".into())))?
+        .map_err(internal)
+}
+
+/// Keep the configuration file in the history of applied versions
+/// (`backups.keep_applied`).
+fn record_applied(config_path: &std::path::Path, config: &Config) {
+    match crate::config::backup::record_applied(config_path, &config.backups) {
+        Ok(Some(version)) => debug!("Recorded applied configuration {}", version.timestamp),
+        Ok(None) => {},
+        Err(e) => warn!("Failed to record the applied configuration: {}", e),
+    }
 }
 
 /// Count total tools across all enabled servers
 async fn count_all_tools(state: &AppState) -> crate::error::Result<usize> {
-    let config = state.config.as_ref();
+    let config = state.config.load();
     let mut total = 0;
 
     for server_config in &config.servers {
@@ -1009,6 +2495,9 @@ fn get_transport_name(transport: &TransportConfig) -> &'static str {
         TransportConfig::Sse { .. } => "SSE",
         TransportConfig::StreamableHttp { .. } => "Streamable HTTP",
         TransportConfig::Stdio { .. } => "STDIO",
+        TransportConfig::Grpc { .. } => "gRPC",
+        TransportConfig::Unix { .. } => "Unix socket",
+        TransportConfig::Docker { .. } => "Docker",
     }
 }
 
@@ -1017,9 +2506,9 @@ async fn health_check_handler(State(state): State<AppState>) -> impl IntoRespons
     use axum::http::StatusCode;
     use serde_json::json;
 
-    // Check if registry has any servers
+    // Check if registry has any servers that are not unhealthy
     let registry = state.registry.read().await;
-    let server_count = registry.len();
+    let server_count = registry.get_healthy_servers().await.len();
 
     let status = if server_count > 0 { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
 
@@ -1032,3 +2521,98 @@ async fn health_check_handler(State(state): State<AppState>) -> impl IntoRespons
         })),
     )
 }
+
+/// GET /health/live - Liveness probe: the process is up and serving.
+async fn health_live_handler(State(state): State<AppState>) -> impl IntoResponse {
+    Json(serde_json::json!({
+        "status": "alive",
+        "uptime_seconds": state.start_time.elapsed().as_secs(),
+    }))
+}
+
+/// Whether the proxy should receive traffic, or why not: it must not be
+/// shutting down and at least one routed backend must be available (unless
+/// it answers from dry-run fixtures).
+async fn readiness(state: &AppState, drain: &DrainState) -> std::result::Result<usize, String> {
+    if drain.is_draining() {
+        return Err("shutting down".to_string());
+    }
+    if state.dry_run.is_some() {
+        return Ok(0);
+    }
+    let servers_healthy = state.registry.read().await.get_healthy_servers().await.len();
+    if servers_healthy == 0 {
+        return Err("no healthy backends".to_string());
+    }
+    Ok(servers_healthy)
+}
+
+/// GET /health/ready - Readiness probe: 503 while no backend can serve
+/// requests or the proxy is draining.
+async fn health_ready_handler(
+    State(state): State<AppState>,
+    drain: Arc<DrainState>,
+) -> impl IntoResponse {
+    use serde_json::json;
+
+    match readiness(&state, &drain).await {
+        Ok(servers_healthy) => (
+            StatusCode::OK,
+            Json(json!({ "status": "ready", "servers_healthy": servers_healthy })),
+        ),
+        Err(reason) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({ "status": "not_ready", "reason": reason })),
+        ),
+    }
+}
+
+/// GET /health/detail - Per-backend health, last check, consecutive
+/// failures and circuit state.
+async fn health_detail_handler(
+    State(state): State<AppState>,
+    drain: Arc<DrainState>,
+) -> Json<crate::types::HealthDetail> {
+    let config = state.config.load();
+    let ready = readiness(&state, &drain).await.is_ok();
+
+    let registry = state.registry.read().await;
+    let servers_total = config.routed_servers().count();
+    let servers_healthy = registry.get_healthy_servers().await.len();
+
+    let mut backends = Vec::new();
+    for server in &config.servers {
+        let health = registry.health_state(&server.id);
+        let checker = state.health_checkers.get(&server.id).map(|c| c.value().clone());
+        let status = match checker {
+            Some(checker) => Some(checker.get_status().await),
+            None => None,
+        };
+        backends.push(crate::types::BackendHealthDetail {
+            id: server.id.clone(),
+            enabled: server.enabled,
+            health: format!("{:?}", health).to_lowercase(),
+            // The published state is unhealthy while crash looping too
+            circuit: if health == HealthState::Unhealthy { "open" } else { "closed" }.to_string(),
+            last_check: status.as_ref().and_then(|s| s.last_checked),
+            consecutive_failures: status.as_ref().map_or(0, |s| s.failure_count),
+            consecutive_successes: status.as_ref().map_or(0, |s| s.success_count),
+            last_error: status.and_then(|s| s.last_failure_reason).map(|error| {
+                crate::config::redact::redact_values(&error, &server.redacted_env_values())
+            }),
+            restart: server
+                .transport
+                .is_process()
+                .then(|| state.restarts.status(&server.id, &server.restart)),
+        });
+    }
+    drop(registry);
+
+    Json(crate::types::HealthDetail {
+        status: overall_status(servers_total, servers_healthy).to_string(),
+        ready,
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        uptime_seconds: state.start_time.elapsed().as_secs(),
+        backends,
+    })
+}
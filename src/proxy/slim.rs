@@ -0,0 +1,183 @@
+//! Slim tool catalogs (`context_optimization.slim`).
+//!
+//! A tools/list result aggregated from dozens of backends can take a large
+//! share of a client's context. In slim mode, long tool descriptions are
+//! shortened and input schemas over the size limit lose their descriptions,
+//! titles and examples, keeping the structure clients need to build calls.
+//! In names mode only tool names are listed, and clients fetch the full
+//! definitions of the tools they want with `tools/describe`.
+
+use serde_json::{json, Value};
+
+use crate::config::{CatalogMode, SlimConfig};
+
+/// Schema keywords that document a schema without constraining it.
+const ANNOTATIONS: [&str; 5] = ["description", "title", "examples", "example", "$comment"];
+
+/// Reduce the tools of a tools/list result to what `mode` lists.
+pub fn slim_tools(tools: &mut [Value], mode: CatalogMode, config: &SlimConfig) {
+    for tool in tools.iter_mut() {
+        match mode {
+            CatalogMode::Full => {},
+            CatalogMode::Slim => slim_tool(tool, config),
+            CatalogMode::Names => names_only(tool),
+        }
+    }
+}
+
+/// Shorten a tool's description and strip its input schema if it is over
+/// the size limit.
+fn slim_tool(tool: &mut Value, config: &SlimConfig) {
+    let Some(fields) = tool.as_object_mut() else {
+        return;
+    };
+    if let Some(Value::String(description)) = fields.get_mut("description") {
+        shorten(description, config.max_description_chars);
+    }
+    if let Some(schema) = fields.get_mut("inputSchema") {
+        let size = serde_json::to_vec(schema).map(|bytes| bytes.len()).unwrap_or_default();
+        if size > config.max_schema_bytes {
+            strip_annotations(schema);
+        }
+    }
+}
+
+/// Replace a tool with its name and an open object schema, keeping the
+/// proxy's own `_meta` annotations.
+fn names_only(tool: &mut Value) {
+    let mut slim = json!({
+        "name": tool.get("name").cloned().unwrap_or(Value::Null),
+        "inputSchema": {"type": "object"},
+    });
+    if let Some(meta) = tool.get("_meta") {
+        slim["_meta"] = meta.clone();
+    }
+    *tool = slim;
+}
+
+/// Cut `text` to at most `max_chars` characters, at a word boundary when
+/// there is one, marking the cut with an ellipsis.
+fn shorten(text: &mut String, max_chars: usize) {
+    let Some((cut, _)) = text.char_indices().nth(max_chars) else {
+        return;
+    };
+    let kept = &text[..cut];
+    let cut = kept.rfind(char::is_whitespace).filter(|&space| space > 0).unwrap_or(cut);
+    text.truncate(cut);
+    text.truncate(text.trim_end().len());
+    text.push('…');
+}
+
+/// Remove documentation keywords from a JSON Schema and its subschemas.
+/// Property names are never touched, even ones called `description`.
+fn strip_annotations(schema: &mut Value) {
+    let Some(fields) = schema.as_object_mut() else {
+        return;
+    };
+    for keyword in ANNOTATIONS {
+        fields.remove(keyword);
+    }
+    for (keyword, value) in fields.iter_mut() {
+        match keyword.as_str() {
+            // Maps of names to subschemas
+            "properties" | "patternProperties" | "$defs" | "definitions" => {
+                if let Some(subschemas) = value.as_object_mut() {
+                    subschemas.values_mut().for_each(strip_annotations);
+                }
+            },
+            // A subschema or a list of them
+            "items"
+            | "additionalProperties"
+            | "not"
+            | "anyOf"
+            | "oneOf"
+            | "allOf"
+            | "prefixItems" => match value {
+                Value::Array(subschemas) => subschemas.iter_mut().for_each(strip_annotations),
+                subschema => strip_annotations(subschema),
+            },
+            _ => {},
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(max_description_chars: usize, max_schema_bytes: usize) -> SlimConfig {
+        SlimConfig {
+            max_description_chars,
+            max_schema_bytes,
+            ..SlimConfig::default()
+        }
+    }
+
+    fn tool() -> Value {
+        json!({
+            "name": "search",
+            "description": "Search issues by query. Supports qualifiers.",
+            "inputSchema": {
+                "type": "object",
+                "description": "Search parameters",
+                "properties": {
+                    "query": {"type": "string", "description": "Terms", "examples": ["bug"]},
+                    "description": {"type": "string", "title": "Issue body"},
+                    "labels": {"type": "array", "items": {"type": "string", "description": "A label"}}
+                },
+                "required": ["query"]
+            },
+            "_meta": {"health": "healthy"}
+        })
+    }
+
+    #[test]
+    fn test_slim_mode_strips_large_schemas() {
+        let mut tools = vec![tool()];
+        slim_tools(&mut tools, CatalogMode::Slim, &config(20, 64));
+        assert_eq!(tools[0]["description"], "Search issues by…");
+        assert_eq!(
+            tools[0]["inputSchema"],
+            json!({
+                "type": "object",
+                "properties": {
+                    "query": {"type": "string"},
+                    "description": {"type": "string"},
+                    "labels": {"type": "array", "items": {"type": "string"}}
+                },
+                "required": ["query"]
+            })
+        );
+        assert_eq!(tools[0]["_meta"]["health"], "healthy");
+
+        // Small schemas and short descriptions are kept as they are
+        let mut tools = vec![tool()];
+        slim_tools(&mut tools, CatalogMode::Slim, &config(200, 4096));
+        assert_eq!(tools[0], tool());
+    }
+
+    #[test]
+    fn test_names_mode() {
+        let mut tools = vec![tool()];
+        slim_tools(&mut tools, CatalogMode::Names, &SlimConfig::default());
+        assert_eq!(
+            tools[0],
+            json!({
+                "name": "search",
+                "inputSchema": {"type": "object"},
+                "_meta": {"health": "healthy"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_shorten_without_spaces() {
+        let mut text = "ééééé".to_string();
+        shorten(&mut text, 3);
+        assert_eq!(text, "ééé…");
+
+        let mut text = "short".to_string();
+        shorten(&mut text, 5);
+        assert_eq!(text, "short");
+    }
+}
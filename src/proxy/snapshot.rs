@@ -0,0 +1,237 @@
+//! Runtime state bundles for support (`only1mcp state export|import`).
+//!
+//! A bundle is a single JSON document holding the configuration with secrets
+//! redacted, the versions involved and, when exported from a running
+//! instance, a snapshot of its registry, recent health transitions and cache
+//! statistics. Importing one writes the configuration back out so the
+//! user's setup can be started locally while triaging a report.
+
+use std::fmt;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::cache::CacheStats;
+use crate::config::{redact::redact_config, Config};
+use crate::error::{Error, Result};
+use crate::health::checker::HealthTransition;
+use crate::proxy::server::AppState;
+
+/// Bundle layout version; bumped on incompatible changes.
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Sanitized snapshot of an instance's configuration and runtime state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateBundle {
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub versions: Versions,
+    /// Configuration with secret-bearing values redacted
+    pub config: Config,
+    /// Fields whose values were redacted (need real values to reproduce)
+    #[serde(default)]
+    pub redacted: Vec<String>,
+    /// Live state, absent when no instance was running at export
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<RuntimeSnapshot>,
+}
+
+/// Build and platform information of the exporting instance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Versions {
+    pub only1mcp: String,
+    pub os: String,
+    pub arch: String,
+}
+
+impl Versions {
+    fn current() -> Self {
+        Self {
+            only1mcp: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+        }
+    }
+}
+
+/// Live state of a running instance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeSnapshot {
+    pub uptime_seconds: u64,
+    /// Registered backends, sorted by ID
+    pub servers: Vec<ServerSnapshot>,
+    /// Recent health transitions, oldest first
+    pub health_history: Vec<HealthTransition>,
+    pub cache: CacheStats,
+}
+
+/// A registered backend as seen by the registry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerSnapshot {
+    pub id: String,
+    pub health: String,
+    /// Tools advertised in the backend's latest tools/list
+    pub tools: Vec<String>,
+}
+
+impl StateBundle {
+    /// Bundle for a configuration alone, without runtime state.
+    pub fn from_config(config: &Config) -> Self {
+        let (config, redacted) = redact_config(config);
+        Self {
+            format_version: BUNDLE_FORMAT_VERSION,
+            exported_at: Utc::now(),
+            versions: Versions::current(),
+            config,
+            redacted,
+            runtime: None,
+        }
+    }
+
+    /// Bundle describing a running instance.
+    pub async fn capture(state: &AppState) -> Self {
+        let mut bundle = Self::from_config(&state.config.load());
+
+        let registry = state.registry.read().await;
+        let servers = registry
+            .server_tools()
+            .into_iter()
+            .map(|(id, tools)| ServerSnapshot {
+                health: format!("{:?}", registry.health_state(&id)),
+                id,
+                tools,
+            })
+            .collect();
+        let health_history = registry.health_history().snapshot();
+        drop(registry);
+
+        bundle.runtime = Some(RuntimeSnapshot {
+            uptime_seconds: state.start_time.elapsed().as_secs(),
+            servers,
+            health_history,
+            cache: state.cache.stats().await,
+        });
+        bundle
+    }
+
+    /// Read a bundle from `path`, rejecting unknown format versions.
+    pub fn read(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| Error::Config(format!("Failed to read state bundle: {}", e)))?;
+        let bundle: Self = serde_json::from_str(&content)
+            .map_err(|e| Error::Config(format!("Invalid state bundle: {}", e)))?;
+
+        if bundle.format_version != BUNDLE_FORMAT_VERSION {
+            return Err(Error::Config(format!(
+                "Unsupported state bundle format {} (expected {})",
+                bundle.format_version, BUNDLE_FORMAT_VERSION
+            )));
+        }
+        Ok(bundle)
+    }
+
+    /// Write the bundle to `path` as pretty-printed JSON.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let content =
+            serde_json::to_string_pretty(self).map_err(|e| Error::Serialization(e.to_string()))?;
+        std::fs::write(path, content)
+            .map_err(|e| Error::Config(format!("Failed to write state bundle: {}", e)))
+    }
+}
+
+impl fmt::Display for StateBundle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Exported {} by Only1MCP v{} ({}/{})",
+            self.exported_at.to_rfc3339(),
+            self.versions.only1mcp,
+            self.versions.os,
+            self.versions.arch
+        )?;
+        writeln!(f, "Servers configured: {}", self.config.servers.len())?;
+
+        match &self.runtime {
+            None => writeln!(f, "Runtime state: not captured (instance was not running)")?,
+            Some(runtime) => {
+                writeln!(f, "Uptime: {}s", runtime.uptime_seconds)?;
+                for server in &runtime.servers {
+                    writeln!(
+                        f,
+                        "  - {}: {} ({} tools)",
+                        server.id,
+                        server.health,
+                        server.tools.len()
+                    )?;
+                }
+                writeln!(
+                    f,
+                    "Cache: {} hits, {} misses ({:.1}% hit rate)",
+                    runtime.cache.total_hits, runtime.cache.total_misses, runtime.cache.hit_rate
+                )?;
+                if !runtime.health_history.is_empty() {
+                    writeln!(f, "Recent health transitions:")?;
+                }
+                for transition in runtime.health_history.iter().rev().take(10) {
+                    writeln!(
+                        f,
+                        "  {} {}: {} -> {}{}",
+                        transition.at.to_rfc3339(),
+                        transition.server_id,
+                        transition.from,
+                        transition.to,
+                        transition.reason.as_ref().map(|r| format!(" ({})", r)).unwrap_or_default()
+                    )?;
+                }
+            },
+        }
+
+        if !self.redacted.is_empty() {
+            writeln!(f, "Redacted values to fill in before starting:")?;
+            for field in &self.redacted {
+                writeln!(f, "  - {}", field)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_bundle_round_trips_and_rejects_unknown_format() {
+        let config: Config = serde_yaml::from_str(
+            r#"
+servers:
+  - id: api
+    name: API
+    transport:
+      type: http
+      url: https://mcp.example.com
+      headers: {Authorization: "Bearer s3cret"}
+"#,
+        )
+        .unwrap();
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("state.json");
+
+        let bundle = StateBundle::from_config(&config);
+        assert_eq!(
+            bundle.redacted,
+            vec!["servers[api].transport.headers.Authorization"]
+        );
+        bundle.write(&file).unwrap();
+        let imported = StateBundle::read(&file).unwrap();
+        assert_eq!(imported.config.servers[0].id, "api");
+        assert!(imported.runtime.is_none());
+
+        let mut future = bundle;
+        future.format_version = BUNDLE_FORMAT_VERSION + 1;
+        future.write(&file).unwrap();
+        assert!(StateBundle::read(&file).is_err());
+    }
+}
@@ -0,0 +1,221 @@
+//! Request and response rewriting (`servers[].transforms`).
+//!
+//! Every request to a backend passes through [`transform_request`] and every
+//! response through [`transform_response`], whichever transport carries
+//! them. Clients only ever see exposed tool names: renamed tools are
+//! advertised under their new name in tools/list, and tools/call is
+//! translated back to the backend's name before it is sent.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::config::TransformConfig;
+use crate::routing::tool_matcher::glob_match;
+use crate::types::{McpRequest, McpResponse};
+
+/// Rewrite a request to a backend: restore the backend's name of a renamed
+/// tool and add default arguments the client did not pass.
+pub fn transform_request(transforms: &TransformConfig, mut request: McpRequest) -> McpRequest {
+    if request.method != "tools/call" || transforms.is_empty() {
+        return request;
+    }
+    let Some(params) = request.params.as_mut().and_then(Value::as_object_mut) else {
+        return request;
+    };
+
+    let exposed = params.get("name").and_then(Value::as_str).unwrap_or_default();
+    let Some(tool) = backend_tool_name(transforms, exposed).map(str::to_string) else {
+        return request;
+    };
+    params.insert("name".to_string(), Value::String(tool.clone()));
+
+    let defaults = transforms
+        .default_arguments
+        .iter()
+        .filter(|(pattern, _)| glob_match(pattern, &tool))
+        .flat_map(|(_, defaults)| defaults);
+    for (name, value) in defaults {
+        let arguments =
+            params.entry("arguments").or_insert_with(|| Value::Object(Default::default()));
+        if let Some(arguments) = arguments.as_object_mut() {
+            arguments.entry(name.clone()).or_insert_with(|| value.clone());
+        }
+    }
+    request
+}
+
+/// Rewrite a backend's response to `method`: advertise renamed tools under
+/// their new name and strip fields from tools/call results.
+pub fn transform_response(
+    transforms: &TransformConfig,
+    method: &str,
+    mut response: McpResponse,
+) -> McpResponse {
+    let Some(result) = response.result.as_mut() else {
+        return response;
+    };
+    match method {
+        "tools/list" if !transforms.rename_tools.is_empty() => {
+            let tools = result.get_mut("tools").and_then(Value::as_array_mut);
+            for tool in tools.into_iter().flatten() {
+                rename_tool(transforms, tool);
+            }
+        },
+        "tools/call" => {
+            for path in &transforms.strip_response_fields {
+                remove_path(result, path);
+            }
+        },
+        _ => {},
+    }
+    response
+}
+
+/// Headers to send to an HTTP-based backend: the transport's `headers` with
+/// the transform's header rules applied.
+pub fn backend_headers(
+    transforms: &TransformConfig,
+    headers: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let mut headers: HashMap<String, String> = headers
+        .iter()
+        .filter(|(name, _)| {
+            !transforms.set_headers.keys().any(|set| set.eq_ignore_ascii_case(name))
+        })
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+    headers
+        .extend(transforms.set_headers.iter().map(|(name, value)| (name.clone(), value.clone())));
+    headers.retain(|name, _| {
+        !transforms
+            .remove_headers
+            .iter()
+            .any(|removed| removed.eq_ignore_ascii_case(name))
+    });
+    headers
+}
+
+/// Whether tools/call results are rewritten, so they cannot be streamed
+/// through unparsed.
+pub fn rewrites_results(transforms: &TransformConfig) -> bool {
+    !transforms.strip_response_fields.is_empty()
+}
+
+/// Backend name of the tool clients call `exposed`, or `None` if a renamed
+/// tool is called by its backend name.
+fn backend_tool_name<'a>(transforms: &'a TransformConfig, exposed: &'a str) -> Option<&'a str> {
+    if let Some((tool, _)) = transforms.rename_tools.iter().find(|(_, name)| *name == exposed) {
+        return Some(tool);
+    }
+    (!transforms.rename_tools.contains_key(exposed)).then_some(exposed)
+}
+
+/// Replace the name of a tool definition with its exposed name.
+fn rename_tool(transforms: &TransformConfig, tool: &mut Value) {
+    let renamed = tool
+        .get("name")
+        .and_then(Value::as_str)
+        .and_then(|name| transforms.rename_tools.get(name));
+    if let (Some(renamed), Some(tool)) = (renamed.cloned(), tool.as_object_mut()) {
+        tool.insert("name".to_string(), Value::String(renamed));
+    }
+}
+
+/// Remove the field at the dotted `path` of `value`, if present.
+fn remove_path(value: &mut Value, path: &str) {
+    let (parents, field) = match path.rsplit_once('.') {
+        Some((parents, field)) => (Some(parents), field),
+        None => (None, path),
+    };
+    let parent = parents
+        .into_iter()
+        .flat_map(|parents| parents.split('.'))
+        .try_fold(value, |value, key| value.get_mut(key));
+    if let Some(parent) = parent.and_then(Value::as_object_mut) {
+        parent.remove(field);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn transforms() -> TransformConfig {
+        serde_yaml::from_str(
+            r#"
+            set_headers: {X-Team: search}
+            remove_headers: [x-debug]
+            rename_tools: {search: web_search}
+            default_arguments:
+              "search*": {limit: 10, safe: true}
+            strip_response_fields: [_meta, structuredContent.debug]
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn call(name: &str, arguments: Value) -> McpRequest {
+        McpRequest::new(
+            "tools/call",
+            json!({"name": name, "arguments": arguments}),
+            Some(json!(1)),
+        )
+    }
+
+    #[test]
+    fn test_tool_call_renamed_back_with_default_arguments() {
+        let request = transform_request(&transforms(), call("web_search", json!({"limit": 3})));
+        assert_eq!(
+            request.params(),
+            json!({"name": "search", "arguments": {"limit": 3, "safe": true}})
+        );
+
+        // The backend name of a renamed tool is not exposed
+        let request = transform_request(&transforms(), call("search", json!({})));
+        assert_eq!(request.params()["arguments"], json!({}));
+    }
+
+    #[test]
+    fn test_responses_renamed_and_stripped() {
+        let list = McpResponse::from_value(json!({
+            "jsonrpc": "2.0", "id": 1,
+            "result": {"tools": [{"name": "search"}, {"name": "fetch"}]}
+        }))
+        .unwrap();
+        let list = transform_response(&transforms(), "tools/list", list);
+        assert_eq!(
+            list.result.unwrap()["tools"],
+            json!([{"name": "web_search"}, {"name": "fetch"}])
+        );
+
+        let result = McpResponse::from_value(json!({
+            "jsonrpc": "2.0", "id": 1,
+            "result": {"content": [], "_meta": {}, "structuredContent": {"debug": 1, "hits": 2}}
+        }))
+        .unwrap();
+        let result = transform_response(&transforms(), "tools/call", result);
+        assert_eq!(
+            result.result.unwrap(),
+            json!({"content": [], "structuredContent": {"hits": 2}})
+        );
+    }
+
+    #[test]
+    fn test_header_rules() {
+        let headers = HashMap::from([
+            ("x-team".to_string(), "old".to_string()),
+            ("X-Debug".to_string(), "1".to_string()),
+            ("Accept".to_string(), "application/json".to_string()),
+        ]);
+        let headers = backend_headers(&transforms(), &headers);
+        assert_eq!(
+            headers,
+            HashMap::from([
+                ("X-Team".to_string(), "search".to_string()),
+                ("Accept".to_string(), "application/json".to_string()),
+            ])
+        );
+    }
+}
@@ -0,0 +1,189 @@
+//! Truncation of oversized tool results (`context_optimization.truncation`).
+//!
+//! A tools/call result whose content is larger than the byte or estimated
+//! token limit of its tool is cut down before it reaches the client. Text is
+//! shortened at a character boundary, images, audio and binary resources
+//! that no longer fit are dropped, and a text notice is appended so the model
+//! knows part of the result is missing. The result's `_meta.truncated`
+//! records how large the content was and how much of it was returned.
+
+use serde_json::{json, Value};
+
+use crate::metrics::tokens::estimate_tokens;
+
+/// Sizes of a truncated result's content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Truncated {
+    pub original_bytes: usize,
+    pub returned_bytes: usize,
+    /// Estimated tokens of the text that was removed
+    pub omitted_tokens: u64,
+}
+
+/// Cut the content of a tools/call `result` down to `max_bytes` and
+/// `max_tokens`, returning its sizes if anything was removed.
+pub fn truncate_result(
+    result: &mut Value,
+    max_bytes: usize,
+    max_tokens: Option<u64>,
+) -> Option<Truncated> {
+    let content = result.get_mut("content").and_then(Value::as_array_mut)?;
+    let sizes: Vec<(usize, u64)> = content.iter_mut().map(payload_size).collect();
+    let original_bytes = sizes.iter().map(|(bytes, _)| bytes).sum();
+    let original_tokens: u64 = sizes.iter().map(|(_, tokens)| tokens).sum();
+
+    // Find the first item that does not fit in what is left of the budgets
+    let (mut bytes_left, mut tokens_left) = (max_bytes, max_tokens.unwrap_or(u64::MAX));
+    let mut overflow = None;
+    for (index, &(bytes, tokens)) in sizes.iter().enumerate() {
+        if bytes > bytes_left || tokens > tokens_left {
+            overflow = Some(index);
+            break;
+        }
+        bytes_left -= bytes;
+        tokens_left -= tokens;
+    }
+    let overflow = overflow?;
+
+    // Keep as much of the overflowing item as still fits if it is text
+    let mut keep = overflow;
+    if let Some((text, true)) = payload(&mut content[overflow]) {
+        let cut = cut_point(text, bytes_left, tokens_left, sizes[overflow].1);
+        if cut > 0 {
+            text.truncate(cut);
+            keep += 1;
+        }
+    }
+    content.truncate(keep);
+
+    let sizes: Vec<(usize, u64)> = content.iter_mut().map(payload_size).collect();
+    let returned_bytes = sizes.iter().map(|(bytes, _)| bytes).sum();
+    let returned_tokens: u64 = sizes.iter().map(|(_, tokens)| tokens).sum();
+    content.push(json!({
+        "type": "text",
+        "text": format!(
+            "[Result truncated: {} of {} bytes omitted]",
+            original_bytes - returned_bytes,
+            original_bytes
+        ),
+    }));
+
+    let meta = result.as_object_mut()?.entry("_meta").or_insert_with(|| json!({}));
+    if let Some(meta) = meta.as_object_mut() {
+        meta.insert(
+            "truncated".into(),
+            json!({ "original_bytes": original_bytes, "returned_bytes": returned_bytes }),
+        );
+    }
+    Some(Truncated {
+        original_bytes,
+        returned_bytes,
+        omitted_tokens: original_tokens.saturating_sub(returned_tokens),
+    })
+}
+
+/// The string carrying a content item's payload, and whether it is text
+/// rather than base64 data. Embedded resources carry theirs in `resource`.
+fn payload(item: &mut Value) -> Option<(&mut String, bool)> {
+    let item = match item.get("resource") {
+        Some(_) => item.get_mut("resource")?,
+        None => item,
+    };
+    let fields = item.as_object_mut()?;
+    let field = ["text", "data", "blob"]
+        .into_iter()
+        .find(|field| fields.get(*field).is_some_and(Value::is_string))?;
+    match fields.get_mut(field) {
+        Some(Value::String(payload)) => Some((payload, field == "text")),
+        _ => None,
+    }
+}
+
+/// Bytes and estimated tokens of a content item's payload; only text counts
+/// toward the token limit.
+fn payload_size(item: &mut Value) -> (usize, u64) {
+    match payload(item) {
+        Some((text, true)) => (text.len(), estimate_tokens(text)),
+        Some((data, false)) => (data.len(), 0),
+        None => (0, 0),
+    }
+}
+
+/// Byte offset at which to cut `text`, whose estimated size is `tokens`, so
+/// it fits both budgets.
+fn cut_point(text: &str, bytes_left: usize, tokens_left: u64, tokens: u64) -> usize {
+    // Tokens are spread evenly enough through a text to scale its length
+    let by_tokens = text.len() as u128 * u128::from(tokens_left) / u128::from(tokens.max(1));
+    let mut cut = bytes_left.min(usize::try_from(by_tokens).unwrap_or(usize::MAX));
+    while !text.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    cut
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_results_untouched() {
+        let mut result = json!({"content": [{"type": "text", "text": "short"}]});
+        assert_eq!(truncate_result(&mut result, 100, Some(100)), None);
+        assert_eq!(
+            result,
+            json!({"content": [{"type": "text", "text": "short"}]})
+        );
+    }
+
+    #[test]
+    fn test_text_cut_and_later_items_dropped() {
+        let mut result = json!({
+            "content": [
+                {"type": "text", "text": "abcdef"},
+                {"type": "text", "text": "ghïjkl"},
+                {"type": "image", "data": "aGVsbG8=", "mimeType": "image/png"}
+            ],
+            "_meta": {"source": "backend"}
+        });
+        // "ï" is two bytes, so the second text is cut before it
+        let truncated = truncate_result(&mut result, 9, None).unwrap();
+        assert_eq!(
+            (truncated.original_bytes, truncated.returned_bytes),
+            (21, 8)
+        );
+        assert_eq!(result["content"][0]["text"], "abcdef");
+        assert_eq!(result["content"][1]["text"], "gh");
+        assert_eq!(
+            result["content"][2]["text"],
+            "[Result truncated: 13 of 21 bytes omitted]"
+        );
+        assert_eq!(result["content"].as_array().unwrap().len(), 3);
+        assert_eq!(
+            result["_meta"],
+            json!({"source": "backend", "truncated": {"original_bytes": 21, "returned_bytes": 8}})
+        );
+    }
+
+    #[test]
+    fn test_token_limit_and_binary_items() {
+        let text = "word ".repeat(1000);
+        let mut result = json!({"content": [{"type": "text", "text": text}]});
+        let truncated = truncate_result(&mut result, usize::MAX, Some(100)).unwrap();
+        let kept = result["content"][0]["text"].as_str().unwrap();
+        assert!(
+            estimate_tokens(kept) <= 110,
+            "kept {} tokens",
+            estimate_tokens(kept)
+        );
+        assert!(truncated.omitted_tokens > 0);
+
+        // Binary payloads are never cut, only dropped
+        let mut result = json!({"content": [
+            {"type": "resource", "resource": {"uri": "file:///a", "blob": "aGVsbG8gd29ybGQ="}}
+        ]});
+        let truncated = truncate_result(&mut result, 10, None).unwrap();
+        assert_eq!(truncated.returned_bytes, 0);
+        assert_eq!(result["content"].as_array().unwrap().len(), 1);
+        assert_eq!(result["content"][0]["type"], "text");
+    }
+}
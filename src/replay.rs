@@ -0,0 +1,292 @@
+//! Replay of recorded sessions, used by `only1mcp replay`.
+//!
+//! Re-sends every request of a recording (`observability.recording`) to a
+//! running proxy, in order and one at a time, and compares each reply with
+//! the recorded one. JSON-RPC ids are ignored, as are any fields the caller
+//! names as volatile, so a clean replay means the proxy and its backends
+//! still answer the same way under the current configuration.
+
+use crate::error::{Error, Result};
+use crate::proxy::recording::Exchange;
+use serde_json::Value;
+use std::fmt;
+use std::time::Duration;
+
+/// Where and how a recording is replayed.
+#[derive(Debug, Clone)]
+pub struct ReplayOptions {
+    /// JSON-RPC endpoint of the proxy (e.g. `http://127.0.0.1:8080/mcp`)
+    pub url: String,
+    /// API key sent as a bearer token to listeners that require one
+    pub token: Option<String>,
+    /// JSON pointers (e.g. `/result/content/0/text`) left out of comparisons
+    pub ignore: Vec<String>,
+    /// Per-request client timeout
+    pub timeout: Duration,
+}
+
+impl Default for ReplayOptions {
+    fn default() -> Self {
+        Self {
+            url: "http://127.0.0.1:8080/mcp".to_string(),
+            token: None,
+            ignore: Vec::new(),
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// How one replayed request compared with its recording.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    /// The reply matched the recorded one
+    Matched,
+    /// The reply first differs at `path` (a JSON pointer)
+    Differed {
+        path: String,
+        expected: Option<Value>,
+        actual: Option<Value>,
+    },
+    /// A notification was sent; there is no reply to compare
+    Sent,
+    /// The request could not be sent or its reply was not JSON
+    Failed(String),
+}
+
+/// Outcome of replaying one recorded exchange.
+#[derive(Debug, Clone)]
+pub struct ReplayedExchange {
+    /// Position in the recording, from 1
+    pub number: usize,
+    pub method: String,
+    pub outcome: Outcome,
+}
+
+/// Outcomes of a whole replay, in recorded order.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayReport {
+    pub exchanges: Vec<ReplayedExchange>,
+}
+
+impl ReplayReport {
+    fn count(&self, matches: impl Fn(&Outcome) -> bool) -> usize {
+        self.exchanges.iter().filter(|e| matches(&e.outcome)).count()
+    }
+
+    /// Whether every reply matched and every request could be sent.
+    pub fn is_clean(&self) -> bool {
+        self.count(|outcome| matches!(outcome, Outcome::Differed { .. } | Outcome::Failed(_))) == 0
+    }
+}
+
+impl fmt::Display for ReplayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for exchange in &self.exchanges {
+            let show = |value: &Option<Value>| match value {
+                Some(value) => value.to_string(),
+                None => "nothing".to_string(),
+            };
+            match &exchange.outcome {
+                Outcome::Differed {
+                    path,
+                    expected,
+                    actual,
+                } => writeln!(
+                    f,
+                    "#{} {} differs at {}: expected {}, got {}",
+                    exchange.number,
+                    exchange.method,
+                    if path.is_empty() { "/" } else { path },
+                    show(expected),
+                    show(actual)
+                )?,
+                Outcome::Failed(reason) => writeln!(
+                    f,
+                    "#{} {} failed: {}",
+                    exchange.number, exchange.method, reason
+                )?,
+                Outcome::Matched | Outcome::Sent => {},
+            }
+        }
+        write!(
+            f,
+            "Replayed {} requests: {} matched, {} differed, {} failed, {} notifications",
+            self.exchanges.len(),
+            self.count(|outcome| *outcome == Outcome::Matched),
+            self.count(|outcome| matches!(outcome, Outcome::Differed { .. })),
+            self.count(|outcome| matches!(outcome, Outcome::Failed(_))),
+            self.count(|outcome| *outcome == Outcome::Sent),
+        )
+    }
+}
+
+/// Re-send `exchanges` to the proxy at `options.url` and compare replies.
+pub async fn replay(exchanges: &[Exchange], options: &ReplayOptions) -> Result<ReplayReport> {
+    let client = reqwest::Client::builder()
+        .timeout(options.timeout)
+        .build()
+        .map_err(|e| Error::Transport(format!("Failed to build HTTP client: {}", e)))?;
+
+    let mut report = ReplayReport::default();
+    for (index, exchange) in exchanges.iter().enumerate() {
+        let outcome = match send(&client, options, &exchange.request).await {
+            Err(reason) => Outcome::Failed(reason),
+            Ok(_) if exchange.response.is_none() => Outcome::Sent,
+            Ok(reply) => compare(exchange.response.as_ref(), reply.as_ref(), &options.ignore),
+        };
+        report.exchanges.push(ReplayedExchange {
+            number: index + 1,
+            method: exchange.method.clone(),
+            outcome,
+        });
+    }
+    Ok(report)
+}
+
+/// Send one request, returning its JSON reply (`None` for an empty body).
+async fn send(
+    client: &reqwest::Client,
+    options: &ReplayOptions,
+    request: &Value,
+) -> std::result::Result<Option<Value>, String> {
+    let mut builder = client.post(&options.url).json(request);
+    if let Some(token) = &options.token {
+        builder = builder.bearer_auth(token);
+    }
+    let body = builder
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .bytes()
+        .await
+        .map_err(|e| e.to_string())?;
+    if body.is_empty() {
+        return Ok(None);
+    }
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| format!("reply is not JSON: {}", e))
+}
+
+/// Compare a reply with the recorded one, ignoring JSON-RPC framing and the
+/// `ignore` pointers.
+fn compare(expected: Option<&Value>, actual: Option<&Value>, ignore: &[String]) -> Outcome {
+    let normalize = |value: Option<&Value>| {
+        value.cloned().map(|mut value| {
+            if let Some(object) = value.as_object_mut() {
+                object.remove("id");
+                object.remove("jsonrpc");
+            }
+            for pointer in ignore {
+                remove_pointer(&mut value, pointer);
+            }
+            value
+        })
+    };
+    let (expected, actual) = (normalize(expected), normalize(actual));
+    match first_difference(expected.as_ref(), actual.as_ref(), String::new()) {
+        None => Outcome::Matched,
+        Some((path, expected, actual)) => Outcome::Differed {
+            path,
+            expected,
+            actual,
+        },
+    }
+}
+
+type Difference = (String, Option<Value>, Option<Value>);
+
+/// JSON pointer and values of the first place `expected` and `actual`
+/// differ, visiting object keys in sorted order.
+fn first_difference(
+    expected: Option<&Value>,
+    actual: Option<&Value>,
+    path: String,
+) -> Option<Difference> {
+    match (expected, actual) {
+        (Some(Value::Object(expected)), Some(Value::Object(actual))) => {
+            let mut keys: Vec<&String> = expected.keys().chain(actual.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            keys.into_iter().find_map(|key| {
+                let path = format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+                first_difference(expected.get(key), actual.get(key), path)
+            })
+        },
+        (Some(Value::Array(expected)), Some(Value::Array(actual))) => {
+            (0..expected.len().max(actual.len())).find_map(|index| {
+                let path = format!("{}/{}", path, index);
+                first_difference(expected.get(index), actual.get(index), path)
+            })
+        },
+        (expected, actual) if expected == actual => None,
+        (expected, actual) => Some((path, expected.cloned(), actual.cloned())),
+    }
+}
+
+/// Remove the value at a JSON `pointer`, if there is one.
+fn remove_pointer(value: &mut Value, pointer: &str) {
+    let Some((parent, last)) = pointer.rsplit_once('/') else {
+        return;
+    };
+    let last = last.replace("~1", "/").replace("~0", "~");
+    match value.pointer_mut(parent) {
+        Some(Value::Object(object)) => {
+            object.remove(&last);
+        },
+        Some(Value::Array(items)) => {
+            if let Ok(index) = last.parse::<usize>() {
+                if index < items.len() {
+                    items.remove(index);
+                }
+            }
+        },
+        _ => {},
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_compare_reports_first_difference() {
+        let recorded = json!({"jsonrpc": "2.0", "id": 1, "result": {"content": [
+            {"type": "text", "text": "3 issues"},
+            {"type": "text", "text": "at 09:30"}
+        ]}});
+        let replayed = json!({"jsonrpc": "2.0", "id": 7, "result": {"content": [
+            {"type": "text", "text": "4 issues"},
+            {"type": "text", "text": "at 10:15"}
+        ]}});
+
+        assert_eq!(
+            compare(Some(&recorded), Some(&recorded.clone()), &[]),
+            Outcome::Matched
+        );
+        assert_eq!(
+            compare(Some(&recorded), Some(&replayed), &[]),
+            Outcome::Differed {
+                path: "/result/content/0/text".to_string(),
+                expected: Some(json!("3 issues")),
+                actual: Some(json!("4 issues")),
+            }
+        );
+
+        // Ignored fields do not count as differences
+        let ignore = ["/result/content/1".to_string()];
+        let replayed = json!({"id": 2, "result": {"content": [
+            {"type": "text", "text": "3 issues"},
+            {"type": "text", "text": "at 10:15"}
+        ]}});
+        assert_eq!(
+            compare(Some(&recorded), Some(&replayed), &ignore),
+            Outcome::Matched
+        );
+        assert!(matches!(
+            compare(Some(&recorded), None, &[]),
+            Outcome::Differed { actual: None, .. }
+        ));
+    }
+}
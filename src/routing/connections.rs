@@ -0,0 +1,98 @@
+//! Live per-backend in-flight request tracking.
+//!
+//! Least-connections routing needs to know how busy each backend is right
+//! now. Every request sent to a backend holds a [`ConnectionGuard`] for as
+//! long as it is outstanding, so the counts stay accurate across retries,
+//! errors and cancelled requests.
+
+use crate::types::ServerId;
+use dashmap::DashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// In-flight request counts per backend, shared by all requests.
+#[derive(Debug, Default)]
+pub struct ConnectionTracker {
+    counts: DashMap<ServerId, AtomicUsize>,
+}
+
+impl ConnectionTracker {
+    /// Create a tracker with no requests in flight.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark a request to `server_id` as in flight until the guard is dropped.
+    pub fn acquire(self: &Arc<Self>, server_id: &str) -> ConnectionGuard {
+        let count = self
+            .counts
+            .entry(server_id.to_string())
+            .or_insert_with(|| AtomicUsize::new(0))
+            .fetch_add(1, Ordering::Relaxed)
+            + 1;
+        Self::record(server_id, count);
+
+        ConnectionGuard {
+            tracker: self.clone(),
+            server_id: server_id.to_string(),
+        }
+    }
+
+    /// Requests currently in flight to `server_id`.
+    pub fn in_flight(&self, server_id: &str) -> usize {
+        self.counts
+            .get(server_id)
+            .map(|count| count.load(Ordering::Relaxed))
+            .unwrap_or(0)
+    }
+
+    fn release(&self, server_id: &str) {
+        if let Some(count) = self.counts.get(server_id) {
+            let previous = count
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+                .unwrap_or(0);
+            Self::record(server_id, previous.saturating_sub(1));
+        }
+    }
+
+    fn record(server_id: &str, count: usize) {
+        crate::metrics::BACKEND_IN_FLIGHT_REQUESTS
+            .with_label_values(&[server_id])
+            .set(count as i64);
+    }
+}
+
+/// Marks one request to a backend as in flight until dropped.
+#[derive(Debug)]
+pub struct ConnectionGuard {
+    tracker: Arc<ConnectionTracker>,
+    server_id: ServerId,
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.tracker.release(&self.server_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guards_track_in_flight_requests() {
+        let tracker = Arc::new(ConnectionTracker::new());
+        assert_eq!(tracker.in_flight("server1"), 0);
+
+        let first = tracker.acquire("server1");
+        let second = tracker.acquire("server1");
+        let _other = tracker.acquire("server2");
+        assert_eq!(tracker.in_flight("server1"), 2);
+        assert_eq!(tracker.in_flight("server2"), 1);
+
+        drop(first);
+        assert_eq!(tracker.in_flight("server1"), 1);
+        drop(second);
+        assert_eq!(tracker.in_flight("server1"), 0);
+    }
+}
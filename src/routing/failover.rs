@@ -0,0 +1,142 @@
+//! Failover groups: a primary backend with warm standbys.
+//!
+//! Each group in `proxy.routing.failover_groups` names a primary server and
+//! standbys exposing the same tools. Standbys stay connected and
+//! health-checked but receive no traffic while the primary is available.
+//! Once the primary turns unhealthy or its circuit opens, the first
+//! available standby takes over, and traffic shifts back as soon as the
+//! primary recovers. Every shift is logged and counted in
+//! `only1mcp_failover_events_total`.
+
+use crate::config::FailoverGroupConfig;
+use crate::types::ServerId;
+use dashmap::DashMap;
+use tracing::{info, warn};
+
+/// Server currently serving each failover group, shared by all requests.
+#[derive(Debug, Default)]
+pub struct FailoverState {
+    active: DashMap<String, ServerId>,
+}
+
+impl FailoverState {
+    /// Create a state in which every group is served by its primary.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drop from `available` the group members that must not take traffic.
+    ///
+    /// `eligible` are the servers offering the requested tool, `available`
+    /// those of them that are healthy. Groups whose primary does not offer
+    /// the tool are left alone.
+    pub fn filter(
+        &self,
+        groups: &[FailoverGroupConfig],
+        eligible: &[ServerId],
+        mut available: Vec<ServerId>,
+    ) -> Vec<ServerId> {
+        for group in groups.iter().filter(|group| eligible.contains(&group.primary)) {
+            let Some(active) = group.members().find(|id| available.contains(id)).cloned() else {
+                continue;
+            };
+            self.activate(group, &active);
+            available.retain(|id| *id == active || !group.members().any(|member| member == id));
+        }
+        available
+    }
+
+    /// Server serving `group`, if any request was routed to it yet.
+    pub fn active(&self, group: &str) -> Option<ServerId> {
+        self.active.get(group).map(|active| active.clone())
+    }
+
+    /// Record that `server_id` serves `group`, logging a shift.
+    fn activate(&self, group: &FailoverGroupConfig, server_id: &ServerId) {
+        let previous = self.active.insert(group.name.clone(), server_id.clone());
+        let previous = previous.unwrap_or_else(|| group.primary.clone());
+        if previous == *server_id {
+            return;
+        }
+
+        let kind = if *server_id == group.primary {
+            info!(
+                "Failover group {}: primary {} recovered, traffic moved back from {}",
+                group.name, server_id, previous
+            );
+            "failback"
+        } else {
+            warn!(
+                "Failover group {}: traffic moved from {} to standby {}",
+                group.name, previous, server_id
+            );
+            "failover"
+        };
+        crate::metrics::FAILOVER_EVENTS_TOTAL
+            .with_label_values(&[&group.name, kind])
+            .inc();
+        crate::metrics::FAILOVER_ON_STANDBY
+            .with_label_values(&[&group.name])
+            .set(i64::from(*server_id != group.primary));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[&str]) -> Vec<ServerId> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    fn group() -> FailoverGroupConfig {
+        FailoverGroupConfig {
+            name: "search".into(),
+            primary: "primary".into(),
+            standbys: ids(&["standby-a", "standby-b"]),
+        }
+    }
+
+    #[test]
+    fn test_standbys_idle_while_primary_available() {
+        let state = FailoverState::new();
+        let eligible = ids(&["primary", "standby-a", "standby-b", "other"]);
+
+        let routed = state.filter(&[group()], &eligible, eligible.clone());
+        assert_eq!(routed, ids(&["primary", "other"]));
+        assert_eq!(state.active("search"), Some("primary".to_string()));
+    }
+
+    #[test]
+    fn test_traffic_shifts_to_first_standby_and_back() {
+        let state = FailoverState::new();
+        let groups = [group()];
+        let eligible = ids(&["primary", "standby-a", "standby-b"]);
+        let failovers = || {
+            crate::metrics::FAILOVER_EVENTS_TOTAL
+                .with_label_values(&["search", "failover"])
+                .get()
+        };
+        let before = failovers();
+
+        let routed = state.filter(&groups, &eligible, ids(&["standby-a", "standby-b"]));
+        assert_eq!(routed, ids(&["standby-a"]));
+        let routed = state.filter(&groups, &eligible, ids(&["standby-b"]));
+        assert_eq!(routed, ids(&["standby-b"]));
+        assert_eq!(failovers(), before + 2.0);
+
+        let routed = state.filter(&groups, &eligible, eligible.clone());
+        assert_eq!(routed, ids(&["primary"]));
+        assert_eq!(state.active("search"), Some("primary".to_string()));
+    }
+
+    #[test]
+    fn test_groups_apply_only_to_tools_of_their_primary() {
+        let state = FailoverState::new();
+
+        // Only the standby offers this tool
+        let routed = state.filter(&[group()], &ids(&["standby-a"]), ids(&["standby-a"]));
+        assert_eq!(routed, ids(&["standby-a"]));
+        assert_eq!(state.active("search"), None);
+    }
+}
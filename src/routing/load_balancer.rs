@@ -7,6 +7,10 @@
 //! - Random for simplicity
 //! - Weighted random for capacity-aware routing
 //! - Health-aware routing with automatic failover
+//!
+//! Every algorithm honors per-server weights, and only servers in the
+//! highest priority tier that is healthy are considered, so lower tiers act
+//! as fallbacks.
 
 use crate::error::Result;
 use crate::types::ServerId;
@@ -21,15 +25,19 @@ use std::time::Duration;
 use tracing::{debug, info, instrument, warn};
 use xxhash_rust::xxh3::Xxh3;
 
+/// Priority of servers that don't declare one
+pub const DEFAULT_PRIORITY: u32 = 100;
+
 /// Routing algorithm selection
-#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum RoutingAlgorithm {
     /// Round-robin distribution
+    #[default]
     RoundRobin,
     /// Least connections using Power of Two Choices
     LeastConnections,
-    /// Random server selection
+    /// Random server selection, proportional to weight
     Random,
     /// Weighted random based on server capacity
     WeightedRandom,
@@ -37,12 +45,6 @@ pub enum RoutingAlgorithm {
     ConsistentHash,
 }
 
-impl Default for RoutingAlgorithm {
-    fn default() -> Self {
-        Self::RoundRobin
-    }
-}
-
 /// Routing configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct RoutingConfig {
@@ -190,6 +192,10 @@ pub struct LoadBalancer {
     health_states: Arc<DashMap<ServerId, HealthState>>,
     /// Per-server connection counts
     connection_counts: Arc<DashMap<ServerId, AtomicUsize>>,
+    /// Per-server weights (absent = 1)
+    weights: Arc<DashMap<ServerId, u32>>,
+    /// Per-server priorities (absent = `DEFAULT_PRIORITY`)
+    priorities: Arc<DashMap<ServerId, u32>>,
     /// Round-robin counter
     round_robin_counter: AtomicUsize,
     /// Session affinity map
@@ -206,6 +212,8 @@ impl LoadBalancer {
             hash_ring: Arc::new(ArcSwap::new(Arc::new(hash_ring))),
             health_states: Arc::new(DashMap::new()),
             connection_counts: Arc::new(DashMap::new()),
+            weights: Arc::new(DashMap::new()),
+            priorities: Arc::new(DashMap::new()),
             round_robin_counter: AtomicUsize::new(0),
             session_map: Arc::new(DashMap::new()),
         }
@@ -253,11 +261,15 @@ impl LoadBalancer {
         if healthy_servers.is_empty() {
             warn!("All backends unhealthy for key: {}", key);
             // Fall back to all servers if none are healthy
-            return self.route(eligible_servers, key).await;
+            let tier = highest_priority_tier(eligible_servers, |id| self.priority(id));
+            return self.route(&tier, key).await;
         }
 
+        // Prefer the highest priority tier that still has healthy servers
+        let tier = highest_priority_tier(&healthy_servers, |id| self.priority(id));
+
         // Apply routing algorithm
-        let selected = self.route(&healthy_servers, key).await?;
+        let selected = self.route(&tier, key).await?;
 
         // Store sticky session
         if self.config.sticky_sessions {
@@ -282,11 +294,22 @@ impl LoadBalancer {
             RoutingAlgorithm::ConsistentHash => self.route_consistent_hash(key, servers),
             RoutingAlgorithm::LeastConnections => self.route_least_connections(servers),
             RoutingAlgorithm::RoundRobin => self.route_round_robin(servers),
-            RoutingAlgorithm::Random => self.route_random(servers),
-            RoutingAlgorithm::WeightedRandom => self.route_weighted_random(servers),
+            RoutingAlgorithm::Random | RoutingAlgorithm::WeightedRandom => {
+                self.route_weighted_random(servers)
+            },
         }
     }
 
+    /// Weight of a server (1 unless configured)
+    fn weight(&self, server_id: &ServerId) -> u32 {
+        self.weights.get(server_id).map(|w| *w).unwrap_or(1)
+    }
+
+    /// Priority of a server (`DEFAULT_PRIORITY` unless configured)
+    fn priority(&self, server_id: &ServerId) -> u32 {
+        self.priorities.get(server_id).map(|p| *p).unwrap_or(DEFAULT_PRIORITY)
+    }
+
     /// Consistent hashing with virtual nodes
     fn route_consistent_hash(&self, key: &str, servers: &[ServerId]) -> Result<ServerId> {
         let hash_ring = self.hash_ring.load();
@@ -317,14 +340,20 @@ impl LoadBalancer {
         let mut rng = rand::thread_rng();
         let candidates: Vec<_> = servers.choose_multiple(&mut rng, 2.min(servers.len())).collect();
 
-        // Select server with minimum connections
+        // Select server with minimum connections per unit of weight
+        let connections = |id: &ServerId| {
+            self.connection_counts
+                .get(id)
+                .map(|count| count.load(Ordering::Relaxed))
+                .unwrap_or(0)
+        };
         let selected = candidates
             .into_iter()
-            .min_by_key(|&id| {
-                self.connection_counts
-                    .get(id)
-                    .map(|count| count.load(Ordering::Relaxed))
-                    .unwrap_or(0)
+            .min_by(|&a, &b| {
+                compare_weighted_load(
+                    (connections(a), self.weight(a)),
+                    (connections(b), self.weight(b)),
+                )
             })
             .ok_or_else(|| crate::error::Error::NoBackendAvailable("".to_string()))?;
 
@@ -332,71 +361,66 @@ impl LoadBalancer {
         Ok(selected.clone())
     }
 
-    /// Round-robin server selection
+    /// Weighted round-robin server selection
     fn route_round_robin(&self, servers: &[ServerId]) -> Result<ServerId> {
         if servers.is_empty() {
             return Err(crate::error::Error::NoBackendAvailable("".to_string()));
         }
 
-        let index = self.round_robin_counter.fetch_add(1, Ordering::Relaxed);
-        let selected = &servers[index % servers.len()];
+        let weights: Vec<u32> = servers.iter().map(|id| self.weight(id)).collect();
+        let turn = self.round_robin_counter.fetch_add(1, Ordering::Relaxed);
+        let selected = &servers[weighted_round_robin_index(&weights, turn)];
 
         debug!("Round-robin selected: {}", selected);
         Ok(selected.clone())
     }
 
-    /// Random server selection
-    fn route_random(&self, servers: &[ServerId]) -> Result<ServerId> {
-        use rand::seq::SliceRandom;
-
-        let mut rng = rand::thread_rng();
-        servers
-            .choose(&mut rng)
-            .cloned()
-            .ok_or_else(|| crate::error::Error::NoBackendAvailable("".to_string()))
-    }
-
     /// Weighted random server selection
-    /// Note: Weights should be provided by ServerInfo in the registry.
-    /// For now, this implementation uses equal weights (default to 1).
-    /// Full weight-based selection is handled at the RequestRouter level.
     fn route_weighted_random(&self, servers: &[ServerId]) -> Result<ServerId> {
-        use rand::Rng;
-
-        if servers.is_empty() {
-            return Err(crate::error::Error::NoBackendAvailable("".to_string()));
-        }
+        use rand::distributions::{Distribution, WeightedIndex};
 
-        // For now, use equal weights since HealthState doesn't store weights
-        // In a full implementation, weights would come from ServerInfo via the registry
-        let default_weight = 1u32;
-        let total_weight = servers.len() as u32 * default_weight;
+        let weights: Vec<u32> = servers.iter().map(|id| self.weight(id)).collect();
+        let dist = WeightedIndex::new(&weights)
+            .map_err(|_| crate::error::Error::NoBackendAvailable("".to_string()))?;
 
-        // Generate random number in range [0, total_weight)
-        let mut rng = rand::thread_rng();
-        let random_weight = rng.gen_range(0..total_weight);
+        let index = dist.sample(&mut rand::thread_rng());
+        let server_id = &servers[index];
 
-        // Select server based on weighted probability
-        let selected_index = (random_weight / default_weight) as usize;
-        let server_id = &servers[selected_index];
-
-        debug!("Weighted random selected: {} (equal weights)", server_id);
+        debug!(
+            "Weighted random selected: {} (weight: {})",
+            server_id, weights[index]
+        );
         Ok(server_id.clone())
     }
 
     /// Add a server to the load balancer
     pub fn add_server(&self, server_id: &ServerId) {
+        self.add_weighted_server(server_id, 1, DEFAULT_PRIORITY);
+    }
+
+    /// Add a server with a weight and priority.
+    ///
+    /// Higher weights receive proportionally more traffic; servers with a
+    /// lower priority only receive traffic while no higher-priority server
+    /// is healthy.
+    pub fn add_weighted_server(&self, server_id: &ServerId, weight: u32, priority: u32) {
         // Initialize health state
         self.health_states.entry(server_id.clone()).or_default();
+        self.weights.insert(server_id.clone(), weight.max(1));
+        self.priorities.insert(server_id.clone(), priority);
 
         // Add to consistent hash ring if using that algorithm
         if self.config.algorithm == RoutingAlgorithm::ConsistentHash {
             let mut ring = ConsistentHashRing::clone(&*self.hash_ring.load());
-            ring.add_server(server_id);
+            ring.remove_server(server_id);
+            ring.add_weighted_server(server_id, weight);
             self.hash_ring.store(Arc::new(ring));
         }
 
-        info!("Added server to load balancer: {}", server_id);
+        info!(
+            "Added server to load balancer: {} (weight: {}, priority: {})",
+            server_id, weight, priority
+        );
     }
 
     /// Remove a server from the load balancer
@@ -404,8 +428,10 @@ impl LoadBalancer {
         // Remove health state
         self.health_states.remove(server_id);
 
-        // Remove connection count
+        // Remove connection count and routing policy
         self.connection_counts.remove(server_id);
+        self.weights.remove(server_id);
+        self.priorities.remove(server_id);
 
         // Remove from consistent hash ring
         if self.config.algorithm == RoutingAlgorithm::ConsistentHash {
@@ -479,6 +505,42 @@ pub struct ServerStats {
     pub avg_latency: Duration,
 }
 
+/// Servers sharing the highest priority among `servers`, in their original
+/// order.
+pub fn highest_priority_tier(
+    servers: &[ServerId],
+    priority: impl Fn(&ServerId) -> u32,
+) -> Vec<ServerId> {
+    let Some(top) = servers.iter().map(&priority).max() else {
+        return Vec::new();
+    };
+    servers.iter().filter(|id| priority(id) == top).cloned().collect()
+}
+
+/// Index picked on the `turn`th round of a weighted round-robin over
+/// `weights`: each index is picked `weight` times per cycle. Zero weights
+/// count as 1.
+pub fn weighted_round_robin_index(weights: &[u32], turn: usize) -> usize {
+    let total: u64 = weights.iter().map(|&w| u64::from(w.max(1))).sum();
+    let mut position = turn as u64 % total.max(1);
+    for (index, &weight) in weights.iter().enumerate() {
+        let weight = u64::from(weight.max(1));
+        if position < weight {
+            return index;
+        }
+        position -= weight;
+    }
+    0
+}
+
+/// Order two `(connections, weight)` loads by connections per unit of
+/// weight; on a tie, the heavier server sorts first.
+pub fn compare_weighted_load(a: (usize, u32), b: (usize, u32)) -> std::cmp::Ordering {
+    let (a_conns, a_weight) = (a.0 as u64, u64::from(a.1.max(1)));
+    let (b_conns, b_weight) = (b.0 as u64, u64::from(b.1.max(1)));
+    (a_conns * b_weight).cmp(&(b_conns * a_weight)).then(b_weight.cmp(&a_weight))
+}
+
 /// Consistent hash ring with virtual nodes
 #[derive(Clone)]
 pub struct ConsistentHashRing {
@@ -499,7 +561,14 @@ impl ConsistentHashRing {
 
     /// Add a server to the hash ring
     pub fn add_server(&mut self, server_id: &ServerId) {
-        for vnode in 0..self.virtual_nodes {
+        self.add_weighted_server(server_id, 1);
+    }
+
+    /// Add a server with `weight` times the usual number of virtual nodes,
+    /// so it owns a proportionally larger share of the key space.
+    pub fn add_weighted_server(&mut self, server_id: &ServerId, weight: u32) {
+        let vnodes = self.virtual_nodes.saturating_mul(weight.max(1));
+        for vnode in 0..vnodes {
             let key = format!("{}:{}", server_id, vnode);
             let hash = xxhash_rust::xxh3::xxh3_64(key.as_bytes());
             self.ring.insert(hash, (server_id.clone(), vnode));
@@ -507,7 +576,7 @@ impl ConsistentHashRing {
 
         debug!(
             "Added {} to hash ring with {} virtual nodes",
-            server_id, self.virtual_nodes
+            server_id, vnodes
         );
     }
 
@@ -516,6 +585,11 @@ impl ConsistentHashRing {
         self.add_server(&node);
     }
 
+    /// Whether a server has any virtual nodes in the ring
+    pub fn contains(&self, server_id: &ServerId) -> bool {
+        self.ring.values().any(|(id, _)| id == server_id)
+    }
+
     /// Remove a server from the hash ring
     pub fn remove_server(&mut self, server_id: &ServerId) {
         self.ring.retain(|_, (id, _)| id != server_id);
@@ -695,4 +769,82 @@ mod tests {
         assert_eq!(s1, "server2");
         assert_eq!(s2, "server2");
     }
+
+    #[tokio::test]
+    async fn test_weighted_round_robin() {
+        let config = RoutingConfig {
+            algorithm: RoutingAlgorithm::RoundRobin,
+            virtual_nodes: 150,
+            hash_key: HashKey::ToolName,
+            sticky_sessions: false,
+            session_ttl: 3600,
+        };
+
+        let lb = LoadBalancer::new(config);
+        lb.add_weighted_server(&"heavy".to_string(), 3, DEFAULT_PRIORITY);
+        lb.add_weighted_server(&"light".to_string(), 1, DEFAULT_PRIORITY);
+        let servers = vec!["heavy".to_string(), "light".to_string()];
+
+        let mut heavy = 0;
+        for i in 0..8 {
+            let selected = lb.select_server(&format!("key{}", i), &servers, None).await.unwrap();
+            if selected == "heavy" {
+                heavy += 1;
+            }
+        }
+
+        assert_eq!(heavy, 6);
+    }
+
+    #[tokio::test]
+    async fn test_priority_tiers_with_fallback() {
+        let config = RoutingConfig {
+            algorithm: RoutingAlgorithm::LeastConnections,
+            virtual_nodes: 150,
+            hash_key: HashKey::ToolName,
+            sticky_sessions: false,
+            session_ttl: 3600,
+        };
+
+        let lb = LoadBalancer::new(config);
+        lb.add_weighted_server(&"primary".to_string(), 1, 200);
+        lb.add_weighted_server(&"backup".to_string(), 1, 100);
+        let servers = vec!["primary".to_string(), "backup".to_string()];
+
+        for _ in 0..5 {
+            assert_eq!(
+                lb.select_server("key", &servers, None).await.unwrap(),
+                "primary"
+            );
+        }
+
+        // Once the primary tier is unhealthy, traffic moves to the next tier
+        for _ in 0..3 {
+            lb.update_health(&"primary".to_string(), false, Duration::ZERO);
+        }
+        assert_eq!(
+            lb.select_server("key", &servers, None).await.unwrap(),
+            "backup"
+        );
+    }
+
+    #[test]
+    fn test_weighted_helpers() {
+        assert_eq!(
+            (0..6)
+                .map(|turn| weighted_round_robin_index(&[2, 1, 0], turn))
+                .collect::<Vec<_>>(),
+            vec![0, 0, 1, 2, 0, 0]
+        );
+
+        // 4 connections on weight 2 is lighter than 3 on weight 1
+        assert_eq!(
+            compare_weighted_load((4, 2), (3, 1)),
+            std::cmp::Ordering::Less
+        );
+
+        let servers = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let tier = highest_priority_tier(&servers, |id| if id == "a" { 1 } else { 5 });
+        assert_eq!(tier, vec!["b".to_string(), "c".to_string()]);
+    }
 }
@@ -1,8 +1,17 @@
 //! Advanced routing algorithms
 
+pub mod connections;
+pub mod failover;
 pub mod load_balancer;
+pub mod split;
+pub mod sticky;
+pub mod tool_matcher;
 
 // Re-export commonly used types
+pub use connections::{ConnectionGuard, ConnectionTracker};
+pub use failover::FailoverState;
 pub use load_balancer::{
     ConsistentHashRing, LoadBalancer, RoutingAlgorithm, RoutingConfig, ServerStats,
 };
+pub use sticky::SessionTable;
+pub use tool_matcher::ToolMatcher;
@@ -0,0 +1,113 @@
+//! Traffic splitting between versions of one logical service.
+//!
+//! Each entry of `proxy.routing.traffic_splits` lists servers running
+//! different versions of the same service, such as a stable release taking
+//! 95% of the traffic and a canary taking 5%. Requests for the service go to
+//! one variant picked by weight, and every call is counted and timed per
+//! variant in `only1mcp_traffic_split_requests_total` and
+//! `only1mcp_traffic_split_request_duration_seconds` so the versions can be
+//! compared.
+
+use std::time::Duration;
+
+use rand::distributions::{Distribution, WeightedIndex};
+use tracing::debug;
+
+use crate::config::TrafficSplitConfig;
+use crate::types::ServerId;
+
+/// Keep one variant of each split in `available`, picked at random in
+/// proportion to the weights of the variants that are available.
+pub fn pick_variants(splits: &[TrafficSplitConfig], mut available: Vec<ServerId>) -> Vec<ServerId> {
+    for split in splits {
+        let candidates: Vec<(&ServerId, u32)> = available
+            .iter()
+            .filter_map(|id| split.weight_of(id).map(|weight| (id, weight)))
+            .collect();
+        // With every available variant at weight 0 they share equally
+        let Ok(dist) = WeightedIndex::new(candidates.iter().map(|(_, weight)| *weight))
+            .or_else(|_| WeightedIndex::new(candidates.iter().map(|_| 1)))
+        else {
+            continue;
+        };
+        let chosen = candidates[dist.sample(&mut rand::thread_rng())].0.clone();
+        debug!("Traffic split {} picked variant {}", split.service, chosen);
+        available.retain(|id| *id == chosen || split.weight_of(id).is_none());
+    }
+    available
+}
+
+/// Count a request to `server_id` and record its latency if the server is a
+/// variant of a split.
+pub fn record_outcome(
+    splits: &[TrafficSplitConfig],
+    server_id: &str,
+    success: bool,
+    latency: Duration,
+) {
+    let Some(split) = splits.iter().find(|split| split.weight_of(server_id).is_some()) else {
+        return;
+    };
+    let outcome = if success { "success" } else { "error" };
+    crate::metrics::TRAFFIC_SPLIT_REQUESTS_TOTAL
+        .with_label_values(&[&split.service, server_id, outcome])
+        .inc();
+    crate::metrics::TRAFFIC_SPLIT_REQUEST_DURATION_SECONDS
+        .with_label_values(&[&split.service, server_id])
+        .observe(latency.as_secs_f64());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SplitVariantConfig;
+
+    fn ids(ids: &[&str]) -> Vec<ServerId> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    fn split(stable: u32, canary: u32) -> TrafficSplitConfig {
+        TrafficSplitConfig {
+            service: "search".into(),
+            variants: vec![
+                SplitVariantConfig {
+                    server: "stable".into(),
+                    weight: stable,
+                },
+                SplitVariantConfig {
+                    server: "canary".into(),
+                    weight: canary,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_one_variant_kept_alongside_other_servers() {
+        let available = ids(&["stable", "canary", "other"]);
+        for _ in 0..20 {
+            let routed = pick_variants(&[split(95, 5)], available.clone());
+            assert_eq!(routed.len(), 2);
+            assert!(routed.contains(&"other".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_variants_picked_by_weight() {
+        let splits = [split(90, 10)];
+        let available = ids(&["stable", "canary"]);
+        let canary = (0..2000)
+            .filter(|_| pick_variants(&splits, available.clone()) == ids(&["canary"]))
+            .count();
+        assert!(
+            (100..=300).contains(&canary),
+            "canary picked {} times",
+            canary
+        );
+
+        // A variant without weight only serves while the others cannot
+        let splits = [split(1, 0)];
+        assert_eq!(pick_variants(&splits, available.clone()), ids(&["stable"]));
+        assert_eq!(pick_variants(&splits, ids(&["canary"])), ids(&["canary"]));
+    }
+}
@@ -0,0 +1,235 @@
+//! Sticky sessions: pin a client to the backend that first served it.
+//!
+//! Stateful MCP tools keep per-session state on the backend, so a client
+//! whose calls are spread across replicas loses that state. When
+//! `proxy.routing.sticky_sessions` is enabled, the first backend chosen for
+//! a client is remembered and reused until either:
+//!
+//! - the pin has not been used for `session_ttl_seconds`, or
+//! - the pinned backend stops being eligible (unhealthy, circuit open, or no
+//!   longer serving the tool), in which case the client is rebalanced onto a
+//!   freshly selected backend.
+//!
+//! Clients are identified by the configured header (`X-Client-Id` by
+//! default) or, failing that, by the API key they authenticated with.
+
+use crate::types::ServerId;
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// A client's pinned backend
+#[derive(Debug, Clone)]
+struct Pin {
+    server_id: ServerId,
+    last_used: Instant,
+}
+
+/// A pin as exchanged between cluster instances, aged relative to the
+/// sender's clock so the instances' clocks need not agree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionEntry {
+    pub client: String,
+    pub server_id: ServerId,
+    /// Milliseconds since the pin was last used
+    pub idle_ms: u64,
+}
+
+/// Client-to-backend session table shared by all requests.
+#[derive(Debug, Default)]
+pub struct SessionTable {
+    pins: DashMap<String, Pin>,
+}
+
+impl SessionTable {
+    /// Create an empty session table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Backend pinned for `client`, if the pin is younger than `ttl` and the
+    /// backend is still `eligible`. Expired or ineligible pins are dropped.
+    pub fn lookup(
+        &self,
+        client: &str,
+        ttl: Duration,
+        eligible: impl Fn(&ServerId) -> bool,
+    ) -> Option<ServerId> {
+        let mut pin = self.pins.get_mut(client)?;
+
+        let outcome = if pin.last_used.elapsed() >= ttl {
+            "expired"
+        } else if !eligible(&pin.server_id) {
+            "rebalanced"
+        } else {
+            pin.last_used = Instant::now();
+            crate::metrics::STICKY_SESSION_LOOKUPS_TOTAL.with_label_values(&["hit"]).inc();
+            return Some(pin.server_id.clone());
+        };
+
+        debug!(
+            "Dropping sticky session {} -> {} ({})",
+            client, pin.server_id, outcome
+        );
+        drop(pin);
+        self.pins.remove(client);
+        crate::metrics::STICKY_SESSION_LOOKUPS_TOTAL.with_label_values(&[outcome]).inc();
+        self.update_gauge();
+        None
+    }
+
+    /// Pin `client` to `server_id`.
+    pub fn pin(&self, client: &str, server_id: &ServerId) {
+        self.pins.insert(
+            client.to_string(),
+            Pin {
+                server_id: server_id.clone(),
+                last_used: Instant::now(),
+            },
+        );
+        debug!("Pinned sticky session {} -> {}", client, server_id);
+        self.update_gauge();
+    }
+
+    /// Drop pins unused for `ttl`, returning how many were removed.
+    pub fn purge_expired(&self, ttl: Duration) -> usize {
+        let before = self.pins.len();
+        self.pins.retain(|_, pin| pin.last_used.elapsed() < ttl);
+        let purged = before.saturating_sub(self.pins.len());
+        if purged > 0 {
+            crate::metrics::STICKY_SESSION_LOOKUPS_TOTAL
+                .with_label_values(&["expired"])
+                .inc_by(purged as f64);
+            self.update_gauge();
+        }
+        purged
+    }
+
+    /// Pins used after `since` (all of them when `None`).
+    pub fn used_since(&self, since: Option<Instant>) -> Vec<SessionEntry> {
+        self.pins
+            .iter()
+            .filter(|pin| since.map_or(true, |since| pin.last_used > since))
+            .map(|pin| SessionEntry {
+                client: pin.key().clone(),
+                server_id: pin.server_id.clone(),
+                idle_ms: pin.last_used.elapsed().as_millis() as u64,
+            })
+            .collect()
+    }
+
+    /// Take over pins from another instance, keeping whichever side used a
+    /// client's pin last. Pins idle for `ttl` are ignored.
+    pub fn merge(&self, entries: Vec<SessionEntry>, ttl: Duration) {
+        let now = Instant::now();
+        for entry in entries {
+            let idle = Duration::from_millis(entry.idle_ms);
+            let Some(last_used) = now.checked_sub(idle).filter(|_| idle < ttl) else {
+                continue;
+            };
+            let mut pin = self.pins.entry(entry.client).or_insert_with(|| Pin {
+                server_id: entry.server_id.clone(),
+                last_used,
+            });
+            if pin.last_used < last_used {
+                *pin = Pin {
+                    server_id: entry.server_id,
+                    last_used,
+                };
+            }
+        }
+        self.update_gauge();
+    }
+
+    /// Number of pinned clients.
+    pub fn len(&self) -> usize {
+        self.pins.len()
+    }
+
+    /// Whether no client is pinned.
+    pub fn is_empty(&self) -> bool {
+        self.pins.is_empty()
+    }
+
+    fn update_gauge(&self) {
+        crate::metrics::STICKY_SESSIONS_ACTIVE.set(self.pins.len() as i64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn test_pinned_backend_is_reused() {
+        let table = SessionTable::new();
+        table.pin("client-a", &"server1".to_string());
+
+        assert_eq!(
+            table.lookup("client-a", TTL, |_| true),
+            Some("server1".to_string())
+        );
+        assert_eq!(table.lookup("client-b", TTL, |_| true), None);
+    }
+
+    #[test]
+    fn test_ineligible_backend_is_rebalanced() {
+        let table = SessionTable::new();
+        table.pin("client-a", &"server1".to_string());
+
+        assert_eq!(table.lookup("client-a", TTL, |id| id != "server1"), None);
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn test_merge_keeps_latest_use() {
+        let local = SessionTable::new();
+        local.pin("client-a", &"server1".to_string());
+        let started = Instant::now();
+        let remote = |client: &str, server_id: &str, idle_ms| SessionEntry {
+            client: client.to_string(),
+            server_id: server_id.to_string(),
+            idle_ms,
+        };
+
+        local.merge(
+            vec![
+                remote("client-a", "server2", 30_000),
+                remote("client-b", "server2", 10),
+                remote("client-c", "server3", 120_000),
+            ],
+            TTL,
+        );
+        assert_eq!(
+            local.lookup("client-a", TTL, |_| true),
+            Some("server1".to_string())
+        );
+        assert_eq!(
+            local.lookup("client-b", TTL, |_| true),
+            Some("server2".to_string())
+        );
+        assert_eq!(local.lookup("client-c", TTL, |_| true), None);
+
+        local.merge(vec![remote("client-a", "server2", 0)], TTL);
+        assert_eq!(
+            local.lookup("client-a", TTL, |_| true),
+            Some("server2".to_string())
+        );
+        assert_eq!(local.used_since(Some(started)).len(), 2);
+    }
+
+    #[test]
+    fn test_expired_pins_are_dropped() {
+        let table = SessionTable::new();
+        table.pin("client-a", &"server1".to_string());
+
+        assert_eq!(table.lookup("client-a", Duration::ZERO, |_| true), None);
+
+        table.pin("client-b", &"server2".to_string());
+        assert_eq!(table.purge_expired(Duration::ZERO), 1);
+        assert!(table.is_empty());
+    }
+}
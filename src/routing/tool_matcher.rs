@@ -0,0 +1,272 @@
+//! Compiled tool routing rules.
+//!
+//! Each backend's `routing.tools` list holds glob patterns (`*` and `?`) that
+//! select which tools it serves; a leading `!` turns a pattern into a deny
+//! rule. Scanning every pattern per request grows linearly with the rule
+//! count, so rules are compiled once at config load into a [`ToolMatcher`]:
+//!
+//! - Exact names go into a hash map (O(1) lookup)
+//! - `prefix*` patterns go into a byte trie walked along the tool name
+//! - `*suffix` patterns go into a trie walked along the reversed name
+//! - Anything else falls back to a linear glob scan
+//!
+//! Lookup cost is therefore bounded by the tool name length plus the number
+//! of irregular globs, independent of how many exact/prefix/suffix rules exist.
+
+use std::collections::HashMap;
+
+/// Byte trie mapping pattern prefixes to rule owners.
+#[derive(Debug, Default)]
+struct Trie {
+    nodes: Vec<TrieNode>,
+}
+
+#[derive(Debug, Default)]
+struct TrieNode {
+    children: Vec<(u8, usize)>,
+    owners: Vec<usize>,
+}
+
+impl Trie {
+    fn new() -> Self {
+        Self {
+            nodes: vec![TrieNode::default()],
+        }
+    }
+
+    fn insert(&mut self, key: impl Iterator<Item = u8>, owner: usize) {
+        let mut node = 0;
+        for byte in key {
+            node = match self.nodes[node].children.iter().find(|(b, _)| *b == byte) {
+                Some(&(_, next)) => next,
+                None => {
+                    let next = self.nodes.len();
+                    self.nodes.push(TrieNode::default());
+                    self.nodes[node].children.push((byte, next));
+                    next
+                },
+            };
+        }
+        self.nodes[node].owners.push(owner);
+    }
+
+    /// Visit the owners of every inserted key that is a prefix of `key`.
+    fn for_each_prefix(&self, key: impl Iterator<Item = u8>, mut visit: impl FnMut(usize)) {
+        let mut node = 0;
+        self.nodes[node].owners.iter().for_each(|&o| visit(o));
+        for byte in key {
+            match self.nodes[node].children.iter().find(|(b, _)| *b == byte) {
+                Some(&(_, next)) => node = next,
+                None => return,
+            }
+            self.nodes[node].owners.iter().for_each(|&o| visit(o));
+        }
+    }
+}
+
+/// A compiled set of glob patterns, each tagged with its owner index.
+#[derive(Debug)]
+struct PatternSet {
+    exact: HashMap<String, Vec<usize>>,
+    prefixes: Trie,
+    suffixes: Trie,
+    globs: Vec<(String, usize)>,
+}
+
+impl PatternSet {
+    fn new() -> Self {
+        Self {
+            exact: HashMap::new(),
+            prefixes: Trie::new(),
+            suffixes: Trie::new(),
+            globs: Vec::new(),
+        }
+    }
+
+    fn insert(&mut self, pattern: &str, owner: usize) {
+        let wildcards = pattern.bytes().filter(|b| *b == b'*' || *b == b'?').count();
+
+        if wildcards == 0 {
+            self.exact.entry(pattern.to_string()).or_default().push(owner);
+        } else if wildcards == 1 && pattern.ends_with('*') {
+            self.prefixes.insert(pattern[..pattern.len() - 1].bytes(), owner);
+        } else if wildcards == 1 && pattern.starts_with('*') {
+            self.suffixes.insert(pattern[1..].bytes().rev(), owner);
+        } else {
+            self.globs.push((pattern.to_string(), owner));
+        }
+    }
+
+    fn for_each_match(&self, tool: &str, mut visit: impl FnMut(usize)) {
+        if let Some(owners) = self.exact.get(tool) {
+            owners.iter().for_each(|&o| visit(o));
+        }
+        self.prefixes.for_each_prefix(tool.bytes(), &mut visit);
+        self.suffixes.for_each_prefix(tool.bytes().rev(), &mut visit);
+        for (pattern, owner) in &self.globs {
+            if glob_match(pattern, tool) {
+                visit(*owner);
+            }
+        }
+    }
+}
+
+/// Compiled allow/deny tool rules for all backends.
+#[derive(Debug)]
+pub struct ToolMatcher {
+    owners: Vec<String>,
+    allow: PatternSet,
+    deny: PatternSet,
+    /// Owners without allow rules, which accept any tool not denied
+    catch_all: Vec<usize>,
+}
+
+impl Default for ToolMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ToolMatcher {
+    /// Create a matcher with no rules.
+    pub fn new() -> Self {
+        Self {
+            owners: Vec::new(),
+            allow: PatternSet::new(),
+            deny: PatternSet::new(),
+            catch_all: Vec::new(),
+        }
+    }
+
+    /// Compile rules from `(server_id, patterns)` pairs.
+    ///
+    /// A server with no allow patterns accepts every tool that it does not
+    /// explicitly deny.
+    pub fn compile<'a, I, P>(rules: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a str, P)>,
+        P: IntoIterator<Item = &'a String>,
+    {
+        let mut matcher = Self::new();
+
+        for (server_id, patterns) in rules {
+            let owner = matcher.owners.len();
+            matcher.owners.push(server_id.to_string());
+
+            let mut has_allow = false;
+            for pattern in patterns {
+                match pattern.strip_prefix('!') {
+                    Some(denied) => matcher.deny.insert(denied, owner),
+                    None => {
+                        has_allow = true;
+                        matcher.allow.insert(pattern, owner);
+                    },
+                }
+            }
+
+            if !has_allow {
+                matcher.catch_all.push(owner);
+            }
+        }
+
+        matcher
+    }
+
+    /// Server IDs whose rules accept `tool`, in rule registration order.
+    pub fn servers_for(&self, tool: &str) -> Vec<String> {
+        let mut selected = vec![false; self.owners.len()];
+        self.allow.for_each_match(tool, |owner| selected[owner] = true);
+        self.catch_all.iter().for_each(|&owner| selected[owner] = true);
+        self.deny.for_each_match(tool, |owner| selected[owner] = false);
+
+        self.owners
+            .iter()
+            .zip(selected)
+            .filter(|(_, keep)| *keep)
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Whether `server_id` accepts `tool`.
+    pub fn allows(&self, server_id: &str, tool: &str) -> bool {
+        self.servers_for(tool).iter().any(|id| id == server_id)
+    }
+
+    /// Number of servers with compiled rules.
+    pub fn len(&self) -> usize {
+        self.owners.len()
+    }
+
+    /// Check if no rules have been compiled
+    pub fn is_empty(&self) -> bool {
+        self.owners.is_empty()
+    }
+}
+
+/// Match `text` against a glob `pattern` supporting `*` and `?`.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let (p, t) = (pattern.as_bytes(), text.as_bytes());
+    let (mut pi, mut ti) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == b'?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == b'*' {
+            backtrack = Some((pi, ti));
+            pi += 1;
+        } else if let Some((star, matched)) = backtrack {
+            pi = star + 1;
+            ti = matched + 1;
+            backtrack = Some((star, ti));
+        } else {
+            return false;
+        }
+    }
+
+    p[pi..].iter().all(|b| *b == b'*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(patterns: &[&str]) -> Vec<String> {
+        patterns.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("read_*", "read_file"));
+        assert!(glob_match("*_file", "read_file"));
+        assert!(glob_match("r?ad_*e", "read_file"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("read_?", "read_file"));
+        assert!(!glob_match("write_*", "read_file"));
+    }
+
+    #[test]
+    fn test_exact_prefix_suffix_and_glob_rules() {
+        let fs = rules(&["read_file", "write_*"]);
+        let gh = rules(&["*_issue", "gh?pr*"]);
+        let matcher = ToolMatcher::compile([("fs", &fs), ("github", &gh)]);
+
+        assert_eq!(matcher.servers_for("read_file"), vec!["fs"]);
+        assert_eq!(matcher.servers_for("write_dir"), vec!["fs"]);
+        assert_eq!(matcher.servers_for("create_issue"), vec!["github"]);
+        assert_eq!(matcher.servers_for("gh_pr_list"), vec!["github"]);
+        assert!(matcher.servers_for("unknown").is_empty());
+    }
+
+    #[test]
+    fn test_catch_all_and_deny() {
+        let any = rules(&[]);
+        let restricted = rules(&["!delete_*"]);
+        let matcher = ToolMatcher::compile([("a", &any), ("b", &restricted)]);
+
+        assert_eq!(matcher.servers_for("read_file"), vec!["a", "b"]);
+        assert_eq!(matcher.servers_for("delete_file"), vec!["a"]);
+        assert!(!matcher.allows("b", "delete_file"));
+    }
+}
@@ -0,0 +1,222 @@
+//! Per-server files of STDIO backend output (`observability.backend_logs`).
+//!
+//! Every line a STDIO backend writes to stderr (and, with `capture_stdout`,
+//! every stdout line that is not a JSON-RPC message) is appended to
+//! `<dir>/<server id>.log` with a timestamp and the process ID, after the
+//! server's secrets are redacted. A file reaching `max_file_bytes` is
+//! rotated to `<id>.log.1`, shifting older ones up to `max_files`. The
+//! files outlive the processes, which is what makes a crashing backend
+//! debuggable; [`tail`] reads them back for `only1mcp logs` and the Admin
+//! API.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use tracing::warn;
+
+use crate::config::BackendLogsConfig;
+
+/// Lines returned when a request does not say.
+pub const DEFAULT_TAIL_LINES: usize = 200;
+
+/// Query of `GET /api/v1/admin/servers/:id/logs`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TailQuery {
+    /// Most recent lines returned (default: 200)
+    pub lines: Option<usize>,
+}
+
+/// Open log files, shared by all processes of a server.
+#[derive(Debug, Default)]
+pub struct BackendLogs {
+    files: DashMap<String, Arc<ServerLog>>,
+}
+
+impl BackendLogs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Log of `server_id` under `config`, `None` when capture is disabled.
+    /// A server whose settings changed on reload gets a fresh handle.
+    pub fn server(&self, server_id: &str, config: &BackendLogsConfig) -> Option<Arc<ServerLog>> {
+        if !config.enabled {
+            return None;
+        }
+        let path = config.path(server_id)?;
+        let mut entry = self
+            .files
+            .entry(server_id.to_string())
+            .or_insert_with(|| Arc::new(ServerLog::new(path.clone(), config)));
+        if entry.path != path
+            || entry.max_file_bytes != config.max_file_bytes
+            || entry.max_files != config.max_files
+        {
+            *entry = Arc::new(ServerLog::new(path, config));
+        }
+        Some(entry.clone())
+    }
+}
+
+/// The log file of one server.
+#[derive(Debug)]
+pub struct ServerLog {
+    path: PathBuf,
+    max_file_bytes: u64,
+    max_files: usize,
+    /// Open file and its size; `None` until the first line or after a
+    /// failed open
+    file: Mutex<Option<(File, u64)>>,
+    /// Whether a write failure was logged, so it is logged once
+    failed: Mutex<bool>,
+}
+
+impl ServerLog {
+    fn new(path: PathBuf, config: &BackendLogsConfig) -> Self {
+        Self {
+            path,
+            max_file_bytes: config.max_file_bytes,
+            max_files: config.max_files,
+            file: Mutex::new(None),
+            failed: Mutex::new(false),
+        }
+    }
+
+    /// Append one line of `stream` output of process `pid`.
+    pub fn append(&self, pid: Option<u32>, stream: &str, line: &str) {
+        let pid = pid.map_or_else(|| "?".to_string(), |pid| pid.to_string());
+        let line = format!(
+            "{} [pid {}] {}: {}\n",
+            chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            pid,
+            stream,
+            line
+        );
+        if let Err(e) = self.write(line.as_bytes()) {
+            let mut failed = self.failed.lock();
+            if !*failed {
+                warn!("Failed to write backend log {}: {}", self.path.display(), e);
+                *failed = true;
+            }
+        }
+    }
+
+    fn write(&self, line: &[u8]) -> io::Result<()> {
+        let mut file = self.file.lock();
+        if file.as_ref().is_some_and(|(_, size)| *size >= self.max_file_bytes) {
+            *file = None;
+            rotate(&self.path, self.max_files)?;
+        }
+        if file.is_none() {
+            if let Some(dir) = self.path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            let opened = OpenOptions::new().create(true).append(true).open(&self.path)?;
+            let size = opened.metadata()?.len();
+            *file = Some((opened, size));
+        }
+        let (opened, size) = file.as_mut().expect("opened above");
+        opened.write_all(line)?;
+        *size += line.len() as u64;
+        Ok(())
+    }
+}
+
+/// `<path>.<n>`, the `n`th newest rotated file.
+fn rotated(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
+
+/// Shift `path` to `path.1`, `path.1` to `path.2` and so on, dropping
+/// files beyond `max_files`.
+fn rotate(path: &Path, max_files: usize) -> io::Result<()> {
+    if max_files == 0 {
+        return std::fs::remove_file(path);
+    }
+    let _ = std::fs::remove_file(rotated(path, max_files));
+    for n in (1..max_files).rev() {
+        let from = rotated(path, n);
+        if from.exists() {
+            std::fs::rename(&from, rotated(path, n + 1))?;
+        }
+    }
+    std::fs::rename(path, rotated(path, 1))
+}
+
+/// The last `lines` lines logged for `server_id`, oldest first, reading
+/// into rotated files when the current one is shorter.
+pub fn tail(config: &BackendLogsConfig, server_id: &str, lines: usize) -> io::Result<Vec<String>> {
+    let Some(path) = config.path(server_id) else {
+        return Ok(Vec::new());
+    };
+    let files =
+        std::iter::once(path.clone()).chain((1..=config.max_files).map(|n| rotated(&path, n)));
+    let mut tail = Vec::new();
+    for file in files {
+        let file = match File::open(&file) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => break,
+            Err(e) => return Err(e),
+        };
+        let mut content: Vec<String> = BufReader::new(file).lines().collect::<io::Result<_>>()?;
+        let wanted = lines - tail.len();
+        content.drain(..content.len().saturating_sub(wanted));
+        content.append(&mut tail);
+        tail = content;
+        if tail.len() >= lines {
+            break;
+        }
+    }
+    Ok(tail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_and_tail() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = BackendLogsConfig {
+            dir: Some(dir.path().to_path_buf()),
+            max_file_bytes: 200,
+            max_files: 2,
+            ..Default::default()
+        };
+        let logs = BackendLogs::new();
+        let log = logs.server("git/hub", &config).unwrap();
+        for i in 0..16 {
+            log.append(Some(42), "stderr", &format!("line {:02}", i));
+        }
+
+        // 50 bytes a line: four lines a file, the oldest four dropped
+        let path = dir.path().join("git_hub.log");
+        assert!(rotated(&path, 2).exists());
+        assert!(!rotated(&path, 3).exists());
+        let lines = tail(&config, "git/hub", 6).unwrap();
+        assert_eq!(lines.len(), 6);
+        assert!(
+            lines[0].ends_with("[pid 42] stderr: line 10"),
+            "{}",
+            lines[0]
+        );
+        assert!(lines[5].ends_with("line 15"));
+        let all = tail(&config, "git/hub", 100).unwrap();
+        assert_eq!(all.len(), 12);
+        assert!(all[0].ends_with("line 04"));
+
+        assert!(tail(&config, "other", 10).unwrap().is_empty());
+        let disabled = BackendLogsConfig {
+            enabled: false,
+            ..config
+        };
+        assert!(logs.server("git/hub", &disabled).is_none());
+    }
+}
@@ -0,0 +1,267 @@
+//! MCP-over-gRPC transport (`type: grpc`).
+//!
+//! Each JSON-RPC message travels as the payload of a unary call:
+//!
+//! ```protobuf
+//! syntax = "proto3";
+//! package only1mcp.v1;
+//!
+//! service McpService {
+//!   // One JSON-RPC 2.0 request, answered with its response
+//!   rpc Call(JsonRpcMessage) returns (JsonRpcMessage);
+//! }
+//!
+//! message JsonRpcMessage {
+//!   bytes payload = 1;  // UTF-8 JSON
+//! }
+//! ```
+//!
+//! Calls to one endpoint are multiplexed over a single HTTP/2 channel.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use bytes::Bytes;
+use thiserror::Error;
+use tonic::client::Grpc;
+use tonic::codec::ProstCodec;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::metadata::{MetadataKey, MetadataValue};
+use tonic::transport::{Channel, Endpoint};
+use tonic::Code;
+
+use crate::config::{BackendTlsConfig, ConnectionPoolConfig};
+use crate::transport::limits::ResponseLimit;
+use crate::types::{McpRequest, McpResponse};
+
+/// Fully qualified name of the MCP service.
+pub const SERVICE_NAME: &str = "only1mcp.v1.McpService";
+
+/// Path of the unary `Call` method.
+pub const CALL_PATH: &str = "/only1mcp.v1.McpService/Call";
+
+/// A JSON-RPC message carried over gRPC.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct JsonRpcMessage {
+    /// UTF-8 JSON-RPC 2.0 message
+    #[prost(bytes = "bytes", tag = "1")]
+    pub payload: Bytes,
+}
+
+/// gRPC transport errors
+#[derive(Error, Debug)]
+pub enum GrpcError {
+    #[error("Invalid gRPC endpoint {0}")]
+    InvalidEndpoint(String),
+
+    #[error("TLS configuration error: {0}")]
+    Tls(String),
+
+    #[error("Invalid metadata: {0}")]
+    InvalidMetadata(String),
+
+    #[error("Connection failed: {0}")]
+    ConnectionFailed(String),
+
+    #[error("Call failed with {code}: {message}")]
+    Status { code: Code, message: String },
+
+    #[error("Invalid response format: {0}")]
+    InvalidResponse(String),
+
+    #[error("Response exceeds {0} byte limit")]
+    ResponseTooLarge(usize),
+}
+
+impl From<tonic::Status> for GrpcError {
+    fn from(status: tonic::Status) -> Self {
+        GrpcError::Status {
+            code: status.code(),
+            message: status.message().to_string(),
+        }
+    }
+}
+
+impl From<GrpcError> for crate::error::Error {
+    fn from(err: GrpcError) -> Self {
+        match err {
+            GrpcError::ResponseTooLarge(_) => {
+                crate::error::Error::ResponseTooLarge(err.to_string())
+            },
+            _ => crate::error::Error::Transport(err.to_string()),
+        }
+    }
+}
+
+/// gRPC channels to MCP backends, one per endpoint and TLS settings.
+pub struct GrpcTransportPool {
+    channels: dashmap::DashMap<(String, Option<BackendTlsConfig>), Channel>,
+    /// Largest response message accepted
+    max_response_bytes: ResponseLimit,
+    /// Keep-alive settings of new channels
+    pool: ConnectionPoolConfig,
+}
+
+impl Default for GrpcTransportPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GrpcTransportPool {
+    /// Create an empty pool; channels are opened on first use.
+    pub fn new() -> Self {
+        Self {
+            channels: dashmap::DashMap::new(),
+            max_response_bytes: ResponseLimit::default(),
+            pool: ConnectionPoolConfig::default(),
+        }
+    }
+
+    /// Accept backend responses up to `limit` bytes
+    pub fn with_response_limit(mut self, limit: ResponseLimit) -> Self {
+        self.max_response_bytes = limit;
+        self
+    }
+
+    /// Tune new channels' keep-alive per `proxy.connection_pool`
+    pub fn with_pool_config(mut self, pool: &ConnectionPoolConfig) -> Self {
+        self.pool = pool.clone();
+        self
+    }
+
+    /// Send `request` to the backend at `endpoint` with `metadata` attached,
+    /// connecting with `tls` when set.
+    pub async fn send_request(
+        &self,
+        endpoint: &str,
+        request: McpRequest,
+        metadata: &HashMap<String, String>,
+        timeout: Option<Duration>,
+        tls: Option<&BackendTlsConfig>,
+    ) -> Result<McpResponse, GrpcError> {
+        let payload = crate::json::to_bytes(&request)
+            .map_err(|e| GrpcError::InvalidResponse(e.to_string()))?;
+        let mut call = tonic::Request::new(JsonRpcMessage { payload });
+        for (key, value) in metadata {
+            let key = MetadataKey::from_bytes(key.to_ascii_lowercase().as_bytes())
+                .map_err(|_| GrpcError::InvalidMetadata(key.clone()))?;
+            let value = MetadataValue::try_from(value.as_str())
+                .map_err(|_| GrpcError::InvalidMetadata(key.to_string()))?;
+            call.metadata_mut().insert(key, value);
+        }
+        if let Some(timeout) = timeout {
+            call.set_timeout(timeout);
+        }
+
+        let limit = self.max_response_bytes.get();
+        let mut client = Grpc::new(self.channel(endpoint, tls)?).max_decoding_message_size(limit);
+        client.ready().await.map_err(|e| GrpcError::ConnectionFailed(e.to_string()))?;
+
+        let codec = ProstCodec::<JsonRpcMessage, JsonRpcMessage>::default();
+        let response = client
+            .unary(call, PathAndQuery::from_static(CALL_PATH), codec)
+            .await
+            .map_err(|status| match status.code() {
+                // Raised by the decoder for messages over the limit
+                Code::OutOfRange if status.message().contains("too large") => {
+                    GrpcError::ResponseTooLarge(limit)
+                },
+                // The lazy channel reports connect errors on the first call
+                Code::Unavailable => GrpcError::ConnectionFailed(status.message().to_string()),
+                _ => status.into(),
+            })?;
+
+        crate::json::from_slice(&response.into_inner().payload)
+            .map_err(|e| GrpcError::InvalidResponse(e.to_string()))
+    }
+
+    /// Connect to the backend at `endpoint` ahead of its first request.
+    pub async fn prewarm(
+        &self,
+        endpoint: &str,
+        tls: Option<&BackendTlsConfig>,
+    ) -> Result<(), GrpcError> {
+        let mut client = Grpc::new(self.channel(endpoint, tls)?);
+        client.ready().await.map_err(|e| GrpcError::ConnectionFailed(e.to_string()))
+    }
+
+    /// The channel to `endpoint`, created (without connecting) on first use.
+    fn channel(
+        &self,
+        endpoint: &str,
+        tls: Option<&BackendTlsConfig>,
+    ) -> Result<Channel, GrpcError> {
+        let key = (endpoint.to_string(), tls.cloned());
+        if let Some(channel) = self.channels.get(&key) {
+            return Ok(channel.clone());
+        }
+
+        let channel = endpoint_for(endpoint, tls, &self.pool)?.connect_lazy();
+        Ok(self.channels.entry(key).or_insert(channel).clone())
+    }
+}
+
+/// Check that the gRPC server at `endpoint` accepts connections.
+pub async fn probe(
+    endpoint: &str,
+    tls: Option<&BackendTlsConfig>,
+    timeout: Duration,
+) -> Result<(), GrpcError> {
+    endpoint_for(endpoint, tls, &ConnectionPoolConfig::default())?
+        .connect_timeout(timeout)
+        .connect()
+        .await
+        .map(|_| ())
+        .map_err(|e| GrpcError::ConnectionFailed(connect_error(&e)))
+}
+
+/// Channel settings for `endpoint`: TLS for `https` endpoints, keep-alive
+/// per `pool`.
+fn endpoint_for(
+    endpoint: &str,
+    tls: Option<&BackendTlsConfig>,
+    pool: &ConnectionPoolConfig,
+) -> Result<Endpoint, GrpcError> {
+    let mut channel = Endpoint::from_shared(endpoint.to_string())
+        .map_err(|e| GrpcError::InvalidEndpoint(format!("{}: {}", endpoint, e)))?
+        .connect_timeout(Duration::from_secs(10))
+        .tcp_keepalive(Some(Duration::from_secs(90)));
+    if let Some(interval) = pool.http2_keep_alive() {
+        channel = channel.http2_keep_alive_interval(interval).keep_alive_while_idle(true);
+    }
+    if endpoint.starts_with("https://") {
+        let config = crate::transport::tls::grpc_client_config(tls.cloned().unwrap_or_default())
+            .map_err(GrpcError::Tls)?;
+        channel = channel.tls_config(config).map_err(|e| GrpcError::Tls(e.to_string()))?;
+    }
+    Ok(channel)
+}
+
+/// Describe a connection error including its cause, which tonic keeps out
+/// of the top-level message.
+fn connect_error(error: &tonic::transport::Error) -> String {
+    match std::error::Error::source(error) {
+        Some(source) => format!("{}: {}", error, source),
+        None => error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_invalid_endpoint_and_metadata() {
+        let pool = GrpcTransportPool::new();
+        let request = McpRequest::new("ping", serde_json::json!({}), Some(serde_json::json!(1)));
+
+        let no_metadata = HashMap::new();
+        let result = pool.send_request("not a uri", request.clone(), &no_metadata, None, None);
+        assert!(matches!(result.await, Err(GrpcError::InvalidEndpoint(_))));
+
+        let metadata = HashMap::from([("bad key".to_string(), "v".to_string())]);
+        let result = pool.send_request("http://127.0.0.1:1", request, &metadata, None, None);
+        assert!(matches!(result.await, Err(GrpcError::InvalidMetadata(_))));
+    }
+}
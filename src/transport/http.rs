@@ -6,12 +6,20 @@
 
 use async_trait::async_trait;
 use bb8::{ManageConnection, Pool};
-use reqwest::{Client, StatusCode};
+use reqwest::{Client, ClientBuilder, StatusCode};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use thiserror::Error;
 
+use crate::auth::oauth::ClientCredentials;
+use crate::config::{
+    BackendOAuthConfig, BackendTlsConfig, Config, ConnectionPoolConfig, McpServerConfig,
+    OutboundProxyConfig, RequestSigningConfig,
+};
+use crate::transport::limits::{read_body, read_text, BodyError, ResponseLimit};
+use crate::transport::signing::signature_headers;
+use crate::transport::streaming::{should_stream, BackendReply};
 use crate::types::{McpRequest, McpResponse};
 
 /// HTTP transport errors
@@ -40,6 +48,44 @@ pub enum HttpError {
 
     #[error("Server error: {0}")]
     ServerError(String),
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("OAuth token request failed: {0}")]
+    OAuth(String),
+
+    #[error("TLS configuration error: {0}")]
+    Tls(String),
+
+    #[error("Proxy configuration error: {0}")]
+    Proxy(String),
+
+    #[error("Response exceeds {0} byte limit")]
+    ResponseTooLarge(usize),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+impl From<BodyError> for HttpError {
+    fn from(err: BodyError) -> Self {
+        match err {
+            BodyError::TooLarge(limit) => HttpError::ResponseTooLarge(limit),
+            BodyError::Read(e) => HttpError::RequestFailed(e),
+        }
+    }
+}
+
+impl From<HttpError> for crate::error::Error {
+    fn from(err: HttpError) -> Self {
+        match err {
+            HttpError::ResponseTooLarge(_) => {
+                crate::error::Error::ResponseTooLarge(err.to_string())
+            },
+            _ => crate::error::Error::Transport(err.to_string()),
+        }
+    }
 }
 
 /// HTTP transport configuration
@@ -68,6 +114,18 @@ pub struct HttpTransportConfig {
 
     /// Custom HTTP headers
     pub headers: std::collections::HashMap<String, String>,
+
+    /// Client certificate and trust roots for HTTPS backends
+    pub tls: Option<BackendTlsConfig>,
+
+    /// Outbound proxy the backend is reached through
+    pub proxy: Option<OutboundProxyConfig>,
+
+    /// Largest response body read from the server
+    pub max_response_bytes: ResponseLimit,
+
+    /// Idle connection and keep-alive tuning
+    pub pool: ConnectionPoolConfig,
 }
 
 impl Default for HttpTransportConfig {
@@ -81,10 +139,28 @@ impl Default for HttpTransportConfig {
             max_connections_per_host: 10,
             compression: true,
             headers: std::collections::HashMap::new(),
+            tls: None,
+            proxy: None,
+            max_response_bytes: ResponseLimit::default(),
+            pool: ConnectionPoolConfig::default(),
         }
     }
 }
 
+/// Apply `pool`'s idle connection and HTTP/2 keep-alive settings to a
+/// backend client.
+pub fn configure_pool(builder: ClientBuilder, pool: &ConnectionPoolConfig) -> ClientBuilder {
+    let builder = builder
+        .pool_max_idle_per_host(pool.max_idle_per_host)
+        .pool_idle_timeout(pool.idle_timeout());
+    match pool.http2_keep_alive() {
+        Some(interval) => {
+            builder.http2_keep_alive_interval(interval).http2_keep_alive_while_idle(true)
+        },
+        None => builder,
+    }
+}
+
 /// HTTP connection manager for bb8 pool
 pub struct HttpConnectionManager {
     /// Base URL for the backend
@@ -98,26 +174,40 @@ pub struct HttpConnectionManager {
 
     /// Custom HTTP headers
     headers: std::collections::HashMap<String, String>,
+
+    /// Largest response body read from the server
+    max_response_bytes: ResponseLimit,
 }
 
 impl HttpConnectionManager {
     /// Create new HTTP connection manager
-    pub fn new(config: HttpTransportConfig) -> Self {
-        let client = Client::builder()
+    ///
+    /// Fails when the backend's TLS material cannot be loaded or its proxy
+    /// URL is invalid.
+    pub fn new(config: HttpTransportConfig) -> Result<Self, HttpError> {
+        let builder = Client::builder()
             .timeout(config.request_timeout)
             .connect_timeout(config.connection_timeout)
-            .tcp_keepalive(Some(config.keep_alive))
-            .pool_max_idle_per_host(config.max_connections_per_host)
-            // Note: gzip/brotli compression is enabled by default in reqwest
-            .build()
-            .expect("Failed to build HTTP client");
+            .tcp_keepalive(Some(config.keep_alive));
+        let mut builder = configure_pool(builder, &config.pool);
+        // Note: gzip/brotli compression is enabled by default in reqwest
+        if let Some(tls) = &config.tls {
+            builder =
+                crate::transport::tls::configure_client(builder, tls).map_err(HttpError::Tls)?;
+        }
+        if let Some(proxy) = &config.proxy {
+            builder = crate::transport::outbound_proxy::configure_client(builder, proxy)
+                .map_err(HttpError::Proxy)?;
+        }
+        let client = builder.build().map_err(|e| HttpError::Tls(e.to_string()))?;
 
-        Self {
+        Ok(Self {
             base_url: config.base_url,
             client,
             timeout: config.connection_timeout,
             headers: config.headers,
-        }
+            max_response_bytes: config.max_response_bytes,
+        })
     }
 }
 
@@ -145,6 +235,7 @@ impl ManageConnection for HttpConnectionManager {
             created_at: Instant::now(),
             request_count: Arc::new(AtomicU64::new(0)),
             headers: self.headers.clone(),
+            max_response_bytes: self.max_response_bytes.clone(),
         })
     }
 
@@ -199,6 +290,9 @@ pub struct HttpConnection {
 
     /// Custom HTTP headers
     headers: std::collections::HashMap<String, String>,
+
+    /// Largest response body read from the server
+    max_response_bytes: ResponseLimit,
 }
 
 impl HttpConnection {
@@ -211,12 +305,15 @@ impl HttpConnection {
 
         if !response.status().is_success() {
             let status = response.status();
-            let body = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            let body = read_text(response, self.max_response_bytes.get())
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
             return Err(HttpError::ServerError(format!("{}: {}", status, body)));
         }
 
-        let mcp_response: McpResponse =
-            response.json().await.map_err(|e| HttpError::InvalidResponse(e.to_string()))?;
+        let body = read_body(response, self.max_response_bytes.get()).await?;
+        let mcp_response: McpResponse = crate::json::from_slice(&body)
+            .map_err(|e| HttpError::InvalidResponse(e.to_string()))?;
 
         Ok(mcp_response)
     }
@@ -254,7 +351,7 @@ pub struct HttpTransport {
 impl HttpTransport {
     /// Create new HTTP transport
     pub async fn new(config: HttpTransportConfig) -> Result<Self, HttpError> {
-        let manager = HttpConnectionManager::new(config.clone());
+        let manager = HttpConnectionManager::new(config.clone())?;
 
         let pool = Pool::builder()
             .max_size(config.max_connections_per_host as u32)
@@ -369,19 +466,14 @@ impl HttpTransport {
             Ok(response) => {
                 if !response.status().is_success() {
                     let status = response.status();
-                    let body =
-                        response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+                    let body = read_text(response, self.config.max_response_bytes.get())
+                        .await
+                        .unwrap_or_else(|_| "Unknown error".to_string());
                     self.metrics.error_count.fetch_add(1, Ordering::Relaxed);
                     return Err(HttpError::ServerError(format!("{}: {}", status, body)));
                 }
 
-                let mcp_response: McpResponse =
-                    response.json().await.map_err(|e| HttpError::InvalidResponse(e.to_string()))?;
-
-                let elapsed = start.elapsed().as_micros() as u64;
-                self.metrics.total_latency_us.fetch_add(elapsed, Ordering::Relaxed);
-
-                Ok(mcp_response)
+                self.read_response(response, start).await
             },
             Err(e) => {
                 self.metrics.error_count.fetch_add(1, Ordering::Relaxed);
@@ -391,6 +483,24 @@ impl HttpTransport {
     }
 }
 
+impl HttpTransport {
+    /// Parse a successful response, counting its latency from `start`.
+    async fn read_response(
+        &self,
+        response: reqwest::Response,
+        start: Instant,
+    ) -> Result<McpResponse, HttpError> {
+        let body = read_body(response, self.config.max_response_bytes.get()).await?;
+        let mcp_response: McpResponse = crate::json::from_slice(&body)
+            .map_err(|e| HttpError::InvalidResponse(e.to_string()))?;
+
+        let elapsed = start.elapsed().as_micros() as u64;
+        self.metrics.total_latency_us.fetch_add(elapsed, Ordering::Relaxed);
+
+        Ok(mcp_response)
+    }
+}
+
 /// Pool statistics
 #[derive(Debug, Default)]
 pub struct PoolStats {
@@ -436,7 +546,7 @@ impl TransportMetrics {
 
         MetricsStats {
             request_count: count,
-            average_latency_us: if count > 0 { total_latency / count } else { 0 },
+            average_latency_us: total_latency.checked_div(count).unwrap_or(0),
             error_count: self.error_count.load(Ordering::Relaxed),
         }
     }
@@ -450,12 +560,56 @@ pub struct MetricsStats {
     pub error_count: u64,
 }
 
+/// Per-backend settings of a request sent through [`HttpTransportPool`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BackendRequestOptions<'a> {
+    /// Overrides the transport's request timeout
+    pub timeout: Option<Duration>,
+    /// Client-credentials grant supplying a bearer token
+    pub oauth: Option<&'a BackendOAuthConfig>,
+    /// TLS settings of the connection
+    pub tls: Option<&'a BackendTlsConfig>,
+    /// Outbound proxy to connect through
+    pub proxy: Option<&'a OutboundProxyConfig>,
+    /// HMAC signature of the request body
+    pub signing: Option<&'a RequestSigningConfig>,
+}
+
+impl<'a> BackendRequestOptions<'a> {
+    /// Settings of `server`: its request timeout, OAuth client, TLS and
+    /// signing settings, and the outbound proxy `config` gives it.
+    pub fn for_server(server: &'a McpServerConfig, config: &'a Config) -> Self {
+        Self {
+            timeout: Some(server.request_timeout()),
+            oauth: server.transport.oauth(),
+            tls: server.transport.tls(),
+            proxy: config.outbound_proxy(&server.transport),
+            signing: server.transport.signing(),
+        }
+    }
+}
+
+/// OAuth client (token URL, client ID and scopes) and outbound proxy of a
+/// token source.
+type CredentialsKey = (String, Option<OutboundProxyConfig>);
+
+/// Backend origin, TLS and proxy settings of a pooled transport.
+type TransportKey = (
+    String,
+    Option<BackendTlsConfig>,
+    Option<OutboundProxyConfig>,
+);
+
 /// Multi-backend HTTP transport pool manager
 pub struct HttpTransportPool {
-    /// Transports per endpoint (lazy initialization)
-    transports: dashmap::DashMap<String, Arc<HttpTransport>>,
+    /// Transports per backend origin, TLS and proxy settings (lazy
+    /// initialization)
+    transports: dashmap::DashMap<TransportKey, Arc<HttpTransport>>,
     /// Default configuration for new transports
     default_config: HttpTransportConfig,
+    /// OAuth2 client-credentials token sources, shared by backends using
+    /// the same client and outbound proxy
+    credentials: dashmap::DashMap<CredentialsKey, Arc<ClientCredentials>>,
 }
 
 impl Default for HttpTransportPool {
@@ -470,37 +624,90 @@ impl HttpTransportPool {
         Self {
             transports: dashmap::DashMap::new(),
             default_config: HttpTransportConfig::default(),
+            credentials: dashmap::DashMap::new(),
         }
     }
 
-    /// Get or create an HTTP transport for a specific endpoint
-    async fn get_or_create(&self, endpoint: &str) -> Result<Arc<HttpTransport>, HttpError> {
-        // Check if we already have a transport for this endpoint
-        if let Some(transport) = self.transports.get(endpoint) {
-            return Ok(transport.clone());
-        }
+    /// Read backend responses up to `limit` bytes
+    pub fn with_response_limit(mut self, limit: ResponseLimit) -> Self {
+        self.default_config.max_response_bytes = limit;
+        self
+    }
+
+    /// Size new transports' pools and tune their idle connections per
+    /// `proxy.connection_pool`
+    pub fn with_pool_config(mut self, pool: &ConnectionPoolConfig) -> Self {
+        self.default_config.max_connections_per_host = pool.max_per_backend;
+        self.default_config.pool = pool.clone();
+        self
+    }
+
+    /// Open a connection to the backend at `endpoint` ahead of its first
+    /// request.
+    pub async fn prewarm(
+        &self,
+        endpoint: &str,
+        tls: Option<&BackendTlsConfig>,
+        proxy: Option<&OutboundProxyConfig>,
+    ) -> Result<(), HttpError> {
+        // Creating the transport fills its pool, which connects to the backend
+        self.get_or_create(endpoint, tls, proxy).await.map(|_| ())
+    }
+
+    /// Pool statistics of the transport for `endpoint`, if one was created.
+    pub fn pool_stats(
+        &self,
+        endpoint: &str,
+        tls: Option<&BackendTlsConfig>,
+        proxy: Option<&OutboundProxyConfig>,
+    ) -> Option<PoolStats> {
+        let (_, key) = Self::transport_key(endpoint, tls, proxy);
+        self.transports.get(&key).map(|transport| transport.pool_stats())
+    }
 
-        // Extract base URL from endpoint
+    /// Base URL (scheme, host and port) of `endpoint`, and the key of its
+    /// transport.
+    fn transport_key(
+        endpoint: &str,
+        tls: Option<&BackendTlsConfig>,
+        proxy: Option<&OutboundProxyConfig>,
+    ) -> (String, TransportKey) {
         let base_url = if let Ok(url) = url::Url::parse(endpoint) {
-            format!(
-                "{}://{}",
-                url.scheme(),
-                url.host_str().unwrap_or("localhost")
-            )
+            url.origin().ascii_serialization()
         } else {
             endpoint.to_string()
         };
+        let key = (base_url.clone(), tls.cloned(), proxy.cloned());
+        (base_url, key)
+    }
+
+    /// Get or create an HTTP transport for a specific endpoint, connecting
+    /// with `tls` and through `proxy` when set
+    async fn get_or_create(
+        &self,
+        endpoint: &str,
+        tls: Option<&BackendTlsConfig>,
+        proxy: Option<&OutboundProxyConfig>,
+    ) -> Result<Arc<HttpTransport>, HttpError> {
+        let (base_url, key) = Self::transport_key(endpoint, tls, proxy);
+
+        // Check if we already have a transport for this backend
+        if let Some(transport) = self.transports.get(&key) {
+            return Ok(transport.clone());
+        }
 
         // Create new transport
         let config = HttpTransportConfig {
-            base_url: base_url.clone(),
+            base_url,
+            tls: tls.cloned(),
+            proxy: proxy.cloned(),
             ..self.default_config.clone()
         };
 
         let transport = Arc::new(HttpTransport::new(config).await?);
 
         // Store for reuse
-        self.transports.insert(base_url, transport.clone());
+        self.transports.insert(key, transport.clone());
 
         Ok(transport)
     }
@@ -522,17 +729,170 @@ impl HttpTransportPool {
         request: crate::types::McpRequest,
         headers: std::collections::HashMap<String, String>,
     ) -> Result<crate::types::McpResponse, HttpError> {
-        // Get or create base transport
-        let transport = self.get_or_create(endpoint).await?;
+        self.send_request_with_options(endpoint, request, headers, None).await
+    }
+
+    /// Send request with custom headers and the backend's `options`: an
+    /// optional timeout, a client-credentials bearer token when `oauth` is
+    /// set, a connection using its `tls` settings and `proxy`, and with
+    /// `signing`, an HMAC signature of the body.
+    ///
+    /// Tokens are cached until shortly before they expire. If the backend
+    /// rejects a token with 401, a new one is requested and the request is
+    /// sent once more.
+    pub async fn send_request_with_auth(
+        &self,
+        endpoint: &str,
+        request: crate::types::McpRequest,
+        headers: std::collections::HashMap<String, String>,
+        options: BackendRequestOptions<'_>,
+    ) -> Result<crate::types::McpResponse, HttpError> {
+        let start = Instant::now();
+        let (transport, response) =
+            self.post_with_auth(endpoint, &request, headers, options).await?;
+        transport.read_response(response, start).await
+    }
 
-        // If no headers provided, use existing transport logic
-        if headers.is_empty() {
+    /// Like [`send_request_with_auth`](Self::send_request_with_auth), but a
+    /// JSON result of at least `stream_from` bytes, or of unknown length, is
+    /// returned unread for the caller to stream.
+    pub async fn send_request_streaming(
+        &self,
+        endpoint: &str,
+        request: crate::types::McpRequest,
+        headers: std::collections::HashMap<String, String>,
+        options: BackendRequestOptions<'_>,
+        stream_from: usize,
+    ) -> Result<BackendReply, HttpError> {
+        let start = Instant::now();
+        let (transport, response) =
+            self.post_with_auth(endpoint, &request, headers, options).await?;
+        if should_stream(&response, stream_from) {
+            return Ok(BackendReply::Streaming(response));
+        }
+        transport.read_response(response, start).await.map(BackendReply::Parsed)
+    }
+
+    /// Post `request` with the OAuth token handling and signing of
+    /// [`send_request_with_auth`](Self::send_request_with_auth), returning
+    /// the successful response unread.
+    async fn post_with_auth(
+        &self,
+        endpoint: &str,
+        request: &crate::types::McpRequest,
+        headers: std::collections::HashMap<String, String>,
+        options: BackendRequestOptions<'_>,
+    ) -> Result<(Arc<HttpTransport>, reqwest::Response), HttpError> {
+        let BackendRequestOptions {
+            timeout,
+            oauth,
+            tls,
+            proxy,
+            signing,
+        } = options;
+        let transport = self.get_or_create(endpoint, tls, proxy).await?;
+        let post =
+            |headers| Self::post_on(&transport, endpoint, request, headers, timeout, signing);
+        let Some(oauth) = oauth else {
+            let response = post(headers).await?;
+            return Ok((transport, response));
+        };
+        let credentials = self.credentials_for(oauth, proxy)?;
+
+        let with_token = |token: String| {
+            let mut headers = headers.clone();
+            headers.insert("Authorization".to_string(), format!("Bearer {}", token));
+            headers
+        };
+        let token =
+            credentials.access_token().await.map_err(|e| HttpError::OAuth(e.to_string()))?;
+
+        let response = match post(with_token(token)).await {
+            Err(HttpError::Unauthorized(reason)) => {
+                tracing::warn!(
+                    "{} rejected OAuth token ({}), requesting a new one",
+                    endpoint,
+                    reason
+                );
+                credentials.invalidate().await;
+                let token = credentials
+                    .access_token()
+                    .await
+                    .map_err(|e| HttpError::OAuth(e.to_string()))?;
+                post(with_token(token)).await
+            },
+            result => result,
+        }?;
+        Ok((transport, response))
+    }
+
+    /// Token source for an OAuth client requesting tokens through `proxy`,
+    /// created on first use.
+    fn credentials_for(
+        &self,
+        oauth: &BackendOAuthConfig,
+        proxy: Option<&OutboundProxyConfig>,
+    ) -> Result<Arc<ClientCredentials>, HttpError> {
+        let client = format!(
+            "{} {} {}",
+            oauth.token_url,
+            oauth.client_id,
+            oauth.scopes.join(",")
+        );
+        let key = (client, proxy.cloned());
+        if let Some(credentials) = self.credentials.get(&key) {
+            return Ok(credentials.clone());
+        }
+
+        let credentials = ClientCredentials::new(oauth.clone(), proxy)
+            .map_err(|e| HttpError::OAuth(e.to_string()))?;
+        Ok(self.credentials.entry(key).or_insert(Arc::new(credentials)).clone())
+    }
+
+    /// Send request with custom headers and an optional per-request timeout
+    /// overriding the transport default
+    pub async fn send_request_with_options(
+        &self,
+        endpoint: &str,
+        request: crate::types::McpRequest,
+        headers: std::collections::HashMap<String, String>,
+        timeout: Option<Duration>,
+    ) -> Result<crate::types::McpResponse, HttpError> {
+        let transport = self.get_or_create(endpoint, None, None).await?;
+        Self::send_on(&transport, endpoint, request, headers, timeout).await
+    }
+
+    /// Send request on `transport`, applying header and timeout overrides
+    async fn send_on(
+        transport: &HttpTransport,
+        endpoint: &str,
+        request: crate::types::McpRequest,
+        headers: std::collections::HashMap<String, String>,
+        timeout: Option<Duration>,
+    ) -> Result<crate::types::McpResponse, HttpError> {
+        // If no overrides provided, use existing transport logic
+        if headers.is_empty() && timeout.is_none() {
             return transport.send_request(endpoint, request).await;
         }
 
-        // For requests with headers, we need to override the connection's headers
-        // This is a simplified approach - get pooled connection and send with custom headers
         let start = Instant::now();
+        let response = Self::post_on(transport, endpoint, &request, headers, timeout, None).await?;
+        transport.read_response(response, start).await
+    }
+
+    /// Post `request` on `transport` with header and timeout overrides,
+    /// signed when `signing` is set, returning the successful response
+    /// unread.
+    async fn post_on(
+        transport: &HttpTransport,
+        endpoint: &str,
+        request: &crate::types::McpRequest,
+        headers: std::collections::HashMap<String, String>,
+        timeout: Option<Duration>,
+        signing: Option<&RequestSigningConfig>,
+    ) -> Result<reqwest::Response, HttpError> {
+        // For requests with overrides, we need to override the connection's settings
+        // This is a simplified approach - get pooled connection and send with custom headers
 
         // Get pooled connection
         let conn = transport
@@ -544,37 +904,39 @@ impl HttpTransportPool {
         // Record attempt
         transport.metrics.request_count.fetch_add(1, Ordering::Relaxed);
 
-        // Build request with custom headers
+        // Build request with custom headers; the body is serialized here so
+        // a signature covers exactly what is sent
+        let body = serde_json::to_vec(request)?;
         let mut request_builder = conn
             .client
             .post(endpoint)
-            .json(&request)
-            .timeout(transport.config.request_timeout);
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .timeout(timeout.unwrap_or(transport.config.request_timeout));
 
         // Apply custom headers (these override any default headers)
         for (key, value) in &headers {
             request_builder = request_builder.header(key, value);
         }
+        if let Some(signing) = signing {
+            for (name, value) in signature_headers(signing, &body) {
+                request_builder = request_builder.header(name, value);
+            }
+        }
 
-        let result = request_builder.send().await;
-
-        match result {
+        match request_builder.body(body).send().await {
             Ok(response) => {
                 if !response.status().is_success() {
                     let status = response.status();
-                    let body =
-                        response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+                    let body = read_text(response, transport.config.max_response_bytes.get())
+                        .await
+                        .unwrap_or_else(|_| "Unknown error".to_string());
                     transport.metrics.error_count.fetch_add(1, Ordering::Relaxed);
+                    if status == StatusCode::UNAUTHORIZED {
+                        return Err(HttpError::Unauthorized(body));
+                    }
                     return Err(HttpError::ServerError(format!("{}: {}", status, body)));
                 }
-
-                let mcp_response: crate::types::McpResponse =
-                    response.json().await.map_err(|e| HttpError::InvalidResponse(e.to_string()))?;
-
-                let elapsed = start.elapsed().as_micros() as u64;
-                transport.metrics.total_latency_us.fetch_add(elapsed, Ordering::Relaxed);
-
-                Ok(mcp_response)
+                Ok(response)
             },
             Err(e) => {
                 transport.metrics.error_count.fetch_add(1, Ordering::Relaxed);
@@ -603,7 +965,7 @@ mod tests {
             ..Default::default()
         };
 
-        let manager = HttpConnectionManager::new(config);
+        let manager = HttpConnectionManager::new(config).unwrap();
         assert_eq!(manager.base_url, "http://localhost:8080");
     }
 
@@ -0,0 +1,93 @@
+//! Size limits on backend responses (`proxy.limits.max_response_bytes`).
+//!
+//! Bodies are read chunk by chunk and abandoned as soon as they pass the
+//! limit, so a misbehaving backend cannot stream gigabytes into memory.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use bytes::{Bytes, BytesMut};
+
+/// Largest backend response body, in bytes, the transports will read.
+///
+/// Cloned into every transport pool; a config reload updates the shared
+/// value, so transports that are already connected pick it up too.
+#[derive(Debug, Clone)]
+pub struct ResponseLimit(Arc<AtomicUsize>);
+
+impl ResponseLimit {
+    pub fn new(bytes: usize) -> Self {
+        Self(Arc::new(AtomicUsize::new(bytes)))
+    }
+
+    pub fn get(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn set(&self, bytes: usize) {
+        self.0.store(bytes, Ordering::Relaxed);
+    }
+}
+
+impl Default for ResponseLimit {
+    fn default() -> Self {
+        Self::new(crate::config::LimitsConfig::default().max_response_bytes)
+    }
+}
+
+/// Errors reading a size-limited response body
+#[derive(Debug, thiserror::Error)]
+pub enum BodyError {
+    #[error("Response exceeds {0} byte limit")]
+    TooLarge(usize),
+
+    #[error("Failed to read response body: {0}")]
+    Read(#[from] reqwest::Error),
+}
+
+/// Read `response`'s body, failing once it passes `limit` bytes.
+pub async fn read_body(mut response: reqwest::Response, limit: usize) -> Result<Bytes, BodyError> {
+    if response.content_length().is_some_and(|length| length > limit as u64) {
+        return Err(BodyError::TooLarge(limit));
+    }
+
+    let mut body = BytesMut::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > limit {
+            return Err(BodyError::TooLarge(limit));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body.freeze())
+}
+
+/// Read `response`'s body as text, failing once it passes `limit` bytes.
+pub async fn read_text(response: reqwest::Response, limit: usize) -> Result<String, BodyError> {
+    let body = read_body(response, limit).await?;
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(body: &'static str) -> reqwest::Response {
+        reqwest::Response::from(axum::http::Response::new(body))
+    }
+
+    #[tokio::test]
+    async fn test_read_body_stops_at_limit() {
+        assert_eq!(read_body(response("hello"), 5).await.unwrap(), "hello");
+        assert!(matches!(
+            read_body(response("hello!"), 5).await,
+            Err(BodyError::TooLarge(5))
+        ));
+
+        let limit = ResponseLimit::new(5);
+        limit.clone().set(6);
+        assert_eq!(
+            read_text(response("hello!"), limit.get()).await.unwrap(),
+            "hello!"
+        );
+    }
+}
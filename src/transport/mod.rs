@@ -5,15 +5,30 @@
 //! - HTTP (request-response)
 //! - SSE (server-sent events, legacy)
 //! - Streamable HTTP (modern MCP 2025-03-26 specification)
+//! - gRPC (JSON-RPC messages in unary calls)
+//! - Unix domain sockets (line-delimited JSON-RPC to local processes)
 //! - WebSocket (full-duplex)
 
+pub mod backend_logs;
+pub mod grpc;
 pub mod http;
+pub mod limits;
+pub mod npx;
+pub mod outbound_proxy;
+pub mod resources;
+pub mod sandbox;
+pub mod signing;
 pub mod sse;
 pub mod stdio;
 pub mod streamable_http;
+pub mod streaming;
+pub mod supervisor;
+pub mod tls;
+pub mod unix;
 pub mod websocket;
 
 // Re-export commonly used types
+pub use limits::ResponseLimit;
 pub use streamable_http::{
     StreamableHttpConfig, StreamableHttpTransport, StreamableHttpTransportPool,
 };
@@ -0,0 +1,284 @@
+//! Running NPX-launched STDIO servers directly with `node`.
+//!
+//! `npx -y <package>[@version] [args]` puts npx, a shell and a second node
+//! between the proxy and the server, which makes pipes and signals
+//! unreliable. [`find_installed`] instead looks for the package in the
+//! proxy's managed install directory and in npx's cache, checks each copy's
+//! `package.json` against the requested name and pinned version, and picks
+//! the newest match. With `proxy.npx.auto_install`, a missing package is
+//! first installed into the managed directory by [`install`]. When no copy
+//! fits, the server runs through plain npx.
+
+use std::cmp::Ordering;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::OnceLock;
+
+use tokio::process::Command;
+use tracing::debug;
+
+/// npx flags that may precede the package without changing what it runs.
+const PASSTHROUGH_FLAGS: [&str; 4] = ["-y", "--yes", "-q", "--quiet"];
+
+/// A package as written on the npx command line, `name[@version]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageSpec {
+    pub name: String,
+    /// Pinned version, `None` for whatever is installed
+    pub version: Option<String>,
+}
+
+impl PackageSpec {
+    /// Split `@scope/name@1.2.3` into name and version.
+    pub fn parse(spec: &str) -> Self {
+        match spec.rfind('@') {
+            Some(at) if at > 0 => Self {
+                name: spec[..at].to_string(),
+                version: Some(spec[at + 1..].to_string()).filter(|v| !v.is_empty()),
+            },
+            _ => Self {
+                name: spec.to_string(),
+                version: None,
+            },
+        }
+    }
+
+    /// Directory of this package under the managed install directory.
+    fn install_dir_name(&self) -> String {
+        format!(
+            "{}@{}",
+            self.name.replace('/', "+"),
+            self.version.as_deref().unwrap_or("latest")
+        )
+    }
+}
+
+impl std::fmt::Display for PackageSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.version {
+            Some(version) => write!(f, "{}@{}", self.name, version),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// The package and the server's own arguments of an npx command line, or
+/// `None` when it uses npx options that cannot be run directly.
+pub fn parse_args(args: &[String]) -> Option<(PackageSpec, Vec<String>)> {
+    let start = args.iter().position(|arg| !PASSTHROUGH_FLAGS.contains(&arg.as_str()))?;
+    let spec = &args[start];
+    if spec.starts_with('-') {
+        return None;
+    }
+    Some((PackageSpec::parse(spec), args[start + 1..].to_vec()))
+}
+
+/// An installed copy of a package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledPackage {
+    pub dir: PathBuf,
+    /// Version from its `package.json`
+    pub version: String,
+    /// Script to run with `node`
+    pub entry: PathBuf,
+}
+
+/// Newest copy of `spec` under `roots`, each holding install trees as
+/// `<root>/<any>/node_modules/<name>` (npx's cache and the managed
+/// directory alike). Copies whose `package.json` names another package or,
+/// when pinned, another version are skipped.
+pub fn find_installed(roots: &[PathBuf], spec: &PackageSpec) -> Option<InstalledPackage> {
+    let mut best: Option<InstalledPackage> = None;
+    for root in roots {
+        let Ok(entries) = std::fs::read_dir(root) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let dir = entry.path().join("node_modules").join(&spec.name);
+            let Some(package) = inspect(&dir, spec) else {
+                continue;
+            };
+            let newer = best.as_ref().map_or(true, |best| {
+                compare_versions(&package.version, &best.version).is_gt()
+            });
+            if newer {
+                best = Some(package);
+            }
+        }
+    }
+    best
+}
+
+/// `dir` as an installed copy of `spec`, if it is one.
+fn inspect(dir: &Path, spec: &PackageSpec) -> Option<InstalledPackage> {
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(dir.join("package.json")).ok()?).ok()?;
+    if manifest.get("name").and_then(|name| name.as_str()) != Some(spec.name.as_str()) {
+        debug!("Skipping {}: not {}", dir.display(), spec.name);
+        return None;
+    }
+    let version = manifest.get("version").and_then(|version| version.as_str())?;
+    if spec.version.as_deref().is_some_and(|pinned| pinned != version) {
+        debug!(
+            "Skipping {} {} in {}: pinned to {}",
+            spec.name,
+            version,
+            dir.display(),
+            spec.version.as_deref().unwrap_or_default()
+        );
+        return None;
+    }
+    Some(InstalledPackage {
+        dir: dir.to_path_buf(),
+        version: version.to_string(),
+        entry: entry_point(dir, &manifest, &spec.name)?,
+    })
+}
+
+/// Script npx would run: the package's binary named after it (or its only
+/// one), else its `main`, else `index.js`.
+fn entry_point(dir: &Path, manifest: &serde_json::Value, name: &str) -> Option<PathBuf> {
+    let unscoped = name.rsplit('/').next().unwrap_or(name);
+    let script = match manifest.get("bin") {
+        Some(serde_json::Value::String(bin)) => Some(bin.as_str()),
+        Some(serde_json::Value::Object(bins)) => bins
+            .get(unscoped)
+            .or_else(|| (bins.len() == 1).then(|| bins.values().next()).flatten())
+            .and_then(|bin| bin.as_str()),
+        _ => None,
+    };
+    let script = script
+        .or_else(|| manifest.get("main").and_then(|main| main.as_str()))
+        .unwrap_or("index.js");
+    let entry = dir.join(script);
+    entry.is_file().then_some(entry)
+}
+
+/// Order two versions by their numeric `major.minor.patch`, a pre-release
+/// before its release.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let key = |version: &str| {
+        let (release, pre) = match version.split_once('-') {
+            Some((release, _)) => (release, true),
+            None => (version, false),
+        };
+        let numbers: Vec<u64> = release.split('.').map(|n| n.parse().unwrap_or(0)).collect();
+        (numbers, !pre)
+    };
+    key(a).cmp(&key(b))
+}
+
+/// Where npx keeps the packages it installed.
+pub fn npx_cache_dir() -> Option<PathBuf> {
+    let output = std::process::Command::new("npm").args(["config", "get", "cache"]).output();
+    let cache = match output {
+        Ok(output) if output.status.success() => {
+            PathBuf::from(String::from_utf8_lossy(&output.stdout).trim())
+        },
+        _ => dirs::home_dir()?.join(".npm"),
+    };
+    Some(cache.join("_npx")).filter(|dir| dir.is_dir())
+}
+
+/// Managed install directory used when `proxy.npx.install_dir` is unset.
+pub fn default_install_dir() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join("only1mcp").join("npx"))
+}
+
+/// Install `spec` into its own directory under `install_dir`: `npm install`
+/// the first time, which writes a lockfile, then `npm ci` from it.
+pub async fn install(install_dir: &Path, spec: &PackageSpec) -> io::Result<()> {
+    // One install at a time, so pool processes do not race on a directory
+    static INSTALLING: OnceLock<tokio::sync::Mutex<()>> = OnceLock::new();
+    let _installing = INSTALLING.get_or_init(Default::default).lock().await;
+
+    let dir = install_dir.join(spec.install_dir_name());
+    if find_installed(&[install_dir.to_path_buf()], spec).is_some_and(|p| p.dir.starts_with(&dir)) {
+        return Ok(());
+    }
+    tokio::fs::create_dir_all(&dir).await?;
+    let manifest = dir.join("package.json");
+    if !manifest.exists() {
+        let package = serde_json::json!({
+            "private": true,
+            "dependencies": { &spec.name: spec.version.as_deref().unwrap_or("latest") },
+        });
+        tokio::fs::write(&manifest, package.to_string()).await?;
+    }
+    let subcommand = match dir.join("package-lock.json").exists() {
+        true => "ci",
+        false => "install",
+    };
+
+    let output = Command::new("npm")
+        .args([subcommand, "--omit=dev", "--no-audit", "--no-fund"])
+        .current_dir(&dir)
+        .stdin(Stdio::null())
+        .output()
+        .await?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "npm {} of {} failed: {}",
+            subcommand,
+            spec,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_npx_command_lines() {
+        let args = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+
+        let (spec, rest) = parse_args(&args(&[
+            "-y",
+            "@modelcontextprotocol/server-filesystem@0.6.2",
+            "/srv",
+        ]))
+        .unwrap();
+        assert_eq!(spec.name, "@modelcontextprotocol/server-filesystem");
+        assert_eq!(spec.version.as_deref(), Some("0.6.2"));
+        assert_eq!(rest, ["/srv"]);
+
+        assert_eq!(PackageSpec::parse("@scope/name").version, None);
+        assert_eq!(
+            PackageSpec::parse("name@latest").version.as_deref(),
+            Some("latest")
+        );
+        assert!(parse_args(&args(&["--package", "a", "b"])).is_none());
+        assert_eq!(compare_versions("1.10.0", "1.9.3"), Ordering::Greater);
+        assert_eq!(compare_versions("2.0.0-rc.1", "2.0.0"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_find_installed_checks_package_json() {
+        let root = tempfile::tempdir().unwrap();
+        let install = |tree: &str, name: &str, version: &str| {
+            let dir = root.path().join(tree).join("node_modules").join("@mcp/server");
+            std::fs::create_dir_all(dir.join("dist")).unwrap();
+            let manifest = serde_json::json!({
+                "name": name, "version": version, "bin": {"server": "dist/index.js"},
+            });
+            std::fs::write(dir.join("package.json"), manifest.to_string()).unwrap();
+            std::fs::write(dir.join("dist/index.js"), "").unwrap();
+        };
+        install("a1", "@mcp/server", "1.2.0");
+        install("b2", "@mcp/server", "1.10.0");
+        install("c3", "@other/server", "9.0.0");
+        let roots = [root.path().to_path_buf()];
+
+        let newest = find_installed(&roots, &PackageSpec::parse("@mcp/server")).unwrap();
+        assert_eq!(newest.version, "1.10.0");
+        assert!(newest.entry.ends_with("b2/node_modules/@mcp/server/dist/index.js"));
+
+        let pinned = find_installed(&roots, &PackageSpec::parse("@mcp/server@1.2.0")).unwrap();
+        assert_eq!(pinned.version, "1.2.0");
+        assert!(find_installed(&roots, &PackageSpec::parse("@mcp/server@2.0.0")).is_none());
+    }
+}
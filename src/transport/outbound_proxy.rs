@@ -0,0 +1,109 @@
+//! Outbound proxies for HTTP, SSE and Streamable HTTP backends
+//! (`proxy.outbound_proxy`, or `transport.proxy` per server).
+//!
+//! Requests to `http://` backends go through `http_proxy`, and connections
+//! to `https://` backends through `https_proxy`, tunnelled with CONNECT
+//! when it is an HTTP proxy. `all_proxy` covers either scheme otherwise.
+//! Each may be an `http://`, `https://`, `socks5://` or `socks5h://` URL
+//! (with `socks5h` the proxy resolves backend host names). `username` and
+//! `password` are sent as `Proxy-Authorization` to HTTP proxies and as
+//! SOCKS5 username/password authentication.
+//!
+//! Once a proxy block applies to a backend, the `HTTP_PROXY` family of
+//! environment variables is ignored for it; without one, they apply as
+//! before.
+
+use reqwest::{ClientBuilder, NoProxy, Proxy};
+
+use crate::config::OutboundProxyConfig;
+
+/// URL schemes accepted for proxies.
+pub const SCHEMES: [&str; 4] = ["http", "https", "socks5", "socks5h"];
+
+/// Builds the proxy for one scheme of backend URLs from a proxy URL.
+type Route = fn(&str) -> reqwest::Result<Proxy>;
+
+/// Route a backend HTTP client's connections through the proxies in
+/// `proxy`, connecting directly to hosts in `no_proxy`.
+pub fn configure_client(
+    builder: ClientBuilder,
+    proxy: &OutboundProxyConfig,
+) -> Result<ClientBuilder, String> {
+    // Drop the proxies taken from the environment
+    let mut builder = builder.no_proxy();
+    let no_proxy = NoProxy::from_string(&proxy.no_proxy.join(","));
+
+    let routes: [(&Option<String>, Route); 3] = [
+        (&proxy.http_proxy, |url| Proxy::http(url)),
+        (&proxy.https_proxy, |url| Proxy::https(url)),
+        (&proxy.all_proxy, |url| Proxy::all(url)),
+    ];
+    for (url, route) in routes {
+        let Some(url) = url else {
+            continue;
+        };
+        check_scheme(url)?;
+        let mut route = route(url).map_err(|e| format!("Invalid proxy {}: {}", url, e))?;
+        if let Some(username) = &proxy.username {
+            route = route.basic_auth(username, proxy.password.as_deref().unwrap_or_default());
+        }
+        builder = builder.proxy(route.no_proxy(no_proxy.clone()));
+    }
+
+    Ok(builder)
+}
+
+/// Fail unless `url` is a proxy URL with one of [`SCHEMES`].
+pub fn check_scheme(url: &str) -> Result<(), String> {
+    let parsed = url::Url::parse(url).map_err(|e| format!("Invalid proxy {}: {}", url, e))?;
+    if !SCHEMES.contains(&parsed.scheme()) {
+        return Err(format!(
+            "Proxy {} must use one of {}",
+            url,
+            SCHEMES.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proxy(yaml: &str) -> OutboundProxyConfig {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_proxy_schemes() {
+        for url in ["http://proxy:3128", "socks5h://proxy:1080", "https://proxy"] {
+            check_scheme(url).unwrap();
+        }
+        assert!(check_scheme("socks4://proxy:1080").unwrap_err().contains("socks5h"));
+        assert!(check_scheme("proxy:3128").is_err());
+    }
+
+    #[test]
+    fn test_client_configured() {
+        let proxies = proxy(
+            r#"
+            http_proxy: http://proxy:3128
+            all_proxy: socks5://proxy:1080
+            no_proxy: [localhost, 10.0.0.0/8]
+            username: user
+            password: secret
+            "#,
+        );
+        let builder = configure_client(reqwest::Client::builder(), &proxies).unwrap();
+        builder.build().unwrap();
+
+        // A block without URLs connects directly
+        configure_client(reqwest::Client::builder(), &proxy("{}"))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let invalid = proxy("https_proxy: ftp://proxy");
+        assert!(configure_client(reqwest::Client::builder(), &invalid).is_err());
+    }
+}
@@ -0,0 +1,203 @@
+//! Runtime resource limits of STDIO processes (`servers[].resources`).
+//!
+//! The proxy periodically samples every running STDIO process (see
+//! [`SystemMetricsCollector`](crate::metrics::SystemMetricsCollector)) and
+//! hands the usage to [`ResourceMonitor`], which keeps the latest figures
+//! for the Admin API and reports processes that stayed over their server's
+//! limits for `violation_seconds`. Those are killed and then handled like
+//! any other crash by the restart supervisor.
+
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use dashmap::DashMap;
+
+use crate::config::ResourceLimitsConfig;
+use crate::metrics::system::{ChildProcess, ProcessUsage};
+use crate::types::{ResourceUsage, ServerId};
+
+/// A process that stayed over a limit for too long.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LimitBreach {
+    pub process: ChildProcess,
+    /// `memory` or `cpu`
+    pub resource: &'static str,
+    /// What was exceeded, for logs
+    pub reason: String,
+}
+
+/// Since when a process has been over a limit.
+#[derive(Debug)]
+struct Violation {
+    pid: u32,
+    since: Instant,
+}
+
+/// Latest usage and limit violations of all STDIO processes.
+#[derive(Debug, Default)]
+pub struct ResourceMonitor {
+    /// Latest usage by server and pool slot
+    usage: DashMap<ServerId, BTreeMap<usize, ProcessUsage>>,
+    violations: DashMap<(ServerId, usize), Violation>,
+    /// Processes killed over limits, by server
+    kills: DashMap<ServerId, u64>,
+}
+
+impl ResourceMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one pass of samples taken at `now`, replacing the previous
+    /// one, and return the processes to kill. `limits` gives each server's
+    /// limits by ID; servers without an entry are not limited.
+    pub fn record<'a>(
+        &self,
+        samples: &[(ChildProcess, ProcessUsage)],
+        limits: impl Fn(&str) -> Option<&'a ResourceLimitsConfig>,
+        now: Instant,
+    ) -> Vec<LimitBreach> {
+        self.usage.clear();
+        let mut breaches = Vec::new();
+        let mut violating = Vec::new();
+        for (process, usage) in samples {
+            self.usage
+                .entry(process.server_id.clone())
+                .or_default()
+                .insert(process.slot, *usage);
+
+            let Some(limits) = limits(&process.server_id) else {
+                continue;
+            };
+            let Some((resource, reason)) = exceeded(limits, usage) else {
+                continue;
+            };
+            let key = (process.server_id.clone(), process.slot);
+            violating.push(key.clone());
+            let since = {
+                let mut violation = self.violations.entry(key.clone()).or_insert(Violation {
+                    pid: process.pid,
+                    since: now,
+                });
+                // A restarted process starts over
+                if violation.pid != process.pid {
+                    *violation = Violation {
+                        pid: process.pid,
+                        since: now,
+                    };
+                }
+                violation.since
+            };
+            if now.duration_since(since) < limits.violation_window() {
+                continue;
+            }
+
+            self.violations.remove(&key);
+            breaches.push(LimitBreach {
+                process: process.clone(),
+                resource,
+                reason,
+            });
+        }
+
+        self.violations.retain(|key, _| violating.contains(key));
+        for breach in &breaches {
+            *self.kills.entry(breach.process.server_id.clone()).or_default() += 1;
+        }
+        breaches
+    }
+
+    /// Usage of a server's processes as of the last pass, for the Admin
+    /// API. `None` when none of its processes were sampled and none were
+    /// ever killed.
+    pub fn usage(&self, server_id: &str) -> Option<ResourceUsage> {
+        let limit_kills = self.kills.get(server_id).map_or(0, |kills| *kills);
+        let Some(processes) = self.usage.get(server_id) else {
+            return (limit_kills > 0).then(|| ResourceUsage {
+                limit_kills,
+                ..Default::default()
+            });
+        };
+        let cpu: Vec<f64> = processes.values().filter_map(|usage| usage.cpu_percent).collect();
+        Some(ResourceUsage {
+            processes: processes.len(),
+            memory_bytes: processes.values().map(|usage| usage.memory_bytes).sum(),
+            cpu_percent: (!cpu.is_empty()).then(|| cpu.iter().sum()),
+            limit_kills,
+        })
+    }
+}
+
+/// The first limit `usage` exceeds, with a description.
+fn exceeded(limits: &ResourceLimitsConfig, usage: &ProcessUsage) -> Option<(&'static str, String)> {
+    if let Some(max_mb) = limits.max_memory_mb {
+        if usage.memory_bytes > max_mb * 1024 * 1024 {
+            return Some((
+                "memory",
+                format!(
+                    "{} MB resident exceeds {} MB",
+                    usage.memory_bytes / (1024 * 1024),
+                    max_mb
+                ),
+            ));
+        }
+    }
+    match (limits.max_cpu_percent, usage.cpu_percent) {
+        (Some(max), Some(percent)) if percent > max as f64 => {
+            Some(("cpu", format!("{:.0}% CPU exceeds {}%", percent, max)))
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn sample(pid: u32, memory_mb: u64, cpu_percent: f64) -> (ChildProcess, ProcessUsage) {
+        (
+            ChildProcess {
+                server_id: "fs".into(),
+                slot: 0,
+                pid,
+            },
+            ProcessUsage {
+                memory_bytes: memory_mb * 1024 * 1024,
+                cpu_percent: Some(cpu_percent),
+            },
+        )
+    }
+
+    #[test]
+    fn test_only_sustained_violations_are_breaches() {
+        let monitor = ResourceMonitor::new();
+        let limits = ResourceLimitsConfig {
+            max_memory_mb: Some(100),
+            max_cpu_percent: Some(50),
+            violation_seconds: 30,
+        };
+        let limits_for = |_: &str| Some(&limits);
+        let start = Instant::now();
+        let at = |seconds| start + Duration::from_secs(seconds);
+
+        assert!(monitor.record(&[sample(1, 200, 10.0)], limits_for, at(0)).is_empty());
+        // Back under the limit: the violation is forgotten
+        assert!(monitor.record(&[sample(1, 50, 10.0)], limits_for, at(10)).is_empty());
+        assert!(monitor.record(&[sample(1, 50, 90.0)], limits_for, at(20)).is_empty());
+        assert!(monitor.record(&[sample(1, 50, 90.0)], limits_for, at(40)).is_empty());
+
+        let breaches = monitor.record(&[sample(1, 50, 90.0)], limits_for, at(50));
+        assert_eq!(breaches.len(), 1);
+        assert_eq!(breaches[0].resource, "cpu");
+
+        // A new process in the slot gets its own window
+        assert!(monitor.record(&[sample(2, 200, 0.0)], limits_for, at(60)).is_empty());
+
+        let usage = monitor.usage("fs").unwrap();
+        assert_eq!(usage.processes, 1);
+        assert_eq!(usage.memory_bytes, 200 * 1024 * 1024);
+        assert_eq!(usage.limit_kills, 1);
+        assert!(monitor.usage("other").is_none());
+    }
+}
@@ -0,0 +1,186 @@
+//! Sandbox strategies for STDIO processes (`servers[].sandbox`).
+//!
+//! `bubblewrap` and `firejail` run the server through the sandboxing tool,
+//! so [`wrap`] rewrites the command line. `seccomp` reads a compiled BPF
+//! program with [`load_seccomp_profile`] before the process is spawned and
+//! installs it with [`install_seccomp_filter`] between fork and exec.
+
+#[cfg(target_os = "linux")]
+use std::io;
+
+use crate::config::{SandboxConfig, SandboxStrategy};
+
+/// Command line running `command` with `args` under `sandbox`, the same
+/// command line for strategies that do not wrap the process.
+pub fn wrap(
+    sandbox: &SandboxConfig,
+    command: &str,
+    args: &[String],
+    cwd: Option<&str>,
+) -> (String, Vec<String>) {
+    let mut wrapped = Vec::new();
+    let program = match sandbox.strategy {
+        SandboxStrategy::Bubblewrap => {
+            wrapped
+                .extend(["--die-with-parent", "--new-session", "--unshare-all"].map(String::from));
+            if sandbox.network {
+                wrapped.push("--share-net".to_string());
+            }
+            wrapped
+                .extend(["--proc", "/proc", "--dev", "/dev", "--tmpfs", "/tmp"].map(String::from));
+            // The working directory is readable unless it is writable
+            let read_only =
+                sandbox.read_only_paths.iter().map(String::as_str).chain(
+                    cwd.filter(|cwd| !sandbox.writable_paths.iter().any(|path| path == cwd)),
+                );
+            for path in read_only {
+                wrapped.extend(["--ro-bind-try", path, path].map(String::from));
+            }
+            for path in &sandbox.writable_paths {
+                wrapped.extend(["--bind-try", path, path].map(String::from));
+            }
+            if let Some(cwd) = cwd {
+                wrapped.extend(["--chdir", cwd].map(String::from));
+            }
+            "bwrap"
+        },
+        SandboxStrategy::Firejail => {
+            wrapped.extend(
+                [
+                    "--quiet",
+                    "--noprofile",
+                    "--nonewprivs",
+                    "--caps.drop=all",
+                    "--private-tmp",
+                    "--private-dev",
+                ]
+                .map(String::from),
+            );
+            if !sandbox.network {
+                wrapped.push("--net=none".to_string());
+            }
+            for path in &sandbox.read_only_paths {
+                wrapped.push(format!("--read-only={}", path));
+            }
+            for path in &sandbox.writable_paths {
+                wrapped.push(format!("--read-write={}", path));
+            }
+            "firejail"
+        },
+        SandboxStrategy::None | SandboxStrategy::Rlimit | SandboxStrategy::Seccomp => {
+            return (command.to_string(), args.to_vec());
+        },
+    };
+    wrapped.push("--".to_string());
+    wrapped.push(command.to_string());
+    wrapped.extend(args.iter().cloned());
+    (program.to_string(), wrapped)
+}
+
+/// Read a raw seccomp BPF program: `struct sock_filter` instructions in
+/// native byte order, as written by libseccomp's `seccomp_export_bpf`.
+#[cfg(target_os = "linux")]
+pub fn load_seccomp_profile(path: &str) -> io::Result<Vec<libc::sock_filter>> {
+    let bytes = std::fs::read(path)?;
+    const INSTRUCTION: usize = std::mem::size_of::<libc::sock_filter>();
+    if bytes.is_empty() || bytes.len() % INSTRUCTION != 0 || bytes.len() / INSTRUCTION > 4096 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} is not a seccomp BPF program", path),
+        ));
+    }
+    Ok(bytes
+        .chunks_exact(INSTRUCTION)
+        .map(|chunk| libc::sock_filter {
+            code: u16::from_ne_bytes([chunk[0], chunk[1]]),
+            jt: chunk[2],
+            jf: chunk[3],
+            k: u32::from_ne_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]),
+        })
+        .collect())
+}
+
+/// Install `filter` on the calling process. Meant for `pre_exec`, so it
+/// only makes async-signal-safe calls.
+#[cfg(target_os = "linux")]
+pub fn install_seccomp_filter(filter: &[libc::sock_filter]) -> io::Result<()> {
+    let program = libc::sock_fprog {
+        len: filter.len() as libc::c_ushort,
+        filter: filter.as_ptr() as *mut libc::sock_filter,
+    };
+    // SAFETY: program points to `filter`, which outlives both calls; no new
+    // privileges lets an unprivileged process install the filter
+    unsafe {
+        if libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) != 0
+            || libc::prctl(
+                libc::PR_SET_SECCOMP,
+                libc::SECCOMP_MODE_FILTER,
+                &program as *const libc::sock_fprog,
+            ) != 0
+        {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sandbox(strategy: SandboxStrategy) -> SandboxConfig {
+        SandboxConfig {
+            strategy,
+            read_only_paths: vec!["/usr".into()],
+            writable_paths: vec!["/srv/data".into()],
+            network: false,
+            seccomp_profile: None,
+        }
+    }
+
+    #[test]
+    fn test_wrapped_command_lines() {
+        let args = vec!["--stdio".to_string()];
+
+        let (program, wrapped) = wrap(
+            &sandbox(SandboxStrategy::Bubblewrap),
+            "server",
+            &args,
+            Some("/srv"),
+        );
+        assert_eq!(program, "bwrap");
+        assert_eq!(
+            wrapped.join(" "),
+            "--die-with-parent --new-session --unshare-all --proc /proc --dev /dev \
+             --tmpfs /tmp --ro-bind-try /usr /usr --ro-bind-try /srv /srv \
+             --bind-try /srv/data /srv/data --chdir /srv -- server --stdio"
+        );
+
+        let (program, wrapped) = wrap(&sandbox(SandboxStrategy::Firejail), "server", &args, None);
+        assert_eq!(program, "firejail");
+        assert!(wrapped.contains(&"--net=none".to_string()));
+        assert!(wrapped.contains(&"--read-write=/srv/data".to_string()));
+        assert_eq!(wrapped[wrapped.len() - 3..], ["--", "server", "--stdio"]);
+
+        let (program, wrapped) = wrap(&sandbox(SandboxStrategy::Rlimit), "server", &args, None);
+        assert_eq!((program.as_str(), wrapped), ("server", args));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_seccomp_profile_must_be_whole_instructions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("filter.bpf");
+
+        // BPF_RET | BPF_K, SECCOMP_RET_ALLOW
+        let mut allow_all = 0x06u16.to_ne_bytes().to_vec();
+        allow_all.extend([0, 0]);
+        allow_all.extend(0x7fff_0000u32.to_ne_bytes());
+        std::fs::write(&path, &allow_all).unwrap();
+        let filter = load_seccomp_profile(path.to_str().unwrap()).unwrap();
+        assert_eq!((filter.len(), filter[0].k), (1, 0x7fff_0000));
+
+        std::fs::write(&path, &allow_all[..5]).unwrap();
+        assert!(load_seccomp_profile(path.to_str().unwrap()).is_err());
+    }
+}
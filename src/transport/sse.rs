@@ -12,7 +12,10 @@ use reqwest::{Client, StatusCode};
 use std::sync::Arc;
 use std::time::Duration;
 use thiserror::Error;
+use tracing::warn;
 
+use crate::config::OutboundProxyConfig;
+use crate::transport::limits::{read_text, BodyError, ResponseLimit};
 use crate::types::{McpRequest, McpResponse};
 
 /// SSE transport errors
@@ -41,6 +44,46 @@ pub enum SseError {
     /// Server returned non-success status code
     #[error("Server error {0}: {1}")]
     ServerError(StatusCode, String),
+
+    /// Response body exceeded the configured limit
+    #[error("Response exceeds {0} byte limit")]
+    ResponseTooLarge(usize),
+}
+
+impl From<BodyError> for SseError {
+    fn from(err: BodyError) -> Self {
+        match err {
+            BodyError::TooLarge(limit) => SseError::ResponseTooLarge(limit),
+            BodyError::Read(e) => SseError::RequestFailed(e),
+        }
+    }
+}
+
+impl From<SseError> for crate::error::Error {
+    fn from(err: SseError) -> Self {
+        match err {
+            SseError::ResponseTooLarge(_) => crate::error::Error::ResponseTooLarge(err.to_string()),
+            _ => crate::error::Error::Transport(err.to_string()),
+        }
+    }
+}
+
+impl SseError {
+    /// Whether the connection or session to the server broke before the
+    /// request was processed, so it may be replayed on a fresh one.
+    ///
+    /// Only a failure to connect or a status rejecting the session qualifies:
+    /// other request errors can happen after the body was sent, and a
+    /// replayed `tools/call` could then run twice on the backend.
+    pub fn is_broken_session(&self) -> bool {
+        match self {
+            SseError::RequestFailed(e) => e.is_connect(),
+            SseError::ServerError(status, _) => {
+                *status == StatusCode::NOT_FOUND || *status == StatusCode::GONE
+            },
+            _ => false,
+        }
+    }
 }
 
 /// SSE transport configuration
@@ -54,6 +97,12 @@ pub struct SseTransportConfig {
 
     /// Custom HTTP headers (e.g., Accept, Content-Type, Authorization)
     pub headers: std::collections::HashMap<String, String>,
+
+    /// Largest response body read from the server
+    pub max_response_bytes: ResponseLimit,
+
+    /// Outbound proxy the server is reached through
+    pub proxy: Option<OutboundProxyConfig>,
 }
 
 impl Default for SseTransportConfig {
@@ -62,6 +111,8 @@ impl Default for SseTransportConfig {
             base_url: String::new(),
             request_timeout: Duration::from_secs(30),
             headers: std::collections::HashMap::new(),
+            max_response_bytes: ResponseLimit::default(),
+            proxy: None,
         }
     }
 }
@@ -89,12 +140,14 @@ impl SseTransport {
     /// # Returns
     ///
     /// * `Ok(SseTransport)` - Successfully created transport
-    /// * `Err(SseError)` - Failed to create HTTP client
+    /// * `Err(SseError)` - Failed to create HTTP client or configure its proxy
     pub async fn new(config: SseTransportConfig) -> Result<Self, SseError> {
-        let client = Client::builder()
-            .timeout(config.request_timeout)
-            .build()
-            .map_err(|e| SseError::ConnectionFailed(e.to_string()))?;
+        let mut builder = Client::builder().timeout(config.request_timeout);
+        if let Some(proxy) = &config.proxy {
+            builder = crate::transport::outbound_proxy::configure_client(builder, proxy)
+                .map_err(SseError::ConnectionFailed)?;
+        }
+        let client = builder.build().map_err(|e| SseError::ConnectionFailed(e.to_string()))?;
 
         Ok(Self { config, client })
     }
@@ -114,13 +167,24 @@ impl SseTransport {
         &self,
         endpoint: &str,
         request: McpRequest,
+    ) -> Result<McpResponse, SseError> {
+        self.send_request_with_timeout(endpoint, request, self.config.request_timeout)
+            .await
+    }
+
+    /// Send an MCP request with a timeout overriding `request_timeout`
+    pub async fn send_request_with_timeout(
+        &self,
+        endpoint: &str,
+        request: McpRequest,
+        timeout: Duration,
     ) -> Result<McpResponse, SseError> {
         // Build request with SSE headers
         let mut request_builder = self
             .client
             .post(endpoint)
             .json(&request)
-            .timeout(self.config.request_timeout)
+            .timeout(timeout)
             .header("Accept", "application/json, text/event-stream")
             .header("Content-Type", "application/json");
 
@@ -135,12 +199,14 @@ impl SseTransport {
         // Check status code
         if !response.status().is_success() {
             let status = response.status();
-            let body = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            let body = read_text(response, self.config.max_response_bytes.get())
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
             return Err(SseError::ServerError(status, body));
         }
 
         // Get response body as text (SSE format)
-        let body = response.text().await?;
+        let body = read_text(response, self.config.max_response_bytes.get()).await?;
 
         // Parse SSE format and extract JSON
         self.parse_sse_response(&body)
@@ -197,7 +263,7 @@ impl SseTransport {
         }
 
         // Parse JSON-RPC response
-        serde_json::from_str(&json_str)
+        crate::json::from_str(&json_str)
             .map_err(|e| SseError::InvalidJson(format!("{}: {}", e, json_str)))
     }
 }
@@ -207,8 +273,8 @@ impl SseTransport {
 /// Maintains a pool of SSE transports, one per unique endpoint. Supports
 /// per-request header customization for authentication and content negotiation.
 pub struct SseTransportPool {
-    /// Transports per endpoint (lazy initialization)
-    transports: dashmap::DashMap<String, Arc<SseTransport>>,
+    /// Transports per endpoint, headers and proxy (lazy initialization)
+    transports: dashmap::DashMap<(String, Option<OutboundProxyConfig>), Arc<SseTransport>>,
 
     /// Default configuration for new transports
     default_config: SseTransportConfig,
@@ -253,17 +319,17 @@ impl SseTransportPool {
         endpoint: &str,
         headers: std::collections::HashMap<String, String>,
     ) -> Result<Arc<SseTransport>, SseError> {
-        self.get_or_create_internal(endpoint, headers).await
+        self.get_or_create_internal(endpoint, headers, None).await
     }
 
-    /// Internal get_or_create implementation
-    async fn get_or_create_internal(
-        &self,
+    /// Cache key for a transport, including headers (for authentication
+    /// scenarios) and proxy
+    fn cache_key(
         endpoint: &str,
-        headers: std::collections::HashMap<String, String>,
-    ) -> Result<Arc<SseTransport>, SseError> {
-        // Create cache key that includes headers (for authentication scenarios)
-        let cache_key = if headers.is_empty() {
+        headers: &std::collections::HashMap<String, String>,
+        proxy: Option<&OutboundProxyConfig>,
+    ) -> (String, Option<OutboundProxyConfig>) {
+        let endpoint = if headers.is_empty() {
             endpoint.to_string()
         } else {
             // Include sorted headers in key for caching
@@ -271,6 +337,17 @@ impl SseTransportPool {
             header_keys.sort();
             format!("{}:{}", endpoint, header_keys.join(","))
         };
+        (endpoint, proxy.cloned())
+    }
+
+    /// Internal get_or_create implementation
+    async fn get_or_create_internal(
+        &self,
+        endpoint: &str,
+        headers: std::collections::HashMap<String, String>,
+        proxy: Option<&OutboundProxyConfig>,
+    ) -> Result<Arc<SseTransport>, SseError> {
+        let cache_key = Self::cache_key(endpoint, &headers, proxy);
 
         // Check if we already have a transport for this endpoint+headers combo
         if let Some(transport) = self.transports.get(&cache_key) {
@@ -282,6 +359,8 @@ impl SseTransportPool {
             base_url: endpoint.to_string(),
             request_timeout: self.default_config.request_timeout,
             headers,
+            max_response_bytes: self.default_config.max_response_bytes.clone(),
+            proxy: proxy.cloned(),
         };
 
         let transport = Arc::new(SseTransport::new(config).await?);
@@ -310,11 +389,57 @@ impl SseTransportPool {
         request: McpRequest,
         headers: std::collections::HashMap<String, String>,
     ) -> Result<McpResponse, SseError> {
+        self.send_request_with_options(endpoint, request, headers, None, None).await
+    }
+
+    /// Send request with custom headers and an optional per-request timeout
+    ///
+    /// If the connection or session turns out to be broken, the cached
+    /// transport is replaced and the request is replayed once.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - Full SSE endpoint URL
+    /// * `request` - MCP JSON-RPC request
+    /// * `headers` - Custom HTTP headers
+    /// * `timeout` - Overrides the transport's `request_timeout` when set
+    /// * `proxy` - Outbound proxy the endpoint is reached through
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(McpResponse)` - Parsed response
+    /// * `Err(SseError)` - Request or parsing failed
+    pub async fn send_request_with_options(
+        &self,
+        endpoint: &str,
+        request: McpRequest,
+        headers: std::collections::HashMap<String, String>,
+        timeout: Option<Duration>,
+        proxy: Option<&OutboundProxyConfig>,
+    ) -> Result<McpResponse, SseError> {
+        let timeout = timeout.unwrap_or(self.default_config.request_timeout);
+
         // Get or create transport
-        let transport = self.get_or_create_internal(endpoint, headers).await?;
+        let transport = self.get_or_create_internal(endpoint, headers.clone(), proxy).await?;
 
         // Send request
-        transport.send_request(endpoint, request).await
+        match transport.send_request_with_timeout(endpoint, request.clone(), timeout).await {
+            Err(e) if e.is_broken_session() => {
+                warn!(
+                    "Connection to {} broken ({}), replaying request on a new one",
+                    endpoint, e
+                );
+                self.transports.remove(&Self::cache_key(endpoint, &headers, proxy));
+                let transport = self.get_or_create_internal(endpoint, headers, proxy).await?;
+                let response =
+                    transport.send_request_with_timeout(endpoint, request, timeout).await?;
+                crate::metrics::BACKEND_SESSION_RECOVERIES_TOTAL
+                    .with_label_values(&["sse", endpoint])
+                    .inc();
+                Ok(response)
+            },
+            result => result,
+        }
     }
 
     /// Send request to a specific endpoint (no custom headers)
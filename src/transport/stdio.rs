@@ -4,18 +4,19 @@
 //! MCP protocol initialization handshake, and security sandboxing.
 
 use crate::error::Result;
+use crate::transport::supervisor::ProcessExit;
 use crate::types::{McpRequest, McpResponse, ServerId};
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::HashMap;
 use std::process::Stdio;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{Child, ChildStderr, ChildStdin, ChildStdout, Command};
-use tokio::sync::Mutex;
+use tokio::sync::{mpsc, oneshot, Mutex};
 use tracing::{debug, error, info, warn};
 
 #[derive(Debug, thiserror::Error)]
@@ -100,6 +101,23 @@ impl ServerCapabilities {
     pub fn supports_prompts(&self) -> bool {
         self.prompts.is_some()
     }
+
+    pub fn supports_logging(&self) -> bool {
+        self.logging.is_some()
+    }
+
+    /// Whether the server offers the protocol surface `method` belongs to.
+    /// Methods outside the tools, resources, prompts and logging surfaces
+    /// are not gated by capabilities.
+    pub fn supports_method(&self, method: &str) -> bool {
+        match method.split('/').next() {
+            Some("tools") => self.supports_tools(),
+            Some("resources") => self.supports_resources(),
+            Some("prompts") => self.supports_prompts(),
+            Some("logging") => self.supports_logging(),
+            _ => true,
+        }
+    }
 }
 
 /// Configuration for STDIO transport.
@@ -111,16 +129,49 @@ pub struct StdioConfig {
     pub args: Vec<String>,
     /// Environment variables
     pub env: HashMap<String, String>,
+    /// Variables of the proxy's environment the process inherits, all of
+    /// them when `None`
+    #[serde(default)]
+    pub inherit_env: Option<Vec<String>>,
+    /// Values replaced in the process's logged stderr
+    #[serde(skip)]
+    pub redacted_values: Vec<String>,
     /// Working directory
     pub cwd: Option<String>,
     /// Request timeout in milliseconds
     pub timeout_ms: u64,
     /// Maximum memory in MB
     pub max_memory_mb: Option<u64>,
-    /// Maximum CPU percentage
+    /// Maximum CPU percentage of one core, enforced while the process runs
+    /// (see `crate::transport::resources`)
     pub max_cpu_percent: Option<u32>,
-    /// Enable security sandbox
+    /// Drop root and apply resource limits
     pub sandbox: bool,
+    /// Sandbox strategy and its restrictions (see `crate::transport::sandbox`)
+    #[serde(default)]
+    pub isolation: crate::config::SandboxConfig,
+    /// How an `npx` command is resolved (see `crate::transport::npx`)
+    #[serde(default)]
+    pub npx: crate::config::NpxConfig,
+    /// Capture of the process's output into the server's log file, none
+    /// when `None`
+    #[serde(default)]
+    pub backend_logs: Option<crate::config::BackendLogsConfig>,
+    /// Number of processes to run for this server
+    #[serde(default = "default_pool_size")]
+    pub pool_size: usize,
+    /// Longest stdout line read from the server; longer messages are
+    /// discarded and the request they answer fails
+    #[serde(default = "default_max_line_bytes")]
+    pub max_line_bytes: usize,
+}
+
+fn default_pool_size() -> usize {
+    1
+}
+
+fn default_max_line_bytes() -> usize {
+    crate::config::LimitsConfig::default().max_stdio_line_bytes
 }
 
 impl Default for StdioConfig {
@@ -129,27 +180,80 @@ impl Default for StdioConfig {
             command: String::new(),
             args: Vec::new(),
             env: HashMap::new(),
+            inherit_env: None,
+            redacted_values: Vec::new(),
             cwd: None,
             timeout_ms: 30000,
             max_memory_mb: Some(512),
             max_cpu_percent: Some(50),
             sandbox: true,
+            isolation: crate::config::SandboxConfig::default(),
+            npx: crate::config::NpxConfig::default(),
+            backend_logs: None,
+            pool_size: default_pool_size(),
+            max_line_bytes: default_max_line_bytes(),
         }
     }
 }
 
+impl StdioConfig {
+    /// Settings for running `server`'s process with its `resources` limits,
+    /// `sandbox` and `env_policy`, under `config`'s pool size and line
+    /// limit. `None` unless it is a STDIO or Docker backend.
+    pub fn for_server(
+        server: &crate::config::McpServerConfig,
+        config: &crate::config::Config,
+    ) -> Option<Self> {
+        let process = server.process_command()?;
+        // Containers are isolated by Docker
+        let isolation = match process.sandbox {
+            true => server.sandbox.clone(),
+            false => crate::config::SandboxConfig {
+                strategy: crate::config::SandboxStrategy::None,
+                ..Default::default()
+            },
+        };
+        let sandboxed = isolation.strategy != crate::config::SandboxStrategy::None;
+        let max_memory_mb = match sandboxed {
+            true => Some(server.resources.max_memory_mb.unwrap_or(512)),
+            false => server.resources.max_memory_mb,
+        };
+        Some(Self {
+            command: process.command,
+            args: process.args,
+            env: process.env,
+            inherit_env: server.env_policy.inherited().map(<[String]>::to_vec),
+            redacted_values: server.redacted_env_values(),
+            cwd: process.cwd,
+            timeout_ms: server.request_timeout_ms(),
+            max_memory_mb,
+            max_cpu_percent: server.resources.max_cpu_percent,
+            sandbox: sandboxed,
+            isolation,
+            npx: config.proxy.npx.clone(),
+            backend_logs: Some(config.observability.backend_logs.clone()),
+            pool_size: config.proxy.connection_pool.stdio_processes_per_server,
+            max_line_bytes: config.proxy.limits.max_stdio_line_bytes,
+        })
+    }
+}
+
 /// STDIO transport handler managing process lifecycle and MCP protocol.
+///
+/// Each server gets a pool of `pool_size` processes. Requests are dispatched
+/// round-robin; a process handles one request at a time, so the pool size
+/// bounds per-server concurrency.
 pub struct StdioTransport {
-    /// Active STDIO processes
-    processes: Arc<DashMap<ServerId, Arc<StdioProcess>>>,
+    /// Process pools per server
+    pools: Arc<DashMap<ServerId, Arc<ProcessPool>>>,
     /// Connection state per server
     connection_states: Arc<DashMap<ServerId, StdioConnectionState>>,
     /// Server capabilities per server (from initialize response)
     server_capabilities: Arc<DashMap<ServerId, ServerCapabilities>>,
-    /// Initialization locks per server (prevent concurrent init)
-    init_locks: Arc<DashMap<ServerId, Arc<Mutex<()>>>>,
     /// Process metrics
     metrics: Arc<ProcessMetrics>,
+    /// Per-server files of process output
+    logs: Arc<crate::transport::backend_logs::BackendLogs>,
 }
 
 impl Default for StdioTransport {
@@ -162,11 +266,11 @@ impl StdioTransport {
     /// Create a new STDIO transport handler.
     pub fn new() -> Self {
         Self {
-            processes: Arc::new(DashMap::new()),
+            pools: Arc::new(DashMap::new()),
             connection_states: Arc::new(DashMap::new()),
             server_capabilities: Arc::new(DashMap::new()),
-            init_locks: Arc::new(DashMap::new()),
             metrics: Arc::new(ProcessMetrics::default()),
+            logs: Arc::new(crate::transport::backend_logs::BackendLogs::new()),
         }
     }
 
@@ -198,23 +302,15 @@ impl StdioTransport {
             }
         });
 
-        process.send_json(&init_request).await?;
-        debug!("Sent initialize request to {}", server_id);
-
-        // WORKAROUND: NPX-based processes need time to fully initialize their I/O streams
-        // before they're ready to receive requests. Without this delay, we may try to read
-        // from stdout before the final process in the chain (npx → sh → node → server) has
-        // set up its stdio properly, resulting in broken pipe or connection closed errors.
-        tokio::time::sleep(Duration::from_millis(300)).await;
-
-        // Step 2: Read initialize response with timeout
-        let init_response = tokio::time::timeout(Duration::from_secs(30), process.receive_json())
-            .await
-            .map_err(|_| {
-                TransportError::InitializationFailed(
+        // Step 2: Send it and wait for the response
+        debug!("Sending initialize request to {}", server_id);
+        let init_response =
+            process.call(init_request, Duration::from_secs(30)).await.map_err(|e| match e {
+                TransportError::Timeout => TransportError::InitializationFailed(
                     "Timeout waiting for initialize response".into(),
-                )
-            })??;
+                ),
+                e => e,
+            })?;
 
         // Step 3: Validate response
         if init_response.get("jsonrpc") != Some(&json!("2.0")) {
@@ -267,57 +363,110 @@ impl StdioTransport {
         Ok(server_capabilities)
     }
 
-    /// Initialize a STDIO connection with full MCP handshake (with retries).
-    async fn initialize_stdio_connection(
+    /// Spawn a process and complete the MCP handshake (with retries).
+    async fn start_process(
         &self,
         server_id: &str,
         config: &StdioConfig,
-    ) -> std::result::Result<(), TransportError> {
-        // Set state to Initializing
+    ) -> std::result::Result<Arc<StdioProcess>, TransportError> {
         self.connection_states
-            .insert(server_id.to_string(), StdioConnectionState::Initializing);
+            .entry(server_id.to_string())
+            .or_insert(StdioConnectionState::Initializing);
 
         // Perform handshake with retry logic
         let mut attempts = 0;
         const MAX_RETRIES: u32 = 3;
 
-        let capabilities = loop {
-            // Spawn new process for this attempt (or get existing healthy one)
-            let process = self.get_or_create_process(server_id.to_string(), config).await?;
+        loop {
+            // Spawn a fresh process for each attempt; a failed one is killed on drop
+            let process = self.spawn_process(server_id.to_string(), config).await?;
 
             match self.initialize_connection(server_id, &process).await {
-                Ok(caps) => break caps,
+                Ok(capabilities) => {
+                    self.server_capabilities.insert(server_id.to_string(), capabilities);
+                    self.connection_states
+                        .insert(server_id.to_string(), StdioConnectionState::Ready);
+                    info!("STDIO server {} initialized successfully", server_id);
+                    return Ok(process);
+                },
                 Err(e) if attempts < MAX_RETRIES => {
                     attempts += 1;
                     warn!(
                         "Initialization attempt {} failed for {}: {}. Retrying...",
                         attempts, server_id, e
                     );
-
-                    // Remove failed process so next attempt spawns a new one
-                    self.processes.remove(&server_id.to_string());
-
                     tokio::time::sleep(Duration::from_millis(500 * attempts as u64)).await;
                 },
                 Err(e) => {
-                    self.connection_states
-                        .insert(server_id.to_string(), StdioConnectionState::Closed);
-                    self.processes.remove(&server_id.to_string());
+                    // Other processes in the pool may still be serving requests
+                    self.connection_states.alter(server_id, |_, state| match state {
+                        StdioConnectionState::Ready => state,
+                        _ => StdioConnectionState::Closed,
+                    });
                     self.metrics.init_failures.fetch_add(1, Ordering::Relaxed);
                     return Err(e);
                 },
             }
-        };
+        }
+    }
 
-        // Store capabilities
-        self.server_capabilities.insert(server_id.to_string(), capabilities);
+    /// Pool for `server_id`, created (or resized) to hold `size` processes.
+    fn pool_for(&self, server_id: &str, size: usize) -> Arc<ProcessPool> {
+        let size = size.max(1);
+        let mut pool = self
+            .pools
+            .entry(server_id.to_string())
+            .or_insert_with(|| Arc::new(ProcessPool::new(size)));
 
-        // Update state to Ready
-        self.connection_states
-            .insert(server_id.to_string(), StdioConnectionState::Ready);
+        if pool.slots.len() != size {
+            // Processes of the old pool are killed once in-flight requests drop them
+            info!(
+                "Resizing STDIO pool for {} from {} to {} process(es)",
+                server_id,
+                pool.slots.len(),
+                size
+            );
+            *pool = Arc::new(ProcessPool::new(size));
+        }
+        pool.clone()
+    }
 
-        info!("STDIO server {} initialized successfully", server_id);
-        Ok(())
+    /// Initialized process for a pool slot, (re)starting it if needed.
+    async fn ready_process(
+        &self,
+        server_id: &str,
+        slot_index: usize,
+        slot: &PoolSlot,
+        config: &StdioConfig,
+    ) -> std::result::Result<Arc<StdioProcess>, TransportError> {
+        // Holding the slot lock keeps concurrent requests from starting it twice
+        let mut current = slot.process.lock().await;
+        if let Some(process) = current.as_ref() {
+            if process.is_healthy().await {
+                // Counts as use so the idle reaper leaves it alone
+                process.touch();
+                return Ok(process.clone());
+            }
+            warn!(
+                "Restarting unhealthy STDIO process {} for server {}",
+                slot_index, server_id
+            );
+            *current = None;
+        }
+
+        let start = std::time::Instant::now();
+        let process = self.start_process(server_id, config).await?;
+        let duration = start.elapsed();
+        debug!(
+            "Initialization took {:?} for {} (process {})",
+            duration, server_id, slot_index
+        );
+        self.metrics
+            .init_duration_sum
+            .fetch_add(duration.as_millis() as u64, Ordering::Relaxed);
+
+        *current = Some(process.clone());
+        Ok(process)
     }
 
     /// Send a request to a STDIO MCP server with explicit config.
@@ -327,54 +476,148 @@ impl StdioTransport {
         config: &StdioConfig,
         request: McpRequest,
     ) -> std::result::Result<McpResponse, TransportError> {
-        // Check if connection needs initialization
-        let needs_init = {
-            let state = self.connection_states.get(&server_id);
-            state.map(|s| *s.value() != StdioConnectionState::Ready).unwrap_or(true)
+        let pool = self.pool_for(&server_id, config.pool_size);
+        let (slot_index, slot) = pool.next_slot();
+
+        let result = match self.ready_process(&server_id, slot_index, slot, config).await {
+            Ok(process) => process.exchange(&request, config.timeout_ms).await,
+            Err(e) => Err(e),
         };
 
-        if needs_init {
-            // Acquire initialization lock to prevent concurrent initialization
-            let init_lock = self
-                .init_locks
-                .entry(server_id.clone())
-                .or_insert_with(|| Arc::new(Mutex::new(())));
-            let _guard = init_lock.lock().await;
-
-            // Double-check state after acquiring lock (another task may have initialized)
-            let state = self.connection_states.get(&server_id);
-            if state.map(|s| *s.value() != StdioConnectionState::Ready).unwrap_or(true) {
-                // Perform initialization
-                let start = std::time::Instant::now();
-                self.initialize_stdio_connection(&server_id, config).await?;
-                let duration = start.elapsed();
-                debug!("Initialization took {:?} for {}", duration, server_id);
-                self.metrics
-                    .init_duration_sum
-                    .fetch_add(duration.as_millis() as u64, Ordering::Relaxed);
-            }
+        match &result {
+            Ok(_) => {
+                slot.record_success();
+                self.metrics.requests_sent.fetch_add(1, Ordering::Relaxed);
+                self.metrics.responses_received.fetch_add(1, Ordering::Relaxed);
+            },
+            Err(e) => {
+                warn!(
+                    "STDIO request to {} (process {}) failed: {}",
+                    server_id, slot_index, e
+                );
+                slot.record_failure(&pool);
+                self.metrics.errors.fetch_add(1, Ordering::Relaxed);
+            },
         }
+        result
+    }
 
-        // Get process
-        let process = self.processes.get(&server_id).ok_or(TransportError::ProcessUnhealthy)?;
+    /// Capabilities the server reported during its handshake, starting a
+    /// process first if none has been initialized yet.
+    pub async fn capabilities_with_config(
+        &self,
+        server_id: ServerId,
+        config: &StdioConfig,
+    ) -> std::result::Result<ServerCapabilities, TransportError> {
+        if let Some(capabilities) = self.server_capabilities.get(&server_id) {
+            return Ok(capabilities.clone());
+        }
 
-        // Send request as JSON-RPC
-        let request_json = serde_json::to_value(&request)?;
-        process.send_json(&request_json).await?;
+        let pool = self.pool_for(&server_id, config.pool_size);
+        let (slot_index, slot) = pool.next_slot();
+        self.ready_process(&server_id, slot_index, slot, config).await?;
 
-        // Read response with timeout
-        let response_json = tokio::time::timeout(
-            Duration::from_millis(config.timeout_ms),
-            process.receive_json(),
-        )
-        .await
-        .map_err(|_| TransportError::Timeout)??;
+        Ok(self
+            .server_capabilities
+            .get(&server_id)
+            .map(|capabilities| capabilities.clone())
+            .unwrap_or_default())
+    }
 
-        // Parse response
-        let response: McpResponse = serde_json::from_value(response_json)?;
+    /// Start and initialize every process of a server's pool ahead of its
+    /// first request.
+    pub async fn prestart_with_config(
+        &self,
+        server_id: ServerId,
+        config: &StdioConfig,
+    ) -> std::result::Result<(), TransportError> {
+        let pool = self.pool_for(&server_id, config.pool_size);
+        for (slot_index, slot) in pool.slots.iter().enumerate() {
+            self.ready_process(&server_id, slot_index, slot, config).await?;
+        }
+        Ok(())
+    }
 
-        self.metrics.requests_sent.fetch_add(1, Ordering::Relaxed);
-        Ok(response)
+    /// Start the process of one pool slot again after it exited.
+    pub async fn restart_slot(
+        &self,
+        server_id: &str,
+        slot_index: usize,
+        config: &StdioConfig,
+    ) -> std::result::Result<(), TransportError> {
+        let pool = self.pool_for(server_id, config.pool_size);
+        // The pool may have shrunk since the process exited
+        let Some(slot) = pool.slots.get(slot_index) else {
+            return Ok(());
+        };
+        self.ready_process(server_id, slot_index, slot, config).await.map(|_| ())
+    }
+
+    /// Remove pooled processes that exited or closed stdout on their own.
+    ///
+    /// Processes stopped by the proxy are already out of their slot and are
+    /// not reported. A removed process still running is killed.
+    pub async fn take_exited(&self) -> Vec<ProcessExit> {
+        let pools: Vec<_> = self
+            .pools
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect();
+
+        let mut exited = Vec::new();
+        for (server_id, pool) in pools {
+            for (index, slot) in pool.slots.iter().enumerate() {
+                // A locked slot is in use; its next request replaces a dead process
+                let Ok(mut current) = slot.process.try_lock() else {
+                    continue;
+                };
+                let Some(process) = current.as_ref() else {
+                    continue;
+                };
+                if process.is_healthy().await {
+                    continue;
+                }
+                let status = process.exit_status().await;
+                let stopped = match status {
+                    Some(_) => Ok(()),
+                    None => process.kill().await,
+                };
+                if let Err(e) = stopped {
+                    warn!("Failed to stop STDIO process for {}: {}", server_id, e);
+                }
+                *current = None;
+                exited.push(ProcessExit {
+                    server_id: server_id.clone(),
+                    slot: index,
+                    status,
+                });
+            }
+        }
+        exited
+    }
+
+    /// Health of each pooled process for a server (empty if none started).
+    pub async fn process_status(&self, server_id: &str) -> Vec<StdioProcessStatus> {
+        let Some(pool) = self.pools.get(server_id).map(|pool| pool.clone()) else {
+            return Vec::new();
+        };
+
+        let mut statuses = Vec::with_capacity(pool.slots.len());
+        for (index, slot) in pool.slots.iter().enumerate() {
+            let process = slot.process.lock().await.clone();
+            let running = match &process {
+                Some(process) => process.is_healthy().await,
+                None => false,
+            };
+            statuses.push(StdioProcessStatus {
+                slot: index,
+                pid: process.and_then(|p| p.pid),
+                running,
+                consecutive_failures: slot.failures.load(Ordering::Relaxed),
+                requests: slot.requests.load(Ordering::Relaxed),
+            });
+        }
+        statuses
     }
 
     /// Send a request to a STDIO MCP server (convenience method using default config).
@@ -389,41 +632,69 @@ impl StdioTransport {
             args: vec![],
             cwd: None,
             env: HashMap::new(),
+            inherit_env: None,
+            redacted_values: Vec::new(),
             timeout_ms: 30000,
             max_memory_mb: None,
             max_cpu_percent: None,
             sandbox: true,
+            isolation: crate::config::SandboxConfig::default(),
+            npx: crate::config::NpxConfig::default(),
+            backend_logs: None,
+            pool_size: 1,
+            max_line_bytes: default_max_line_bytes(),
         };
 
         self.send_request_with_config(server_id.to_string(), &config, request).await
     }
 
-    /// Resolve NPX package to direct node command.
-    ///
-    /// Converts: `npx -y @modelcontextprotocol/server-NAME`
-    /// To: `node /path/to/cache/server-NAME/index.js`
+    /// Resolve an `npx` command to `node` running an installed copy of the
+    /// package (see [`crate::transport::npx`]), installing it first with
+    /// `auto_install`. `None` runs the command through npx as configured.
     ///
     /// This eliminates the multi-process chain complexity (npx → node → sh → server)
     /// and provides more reliable pipe management in sandboxed environments.
-    fn resolve_npx_to_node(config: &StdioConfig) -> Option<StdioConfig> {
-        // Check if this is an NPX command
-        if config.command != "npx" {
+    async fn resolve_npx_to_node(config: &StdioConfig) -> Option<StdioConfig> {
+        use crate::transport::npx;
+
+        if config.command != "npx" || !config.npx.resolve {
             return None;
         }
+        let Some((spec, server_args)) = npx::parse_args(&config.args) else {
+            warn!(
+                "Running {:?} through npx: its options cannot be resolved",
+                config.args
+            );
+            return None;
+        };
 
-        // Extract package name from args
-        // Expected: ["-y", "@modelcontextprotocol/server-NAME"] or similar
-        let package_name = config.args.iter().find(|arg| !arg.starts_with('-'))?;
-
-        debug!("Attempting to resolve NPX package: {}", package_name);
-
-        // Try to find the package in NPX cache
-        let cache_path = Self::find_npx_package(package_name)?;
+        let install_dir = config.npx.install_dir.clone().or_else(npx::default_install_dir);
+        let roots: Vec<_> = install_dir.iter().cloned().chain(npx::npx_cache_dir()).collect();
+        let mut package = npx::find_installed(&roots, &spec);
+        if let (None, true, Some(install_dir)) = (&package, config.npx.auto_install, &install_dir) {
+            info!(
+                "Installing NPX package {} into {}",
+                spec,
+                install_dir.display()
+            );
+            match npx::install(install_dir, &spec).await {
+                Ok(()) => package = npx::find_installed(std::slice::from_ref(install_dir), &spec),
+                Err(e) => warn!("Failed to install NPX package {}: {}", spec, e),
+            }
+        }
+        let Some(package) = package else {
+            warn!(
+                "No installed copy of NPX package {} found; running it through npx",
+                spec
+            );
+            return None;
+        };
 
         info!(
-            "Resolved NPX package {} to: {}",
-            package_name,
-            cache_path.display()
+            "Resolved NPX package {} to {} {}",
+            spec,
+            package.version,
+            package.entry.display()
         );
 
         // Create new config with node command
@@ -431,147 +702,26 @@ impl StdioTransport {
         // 1. They're from npm registry (trusted source)
         // 2. Node.js worker threads need unrestricted process limits
         // 3. RLIMIT_NPROC even at 50 can cause uv_thread_create failures
+        let mut args = vec![package.entry.to_string_lossy().to_string()];
+        args.extend(server_args);
         Some(StdioConfig {
             command: "node".to_string(),
-            args: vec![cache_path.to_string_lossy().to_string()],
-            env: config.env.clone(),
-            timeout_ms: config.timeout_ms,
-            max_memory_mb: config.max_memory_mb,
-            max_cpu_percent: config.max_cpu_percent,
+            args,
             sandbox: false, // Disable sandbox for NPX packages
-            cwd: config.cwd.clone(),
+            // An explicitly chosen sandboxing tool still applies
+            ..config.clone()
         })
     }
 
-    /// Find NPX package in cache.
-    fn find_npx_package(package_name: &str) -> Option<std::path::PathBuf> {
-        // Get npm cache directory
-        let cache_dir = Self::get_npm_cache_dir()?;
-        let npx_cache = cache_dir.join("_npx");
-
-        if !npx_cache.exists() {
-            warn!("NPX cache directory not found: {}", npx_cache.display());
-            return None;
-        }
-
-        // Extract package name without scope
-        // @modelcontextprotocol/server-NAME -> server-NAME
-        let package_name_only = package_name.split('/').next_back().unwrap_or(package_name);
-
-        debug!(
-            "Searching for package: {} in {}",
-            package_name_only,
-            npx_cache.display()
-        );
-
-        // Search patterns for the entry point
-        let search_patterns = vec![
-            format!("{}*/**/index.js", package_name_only),
-            format!("{}*/**/dist/index.js", package_name_only),
-            format!("{}/index.js", package_name_only),
-            format!("{}/dist/index.js", package_name_only),
-        ];
-
-        // Try each pattern
-        for pattern in search_patterns {
-            if let Some(path) = Self::glob_find(&npx_cache, &pattern) {
-                debug!("Found via pattern '{}': {}", pattern, path.display());
-                return Some(path);
-            }
-        }
-
-        warn!("Could not find entry point for package: {}", package_name);
-        None
-    }
-
-    /// Get npm cache directory.
-    fn get_npm_cache_dir() -> Option<std::path::PathBuf> {
-        use std::path::PathBuf;
-        use std::process::Command;
-
-        // Try to get from npm config
-        let output = Command::new("npm").args(["config", "get", "cache"]).output().ok()?;
-
-        if output.status.success() {
-            let cache_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            let cache_path = PathBuf::from(cache_str);
-
-            if cache_path.exists() {
-                debug!("NPM cache directory: {}", cache_path.display());
-                return Some(cache_path);
-            }
-        }
-
-        // Fallback to default location
-        let home = std::env::var("HOME").ok()?;
-        let default_cache = PathBuf::from(home).join(".npm");
-
-        if default_cache.exists() {
-            debug!("Using default NPM cache: {}", default_cache.display());
-            return Some(default_cache);
-        }
-
-        None
-    }
-
-    /// Helper to check if a path matches a glob pattern (reduces nesting).
-    fn matches_glob_pattern(path_str: &str, pattern_parts: &[&str]) -> bool {
-        let mut pos = 0;
-        for part in pattern_parts {
-            if let Some(idx) = path_str[pos..].find(part) {
-                pos += idx + part.len();
-            } else {
-                return false;
-            }
-        }
-        true
-    }
-
-    /// Simple glob-like search for files matching a pattern.
-    fn glob_find(base: &std::path::Path, pattern: &str) -> Option<std::path::PathBuf> {
-        use walkdir::WalkDir;
-
-        // Convert glob pattern to matching logic
-        let pattern_parts: Vec<&str> = pattern.split("**").collect();
-
-        for entry in WalkDir::new(base).max_depth(10).follow_links(false).into_iter().flatten() {
-            let path = entry.path();
-            let path_str = path.to_string_lossy();
-
-            // Simple pattern matching
-            let matches = if pattern.contains("**") {
-                // Check if path contains all pattern parts in order
-                Self::matches_glob_pattern(&path_str, &pattern_parts)
-            } else {
-                path_str.ends_with(pattern)
-            };
-
-            if matches && path.is_file() {
-                return Some(path.to_path_buf());
-            }
-        }
-
-        None
-    }
-
-    /// Get existing or spawn new STDIO process.
-    async fn get_or_create_process(
+    /// Spawn a new STDIO process (not yet initialized).
+    async fn spawn_process(
         &self,
         server_id: ServerId,
         config: &StdioConfig,
     ) -> std::result::Result<Arc<StdioProcess>, TransportError> {
-        // Check if process exists and is healthy
-        if let Some(process) = self.processes.get(&server_id) {
-            if process.is_healthy().await {
-                return Ok(process.clone());
-            }
-            // Process unhealthy, remove it
-            self.processes.remove(&server_id);
-            warn!("Removed unhealthy process for server {}", server_id);
-        }
-
         // Try to resolve NPX to node if applicable
-        let resolved_config = Self::resolve_npx_to_node(config).unwrap_or_else(|| config.clone());
+        let resolved_config =
+            Self::resolve_npx_to_node(config).await.unwrap_or_else(|| config.clone());
 
         if resolved_config.command != config.command {
             info!(
@@ -581,10 +731,29 @@ impl StdioTransport {
             );
         }
 
+        // Run through the sandboxing tool, if any
+        let isolation = &resolved_config.isolation;
+        let (program, args) = crate::transport::sandbox::wrap(
+            isolation,
+            &resolved_config.command,
+            &resolved_config.args,
+            resolved_config.cwd.as_deref(),
+        );
+        if program != resolved_config.command && which::which(&program).is_err() {
+            return Err(TransportError::ProcessSpawnFailed(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!(
+                    "{} not found; install it or change the server's sandbox strategy ({})",
+                    program,
+                    isolation.strategy.as_str()
+                ),
+            )));
+        }
+
         // Spawn new process with security restrictions (using resolved config)
-        let mut command = Command::new(&resolved_config.command);
+        let mut command = Command::new(&program);
         command
-            .args(&resolved_config.args)
+            .args(&args)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -595,13 +764,23 @@ impl StdioTransport {
             command.current_dir(cwd);
         }
 
-        // Apply environment variables
+        // Apply environment variables, over the inherited part of the
+        // proxy's environment
+        if let Some(inherited) = &resolved_config.inherit_env {
+            command.env_clear();
+            for (key, value) in std::env::vars_os() {
+                if inherited.iter().any(|name| key == name.as_str()) {
+                    command.env(key, value);
+                }
+            }
+        }
         for (key, value) in &resolved_config.env {
             command.env(key, value);
         }
 
-        // Security: Restrict process capabilities (Linux)
-        #[cfg(target_os = "linux")]
+        // Security: Restrict process capabilities. CPU usage is limited while
+        // the process runs, as an rlimit could only cap its total CPU time.
+        #[cfg(unix)]
         if resolved_config.sandbox {
             #[allow(unused_imports)]
             use std::os::unix::process::CommandExt;
@@ -616,23 +795,14 @@ impl StdioTransport {
 
             // Set resource limits
             // Clone values needed in closure
-            let max_cpu = resolved_config.max_cpu_percent;
             let max_memory_mb = resolved_config.max_memory_mb;
 
             // SAFETY: pre_exec is called before fork, in a single-threaded context
             unsafe {
                 command.pre_exec(move || {
-                    // Limit CPU time
-                    if let Some(max_cpu) = max_cpu {
-                        let cpu_limit = (max_cpu as u64) * 10; // Convert percentage to deciseconds
-                        let rlimit = libc::rlimit {
-                            rlim_cur: cpu_limit,
-                            rlim_max: cpu_limit,
-                        };
-                        libc::setrlimit(libc::RLIMIT_CPU, &rlimit);
-                    }
-
-                    // Limit memory
+                    // Limit memory. macOS accepts but does not enforce this
+                    // limit; the resource monitor still kills processes
+                    // staying over it.
                     if let Some(max_memory_mb) = max_memory_mb {
                         let memory_bytes = max_memory_mb * 1024 * 1024;
                         let rlimit = libc::rlimit {
@@ -656,22 +826,45 @@ impl StdioTransport {
             }
         }
 
+        #[cfg(target_os = "linux")]
+        if let Some(profile) = isolation
+            .seccomp_profile
+            .as_deref()
+            .filter(|_| isolation.strategy == crate::config::SandboxStrategy::Seccomp)
+        {
+            let filter = crate::transport::sandbox::load_seccomp_profile(profile)
+                .map_err(TransportError::ProcessSpawnFailed)?;
+            // SAFETY: the closure only makes async-signal-safe prctl calls;
+            // the filter was read before forking
+            unsafe {
+                command
+                    .pre_exec(move || crate::transport::sandbox::install_seccomp_filter(&filter));
+            }
+        }
+
         let mut child = command.spawn().map_err(TransportError::ProcessSpawnFailed)?;
 
         let stdin = child.stdin.take().ok_or(TransportError::NoStdin)?;
         let stdout = child.stdout.take().ok_or(TransportError::NoStdout)?;
         let stderr = child.stderr.take().ok_or(TransportError::NoStderr)?;
 
+        let log_config = resolved_config.backend_logs.as_ref();
+        let output = ProcessOutput {
+            server_id: server_id.clone(),
+            pid: child.id(),
+            redacted_values: resolved_config.redacted_values.clone(),
+            file: log_config.and_then(|config| self.logs.server(&server_id, config)),
+            capture_stdout: log_config.is_some_and(|config| config.capture_stdout),
+        };
         let process = Arc::new(StdioProcess::new(
-            server_id.clone(),
             child,
             stdin,
             stdout,
             stderr,
+            resolved_config.max_line_bytes,
+            output,
         ));
 
-        // Store in process map
-        self.processes.insert(server_id.clone(), process.clone());
         self.metrics.processes_spawned.fetch_add(1, Ordering::Relaxed);
 
         info!(
@@ -682,27 +875,187 @@ impl StdioTransport {
         Ok(process)
     }
 
-    /// Kill a specific process.
+    /// Kill all processes of a specific server.
     pub async fn kill_process(&self, server_id: &ServerId) -> Result<()> {
-        if let Some((_, process)) = self.processes.remove(server_id) {
-            process.kill().await?;
+        if let Some((_, pool)) = self.pools.remove(server_id) {
+            self.kill_pool(&pool).await?;
+            self.connection_states.remove(server_id);
             info!("Killed process for server {}", server_id);
         }
         Ok(())
     }
 
+    /// Kill the process in `slot` of a server's pool if it is still `pid`,
+    /// leaving it to be collected as exited. Returns whether it was killed.
+    pub async fn kill_slot(&self, server_id: &str, slot: usize, pid: u32) -> Result<bool> {
+        let Some(pool) = self.pools.get(server_id).map(|pool| pool.clone()) else {
+            return Ok(false);
+        };
+        let Some(slot) = pool.slots.get(slot) else {
+            return Ok(false);
+        };
+        let process = slot.process.lock().await.clone();
+        match process {
+            Some(process) if process.pid == Some(pid) => {
+                process.kill().await?;
+                self.metrics.processes_killed.fetch_add(1, Ordering::Relaxed);
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
+
     /// Kill all processes.
     pub async fn kill_all(&self) -> Result<()> {
-        let processes: Vec<_> = self.processes.iter().map(|entry| entry.value().clone()).collect();
+        let pools: Vec<_> = self.pools.iter().map(|entry| entry.value().clone()).collect();
+        self.pools.clear();
 
-        for process in processes {
-            process.kill().await?;
+        for pool in pools {
+            self.kill_pool(&pool).await?;
         }
 
-        self.processes.clear();
+        self.connection_states.clear();
         info!("Killed all STDIO processes");
         Ok(())
     }
+
+    /// Stop pooled processes that have been idle for at least `idle_timeout`.
+    ///
+    /// A reaped process is started again on its slot's next request.
+    /// Returns the number of processes stopped.
+    pub async fn reap_idle(&self, idle_timeout: Duration) -> usize {
+        let pools: Vec<_> = self
+            .pools
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect();
+
+        let mut reaped = 0;
+        for (server_id, pool) in pools {
+            for (index, slot) in pool.slots.iter().enumerate() {
+                // A slot that is locked is busy starting or handing out its process
+                let Ok(mut current) = slot.process.try_lock() else {
+                    continue;
+                };
+                let idle = match current.as_ref() {
+                    Some(process) => process.idle_for(),
+                    None => continue,
+                };
+                if idle < idle_timeout {
+                    continue;
+                }
+                let Some(process) = current.take() else {
+                    continue;
+                };
+
+                if let Err(e) = process.kill().await {
+                    warn!("Failed to stop idle STDIO process for {}: {}", server_id, e);
+                }
+                self.metrics.processes_killed.fetch_add(1, Ordering::Relaxed);
+                self.metrics.processes_reaped.fetch_add(1, Ordering::Relaxed);
+                crate::metrics::STDIO_PROCESSES_REAPED_TOTAL
+                    .with_label_values(&[&server_id])
+                    .inc();
+                info!(
+                    "Stopped STDIO process {} for server {} after {:?} idle",
+                    index, server_id, idle
+                );
+                reaped += 1;
+            }
+        }
+        reaped
+    }
+
+    async fn kill_pool(&self, pool: &ProcessPool) -> Result<()> {
+        for slot in &pool.slots {
+            if let Some(process) = slot.process.lock().await.take() {
+                process.kill().await?;
+                self.metrics.processes_killed.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Consecutive failures after which a pooled process is skipped for a while.
+const SLOT_FAILURE_THRESHOLD: u32 = 3;
+
+/// Processes serving one STDIO server, dispatched round-robin.
+struct ProcessPool {
+    slots: Vec<PoolSlot>,
+    next: AtomicUsize,
+    created: std::time::Instant,
+}
+
+/// One pooled process and its health record.
+#[derive(Default)]
+struct PoolSlot {
+    /// Running process (`None` until first use or after a failure)
+    process: Mutex<Option<Arc<StdioProcess>>>,
+    /// Consecutive failed requests
+    failures: AtomicU32,
+    /// Milliseconds since pool creation before which the slot is skipped
+    retry_after_ms: AtomicU64,
+    /// Requests served successfully
+    requests: AtomicU64,
+}
+
+impl ProcessPool {
+    fn new(size: usize) -> Self {
+        Self {
+            slots: (0..size).map(|_| PoolSlot::default()).collect(),
+            next: AtomicUsize::new(0),
+            created: std::time::Instant::now(),
+        }
+    }
+
+    /// Next slot in round-robin order, skipping slots that keep failing.
+    ///
+    /// Falls back to the plain round-robin pick if every slot is backing off.
+    fn next_slot(&self) -> (usize, &PoolSlot) {
+        let len = self.slots.len();
+        let start = self.next.fetch_add(1, Ordering::Relaxed) % len;
+        let now = self.created.elapsed().as_millis() as u64;
+
+        let index = (0..len)
+            .map(|offset| (start + offset) % len)
+            .find(|&i| self.slots[i].retry_after_ms.load(Ordering::Relaxed) <= now)
+            .unwrap_or(start);
+        (index, &self.slots[index])
+    }
+}
+
+impl PoolSlot {
+    fn record_success(&self) {
+        self.failures.store(0, Ordering::Relaxed);
+        self.retry_after_ms.store(0, Ordering::Relaxed);
+        self.requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_failure(&self, pool: &ProcessPool) {
+        let failures = self.failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if failures >= SLOT_FAILURE_THRESHOLD {
+            // Back off 1s per failure beyond the threshold, up to 30s
+            let backoff = u64::from(failures - SLOT_FAILURE_THRESHOLD + 1).min(30) * 1000;
+            let now = pool.created.elapsed().as_millis() as u64;
+            self.retry_after_ms.store(now + backoff, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Health snapshot of one pooled STDIO process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StdioProcessStatus {
+    /// Position in the server's pool
+    pub slot: usize,
+    /// OS process ID, if a process has been started
+    pub pid: Option<u32>,
+    /// Whether the process is alive and usable
+    pub running: bool,
+    /// Failed requests since the last success
+    pub consecutive_failures: u32,
+    /// Requests served successfully
+    pub requests: u64,
 }
 
 /// STDIO process wrapper with bidirectional communication.
@@ -711,44 +1064,151 @@ pub struct StdioProcess {
     child: Arc<Mutex<Child>>,
     /// Stdin writer (to server)
     stdin: Arc<Mutex<ChildStdin>>,
-    /// Stdout reader (from server)
-    stdout: Arc<Mutex<BufReader<ChildStdout>>>,
     /// Stderr reader (for diagnostics)
     stderr: Arc<Mutex<BufReader<ChildStderr>>>,
     /// Process health status
     healthy: Arc<AtomicBool>,
+    /// OS process ID
+    pid: Option<u32>,
+    /// Requests awaiting a response, keyed by the id sent to the server
+    pending: Arc<PendingRequests>,
+    /// Source of the ids sent to the server
+    next_id: AtomicU64,
+    /// Messages the server sent on its own (notifications, requests)
+    unsolicited: Mutex<mpsc::Receiver<serde_json::Value>>,
+    /// When the process was spawned
+    started: std::time::Instant,
+    /// Milliseconds after `started` at which the process was last used
+    last_used_ms: AtomicU64,
 }
 
+/// Response channels of in-flight requests, keyed by JSON-RPC id.
+pub(crate) type PendingRequests = DashMap<u64, oneshot::Sender<serde_json::Value>>;
+
+/// Unsolicited messages buffered before the oldest are dropped.
+const UNSOLICITED_BUFFER: usize = 64;
+
 impl StdioProcess {
     /// Create a new STDIO process wrapper.
-    /// Automatically starts a background task to drain stderr to prevent blocking.
+    /// Automatically starts background tasks that route stdout messages to
+    /// waiting requests and drain stderr to prevent blocking.
     fn new(
-        server_id: String,
         child: Child,
         stdin: ChildStdin,
         stdout: ChildStdout,
         stderr: ChildStderr,
+        max_line_bytes: usize,
+        output: ProcessOutput,
     ) -> Self {
         let stderr = Arc::new(Mutex::new(BufReader::new(stderr)));
-        let stderr_clone = stderr.clone();
-        let server_id_clone = server_id.clone();
 
         // CRITICAL: Spawn background task to continuously drain stderr
         // This prevents the stderr buffer from filling up and blocking the process.
         // STDIO MCP servers often print startup messages and logs to stderr, and if
         // we don't read them, the 64KB pipe buffer fills up, causing the process to
         // block on stderr writes and become unresponsive.
-        tokio::spawn(Self::drain_stderr(stderr_clone, server_id_clone));
+        tokio::spawn(Self::drain_stderr(stderr.clone(), output.clone()));
+
+        let healthy = Arc::new(AtomicBool::new(true));
+        let pending = Arc::new(PendingRequests::new());
+        let (unsolicited_tx, unsolicited_rx) = mpsc::channel(UNSOLICITED_BUFFER);
+        tokio::spawn(Self::route_stdout(
+            BufReader::new(stdout),
+            pending.clone(),
+            healthy.clone(),
+            unsolicited_tx,
+            output,
+            max_line_bytes,
+        ));
 
         Self {
+            pid: child.id(),
             child: Arc::new(Mutex::new(child)),
             stdin: Arc::new(Mutex::new(stdin)),
-            stdout: Arc::new(Mutex::new(BufReader::new(stdout))),
             stderr,
-            healthy: Arc::new(AtomicBool::new(true)),
+            healthy,
+            pending,
+            next_id: AtomicU64::new(0),
+            unsolicited: Mutex::new(unsolicited_rx),
+            started: std::time::Instant::now(),
+            last_used_ms: AtomicU64::new(0),
         }
     }
 
+    /// Record that the process is being used.
+    fn touch(&self) {
+        let now = self.started.elapsed().as_millis() as u64;
+        self.last_used_ms.fetch_max(now, Ordering::Relaxed);
+    }
+
+    /// Time since the process was last used; zero while requests are in flight.
+    pub fn idle_for(&self) -> Duration {
+        if !self.pending.is_empty() {
+            return Duration::ZERO;
+        }
+        let last_used = Duration::from_millis(self.last_used_ms.load(Ordering::Relaxed));
+        self.started.elapsed().saturating_sub(last_used)
+    }
+
+    /// Send a request and wait for its response.
+    ///
+    /// Concurrent requests are pipelined over the same pipes; each response
+    /// is matched to its request by id, whatever order the server answers in.
+    pub async fn exchange(
+        &self,
+        request: &McpRequest,
+        timeout_ms: u64,
+    ) -> std::result::Result<McpResponse, TransportError> {
+        let request_json = serde_json::to_value(request)?;
+        let response_json = self.call(request_json, Duration::from_millis(timeout_ms)).await?;
+        Ok(serde_json::from_value(response_json)?)
+    }
+
+    /// Send a JSON-RPC request and wait up to `timeout` for its response.
+    ///
+    /// The request goes out under an id unique to this process, so callers
+    /// may reuse ids freely; the response carries the caller's id again.
+    pub async fn call(
+        &self,
+        mut request: serde_json::Value,
+        timeout: Duration,
+    ) -> std::result::Result<serde_json::Value, TransportError> {
+        let Some(fields) = request.as_object_mut() else {
+            return Err(TransportError::ProtocolError(
+                "JSON-RPC request must be an object".into(),
+            ));
+        };
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let caller_id = fields.insert("id".into(), json!(id)).unwrap_or_default();
+
+        let (tx, rx) = oneshot::channel();
+        self.pending.insert(id, tx);
+        // Forget the request if it times out or the caller goes away
+        let _pending = PendingGuard {
+            pending: &self.pending,
+            id,
+        };
+
+        // The stdout router fails pending requests once the process is gone;
+        // a request registered after that would otherwise wait out its timeout
+        if !self.healthy.load(Ordering::SeqCst) {
+            return Err(TransportError::ProcessUnhealthy);
+        }
+
+        self.touch();
+        self.send_json(&request).await?;
+
+        let response = tokio::time::timeout(timeout, rx).await;
+        self.touch();
+        let mut response = response
+            .map_err(|_| TransportError::Timeout)?
+            .map_err(|_| connection_closed())?;
+        if let Some(fields) = response.as_object_mut() {
+            fields.insert("id".into(), caller_id);
+        }
+        Ok(response)
+    }
+
     /// Send a JSON-RPC message to the STDIO server (line-delimited JSON).
     pub async fn send_json(
         &self,
@@ -769,68 +1229,61 @@ impl StdioProcess {
         Ok(())
     }
 
-    /// Receive a JSON-RPC message from the STDIO server (line-delimited JSON).
-    /// Skips non-JSON lines (like startup messages) until a valid JSON-RPC message is found.
+    /// Receive the next message the server sent on its own initiative
+    /// (notifications and server-to-client requests).
+    ///
+    /// Responses are delivered to the request waiting for them instead.
     pub async fn receive_json(&self) -> std::result::Result<serde_json::Value, TransportError> {
-        let mut stdout = self.stdout.lock().await;
-
-        // Read lines until we find valid JSON
-        loop {
-            let mut line = String::new();
-            let bytes_read = stdout.read_line(&mut line).await?;
-
-            if bytes_read == 0 {
-                return Err(TransportError::Io(std::io::Error::new(
-                    std::io::ErrorKind::UnexpectedEof,
-                    "Connection closed",
-                )));
-            }
+        self.unsolicited.lock().await.recv().await.ok_or_else(connection_closed)
+    }
 
-            let trimmed = line.trim();
-            if trimmed.is_empty() {
-                // Skip empty lines
-                continue;
-            }
+    /// Parse one stdout line as a JSON-RPC message.
+    /// Returns `None` for non-JSON lines (like startup messages) and other noise.
+    fn parse_message(line: &str) -> Option<serde_json::Value> {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            // Skip empty lines
+            return None;
+        }
 
-            // Try to parse as JSON
-            match serde_json::from_str::<serde_json::Value>(trimmed) {
-                Ok(value) if value.is_object() => {
-                    // Valid JSON object - check if it's JSON-RPC
-                    if value.get("jsonrpc").is_some()
-                        || value.get("method").is_some()
-                        || value.get("result").is_some()
-                    {
-                        debug!(
-                            "Received JSON-RPC message: {}",
-                            trimmed.chars().take(100).collect::<String>()
-                        );
-                        return Ok(value);
-                    } else {
-                        // Valid JSON but not JSON-RPC, skip
-                        debug!(
-                            "Skipping non-JSON-RPC message: {}",
-                            trimmed.chars().take(50).collect::<String>()
-                        );
-                        continue;
-                    }
-                },
-                Ok(_) => {
-                    // Valid JSON but not an object (array, string, etc), skip
+        // Try to parse as JSON
+        match crate::json::from_str::<serde_json::Value>(trimmed) {
+            Ok(value) if value.is_object() => {
+                // Valid JSON object - check if it's JSON-RPC
+                if value.get("jsonrpc").is_some()
+                    || value.get("method").is_some()
+                    || value.get("result").is_some()
+                {
                     debug!(
-                        "Skipping non-object JSON: {}",
-                        trimmed.chars().take(50).collect::<String>()
+                        "Received JSON-RPC message: {}",
+                        trimmed.chars().take(100).collect::<String>()
                     );
-                    continue;
-                },
-                Err(_) => {
-                    // Not valid JSON - likely a startup message or log
+                    Some(value)
+                } else {
+                    // Valid JSON but not JSON-RPC, skip
                     debug!(
-                        "Skipping non-JSON line: {}",
+                        "Skipping non-JSON-RPC message: {}",
                         trimmed.chars().take(50).collect::<String>()
                     );
-                    continue;
-                },
-            }
+                    None
+                }
+            },
+            Ok(_) => {
+                // Valid JSON but not an object (array, string, etc), skip
+                debug!(
+                    "Skipping non-object JSON: {}",
+                    trimmed.chars().take(50).collect::<String>()
+                );
+                None
+            },
+            Err(_) => {
+                // Not valid JSON - likely a startup message or log
+                debug!(
+                    "Skipping non-JSON line: {}",
+                    trimmed.chars().take(50).collect::<String>()
+                );
+                None
+            },
         }
     }
 
@@ -882,6 +1335,11 @@ impl StdioProcess {
         }
     }
 
+    /// Exit status, if the process has exited.
+    pub async fn exit_status(&self) -> Option<std::process::ExitStatus> {
+        self.child.lock().await.try_wait().ok().flatten()
+    }
+
     /// Kill the process.
     pub async fn kill(&self) -> Result<()> {
         let mut child = self.child.lock().await;
@@ -899,22 +1357,129 @@ impl StdioProcess {
             "id": 0
         });
 
-        if let Ok(data) = serde_json::to_vec(&ping_request) {
-            if self.send(data).await.is_ok() {
-                // Try to receive response with short timeout
-                if let Ok(Ok(_)) =
-                    tokio::time::timeout(Duration::from_secs(1), self.receive()).await
+        // Wait for the response with a short timeout
+        self.call(ping_request, Duration::from_secs(1)).await.is_ok()
+    }
+
+    /// Background task reading stdout (or any line-delimited JSON-RPC
+    /// stream) and routing each message: responses go to the request
+    /// waiting for them, anything else to [`receive_json`](Self::receive_json).
+    pub(crate) async fn route_stdout<R: AsyncBufRead + Unpin>(
+        mut stdout: R,
+        pending: Arc<PendingRequests>,
+        healthy: Arc<AtomicBool>,
+        unsolicited: mpsc::Sender<serde_json::Value>,
+        output: ProcessOutput,
+        max_line_bytes: usize,
+    ) {
+        let server_id = &output.server_id;
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            let mut limited = (&mut stdout).take(max_line_bytes as u64 + 1);
+            match limited.read_until(b'\n', &mut line).await {
+                Ok(0) => {
+                    debug!("stdout [{}]: EOF reached", server_id);
+                    break;
+                },
+                Ok(_) => {},
+                Err(e) => {
+                    debug!("stdout [{}]: Read error: {}", server_id, e);
+                    break;
+                },
+            }
+            if line.len() > max_line_bytes && line.last() != Some(&b'\n') {
+                if let Err(e) =
+                    Self::skip_oversized_line(&mut stdout, &line, &pending, max_line_bytes).await
                 {
-                    return true;
+                    debug!("stdout [{}]: Read error: {}", server_id, e);
+                    break;
                 }
+                warn!(
+                    "stdout [{}]: Discarded a message over the {} byte line limit",
+                    server_id, max_line_bytes
+                );
+                continue;
+            }
+            let text = String::from_utf8_lossy(&line);
+            let Some(message) = Self::parse_message(&text) else {
+                output.stdout_noise(text.trim());
+                continue;
+            };
+
+            // Server-initiated messages carry a method; responses do not
+            if message.get("method").is_some() {
+                if unsolicited.try_send(message).is_err() {
+                    debug!("stdout [{}]: Dropping unsolicited message", server_id);
+                }
+                continue;
+            }
+            let waiter =
+                message.get("id").and_then(|id| id.as_u64()).and_then(|id| pending.remove(&id));
+            match waiter {
+                Some((_, tx)) => {
+                    let _ = tx.send(message);
+                },
+                None => debug!(
+                    "stdout [{}]: Dropping response to abandoned request {}",
+                    server_id,
+                    message.get("id").unwrap_or(&serde_json::Value::Null)
+                ),
             }
         }
 
-        false
+        // Fail everything still waiting; the process is unusable now
+        healthy.store(false, Ordering::SeqCst);
+        pending.clear();
     }
 
-    /// Background task to drain stderr (prevents blocking). Reduces nesting.
-    async fn drain_stderr(stderr: Arc<Mutex<BufReader<ChildStderr>>>, server_id: String) {
+    /// Discard the rest of a stdout line longer than `limit`, whose first
+    /// bytes are `head`, without buffering it. The request it answers fails
+    /// with [`RESPONSE_TOO_LARGE`](crate::error::RESPONSE_TOO_LARGE); the
+    /// process stays usable.
+    async fn skip_oversized_line<R: AsyncBufRead + Unpin>(
+        stdout: &mut R,
+        head: &[u8],
+        pending: &PendingRequests,
+        limit: usize,
+    ) -> std::io::Result<()> {
+        let mut scanner = IdScanner::default();
+        scanner.feed(head);
+        loop {
+            let buf = stdout.fill_buf().await?;
+            if buf.is_empty() {
+                break;
+            }
+            match buf.iter().position(|&b| b == b'\n') {
+                Some(end) => {
+                    scanner.feed(&buf[..end]);
+                    stdout.consume(end + 1);
+                    break;
+                },
+                None => {
+                    let len = buf.len();
+                    scanner.feed(buf);
+                    stdout.consume(len);
+                },
+            }
+        }
+
+        if let Some((id, tx)) = scanner.finish().and_then(|id| pending.remove(&id)) {
+            let _ = tx.send(json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": {
+                    "code": crate::error::RESPONSE_TOO_LARGE,
+                    "message": format!("Response exceeds {} byte limit", limit),
+                }
+            }));
+        }
+        Ok(())
+    }
+
+    /// Background task to drain stderr (prevents blocking) into `output`.
+    /// Reduces nesting.
+    async fn drain_stderr(stderr: Arc<Mutex<BufReader<ChildStderr>>>, output: ProcessOutput) {
         let mut stderr_lock = stderr.lock().await;
         let mut line = String::new();
         loop {
@@ -922,18 +1487,18 @@ impl StdioProcess {
             match stderr_lock.read_line(&mut line).await {
                 Ok(0) => {
                     // EOF - process has exited
-                    debug!("stderr [{}]: EOF reached", server_id);
+                    debug!("stderr [{}]: EOF reached", output.server_id);
                     break;
                 },
                 Ok(_) => {
-                    // Log stderr output for debugging (skip empty lines)
+                    // Skip empty lines
                     let trimmed = line.trim();
                     if !trimmed.is_empty() {
-                        debug!("stderr [{}]: {}", server_id, trimmed);
+                        output.stderr(trimmed);
                     }
                 },
                 Err(e) => {
-                    debug!("stderr [{}]: Read error: {}", server_id, e);
+                    debug!("stderr [{}]: Read error: {}", output.server_id, e);
                     break;
                 },
             }
@@ -941,11 +1506,143 @@ impl StdioProcess {
     }
 }
 
+/// Where a process's diagnostic output goes: debug logs and, when
+/// `observability.backend_logs` is enabled, the server's log file (see
+/// [`crate::transport::backend_logs`]), with the server's secrets redacted.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ProcessOutput {
+    pub(crate) server_id: String,
+    pid: Option<u32>,
+    redacted_values: Vec<String>,
+    file: Option<Arc<crate::transport::backend_logs::ServerLog>>,
+    /// Also write stdout lines that are not JSON-RPC messages to `file`
+    capture_stdout: bool,
+}
+
+impl ProcessOutput {
+    /// Debug logs only, for connections that are not local processes.
+    pub(crate) fn new(server_id: String) -> Self {
+        Self {
+            server_id,
+            ..Default::default()
+        }
+    }
+
+    fn stderr(&self, line: &str) {
+        let line = crate::config::redact::redact_values(line, &self.redacted_values);
+        debug!("stderr [{}]: {}", self.server_id, line);
+        if let Some(file) = &self.file {
+            file.append(self.pid, "stderr", &line);
+        }
+    }
+
+    fn stdout_noise(&self, line: &str) {
+        if let (Some(file), true, false) = (&self.file, self.capture_stdout, line.is_empty()) {
+            let line = crate::config::redact::redact_values(line, &self.redacted_values);
+            file.append(self.pid, "stdout", &line);
+        }
+    }
+}
+
+/// Finds the top-level numeric `"id"` of a JSON message fed in pieces, so
+/// an oversized response can be matched to its request without keeping it.
+#[derive(Default)]
+struct IdScanner {
+    /// Nesting depth of objects and arrays
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    /// Start of the string being read, enough to recognise `id`
+    string: Vec<u8>,
+    /// The last token was the top-level key `"id"`
+    after_id_key: bool,
+    /// Digits of the id value being read
+    value: Option<String>,
+    id: Option<u64>,
+}
+
+impl IdScanner {
+    fn feed(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            if self.id.is_some() {
+                return;
+            }
+            if self.in_string {
+                match byte {
+                    _ if self.escaped => self.escaped = false,
+                    b'\\' => self.escaped = true,
+                    b'"' => {
+                        self.in_string = false;
+                        self.after_id_key = self.depth == 1 && self.string == b"id";
+                        continue;
+                    },
+                    _ => {},
+                }
+                if self.string.len() < 3 {
+                    self.string.push(byte);
+                }
+                continue;
+            }
+            if let Some(digits) = &mut self.value {
+                match byte {
+                    b'0'..=b'9' => {
+                        digits.push(byte as char);
+                        continue;
+                    },
+                    _ if byte.is_ascii_whitespace() && digits.is_empty() => continue,
+                    _ => {
+                        self.id = digits.parse().ok();
+                        self.value = None;
+                    },
+                }
+            }
+            match byte {
+                b'"' => {
+                    self.in_string = true;
+                    self.string.clear();
+                },
+                b'{' | b'[' => self.depth += 1,
+                b'}' | b']' => self.depth = self.depth.saturating_sub(1),
+                b':' if self.after_id_key => self.value = Some(String::new()),
+                _ => {},
+            }
+            if !byte.is_ascii_whitespace() {
+                self.after_id_key = false;
+            }
+        }
+    }
+
+    /// The id, if the message had a numeric top-level one.
+    fn finish(self) -> Option<u64> {
+        self.id.or_else(|| self.value.and_then(|digits| digits.parse().ok()))
+    }
+}
+
+/// Removes a request from the pending map when its caller stops waiting.
+pub(crate) struct PendingGuard<'a> {
+    pub(crate) pending: &'a PendingRequests,
+    pub(crate) id: u64,
+}
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        self.pending.remove(&self.id);
+    }
+}
+
+pub(crate) fn connection_closed() -> TransportError {
+    TransportError::Io(std::io::Error::new(
+        std::io::ErrorKind::UnexpectedEof,
+        "Connection closed",
+    ))
+}
+
 /// Metrics for STDIO processes and MCP initialization.
 #[derive(Default)]
 pub struct ProcessMetrics {
     pub processes_spawned: AtomicU64,
     pub processes_killed: AtomicU64,
+    pub processes_reaped: AtomicU64,
     pub requests_sent: AtomicU64,
     pub responses_received: AtomicU64,
     pub errors: AtomicU64,
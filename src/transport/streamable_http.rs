@@ -13,7 +13,16 @@
 //! 1. **Initialization**: Client sends `initialize` request without session ID
 //! 2. **Session Creation**: Server responds with `mcp-session-id` header
 //! 3. **Subsequent Requests**: Client includes session ID in all future requests
-//! 4. **Session Expiry**: 400/401 errors trigger reinitialization
+//! 4. **Session Expiry**: 400/401/404 errors on a request carrying a session
+//!    ID trigger reinitialization, and the request is replayed once on the
+//!    new session
+//! 5. **OAuth**: with an `oauth` block every request carries a
+//!    client-credentials bearer token; a 401 discards the token and the
+//!    request is replayed once with a fresh one
+//! 6. **Signing**: with a `signing` block every request carries an HMAC
+//!    signature of its body (see [`crate::transport::signing`])
+//! 7. **Proxy**: with a `proxy` block connections go through an outbound
+//!    HTTP or SOCKS5 proxy (see [`crate::transport::outbound_proxy`])
 //!
 //! # Example
 //!
@@ -29,12 +38,25 @@
 //!         h
 //!     },
 //!     timeout_ms: 30000,
+//!     oauth: None,
+//!     tls: None,
+//!     signing: None,
+//!     proxy: None,
 //! };
 //!
 //! let transport = StreamableHttpTransport::new(config);
 //! ```
 
+use crate::auth::oauth::ClientCredentials;
+use crate::config::{
+    BackendOAuthConfig, BackendTlsConfig, ConnectionPoolConfig, OutboundProxyConfig,
+    RequestSigningConfig,
+};
 use crate::error::Error;
+use crate::transport::http::configure_pool;
+use crate::transport::limits::{read_body, read_text, BodyError, ResponseLimit};
+use crate::transport::signing::signature_headers;
+use crate::transport::streaming::{should_stream, BackendReply};
 use crate::types::{McpRequest, McpResponse};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -62,9 +84,18 @@ pub struct StreamableHttpTransport {
     /// Custom headers per configuration
     headers: HashMap<String, String>,
 
+    /// OAuth2 client-credentials token source, if configured
+    credentials: Option<Arc<ClientCredentials>>,
+
+    /// HMAC request signing, if configured
+    signing: Option<RequestSigningConfig>,
+
     /// Connection timeout (currently set on client, field reserved for per-request timeout control)
     #[allow(dead_code)]
     timeout: Duration,
+
+    /// Largest response body read from the server
+    max_response_bytes: ResponseLimit,
 }
 
 /// Configuration for Streamable HTTP transport
@@ -80,6 +111,22 @@ pub struct StreamableHttpConfig {
     /// Timeout in milliseconds
     #[serde(default = "default_timeout_ms")]
     pub timeout_ms: u64,
+
+    /// Authenticate with OAuth2 client credentials
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oauth: Option<BackendOAuthConfig>,
+
+    /// Client certificate and trust roots for HTTPS backends
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<BackendTlsConfig>,
+
+    /// Sign every request with an HMAC of its body
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing: Option<RequestSigningConfig>,
+
+    /// Outbound proxy the backend is reached through
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<OutboundProxyConfig>,
 }
 
 fn default_timeout_ms() -> u64 {
@@ -106,6 +153,21 @@ pub enum StreamableHttpError {
 
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
+
+    #[error("OAuth token request failed: {0}")]
+    OAuth(String),
+
+    #[error("Response exceeds {0} byte limit")]
+    ResponseTooLarge(usize),
+}
+
+impl From<BodyError> for StreamableHttpError {
+    fn from(err: BodyError) -> Self {
+        match err {
+            BodyError::TooLarge(limit) => StreamableHttpError::ResponseTooLarge(limit),
+            BodyError::Read(e) => StreamableHttpError::ParseError(e.to_string()),
+        }
+    }
 }
 
 impl StreamableHttpTransport {
@@ -119,27 +181,76 @@ impl StreamableHttpTransport {
     ///
     /// Initialized transport ready to send requests
     pub fn new(config: StreamableHttpConfig) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_millis(config.timeout_ms))
-            .build()
-            .unwrap_or_else(|_| Client::new());
+        Self::with_pool_config(config, &ConnectionPoolConfig::default())
+    }
+
+    /// Create a transport whose idle connections are tuned per `pool`.
+    pub fn with_pool_config(config: StreamableHttpConfig, pool: &ConnectionPoolConfig) -> Self {
+        let client_builder = || {
+            let builder = Client::builder().timeout(Duration::from_millis(config.timeout_ms));
+            configure_pool(builder, pool)
+        };
+        let mut builder = client_builder();
+        if let Some(tls) = &config.tls {
+            // Without its client certificate the backend rejects the
+            // handshake, which surfaces the problem on every request
+            match crate::transport::tls::configure_client(builder, tls) {
+                Ok(configured) => builder = configured,
+                Err(e) => {
+                    tracing::error!("{}: {}", config.url, e);
+                    builder = client_builder();
+                },
+            }
+        }
+        if let Some(proxy) = &config.proxy {
+            // Validation rules out proxies that cannot be configured
+            match crate::transport::outbound_proxy::configure_client(builder, proxy) {
+                Ok(configured) => builder = configured,
+                Err(e) => {
+                    tracing::error!("{}: {}", config.url, e);
+                    builder = client_builder();
+                },
+            }
+        }
+        let client = builder.build().unwrap_or_else(|_| Client::new());
+        // Without a token source requests go out unauthenticated and the
+        // backend's 401 surfaces the problem
+        let credentials = config.oauth.and_then(|oauth| {
+            match ClientCredentials::new(oauth, config.proxy.as_ref()) {
+                Ok(credentials) => Some(Arc::new(credentials)),
+                Err(e) => {
+                    tracing::error!("{}: {}", config.url, e);
+                    None
+                },
+            }
+        });
 
         Self {
             client,
             endpoint: config.url,
             session_id: Arc::new(RwLock::new(None)),
             headers: config.headers,
+            credentials,
+            signing: config.signing,
             timeout: Duration::from_millis(config.timeout_ms),
+            max_response_bytes: ResponseLimit::default(),
         }
     }
 
+    /// Read responses up to `limit` bytes
+    pub fn with_response_limit(mut self, limit: ResponseLimit) -> Self {
+        self.max_response_bytes = limit;
+        self
+    }
+
     /// Send request with session management.
     ///
     /// Automatically handles session ID storage and inclusion in requests.
     /// If there's no active session and the request is not `initialize`,
     /// automatically sends an `initialize` request first to establish the session.
-    /// If a session error occurs (400/401), clears the session ID to force
-    /// reinitialization on the next request.
+    /// If the server rejects the session (400/401/404 on a request that
+    /// carried a session ID), a new session is established and the request
+    /// is replayed once before the error is returned.
     ///
     /// # Arguments
     ///
@@ -153,45 +264,101 @@ impl StreamableHttpTransport {
         &self,
         request: McpRequest,
     ) -> Result<McpResponse, StreamableHttpError> {
-        // 0. Check if we need to initialize first
-        let needs_init = {
-            let session = self.session_id.read().await;
-            session.is_none() && request.method() != "initialize"
-        };
+        let response = self.post_with_replay(request).await?;
+        self.parse_response(response).await
+    }
+
+    /// Like [`send_request`](Self::send_request), but a JSON result of at
+    /// least `stream_from` bytes, or of unknown length, is returned unread
+    /// for the caller to stream.
+    pub async fn send_request_streaming(
+        &self,
+        request: McpRequest,
+        stream_from: usize,
+    ) -> Result<BackendReply, StreamableHttpError> {
+        let response = self.post_with_replay(request).await?;
+        if should_stream(&response, stream_from) {
+            return Ok(BackendReply::Streaming(response));
+        }
+        self.parse_response(response).await.map(BackendReply::Parsed)
+    }
+
+    /// Post a request in the session, replaying it once on a new session if
+    /// the server rejects the current one.
+    async fn post_with_replay(
+        &self,
+        request: McpRequest,
+    ) -> Result<reqwest::Response, StreamableHttpError> {
+        match self.send_in_session(request.clone()).await {
+            Err(StreamableHttpError::InvalidSession(reason))
+                if request.method() != "initialize" =>
+            {
+                warn!(
+                    "Session with {} lost ({}), replaying request on a new session",
+                    self.endpoint, reason
+                );
+                let response = self.send_in_session(request).await?;
+                crate::metrics::BACKEND_SESSION_RECOVERIES_TOTAL
+                    .with_label_values(&["streamable_http", &self.endpoint])
+                    .inc();
+                Ok(response)
+            },
+            result => result,
+        }
+    }
 
-        if needs_init {
-            info!("No session ID, sending initialize request first");
-
-            // Send initialize request to establish session
-            let init_request = McpRequest::new(
-                "initialize",
-                serde_json::json!({
-                    "protocolVersion": "2024-11-05",
-                    "capabilities": {},
-                    "clientInfo": {
-                        "name": "Only1MCP",
-                        "version": "0.2.0"
-                    }
-                }),
-                Some(serde_json::json!(1)), // Use a simple numeric ID for init
-            );
-
-            // Send initialize and get session ID
-            let _init_response = self.send_request_internal(init_request).await?;
-            info!("Session initialized successfully");
+    /// Post a request, establishing a session first if there is none.
+    async fn send_in_session(
+        &self,
+        request: McpRequest,
+    ) -> Result<reqwest::Response, StreamableHttpError> {
+        if request.method() != "initialize" {
+            self.ensure_session().await?;
         }
 
         // Now send the actual request with session
         self.send_request_internal(request).await
     }
 
-    /// Internal method to send a request without automatic initialization.
+    /// Establish a session by sending `initialize`, unless one is active.
+    ///
+    /// Called before every request; calling it ahead of the first one also
+    /// opens the connection early.
+    pub async fn ensure_session(&self) -> Result<(), StreamableHttpError> {
+        if self.session_id.read().await.is_some() {
+            return Ok(());
+        }
+        info!("No session ID, sending initialize request first");
+
+        // Send initialize request to establish session
+        let init_request = McpRequest::new(
+            "initialize",
+            serde_json::json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "clientInfo": {
+                    "name": "Only1MCP",
+                    "version": "0.2.0"
+                }
+            }),
+            Some(serde_json::json!(1)), // Use a simple numeric ID for init
+        );
+
+        // Send initialize and get session ID
+        let init_response = self.send_request_internal(init_request).await?;
+        self.parse_response(init_response).await?;
+        info!("Session initialized successfully");
+        Ok(())
+    }
+
+    /// Internal method to send a request without automatic initialization,
+    /// returning the successful response unread.
     ///
     /// This is used by `send_request` after handling initialization logic.
     async fn send_request_internal(
         &self,
         request: McpRequest,
-    ) -> Result<McpResponse, StreamableHttpError> {
+    ) -> Result<reqwest::Response, StreamableHttpError> {
         // 1. Build base request
         let mut req_builder = self
             .client
@@ -204,34 +371,63 @@ impl StreamableHttpTransport {
             req_builder = req_builder.header(key, value);
         }
 
-        // 3. Add session ID if we have one
-        if let Some(session_id) = self.session_id.read().await.as_ref() {
+        // 3. Add a bearer token when OAuth is configured
+        if let Some(credentials) = &self.credentials {
+            let token = credentials
+                .access_token()
+                .await
+                .map_err(|e| StreamableHttpError::OAuth(e.to_string()))?;
+            req_builder = req_builder.bearer_auth(token);
+        }
+
+        // 4. Add session ID if we have one
+        let sent_session = self.session_id.read().await.clone();
+        if let Some(session_id) = sent_session.as_ref() {
             req_builder = req_builder.header("mcp-session-id", session_id);
             debug!("Using session ID: {}", session_id);
         } else {
             debug!("No session ID, expecting server to create new session");
         }
 
-        // 4. Send request
+        // 5. Sign the body when configured, and send the request
+        let body = serde_json::to_vec(&request)?;
+        if let Some(signing) = &self.signing {
+            for (name, value) in signature_headers(signing, &body) {
+                req_builder = req_builder.header(name, value);
+            }
+        }
         let response = req_builder
-            .json(&request)
+            .body(body)
             .send()
             .await
             .map_err(StreamableHttpError::RequestFailed)?;
 
-        // 5. Extract session ID from response (if new or updated)
+        // 6. Extract session ID from response (if new or updated)
         self.extract_session_id(&response).await;
 
-        // 6. Check status code
+        // 7. Check status code
         if !response.status().is_success() {
             let status = response.status();
-            let body = response.text().await.unwrap_or_default();
+            let body = read_text(response, self.max_response_bytes.get()).await.unwrap_or_default();
+
+            // A rejected bearer token is discarded so the replay fetches a new one
+            let token_rejected = status == 401 && self.credentials.is_some();
+            if let Some(credentials) = self.credentials.as_ref().filter(|_| token_rejected) {
+                credentials.invalidate().await;
+            }
 
             // Handle session errors (may need to reinitialize)
-            if status == 400 || status == 401 {
+            if status == 400 || status == 401 || (status == 404 && sent_session.is_some()) {
                 warn!("Session error ({}): {}", status, body);
                 // Clear session ID to force reinitialization
                 *self.session_id.write().await = None;
+
+                if sent_session.is_some() || token_rejected {
+                    return Err(StreamableHttpError::InvalidSession(format!(
+                        "Server returned {}: {}",
+                        status, body
+                    )));
+                }
             }
 
             return Err(StreamableHttpError::ProtocolError(format!(
@@ -240,8 +436,7 @@ impl StreamableHttpTransport {
             )));
         }
 
-        // 7. Parse response (handles both JSON and SSE)
-        self.parse_response(response).await
+        Ok(response)
     }
 
     /// Extract and store session ID from response headers.
@@ -296,9 +491,8 @@ impl StreamableHttpTransport {
         } else {
             debug!("Parsing JSON response");
             // Standard JSON response
-            response
-                .json::<McpResponse>()
-                .await
+            let body = read_body(response, self.max_response_bytes.get()).await?;
+            crate::json::from_slice::<McpResponse>(&body)
                 .map_err(|e| StreamableHttpError::ParseError(e.to_string()))
         }
     }
@@ -329,7 +523,7 @@ impl StreamableHttpTransport {
         &self,
         response: reqwest::Response,
     ) -> Result<McpResponse, StreamableHttpError> {
-        let body = response.text().await?;
+        let body = read_text(response, self.max_response_bytes.get()).await?;
 
         // Parse SSE format: "data: <json>\n\n"
         let mut data_lines = Vec::new();
@@ -354,7 +548,7 @@ impl StreamableHttpTransport {
         debug!("SSE data: {}", json_str);
 
         // Parse as JSON-RPC response
-        serde_json::from_str(&json_str).map_err(|e| {
+        crate::json::from_str(&json_str).map_err(|e| {
             StreamableHttpError::ParseError(format!("Failed to parse SSE data: {}", e))
         })
     }
@@ -389,6 +583,12 @@ impl StreamableHttpTransport {
 pub struct StreamableHttpTransportPool {
     /// Pool of transports keyed by endpoint URL
     transports: Arc<dashmap::DashMap<String, Arc<StreamableHttpTransport>>>,
+
+    /// Largest response body read by the pool's transports
+    max_response_bytes: ResponseLimit,
+
+    /// Idle connection tuning of new transports
+    pool: ConnectionPoolConfig,
 }
 
 impl StreamableHttpTransportPool {
@@ -396,9 +596,23 @@ impl StreamableHttpTransportPool {
     pub fn new() -> Self {
         Self {
             transports: Arc::new(dashmap::DashMap::new()),
+            max_response_bytes: ResponseLimit::default(),
+            pool: ConnectionPoolConfig::default(),
         }
     }
 
+    /// Read backend responses up to `limit` bytes
+    pub fn with_response_limit(mut self, limit: ResponseLimit) -> Self {
+        self.max_response_bytes = limit;
+        self
+    }
+
+    /// Tune new transports' idle connections per `proxy.connection_pool`
+    pub fn with_pool_config(mut self, pool: &ConnectionPoolConfig) -> Self {
+        self.pool = pool.clone();
+        self
+    }
+
     /// Get or create a transport for the given configuration.
     ///
     /// Reuses existing transports for the same endpoint to preserve sessions.
@@ -415,7 +629,12 @@ impl StreamableHttpTransportPool {
 
         self.transports
             .entry(key)
-            .or_insert_with(|| Arc::new(StreamableHttpTransport::new(config)))
+            .or_insert_with(|| {
+                Arc::new(
+                    StreamableHttpTransport::with_pool_config(config, &self.pool)
+                        .with_response_limit(self.max_response_bytes.clone()),
+                )
+            })
             .clone()
     }
 
@@ -439,7 +658,10 @@ impl Default for StreamableHttpTransportPool {
 // Convert StreamableHttpError to our Error type
 impl From<StreamableHttpError> for Error {
     fn from(err: StreamableHttpError) -> Self {
-        Error::Transport(err.to_string())
+        match err {
+            StreamableHttpError::ResponseTooLarge(_) => Error::ResponseTooLarge(err.to_string()),
+            _ => Error::Transport(err.to_string()),
+        }
     }
 }
 
@@ -453,6 +675,10 @@ mod tests {
             url: "http://test".to_string(),
             headers: HashMap::new(),
             timeout_ms: default_timeout_ms(),
+            oauth: None,
+            tls: None,
+            signing: None,
+            proxy: None,
         };
 
         assert_eq!(config.timeout_ms, 30000);
@@ -472,12 +698,20 @@ mod tests {
             url: "http://test1".to_string(),
             headers: HashMap::new(),
             timeout_ms: 30000,
+            oauth: None,
+            tls: None,
+            signing: None,
+            proxy: None,
         };
 
         let config2 = StreamableHttpConfig {
             url: "http://test1".to_string(), // Same URL
             headers: HashMap::new(),
             timeout_ms: 30000,
+            oauth: None,
+            tls: None,
+            signing: None,
+            proxy: None,
         };
 
         let t1 = pool.get_or_create(config1);
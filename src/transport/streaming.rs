@@ -0,0 +1,168 @@
+//! Streaming passthrough of large backend responses (`proxy.streaming`).
+//!
+//! A tool result can be many megabytes. Rather than parsing it into a
+//! `serde_json::Value` and serializing it again, a large JSON body from an
+//! HTTP or Streamable HTTP backend is forwarded to the client chunk by
+//! chunk, counting its size on the way through.
+
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use reqwest::header::CONTENT_TYPE;
+
+use crate::metrics::tokens::estimate_payload_tokens;
+use crate::types::McpResponse;
+
+/// A backend's answer: parsed, or a JSON body left for the caller to stream.
+pub enum BackendReply {
+    Parsed(McpResponse),
+    Streaming(reqwest::Response),
+}
+
+/// Whether a successful `response` should be streamed rather than parsed:
+/// a JSON body of at least `min_bytes`, or of unknown length. SSE bodies
+/// are always parsed, since the client expects plain JSON.
+pub fn should_stream(response: &reqwest::Response, min_bytes: usize) -> bool {
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    is_json && response.content_length().map_or(true, |length| length >= min_bytes as u64)
+}
+
+/// Size of a streamed body, reported when the stream ends.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreamedSize {
+    pub bytes: u64,
+    /// Estimated tokens, see [`estimate_payload_tokens`]
+    pub tokens: u64,
+    /// The whole body was forwarded (not cut off by an error, the limit
+    /// or the client going away)
+    pub complete: bool,
+}
+
+/// Forward `response`'s body, failing once it passes `limit` bytes.
+///
+/// `on_end` is called exactly once with the size forwarded, when the body
+/// is finished or the stream is dropped.
+pub fn metered_body<F>(
+    response: reqwest::Response,
+    limit: usize,
+    on_end: F,
+) -> impl Stream<Item = std::io::Result<Bytes>> + Send
+where
+    F: FnOnce(StreamedSize) + Send + 'static,
+{
+    let meter = Meter {
+        size: StreamedSize::default(),
+        limit,
+        failed: false,
+        on_end: Some(on_end),
+    };
+    futures::stream::unfold(
+        (response.bytes_stream(), meter),
+        |(mut chunks, mut meter)| async move {
+            if meter.failed {
+                return None;
+            }
+            let item = match chunks.next().await {
+                None => {
+                    meter.end(true);
+                    return None;
+                },
+                Some(Err(e)) => Err(std::io::Error::other(e)),
+                Some(Ok(chunk)) if meter.size.bytes + chunk.len() as u64 > meter.limit as u64 => {
+                    Err(std::io::Error::other(format!(
+                        "Response exceeds {} byte limit",
+                        meter.limit
+                    )))
+                },
+                Some(Ok(chunk)) => {
+                    meter.size.bytes += chunk.len() as u64;
+                    meter.size.tokens += estimate_payload_tokens(&chunk);
+                    Ok(chunk)
+                },
+            };
+            meter.failed = item.is_err();
+            Some((item, (chunks, meter)))
+        },
+    )
+}
+
+/// Reports the size forwarded when dropped.
+struct Meter<F: FnOnce(StreamedSize)> {
+    size: StreamedSize,
+    limit: usize,
+    /// An error was yielded; the stream ends after it
+    failed: bool,
+    on_end: Option<F>,
+}
+
+impl<F: FnOnce(StreamedSize)> Meter<F> {
+    /// Report the size, once.
+    fn end(&mut self, complete: bool) {
+        if let Some(on_end) = self.on_end.take() {
+            on_end(StreamedSize {
+                complete,
+                ..self.size
+            });
+        }
+    }
+}
+
+impl<F: FnOnce(StreamedSize)> Drop for Meter<F> {
+    fn drop(&mut self) {
+        self.end(false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    fn response(body: &'static str, content_type: &str) -> reqwest::Response {
+        let response = axum::http::Response::builder()
+            .header(CONTENT_TYPE, content_type)
+            .body(body)
+            .unwrap();
+        reqwest::Response::from(response)
+    }
+
+    #[test]
+    fn test_should_stream_large_json_only() {
+        let body = r#"{"jsonrpc":"2.0","id":1,"result":{}}"#;
+        assert!(should_stream(&response(body, "application/json"), 16));
+        assert!(!should_stream(&response(body, "application/json"), 1024));
+        assert!(!should_stream(&response(body, "text/event-stream"), 16));
+    }
+
+    #[tokio::test]
+    async fn test_metered_body_reports_size() {
+        let body = r#"{"jsonrpc":"2.0","id":1,"result":{"text":"hello"}}"#;
+        let ended = Arc::new(Mutex::new(None));
+
+        let sink = ended.clone();
+        let stream = metered_body(response(body, "application/json"), 1024, move |size| {
+            *sink.lock().unwrap() = Some(size);
+        });
+        let chunks: Vec<_> = stream.collect().await;
+        let forwarded: Vec<u8> = chunks.iter().flat_map(|c| c.as_ref().unwrap().to_vec()).collect();
+        assert_eq!(forwarded, body.as_bytes());
+
+        let size = ended.lock().unwrap().unwrap();
+        assert_eq!(size.bytes, body.len() as u64);
+        assert_eq!(size.tokens, estimate_payload_tokens(body.as_bytes()));
+        assert!(size.complete);
+
+        // Over the limit the stream fails and reports an incomplete body
+        let sink = ended.clone();
+        let stream = metered_body(response(body, "application/json"), 8, move |size| {
+            *sink.lock().unwrap() = Some(size);
+        });
+        let chunks: Vec<_> = stream.collect().await;
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].is_err());
+        assert!(!ended.lock().unwrap().unwrap().complete);
+    }
+}
@@ -0,0 +1,251 @@
+//! Restart supervision of STDIO processes (`servers[].restart`).
+//!
+//! The proxy periodically collects processes that exited on their own (see
+//! [`StdioTransport::take_exited`](crate::transport::stdio::StdioTransport::take_exited))
+//! and asks [`RestartSupervisor`] whether and when to start them again.
+//! Processes the proxy stops itself, idle reaping or an Admin API restart,
+//! are never reported and do not count against the restart limit.
+
+use std::collections::VecDeque;
+use std::process::ExitStatus;
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+
+use crate::config::{RestartConfig, RestartPolicy};
+use crate::types::{RestartStatus, ServerId};
+
+/// A pooled STDIO process that stopped without the proxy asking it to.
+#[derive(Debug, Clone)]
+pub struct ProcessExit {
+    pub server_id: ServerId,
+    /// Position of the process in the server's pool
+    pub slot: usize,
+    /// Exit status, or `None` if the process closed stdout but kept running
+    /// (it is killed when collected)
+    pub status: Option<ExitStatus>,
+}
+
+impl ProcessExit {
+    /// Whether the process exited with an error, was killed by a signal or
+    /// stopped responding.
+    pub fn is_failure(&self) -> bool {
+        !self.status.is_some_and(|status| status.success())
+    }
+
+    /// Human-readable exit reason.
+    pub fn describe(&self) -> String {
+        match &self.status {
+            Some(status) => status.to_string(),
+            None => "closed stdout".to_string(),
+        }
+    }
+}
+
+/// What to do about an exited process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartDecision {
+    /// Leave it stopped; the next request starts it again
+    Ignore,
+    /// Start it again after the delay
+    RestartAfter(Duration),
+    /// The server just exceeded its restart limit
+    CrashLoop,
+}
+
+/// Restart history of one server.
+#[derive(Debug, Default)]
+struct RestartRecord {
+    /// Restarts within the current window, oldest first
+    recent: VecDeque<Instant>,
+    /// Restarts since the proxy started
+    total: u64,
+    crash_loop: bool,
+    last_exit: Option<String>,
+}
+
+/// Restart decisions and history for all STDIO servers.
+#[derive(Debug, Default)]
+pub struct RestartSupervisor {
+    records: DashMap<ServerId, RestartRecord>,
+}
+
+impl RestartSupervisor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that a process exited at `now` and decide whether to restart
+    /// it under `config`.
+    pub fn on_exit(
+        &self,
+        exit: &ProcessExit,
+        config: &RestartConfig,
+        now: Instant,
+    ) -> RestartDecision {
+        let mut record = self.records.entry(exit.server_id.clone()).or_default();
+        record.last_exit = Some(exit.describe());
+
+        let restart = match config.policy {
+            RestartPolicy::Never => false,
+            RestartPolicy::OnFailure => exit.is_failure(),
+            RestartPolicy::Always => true,
+        };
+        if !restart || record.crash_loop {
+            return RestartDecision::Ignore;
+        }
+
+        let window = config.window();
+        while record.recent.front().is_some_and(|at| now.duration_since(*at) >= window) {
+            record.recent.pop_front();
+        }
+        if record.recent.len() >= config.max_restarts as usize {
+            record.crash_loop = true;
+            record.recent.clear();
+            return RestartDecision::CrashLoop;
+        }
+
+        let delay = config.backoff_for(record.recent.len() as u32);
+        record.recent.push_back(now);
+        record.total += 1;
+        RestartDecision::RestartAfter(delay)
+    }
+
+    /// Whether the server exceeded its restart limit and has not been
+    /// restarted by an operator since.
+    pub fn is_crash_looping(&self, server_id: &str) -> bool {
+        self.records.get(server_id).is_some_and(|record| record.crash_loop)
+    }
+
+    /// Forget the server's recent restarts after an operator restarted it.
+    ///
+    /// Returns whether it was crash looping.
+    pub fn reset(&self, server_id: &str) -> bool {
+        match self.records.get_mut(server_id) {
+            Some(mut record) => {
+                record.recent.clear();
+                std::mem::take(&mut record.crash_loop)
+            },
+            None => false,
+        }
+    }
+
+    /// Restart policy and history of a server, for the Admin API.
+    pub fn status(&self, server_id: &str, config: &RestartConfig) -> RestartStatus {
+        let record = self.records.get(server_id);
+        RestartStatus {
+            policy: config.policy.as_str().to_string(),
+            restarts: record.as_ref().map_or(0, |record| record.total),
+            crash_loop: record.as_ref().is_some_and(|record| record.crash_loop),
+            last_exit: record.and_then(|record| record.last_exit.clone()),
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+
+    fn exit(code: i32) -> ProcessExit {
+        ProcessExit {
+            server_id: "fs".into(),
+            slot: 0,
+            status: Some(ExitStatus::from_raw(code << 8)),
+        }
+    }
+
+    fn config(policy: RestartPolicy) -> RestartConfig {
+        RestartConfig {
+            policy,
+            max_restarts: 3,
+            window_seconds: 60,
+            initial_backoff_ms: 100,
+            max_backoff_ms: 300,
+        }
+    }
+
+    #[test]
+    fn test_policy_decides_which_exits_restart() {
+        let supervisor = RestartSupervisor::new();
+        let now = Instant::now();
+
+        let never = config(RestartPolicy::Never);
+        assert_eq!(
+            supervisor.on_exit(&exit(1), &never, now),
+            RestartDecision::Ignore
+        );
+
+        let on_failure = config(RestartPolicy::OnFailure);
+        assert_eq!(
+            supervisor.on_exit(&exit(0), &on_failure, now),
+            RestartDecision::Ignore
+        );
+        assert!(matches!(
+            supervisor.on_exit(&exit(1), &on_failure, now),
+            RestartDecision::RestartAfter(_)
+        ));
+
+        let always = config(RestartPolicy::Always);
+        assert!(matches!(
+            supervisor.on_exit(&exit(0), &always, now),
+            RestartDecision::RestartAfter(_)
+        ));
+        assert_eq!(
+            supervisor.status("fs", &always).last_exit.as_deref(),
+            Some("exit status: 0")
+        );
+    }
+
+    #[test]
+    fn test_backoff_doubles_until_crash_loop() {
+        let supervisor = RestartSupervisor::new();
+        let config = config(RestartPolicy::OnFailure);
+        let now = Instant::now();
+
+        let delays: Vec<_> = (0..4).map(|_| supervisor.on_exit(&exit(1), &config, now)).collect();
+        assert_eq!(
+            delays,
+            vec![
+                RestartDecision::RestartAfter(Duration::from_millis(100)),
+                RestartDecision::RestartAfter(Duration::from_millis(200)),
+                RestartDecision::RestartAfter(Duration::from_millis(300)),
+                RestartDecision::CrashLoop,
+            ]
+        );
+        assert!(supervisor.is_crash_looping("fs"));
+        // No further restarts until an operator intervenes
+        assert_eq!(
+            supervisor.on_exit(&exit(1), &config, now),
+            RestartDecision::Ignore
+        );
+
+        let status = supervisor.status("fs", &config);
+        assert_eq!(status.restarts, 3);
+        assert!(status.crash_loop);
+
+        assert!(supervisor.reset("fs"));
+        assert!(!supervisor.is_crash_looping("fs"));
+        assert_eq!(
+            supervisor.on_exit(&exit(1), &config, now),
+            RestartDecision::RestartAfter(Duration::from_millis(100))
+        );
+    }
+
+    #[test]
+    fn test_restarts_outside_window_are_forgotten() {
+        let supervisor = RestartSupervisor::new();
+        let config = config(RestartPolicy::Always);
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            supervisor.on_exit(&exit(1), &config, start);
+        }
+        let later = start + Duration::from_secs(61);
+        assert_eq!(
+            supervisor.on_exit(&exit(1), &config, later),
+            RestartDecision::RestartAfter(Duration::from_millis(100))
+        );
+        assert!(!supervisor.is_crash_looping("fs"));
+    }
+}
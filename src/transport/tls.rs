@@ -0,0 +1,96 @@
+//! Client-side TLS for HTTPS and gRPC backends (`transport.tls`).
+
+use std::path::Path;
+
+use reqwest::{Certificate, ClientBuilder, Identity};
+use tonic::transport::ClientTlsConfig;
+
+use crate::config::BackendTlsConfig;
+
+/// Configure a backend HTTP client to trust the extra CA bundle in `tls`
+/// and to present its client certificate (mutual TLS).
+pub fn configure_client(
+    mut builder: ClientBuilder,
+    tls: &BackendTlsConfig,
+) -> Result<ClientBuilder, String> {
+    let read = |path: &Path| {
+        std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+    };
+
+    // Identities from PEM are only supported by the rustls backend
+    builder = builder.use_rustls_tls();
+
+    if let Some(ca_path) = &tls.ca_path {
+        let bundle = Certificate::from_pem_bundle(&read(ca_path)?)
+            .map_err(|e| format!("Invalid CA bundle {}: {}", ca_path.display(), e))?;
+        for certificate in bundle {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    match (&tls.client_cert_path, &tls.client_key_path) {
+        (Some(cert_path), Some(key_path)) => {
+            let mut pem = read(cert_path)?;
+            pem.push(b'\n');
+            pem.extend(read(key_path)?);
+            let identity = Identity::from_pem(&pem).map_err(|e| {
+                format!("Invalid client certificate {}: {}", cert_path.display(), e)
+            })?;
+            builder = builder.identity(identity);
+        },
+        (None, None) => {},
+        _ => return Err("client_cert_path and client_key_path must be set together".to_string()),
+    }
+
+    Ok(builder)
+}
+
+/// TLS settings for a gRPC backend: the public web roots plus the extra CA
+/// bundle in `tls`, and its client certificate (mutual TLS).
+pub fn grpc_client_config(tls: BackendTlsConfig) -> Result<ClientTlsConfig, String> {
+    let read = |path: &Path| {
+        std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+    };
+
+    let mut config = ClientTlsConfig::new().with_webpki_roots();
+    if let Some(ca_path) = &tls.ca_path {
+        config = config.ca_certificate(tonic::transport::Certificate::from_pem(read(ca_path)?));
+    }
+
+    match (&tls.client_cert_path, &tls.client_key_path) {
+        (Some(cert_path), Some(key_path)) => {
+            let identity = tonic::transport::Identity::from_pem(read(cert_path)?, read(key_path)?);
+            config = config.identity(identity);
+        },
+        (None, None) => {},
+        _ => return Err("client_cert_path and client_key_path must be set together".to_string()),
+    }
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture(name: &str) -> Option<PathBuf> {
+        Some(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/tls").join(name))
+    }
+
+    #[test]
+    fn test_configure_client() {
+        let tls = BackendTlsConfig {
+            client_cert_path: fixture("client.pem"),
+            client_key_path: fixture("client-key.pem"),
+            ca_path: fixture("ca.pem"),
+        };
+        assert!(configure_client(reqwest::Client::builder(), &tls).unwrap().build().is_ok());
+
+        let tls = BackendTlsConfig {
+            client_key_path: None,
+            ..tls
+        };
+        assert!(configure_client(reqwest::Client::builder(), &tls).is_err());
+    }
+}
@@ -0,0 +1,269 @@
+//! JSON-RPC over Unix domain sockets (`type: unix`).
+//!
+//! Messages are newline-delimited JSON, exactly as on STDIO, but the
+//! backend is an already running local process listening on a socket. One
+//! connection per socket carries every request; responses are matched to
+//! requests by id, so concurrent requests are pipelined. A connection that
+//! closes is replaced on the next request.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use dashmap::DashMap;
+use serde_json::json;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{oneshot, Mutex};
+
+use crate::transport::limits::ResponseLimit;
+use crate::transport::stdio::{
+    connection_closed, PendingGuard, PendingRequests, ProcessOutput, StdioProcess, TransportError,
+};
+use crate::types::{McpRequest, McpResponse};
+
+#[cfg(unix)]
+type SocketWriter = tokio::net::unix::OwnedWriteHalf;
+#[cfg(not(unix))]
+type SocketWriter = tokio::io::Sink;
+
+/// Connections to Unix socket backends, one per socket path.
+pub struct UnixTransportPool {
+    connections: DashMap<PathBuf, Arc<UnixConnection>>,
+    /// Longest response line accepted
+    max_response_bytes: ResponseLimit,
+}
+
+impl Default for UnixTransportPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UnixTransportPool {
+    /// Create an empty pool; sockets are connected on first use.
+    pub fn new() -> Self {
+        Self {
+            connections: DashMap::new(),
+            max_response_bytes: ResponseLimit::default(),
+        }
+    }
+
+    /// Accept backend responses up to `limit` bytes
+    pub fn with_response_limit(mut self, limit: ResponseLimit) -> Self {
+        self.max_response_bytes = limit;
+        self
+    }
+
+    /// Send `request` to the backend listening on `path` and wait up to
+    /// `timeout` for its response.
+    pub async fn send_request(
+        &self,
+        path: &Path,
+        request: McpRequest,
+        timeout: Duration,
+    ) -> Result<McpResponse, TransportError> {
+        let connection = self.connection(path).await?;
+        let response = connection.call(serde_json::to_value(&request)?, timeout).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Connect to the backend on `path` ahead of its first request.
+    pub async fn prewarm(&self, path: &Path) -> Result<(), TransportError> {
+        self.connection(path).await.map(|_| ())
+    }
+
+    /// The open connection to `path`, connecting if there is none.
+    async fn connection(&self, path: &Path) -> Result<Arc<UnixConnection>, TransportError> {
+        if let Some(connection) = self.connections.get(path) {
+            if connection.is_open() {
+                return Ok(connection.clone());
+            }
+        }
+
+        let connection =
+            Arc::new(UnixConnection::connect(path, self.max_response_bytes.get()).await?);
+        // A request racing this one may have connected first; keep its connection
+        let mut entry = self.connections.entry(path.to_path_buf()).or_insert(connection.clone());
+        if !entry.is_open() {
+            *entry = connection;
+        }
+        Ok(entry.clone())
+    }
+}
+
+/// Check that a backend is listening on `path`.
+pub async fn probe(path: &Path, timeout: Duration) -> Result<(), TransportError> {
+    let connect = UnixConnection::connect(path, 0);
+    tokio::time::timeout(timeout, connect)
+        .await
+        .map_err(|_| TransportError::Timeout)?
+        .map(|_| ())
+}
+
+/// One connection to a Unix socket backend.
+struct UnixConnection {
+    writer: Mutex<SocketWriter>,
+    /// Requests awaiting a response, keyed by the id sent to the backend
+    pending: Arc<PendingRequests>,
+    /// Source of the ids sent to the backend
+    next_id: AtomicU64,
+    /// Cleared by the reader task when the backend closes the connection
+    open: Arc<AtomicBool>,
+}
+
+impl UnixConnection {
+    /// Connect to `path` and start routing responses, dropping lines over
+    /// `max_line_bytes`.
+    #[cfg(unix)]
+    async fn connect(path: &Path, max_line_bytes: usize) -> Result<Self, TransportError> {
+        let stream = tokio::net::UnixStream::connect(path).await.map_err(|e| {
+            TransportError::Io(std::io::Error::new(
+                e.kind(),
+                format!("Failed to connect to {}: {}", path.display(), e),
+            ))
+        })?;
+        let (reader, writer) = stream.into_split();
+
+        let pending = Arc::new(PendingRequests::new());
+        let open = Arc::new(AtomicBool::new(true));
+        // Backends may send notifications; nobody listens for them here
+        let (unsolicited, _) = tokio::sync::mpsc::channel(1);
+        tokio::spawn(StdioProcess::route_stdout(
+            tokio::io::BufReader::new(reader),
+            pending.clone(),
+            open.clone(),
+            unsolicited,
+            ProcessOutput::new(format!("unix:{}", path.display())),
+            max_line_bytes,
+        ));
+
+        Ok(Self {
+            writer: Mutex::new(writer),
+            pending,
+            next_id: AtomicU64::new(0),
+            open,
+        })
+    }
+
+    #[cfg(not(unix))]
+    async fn connect(_path: &Path, _max_line_bytes: usize) -> Result<Self, TransportError> {
+        Err(TransportError::Io(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "Unix sockets are not supported on this platform",
+        )))
+    }
+
+    fn is_open(&self) -> bool {
+        self.open.load(Ordering::SeqCst)
+    }
+
+    /// Send a JSON-RPC request and wait up to `timeout` for its response,
+    /// under an id unique to this connection.
+    async fn call(
+        &self,
+        mut request: serde_json::Value,
+        timeout: Duration,
+    ) -> Result<serde_json::Value, TransportError> {
+        let Some(fields) = request.as_object_mut() else {
+            return Err(TransportError::ProtocolError(
+                "JSON-RPC request must be an object".into(),
+            ));
+        };
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let caller_id = fields.insert("id".into(), json!(id)).unwrap_or_default();
+
+        let (tx, rx) = oneshot::channel();
+        self.pending.insert(id, tx);
+        let _pending = PendingGuard {
+            pending: &self.pending,
+            id,
+        };
+        if !self.is_open() {
+            return Err(connection_closed());
+        }
+
+        let mut line = serde_json::to_vec(&request)?;
+        line.push(b'\n');
+        {
+            let mut writer = self.writer.lock().await;
+            writer.write_all(&line).await?;
+            writer.flush().await?;
+        }
+
+        let mut response = tokio::time::timeout(timeout, rx)
+            .await
+            .map_err(|_| TransportError::Timeout)?
+            .map_err(|_| connection_closed())?;
+        if let Some(fields) = response.as_object_mut() {
+            fields.insert("id".into(), caller_id);
+        }
+        Ok(response)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    /// Answer every request on `listener` with its method name as the result.
+    async fn echo_backend(listener: tokio::net::UnixListener) {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let (reader, mut writer) = stream.into_split();
+                let mut lines = BufReader::new(reader).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let request: serde_json::Value = serde_json::from_str(&line).unwrap();
+                    let response = json!({
+                        "jsonrpc": "2.0",
+                        "id": request["id"],
+                        "result": {"method": request["method"]}
+                    });
+                    let mut out = serde_json::to_vec(&response).unwrap();
+                    out.push(b'\n');
+                    writer.write_all(&out).await.unwrap();
+                }
+            });
+        }
+    }
+
+    #[tokio::test]
+    async fn test_requests_share_a_connection() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("backend.sock");
+        tokio::spawn(echo_backend(tokio::net::UnixListener::bind(&path).unwrap()));
+
+        let pool = UnixTransportPool::new();
+        let timeout = Duration::from_secs(5);
+        let (first, second) = tokio::join!(
+            pool.send_request(
+                &path,
+                McpRequest::new("tools/list", json!({}), Some(json!(1))),
+                timeout
+            ),
+            pool.send_request(
+                &path,
+                McpRequest::new("ping", json!({}), Some(json!("a"))),
+                timeout
+            ),
+        );
+
+        let first = first.unwrap();
+        assert_eq!(first.id, Some(json!(1)));
+        assert_eq!(first.result.unwrap()["method"], "tools/list");
+        assert_eq!(second.unwrap().id, Some(json!("a")));
+        assert_eq!(pool.connections.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_missing_socket_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = UnixTransportPool::new();
+        let request = McpRequest::new("ping", json!({}), Some(json!(1)));
+
+        let path = dir.path().join("missing.sock");
+        let result = pool.send_request(&path, request, Duration::from_secs(1)).await;
+        assert!(matches!(result, Err(TransportError::Io(_))));
+    }
+}
@@ -1,9 +1,17 @@
 //! TUI application state and main loop
 
-use super::{event::Event, tabs::TabId, ui};
-use crate::{config::Config, error::Result};
+use super::{
+    client::TuiClient,
+    event::Event,
+    onboarding::{Onboarding, OnboardingAction},
+    tabs::TabId,
+    ui,
+};
+use crate::{config::Config, error::Result, types::ActionResult};
 use ratatui::{backend::CrosstermBackend, Terminal};
+use std::collections::BTreeMap;
 use std::io;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
@@ -23,14 +31,28 @@ pub struct TuiApp {
     // UI state
     pub scroll_offset: usize,
     pub filter_query: String,
+    /// First-run onboarding, shown instead of the tabs while active
+    pub onboarding: Option<Onboarding>,
+    /// Row selected in the Servers tab
+    pub selected_server: usize,
+    /// Action awaiting confirmation in the status bar
+    pub confirm: Option<ServerAction>,
+    /// Confirmed actions not yet sent to the daemon
+    pub pending_actions: Vec<ServerAction>,
+    /// Outcome of the last action, shown in the status bar for a while
+    pub toast: Option<Toast>,
+    /// Admin API of the daemon being monitored
+    pub daemon_url: Option<String>,
 
     // Control
     pub should_quit: bool,
     pub last_update: Instant,
+
+    config: Arc<Config>,
 }
 
 impl TuiApp {
-    pub fn new(_config: Arc<Config>) -> Self {
+    pub fn new(config: Arc<Config>) -> Self {
         Self {
             active_tab: TabId::Overview,
             metrics_snapshot: MetricsSnapshot::default(),
@@ -40,19 +62,105 @@ impl TuiApp {
             log_buffer: Vec::new(),
             scroll_offset: 0,
             filter_query: String::new(),
+            onboarding: None,
+            selected_server: 0,
+            confirm: None,
+            pending_actions: Vec::new(),
+            toast: None,
+            daemon_url: None,
             should_quit: false,
             last_update: Instant::now(),
+            config,
         }
     }
 
+    /// Start with the onboarding pane if no servers are configured.
+    ///
+    /// Servers added during onboarding are written to `config_path`.
+    pub fn with_onboarding(mut self, config_path: PathBuf) -> Self {
+        if self.config.servers.is_empty() {
+            self.onboarding = Some(Onboarding::new(config_path));
+        }
+        self
+    }
+
+    /// Show which daemon the dashboard is connected to.
+    pub fn with_daemon_url(mut self, url: impl Into<String>) -> Self {
+        self.daemon_url = Some(url.into());
+        self
+    }
+
+    /// Operator tags and notes configured for a server.
+    pub fn server_metadata(&self, id: &str) -> Option<crate::config::Metadata> {
+        self.config.servers.iter().find(|s| s.id == id).map(|s| s.metadata())
+    }
+
     pub fn on_tick(&mut self) {
         // Called every 100ms
         self.last_update = Instant::now();
+        if self.toast.as_ref().is_some_and(|t| t.shown_at.elapsed() >= TOAST_DURATION) {
+            self.toast = None;
+        }
+    }
+
+    /// Server selected in the Servers tab.
+    pub fn selected_server(&self) -> Option<&ServerInfo> {
+        self.servers_snapshot.get(self.selected_server)
+    }
+
+    /// Replace the server list, keeping the selection in range.
+    pub fn update_servers(&mut self, servers: Vec<ServerInfo>) {
+        self.servers_snapshot = servers;
+        self.selected_server =
+            self.selected_server.min(self.servers_snapshot.len().saturating_sub(1));
+    }
+
+    /// Show the outcome of an action in the status bar.
+    pub fn on_action_result(&mut self, result: Result<ActionResult>) {
+        self.toast = Some(match result {
+            Ok(result) => Toast::info(result.message),
+            Err(e) => Toast::error(e.to_string()),
+        });
     }
 
     pub fn on_key(&mut self, key: crossterm::event::KeyEvent) {
         use crossterm::event::{KeyCode, KeyModifiers};
 
+        if let Some(onboarding) = &mut self.onboarding {
+            if key.code == KeyCode::Char('c') && key.modifiers == KeyModifiers::CONTROL {
+                self.should_quit = true;
+                return;
+            }
+            match onboarding.on_key(key) {
+                OnboardingAction::Continue => {},
+                OnboardingAction::Quit => self.should_quit = true,
+                OnboardingAction::Finish(message) => {
+                    self.onboarding = None;
+                    if let Some(message) = message {
+                        self.log_buffer.push(LogEntry {
+                            timestamp: chrono::Utc::now(),
+                            level: LogLevel::Info,
+                            message,
+                        });
+                    }
+                },
+            }
+            return;
+        }
+
+        if let Some(action) = self.confirm.take() {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => self.pending_actions.push(action),
+                KeyCode::Char('n') | KeyCode::Esc => {},
+                _ => self.confirm = Some(action),
+            }
+            return;
+        }
+
+        if self.active_tab == TabId::Servers && self.on_servers_key(key) {
+            return;
+        }
+
         match (key.code, key.modifiers) {
             (KeyCode::Char('q'), _) => self.should_quit = true,
             (KeyCode::Char('c'), KeyModifiers::CONTROL) => self.should_quit = true,
@@ -68,6 +176,48 @@ impl TuiApp {
         }
     }
 
+    /// Handle Servers tab keys: selection and management actions, which
+    /// ask for confirmation first. Returns whether the key was handled.
+    fn on_servers_key(&mut self, key: crossterm::event::KeyEvent) -> bool {
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        if key.modifiers != KeyModifiers::NONE {
+            return false;
+        }
+        let selected = self.selected_server().cloned();
+        let action = match (key.code, selected) {
+            (KeyCode::Up, _) => {
+                self.selected_server = self.selected_server.saturating_sub(1);
+                return true;
+            },
+            (KeyCode::Down, _) => {
+                if self.selected_server + 1 < self.servers_snapshot.len() {
+                    self.selected_server += 1;
+                }
+                return true;
+            },
+            (KeyCode::Char('c'), _) => ServerAction::ClearCache,
+            (KeyCode::Char('e'), Some(server)) if server.enabled => {
+                ServerAction::Disable(server.id)
+            },
+            (KeyCode::Char('e'), Some(server)) => ServerAction::Enable(server.id),
+            (KeyCode::Char('h'), Some(server)) => ServerAction::HealthCheck(server.id),
+            (KeyCode::Char('r'), Some(server)) if server.transport == "STDIO" => {
+                ServerAction::Restart(server.id)
+            },
+            (KeyCode::Char('r'), Some(server)) => {
+                self.toast = Some(Toast::error(format!(
+                    "Only STDIO servers can be restarted ({} uses {})",
+                    server.id, server.transport
+                )));
+                return true;
+            },
+            _ => return false,
+        };
+        self.confirm = Some(action);
+        true
+    }
+
     pub fn next_tab(&mut self) {
         self.active_tab = match self.active_tab {
             TabId::Overview => TabId::Servers,
@@ -89,16 +239,36 @@ impl TuiApp {
 }
 
 /// Run the TUI in a dedicated tokio task
-pub async fn run_tui(config: Arc<Config>, event_rx: mpsc::UnboundedReceiver<Event>) -> Result<()> {
+///
+/// `config_path` is where servers added from the onboarding pane are saved;
+/// without one (a remote daemon) onboarding is skipped. Server management
+/// actions are sent through `client`.
+pub async fn run_tui(
+    config: Arc<Config>,
+    config_path: Option<PathBuf>,
+    client: TuiClient,
+    event_rx: mpsc::UnboundedReceiver<Event>,
+) -> Result<()> {
+    // Actions run on the async runtime while the terminal loop blocks
+    let runtime = tokio::runtime::Handle::current();
+
     // Spawn blocking task for terminal I/O
-    tokio::task::spawn_blocking(move || run_tui_blocking(config, event_rx))
-        .await
-        .map_err(|e| crate::error::Error::Server(format!("TUI task failed: {}", e)))??;
+    tokio::task::spawn_blocking(move || {
+        run_tui_blocking(config, config_path, client, event_rx, runtime)
+    })
+    .await
+    .map_err(|e| crate::error::Error::Server(format!("TUI task failed: {}", e)))??;
 
     Ok(())
 }
 
-fn run_tui_blocking(config: Arc<Config>, event_rx: mpsc::UnboundedReceiver<Event>) -> Result<()> {
+fn run_tui_blocking(
+    config: Arc<Config>,
+    config_path: Option<PathBuf>,
+    client: TuiClient,
+    event_rx: mpsc::UnboundedReceiver<Event>,
+    runtime: tokio::runtime::Handle,
+) -> Result<()> {
     let mut event_rx = event_rx;
     use crossterm::{
         event::{DisableMouseCapture, EnableMouseCapture},
@@ -115,8 +285,16 @@ fn run_tui_blocking(config: Arc<Config>, event_rx: mpsc::UnboundedReceiver<Event
     let mut terminal = Terminal::new(backend)
         .map_err(|e| crate::error::Error::Server(format!("Terminal error: {}", e)))?;
 
+    // Admin API client for server management actions
+    let daemon_url = client.base_url().to_string();
+    let client = Arc::new(client);
+    let (result_tx, mut result_rx) = mpsc::unbounded_channel();
+
     // Create app
-    let mut app = TuiApp::new(config);
+    let mut app = TuiApp::new(config).with_daemon_url(daemon_url);
+    if let Some(config_path) = config_path {
+        app = app.with_onboarding(config_path);
+    }
     let tick_duration = Duration::from_millis(100); // 10 FPS
 
     // Event loop
@@ -144,7 +322,10 @@ fn run_tui_blocking(config: Arc<Config>, event_rx: mpsc::UnboundedReceiver<Event
                     app.metrics_snapshot = snapshot;
                 },
                 Event::ServersUpdate(servers) => {
-                    app.servers_snapshot = servers;
+                    app.update_servers(servers);
+                },
+                Event::RequestsUpdate(requests) => {
+                    app.request_log = requests;
                 },
                 Event::LogMessage(entry) => {
                     app.log_buffer.push(entry);
@@ -158,6 +339,18 @@ fn run_tui_blocking(config: Arc<Config>, event_rx: mpsc::UnboundedReceiver<Event
             }
         }
 
+        // Send confirmed actions and report their outcome
+        for action in app.pending_actions.drain(..) {
+            let client = client.clone();
+            let result_tx = result_tx.clone();
+            runtime.spawn(async move {
+                let _ = result_tx.send(action.run(&client).await);
+            });
+        }
+        while let Ok(result) = result_rx.try_recv() {
+            app.on_action_result(result);
+        }
+
         app.on_tick();
 
         if app.should_quit {
@@ -194,15 +387,126 @@ pub struct MetricsSnapshot {
     pub cache_hit_rate: f64,
     pub error_rate: f64,
     pub active_batches: usize,
+    /// Estimated tokens saved by caching, deduplication and batching, per server
+    pub tokens_saved: BTreeMap<String, u64>,
+    /// Cost of tool calls in dollars since startup, per server
+    pub api_cost: BTreeMap<String, f64>,
+}
+
+impl MetricsSnapshot {
+    /// Estimated tokens saved across all servers.
+    pub fn total_tokens_saved(&self) -> u64 {
+        self.tokens_saved.values().sum()
+    }
+
+    /// Cost of tool calls across all servers, in dollars.
+    pub fn total_api_cost(&self) -> f64 {
+        self.api_cost.values().sum()
+    }
+}
+
+/// How long a toast stays in the status bar
+const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+/// Server management action triggered from the Servers tab
+#[derive(Clone, PartialEq, Debug)]
+pub enum ServerAction {
+    Enable(String),
+    Disable(String),
+    HealthCheck(String),
+    /// Restart a STDIO server's processes
+    Restart(String),
+    ClearCache,
+}
+
+impl ServerAction {
+    /// Confirmation question shown before the action runs.
+    pub fn prompt(&self) -> String {
+        match self {
+            Self::Enable(id) => format!("Enable {}?", id),
+            Self::Disable(id) => format!("Disable {}?", id),
+            Self::HealthCheck(id) => format!("Run a health check of {}?", id),
+            Self::Restart(id) => format!("Restart {}?", id),
+            Self::ClearCache => "Clear the response cache?".to_string(),
+        }
+    }
+
+    /// Perform the action through the daemon's Admin API.
+    pub async fn run(&self, client: &TuiClient) -> Result<ActionResult> {
+        match self {
+            Self::Enable(id) => client.set_server_enabled(id, true).await,
+            Self::Disable(id) => client.set_server_enabled(id, false).await,
+            Self::HealthCheck(id) => client.check_server(id).await,
+            Self::Restart(id) => client.restart_server(id).await,
+            Self::ClearCache => client.clear_cache().await,
+        }
+    }
+}
+
+/// Short-lived status bar message
+#[derive(Clone, Debug)]
+pub struct Toast {
+    pub message: String,
+    pub is_error: bool,
+    pub shown_at: Instant,
+}
+
+impl Toast {
+    pub fn info(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            is_error: false,
+            shown_at: Instant::now(),
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            is_error: true,
+            ..Self::info(message)
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct ServerInfo {
     pub id: String,
     pub name: String,
+    /// Transport name as reported by the Admin API (e.g. "STDIO")
+    pub transport: String,
+    pub enabled: bool,
     pub status: ServerStatus,
     pub health_percentage: u8,
     pub requests_per_second: u32,
+    /// CPU and memory of a STDIO server's processes
+    pub resources: Option<crate::types::ResourceUsage>,
+}
+
+impl From<&crate::types::ServerStatus> for ServerInfo {
+    fn from(server: &crate::types::ServerStatus) -> Self {
+        let crash_loop = server.restart.as_ref().is_some_and(|restart| restart.crash_loop);
+        let status = match server.health.as_deref() {
+            _ if !server.enabled => ServerStatus::Down,
+            _ if crash_loop => ServerStatus::CrashLoop,
+            Some("Unhealthy") => ServerStatus::Down,
+            Some("Degraded") => ServerStatus::Degraded,
+            _ => ServerStatus::Up,
+        };
+        Self {
+            id: server.id.clone(),
+            name: server.name.clone(),
+            transport: server.transport.clone(),
+            enabled: server.enabled,
+            health_percentage: match status {
+                ServerStatus::Up => 100,
+                ServerStatus::Degraded => 50,
+                ServerStatus::Down | ServerStatus::CrashLoop => 0,
+            },
+            status,
+            requests_per_second: 0,
+            resources: server.resources.clone(),
+        }
+    }
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -210,6 +514,8 @@ pub enum ServerStatus {
     Up,
     Degraded,
     Down,
+    /// STDIO processes exceeded their restart limit
+    CrashLoop,
 }
 
 #[derive(Clone)]
@@ -232,9 +538,32 @@ pub enum LogLevel {
 pub struct RequestEntry {
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub method: String,
+    /// Called tool of a tools/call
+    pub tool: Option<String>,
+    /// Backends the request went to, or `proxy` when it answered itself
     pub server_id: String,
     pub latency_ms: f64,
-    pub status_code: u16,
+    /// `success` or `error`
+    pub status: String,
+    pub client: Option<String>,
+}
+
+impl From<&crate::types::RequestRecord> for RequestEntry {
+    fn from(record: &crate::types::RequestRecord) -> Self {
+        Self {
+            timestamp: record.at,
+            method: record.method.clone(),
+            tool: record.tool.clone(),
+            server_id: if record.servers.is_empty() {
+                "proxy".to_string()
+            } else {
+                record.servers.join(", ")
+            },
+            latency_ms: record.latency_ms,
+            status: record.status.clone(),
+            client: record.client.clone(),
+        }
+    }
 }
 
 #[derive(Default, Clone)]
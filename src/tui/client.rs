@@ -1,12 +1,38 @@
+use super::{
+    app::{LogEntry, LogLevel, RequestEntry, ServerInfo},
+    event::Event,
+};
+use crate::config::BackendTlsConfig;
 use crate::error::{Error, Result};
-use crate::types::{HealthStatus, ServerStatus, SystemInfo, ToolInfo};
-use reqwest::Client;
+use crate::proxy::snapshot::StateBundle;
+use crate::types::{ActionResult, HealthStatus, RequestRecord, ServerStatus, SystemInfo, ToolInfo};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
 use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Most recent requests shown in the Requests tab
+const REQUESTS_SHOWN: usize = 500;
+
+/// Credentials and TLS settings for connecting to a remote daemon
+#[derive(Debug, Clone, Default)]
+pub struct RemoteOptions {
+    /// API key sent as a bearer token to admin listeners that require one
+    pub token: Option<String>,
+    /// Extra CA bundle and client certificate (mutual TLS) for `https` URLs
+    pub tls: BackendTlsConfig,
+}
 
 /// HTTP client for communicating with Only1MCP daemon via Admin API
+///
+/// Endpoints the daemon does not expose (older versions, or listeners
+/// without the admin route group) fail with [`Error::Http`], so callers can
+/// tell them apart from connection and authentication errors and degrade
+/// gracefully.
 pub struct TuiClient {
     client: Client,
     base_url: String,
+    token: Option<String>,
 }
 
 impl TuiClient {
@@ -17,8 +43,39 @@ impl TuiClient {
                 .timeout(Duration::from_secs(5))
                 .build()
                 .expect("Failed to build HTTP client"),
-            base_url: format!("http://{}:{}", host, port),
+            base_url: format!("http://{}", crate::config::host_port(host, port)),
+            token: None,
+        }
+    }
+
+    /// Create a TUI client for the daemon at `url` (`http` or `https`),
+    /// e.g. `https://proxy.example.com:8443`.
+    pub fn connect(url: &str, options: &RemoteOptions) -> Result<Self> {
+        let parsed = reqwest::Url::parse(url)
+            .map_err(|e| Error::Config(format!("Invalid daemon URL '{}': {}", url, e)))?;
+        if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+            return Err(Error::Config(format!(
+                "Daemon URL must be http(s)://host[:port], got '{}'",
+                url
+            )));
         }
+
+        let builder = Client::builder().timeout(Duration::from_secs(5));
+        let client = crate::transport::tls::configure_client(builder, &options.tls)
+            .map_err(Error::Config)?
+            .build()
+            .map_err(|e| Error::Config(format!("Failed to build HTTP client: {}", e)))?;
+
+        Ok(Self {
+            client,
+            base_url: url.trim_end_matches('/').to_string(),
+            token: options.token.clone(),
+        })
+    }
+
+    /// Base URL of the daemon this client talks to
+    pub fn base_url(&self) -> &str {
+        &self.base_url
     }
 
     /// Check if daemon is running and responding
@@ -26,103 +83,189 @@ impl TuiClient {
         self.get_health().await.is_ok()
     }
 
-    /// GET /api/v1/admin/servers
-    pub async fn get_servers(&self) -> Result<Vec<ServerStatus>> {
-        let url = format!("{}/api/v1/admin/servers", self.base_url);
-
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| Error::Transport(format!("Failed to fetch servers: {}", e)))?;
+    /// Send the server list to the TUI every `interval` until it quits.
+    ///
+    /// Failures are reported to the Logs tab once per distinct error, so an
+    /// unreachable daemon or a missing endpoint does not flood it.
+    pub async fn poll_servers(self, events: mpsc::UnboundedSender<Event>, interval: Duration) {
+        let mut last_error = None;
+        while !events.is_closed() {
+            let event = match self.get_servers().await {
+                Ok(servers) => {
+                    last_error = None;
+                    Some(Event::ServersUpdate(
+                        servers.iter().map(ServerInfo::from).collect(),
+                    ))
+                },
+                Err(e) => unavailable(&mut last_error, "Server list", e),
+            };
+            if let Some(event) = event {
+                if events.send(event).is_err() {
+                    break;
+                }
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
 
-        if !response.status().is_success() {
-            return Err(Error::Transport(format!(
-                "HTTP {}: {}",
-                response.status(),
-                url
-            )));
+    /// Send the daemon's request history to the TUI every `interval` until
+    /// it quits, reporting failures like [`Self::poll_servers`].
+    pub async fn poll_requests(self, events: mpsc::UnboundedSender<Event>, interval: Duration) {
+        let mut last_error = None;
+        while !events.is_closed() {
+            let event = match self.get_requests(REQUESTS_SHOWN).await {
+                Ok(requests) => {
+                    last_error = None;
+                    Some(Event::RequestsUpdate(
+                        requests.iter().rev().map(RequestEntry::from).collect(),
+                    ))
+                },
+                Err(e) => unavailable(&mut last_error, "Request history", e),
+            };
+            if let Some(event) = event {
+                if events.send(event).is_err() {
+                    break;
+                }
+            }
+            tokio::time::sleep(interval).await;
         }
+    }
 
-        response
-            .json()
-            .await
-            .map_err(|e| Error::Transport(format!("Failed to parse servers: {}", e)))
+    /// GET /api/v1/admin/servers
+    pub async fn get_servers(&self) -> Result<Vec<ServerStatus>> {
+        self.get_json("servers", "servers").await
+    }
+
+    /// GET /api/v1/admin/requests, newest first
+    pub async fn get_requests(&self, limit: usize) -> Result<Vec<RequestRecord>> {
+        self.get_json(&format!("requests?limit={}", limit), "request history").await
     }
 
     /// GET /api/v1/admin/tools
     pub async fn get_tools(&self) -> Result<Vec<ToolInfo>> {
-        let url = format!("{}/api/v1/admin/tools", self.base_url);
+        self.get_json("tools", "tools").await
+    }
+
+    /// GET /api/v1/admin/health
+    pub async fn get_health(&self) -> Result<HealthStatus> {
+        self.get_json("health", "health").await
+    }
+
+    /// GET /api/v1/admin/system
+    pub async fn get_system_info(&self) -> Result<SystemInfo> {
+        self.get_json("system", "system info").await
+    }
 
+    /// GET /api/v1/admin/state
+    pub async fn get_state(&self) -> Result<StateBundle> {
+        self.get_json("state", "state").await
+    }
+
+    /// POST /api/v1/admin/servers/:id/enable or /disable
+    pub async fn set_server_enabled(&self, server_id: &str, enabled: bool) -> Result<ActionResult> {
+        let action = if enabled { "enable" } else { "disable" };
+        self.post_action(&format!("servers/{}/{}", server_id, action)).await
+    }
+
+    /// POST /api/v1/admin/servers/:id/health-check
+    pub async fn check_server(&self, server_id: &str) -> Result<ActionResult> {
+        self.post_action(&format!("servers/{}/health-check", server_id)).await
+    }
+
+    /// POST /api/v1/admin/servers/:id/restart
+    pub async fn restart_server(&self, server_id: &str) -> Result<ActionResult> {
+        self.post_action(&format!("servers/{}/restart", server_id)).await
+    }
+
+    /// POST /api/v1/admin/cache/clear
+    pub async fn clear_cache(&self) -> Result<ActionResult> {
+        self.post_action("cache/clear").await
+    }
+
+    /// POST /api/v1/admin/logs/rotate
+    pub async fn rotate_logs(&self) -> Result<ActionResult> {
+        self.post_action("logs/rotate").await
+    }
+
+    /// GET an Admin API endpoint and parse its JSON body.
+    async fn get_json<T: DeserializeOwned>(&self, path: &str, what: &str) -> Result<T> {
         let response = self
-            .client
-            .get(&url)
+            .authorized(self.client.get(self.url(path)))
             .send()
             .await
-            .map_err(|e| Error::Transport(format!("Failed to fetch tools: {}", e)))?;
+            .map_err(|e| Error::Transport(format!("Failed to fetch {}: {}", what, e)))?;
 
-        if !response.status().is_success() {
-            return Err(Error::Transport(format!(
-                "HTTP {}: {}",
-                response.status(),
-                url
-            )));
-        }
-
-        response
+        self.check(response, path)
+            .await?
             .json()
             .await
-            .map_err(|e| Error::Transport(format!("Failed to parse tools: {}", e)))
+            .map_err(|e| Error::Transport(format!("Failed to parse {}: {}", what, e)))
     }
 
-    /// GET /api/v1/admin/health
-    pub async fn get_health(&self) -> Result<HealthStatus> {
-        let url = format!("{}/api/v1/admin/health", self.base_url);
-
+    /// POST to an Admin API action, reporting the daemon's error message on
+    /// failure.
+    async fn post_action(&self, path: &str) -> Result<ActionResult> {
         let response = self
-            .client
-            .get(&url)
+            .authorized(self.client.post(self.url(path)))
             .send()
             .await
-            .map_err(|e| Error::Transport(format!("Failed to fetch health: {}", e)))?;
+            .map_err(|e| Error::Transport(format!("Failed to send action: {}", e)))?;
 
-        if !response.status().is_success() {
-            return Err(Error::Transport(format!(
-                "HTTP {}: {}",
-                response.status(),
-                url
-            )));
-        }
-
-        response
+        self.check(response, path)
+            .await?
             .json()
             .await
-            .map_err(|e| Error::Transport(format!("Failed to parse health: {}", e)))
+            .map_err(|e| Error::Transport(format!("Failed to parse action result: {}", e)))
     }
 
-    /// GET /api/v1/admin/system
-    pub async fn get_system_info(&self) -> Result<SystemInfo> {
-        let url = format!("{}/api/v1/admin/system", self.base_url);
+    fn url(&self, path: &str) -> String {
+        format!("{}/api/v1/admin/{}", self.base_url, path)
+    }
 
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| Error::Transport(format!("Failed to fetch system info: {}", e)))?;
+    fn authorized(&self, request: RequestBuilder) -> RequestBuilder {
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
 
-        if !response.status().is_success() {
-            return Err(Error::Transport(format!(
-                "HTTP {}: {}",
-                response.status(),
-                url
-            )));
+    /// Map unsuccessful responses to errors. Unknown routes come back as an
+    /// empty 404, unlike the handlers' own 404s which explain what is missing.
+    async fn check(&self, response: Response, path: &str) -> Result<Response> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
         }
 
-        response
-            .json()
-            .await
-            .map_err(|e| Error::Transport(format!("Failed to parse system info: {}", e)))
+        let message = response.text().await.unwrap_or_default();
+        Err(match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Error::AuthFailed(format!(
+                "{} rejected the credentials (HTTP {})",
+                self.base_url, status
+            )),
+            StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED if message.is_empty() => {
+                Error::Http(format!("{} is not supported by this daemon", path))
+            },
+            _ if message.is_empty() => {
+                Error::Transport(format!("HTTP {}: {}", status, self.url(path)))
+            },
+            _ => Error::Transport(message),
+        })
+    }
+}
+
+/// A Logs tab warning that `what` could not be fetched, or `None` when the
+/// same error was already reported so a down daemon does not flood it.
+fn unavailable(last_error: &mut Option<String>, what: &str, error: Error) -> Option<Event> {
+    let error = error.to_string();
+    if last_error.as_ref() == Some(&error) {
+        return None;
     }
+    let message = format!("{} unavailable: {}", what, error);
+    *last_error = Some(error);
+    Some(Event::LogMessage(LogEntry {
+        timestamp: chrono::Utc::now(),
+        level: LogLevel::Warn,
+        message,
+    }))
 }
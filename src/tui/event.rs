@@ -1,6 +1,6 @@
 //! Event types for TUI updates
 
-use super::app::{LogEntry, MetricsSnapshot, ServerInfo};
+use super::app::{LogEntry, MetricsSnapshot, RequestEntry, ServerInfo};
 
 #[derive(Clone)]
 pub enum Event {
@@ -10,6 +10,9 @@ pub enum Event {
     /// Server list updated
     ServersUpdate(Vec<ServerInfo>),
 
+    /// Request history updated, oldest first
+    RequestsUpdate(Vec<RequestEntry>),
+
     /// New log message
     LogMessage(LogEntry),
 
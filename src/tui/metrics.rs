@@ -2,6 +2,7 @@
 
 use crate::tui::app::MetricsSnapshot;
 use prometheus::proto::MetricFamily;
+use std::collections::BTreeMap;
 
 pub fn scrape_metrics() -> MetricsSnapshot {
     let metrics = prometheus::default_registry().gather();
@@ -37,6 +38,15 @@ pub fn scrape_metrics() -> MetricsSnapshot {
             "only1mcp_active_batches" => {
                 snapshot.active_batches = get_gauge_value(&mf) as usize;
             },
+            "only1mcp_context_tokens_saved_total" => {
+                snapshot.tokens_saved = sum_by_server(&mf)
+                    .into_iter()
+                    .map(|(server, tokens)| (server, tokens as u64))
+                    .collect();
+            },
+            "only1mcp_api_cost_dollars_total" => {
+                snapshot.api_cost = sum_by_server(&mf);
+            },
             _ => {},
         }
     }
@@ -52,6 +62,18 @@ fn get_gauge_value(mf: &MetricFamily) -> f64 {
     mf.get_metric().first().map(|m| m.get_gauge().get_value()).unwrap_or(0.0)
 }
 
+/// Sum a counter over its other labels, keyed by `server_id`.
+fn sum_by_server(mf: &MetricFamily) -> BTreeMap<String, f64> {
+    let mut sums = BTreeMap::new();
+    for m in mf.get_metric() {
+        let Some(server) = m.get_label().iter().find(|l| l.get_name() == "server_id") else {
+            continue;
+        };
+        *sums.entry(server.get_value().to_string()).or_insert(0.0) += m.get_counter().get_value();
+    }
+    sums
+}
+
 fn calculate_rate(mf: &MetricFamily) -> f64 {
     // Simple rate calculation (total / uptime)
     // In production, track previous value and calculate delta
@@ -6,13 +6,17 @@ mod app;
 pub mod client;
 mod event;
 mod metrics;
+mod onboarding;
 mod tabs;
 mod ui;
 
 #[cfg(test)]
 mod tests;
 
-pub use app::{run_tui, LogEntry, LogLevel, MetricsSnapshot, ServerInfo, ServerStatus, TuiApp};
-pub use client::TuiClient;
+pub use app::{
+    run_tui, LogEntry, LogLevel, MetricsSnapshot, ServerAction, ServerInfo, ServerStatus, Toast,
+    TuiApp,
+};
+pub use client::{RemoteOptions, TuiClient};
 pub use event::Event;
 pub use metrics::scrape_metrics;
@@ -0,0 +1,461 @@
+//! First-run onboarding pane
+//!
+//! Shown instead of the dashboard when no backend servers are configured.
+//! Servers can be imported from Claude Desktop, collected from every MCP
+//! client found on the machine, or entered by hand. Everything is written to
+//! the config file, which a running proxy picks up through hot reload.
+
+use crate::config::{
+    edit::{add_server, ServerSpec},
+    import::{self, McpClient},
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+use std::path::PathBuf;
+
+const MENU: [&str; 4] = [
+    "Import servers from Claude Desktop",
+    "Discover servers from installed MCP clients",
+    "Add a server manually",
+    "Skip to dashboard",
+];
+
+const TRANSPORTS: [&str; 4] = ["stdio", "http", "sse", "streamable_http"];
+
+/// Result of handling a key press during onboarding
+#[derive(Debug, Clone, PartialEq)]
+pub enum OnboardingAction {
+    /// Stay on the onboarding pane
+    Continue,
+    /// Leave onboarding, with an optional summary for the log
+    Finish(Option<String>),
+    /// Quit the TUI
+    Quit,
+}
+
+#[derive(Debug, Clone)]
+pub enum OnboardingStep {
+    /// Choose how to add the first servers
+    Menu,
+    /// Pick which imported servers to add
+    Review {
+        source: String,
+        servers: Vec<ServerSpec>,
+        selected: Vec<bool>,
+    },
+    /// Add-server wizard
+    Wizard(ServerForm),
+}
+
+/// Onboarding state
+pub struct Onboarding {
+    config_path: PathBuf,
+    pub step: OnboardingStep,
+    pub cursor: usize,
+    /// Result of the last action (e.g. "no config found")
+    pub message: Option<String>,
+}
+
+impl Onboarding {
+    pub fn new(config_path: PathBuf) -> Self {
+        Self {
+            config_path,
+            step: OnboardingStep::Menu,
+            cursor: 0,
+            message: None,
+        }
+    }
+
+    pub fn on_key(&mut self, key: KeyEvent) -> OnboardingAction {
+        match self.step {
+            OnboardingStep::Menu => self.on_menu_key(key),
+            OnboardingStep::Review { .. } => self.on_review_key(key),
+            OnboardingStep::Wizard(_) => self.on_wizard_key(key),
+        }
+    }
+
+    fn on_menu_key(&mut self, key: KeyEvent) -> OnboardingAction {
+        match key.code {
+            KeyCode::Char('q') => return OnboardingAction::Quit,
+            KeyCode::Esc => return OnboardingAction::Finish(None),
+            KeyCode::Up => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Down => self.cursor = (self.cursor + 1).min(MENU.len() - 1),
+            KeyCode::Enter => match self.cursor {
+                0 => self.import_claude_desktop(),
+                1 => self.discover(),
+                2 => self.open_step(OnboardingStep::Wizard(ServerForm::default())),
+                _ => return OnboardingAction::Finish(None),
+            },
+            _ => {},
+        }
+        OnboardingAction::Continue
+    }
+
+    fn on_review_key(&mut self, key: KeyEvent) -> OnboardingAction {
+        let OnboardingStep::Review {
+            servers, selected, ..
+        } = &mut self.step
+        else {
+            return OnboardingAction::Continue;
+        };
+
+        match key.code {
+            KeyCode::Esc => self.back_to_menu(None),
+            KeyCode::Up => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Down => self.cursor = (self.cursor + 1).min(servers.len().saturating_sub(1)),
+            KeyCode::Char(' ') => {
+                if let Some(flag) = selected.get_mut(self.cursor) {
+                    *flag = !*flag;
+                }
+            },
+            KeyCode::Enter => {
+                let chosen: Vec<ServerSpec> = servers
+                    .iter()
+                    .zip(selected.iter())
+                    .filter(|(_, selected)| **selected)
+                    .map(|(spec, _)| spec.clone())
+                    .collect();
+                if chosen.is_empty() {
+                    self.message = Some("Select at least one server (Space)".into());
+                    return OnboardingAction::Continue;
+                }
+
+                match import::import_servers(&self.config_path, &chosen) {
+                    Ok(summary) if !summary.added.is_empty() => {
+                        let mut message = format!("Imported servers: {}", summary.added.join(", "));
+                        if !summary.skipped.is_empty() {
+                            message.push_str(&format!(
+                                " (already configured: {})",
+                                summary.skipped.join(", ")
+                            ));
+                        }
+                        return OnboardingAction::Finish(Some(message));
+                    },
+                    Ok(summary) => self.back_to_menu(Some(format!(
+                        "Nothing imported, already configured: {}",
+                        summary.skipped.join(", ")
+                    ))),
+                    Err(e) => self.message = Some(format!("Import failed: {}", e)),
+                }
+            },
+            _ => {},
+        }
+        OnboardingAction::Continue
+    }
+
+    fn on_wizard_key(&mut self, key: KeyEvent) -> OnboardingAction {
+        let OnboardingStep::Wizard(form) = &mut self.step else {
+            return OnboardingAction::Continue;
+        };
+
+        match key.code {
+            KeyCode::Esc => self.back_to_menu(None),
+            KeyCode::Enter => match form.to_spec() {
+                Ok(spec) => match add_server(&self.config_path, &spec) {
+                    Ok(()) => {
+                        return OnboardingAction::Finish(Some(format!(
+                            "Added server '{}'",
+                            spec.id
+                        )))
+                    },
+                    Err(e) => self.message = Some(e.to_string()),
+                },
+                Err(e) => self.message = Some(e),
+            },
+            _ => form.on_key(key),
+        }
+        OnboardingAction::Continue
+    }
+
+    fn import_claude_desktop(&mut self) {
+        let client = McpClient::ClaudeDesktop;
+        match import::load_client(client) {
+            Ok(Some(found)) => self.review(found.path.display().to_string(), found.servers),
+            Ok(None) => {
+                let location = client
+                    .config_path()
+                    .map(|path| path.display().to_string())
+                    .unwrap_or_else(|| "its default location".into());
+                self.message = Some(format!("No Claude Desktop config found at {}", location));
+            },
+            Err(e) => self.message = Some(e.to_string()),
+        }
+    }
+
+    fn discover(&mut self) {
+        let found = import::discover();
+        if found.is_empty() {
+            self.message = Some("No MCP client configurations found".into());
+            return;
+        }
+
+        let source = found.iter().map(|c| c.client.label()).collect::<Vec<_>>().join(", ");
+        let mut servers: Vec<ServerSpec> = Vec::new();
+        for spec in found.into_iter().flat_map(|c| c.servers) {
+            if !servers.iter().any(|s| s.id == spec.id) {
+                servers.push(spec);
+            }
+        }
+        self.review(source, servers);
+    }
+
+    fn review(&mut self, source: String, servers: Vec<ServerSpec>) {
+        if servers.is_empty() {
+            self.message = Some(format!("No servers configured in {}", source));
+            return;
+        }
+        let selected = vec![true; servers.len()];
+        self.open_step(OnboardingStep::Review {
+            source,
+            servers,
+            selected,
+        });
+    }
+
+    fn open_step(&mut self, step: OnboardingStep) {
+        self.step = step;
+        self.cursor = 0;
+        self.message = None;
+    }
+
+    fn back_to_menu(&mut self, message: Option<String>) {
+        self.open_step(OnboardingStep::Menu);
+        self.message = message;
+    }
+}
+
+/// Field values of the add-server wizard
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServerForm {
+    pub id: String,
+    pub name: String,
+    /// Index into the supported transports
+    pub transport: usize,
+    /// Command (stdio) or URL (HTTP-based transports)
+    pub target: String,
+    /// Space-separated command arguments (stdio only)
+    pub args: String,
+    pub focus: usize,
+}
+
+impl ServerForm {
+    /// Number of fields shown; arguments only apply to stdio
+    fn field_count(&self) -> usize {
+        if self.is_stdio() {
+            5
+        } else {
+            4
+        }
+    }
+
+    fn on_key(&mut self, key: KeyEvent) {
+        let fields = self.field_count();
+        match key.code {
+            KeyCode::Tab | KeyCode::Down => self.focus = (self.focus + 1) % fields,
+            KeyCode::BackTab | KeyCode::Up => self.focus = (self.focus + fields - 1) % fields,
+            KeyCode::Left if self.focus == 2 => {
+                self.transport = (self.transport + TRANSPORTS.len() - 1) % TRANSPORTS.len()
+            },
+            KeyCode::Right | KeyCode::Char(' ') if self.focus == 2 => {
+                self.transport = (self.transport + 1) % TRANSPORTS.len()
+            },
+            KeyCode::Backspace => {
+                if let Some(field) = self.text_field() {
+                    field.pop();
+                }
+            },
+            KeyCode::Char(c) => {
+                if let Some(field) = self.text_field() {
+                    field.push(c);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn text_field(&mut self) -> Option<&mut String> {
+        match self.focus {
+            0 => Some(&mut self.id),
+            1 => Some(&mut self.name),
+            3 => Some(&mut self.target),
+            4 => Some(&mut self.args),
+            _ => None,
+        }
+    }
+
+    fn is_stdio(&self) -> bool {
+        self.transport == 0
+    }
+
+    fn to_spec(&self) -> std::result::Result<ServerSpec, String> {
+        let id = self.id.trim();
+        if id.is_empty() {
+            return Err("Server ID is required".into());
+        }
+        let target = self.target.trim();
+        if target.is_empty() {
+            return Err(if self.is_stdio() {
+                "Command is required".into()
+            } else {
+                "URL is required".into()
+            });
+        }
+        let name = match self.name.trim() {
+            "" => id,
+            name => name,
+        };
+
+        let spec = ServerSpec {
+            id: id.to_string(),
+            name: name.to_string(),
+            transport: TRANSPORTS[self.transport].to_string(),
+            ..Default::default()
+        };
+        Ok(if self.is_stdio() {
+            ServerSpec {
+                command: Some(target.to_string()),
+                args: self.args.split_whitespace().map(String::from).collect(),
+                ..spec
+            }
+        } else {
+            ServerSpec {
+                url: Some(target.to_string()),
+                ..spec
+            }
+        })
+    }
+}
+
+pub fn draw(f: &mut Frame, area: Rect, onboarding: &Onboarding) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4), // Intro
+            Constraint::Min(0),    // Current step
+            Constraint::Length(3), // Message
+        ])
+        .split(area);
+
+    let intro = Paragraph::new(vec![
+        Line::from(Span::styled(
+            "Welcome to Only1MCP",
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )),
+        Line::from("No MCP servers are configured yet. Let's add some."),
+    ])
+    .block(Block::default().borders(Borders::ALL).title("Getting Started"));
+    f.render_widget(intro, chunks[0]);
+
+    match &onboarding.step {
+        OnboardingStep::Menu => draw_menu(f, chunks[1], onboarding.cursor),
+        OnboardingStep::Review {
+            source,
+            servers,
+            selected,
+        } => draw_review(f, chunks[1], source, servers, selected, onboarding.cursor),
+        OnboardingStep::Wizard(form) => draw_wizard(f, chunks[1], form),
+    }
+
+    let message = Paragraph::new(onboarding.message.clone().unwrap_or_default())
+        .style(Style::default().fg(Color::Yellow))
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(message, chunks[2]);
+}
+
+fn draw_menu(f: &mut Frame, area: Rect, cursor: usize) {
+    let items: Vec<ListItem> = MENU
+        .iter()
+        .enumerate()
+        .map(|(i, label)| ListItem::new(format!("{} {}", marker(i == cursor), label)))
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("What would you like to do?"));
+    f.render_widget(list, area);
+}
+
+fn draw_review(
+    f: &mut Frame,
+    area: Rect,
+    source: &str,
+    servers: &[ServerSpec],
+    selected: &[bool],
+    cursor: usize,
+) {
+    let items: Vec<ListItem> = servers
+        .iter()
+        .zip(selected)
+        .enumerate()
+        .map(|(i, (spec, selected))| {
+            let target = spec.command.as_deref().or(spec.url.as_deref()).unwrap_or_default();
+            let line = format!(
+                "{} [{}] {:<20} {:<16} {}",
+                marker(i == cursor),
+                if *selected { "x" } else { " " },
+                spec.id,
+                spec.transport,
+                target
+            );
+            let style =
+                if i == cursor { Style::default().fg(Color::Yellow) } else { Style::default() };
+            ListItem::new(line).style(style)
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Servers found in {}", source)),
+    );
+    f.render_widget(list, area);
+}
+
+fn draw_wizard(f: &mut Frame, area: Rect, form: &ServerForm) {
+    let target_label = if form.is_stdio() { "Command" } else { "URL" };
+    let transport = format!("< {} >", TRANSPORTS[form.transport]);
+    let fields = [
+        ("Server ID", form.id.as_str()),
+        ("Name", form.name.as_str()),
+        ("Transport", transport.as_str()),
+        (target_label, form.target.as_str()),
+        ("Arguments", form.args.as_str()),
+    ];
+
+    let lines: Vec<Line> = fields
+        .iter()
+        .take(form.field_count())
+        .enumerate()
+        .map(|(i, (label, value))| {
+            let focused = i == form.focus;
+            let style = if focused {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            Line::from(vec![
+                Span::styled(format!("{} {:<10} ", marker(focused), label), style),
+                Span::raw(value.to_string()),
+                Span::styled(if focused { "_" } else { "" }, style),
+            ])
+        })
+        .collect();
+
+    let wizard =
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Add MCP Server"));
+    f.render_widget(wizard, area);
+}
+
+fn marker(active: bool) -> &'static str {
+    if active {
+        ">"
+    } else {
+        " "
+    }
+}
@@ -16,6 +16,8 @@ pub fn draw(f: &mut Frame, area: Rect, app: &TuiApp) {
             Constraint::Length(3), // Latency percentiles
             Constraint::Length(3), // Servers + Cache
             Constraint::Length(3), // Error rate + Batches
+            Constraint::Length(3), // Tokens saved
+            Constraint::Length(3), // API cost
         ])
         .split(area);
 
@@ -24,6 +26,8 @@ pub fn draw(f: &mut Frame, area: Rect, app: &TuiApp) {
     draw_latency_percentiles(f, chunks[2], app);
     draw_servers_cache(f, chunks[3], app);
     draw_error_batches(f, chunks[4], app);
+    draw_tokens_saved(f, chunks[5], app);
+    draw_api_cost(f, chunks[6], app);
 }
 
 fn draw_uptime_status(f: &mut Frame, area: Rect, app: &TuiApp) {
@@ -129,6 +133,63 @@ fn draw_error_batches(f: &mut Frame, area: Rect, app: &TuiApp) {
     f.render_widget(text, area);
 }
 
+fn draw_tokens_saved(f: &mut Frame, area: Rect, app: &TuiApp) {
+    let snapshot = &app.metrics_snapshot;
+    let mut top: Vec<_> = snapshot.tokens_saved.iter().collect();
+    top.sort_by(|a, b| b.1.cmp(a.1));
+
+    let mut spans = vec![
+        Span::raw("Tokens Saved: "),
+        Span::styled(
+            format_tokens(snapshot.total_tokens_saved()),
+            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+        ),
+    ];
+    for (server, tokens) in top.into_iter().take(3) {
+        spans.push(Span::raw(format!("   {}: ", server)));
+        spans.push(Span::styled(
+            format_tokens(*tokens),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+
+    let text = Paragraph::new(Line::from(spans)).block(Block::default().borders(Borders::ALL));
+    f.render_widget(text, area);
+}
+
+fn draw_api_cost(f: &mut Frame, area: Rect, app: &TuiApp) {
+    let snapshot = &app.metrics_snapshot;
+    let mut top: Vec<_> = snapshot.api_cost.iter().filter(|(_, cost)| **cost > 0.0).collect();
+    top.sort_by(|a, b| b.1.total_cmp(a.1));
+
+    let mut spans = vec![
+        Span::raw("API Cost: "),
+        Span::styled(
+            format!("${:.2}", snapshot.total_api_cost()),
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        ),
+    ];
+    for (server, cost) in top.into_iter().take(3) {
+        spans.push(Span::raw(format!("   {}: ", server)));
+        spans.push(Span::styled(
+            format!("${:.2}", cost),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+
+    let text = Paragraph::new(Line::from(spans)).block(Block::default().borders(Borders::ALL));
+    f.render_widget(text, area);
+}
+
+/// Compact token count, e.g. `950`, `12.3K`, `4.1M`.
+pub(crate) fn format_tokens(tokens: u64) -> String {
+    match tokens {
+        0..=999 => tokens.to_string(),
+        1_000..=999_999 => format!("{:.1}K", tokens as f64 / 1_000.0),
+        _ => format!("{:.1}M", tokens as f64 / 1_000_000.0),
+    }
+}
+
 fn format_uptime(seconds: u64) -> String {
     let hours = seconds / 3600;
     let minutes = (seconds % 3600) / 60;
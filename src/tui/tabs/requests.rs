@@ -13,7 +13,9 @@ pub fn draw(f: &mut Frame, area: Rect, app: &TuiApp) {
     let headers = Row::new(vec![
         Cell::from("Time").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         Cell::from("Method").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Tool").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         Cell::from("Server").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Client").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         Cell::from("Latency").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         Cell::from("Status").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
     ])
@@ -37,14 +39,13 @@ pub fn draw(f: &mut Frame, area: Rect, app: &TuiApp) {
         .map(|req| {
             let time_str = req.timestamp.format("%H:%M:%S").to_string();
 
-            let status_text = format!("{}", req.status_code);
-            let status_color = match req.status_code {
-                200..=299 => Color::Green,
-                400..=499 => Color::Yellow,
-                500..=599 => Color::Red,
+            let status_color = match req.status.as_str() {
+                "success" => Color::Green,
+                "error" => Color::Red,
                 _ => Color::White,
             };
-            let status_cell = Cell::from(status_text).style(Style::default().fg(status_color));
+            let status_cell =
+                Cell::from(req.status.clone()).style(Style::default().fg(status_color));
 
             let latency_text = format!("{:.2}ms", req.latency_ms);
             let latency_color = if req.latency_ms < 50.0 {
@@ -59,7 +60,9 @@ pub fn draw(f: &mut Frame, area: Rect, app: &TuiApp) {
             Row::new(vec![
                 Cell::from(time_str),
                 Cell::from(req.method.clone()),
+                Cell::from(req.tool.clone().unwrap_or_default()),
                 Cell::from(req.server_id.clone()),
+                Cell::from(req.client.clone().unwrap_or_default()),
                 latency_cell,
                 status_cell,
             ])
@@ -71,8 +74,10 @@ pub fn draw(f: &mut Frame, area: Rect, app: &TuiApp) {
         rows,
         [
             Constraint::Length(10), // Time
-            Constraint::Length(12), // Method
+            Constraint::Length(16), // Method
+            Constraint::Length(20), // Tool
             Constraint::Length(20), // Server
+            Constraint::Length(14), // Client
             Constraint::Length(12), // Latency
             Constraint::Length(8),  // Status
         ],
@@ -1,4 +1,5 @@
 use crate::tui::app::{ServerStatus, TuiApp};
+use crate::tui::tabs::overview::format_tokens;
 use ratatui::{
     layout::{Constraint, Rect},
     style::{Color, Modifier, Style},
@@ -16,19 +17,31 @@ pub fn draw(f: &mut Frame, area: Rect, app: &TuiApp) {
         Cell::from("Status").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         Cell::from("Health").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         Cell::from("RPS").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Saved").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("CPU").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Memory").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Tags").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Notes").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
     ])
     .height(1);
 
     // Table rows
     let rows: Vec<Row> = servers
         .iter()
-        .map(|server| {
+        .enumerate()
+        .map(|(index, server)| {
             let status_cell = match server.status {
+                _ if !server.enabled => {
+                    Cell::from("⏸ DISABLED").style(Style::default().fg(Color::DarkGray))
+                },
                 ServerStatus::Up => Cell::from("✅ UP").style(Style::default().fg(Color::Green)),
                 ServerStatus::Degraded => {
                     Cell::from("⚠️  DEGRADED").style(Style::default().fg(Color::Yellow))
                 },
                 ServerStatus::Down => Cell::from("🔴 DOWN").style(Style::default().fg(Color::Red)),
+                ServerStatus::CrashLoop => {
+                    Cell::from("🔁 CRASHING").style(Style::default().fg(Color::Red))
+                },
             };
 
             let health_text = format!("{}%", server.health_percentage);
@@ -39,13 +52,28 @@ pub fn draw(f: &mut Frame, area: Rect, app: &TuiApp) {
             };
             let health_cell = Cell::from(health_text).style(Style::default().fg(health_color));
 
-            Row::new(vec![
+            let metadata = app.server_metadata(&server.id).unwrap_or_default();
+            let (cpu, memory) = format_resources(server.resources.as_ref());
+
+            let row = Row::new(vec![
                 Cell::from(server.id.clone()),
                 Cell::from(server.name.clone()),
                 status_cell,
                 health_cell,
                 Cell::from(format!("{}", server.requests_per_second)),
-            ])
+                Cell::from(format_tokens(
+                    app.metrics_snapshot.tokens_saved.get(&server.id).copied().unwrap_or(0),
+                )),
+                Cell::from(cpu),
+                Cell::from(memory),
+                Cell::from(metadata.tags.join(", ")).style(Style::default().fg(Color::Magenta)),
+                Cell::from(metadata.notes.unwrap_or_default()),
+            ]);
+            if index == app.selected_server {
+                row.style(Style::default().add_modifier(Modifier::REVERSED))
+            } else {
+                row
+            }
         })
         .collect();
 
@@ -58,6 +86,11 @@ pub fn draw(f: &mut Frame, area: Rect, app: &TuiApp) {
             Constraint::Length(12), // Status
             Constraint::Length(8),  // Health
             Constraint::Length(8),  // RPS
+            Constraint::Length(8),  // Saved tokens
+            Constraint::Length(6),  // CPU
+            Constraint::Length(9),  // Memory
+            Constraint::Length(20), // Tags
+            Constraint::Min(10),    // Notes
         ],
     )
     .header(headers)
@@ -70,3 +103,19 @@ pub fn draw(f: &mut Frame, area: Rect, app: &TuiApp) {
 
     f.render_widget(table, area);
 }
+
+/// CPU and memory cells of a STDIO server, `-` for other transports and
+/// servers without running processes.
+pub fn format_resources(resources: Option<&crate::types::ResourceUsage>) -> (String, String) {
+    let Some(resources) = resources.filter(|resources| resources.processes > 0) else {
+        return ("-".to_string(), "-".to_string());
+    };
+    let cpu = resources
+        .cpu_percent
+        .map_or_else(|| "-".to_string(), |percent| format!("{:.0}%", percent));
+    let memory = match resources.memory_bytes {
+        bytes if bytes >= 1 << 30 => format!("{:.1} GB", bytes as f64 / (1u64 << 30) as f64),
+        bytes => format!("{:.0} MB", bytes as f64 / (1u64 << 20) as f64),
+    };
+    (cpu, memory)
+}
@@ -2,8 +2,8 @@
 
 use crate::config::Config;
 use crate::tui::app::{
-    CacheLayerStats, CacheStats, LogEntry, LogLevel, MetricsSnapshot, RequestEntry, ServerInfo,
-    ServerStatus, TuiApp,
+    CacheLayerStats, CacheStats, LogEntry, LogLevel, MetricsSnapshot, RequestEntry, ServerAction,
+    ServerInfo, ServerStatus, TuiApp,
 };
 use crate::tui::tabs::TabId;
 use chrono::Utc;
@@ -175,25 +175,34 @@ mod tui_tests {
         let server_up = ServerInfo {
             id: "s1".to_string(),
             name: "Server 1".to_string(),
+            transport: "HTTP".to_string(),
+            enabled: true,
             status: ServerStatus::Up,
             health_percentage: 100,
             requests_per_second: 50,
+            resources: None,
         };
 
         let server_degraded = ServerInfo {
             id: "s2".to_string(),
             name: "Server 2".to_string(),
+            transport: "HTTP".to_string(),
+            enabled: true,
             status: ServerStatus::Degraded,
             health_percentage: 75,
             requests_per_second: 30,
+            resources: None,
         };
 
         let server_down = ServerInfo {
             id: "s3".to_string(),
             name: "Server 3".to_string(),
+            transport: "HTTP".to_string(),
+            enabled: true,
             status: ServerStatus::Down,
             health_percentage: 0,
             requests_per_second: 0,
+            resources: None,
         };
 
         assert_eq!(server_up.status, ServerStatus::Up);
@@ -266,15 +275,17 @@ mod tui_tests {
         let req = RequestEntry {
             timestamp: Utc::now(),
             method: "tools/list".to_string(),
+            tool: None,
             server_id: "server1".to_string(),
             latency_ms: 45.3,
-            status_code: 200,
+            status: "success".to_string(),
+            client: None,
         };
 
         assert_eq!(req.method, "tools/list");
         assert_eq!(req.server_id, "server1");
         assert!((req.latency_ms - 45.3).abs() < 0.01);
-        assert_eq!(req.status_code, 200);
+        assert_eq!(req.status, "success");
     }
 
     #[test]
@@ -287,6 +298,29 @@ mod tui_tests {
         assert_eq!(snapshot.cache_hit_rate, 0.0);
     }
 
+    #[test]
+    fn test_tokens_saved_per_server() {
+        let snapshot = MetricsSnapshot {
+            tokens_saved: [("github".to_string(), 1_500), ("proxy".to_string(), 250)].into(),
+            ..Default::default()
+        };
+
+        assert_eq!(snapshot.total_tokens_saved(), 1_750);
+        assert_eq!(crate::tui::tabs::overview::format_tokens(950), "950");
+        assert_eq!(crate::tui::tabs::overview::format_tokens(12_345), "12.3K");
+        assert_eq!(crate::tui::tabs::overview::format_tokens(4_100_000), "4.1M");
+    }
+
+    #[test]
+    fn test_api_cost_total() {
+        let snapshot = MetricsSnapshot {
+            api_cost: [("github".to_string(), 1.5), ("images".to_string(), 0.25)].into(),
+            ..Default::default()
+        };
+
+        assert!((snapshot.total_api_cost() - 1.75).abs() < 1e-9);
+    }
+
     #[test]
     fn test_format_uptime() {
         assert_eq!(format_uptime(0), "0s");
@@ -369,4 +403,251 @@ mod tui_tests {
         // In production, rate limiting would drop logs if >100/s
         // This is a mock test showing buffer constraint
     }
+
+    #[test]
+    fn test_onboarding_shown_without_servers() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let mut app = TuiApp::new(create_test_config()).with_onboarding("only1mcp.yaml".into());
+        assert!(app.onboarding.is_some());
+
+        // Esc skips straight to the dashboard
+        app.on_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(app.onboarding.is_none());
+        assert!(!app.should_quit);
+
+        let mut config = Config::default();
+        config.servers.push(
+            serde_yaml::from_str(
+                "{id: a, name: A, transport: {type: http, url: \"http://localhost:9000\"}}",
+            )
+            .unwrap(),
+        );
+        let app = TuiApp::new(Arc::new(config)).with_onboarding("only1mcp.yaml".into());
+        assert!(app.onboarding.is_none());
+    }
+
+    #[test]
+    fn test_onboarding_wizard_adds_server() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("only1mcp.yaml");
+        std::fs::write(&path, "servers: []\n").unwrap();
+
+        let mut app = TuiApp::new(create_test_config()).with_onboarding(path.clone());
+        let press = |app: &mut TuiApp, code| app.on_key(KeyEvent::new(code, KeyModifiers::NONE));
+        let type_text = |app: &mut TuiApp, text: &str| {
+            for c in text.chars() {
+                app.on_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+            }
+        };
+
+        // Open the wizard (third menu entry)
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+
+        // ID, name, transport (stdio), command, arguments
+        type_text(&mut app, "memory");
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Tab);
+        type_text(&mut app, "npx");
+        press(&mut app, KeyCode::Tab);
+        type_text(&mut app, "-y @modelcontextprotocol/server-memory");
+        press(&mut app, KeyCode::Enter);
+
+        assert!(app.onboarding.is_none(), "wizard should finish onboarding");
+        assert_eq!(
+            app.log_buffer.last().unwrap().message,
+            "Added server 'memory'"
+        );
+
+        let config = Config::from_file(&path).unwrap();
+        assert_eq!(config.servers[0].id, "memory");
+        assert_eq!(config.servers[0].name, "memory");
+        match &config.servers[0].transport {
+            crate::config::TransportConfig::Stdio { command, args, .. } => {
+                assert_eq!(command, "npx");
+                assert_eq!(args, &["-y", "@modelcontextprotocol/server-memory"]);
+            },
+            other => panic!("unexpected transport {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_server_metadata_from_config() {
+        let config: Config = serde_yaml::from_str(
+            r#"
+servers:
+  - id: fs
+    name: Files
+    transport: {type: stdio, command: mcp-fs}
+    tags: [local, files]
+    notes: Owned by the platform team
+"#,
+        )
+        .unwrap();
+        let app = TuiApp::new(Arc::new(config));
+
+        let metadata = app.server_metadata("fs").unwrap();
+        assert_eq!(metadata.tags, vec!["local", "files"]);
+        assert_eq!(
+            metadata.notes.as_deref(),
+            Some("Owned by the platform team")
+        );
+        assert!(app.server_metadata("missing").is_none());
+    }
+
+    fn server(id: &str, transport: &str, enabled: bool) -> ServerInfo {
+        ServerInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            transport: transport.to_string(),
+            enabled,
+            status: ServerStatus::Up,
+            health_percentage: 100,
+            requests_per_second: 0,
+            resources: None,
+        }
+    }
+
+    #[test]
+    fn test_server_actions_need_confirmation() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let mut app = TuiApp::new(create_test_config());
+        app.active_tab = TabId::Servers;
+        app.update_servers(vec![
+            server("api", "HTTP", true),
+            server("fs", "STDIO", false),
+        ]);
+        let press = |app: &mut TuiApp, code| app.on_key(KeyEvent::new(code, KeyModifiers::NONE));
+
+        // Toggling asks first and is dropped on cancel
+        press(&mut app, KeyCode::Char('e'));
+        assert_eq!(app.confirm, Some(ServerAction::Disable("api".into())));
+        press(&mut app, KeyCode::Esc);
+        assert!(app.confirm.is_none());
+        assert!(app.pending_actions.is_empty());
+
+        // Other keys leave the prompt open
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Char('e'));
+        press(&mut app, KeyCode::Char('x'));
+        assert_eq!(app.confirm, Some(ServerAction::Enable("fs".into())));
+        press(&mut app, KeyCode::Char('y'));
+
+        press(&mut app, KeyCode::Char('r'));
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Char('c'));
+        press(&mut app, KeyCode::Char('y'));
+        assert_eq!(
+            app.pending_actions,
+            vec![
+                ServerAction::Enable("fs".into()),
+                ServerAction::Restart("fs".into()),
+                ServerAction::ClearCache,
+            ]
+        );
+
+        // Only STDIO servers can be restarted
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Char('r'));
+        assert!(app.confirm.is_none());
+        assert!(app.toast.as_ref().unwrap().is_error);
+    }
+
+    #[test]
+    fn test_action_results_shown_as_toasts() {
+        let mut app = TuiApp::new(create_test_config());
+        app.update_servers(vec![
+            server("api", "HTTP", true),
+            server("fs", "STDIO", true),
+        ]);
+        app.selected_server = 1;
+
+        // The selection stays on a listed server
+        app.update_servers(vec![server("api", "HTTP", true)]);
+        assert_eq!(app.selected_server().unwrap().id, "api");
+
+        app.on_action_result(Ok(crate::types::ActionResult {
+            message: "Disabled api".into(),
+        }));
+        let toast = app.toast.clone().unwrap();
+        assert_eq!(toast.message, "Disabled api");
+        assert!(!toast.is_error);
+
+        app.on_action_result(Err(crate::error::Error::Transport(
+            "Server 'x' not found".into(),
+        )));
+        assert!(app.toast.as_ref().unwrap().is_error);
+
+        // Toasts expire
+        app.toast.as_mut().unwrap().shown_at -= std::time::Duration::from_secs(5);
+        app.on_tick();
+        assert!(app.toast.is_none());
+    }
+
+    #[test]
+    fn test_server_info_from_admin_status() {
+        let status = crate::types::ServerStatus {
+            id: "fs".into(),
+            name: "Files".into(),
+            enabled: true,
+            transport: "STDIO".into(),
+            tool_count: 3,
+            health: Some("Degraded".into()),
+            catalog_refreshed_at: None,
+            startup: None,
+            restart: None,
+            resources: None,
+            tags: vec![],
+            notes: None,
+        };
+        let info = ServerInfo::from(&status);
+        assert_eq!(info.status, ServerStatus::Degraded);
+        assert_eq!(info.transport, "STDIO");
+        assert_eq!(
+            crate::tui::tabs::servers::format_resources(info.resources.as_ref()),
+            ("-".to_string(), "-".to_string())
+        );
+
+        let sampled = crate::types::ServerStatus {
+            resources: Some(crate::types::ResourceUsage {
+                processes: 2,
+                memory_bytes: 300 << 20,
+                cpu_percent: Some(12.4),
+                limit_kills: 0,
+            }),
+            ..status.clone()
+        };
+        assert_eq!(
+            crate::tui::tabs::servers::format_resources(
+                ServerInfo::from(&sampled).resources.as_ref()
+            ),
+            ("12%".to_string(), "300 MB".to_string())
+        );
+
+        let crash_looping = crate::types::ServerStatus {
+            restart: Some(crate::types::RestartStatus {
+                policy: "on_failure".into(),
+                restarts: 5,
+                crash_loop: true,
+                last_exit: Some("exit status: 1".into()),
+            }),
+            ..status.clone()
+        };
+        let info = ServerInfo::from(&crash_looping);
+        assert_eq!(info.status, ServerStatus::CrashLoop);
+        assert_eq!(info.health_percentage, 0);
+
+        let disabled = crate::types::ServerStatus {
+            enabled: false,
+            health: Some("Healthy".into()),
+            ..status
+        };
+        assert_eq!(ServerInfo::from(&disabled).status, ServerStatus::Down);
+    }
 }
@@ -22,7 +22,12 @@ pub fn draw(f: &mut Frame, app: &TuiApp) {
     // Tab bar
     draw_tabs(f, chunks[0], app);
 
-    // Content area (tab-specific)
+    // Content area (onboarding on first run, otherwise tab-specific)
+    if let Some(onboarding) = &app.onboarding {
+        super::onboarding::draw(f, chunks[1], onboarding);
+        draw_status_bar(f, chunks[2], app);
+        return;
+    }
     match app.active_tab {
         TabId::Overview => super::tabs::overview::draw(f, chunks[1], app),
         TabId::Servers => super::tabs::servers::draw(f, chunks[1], app),
@@ -47,7 +52,12 @@ fn draw_tabs(f: &mut Frame, area: Rect, app: &TuiApp) {
     };
 
     let tabs = Tabs::new(titles)
-        .block(Block::default().borders(Borders::ALL).title("Only1MCP TUI"))
+        .block(
+            Block::default().borders(Borders::ALL).title(match &app.daemon_url {
+                Some(url) => format!("Only1MCP TUI — {}", url),
+                None => "Only1MCP TUI".to_string(),
+            }),
+        )
         .select(selected)
         .style(Style::default().fg(Color::White))
         .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
@@ -55,17 +65,62 @@ fn draw_tabs(f: &mut Frame, area: Rect, app: &TuiApp) {
     f.render_widget(tabs, area);
 }
 
-fn draw_status_bar(f: &mut Frame, area: Rect, _app: &TuiApp) {
-    let status = Paragraph::new(Line::from(vec![
-        Span::raw("Press "),
-        Span::styled("q", Style::default().fg(Color::Yellow)),
-        Span::raw(" to quit | "),
-        Span::styled("Tab", Style::default().fg(Color::Yellow)),
-        Span::raw(" to switch tabs | "),
-        Span::styled("↑↓", Style::default().fg(Color::Yellow)),
-        Span::raw(" to scroll"),
-    ]))
-    .style(Style::default().bg(Color::DarkGray));
+fn draw_status_bar(f: &mut Frame, area: Rect, app: &TuiApp) {
+    let hints = if app.onboarding.is_some() {
+        vec![
+            Span::styled("↑↓", Style::default().fg(Color::Yellow)),
+            Span::raw(" to move | "),
+            Span::styled("Enter", Style::default().fg(Color::Yellow)),
+            Span::raw(" to confirm | "),
+            Span::styled("Space", Style::default().fg(Color::Yellow)),
+            Span::raw(" to toggle | "),
+            Span::styled("Esc", Style::default().fg(Color::Yellow)),
+            Span::raw(" to go back | "),
+            Span::styled("Ctrl+C", Style::default().fg(Color::Yellow)),
+            Span::raw(" to quit"),
+        ]
+    } else if let Some(action) = &app.confirm {
+        vec![
+            Span::styled(action.prompt(), Style::default().fg(Color::White)),
+            Span::raw(" "),
+            Span::styled("y", Style::default().fg(Color::Yellow)),
+            Span::raw(" to confirm | "),
+            Span::styled("n", Style::default().fg(Color::Yellow)),
+            Span::raw(" to cancel"),
+        ]
+    } else if let Some(toast) = &app.toast {
+        let color = if toast.is_error { Color::Red } else { Color::Green };
+        vec![Span::styled(
+            toast.message.clone(),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        )]
+    } else if app.active_tab == TabId::Servers {
+        vec![
+            Span::styled("↑↓", Style::default().fg(Color::Yellow)),
+            Span::raw(" to select | "),
+            Span::styled("e", Style::default().fg(Color::Yellow)),
+            Span::raw(" enable/disable | "),
+            Span::styled("h", Style::default().fg(Color::Yellow)),
+            Span::raw(" health check | "),
+            Span::styled("r", Style::default().fg(Color::Yellow)),
+            Span::raw(" restart | "),
+            Span::styled("c", Style::default().fg(Color::Yellow)),
+            Span::raw(" clear cache | "),
+            Span::styled("q", Style::default().fg(Color::Yellow)),
+            Span::raw(" to quit"),
+        ]
+    } else {
+        vec![
+            Span::raw("Press "),
+            Span::styled("q", Style::default().fg(Color::Yellow)),
+            Span::raw(" to quit | "),
+            Span::styled("Tab", Style::default().fg(Color::Yellow)),
+            Span::raw(" to switch tabs | "),
+            Span::styled("↑↓", Style::default().fg(Color::Yellow)),
+            Span::raw(" to scroll"),
+        ]
+    };
+    let status = Paragraph::new(Line::from(hints)).style(Style::default().bg(Color::DarkGray));
 
     f.render_widget(status, area);
 }
@@ -107,7 +107,7 @@ impl McpResponse {
 }
 
 /// MCP error object
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct McpError {
     pub code: i32,
     pub message: String,
@@ -153,6 +153,13 @@ pub struct Tool {
     pub description: Option<String>,
     #[serde(rename = "inputSchema")]
     pub input_schema: Value,
+    /// Schema of the tool's `structuredContent`, if it declares one
+    #[serde(
+        rename = "outputSchema",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub output_schema: Option<Value>,
 }
 
 /// MCP Resource definition
@@ -201,6 +208,81 @@ pub struct ServerStatus {
     pub tool_count: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub health: Option<String>,
+    /// When the server's tools, resources or prompts were last fetched
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub catalog_refreshed_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Startup policy and progress of a STDIO server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub startup: Option<StartupStatus>,
+    /// Restart policy and restarts of a STDIO server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restart: Option<RestartStatus>,
+    /// CPU and memory used by a STDIO server's processes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resources: Option<ResourceUsage>,
+    /// Operator tags from the server's config
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Operator notes from the server's config
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+/// Startup policy of a STDIO server and how far its processes got
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StartupStatus {
+    /// `eager`, `lazy` or `on_demand_with_preinit`
+    pub policy: String,
+    pub state: StartupState,
+    /// Why starting the processes failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Progress of a STDIO server's startup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupState {
+    /// No process started yet; the first request starts one
+    Deferred,
+    /// Processes are being started and initialized
+    Starting,
+    /// At least one process is initialized
+    Ready,
+    /// Starting the processes failed; requests retry on demand
+    Failed,
+}
+
+/// Restart policy of a STDIO server and how often its processes were
+/// restarted after exiting
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestartStatus {
+    /// `never`, `on_failure` or `always`
+    pub policy: String,
+    /// Restarts since the proxy started
+    pub restarts: u64,
+    /// Exceeded its restart limit; left stopped until restarted
+    #[serde(default)]
+    pub crash_loop: bool,
+    /// How the last process exited
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_exit: Option<String>,
+}
+
+/// CPU and memory used by the running processes of a STDIO server, as of
+/// the last sample
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourceUsage {
+    /// Processes sampled
+    pub processes: usize,
+    /// Resident memory of all processes
+    pub memory_bytes: u64,
+    /// Percent of one core used by all processes, once measured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_percent: Option<f64>,
+    /// Processes killed for exceeding the server's limits
+    #[serde(default)]
+    pub limit_kills: u64,
 }
 
 /// Tool information with server attribution
@@ -210,6 +292,12 @@ pub struct ToolInfo {
     pub server: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Effective tags: the server's tags followed by the tool's own
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Operator notes configured for the tool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
 }
 
 /// System health status
@@ -222,6 +310,68 @@ pub struct HealthStatus {
     pub uptime_seconds: u64,
 }
 
+/// Per-backend health for `/health/detail`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthDetail {
+    pub status: String, // "healthy", "degraded", "unhealthy"
+    /// Whether `/health/ready` would succeed
+    pub ready: bool,
+    pub version: String,
+    pub uptime_seconds: u64,
+    pub backends: Vec<BackendHealthDetail>,
+}
+
+/// Health of one backend as seen by its active health checker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackendHealthDetail {
+    pub id: String,
+    pub enabled: bool,
+    /// `healthy`, `degraded`, `unhealthy`, or `unknown` until checked
+    pub health: String,
+    /// `open` while the backend receives no traffic, otherwise `closed`
+    pub circuit: String,
+    /// When the last active check completed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_check: Option<chrono::DateTime<chrono::Utc>>,
+    pub consecutive_failures: u32,
+    pub consecutive_successes: u32,
+    /// Reason given by the last failed check
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// Restart policy and restarts of a STDIO server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restart: Option<RestartStatus>,
+}
+
+/// One request answered by the proxy, as kept in the request history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestRecord {
+    /// Increases by one per recorded request
+    pub id: u64,
+    /// When the request arrived
+    pub at: chrono::DateTime<chrono::Utc>,
+    pub method: String,
+    /// Called tool of a tools/call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    /// Backends the request was sent to; empty when the proxy answered it
+    /// itself (e.g. from cache)
+    #[serde(default)]
+    pub servers: Vec<String>,
+    /// `success` or `error`
+    pub status: String,
+    /// Message of a JSON-RPC error reply
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub latency_ms: f64,
+    /// Client id header value, or the authenticated principal
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client: Option<String>,
+    /// `Mcp-Session-Id` of the client's session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+}
+
 /// System runtime information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemInfo {
@@ -229,4 +379,124 @@ pub struct SystemInfo {
     pub config_path: String,
     pub pid: u32,
     pub uptime_seconds: u64,
+    /// Set when the configuration follows a git repository (`gitops`)
+    #[serde(default)]
+    pub gitops: Option<GitOpsStatus>,
+}
+
+/// Where GitOps sync stands
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitOpsStatus {
+    pub repository: String,
+    pub branch: String,
+    pub path: String,
+    /// Commit whose configuration is applied
+    pub commit: Option<String>,
+    pub applied_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// When the branch was last fetched
+    pub checked_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Why the last sync failed, until a commit is applied
+    pub error: Option<String>,
+}
+
+/// Outcome of an Admin API management action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionResult {
+    pub message: String,
+}
+
+/// An applied version of the configuration file
+/// (`GET /api/v1/admin/config/history`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigVersion {
+    /// As accepted by `POST /api/v1/admin/config/rollback`
+    pub timestamp: String,
+    pub path: String,
+    /// The version applied last
+    pub current: bool,
+}
+
+/// Result of checking a candidate configuration without applying it
+/// (`POST /api/v1/admin/config/validate`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigValidation {
+    /// No errors were found and every probed server is reachable
+    pub valid: bool,
+    pub diagnostics: Vec<ConfigDiagnostic>,
+    /// Added and changed servers, probed like their health checks
+    pub reachability: Vec<ServerReachability>,
+    /// What applying the candidate would change; absent when it does not load
+    pub diff: Option<ConfigChanges>,
+}
+
+/// A problem found in a candidate configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigDiagnostic {
+    /// `error` or `warning`
+    pub severity: String,
+    /// Location in the config, e.g. `servers[0].transport.url`
+    pub path: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+/// Whether a server of a candidate configuration answered a probe
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerReachability {
+    pub server_id: String,
+    pub reachable: bool,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// Differences between the running and a candidate configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigChanges {
+    pub servers_added: Vec<String>,
+    pub servers_removed: Vec<String>,
+    pub servers_changed: Vec<String>,
+    /// Servers whose tags or notes alone differ
+    pub servers_annotated: Vec<String>,
+    /// Other sections that would change without a restart
+    pub settings_changed: Vec<String>,
+    /// Sections that would only change after a restart
+    pub restart_required: Vec<String>,
+    /// One-line description, as logged on reload
+    pub summary: String,
+}
+
+/// This instance's view of the cluster (`GET /api/v1/admin/cluster`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterStatus {
+    pub node_id: String,
+    pub peers: Vec<ClusterPeer>,
+    /// Sticky session pins known here, including those learnt from peers
+    pub sessions: usize,
+}
+
+/// A configured peer or an instance that contacted this one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterPeer {
+    /// Unknown until the peer has answered
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<String>,
+    /// Configured URL; absent for instances that only contacted this one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Heard from within `cluster.peer_timeout_ms`
+    pub up: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seen_ms: Option<u64>,
+    /// Why the last exchange with it failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Latest captured output of a STDIO backend, oldest line first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerLogs {
+    pub server_id: String,
+    pub lines: Vec<String>,
 }
@@ -0,0 +1,226 @@
+//! Integration tests for how the proxy reaches HTTP backends: OAuth, request signing and outbound proxies
+
+mod common;
+
+use common::*;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_http_backend_uses_oauth_client_credentials() {
+    use only1mcp::config::{BackendOAuthConfig, TransportConfig};
+    use wiremock::{
+        matchers::{body_string_contains, header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    // Given: A backend that only accepts tokens from its authorization server
+    let backend = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .and(body_string_contains("grant_type=client_credentials"))
+        .and(body_string_contains("scope=mcp%3Aread"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "access_token": "tok-1", "token_type": "Bearer", "expires_in": 3600
+        })))
+        .expect(1)
+        .mount(&backend)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mcp"))
+        .and(header("Authorization", "Bearer tok-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0", "id": 1, "result": {"tools": [sample_tool("echo", "Echo")]}
+        })))
+        .mount(&backend)
+        .await;
+    Mock::given(path("/health"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&backend)
+        .await;
+
+    std::env::set_var("ONLY1MCP_TEST_OAUTH_SECRET", "s3cret");
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.servers[0].transport = TransportConfig::Http {
+        url: format!("{}/mcp", backend.uri()),
+        headers: Default::default(),
+        oauth: Some(BackendOAuthConfig {
+            token_url: format!("{}/oauth/token", backend.uri()),
+            client_id: "only1mcp".to_string(),
+            client_secret: None,
+            client_secret_env: Some("ONLY1MCP_TEST_OAUTH_SECRET".to_string()),
+            scopes: vec!["mcp:read".to_string()],
+        }),
+        tls: None,
+        signing: None,
+        proxy: None,
+    };
+    let server = start_test_server(config).await;
+
+    // When: Tools are listed twice
+    for _ in 0..2 {
+        let response = server.rpc("tools/list", json!({})).await;
+
+        // Then: The backend is reached with the bearer token
+        assert_eq!(
+            response["result"]["tools"][0]["name"], "echo",
+            "{}",
+            response
+        );
+    }
+
+    // And: The cached token is reused (verified by `expect(1)` on drop)
+}
+
+/// Matches requests whose `X-Hub-Signature-256` header is the HMAC-SHA256
+/// of their body under `secret`.
+struct SignedWith(&'static str);
+
+impl wiremock::Match for SignedWith {
+    fn matches(&self, request: &wiremock::Request) -> bool {
+        let key = ring::hmac::Key::new(ring::hmac::HMAC_SHA256, self.0.as_bytes());
+        let digest = ring::hmac::sign(&key, &request.body);
+        let expected: String = digest.as_ref().iter().map(|b| format!("{:02x}", b)).collect();
+        request.headers.get("X-Hub-Signature-256").and_then(|value| value.to_str().ok())
+            == Some(format!("sha256={}", expected).as_str())
+    }
+}
+
+#[tokio::test]
+async fn test_requests_to_backend_signed() {
+    use only1mcp::config::TransportConfig;
+    use wiremock::{
+        matchers::{body_partial_json, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    // Given: A backend listing its tools only for requests signed with its
+    // secret
+    let backend = MockServer::start().await;
+    Mock::given(path("/health"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&backend)
+        .await;
+    Mock::given(body_partial_json(json!({"method": "tools/list"})))
+        .and(SignedWith("hook-secret"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0", "id": 1, "result": {"tools": [sample_tool("deploy", "Deploy")]}
+        })))
+        .mount(&backend)
+        .await;
+
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.servers[0].transport = TransportConfig::Http {
+        url: backend.uri(),
+        headers: Default::default(),
+        oauth: None,
+        tls: None,
+        signing: Some(
+            serde_yaml::from_str(
+                "{secret: hook-secret, header: X-Hub-Signature-256, prefix: sha256=}",
+            )
+            .unwrap(),
+        ),
+        proxy: None,
+    };
+    let server = start_test_server(config).await;
+
+    // When: Tools are listed
+    let body = server.rpc("tools/list", json!({})).await;
+
+    // Then: The backend accepted the signature
+    assert_eq!(body["result"]["tools"][0]["name"], "deploy", "{}", body);
+}
+
+/// Matches requests the forward proxy receives for `backend.invalid`,
+/// which only it can reach.
+fn for_backend(request: &wiremock::Request) -> bool {
+    request.url.host_str() == Some("backend.invalid")
+}
+
+#[tokio::test]
+async fn test_backend_reached_through_outbound_proxy() {
+    use wiremock::{
+        matchers::{body_partial_json, header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    // Given: A forward proxy answering for a backend that only it can reach
+    let proxy = MockServer::start().await;
+    Mock::given(path("/health"))
+        .and(for_backend)
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&proxy)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mcp"))
+        .and(for_backend)
+        .and(header("proxy-authorization", "Basic Y29ycDpwcjB4eQ=="))
+        .and(body_partial_json(json!({"method": "tools/list"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0", "id": 1, "result": {"tools": [sample_tool("lookup", "Lookup")]}
+        })))
+        .expect(1)
+        .mount(&proxy)
+        .await;
+
+    let mut config = test_config_with_backends(0, vec!["http://backend.invalid/mcp".into()]);
+    config.proxy.outbound_proxy = Some(
+        serde_yaml::from_str(&format!(
+            "{{http_proxy: '{}', username: corp, password: pr0xy}}",
+            proxy.uri()
+        ))
+        .unwrap(),
+    );
+    let server = start_test_server(config).await;
+
+    // When: Tools are listed
+    let body = server.rpc("tools/list", json!({})).await;
+
+    // Then: The request went through the proxy with its credentials
+    assert_eq!(body["result"]["tools"][0]["name"], "lookup", "{}", body);
+}
+
+#[tokio::test]
+async fn test_health_checks_reach_backend_through_outbound_proxy() {
+    use std::time::Duration;
+    use wiremock::{matchers::path, Mock, MockServer, ResponseTemplate};
+
+    // Given: A health-checked backend that only the forward proxy can reach
+    let proxy = MockServer::start().await;
+    Mock::given(path("/health"))
+        .and(for_backend)
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1..)
+        .mount(&proxy)
+        .await;
+
+    let mut config = test_config_with_backends(0, vec!["http://backend.invalid".into()]);
+    let health_check = &mut config.servers[0].health_check;
+    health_check.enabled = true;
+    health_check.interval_seconds = 1;
+    health_check.healthy_threshold = 1;
+    health_check.unhealthy_threshold = 1;
+    config.proxy.outbound_proxy =
+        Some(serde_yaml::from_str(&format!("{{http_proxy: '{}'}}", proxy.uri())).unwrap());
+    let server = start_test_server(config).await;
+
+    // When: The backend has been checked
+    let mut detail = serde_json::Value::Null;
+    for _ in 0..30 {
+        detail = test_client()
+            .get(format!("{}/health/detail", server.url()))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        if detail["backends"][0]["health"] != "unknown" {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    // Then: The check went through the proxy and found it healthy
+    assert_eq!(detail["backends"][0]["health"], "healthy", "{}", detail);
+}
@@ -9,15 +9,17 @@ use reqwest::Client;
 use serde_json::{json, Value};
 use std::net::SocketAddr;
 use std::time::Duration;
-use wiremock::matchers::{body_json, method};
+use wiremock::matchers::{body_partial_json, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 /// Create a test configuration with sensible defaults
+#[allow(dead_code)]
 pub fn test_config() -> Config {
     test_config_with_port(0)
 }
 
 /// Create a test configuration with a specific port
+#[allow(dead_code)]
 pub fn test_config_with_port(port: u16) -> Config {
     Config {
         server: ServerConfig {
@@ -26,6 +28,10 @@ pub fn test_config_with_port(port: u16) -> Config {
             worker_threads: 2,
             max_connections: 100,
             tls: Default::default(),
+            drain_timeout_seconds: 5,
+            upgrade_timeout_seconds: 30,
+            listeners: Vec::new(),
+            listen_socket: None,
         },
         servers: vec![],
         proxy: ProxyConfig::default(),
@@ -33,6 +39,10 @@ pub fn test_config_with_port(port: u16) -> Config {
         auth: Default::default(),
         observability: Default::default(),
         tui: Default::default(),
+        backups: Default::default(),
+        cluster: Default::default(),
+        discovery: Default::default(),
+        gitops: Default::default(),
     }
 }
 
@@ -49,6 +59,10 @@ pub fn test_config_with_backends(port: u16, backend_urls: Vec<String>) -> Config
             transport: TransportConfig::Http {
                 url: url.clone(),
                 headers: Default::default(),
+                oauth: None,
+                tls: None,
+                signing: None,
+                proxy: None,
             },
             health_check: HealthCheckConfig {
                 enabled: false, // Disable health checks for tests
@@ -64,6 +78,19 @@ pub fn test_config_with_backends(port: u16, backend_urls: Vec<String>) -> Config
                 weight: 1,
             },
             weight: 1,
+            timeout_ms: 30000,
+            retry: Default::default(),
+            tags: Vec::new(),
+            notes: None,
+            tool_metadata: Default::default(),
+            cost: None,
+            startup: Default::default(),
+            restart: Default::default(),
+            resources: Default::default(),
+            sandbox: Default::default(),
+            env_policy: Default::default(),
+            mirror: None,
+            transforms: Default::default(),
         });
     }
 
@@ -74,6 +101,10 @@ pub fn test_config_with_backends(port: u16, backend_urls: Vec<String>) -> Config
             worker_threads: 2,
             max_connections: 100,
             tls: Default::default(),
+            drain_timeout_seconds: 5,
+            upgrade_timeout_seconds: 30,
+            listeners: Vec::new(),
+            listen_socket: None,
         },
         servers,
         proxy: ProxyConfig::default(),
@@ -81,6 +112,10 @@ pub fn test_config_with_backends(port: u16, backend_urls: Vec<String>) -> Config
         auth: Default::default(),
         observability: Default::default(),
         tui: Default::default(),
+        backups: Default::default(),
+        cluster: Default::default(),
+        discovery: Default::default(),
+        gitops: Default::default(),
     }
 }
 
@@ -94,6 +129,10 @@ pub fn mock_server_config(id: &str, url: &str) -> McpServerConfig {
         transport: TransportConfig::Http {
             url: url.to_string(),
             headers: Default::default(),
+            oauth: None,
+            tls: None,
+            signing: None,
+            proxy: None,
         },
         health_check: HealthCheckConfig {
             enabled: false, // Disable for tests
@@ -105,11 +144,34 @@ pub fn mock_server_config(id: &str, url: &str) -> McpServerConfig {
         },
         routing: RoutingConfig::default(),
         weight: 1,
+        timeout_ms: 30000,
+        retry: Default::default(),
+        tags: Vec::new(),
+        notes: None,
+        tool_metadata: Default::default(),
+        cost: None,
+        startup: Default::default(),
+        restart: Default::default(),
+        resources: Default::default(),
+        sandbox: Default::default(),
+        env_policy: Default::default(),
+        mirror: None,
+        transforms: Default::default(),
     }
 }
 
 /// Start a test proxy server with the given config
-pub async fn start_test_server(mut config: Config) -> TestServer {
+pub async fn start_test_server(config: Config) -> TestServer {
+    start_test_server_with(config, |server| server).await
+}
+
+/// Start a test proxy server with the given config, adjusting the server
+/// (e.g. `with_dry_run`) before it runs
+#[allow(dead_code)]
+pub async fn start_test_server_with(
+    mut config: Config,
+    customize: impl FnOnce(ProxyServer) -> ProxyServer,
+) -> TestServer {
     // Use a fixed test port if not specified
     if config.server.port == 0 {
         config.server.port = find_free_port().await;
@@ -122,6 +184,7 @@ pub async fn start_test_server(mut config: Config) -> TestServer {
     let server = ProxyServer::new(config, std::path::PathBuf::from("test-config.yaml"))
         .await
         .expect("Failed to create server");
+    let server = customize(server);
 
     // Spawn server in background
     let handle = tokio::spawn(async move {
@@ -158,6 +221,21 @@ impl TestServer {
     pub fn url(&self) -> String {
         format!("http://{}", self.addr)
     }
+
+    /// Send a JSON-RPC request to the MCP endpoint and return the response
+    /// body
+    #[allow(dead_code)]
+    pub async fn rpc(&self, method: &str, params: Value) -> Value {
+        test_client()
+            .post(format!("{}/mcp", self.url()))
+            .json(&json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params}))
+            .send()
+            .await
+            .expect("Failed to send request")
+            .json()
+            .await
+            .expect("Failed to parse JSON")
+    }
 }
 
 impl Drop for TestServer {
@@ -202,14 +280,25 @@ pub fn assert_jsonrpc_error(response: &Value, expected_code: i32) {
     );
 }
 
+/// Start a mock MCP backend that passes health checks and lists `tools`
+#[allow(dead_code)]
+pub async fn mock_backend(tools: Vec<Value>) -> MockServer {
+    let backend = MockServer::start().await;
+    Mock::given(path("/health"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&backend)
+        .await;
+    mount_tools_list(&backend, tools).await;
+    backend
+}
+
 /// Mount a mock tools/list endpoint
 #[allow(dead_code)]
 pub async fn mount_tools_list(mock: &MockServer, tools: Vec<Value>) {
-    Mock::given(method("POST"))
-        .and(body_json(json!({"method": "tools/list"})))
+    Mock::given(body_partial_json(json!({"method": "tools/list"})))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!({
             "jsonrpc": "2.0",
-            "id": null,
+            "id": 1,
             "result": {
                 "tools": tools
             }
@@ -221,18 +310,33 @@ pub async fn mount_tools_list(mock: &MockServer, tools: Vec<Value>) {
 /// Mount a mock tools/call endpoint
 #[allow(dead_code)]
 pub async fn mount_tools_call(mock: &MockServer, tool_name: &str, response: Value) {
-    Mock::given(method("POST"))
-        .and(body_json(json!({
-            "method": "tools/call",
-            "params": {"name": tool_name}
-        })))
-        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "jsonrpc": "2.0",
-            "id": null,
-            "result": response
-        })))
-        .mount(mock)
-        .await;
+    Mock::given(body_partial_json(json!({
+        "method": "tools/call",
+        "params": {"name": tool_name}
+    })))
+    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "result": response
+    })))
+    .mount(mock)
+    .await;
+}
+
+/// Mount a mock tools/call endpoint answering with a JSON-RPC error
+#[allow(dead_code)]
+pub async fn mount_tools_error(mock: &MockServer, tool_name: &str, message: &str) {
+    Mock::given(body_partial_json(json!({
+        "method": "tools/call",
+        "params": {"name": tool_name}
+    })))
+    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "error": {"code": -32000, "message": message}
+    })))
+    .mount(mock)
+    .await;
 }
 
 /// Create a sample tool JSON object
@@ -0,0 +1,417 @@
+//! Integration tests for applying configuration file changes to a running proxy
+
+#[allow(dead_code)]
+mod common;
+
+use common::*;
+use only1mcp::config::Config;
+use only1mcp::metrics::CONFIG_RELOAD_TOTAL;
+use only1mcp::proxy::ProxyServer;
+use only1mcp::tui::TuiClient;
+use serde_json::json;
+use std::path::Path;
+use std::time::Duration;
+
+fn write_config(path: &Path, server_ids: &[&str], cache_ttl_seconds: u64) {
+    let servers: String = server_ids
+        .iter()
+        .map(|id| {
+            format!(
+                r#"
+  - id: {id}
+    name: {id}
+    transport:
+      type: http
+      url: "http://127.0.0.1:19100/{id}"
+    health_check:
+      enabled: false"#
+            )
+        })
+        .collect();
+
+    let yaml = format!(
+        r#"
+server:
+  host: "127.0.0.1"
+  port: 8080
+servers:{servers}
+context_optimization:
+  cache:
+    ttl_seconds: {cache_ttl_seconds}
+"#
+    );
+    std::fs::write(path, yaml).unwrap();
+}
+
+#[tokio::test]
+async fn test_config_file_changes_applied_live() {
+    // Given: A running proxy watching its config file
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("only1mcp.yaml");
+    write_config(&path, &["alpha"], 300);
+
+    let server = ProxyServer::new(Config::from_file(&path).unwrap(), path.clone()).await.unwrap();
+    let _loader = server.watch_config().unwrap();
+    let reloads_before = CONFIG_RELOAD_TOTAL.get();
+
+    // Give the watcher time to start
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // When: A backend is added and cache settings change
+    write_config(&path, &["alpha", "beta"], 60);
+
+    // Then: The running server picks both up without a restart
+    // (the reload counter is bumped after subscribers are notified)
+    let reloaded = wait_for(
+        || server.config().servers.len() == 2 && CONFIG_RELOAD_TOTAL.get() > reloads_before,
+        Duration::from_secs(5),
+    )
+    .await;
+    assert!(reloaded, "config change was not applied");
+
+    let config = server.config();
+    assert_eq!(config.servers[1].id, "beta");
+    assert_eq!(config.context_optimization.cache.ttl_seconds, 60);
+}
+
+#[tokio::test]
+async fn test_update_config_reports_diff_and_keeps_listener() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("only1mcp.yaml");
+    write_config(&path, &["alpha", "beta"], 300);
+
+    // The listener address came from CLI flags, not the file
+    let mut config = Config::from_file(&path).unwrap();
+    config.server.port = 9999;
+    let server = ProxyServer::new(config, path.clone()).await.unwrap();
+
+    write_config(&path, &["beta", "gamma"], 120);
+    let diff = server.update_config(&Config::from_file(&path).unwrap()).await.unwrap();
+
+    assert_eq!(diff.servers_added, vec!["gamma"]);
+    assert_eq!(diff.servers_removed, vec!["alpha"]);
+    assert!(diff.servers_changed.is_empty());
+    assert!(diff.cache_changed);
+    assert!(diff.restart_required.is_empty());
+    assert_eq!(server.config().server.port, 9999);
+
+    // Applying the same file again is a no-op
+    let diff = server.update_config(&Config::from_file(&path).unwrap()).await.unwrap();
+    assert!(diff.is_empty());
+}
+
+#[tokio::test]
+async fn test_admin_metadata_persisted_and_applied() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("only1mcp.yaml");
+    write_config(&path, &["alpha"], 300);
+
+    let port = {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        listener.local_addr().unwrap().port()
+    };
+    let mut config = Config::from_file(&path).unwrap();
+    config.server.port = port;
+    let server = ProxyServer::new(config, path.clone()).await.unwrap();
+    let running = server.clone();
+    let handle = tokio::spawn(async move {
+        let _ = running.run().await;
+    });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let client = test_client();
+    let admin = format!("http://127.0.0.1:{}/api/v1/admin", port);
+
+    // When: Tags and notes are set on the server and one of its tools
+    let response = client
+        .put(format!("{}/servers/alpha/metadata", admin))
+        .json(&serde_json::json!({"tags": ["prod"], "notes": "Primary backend"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let response = client
+        .put(format!("{}/servers/alpha/tools/drop_table/metadata", admin))
+        .json(&serde_json::json!({"tags": ["destructive"]}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    // Then: They are live and saved to the file
+    let config = server.config();
+    assert_eq!(config.servers[0].tags, vec!["prod"]);
+    assert_eq!(
+        config.servers[0].tool_tags("drop_table").collect::<Vec<_>>(),
+        vec!["prod", "destructive"]
+    );
+
+    let saved = Config::from_file(&path).unwrap();
+    assert_eq!(saved.servers[0].notes.as_deref(), Some("Primary backend"));
+    assert_eq!(
+        saved.servers[0].tool_metadata["drop_table"].tags,
+        vec!["destructive"]
+    );
+
+    // Unknown servers and invalid tags are rejected
+    let response = client
+        .put(format!("{}/servers/missing/metadata", admin))
+        .json(&serde_json::json!({"tags": ["prod"]}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+    let response = client
+        .put(format!("{}/servers/alpha/metadata", admin))
+        .json(&serde_json::json!({"tags": ["two words"]}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_admin_server_actions() {
+    // Given: A running proxy with two HTTP backends
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("only1mcp.yaml");
+    write_config(&path, &["alpha", "beta"], 300);
+
+    let port = {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        listener.local_addr().unwrap().port()
+    };
+    let mut config = Config::from_file(&path).unwrap();
+    config.server.port = port;
+    let server = ProxyServer::new(config, path.clone()).await.unwrap();
+    let running = server.clone();
+    let handle = tokio::spawn(async move {
+        let _ = running.run().await;
+    });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let client = TuiClient::new("127.0.0.1", port);
+
+    // When: A backend is disabled from the TUI
+    let result = client.set_server_enabled("alpha", false).await.unwrap();
+    assert_eq!(result.message, "Disabled alpha");
+
+    // Then: It is disabled live and in the file, keeping its port
+    let config = server.config();
+    assert!(!config.servers[0].enabled);
+    assert!(config.servers[1].enabled);
+    assert_eq!(config.server.port, port);
+    assert!(!Config::from_file(&path).unwrap().servers[0].enabled);
+
+    // And: Enabling it again applies the same way
+    client.set_server_enabled("alpha", true).await.unwrap();
+    assert!(server.config().servers[0].enabled);
+    assert!(Config::from_file(&path).unwrap().servers[0].enabled);
+
+    // Cache clears report how much was dropped
+    let result = client.clear_cache().await.unwrap();
+    assert_eq!(result.message, "Cleared 0 cached responses");
+
+    // Actions that do not apply are rejected with the reason
+    let err = client.restart_server("alpha").await.unwrap_err();
+    assert!(err.to_string().contains("not a STDIO server"), "{}", err);
+    let err = client.check_server("alpha").await.unwrap_err();
+    assert!(
+        err.to_string().contains("no active health check"),
+        "{}",
+        err
+    );
+    let err = client.set_server_enabled("missing", false).await.unwrap_err();
+    assert!(err.to_string().contains("not found"), "{}", err);
+
+    handle.abort();
+}
+
+fn git(repo: &Path, args: &[&str]) -> String {
+    let output = std::process::Command::new("git").arg("-C").arg(repo).args(args).output();
+    let output = output.unwrap();
+    assert!(output.status.success(), "git {:?}", args);
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+/// Write a config that follows `repo`.
+fn write_synced_config(path: &Path, server_ids: &[&str], repo: &Path) {
+    write_config(path, server_ids, 300);
+    let gitops = format!(
+        "gitops:\n  enabled: true\n  repository: '{}'\n  poll_interval_ms: 0\n",
+        repo.display()
+    );
+    std::fs::write(
+        path,
+        std::fs::read_to_string(path).unwrap() + gitops.as_str(),
+    )
+    .unwrap();
+}
+
+/// Commit a config following `repo` there, returning the commit.
+fn commit_config(repo: &Path, server_ids: &[&str]) -> String {
+    write_synced_config(&repo.join("only1mcp.yaml"), server_ids, repo);
+    git(repo, &["add", "only1mcp.yaml"]);
+    git(
+        repo,
+        &[
+            "-c",
+            "user.name=test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-qm",
+            "update",
+        ],
+    );
+    git(repo, &["rev-parse", "HEAD"])
+}
+
+#[tokio::test]
+async fn test_config_synced_from_git() {
+    // Given: A repository holding a config with two backends, and a proxy
+    // following it from a config with one
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().join("repo");
+    std::fs::create_dir(&repo).unwrap();
+    git(&repo, &["init", "--quiet", "--initial-branch", "main"]);
+    let first = commit_config(&repo, &["alpha", "beta"]);
+    let path = dir.path().join("etc").join("only1mcp.yaml");
+    std::fs::create_dir(path.parent().unwrap()).unwrap();
+    write_synced_config(&path, &["alpha"], &repo);
+
+    let port = {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        listener.local_addr().unwrap().port()
+    };
+    let mut config = Config::from_file(&path).unwrap();
+    config.server.port = port;
+    let server = ProxyServer::new(config, path.clone()).await.unwrap();
+    let running = server.clone();
+    let handle = tokio::spawn(async move {
+        let _ = running.run().await;
+    });
+    let client = test_client();
+    let admin = format!("http://127.0.0.1:{}/api/v1/admin", port);
+
+    // Then: The repository's config is applied at startup and written to
+    // the file, and the commit is reported
+    let synced = wait_for(
+        || server.config().servers.len() == 2,
+        Duration::from_secs(10),
+    )
+    .await;
+    assert!(synced, "config was not synced from git");
+    assert_eq!(Config::from_file(&path).unwrap().servers.len(), 2);
+    let system: serde_json::Value = client
+        .get(format!("{}/system", admin))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(system["gitops"]["commit"], first.as_str(), "{}", system);
+
+    // When: A new commit is pushed and a sync requested
+    let second = commit_config(&repo, &["gamma"]);
+    let response = client.post(format!("{}/gitops/sync", admin)).send().await.unwrap();
+    assert_eq!(response.status(), 202);
+
+    // Then: It is applied without polling
+    let synced = wait_for(
+        || server.config().servers.iter().map(|s| s.id.as_str()).eq(["gamma"]),
+        Duration::from_secs(10),
+    )
+    .await;
+    assert!(synced, "new commit was not applied");
+    let system: serde_json::Value = client
+        .get(format!("{}/system", admin))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(system["gitops"]["commit"], second.as_str(), "{}", system);
+    assert!(system["gitops"]["error"].is_null(), "{}", system);
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_config_validate_reports_diff_without_applying() {
+    use wiremock::{matchers::path, Mock, MockServer, ResponseTemplate};
+
+    // Given: A proxy with one backend, and a second healthy backend
+    let backend = MockServer::start().await;
+    let added = MockServer::start().await;
+    Mock::given(path("/health"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&added)
+        .await;
+    let config = test_config_with_backends(0, vec![backend.uri()]);
+    let server = start_test_server(config.clone()).await;
+    let client = test_client();
+    let validate = format!("{}/api/v1/admin/config/validate", server.url());
+    // The running listen address is kept, so any valid port will do
+    let mut config = config;
+    config.server.port = 8080;
+
+    // When: A candidate adds the healthy backend and changes the cache
+    let mut healthy = config.clone();
+    healthy.servers.push(mock_server_config("added", &added.uri()));
+    healthy.context_optimization.cache.ttl_seconds += 1;
+    let healthy = serde_yaml::to_string(&healthy).unwrap();
+    let response = client.post(&validate).body(healthy.clone()).send().await.unwrap();
+
+    // Then: It is valid and the diff names the changes
+    let status = response.status();
+    let result: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(status, 200, "{}", result);
+    assert_eq!(result["valid"], true);
+    assert_eq!(result["diff"]["servers_added"], json!(["added"]));
+    assert_eq!(
+        result["diff"]["settings_changed"],
+        json!(["context_optimization.cache"])
+    );
+    assert_eq!(result["reachability"][0]["server_id"], "added");
+    assert_eq!(result["reachability"][0]["reachable"], true);
+
+    // When: A candidate adds a backend nothing listens on
+    let mut candidate = config.clone();
+    candidate.servers.push(mock_server_config("down", "http://127.0.0.1:9"));
+    let response = client
+        .post(&validate)
+        .body(serde_yaml::to_string(&candidate).unwrap())
+        .send()
+        .await
+        .unwrap();
+
+    // Then: It is rejected as unreachable
+    assert_eq!(response.status(), 422);
+    let result: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(result["reachability"][0]["reachable"], false, "{}", result);
+    assert!(result["reachability"][0]["error"].is_string());
+
+    // When: A candidate does not validate
+    let response = client.post(&validate).body("servers:\n  - id: broken\n").send().await.unwrap();
+
+    // Then: The problems are reported and there is no diff
+    assert_eq!(response.status(), 422);
+    let result: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(result["diagnostics"][0]["severity"], "error", "{}", result);
+    assert!(result["diff"].is_null());
+
+    // And: Nothing was applied
+    let response = client.post(&validate).body(healthy).send().await.unwrap();
+    let result: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(
+        result["diff"]["servers_added"],
+        json!(["added"]),
+        "{}",
+        result
+    );
+}
@@ -6,6 +6,7 @@
 //! - Duplicate instance prevention
 //! - Stale PID file handling
 //! - Graceful shutdown via signals
+//! - Zero-downtime binary upgrades via SIGUSR2
 
 use only1mcp::daemon::DaemonManager;
 use std::fs;
@@ -46,7 +47,7 @@ fn test_daemon_start_and_stop() {
 
     // Start daemon
     let start_output = Command::new(&binary)
-        .args(&["start"])
+        .args(["start"])
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .output()
@@ -67,7 +68,7 @@ fn test_daemon_start_and_stop() {
     assert!(pid_path.exists(), "PID file should exist after start");
 
     // Check process is running
-    let pid = fs::read_to_string(&pid_path)
+    let pid = fs::read_to_string(pid_path)
         .expect("Should be able to read PID file")
         .trim()
         .parse::<u32>()
@@ -77,7 +78,7 @@ fn test_daemon_start_and_stop() {
 
     // Stop daemon
     let stop_output = Command::new(&binary)
-        .args(&["stop"])
+        .args(["stop"])
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .output()
@@ -107,7 +108,7 @@ fn test_foreground_mode() {
 
     // Start in foreground mode with timeout
     let mut child = Command::new(&binary)
-        .args(&["start", "--foreground"])
+        .args(["start", "--foreground"])
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
@@ -143,7 +144,7 @@ fn test_duplicate_instance_prevention() {
 
     // Start first instance
     let start1_output = Command::new(&binary)
-        .args(&["start"])
+        .args(["start"])
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .output()
@@ -156,7 +157,7 @@ fn test_duplicate_instance_prevention() {
 
     // Try to start second instance
     let start2_output = Command::new(&binary)
-        .args(&["start"])
+        .args(["start"])
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .output()
@@ -186,13 +187,13 @@ fn test_stale_pid_file_handling() {
 
     // Create stale PID file with non-existent PID
     let stale_pid = 99999u32;
-    fs::write(&pid_path, stale_pid.to_string()).expect("Failed to write stale PID file");
+    fs::write(pid_path, stale_pid.to_string()).expect("Failed to write stale PID file");
 
     let binary = get_binary_path();
 
     // Start should detect stale PID and proceed
     let start_output = Command::new(&binary)
-        .args(&["start"])
+        .args(["start"])
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .output()
@@ -209,7 +210,7 @@ fn test_stale_pid_file_handling() {
     thread::sleep(Duration::from_secs(2));
 
     // Verify new PID is different
-    let new_pid = fs::read_to_string(&pid_path)
+    let new_pid = fs::read_to_string(pid_path)
         .expect("Should be able to read PID file")
         .trim()
         .parse::<u32>()
@@ -230,7 +231,7 @@ fn test_graceful_shutdown_signal() {
 
     // Start daemon
     let start_output = Command::new(&binary)
-        .args(&["start"])
+        .args(["start"])
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .output()
@@ -244,7 +245,7 @@ fn test_graceful_shutdown_signal() {
     // Get PID
     let daemon_mgr = DaemonManager::new().unwrap();
     let pid_path = daemon_mgr.get_pid_path();
-    let pid = fs::read_to_string(&pid_path)
+    let pid = fs::read_to_string(pid_path)
         .expect("Should be able to read PID file")
         .trim()
         .parse::<u32>()
@@ -263,7 +264,7 @@ fn test_graceful_shutdown_signal() {
     {
         // Windows doesn't have SIGTERM, use taskkill
         Command::new("taskkill")
-            .args(&["/PID", &pid.to_string()])
+            .args(["/PID", &pid.to_string()])
             .output()
             .expect("Failed to kill process");
     }
@@ -281,6 +282,94 @@ fn test_graceful_shutdown_signal() {
     cleanup_daemon();
 }
 
+#[cfg(unix)]
+#[test]
+fn test_binary_upgrade_keeps_serving() {
+    use nix::sys::signal::{kill, Signal};
+    use nix::unistd::Pid;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpStream;
+    use std::sync::mpsc;
+
+    // A config directory of its own, so the shared daemon files of the other
+    // tests are left alone
+    let config_home = tempfile::tempdir().unwrap();
+    let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+
+    let alive = || -> bool {
+        let Ok(mut stream) = TcpStream::connect(("127.0.0.1", port)) else {
+            return false;
+        };
+        let request = "GET /health/live HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+        let mut response = String::new();
+        stream.write_all(request.as_bytes()).is_ok()
+            && stream.read_to_string(&mut response).is_ok()
+            && response.starts_with("HTTP/1.1 200")
+    };
+
+    let mut child = Command::new(get_binary_path())
+        .args(["start", "--foreground", "--port", &port.to_string()])
+        .env("XDG_CONFIG_HOME", config_home.path())
+        .env_remove("ONLY1MCP_CONFIG")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("Failed to execute start --foreground");
+
+    // The upgraded process inherits the log output and is named in it
+    let (pid_tx, pid_rx) = mpsc::channel();
+    let stdout = child.stdout.take().unwrap();
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(|line| line.ok()) {
+            if let Some((_, rest)) = line.split_once("Upgraded process ") {
+                if let Some(pid) = rest.split(' ').next().and_then(|pid| pid.parse::<u32>().ok()) {
+                    let _ = pid_tx.send(pid);
+                }
+            }
+        }
+    });
+
+    let mut attempts = 0;
+    while !alive() && attempts < 100 {
+        thread::sleep(Duration::from_millis(100));
+        attempts += 1;
+    }
+    assert!(alive(), "Proxy should serve before the upgrade");
+
+    kill(Pid::from_raw(child.id() as i32), Signal::SIGUSR2).expect("Failed to send SIGUSR2");
+
+    // Every request succeeds while the new process takes over the socket
+    // and the old one drains and exits
+    let mut exited = None;
+    for _ in 0..300 {
+        assert!(alive(), "Request failed during the upgrade");
+        exited = child.try_wait().unwrap();
+        if exited.is_some() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    assert!(exited.expect("Old process should exit").success());
+
+    let new_pid = pid_rx
+        .recv_timeout(Duration::from_secs(5))
+        .expect("Upgraded process should be logged");
+    assert!(
+        is_process_running(new_pid),
+        "Upgraded process should be running"
+    );
+    assert!(alive(), "Upgraded process should serve");
+
+    kill(Pid::from_raw(new_pid as i32), Signal::SIGTERM).expect("Failed to send SIGTERM");
+    for _ in 0..50 {
+        if !is_process_running(new_pid) {
+            break;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    assert!(!alive(), "Upgraded process should stop on SIGTERM");
+}
+
 /// Helper: Check if a process with given PID is running
 fn is_process_running(pid: u32) -> bool {
     #[cfg(unix)]
@@ -295,7 +384,7 @@ fn is_process_running(pid: u32) -> bool {
     #[cfg(windows)]
     {
         let output = Command::new("tasklist")
-            .args(&["/FI", &format!("PID eq {}", pid), "/NH"])
+            .args(["/FI", &format!("PID eq {}", pid), "/NH"])
             .output()
             .expect("Failed to execute tasklist");
 
@@ -0,0 +1,93 @@
+//! Integration tests for discovering backends from Docker container labels
+
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use serde_json::json;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Serve a Docker API on `socket` whose container list is the returned JSON
+fn fake_docker_api(socket: &Path) -> Arc<Mutex<String>> {
+    let containers = Arc::new(Mutex::new(String::from("[]")));
+    let listener = tokio::net::UnixListener::bind(socket).unwrap();
+    let listed = containers.clone();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                match stream.read(&mut buf).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => request.extend_from_slice(&buf[..n]),
+                }
+            }
+            let body = listed.lock().unwrap().clone();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        }
+    });
+    containers
+}
+
+/// Wait until tools/list returns `expected` tools
+async fn wait_for_tools(server: &TestServer, expected: usize) {
+    let mut tools = Vec::new();
+    for _ in 0..50 {
+        let body = server.rpc("tools/list", json!({})).await;
+        tools = body["result"]["tools"].as_array().cloned().unwrap_or_default();
+        if tools.len() == expected {
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    panic!("expected {} tools, got {:?}", expected, tools);
+}
+
+#[tokio::test]
+async fn test_docker_discovery_registers_labelled_containers() {
+    // Given: A backend, and a Docker API whose container list can change
+    let backend = mock_backend(vec![sample_tool("forecast", "Forecast")]).await;
+    let dir = tempfile::tempdir().unwrap();
+    let socket = dir.path().join("docker.sock");
+    let containers = fake_docker_api(&socket);
+
+    let mut config = test_config_with_backends(0, vec![]);
+    config.discovery.docker.enabled = true;
+    config.discovery.docker.socket = socket;
+    config.discovery.docker.poll_interval_ms = 100;
+    let server = start_test_server(config).await;
+
+    // When: A labelled container starts
+    *containers.lock().unwrap() = json!([{
+        "Id": "0123456789abcdef",
+        "Names": ["/weather"],
+        "Labels": {"only1mcp.transport": "http", "only1mcp.url": backend.uri()}
+    }])
+    .to_string();
+
+    // Then: Its tools are served
+    wait_for_tools(&server, 1).await;
+    let servers: serde_json::Value = test_client()
+        .get(format!("{}/api/v1/admin/servers", server.url()))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(servers.to_string().contains("weather"), "{}", servers);
+
+    // When: The container stops
+    *containers.lock().unwrap() = "[]".to_string();
+
+    // Then: Its tools are gone
+    wait_for_tools(&server, 0).await;
+}
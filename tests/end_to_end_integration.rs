@@ -30,13 +30,11 @@ use tokio::time::sleep;
 // ============================================================================
 
 /// Mock HTTP MCP server for testing
-async fn spawn_mock_http_server(port: u16, _response_tools: Vec<String>) -> tokio::task::JoinHandle<()> {
-    use axum::{
-        extract::Json,
-        response::IntoResponse,
-        routing::post,
-        Router,
-    };
+async fn spawn_mock_http_server(
+    port: u16,
+    _response_tools: Vec<String>,
+) -> tokio::task::JoinHandle<()> {
+    use axum::{extract::Json, response::IntoResponse, routing::post, Router};
     use std::net::SocketAddr;
 
     async fn handle_request(Json(req): Json<serde_json::Value>) -> impl IntoResponse {
@@ -45,13 +43,11 @@ async fn spawn_mock_http_server(port: u16, _response_tools: Vec<String>) -> toki
 
         match method {
             "tools/list" => {
-                let tools = vec![
-                    json!({
-                        "name": "test_tool",
-                        "description": "A test tool",
-                        "inputSchema": {"type": "object", "properties": {}}
-                    })
-                ];
+                let tools = vec![json!({
+                    "name": "test_tool",
+                    "description": "A test tool",
+                    "inputSchema": {"type": "object", "properties": {}}
+                })];
                 axum::Json(json!({
                     "jsonrpc": "2.0",
                     "id": req.get("id"),
@@ -60,24 +56,20 @@ async fn spawn_mock_http_server(port: u16, _response_tools: Vec<String>) -> toki
                     }
                 }))
             },
-            "resources/list" => {
-                axum::Json(json!({
-                    "jsonrpc": "2.0",
-                    "id": req.get("id"),
-                    "result": {
-                        "resources": []
-                    }
-                }))
-            },
-            _ => {
-                axum::Json(json!({
-                    "jsonrpc": "2.0",
-                    "id": req.get("id"),
-                    "result": {
-                        "message": "ok"
-                    }
-                }))
-            }
+            "resources/list" => axum::Json(json!({
+                "jsonrpc": "2.0",
+                "id": req.get("id"),
+                "result": {
+                    "resources": []
+                }
+            })),
+            _ => axum::Json(json!({
+                "jsonrpc": "2.0",
+                "id": req.get("id"),
+                "result": {
+                    "message": "ok"
+                }
+            })),
         }
     }
 
@@ -121,16 +113,37 @@ fn create_test_config_http(backend_port: u16, proxy_port: u16) -> Config {
             transport: TransportConfig::Http {
                 url: format!("http://127.0.0.1:{}", backend_port),
                 headers: std::collections::HashMap::new(),
+                oauth: None,
+                tls: None,
+                signing: None,
+                proxy: None,
             },
             health_check: Default::default(),
             routing: Default::default(),
             weight: 1,
+            timeout_ms: 30000,
+            retry: Default::default(),
+            tags: Vec::new(),
+            notes: None,
+            tool_metadata: Default::default(),
+            cost: None,
+            startup: Default::default(),
+            restart: Default::default(),
+            resources: Default::default(),
+            sandbox: Default::default(),
+            env_policy: Default::default(),
+            mirror: None,
+            transforms: Default::default(),
         }],
         proxy: ProxyConfig::default(),
         context_optimization: Default::default(),
         auth: Default::default(),
         observability: Default::default(),
         tui: Default::default(),
+        backups: Default::default(),
+        cluster: Default::default(),
+        discovery: Default::default(),
+        gitops: Default::default(),
     }
 }
 
@@ -152,10 +165,27 @@ fn create_test_config_multi_backend(backend_ports: Vec<u16>, proxy_port: u16) ->
                 transport: TransportConfig::Http {
                     url: format!("http://127.0.0.1:{}", port),
                     headers: std::collections::HashMap::new(),
+                    oauth: None,
+                    tls: None,
+                    signing: None,
+                    proxy: None,
                 },
                 health_check: Default::default(),
                 routing: Default::default(),
                 weight: 1,
+                timeout_ms: 30000,
+                retry: Default::default(),
+                tags: Vec::new(),
+                notes: None,
+                tool_metadata: Default::default(),
+                cost: None,
+                startup: Default::default(),
+                restart: Default::default(),
+                resources: Default::default(),
+                sandbox: Default::default(),
+                env_policy: Default::default(),
+                mirror: None,
+                transforms: Default::default(),
             })
             .collect(),
         proxy: ProxyConfig::default(),
@@ -163,6 +193,10 @@ fn create_test_config_multi_backend(backend_ports: Vec<u16>, proxy_port: u16) ->
         auth: Default::default(),
         observability: Default::default(),
         tui: Default::default(),
+        backups: Default::default(),
+        cluster: Default::default(),
+        discovery: Default::default(),
+        gitops: Default::default(),
     }
 }
 
@@ -245,23 +279,39 @@ async fn test_full_proxy_flow_stdio_backend() -> Result<()> {
                 command: "echo".to_string(), // Simple command that exists
                 args: vec!["test".to_string()],
                 env: std::collections::HashMap::new(),
+                cwd: None,
             },
             health_check: Default::default(),
             routing: Default::default(),
             weight: 1,
+            timeout_ms: 30000,
+            retry: Default::default(),
+            tags: Vec::new(),
+            notes: None,
+            tool_metadata: Default::default(),
+            cost: None,
+            startup: Default::default(),
+            restart: Default::default(),
+            resources: Default::default(),
+            sandbox: Default::default(),
+            env_policy: Default::default(),
+            mirror: None,
+            transforms: Default::default(),
         }],
         proxy: Default::default(),
         context_optimization: Default::default(),
         auth: Default::default(),
         observability: Default::default(),
         tui: Default::default(),
+        backups: Default::default(),
+        cluster: Default::default(),
+        discovery: Default::default(),
+        gitops: Default::default(),
     };
 
     let config_path = PathBuf::from("/tmp/only1mcp-test-stdio.yaml");
-    let server = ProxyServer::new(config, config_path).await?;
-
     // Verify server created successfully with STDIO transport
-    assert!(true, "Server with STDIO transport initialized successfully");
+    let _server = ProxyServer::new(config, config_path).await?;
 
     Ok(())
 }
@@ -348,10 +398,27 @@ async fn test_circuit_breaker_failover() -> Result<()> {
                 transport: TransportConfig::Http {
                     url: format!("http://127.0.0.1:{}", failing_port),
                     headers: std::collections::HashMap::new(),
+                    oauth: None,
+                    tls: None,
+                    signing: None,
+                    proxy: None,
                 },
                 health_check: Default::default(),
                 routing: Default::default(),
                 weight: 1,
+                timeout_ms: 30000,
+                retry: Default::default(),
+                tags: Vec::new(),
+                notes: None,
+                tool_metadata: Default::default(),
+                cost: None,
+                startup: Default::default(),
+                restart: Default::default(),
+                resources: Default::default(),
+                sandbox: Default::default(),
+                env_policy: Default::default(),
+                mirror: None,
+                transforms: Default::default(),
             },
             McpServerConfig {
                 id: "healthy-backend".to_string(),
@@ -360,10 +427,27 @@ async fn test_circuit_breaker_failover() -> Result<()> {
                 transport: TransportConfig::Http {
                     url: format!("http://127.0.0.1:{}", healthy_port),
                     headers: std::collections::HashMap::new(),
+                    oauth: None,
+                    tls: None,
+                    signing: None,
+                    proxy: None,
                 },
                 health_check: Default::default(),
                 routing: Default::default(),
                 weight: 1,
+                timeout_ms: 30000,
+                retry: Default::default(),
+                tags: Vec::new(),
+                notes: None,
+                tool_metadata: Default::default(),
+                cost: None,
+                startup: Default::default(),
+                restart: Default::default(),
+                resources: Default::default(),
+                sandbox: Default::default(),
+                env_policy: Default::default(),
+                mirror: None,
+                transforms: Default::default(),
             },
         ],
         proxy: Default::default(),
@@ -371,6 +455,10 @@ async fn test_circuit_breaker_failover() -> Result<()> {
         auth: Default::default(),
         observability: Default::default(),
         tui: Default::default(),
+        backups: Default::default(),
+        cluster: Default::default(),
+        discovery: Default::default(),
+        gitops: Default::default(),
     };
 
     let config_path = PathBuf::from("/tmp/only1mcp-test-cb.yaml");
@@ -446,6 +534,10 @@ async fn test_authentication_jwt_flow() -> Result<()> {
         auth: Default::default(), // Auth config placeholder
         observability: Default::default(),
         tui: Default::default(),
+        backups: Default::default(),
+        cluster: Default::default(),
+        discovery: Default::default(),
+        gitops: Default::default(),
     };
 
     let config_path = PathBuf::from("/tmp/only1mcp-test-auth.yaml");
@@ -650,10 +742,7 @@ async fn test_health_check_endpoint() -> Result<()> {
 
     // Request health endpoint
     let client = reqwest::Client::new();
-    let response = client
-        .get(format!("http://127.0.0.1:{}/health", proxy_port))
-        .send()
-        .await?;
+    let response = client.get(format!("http://127.0.0.1:{}/health", proxy_port)).send().await?;
 
     assert_eq!(response.status(), 200);
 
@@ -695,21 +784,30 @@ async fn test_admin_api_endpoints() -> Result<()> {
 
     // Test /api/v1/admin/health
     let health = client
-        .get(format!("http://127.0.0.1:{}/api/v1/admin/health", proxy_port))
+        .get(format!(
+            "http://127.0.0.1:{}/api/v1/admin/health",
+            proxy_port
+        ))
         .send()
         .await?;
     assert_eq!(health.status(), 200);
 
     // Test /api/v1/admin/servers
     let servers = client
-        .get(format!("http://127.0.0.1:{}/api/v1/admin/servers", proxy_port))
+        .get(format!(
+            "http://127.0.0.1:{}/api/v1/admin/servers",
+            proxy_port
+        ))
         .send()
         .await?;
     assert_eq!(servers.status(), 200);
 
     // Test /api/v1/admin/system
     let system = client
-        .get(format!("http://127.0.0.1:{}/api/v1/admin/system", proxy_port))
+        .get(format!(
+            "http://127.0.0.1:{}/api/v1/admin/system",
+            proxy_port
+        ))
         .send()
         .await?;
     assert_eq!(system.status(), 200);
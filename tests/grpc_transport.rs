@@ -0,0 +1,166 @@
+//! Integration tests for the gRPC transport
+//!
+//! Runs a minimal `only1mcp.v1.McpService` server with tonic and calls it
+//! directly and through the proxy.
+
+mod common;
+
+use futures::future::BoxFuture;
+use only1mcp::config::TransportConfig;
+use only1mcp::transport::grpc::{GrpcError, GrpcTransportPool, JsonRpcMessage, SERVICE_NAME};
+use only1mcp::types::McpRequest;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tonic::body::BoxBody;
+use tonic::codec::ProstCodec;
+use tonic::codegen::http;
+use tonic::server::{NamedService, UnaryService};
+use tonic::Status;
+
+/// MCP backend answering `tools/list` with one tool named after the
+/// `x-tenant` metadata it received.
+#[derive(Clone)]
+struct McpGrpcBackend;
+
+impl NamedService for McpGrpcBackend {
+    const NAME: &'static str = SERVICE_NAME;
+}
+
+impl tower::Service<http::Request<BoxBody>> for McpGrpcBackend {
+    type Response = http::Response<BoxBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<BoxBody>) -> Self::Future {
+        Box::pin(async move {
+            let codec = ProstCodec::<JsonRpcMessage, JsonRpcMessage>::default();
+            Ok(tonic::server::Grpc::new(codec).unary(Call, request).await)
+        })
+    }
+}
+
+struct Call;
+
+impl UnaryService<JsonRpcMessage> for Call {
+    type Response = JsonRpcMessage;
+    type Future = BoxFuture<'static, Result<tonic::Response<JsonRpcMessage>, Status>>;
+
+    fn call(&mut self, request: tonic::Request<JsonRpcMessage>) -> Self::Future {
+        Box::pin(async move {
+            let tenant = request
+                .metadata()
+                .get("x-tenant")
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or("anonymous")
+                .to_string();
+            let message: Value = serde_json::from_slice(&request.get_ref().payload)
+                .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+            let response = json!({
+                "jsonrpc": "2.0",
+                "id": message["id"],
+                "result": {
+                    "tools": [{
+                        "name": format!("grpc_{}", tenant),
+                        "description": "Served over gRPC",
+                        "inputSchema": {"type": "object"}
+                    }]
+                }
+            });
+            Ok(tonic::Response::new(JsonRpcMessage {
+                payload: serde_json::to_vec(&response).unwrap().into(),
+            }))
+        })
+    }
+}
+
+/// Start the gRPC backend, returning its endpoint
+async fn start_grpc_backend() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+
+    tokio::spawn(tonic::transport::Server::builder().add_service(McpGrpcBackend).serve(addr));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    format!("http://{}", addr)
+}
+
+#[tokio::test]
+async fn test_grpc_call_carries_metadata() {
+    // Given: a gRPC backend and metadata naming the tenant
+    let endpoint = start_grpc_backend().await;
+    let pool = GrpcTransportPool::new();
+    let metadata = HashMap::from([("X-Tenant".to_string(), "acme".to_string())]);
+
+    // When: tools/list is sent over the channel
+    let request = McpRequest::new("tools/list", json!({}), Some(json!(7)));
+    let response = pool
+        .send_request(
+            &endpoint,
+            request,
+            &metadata,
+            Some(Duration::from_secs(5)),
+            None,
+        )
+        .await
+        .expect("gRPC call should succeed");
+
+    // Then: the backend saw the metadata and the id is preserved
+    assert_eq!(response.id, Some(json!(7)));
+    assert_eq!(response.result.unwrap()["tools"][0]["name"], "grpc_acme");
+}
+
+#[tokio::test]
+async fn test_grpc_unreachable_backend_fails() {
+    // Given: an endpoint nothing listens on
+    let pool = GrpcTransportPool::new();
+
+    // When: a request is sent to it
+    let request = McpRequest::new("tools/list", json!({}), Some(json!(1)));
+    let result = pool
+        .send_request("http://127.0.0.1:1", request, &HashMap::new(), None, None)
+        .await;
+
+    // Then: the call fails to connect
+    assert!(matches!(result, Err(GrpcError::ConnectionFailed(_))));
+}
+
+#[tokio::test]
+async fn test_proxy_routes_to_grpc_backend() {
+    // Given: a proxy whose only backend speaks gRPC
+    let endpoint = start_grpc_backend().await;
+    let mut config = common::test_config_with_backends(0, vec![endpoint.clone()]);
+    config.servers[0].transport = TransportConfig::Grpc {
+        endpoint,
+        metadata: HashMap::from([("x-tenant".to_string(), "proxy".to_string())]),
+        tls: None,
+    };
+    let server = common::start_test_server(config).await;
+
+    // When: a client lists tools
+    let response = common::test_client()
+        .post(format!("{}/mcp", server.url()))
+        .json(&json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"}))
+        .send()
+        .await
+        .unwrap();
+
+    // Then: the gRPC backend's tool is listed
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    let tools = body["result"]["tools"].as_array().expect("tools array");
+    assert!(
+        tools
+            .iter()
+            .any(|tool| tool["name"].as_str().unwrap_or("").contains("grpc_proxy")),
+        "unexpected tools: {:?}",
+        tools
+    );
+}
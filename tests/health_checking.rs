@@ -1,9 +1,13 @@
 //! Comprehensive tests for active health checking feature
 
+mod common;
+
+use common::*;
 use only1mcp::{
-    config::HealthCheckConfig,
+    config::{BackendTlsConfig, HealthCheckConfig},
     health::checker::{HealthCheckTransport, HealthChecker, HealthState},
 };
+use serde_json::json;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::sleep;
@@ -24,6 +28,60 @@ fn test_health_config() -> HealthCheckConfig {
     }
 }
 
+/// Start an HTTPS backend that requires a client certificate signed by the
+/// test CA and answers every request with 200, returning its URL.
+async fn start_mtls_backend() -> String {
+    use hyper_util::rt::{TokioExecutor, TokioIo};
+    use hyper_util::service::TowerToHyperService;
+
+    let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/tls");
+    let pem = |name: &str| std::fs::read(fixtures.join(name)).unwrap();
+    let certs = |name: &str| {
+        rustls_pemfile::certs(&mut pem(name).as_slice())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    };
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let mut roots = rustls::RootCertStore::empty();
+    roots.add_parsable_certificates(certs("ca.pem"));
+    let verifier = rustls::server::WebPkiClientVerifier::builder_with_provider(
+        Arc::new(roots),
+        provider.clone(),
+    )
+    .build()
+    .unwrap();
+    let key = rustls_pemfile::private_key(&mut pem("key-a.pem").as_slice()).unwrap().unwrap();
+    let config = rustls::ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .unwrap()
+        .with_client_cert_verifier(verifier)
+        .with_single_cert(certs("cert-a.pem"), key)
+        .unwrap();
+    let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!(
+        "https://localhost:{}",
+        listener.local_addr().unwrap().port()
+    );
+    let router = axum::Router::new().fallback(|| async { "ok" });
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let (acceptor, router) = (acceptor.clone(), router.clone());
+            tokio::spawn(async move {
+                let Ok(stream) = acceptor.accept(stream).await else {
+                    return;
+                };
+                let _ = hyper_util::server::conn::auto::Builder::new(TokioExecutor::new())
+                    .serve_connection(TokioIo::new(stream), TowerToHyperService::new(router))
+                    .await;
+            });
+        }
+    });
+    url
+}
+
 #[tokio::test]
 async fn test_http_health_check_success() {
     // Start mock HTTP server
@@ -47,12 +105,13 @@ async fn test_http_health_check_success() {
     // Create health checker
     let transport = HealthCheckTransport::Http {
         endpoint: mock_server.uri(),
+        tls: None,
+        proxy: None,
     };
-    let checker = Arc::new(HealthChecker::from_config(
-        "test-server".to_string(),
-        transport,
-        test_health_config(),
-    ));
+    let checker = Arc::new(
+        HealthChecker::from_config("test-server".to_string(), transport, test_health_config())
+            .unwrap(),
+    );
 
     // Start health checking in background
     let checker_clone = checker.clone();
@@ -77,6 +136,34 @@ async fn test_http_health_check_success() {
     handle.abort();
 }
 
+#[tokio::test]
+async fn test_check_now_runs_outside_interval() {
+    // Given: A health checker that is not running on its interval
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let checker = HealthChecker::from_config(
+        "test-server".to_string(),
+        HealthCheckTransport::Http {
+            endpoint: mock_server.uri(),
+            tls: None,
+            proxy: None,
+        },
+        test_health_config(),
+    )
+    .unwrap();
+
+    // When/Then: Each on-demand check counts towards the healthy threshold
+    assert_eq!(checker.check_now().await, HealthState::Unknown);
+    assert_eq!(checker.check_now().await, HealthState::Healthy);
+    assert_eq!(checker.get_status().await.success_count, 2);
+}
+
 #[tokio::test]
 async fn test_http_health_check_failure() {
     // Start mock HTTP server
@@ -93,12 +180,13 @@ async fn test_http_health_check_failure() {
     // Create health checker
     let transport = HealthCheckTransport::Http {
         endpoint: mock_server.uri(),
+        tls: None,
+        proxy: None,
     };
-    let checker = Arc::new(HealthChecker::from_config(
-        "test-server".to_string(),
-        transport,
-        test_health_config(),
-    ));
+    let checker = Arc::new(
+        HealthChecker::from_config("test-server".to_string(), transport, test_health_config())
+            .unwrap(),
+    );
 
     // Start health checking in background
     let checker_clone = checker.clone();
@@ -138,12 +226,13 @@ async fn test_http_health_check_timeout() {
     // Create health checker with short timeout
     let transport = HealthCheckTransport::Http {
         endpoint: mock_server.uri(),
+        tls: None,
+        proxy: None,
     };
-    let checker = Arc::new(HealthChecker::from_config(
-        "test-server".to_string(),
-        transport,
-        test_health_config(),
-    ));
+    let checker = Arc::new(
+        HealthChecker::from_config("test-server".to_string(), transport, test_health_config())
+            .unwrap(),
+    );
 
     // Start health checking in background
     let checker_clone = checker.clone();
@@ -176,11 +265,10 @@ async fn test_stdio_health_check_valid_command() {
         args: vec![],
     };
 
-    let checker = Arc::new(HealthChecker::from_config(
-        "test-stdio".to_string(),
-        transport,
-        test_health_config(),
-    ));
+    let checker = Arc::new(
+        HealthChecker::from_config("test-stdio".to_string(), transport, test_health_config())
+            .unwrap(),
+    );
 
     // Start health checking in background
     let checker_clone = checker.clone();
@@ -209,11 +297,14 @@ async fn test_stdio_health_check_invalid_command() {
         args: vec![],
     };
 
-    let checker = Arc::new(HealthChecker::from_config(
-        "test-stdio-invalid".to_string(),
-        transport,
-        test_health_config(),
-    ));
+    let checker = Arc::new(
+        HealthChecker::from_config(
+            "test-stdio-invalid".to_string(),
+            transport,
+            test_health_config(),
+        )
+        .unwrap(),
+    );
 
     // Start health checking in background
     let checker_clone = checker.clone();
@@ -252,12 +343,17 @@ async fn test_health_threshold_transitions() {
     // Create health checker
     let transport = HealthCheckTransport::Http {
         endpoint: mock_server.uri(),
+        tls: None,
+        proxy: None,
     };
-    let checker = Arc::new(HealthChecker::from_config(
-        "test-threshold".to_string(),
-        transport,
-        test_health_config(),
-    ));
+    let checker = Arc::new(
+        HealthChecker::from_config(
+            "test-threshold".to_string(),
+            transport,
+            test_health_config(),
+        )
+        .unwrap(),
+    );
 
     // Start health checking
     let checker_clone = checker.clone();
@@ -310,12 +406,13 @@ async fn test_health_metrics_recorded() {
     // Create health checker
     let transport = HealthCheckTransport::Http {
         endpoint: mock_server.uri(),
+        tls: None,
+        proxy: None,
     };
-    let checker = Arc::new(HealthChecker::from_config(
-        "test-metrics".to_string(),
-        transport,
-        test_health_config(),
-    ));
+    let checker = Arc::new(
+        HealthChecker::from_config("test-metrics".to_string(), transport, test_health_config())
+            .unwrap(),
+    );
 
     // Get initial metric values
     let initial_total = HEALTH_CHECK_TOTAL.with_label_values(&["test-metrics", "success"]).get();
@@ -349,3 +446,243 @@ async fn test_health_metrics_recorded() {
     checker.stop();
     handle.abort();
 }
+
+#[tokio::test]
+async fn test_health_checker_drives_registry() {
+    use only1mcp::config::{Config, McpServerConfig, RoutingConfig, TransportConfig};
+    use only1mcp::metrics::BACKEND_HEALTH_STATUS;
+    use only1mcp::proxy::router::ServerRegistry;
+    use std::collections::HashMap;
+
+    // Start mock HTTP server that always fails health checks
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&mock_server)
+        .await;
+
+    // Registry with the failing backend
+    let config = Config {
+        servers: vec![McpServerConfig {
+            id: "registry-backend".to_string(),
+            name: "Registry Backend".to_string(),
+            enabled: true,
+            transport: TransportConfig::Http {
+                url: mock_server.uri(),
+                headers: HashMap::new(),
+                oauth: None,
+                tls: None,
+                signing: None,
+                proxy: None,
+            },
+            health_check: test_health_config(),
+            routing: RoutingConfig::default(),
+            weight: 1,
+            timeout_ms: 30000,
+            retry: Default::default(),
+            tags: Vec::new(),
+            notes: None,
+            tool_metadata: Default::default(),
+            cost: None,
+            startup: Default::default(),
+            restart: Default::default(),
+            resources: Default::default(),
+            sandbox: Default::default(),
+            env_policy: Default::default(),
+            mirror: None,
+            transforms: Default::default(),
+        }],
+        ..Default::default()
+    };
+    let registry = ServerRegistry::from_config(&config).await.unwrap();
+
+    // Unchecked servers are available
+    assert_eq!(
+        registry.health_state("registry-backend"),
+        HealthState::Unknown
+    );
+    assert_eq!(
+        registry.get_healthy_servers().await,
+        vec!["registry-backend"]
+    );
+
+    // Health checker publishes its state into the registry
+    let checker = Arc::new(
+        HealthChecker::from_config(
+            "registry-backend".to_string(),
+            HealthCheckTransport::from_transport_config(&config.servers[0].transport, None),
+            test_health_config(),
+        )
+        .unwrap()
+        .with_state_sink(registry.health_states()),
+    );
+    let handle = tokio::spawn(checker.clone().start());
+
+    // Wait for the unhealthy threshold (2 failures at 1s interval)
+    sleep(Duration::from_secs(3)).await;
+
+    assert_eq!(
+        registry.health_state("registry-backend"),
+        HealthState::Unhealthy
+    );
+    assert!(registry.get_healthy_servers().await.is_empty());
+    assert_eq!(
+        BACKEND_HEALTH_STATUS.with_label_values(&["registry-backend", "http"]).get(),
+        0.0
+    );
+
+    checker.stop();
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_http_health_check_uses_backend_tls() {
+    let endpoint = start_mtls_backend().await;
+    let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/tls");
+    let tls = BackendTlsConfig {
+        client_cert_path: Some(fixtures.join("client.pem")),
+        client_key_path: Some(fixtures.join("client-key.pem")),
+        ca_path: Some(fixtures.join("ca.pem")),
+    };
+    let checker = |tls| {
+        HealthChecker::from_config(
+            "mtls-backend".to_string(),
+            HealthCheckTransport::Http {
+                endpoint: endpoint.clone(),
+                tls: Some(tls),
+                proxy: None,
+            },
+            test_health_config(),
+        )
+        .unwrap()
+    };
+
+    // The check presents the backend's client certificate and trusts its CA
+    let result = checker(tls.clone()).probe().await;
+    assert!(result.is_ok(), "mTLS health check failed: {:?}", result);
+
+    // Without the client certificate the backend rejects the handshake
+    let anonymous = BackendTlsConfig {
+        client_cert_path: None,
+        client_key_path: None,
+        ..tls.clone()
+    };
+    assert!(checker(anonymous).probe().await.is_err());
+
+    // Unreadable TLS material is reported instead of ignored
+    let missing = BackendTlsConfig {
+        ca_path: Some(fixtures.join("missing.pem")),
+        ..tls
+    };
+    assert!(HealthChecker::from_config(
+        "mtls-backend".to_string(),
+        HealthCheckTransport::Http {
+            endpoint,
+            tls: Some(missing),
+            proxy: None,
+        },
+        test_health_config(),
+    )
+    .is_err());
+}
+
+#[tokio::test]
+async fn test_tools_annotated_with_server_health() {
+    // Given: A health-checked backend with one tool
+    let backend = mock_backend(vec![sample_tool("echo", "Echo")]).await;
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.servers[0].health_check.enabled = true;
+    config.servers[0].health_check.interval_seconds = 1;
+    config.servers[0].health_check.healthy_threshold = 1;
+    let server = start_test_server(config).await;
+
+    // When: tools/list is requested once the backend has passed a check
+    let mut health = serde_json::Value::Null;
+    for _ in 0..30 {
+        let body = server.rpc("tools/list", json!({})).await;
+        health = body["result"]["tools"][0]["_meta"]["health"].clone();
+        if !health.is_null() {
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+
+    // Then: The tool carries its server's health
+    assert_eq!(health, "healthy");
+}
+
+#[tokio::test]
+async fn test_health_probes_and_detail() {
+    // Given: A proxy without backends
+    let server = start_test_server(test_config()).await;
+    let client = test_client();
+
+    // Then: It is live but not ready
+    let live = client.get(format!("{}/health/live", server.url())).send().await.unwrap();
+    assert_eq!(live.status(), 200);
+    let ready = client.get(format!("{}/health/ready", server.url())).send().await.unwrap();
+    assert_eq!(ready.status(), 503);
+    let body: serde_json::Value = ready.json().await.unwrap();
+    assert_eq!(body["reason"], "no healthy backends");
+
+    // Given: One passing and one failing health-checked backend
+    let up = MockServer::start().await;
+    Mock::given(path("/health"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&up)
+        .await;
+    let down = MockServer::start().await;
+    Mock::given(path("/health"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&down)
+        .await;
+
+    let mut config = test_config_with_backends(0, vec![up.uri(), down.uri()]);
+    for backend in &mut config.servers {
+        backend.health_check.enabled = true;
+        backend.health_check.interval_seconds = 1;
+        backend.health_check.healthy_threshold = 1;
+        backend.health_check.unhealthy_threshold = 1;
+    }
+    let server = start_test_server(config).await;
+
+    // When: Both have been checked
+    let mut detail = serde_json::Value::Null;
+    for _ in 0..30 {
+        detail = client
+            .get(format!("{}/health/detail", server.url()))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        let checked = detail["backends"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|backend| backend["health"] != "unknown");
+        if checked {
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+
+    // Then: The detail reports each backend's checks and circuit
+    assert_eq!(detail["status"], "degraded", "{}", detail);
+    assert_eq!(detail["ready"], true);
+    let backends = detail["backends"].as_array().unwrap();
+    assert_eq!(backends[0]["health"], "healthy");
+    assert_eq!(backends[0]["circuit"], "closed");
+    assert!(backends[0]["last_check"].is_string());
+    assert_eq!(backends[1]["health"], "unhealthy");
+    assert_eq!(backends[1]["circuit"], "open");
+    assert!(backends[1]["consecutive_failures"].as_u64().unwrap() >= 1);
+    assert!(backends[1]["last_error"].is_string());
+
+    // And: The proxy is ready with one healthy backend
+    let ready = client.get(format!("{}/health/ready", server.url())).send().await.unwrap();
+    assert_eq!(ready.status(), 200);
+}
@@ -0,0 +1,300 @@
+//! Integration tests for listeners: route groups, per-listener auth, TLS and bind addresses
+
+mod common;
+
+use common::*;
+use only1mcp::config::{ListenerConfig, PolicyConfig, RouteGroup, TlsConfig};
+use serde_json::json;
+
+/// A free TCP port on `host`
+fn free_port(host: &str) -> u16 {
+    std::net::TcpListener::bind((host, 0)).unwrap().local_addr().unwrap().port()
+}
+
+/// A plaintext listener without auth serving `routes`
+fn listener(name: &str, bind: String, routes: Vec<RouteGroup>) -> ListenerConfig {
+    ListenerConfig {
+        name: name.to_string(),
+        bind,
+        routes,
+        tls: Default::default(),
+        auth: None,
+        ipv6_only: false,
+    }
+}
+
+/// Certificate fixtures signed by the test CA
+fn tls_fixture(name: &str) -> std::path::PathBuf {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/tls")
+        .join(name)
+}
+
+/// Listener TLS serving the `localhost` certificate of the test CA
+fn server_tls() -> TlsConfig {
+    TlsConfig {
+        enabled: true,
+        cert_path: Some(tls_fixture("cert-a.pem")),
+        key_path: Some(tls_fixture("key-a.pem")),
+        ..Default::default()
+    }
+}
+
+/// HTTPS client trusting the test CA, presenting `identity` if given
+fn https_client(identity: Option<reqwest::Identity>) -> reqwest::Client {
+    let ca = std::fs::read(tls_fixture("ca.pem")).unwrap();
+    let mut builder = reqwest::Client::builder()
+        .use_rustls_tls()
+        .add_root_certificate(reqwest::Certificate::from_pem(&ca).unwrap());
+    if let Some(identity) = identity {
+        builder = builder.identity(identity);
+    }
+    builder.build().unwrap()
+}
+
+/// A backend offering one GitHub and one filesystem tool
+async fn role_backend() -> wiremock::MockServer {
+    mock_backend(vec![
+        sample_tool("github.search", "Search GitHub"),
+        sample_tool("filesystem.read", "Read a file"),
+    ])
+    .await
+}
+
+/// Policy limiting the "analyst" role to GitHub tools
+fn analyst_policy() -> PolicyConfig {
+    PolicyConfig {
+        role: "analyst".to_string(),
+        allow: vec!["github.*".parse().unwrap()],
+        deny: Vec::new(),
+    }
+}
+
+/// Names of the tools in a tools/list response
+fn tool_names(list: &serde_json::Value) -> Vec<&str> {
+    list["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tool| tool["name"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_listeners_separate_mcp_and_admin_routes() {
+    use only1mcp::config::ListenerAuthConfig;
+
+    let (mcp_port, admin_port) = (free_port("127.0.0.1"), free_port("127.0.0.1"));
+
+    // Given: MCP on one port, admin and metrics on another behind an API key
+    let mut config = test_config();
+    let mut admin_listener = listener(
+        "admin",
+        format!("127.0.0.1:{}", admin_port),
+        vec![RouteGroup::Admin, RouteGroup::Metrics],
+    );
+    admin_listener.auth = Some(ListenerAuthConfig {
+        api_keys: vec!["s3cret".into()],
+        jwt: None,
+    });
+    config.server.listeners = vec![
+        listener(
+            "mcp",
+            format!("127.0.0.1:{}", mcp_port),
+            vec![RouteGroup::Mcp],
+        ),
+        admin_listener,
+    ];
+    let _server = start_test_server(config).await;
+    let client = test_client();
+    let mcp = format!("http://127.0.0.1:{}", mcp_port);
+    let admin = format!("http://127.0.0.1:{}", admin_port);
+    let initialize = json!({"jsonrpc": "2.0", "id": 1, "method": "initialize"});
+
+    // Then: MCP requests are served on the MCP listener only
+    let response = client.post(format!("{}/mcp", mcp)).json(&initialize).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    let response = client.get(format!("{}/api/v1/admin/servers", mcp)).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+
+    // And: The admin listener requires its API key
+    let response = client.get(format!("{}/api/v1/admin/servers", admin)).send().await.unwrap();
+    assert_eq!(response.status(), 401);
+    let response = client
+        .get(format!("{}/api/v1/admin/servers", admin))
+        .bearer_auth("s3cret")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let response = client
+        .get(format!("{}/metrics", admin))
+        .header("X-API-Key", "s3cret")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let response = client
+        .post(format!("{}/mcp", admin))
+        .header("X-API-Key", "s3cret")
+        .json(&initialize)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_policies_restrict_tools_by_role() {
+    use only1mcp::config::{ApiKeyConfig, ListenerAuthConfig};
+
+    // Given: A backend offering GitHub and filesystem tools
+    let backend = role_backend().await;
+
+    // And: An "analyst" role that may only use GitHub tools
+    let port = free_port("127.0.0.1");
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    let mut mcp = listener("mcp", format!("127.0.0.1:{}", port), vec![RouteGroup::Mcp]);
+    mcp.auth = Some(ListenerAuthConfig {
+        api_keys: vec![ApiKeyConfig::WithRoles {
+            key: "analyst-key".to_string(),
+            roles: vec!["analyst".to_string()],
+        }],
+        jwt: None,
+    });
+    config.server.listeners = vec![mcp];
+    config.auth.policies = vec![analyst_policy()];
+    let _server = start_test_server(config).await;
+    let call = |method: &str, params: serde_json::Value| {
+        test_client()
+            .post(format!("http://127.0.0.1:{}/mcp", port))
+            .bearer_auth("analyst-key")
+            .json(&json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params}))
+            .send()
+    };
+
+    // When: The analyst lists tools
+    let list: serde_json::Value =
+        call("tools/list", json!({})).await.unwrap().json().await.unwrap();
+
+    // Then: Only the GitHub tool is visible
+    assert_eq!(tool_names(&list), vec!["github.search"], "{}", list);
+
+    // And: Calling a filesystem tool is forbidden
+    let body: serde_json::Value = call(
+        "tools/call",
+        json!({"name": "filesystem.read", "arguments": {}}),
+    )
+    .await
+    .unwrap()
+    .json()
+    .await
+    .unwrap();
+    assert!(
+        body["error"]["message"].as_str().unwrap_or_default().contains("Not allowed"),
+        "{}",
+        body
+    );
+}
+
+#[tokio::test]
+async fn test_tls_listener_serves_https() {
+    let port = free_port("127.0.0.1");
+
+    // Given: An MCP listener terminating TLS with a certificate from the test CA
+    let mut config = test_config();
+    let mut mcp = listener("mcp", format!("127.0.0.1:{}", port), vec![RouteGroup::Mcp]);
+    mcp.tls = server_tls();
+    config.server.listeners = vec![mcp];
+    let _server = start_test_server(config).await;
+    let initialize = json!({"jsonrpc": "2.0", "id": 1, "method": "initialize"});
+
+    // When: A client trusting the test CA connects over HTTPS
+    let response = https_client(None)
+        .post(format!("https://localhost:{}/mcp", port))
+        .json(&initialize)
+        .send()
+        .await
+        .unwrap();
+
+    // Then: The request is served
+    assert_eq!(response.status(), 200);
+
+    // And: Plaintext HTTP is not
+    assert!(test_client()
+        .post(format!("http://127.0.0.1:{}/mcp", port))
+        .json(&initialize)
+        .send()
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_mtls_client_certificate_maps_to_roles() {
+    // Given: A backend offering GitHub and filesystem tools
+    let backend = role_backend().await;
+
+    // And: A listener requiring client certificates, mapping the
+    // "analyst-bot" certificate to a role limited to GitHub tools
+    let port = free_port("127.0.0.1");
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    let mut mcp = listener("mcp", format!("127.0.0.1:{}", port), vec![RouteGroup::Mcp]);
+    mcp.tls = TlsConfig {
+        client_ca_path: Some(tls_fixture("ca.pem")),
+        client_roles: [("analyst-bot".to_string(), vec!["analyst".to_string()])].into(),
+        ..server_tls()
+    };
+    config.server.listeners = vec![mcp];
+    config.auth.policies = vec![analyst_policy()];
+    let _server = start_test_server(config).await;
+    let list = json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"});
+    let url = format!("https://localhost:{}/mcp", port);
+
+    // When: A client without a certificate connects
+    // Then: The handshake is refused
+    assert!(https_client(None).post(&url).json(&list).send().await.is_err());
+
+    // When: The analyst bot lists tools with its certificate
+    let mut pem = std::fs::read(tls_fixture("client.pem")).unwrap();
+    pem.extend(std::fs::read(tls_fixture("client-key.pem")).unwrap());
+    let bot = https_client(Some(reqwest::Identity::from_pem(&pem).unwrap()));
+    let response: serde_json::Value =
+        bot.post(&url).json(&list).send().await.unwrap().json().await.unwrap();
+
+    // Then: Only the tools its role allows are listed
+    assert_eq!(tool_names(&response), vec!["github.search"], "{}", response);
+}
+
+#[tokio::test]
+async fn test_listeners_on_ipv6_and_dual_stack_addresses() {
+    // Given: A dual-stack listener and an IPv6-only one, both on [::]
+    let (dual_port, v6_port) = (free_port("::"), free_port("::"));
+    let mut config = test_config();
+    config.server.listeners = vec![
+        listener("dual", format!("[::]:{}", dual_port), vec![RouteGroup::Mcp]),
+        ListenerConfig {
+            ipv6_only: true,
+            ..listener("v6", format!("[::]:{}", v6_port), vec![RouteGroup::Mcp])
+        },
+    ];
+    let _server = start_test_server(config).await;
+    let client = test_client();
+    let initialize = |url: String| {
+        let request = client
+            .post(url)
+            .json(&json!({"jsonrpc": "2.0", "id": 1, "method": "initialize"}));
+        async move { request.send().await.map(|response| response.status()) }
+    };
+
+    // Then: The dual-stack listener accepts IPv4 and IPv6 clients
+    for host in ["127.0.0.1", "[::1]"] {
+        let status = initialize(format!("http://{}:{}/mcp", host, dual_port)).await;
+        assert_eq!(status.unwrap(), 200, "{}", host);
+    }
+
+    // And: The IPv6-only listener accepts IPv6 clients only
+    let status = initialize(format!("http://[::1]:{}/mcp", v6_port)).await;
+    assert_eq!(status.unwrap(), 200);
+    let status = initialize(format!("http://127.0.0.1:{}/mcp", v6_port)).await;
+    assert!(status.is_err(), "{:?}", status);
+}
@@ -0,0 +1,170 @@
+//! End-to-end load tests with reproducible scenarios
+//!
+//! Each scenario starts N mock backends with seeded latency distributions,
+//! drives the proxy at a target request rate using the built-in benchmark
+//! engine (`only1mcp::benchmark`), and asserts SLO-style thresholds.
+//!
+//! The scenarios are slow and timing-sensitive, so they are ignored by
+//! default. Run them in release mode before cutting a release:
+//!
+//! ```text
+//! cargo test --release --test load -- --ignored --test-threads=1
+//! ```
+
+#[allow(dead_code)]
+#[path = "../common/mod.rs"]
+mod common;
+mod mock_backend;
+
+use common::{start_test_server, test_config_with_backends};
+use mock_backend::LatencyProfile;
+use only1mcp::benchmark::{self, BenchmarkConfig, BenchmarkReport};
+use std::time::Duration;
+
+/// SLO thresholds a scenario must meet.
+struct Slo {
+    p99: Duration,
+    max_errors: usize,
+}
+
+/// A reproducible load scenario.
+struct Scenario {
+    name: &'static str,
+    backends: Vec<LatencyProfile>,
+    cache: bool,
+    requests: usize,
+    concurrency: usize,
+    target_rps: f64,
+    slo: Slo,
+}
+
+impl Scenario {
+    /// Run the scenario and assert its SLO.
+    async fn run(self) -> BenchmarkReport {
+        let mut backends = Vec::new();
+        for (i, profile) in self.backends.iter().enumerate() {
+            backends.push(mock_backend::spawn(&format!("b{}", i), *profile, 42 + i as u64).await);
+        }
+
+        let mut config =
+            test_config_with_backends(0, backends.iter().map(|b| b.url.clone()).collect());
+        config.context_optimization.cache.enabled = self.cache;
+        let server = start_test_server(config).await;
+
+        let bench = BenchmarkConfig {
+            url: format!("{}/mcp", server.url()),
+            requests: self.requests,
+            concurrency: self.concurrency,
+            target_rps: Some(self.target_rps),
+            method: "tools/list".to_string(),
+            timeout: Duration::from_secs(10),
+            ..Default::default()
+        };
+
+        // Warm up connection pools and caches outside the measured window
+        benchmark::run(BenchmarkConfig {
+            requests: self.concurrency,
+            target_rps: None,
+            ..bench.clone()
+        })
+        .await
+        .expect("warm-up run failed");
+
+        let report = benchmark::run(bench).await.expect("benchmark run failed");
+
+        println!("== {}\n{}", self.name, report);
+
+        assert!(
+            report.errors <= self.slo.max_errors,
+            "{}: {} errors (max {})",
+            self.name,
+            report.errors,
+            self.slo.max_errors
+        );
+        assert!(
+            report.latency.p99 < self.slo.p99,
+            "{}: p99 {:?} exceeds SLO {:?}",
+            self.name,
+            report.latency.p99,
+            self.slo.p99
+        );
+
+        report
+    }
+}
+
+fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[ignore = "load test; run with --ignored"]
+async fn load_fanout_uniform_latency() {
+    Scenario {
+        name: "fan-out to 3 backends, uniform 5-15ms",
+        backends: vec![
+            LatencyProfile::Uniform {
+                min: ms(5),
+                max: ms(15),
+            };
+            3
+        ],
+        cache: false,
+        requests: 1_000,
+        concurrency: 32,
+        target_rps: 200.0,
+        slo: Slo {
+            p99: ms(150),
+            max_errors: 0,
+        },
+    }
+    .run()
+    .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[ignore = "load test; run with --ignored"]
+async fn load_fanout_with_long_tail_backend() {
+    Scenario {
+        name: "fan-out to 4 backends, one with a 5% 80ms tail",
+        backends: vec![
+            LatencyProfile::Constant(ms(5)),
+            LatencyProfile::Constant(ms(5)),
+            LatencyProfile::Constant(ms(5)),
+            LatencyProfile::LongTail {
+                base: ms(5),
+                tail: ms(80),
+                tail_ratio: 0.05,
+            },
+        ],
+        cache: false,
+        requests: 1_000,
+        concurrency: 32,
+        target_rps: 200.0,
+        slo: Slo {
+            p99: ms(300),
+            max_errors: 0,
+        },
+    }
+    .run()
+    .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[ignore = "load test; run with --ignored"]
+async fn load_cached_hot_path() {
+    Scenario {
+        name: "cached tools/list, 2 backends at 20ms",
+        backends: vec![LatencyProfile::Constant(ms(20)); 2],
+        cache: true,
+        requests: 5_000,
+        concurrency: 64,
+        target_rps: 1_000.0,
+        slo: Slo {
+            p99: ms(50),
+            max_errors: 0,
+        },
+    }
+    .run()
+    .await;
+}
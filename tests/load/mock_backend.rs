@@ -0,0 +1,112 @@
+//! Mock MCP backends with configurable, seeded latency distributions.
+
+use axum::{extract::State, routing::post, Json, Router};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Response latency distribution of a mock backend.
+#[derive(Debug, Clone, Copy)]
+pub enum LatencyProfile {
+    /// Every response takes exactly this long
+    Constant(Duration),
+    /// Uniformly distributed between `min` and `max`
+    Uniform { min: Duration, max: Duration },
+    /// Mostly `base`, but a `tail_ratio` fraction of responses take `tail`
+    LongTail {
+        base: Duration,
+        tail: Duration,
+        tail_ratio: f64,
+    },
+}
+
+impl LatencyProfile {
+    fn sample(&self, rng: &mut StdRng) -> Duration {
+        match *self {
+            LatencyProfile::Constant(delay) => delay,
+            LatencyProfile::Uniform { min, max } => {
+                Duration::from_micros(rng.gen_range(min.as_micros()..=max.as_micros()) as u64)
+            },
+            LatencyProfile::LongTail {
+                base,
+                tail,
+                tail_ratio,
+            } => {
+                if rng.gen_bool(tail_ratio) {
+                    tail
+                } else {
+                    base
+                }
+            },
+        }
+    }
+}
+
+#[derive(Clone)]
+struct BackendState {
+    name: String,
+    profile: LatencyProfile,
+    rng: Arc<Mutex<StdRng>>,
+}
+
+/// A running mock backend.
+pub struct MockBackend {
+    pub url: String,
+    handle: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for MockBackend {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// Spawn a backend serving five tools with the given latency.
+///
+/// The RNG is seeded so the latency sequence is identical across runs.
+pub async fn spawn(name: &str, profile: LatencyProfile, seed: u64) -> MockBackend {
+    let state = BackendState {
+        name: name.to_string(),
+        profile,
+        rng: Arc::new(Mutex::new(StdRng::seed_from_u64(seed))),
+    };
+
+    let router = Router::new()
+        .route("/", post(handle))
+        .route("/health", axum::routing::get(|| async { "ok" }))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = tokio::spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+
+    MockBackend { url, handle }
+}
+
+async fn handle(State(state): State<BackendState>, Json(request): Json<Value>) -> Json<Value> {
+    let delay = state.profile.sample(&mut state.rng.lock().unwrap());
+    tokio::time::sleep(delay).await;
+
+    let result = match request.get("method").and_then(|m| m.as_str()) {
+        Some("tools/list") => json!({
+            "tools": (0..5).map(|i| json!({
+                "name": format!("{}_tool_{}", state.name, i),
+                "description": "Load test tool",
+                "inputSchema": {"type": "object"}
+            })).collect::<Vec<_>>()
+        }),
+        Some("resources/list") => json!({"resources": []}),
+        Some("prompts/list") => json!({"prompts": []}),
+        _ => json!({}),
+    };
+
+    Json(json!({
+        "jsonrpc": "2.0",
+        "id": request.get("id").cloned().unwrap_or(Value::Null),
+        "result": result
+    }))
+}
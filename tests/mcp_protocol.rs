@@ -0,0 +1,244 @@
+//! Integration tests for JSON-RPC handling on the MCP endpoint
+
+mod common;
+
+use common::*;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_jsonrpc_batch_preserves_order_and_skips_notifications() {
+    // Given: A running server with no backends
+    let config = test_config();
+    let server = start_test_server(config).await;
+
+    // When: A batch mixing requests, a notification and an invalid entry is sent
+    let client = test_client();
+    let response = client
+        .post(format!("{}/mcp", server.url()))
+        .json(&json!([
+            {"jsonrpc": "2.0", "id": 1, "method": "tools/list"},
+            {"jsonrpc": "2.0", "method": "notifications/tools/list_changed"},
+            42,
+            {"jsonrpc": "2.0", "id": "b", "method": "tools/list"}
+        ]))
+        .send()
+        .await
+        .expect("Failed to send request");
+
+    // Then: One response per non-notification entry, in request order
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.expect("Failed to parse JSON");
+    let entries = body.as_array().expect("Batch response should be an array");
+
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0]["id"], 1);
+    assert_eq!(entries[1]["id"], serde_json::Value::Null);
+    assert_eq!(entries[2]["id"], "b");
+    for entry in entries {
+        assert!(entry.get("error").is_some(), "Expected error: {:?}", entry);
+    }
+}
+
+#[tokio::test]
+async fn test_jsonrpc_batch_of_notifications_returns_no_body() {
+    // Given: A running server
+    let config = test_config();
+    let server = start_test_server(config).await;
+    let client = test_client();
+
+    // When: A batch containing only notifications is sent
+    let response = client
+        .post(format!("{}/", server.url()))
+        .json(&json!([
+            {"jsonrpc": "2.0", "method": "notifications/tools/list_changed"}
+        ]))
+        .send()
+        .await
+        .expect("Failed to send request");
+
+    // Then: The request is accepted without a response body
+    assert_eq!(response.status(), 202);
+    assert!(response.text().await.unwrap().is_empty());
+
+    // And: An empty batch is rejected as an invalid request
+    let response = client
+        .post(format!("{}/", server.url()))
+        .json(&json!([]))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn test_initialize_advertises_aggregated_capabilities() {
+    use wiremock::{matchers::body_partial_json, Mock, ResponseTemplate};
+
+    // Given: Two backends with different capability sets
+    let initialize = |capabilities: serde_json::Value| {
+        ResponseTemplate::new(200).set_body_json(json!({"jsonrpc": "2.0", "id": 0, "result": {
+            "protocolVersion": "2024-11-05",
+            "capabilities": capabilities,
+            "serverInfo": {"name": "mock", "version": "1.0"}
+        }}))
+    };
+    let tools = mock_backend(vec![]).await;
+    Mock::given(body_partial_json(json!({"method": "initialize"})))
+        .respond_with(initialize(json!({"tools": {"listChanged": true}})))
+        .mount(&tools)
+        .await;
+    let resources = mock_backend(vec![]).await;
+    Mock::given(body_partial_json(json!({"method": "initialize"})))
+        .respond_with(initialize(
+            json!({"resources": {"subscribe": true}, "logging": {}}),
+        ))
+        .mount(&resources)
+        .await;
+
+    let config = test_config_with_backends(0, vec![tools.uri(), resources.uri()]);
+    let server = start_test_server(config).await;
+
+    // When: A client initializes with a protocol version the proxy speaks
+    let body = server
+        .rpc(
+            "initialize",
+            json!({
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": {"name": "test", "version": "0.1"}
+            }),
+        )
+        .await;
+
+    // Then: The proxy answers itself with the union of backend capabilities
+    let result = &body["result"];
+    assert_eq!(result["protocolVersion"], "2025-03-26");
+    assert_eq!(result["serverInfo"]["name"], "Only1MCP");
+    assert_eq!(
+        result["capabilities"],
+        json!({"tools": {}, "resources": {"subscribe": true}, "logging": {}})
+    );
+
+    // And: The initialized notification is accepted without a body
+    let response = test_client()
+        .post(format!("{}/mcp", server.url()))
+        .json(&json!({"jsonrpc": "2.0", "method": "notifications/initialized"}))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 202);
+    assert!(response.text().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_list_requests_skip_backends_without_capability() {
+    use wiremock::{
+        matchers::{body_partial_json, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let reply = |result: serde_json::Value| {
+        ResponseTemplate::new(200)
+            .set_body_json(json!({"jsonrpc": "2.0", "id": 0, "result": result}))
+    };
+    let on = |method: &str| body_partial_json(json!({"method": method}));
+    let unexpected =
+        |method: &str| Mock::given(on(method)).respond_with(ResponseTemplate::new(500)).expect(0);
+
+    // Given: A tools-only backend and a prompts-only backend
+    let tools = mock_backend(vec![sample_tool("search", "Search")]).await;
+    Mock::given(on("initialize"))
+        .respond_with(reply(
+            json!({"protocolVersion": "2024-11-05", "capabilities": {"tools": {}}}),
+        ))
+        .mount(&tools)
+        .await;
+    unexpected("prompts/list").mount(&tools).await;
+
+    let prompts = MockServer::start().await;
+    Mock::given(path("/health"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&prompts)
+        .await;
+    Mock::given(on("initialize"))
+        .respond_with(reply(
+            json!({"protocolVersion": "2024-11-05", "capabilities": {"prompts": {}}}),
+        ))
+        .mount(&prompts)
+        .await;
+    Mock::given(on("prompts/list"))
+        .respond_with(reply(json!({"prompts": [{"name": "summarize"}]})))
+        .mount(&prompts)
+        .await;
+    unexpected("tools/list").mount(&prompts).await;
+
+    let config = test_config_with_backends(0, vec![tools.uri(), prompts.uri()]);
+    let server = start_test_server(config).await;
+
+    // When: Capabilities are learned, then each list is requested
+    server.rpc("initialize", json!({})).await;
+    let tool_list = server.rpc("tools/list", json!({})).await;
+    let prompt_list = server.rpc("prompts/list", json!({})).await;
+
+    // Then: Each list comes from the backend offering it, without errors
+    assert_eq!(tool_list["result"]["tools"][0]["name"], "search");
+    assert!(tool_list["result"].get("_meta").is_none(), "{}", tool_list);
+    assert_eq!(prompt_list["result"]["prompts"][0]["name"], "summarize");
+
+    // And: Neither backend was asked for a surface it does not offer
+    tools.verify().await;
+    prompts.verify().await;
+}
+
+#[tokio::test]
+async fn test_payload_size_limits() {
+    use wiremock::{matchers::body_partial_json, Mock, ResponseTemplate};
+
+    let read_result = |uri: &str, text: String| {
+        ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0", "id": 1, "result": {"contents": [{"uri": uri, "text": text}]}
+        }))
+    };
+
+    // Given: A backend whose answer to one resource is far over the limit
+    let backend = mock_backend(vec![]).await;
+    Mock::given(body_partial_json(
+        json!({"params": {"uri": "file:///huge.log"}}),
+    ))
+    .respond_with(read_result("file:///huge.log", "x".repeat(64 * 1024)))
+    .mount(&backend)
+    .await;
+    Mock::given(body_partial_json(json!({"method": "resources/read"})))
+        .respond_with(read_result("file:///notes.md", "hello".to_string()))
+        .mount(&backend)
+        .await;
+
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.proxy.limits.max_request_bytes = 1024;
+    config.proxy.limits.max_response_bytes = 4096;
+    let server = start_test_server(config).await;
+
+    // When: A request body is over the request limit
+    let response = test_client()
+        .post(format!("{}/mcp", server.url()))
+        .json(&json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call",
+                      "params": {"name": "echo", "arguments": {"text": "x".repeat(2048)}}}))
+        .send()
+        .await
+        .unwrap();
+
+    // Then: It is rejected as an invalid request
+    assert_eq!(response.status(), 413);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["error"]["code"], -32600, "{}", body);
+
+    // When: A backend response is over the response limit
+    let body = server.rpc("resources/read", json!({"uri": "file:///huge.log"})).await;
+
+    // Then: The client gets a response-too-large error
+    assert_eq!(body["error"]["code"], -32006, "{}", body);
+
+    // And: Responses within the limit still pass
+    let body = server.rpc("resources/read", json!({"uri": "file:///notes.md"})).await;
+    assert_eq!(body["result"]["contents"][0]["text"], "hello", "{}", body);
+}
@@ -0,0 +1,283 @@
+//! Integration tests for metrics, request history, alerts, cost reporting and state export
+
+mod common;
+
+use common::*;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_state_export_redacts_and_captures_runtime() {
+    use only1mcp::config::TransportConfig;
+    use only1mcp::proxy::snapshot::StateBundle;
+    use std::time::Duration;
+
+    // Given: A health-checked backend that needs an auth header
+    let backend = mock_backend(vec![sample_tool("echo", "Echo")]).await;
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.servers[0].transport = TransportConfig::Http {
+        url: backend.uri(),
+        headers: [("Authorization".to_string(), "Bearer s3cret".to_string())].into(),
+        oauth: None,
+        tls: None,
+        signing: None,
+        proxy: None,
+    };
+    config.servers[0].health_check.enabled = true;
+    config.servers[0].health_check.interval_seconds = 1;
+    config.servers[0].health_check.healthy_threshold = 1;
+    let server = start_test_server(config).await;
+    let client = test_client();
+
+    // When: Tools were listed and the backend passed a check before export
+    server.rpc("tools/list", json!({})).await;
+    let mut bundle = None;
+    for _ in 0..30 {
+        let exported: StateBundle = client
+            .get(format!("{}/api/v1/admin/state", server.url()))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        if !exported.runtime.as_ref().unwrap().health_history.is_empty() {
+            bundle = Some(exported);
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    // Then: Secrets are redacted and the runtime snapshot is included
+    let bundle = bundle.expect("health transition was not recorded");
+    let dump = serde_json::to_string(&bundle).unwrap();
+    assert!(!dump.contains("s3cret"));
+    assert_eq!(
+        bundle.redacted,
+        vec!["servers[test-backend-0].transport.headers.Authorization"]
+    );
+
+    let runtime = bundle.runtime.as_ref().unwrap();
+    assert_eq!(runtime.servers[0].id, "test-backend-0");
+    assert_eq!(runtime.servers[0].health, "Healthy");
+    assert_eq!(runtime.servers[0].tools, vec!["echo"]);
+    assert_eq!(runtime.health_history[0].to, "Healthy");
+}
+
+#[tokio::test]
+async fn test_tool_calls_are_charged_with_cost_model() {
+    // Given: A backend whose tools cost $0.01 per call, and `render` $0.50
+    let backend = mock_backend(vec![
+        sample_tool("echo", "Echo"),
+        sample_tool("render", "Render"),
+    ])
+    .await;
+    for tool in ["echo", "render"] {
+        mount_tools_call(
+            &backend,
+            tool,
+            json!({"content": [{"type": "text", "text": "done"}]}),
+        )
+        .await;
+    }
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.servers[0].cost =
+        Some(serde_yaml::from_str("per_call: 0.01\ntools: {render: {per_call: 0.5}}").unwrap());
+    let server = start_test_server(config).await;
+
+    // When: Each tool is called after the catalog is loaded
+    server.rpc("tools/list", json!({})).await;
+    for tool in ["echo", "render"] {
+        let body = server.rpc("tools/call", json!({"name": tool, "arguments": {}})).await;
+        assert!(body.get("error").is_none(), "{}", body);
+    }
+
+    // Then: The cost report charges every call with the model
+    let report: serde_json::Value = test_client()
+        .get(format!("{}/api/v1/admin/costs", server.url()))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(report["total_calls"], 2, "{}", report);
+    assert!(
+        (report["total_cost"].as_f64().unwrap() - 0.51).abs() < 1e-9,
+        "{}",
+        report
+    );
+    assert!(report["servers"][0]["tools"][0]["tokens"].as_u64().unwrap() > 0);
+}
+
+#[tokio::test]
+async fn test_request_metrics_carry_server_method_and_status() {
+    // Given: A backend whose `fail` tool returns a JSON-RPC error
+    let backend = mock_backend(vec![
+        sample_tool("echo", "Echo"),
+        sample_tool("fail", "Fail"),
+    ])
+    .await;
+    mount_tools_call(&backend, "echo", json!({"content": []})).await;
+    mount_tools_error(&backend, "fail", "failed").await;
+
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.servers[0].id = "labelled-backend".to_string();
+    let server = start_test_server(config).await;
+
+    // When: Tools are listed and called, and an unknown method is sent
+    server.rpc("tools/list", json!({})).await;
+    server.rpc("tools/call", json!({"name": "echo", "arguments": {}})).await;
+    server.rpc("tools/call", json!({"name": "fail", "arguments": {}})).await;
+    server.rpc("vendor/custom", json!({})).await;
+
+    // Then: Requests are counted per backend, method and status
+    let metrics = test_client()
+        .get(format!("{}/api/v1/admin/metrics", server.url()))
+        .send()
+        .await
+        .expect("Failed to send request")
+        .text()
+        .await
+        .unwrap();
+    let has_series = |labels: &[&str]| {
+        metrics.lines().any(|line| {
+            line.starts_with("only1mcp_mcp_requests_total{")
+                && labels.iter().all(|label| line.contains(label))
+        })
+    };
+    for (server_id, method, status) in [
+        ("labelled-backend", "tools/list", "success"),
+        ("labelled-backend", "tools/call", "success"),
+        ("labelled-backend", "tools/call", "error"),
+        ("proxy", "tools/call", "error"),
+        ("proxy", "other", "error"),
+    ] {
+        let labels = [
+            format!(r#"server_id="{}""#, server_id),
+            format!(r#"method="{}""#, method),
+            format!(r#"status="{}""#, status),
+        ];
+        let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+        assert!(has_series(&labels), "missing {:?}\n{}", labels, metrics);
+    }
+    assert!(metrics.lines().any(|line| {
+        line.starts_with("only1mcp_backend_latency_seconds_count{")
+            && line.contains(r#"endpoint="tools/call""#)
+            && line.contains(r#"server_id="labelled-backend""#)
+    }));
+}
+
+#[tokio::test]
+async fn test_error_rate_alert_fires_webhook() {
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    // Given: A backend answering every request with an error, and a rule
+    // alerting a webhook once more than half of its requests fail
+    let backend = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0", "id": 1, "error": {"code": -32000, "message": "broken"}
+        })))
+        .mount(&backend)
+        .await;
+    Mock::given(path("/health"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&backend)
+        .await;
+    let webhook = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&webhook)
+        .await;
+
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.servers[0].id = "alerting-backend".to_string();
+    config.observability.alerts = serde_yaml::from_str(&format!(
+        "interval_seconds: 1\n\
+         notifiers: [{{name: hook, type: webhook, url: '{}/alerts'}}]\n\
+         rules: [{{name: errors, type: error_rate, above_percent: 50, min_requests: 2, servers: [alerting-backend]}}]\n",
+        webhook.uri()
+    ))
+    .unwrap();
+    let server = start_test_server(config).await;
+
+    // When: Requests to it fail
+    for _ in 0..3 {
+        server.rpc("tools/list", json!({})).await;
+    }
+
+    // Then: The webhook is told the alert is firing
+    let mut events = Vec::new();
+    for _ in 0..50 {
+        events = webhook.received_requests().await.unwrap();
+        if !events.is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    let event: serde_json::Value = events.first().expect("no alert sent").body_json().unwrap();
+    assert_eq!(event["rule"], "errors");
+    assert_eq!(event["subject"], "alerting-backend");
+    assert_eq!(event["state"], "firing");
+
+    // And: The admin API lists it
+    let active: serde_json::Value = test_client()
+        .get(format!("{}/api/v1/admin/alerts", server.url()))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(active[0]["rule"], "errors", "{}", active);
+}
+
+#[tokio::test]
+async fn test_request_history_lists_recent_requests() {
+    // Given: A backend whose `fail` tool returns a JSON-RPC error
+    let backend = mock_backend(vec![sample_tool("fail", "Fail")]).await;
+    mount_tools_error(&backend, "fail", "failed").await;
+
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.servers[0].id = "history-backend".to_string();
+    let server = start_test_server(config).await;
+    let client = test_client();
+
+    // When: A client lists tools and calls one within a session
+    for (method, params) in [
+        ("tools/list", json!({})),
+        ("tools/call", json!({"name": "fail", "arguments": {}})),
+    ] {
+        client
+            .post(format!("{}/mcp", server.url()))
+            .header("x-client-id", "ide")
+            .header("mcp-session-id", "session-1")
+            .json(&json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params}))
+            .send()
+            .await
+            .expect("Failed to send request");
+    }
+
+    // Then: Both are listed newest first with their backend, caller and outcome
+    let requests: Vec<serde_json::Value> = client
+        .get(format!("{}/api/v1/admin/requests?client=ide", server.url()))
+        .send()
+        .await
+        .expect("Failed to send request")
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(requests.len(), 2, "{:?}", requests);
+    assert_eq!(requests[0]["method"], "tools/call");
+    assert_eq!(requests[0]["tool"], "fail");
+    assert_eq!(requests[0]["servers"], json!(["history-backend"]));
+    assert_eq!(requests[0]["status"], "error");
+    assert_eq!(requests[0]["error"], "failed");
+    assert_eq!(requests[0]["session"], "session-1");
+    assert_eq!(requests[1]["method"], "tools/list");
+    assert_eq!(requests[1]["status"], "success");
+}
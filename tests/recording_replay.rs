@@ -0,0 +1,141 @@
+//! Integration tests for recording, replay, dry-run fixtures and the mock MCP server
+
+mod common;
+
+use common::*;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_recorded_session_replays_and_detects_changes() {
+    use only1mcp::proxy::recording::read_recording;
+    use only1mcp::replay::{replay, ReplayOptions};
+    use wiremock::{matchers::body_partial_json, Mock, ResponseTemplate};
+
+    let reply = |text: &str| json!({"content": [{"type": "text", "text": text}]});
+
+    // Given: A proxy recording its traffic in front of one backend
+    let backend = mock_backend(vec![sample_tool("echo", "Echo")]).await;
+    mount_tools_call(&backend, "echo", reply("3 issues")).await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let recording = dir.path().join("session.jsonl");
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.observability.recording.enabled = true;
+    config.observability.recording.path = recording.clone();
+    let server = start_test_server(config).await;
+
+    // When: A client lists tools and calls one with a credential
+    server.rpc("tools/list", json!({})).await;
+    server
+        .rpc(
+            "tools/call",
+            json!({"name": "echo", "arguments": {"api_key": "k-123"}}),
+        )
+        .await;
+
+    // Then: Both exchanges are recorded with the credential redacted
+    let exchanges = read_recording(&recording).unwrap();
+    assert_eq!(exchanges.len(), 2);
+    assert_eq!(exchanges[1].method, "tools/call");
+    assert_eq!(
+        exchanges[1].request["params"]["arguments"]["api_key"],
+        "[REDACTED]"
+    );
+    assert_eq!(
+        exchanges[1].response.as_ref().unwrap()["result"]["content"][0]["text"],
+        "3 issues"
+    );
+
+    // And: Replaying against the unchanged backend matches
+    let options = ReplayOptions {
+        url: format!("{}/mcp", server.url()),
+        ..Default::default()
+    };
+    let report = replay(&exchanges, &options).await.unwrap();
+    assert!(report.is_clean(), "{}", report);
+
+    // And: A changed reply is reported
+    Mock::given(body_partial_json(json!({"method": "tools/call"})))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"jsonrpc": "2.0", "id": 1, "result": reply("4 issues")})),
+        )
+        .with_priority(1)
+        .mount(&backend)
+        .await;
+    let report = replay(&exchanges, &options).await.unwrap();
+    assert!(!report.is_clean(), "{}", report);
+}
+
+#[tokio::test]
+async fn test_dry_run_answers_from_fixtures() {
+    use only1mcp::proxy::dry_run::Fixtures;
+
+    // Given: A proxy in dry-run mode with a fixture catalog and no backends
+    let fixtures: Fixtures = serde_yaml::from_str(
+        r#"
+tools:
+  - name: search_repos
+    description: Search repositories
+    inputSchema: {type: object}
+    responses:
+      - arguments: {query: rust}
+        result: {content: [{type: text, text: "3 repositories"}]}
+"#,
+    )
+    .unwrap();
+    let config = test_config_with_backends(0, vec![]);
+    let server = start_test_server_with(config, |server| server.with_dry_run(fixtures)).await;
+
+    // When: A client lists and calls tools
+    let list = server.rpc("tools/list", json!({})).await;
+    let call = server
+        .rpc(
+            "tools/call",
+            json!({"name": "search_repos", "arguments": {"query": "rust"}}),
+        )
+        .await;
+
+    // Then: The fixture catalog and canned reply are returned
+    assert_eq!(list["result"]["tools"][0]["name"], "search_repos");
+    assert_eq!(call["result"]["content"][0]["text"], "3 repositories");
+
+    // And: The proxy reports ready without any backend
+    let ready = test_client()
+        .get(format!("{}/health/ready", server.url()))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert!(ready.status().is_success());
+}
+
+#[tokio::test]
+async fn test_proxy_forwards_to_mock_server() {
+    use only1mcp::mock::{echo_fixtures, MockOptions, MockServer};
+
+    // Given: A mock MCP server serving the echo tool, behind the proxy
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mock = MockServer::new(MockOptions {
+        fixtures: echo_fixtures(),
+        ..Default::default()
+    });
+    tokio::spawn(async move { axum::serve(listener, mock.router()).await });
+    let config = test_config_with_backends(0, vec![format!("http://{}/mcp", addr)]);
+    let server = start_test_server(config).await;
+
+    // When: A client calls the echo tool through the proxy
+    let call = server
+        .rpc(
+            "tools/call",
+            json!({"name": "echo", "arguments": {"message": "hi"}}),
+        )
+        .await;
+
+    // Then: The mock server's echo comes back
+    assert_eq!(
+        call["result"]["content"][0]["text"], r#"{"message":"hi"}"#,
+        "unexpected reply: {}",
+        call
+    );
+}
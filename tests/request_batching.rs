@@ -28,6 +28,7 @@ fn test_config(window_ms: u64, max_batch_size: usize) -> BatchingConfig {
             "resources/list".to_string(),
             "prompts/list".to_string(),
         ],
+        adaptive: Default::default(),
     }
 }
 
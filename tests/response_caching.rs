@@ -1,6 +1,10 @@
 //! Comprehensive tests for response caching with TTL and LRU eviction.
 
-use only1mcp::cache::{CacheConfig, LayeredCache};
+mod common;
+
+use common::*;
+use only1mcp::cache::{CacheConfig, LayeredCache, TokenSources};
+use serde_json::json;
 use std::time::Duration;
 use tokio::time::sleep;
 
@@ -20,7 +24,7 @@ async fn test_cache_hit_and_miss() {
 
     // Get should now be a hit
     let result = cache.get(&key).await;
-    assert_eq!(result, Some(value));
+    assert_eq!(result.as_deref(), Some(&value[..]));
 
     // Stats should reflect 1 hit and 1 miss
     let stats = cache.stats().await;
@@ -34,11 +38,19 @@ async fn test_ttl_expiry() {
     let config = CacheConfig {
         enabled: true,
         l1_capacity: 100,
+        l1_max_bytes: None,
         l1_ttl: Duration::from_millis(100), // Very short TTL for testing
         l2_capacity: 50,
+        l2_max_bytes: None,
         l2_ttl: Duration::from_secs(1800),
         l3_capacity: 20,
+        l3_max_bytes: None,
         l3_ttl: Duration::from_secs(7200),
+        method_ttls: Default::default(),
+        stale_grace: Duration::ZERO,
+        l1_compression: None,
+        l2_compression: None,
+        l3_compression: None,
     };
     let cache = LayeredCache::new(config);
 
@@ -49,7 +61,7 @@ async fn test_ttl_expiry() {
 
     // Should be retrievable immediately
     let result = cache.get(&key).await;
-    assert_eq!(result, Some(value));
+    assert_eq!(result.as_deref(), Some(&value[..]));
 
     // Wait for TTL to expire
     sleep(Duration::from_millis(150)).await;
@@ -64,11 +76,19 @@ async fn test_lru_eviction() {
     let config = CacheConfig {
         enabled: true,
         l1_capacity: 3, // Very small capacity
+        l1_max_bytes: None,
         l1_ttl: Duration::from_secs(300),
         l2_capacity: 50,
+        l2_max_bytes: None,
         l2_ttl: Duration::from_secs(1800),
         l3_capacity: 20,
+        l3_max_bytes: None,
         l3_ttl: Duration::from_secs(7200),
+        method_ttls: Default::default(),
+        stale_grace: Duration::ZERO,
+        l1_compression: None,
+        l2_compression: None,
+        l3_compression: None,
     };
     let cache = LayeredCache::new(config);
 
@@ -129,6 +149,97 @@ async fn test_cache_invalidation() {
     assert!(cache.get("key3").await.is_some());
 }
 
+#[tokio::test]
+async fn test_content_keys_invalidated_by_prefix() {
+    let cache = LayeredCache::new(CacheConfig::default());
+    let read = |uri: &str, range: u64| {
+        LayeredCache::content_key(
+            "resources/read",
+            uri,
+            &serde_json::json!({"uri": uri, "range": range}),
+        )
+    };
+
+    // Reads of one resource with different params are cached separately
+    assert_ne!(read("file:///a", 1), read("file:///a", 2));
+    cache.set(read("file:///a", 1), vec![1], "resources/read").await;
+    cache.set(read("file:///a", 2), vec![2], "resources/read").await;
+    cache.set(read("file:///ab", 1), vec![3], "resources/read").await;
+
+    // Dropping one resource's reads leaves other resources cached
+    cache.invalidate_prefix("resources/read:file:///a#");
+    assert_eq!(cache.get(&read("file:///a", 1)).await, None);
+    assert_eq!(cache.get(&read("file:///a", 2)).await, None);
+    assert!(cache.get(&read("file:///ab", 1)).await.is_some());
+}
+
+#[tokio::test]
+async fn test_method_ttl_overrides_tier_ttl() {
+    let config = CacheConfig {
+        method_ttls: [("prompts/get".to_string(), Duration::from_millis(100))].into(),
+        ..Default::default()
+    };
+    let cache = LayeredCache::new(config);
+
+    cache.set("prompt".to_string(), vec![1], "prompts/get").await;
+    cache.set("prompts".to_string(), vec![2], "prompts/list").await;
+
+    // prompts/get expires on its own TTL, the rest of its tier does not
+    sleep(Duration::from_millis(150)).await;
+    assert_eq!(cache.get("prompt").await, None);
+    assert!(cache.get("prompts").await.is_some());
+}
+
+#[tokio::test]
+async fn test_stale_entries_are_served_while_one_refresh_runs() {
+    let config = CacheConfig {
+        method_ttls: [("tools/list".to_string(), Duration::from_millis(50))].into(),
+        stale_grace: Duration::from_millis(500),
+        ..Default::default()
+    };
+    let cache = std::sync::Arc::new(LayeredCache::new(config));
+    let refreshes = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let (release, released) = tokio::sync::watch::channel(false);
+
+    cache.set("tools".to_string(), vec![1], "tools/list").await;
+    sleep(Duration::from_millis(80)).await;
+
+    // Plain lookups treat the stale entry as expired
+    assert_eq!(cache.get("tools").await, None);
+
+    // Concurrent lookups get the stale value and start a single refresh
+    for _ in 0..5 {
+        let (cache, refreshes, mut released) = (cache.clone(), refreshes.clone(), released.clone());
+        let (stale, _) = cache
+            .clone()
+            .get_or_revalidate("tools", move || {
+                refreshes.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                async move {
+                    let _ = released.wait_for(|done| *done).await;
+                    cache.set("tools".to_string(), vec![2], "tools/list").await;
+                }
+            })
+            .await
+            .unwrap();
+        assert_eq!(&stale[..], &[1][..]);
+    }
+    assert_eq!(refreshes.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+    // Once the refresh lands the fresh value is served
+    release.send(true).unwrap();
+    for _ in 0..50 {
+        if cache.get("tools").await.is_some() {
+            break;
+        }
+        sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(cache.get("tools").await.as_deref(), Some(&[2][..]));
+
+    // Past the grace period the entry is gone
+    sleep(Duration::from_millis(600)).await;
+    assert_eq!(cache.get_or_revalidate("tools", || async {}).await, None);
+}
+
 #[tokio::test]
 async fn test_cache_clear_all() {
     let config = CacheConfig::default();
@@ -288,7 +399,7 @@ async fn test_concurrent_cache_access() {
 
             // Get
             let result = cache_clone.get(&key).await;
-            assert_eq!(result, Some(value));
+            assert_eq!(result.as_deref(), Some(&value[..]));
         });
         handles.push(handle);
     }
@@ -311,11 +422,19 @@ async fn test_cache_eviction_metrics() {
     let config = CacheConfig {
         enabled: true,
         l1_capacity: 2, // Very small to force evictions
+        l1_max_bytes: None,
         l1_ttl: Duration::from_secs(300),
         l2_capacity: 50,
+        l2_max_bytes: None,
         l2_ttl: Duration::from_secs(1800),
         l3_capacity: 20,
+        l3_max_bytes: None,
         l3_ttl: Duration::from_secs(7200),
+        method_ttls: Default::default(),
+        stale_grace: Duration::ZERO,
+        l1_compression: None,
+        l2_compression: None,
+        l3_compression: None,
     };
     let cache = LayeredCache::new(config);
 
@@ -338,3 +457,172 @@ async fn test_cache_eviction_metrics() {
     // Note: Eviction count tracking depends on eviction_listener being called
     // The actual count may vary based on moka's internal timing
 }
+
+#[tokio::test]
+async fn test_dedup_savings_only_counted_across_keys() {
+    let cache = LayeredCache::new(CacheConfig::default());
+    let docs = br#"{"contents":[{"text":"documentation shared by several servers"}]}"#.to_vec();
+    let saved = || {
+        only1mcp::metrics::CONTEXT_TOKENS_SAVED
+            .with_label_values(&["proxy", "deduplication"])
+            .get()
+    };
+
+    // Re-setting a key with the same body replaces its entry
+    cache.set("server-a".to_string(), docs.clone(), "resources/read").await;
+    let before = saved();
+    cache.set("server-a".to_string(), docs.clone(), "resources/read").await;
+    assert_eq!(saved(), before);
+    cache.sync().await;
+    cache.set("server-a".to_string(), docs.clone(), "resources/read").await;
+    assert_eq!(saved(), before);
+
+    // Another key sharing the body is a saving
+    cache.set("server-b".to_string(), docs, "resources/read").await;
+    assert!(saved() > before);
+}
+
+#[tokio::test]
+async fn test_savings_attributed_to_source_servers() {
+    let cache = LayeredCache::new(CacheConfig::default());
+    let docs = br#"{"contents":[{"text":"documentation mirrored by two servers"}]}"#.to_vec();
+    let saved = |server: &str| {
+        only1mcp::metrics::CONTEXT_TOKENS_SAVED
+            .with_label_values(&[server, "deduplication"])
+            .get()
+    };
+
+    // Given: A body cached from one server
+    let sources = TokenSources::server("docs-primary", &docs);
+    cache
+        .set_from(
+            "primary".to_string(),
+            docs.clone(),
+            "resources/read",
+            sources.clone(),
+        )
+        .await;
+
+    // When: Another server returns the same body
+    let mirror = TokenSources::server("docs-mirror", &docs);
+    cache.set_from("mirror".to_string(), docs, "resources/read", mirror).await;
+
+    // Then: The deduplicated copy is credited to the server it came from
+    assert!(saved("docs-mirror") > 0.0);
+    assert_eq!(saved("docs-primary"), 0.0);
+
+    // And: Hits return the sources the entry was stored with
+    let (_, hit) = cache.get_with_sources("primary").await.unwrap();
+    assert_eq!(hit, sources);
+}
+
+#[tokio::test]
+async fn test_cache_hits_report_estimated_tokens_saved() {
+    // Given: A backend with one tool
+    let backend = mock_backend(vec![sample_tool("token_counter", "Count tokens")]).await;
+    let server = start_test_server(test_config_with_backends(0, vec![backend.uri()])).await;
+
+    // When: The tool list is fetched twice, the second time from cache
+    for _ in 0..2 {
+        let response = server.rpc("tools/list", json!({})).await;
+        assert_eq!(response["result"]["tools"][0]["name"], "token_counter");
+    }
+
+    // Then: The cache hit is reported in estimated tokens, not as a count of 1
+    let metrics = test_client()
+        .get(format!("{}/api/v1/admin/metrics", server.url()))
+        .send()
+        .await
+        .expect("Failed to send request")
+        .text()
+        .await
+        .unwrap();
+    let saved: f64 = metrics
+        .lines()
+        .filter(|line| {
+            line.starts_with("only1mcp_context_tokens_saved_total")
+                && line.contains(r#"optimization_type="cache_hit""#)
+        })
+        .filter_map(|line| line.rsplit(' ').next()?.parse::<f64>().ok())
+        .sum();
+    assert!(saved > 1.0, "tokens saved: {}\n{}", saved, metrics);
+
+    // And: It is credited to the backend the tools came from
+    assert!(
+        metrics.lines().any(
+            |line| line.starts_with("only1mcp_context_tokens_saved_total")
+                && line.contains(r#"optimization_type="cache_hit""#)
+                && line.contains(r#"server_id="test-backend-0""#)
+        ),
+        "{}",
+        metrics
+    );
+}
+
+#[tokio::test]
+async fn test_resource_reads_cached_until_updated() {
+    use wiremock::{matchers::body_partial_json, Mock, ResponseTemplate};
+
+    // Given: A backend serving a resource
+    let backend = mock_backend(vec![]).await;
+    Mock::given(body_partial_json(json!({"method": "resources/read"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0", "id": 1, "result": {"contents": [
+                {"uri": "file:///notes.md", "text": "hello"}
+            ]}
+        })))
+        .mount(&backend)
+        .await;
+    let reads = || async {
+        backend
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|request| String::from_utf8_lossy(&request.body).contains("resources/read"))
+            .count()
+    };
+
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.servers[0].id = "notes-backend".to_string();
+    let server = start_test_server(config).await;
+    let client = test_client();
+    let mcp = format!("{}/mcp", server.url());
+    let read = |id: u64| {
+        json!({"jsonrpc": "2.0", "id": id, "method": "resources/read",
+               "params": {"uri": "file:///notes.md"}})
+    };
+
+    // When: The resource is read twice
+    let first: serde_json::Value =
+        client.post(&mcp).json(&read(1)).send().await.unwrap().json().await.unwrap();
+    let second: serde_json::Value =
+        client.post(&mcp).json(&read(2)).send().await.unwrap().json().await.unwrap();
+
+    // Then: The second read is served from cache under its own id
+    assert_eq!(first["result"]["contents"][0]["text"], "hello", "{}", first);
+    assert_eq!(second["result"], first["result"]);
+    assert_eq!(second["id"], 2);
+    assert_eq!(reads().await, 1);
+
+    // And: The tokens it saved are credited to the backend
+    let saved = only1mcp::metrics::CONTEXT_TOKENS_SAVED
+        .with_label_values(&["notes-backend", "cache_hit"])
+        .get();
+    assert!(saved > 0.0, "tokens saved: {}", saved);
+
+    // When: The backend reports the resource updated
+    client
+        .post(&mcp)
+        .json(
+            &json!({"jsonrpc": "2.0", "method": "notifications/resources/updated",
+                      "params": {"uri": "file:///notes.md"}}),
+        )
+        .send()
+        .await
+        .unwrap();
+    client.post(&mcp).json(&read(3)).send().await.unwrap();
+
+    // Then: The next read goes to the backend again
+    assert_eq!(reads().await, 2);
+}
@@ -186,3 +186,254 @@ async fn test_concurrent_requests() {
         );
     }
 }
+
+#[tokio::test]
+async fn test_backends_initialized_in_parallel_with_timeout() {
+    use std::time::{Duration, Instant};
+    use wiremock::{matchers::path, Mock, MockServer, ResponseTemplate};
+
+    // Given: Four backends taking 400ms to list their tools, and one that
+    // never answers in time
+    let mut backends = Vec::new();
+    for delay in [400, 400, 400, 400, 10_000] {
+        let backend = MockServer::start().await;
+        Mock::given(path("/health"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&backend)
+            .await;
+        Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"jsonrpc": "2.0", "id": 1, "result": {"tools": [
+                        {"name": "echo", "inputSchema": {"type": "object"}}
+                    ]}}))
+                    .set_delay(Duration::from_millis(delay)),
+            )
+            .mount(&backend)
+            .await;
+        backends.push(backend);
+    }
+    let mut config = test_config_with_backends(0, backends.iter().map(MockServer::uri).collect());
+    config.proxy.initialization.max_parallel = 5;
+    config.proxy.initialization.timeout_ms = 1000;
+    let server = only1mcp::proxy::server::ProxyServer::new(
+        config,
+        std::path::PathBuf::from("test-config.yaml"),
+    )
+    .await
+    .unwrap();
+
+    // When: The servers are loaded at startup
+    let start = Instant::now();
+    server.log_loaded_servers().await.unwrap();
+
+    // Then: They load side by side (one after another would take 2.6s),
+    // and the stuck one is given up on
+    let elapsed = start.elapsed();
+    assert!(elapsed < Duration::from_millis(2000), "took {:?}", elapsed);
+}
+
+#[tokio::test]
+async fn test_shutdown_drains_in_flight_requests() {
+    use only1mcp::proxy::ProxyServer;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    // Given: A proxy in front of a slow backend
+    let backend = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_delay(Duration::from_millis(500))
+                .set_body_json(json!({"jsonrpc": "2.0", "id": 1, "result": {"tools": []}})),
+        )
+        .mount(&backend)
+        .await;
+    // Pooled connections are validated against the health endpoint
+    Mock::given(path("/health"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&backend)
+        .await;
+
+    let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let config = test_config_with_backends(port, vec![backend.uri()]);
+    let server = ProxyServer::new(config, std::path::PathBuf::from("test-config.yaml"))
+        .await
+        .unwrap();
+    let running = tokio::spawn(server.clone().run());
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let url = format!("http://127.0.0.1:{}/mcp", port);
+    let in_flight = {
+        let request = test_client()
+            .post(&url)
+            .json(&json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"}));
+        tokio::spawn(request.send())
+    };
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // When: Shutdown starts while the request is still waiting on the backend
+    server.shutdown();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    // Then: New requests are turned away
+    let refused = test_client()
+        .post(&url)
+        .json(&json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}))
+        .send()
+        .await;
+    if let Ok(response) = refused {
+        assert_eq!(response.status(), 503);
+    }
+
+    // And: The in-flight request still completes before the server exits
+    let response = in_flight.await.unwrap().expect("in-flight request was dropped");
+    assert!(response.status().is_success());
+
+    tokio::time::timeout(Duration::from_secs(5), running)
+        .await
+        .expect("server did not stop after draining")
+        .unwrap()
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_prefetch_warms_lists_before_first_request() {
+    // Given: A backend with one tool
+    let backend = mock_backend(vec![sample_tool("echo", "Echo")]).await;
+    let tools_lists = || async {
+        backend
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|request| String::from_utf8_lossy(&request.body).contains("tools/list"))
+            .count()
+    };
+
+    // When: The proxy starts with prefetching enabled
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.context_optimization.catalog.prefetch = true;
+    let server = start_test_server(config).await;
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+
+    // Then: The backend's tools were fetched before any client asked
+    let fetched = tools_lists().await;
+    assert!(fetched >= 1);
+
+    // And: The first tools/list is answered without asking the backend
+    let body = server.rpc("tools/list", json!({})).await;
+    assert_eq!(body["result"]["tools"][0]["name"], "echo", "{}", body);
+    assert_eq!(tools_lists().await, fetched);
+
+    // When: A list_changed notification arrives
+    test_client()
+        .post(format!("{}/mcp", server.url()))
+        .json(&json!({"jsonrpc": "2.0", "method": "notifications/tools/list_changed"}))
+        .send()
+        .await
+        .unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+
+    // Then: The refresher fetches the tools again
+    assert_eq!(tools_lists().await, fetched + 1);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_startup_policies_start_stdio_servers() {
+    // Minimal MCP server answering every request
+    const SERVER: &str = r#"
+while IFS= read -r line; do
+  id=$(printf '%s' "$line" | sed -n 's/.*"id":\([0-9][0-9]*\).*/\1/p')
+  [ -z "$id" ] && continue
+  printf '{"jsonrpc":"2.0","id":%s,"result":{"protocolVersion":"2024-11-05","capabilities":{},"tools":[]}}\n' "$id"
+done
+"#;
+    let stdio = |id: &str, command: &str, startup: &str| -> only1mcp::config::McpServerConfig {
+        serde_json::from_value(json!({
+            "id": id,
+            "name": id,
+            "transport": {"type": "stdio", "command": command, "args": ["-c", SERVER]},
+            "health_check": {"enabled": false},
+            "startup": startup
+        }))
+        .unwrap()
+    };
+
+    // Given: An eager, a lazy and a preinitialized server that cannot start
+    let mut config = test_config();
+    config.servers = vec![
+        stdio("eager", "sh", "eager"),
+        stdio("lazy", "sh", "lazy"),
+        stdio(
+            "broken",
+            "/nonexistent/mcp-server",
+            "on_demand_with_preinit",
+        ),
+    ];
+
+    // When: The proxy starts
+    let server = start_test_server(config).await;
+
+    // Then: The startup of each server is reported
+    let servers: serde_json::Value = test_client()
+        .get(format!("{}/api/v1/admin/servers", server.url()))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let startup = |id: &str| {
+        servers.as_array().unwrap().iter().find(|s| s["id"] == id).unwrap()["startup"].clone()
+    };
+    assert_eq!(startup("eager")["policy"], "eager");
+    assert_eq!(startup("eager")["state"], "ready", "{}", servers);
+    assert_eq!(startup("lazy")["state"], "deferred", "{}", servers);
+    assert_eq!(startup("broken")["policy"], "on_demand_with_preinit");
+    assert_eq!(startup("broken")["state"], "failed", "{}", servers);
+    assert!(startup("broken")["error"].is_string());
+}
+
+#[tokio::test]
+async fn test_http_backends_prewarmed_at_startup() {
+    use std::time::Duration;
+
+    // Given: Two HTTP backends, one of them behind a proxy that prewarms
+    let warm = mock_backend(vec![]).await;
+    let cold = mock_backend(vec![]).await;
+
+    let mut config = test_config_with_backends(0, vec![warm.uri()]);
+    config.servers[0].id = "prewarmed-backend".to_string();
+    let warm_server = start_test_server(config).await;
+
+    let mut config = test_config_with_backends(0, vec![cold.uri()]);
+    config.proxy.connection_pool.prewarm = false;
+    let _cold_server = start_test_server(config).await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    // Then: Only the prewarming proxy connected before any request
+    assert!(!warm.received_requests().await.unwrap().is_empty());
+    assert!(cold.received_requests().await.unwrap().is_empty());
+
+    // And: Its pool is published
+    let metrics = test_client()
+        .get(format!("{}/api/v1/admin/metrics", warm_server.url()))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(
+        metrics.lines().any(|line| line.starts_with("only1mcp_connection_pool_size")
+            && line.contains(r#"server_id="prewarmed-backend""#)
+            && line.contains(r#"state="idle""#)),
+        "{}",
+        metrics
+    );
+}
@@ -29,6 +29,8 @@ async fn test_context7_tools_list() {
         base_url: "https://mcp.context7.com/mcp".to_string(),
         request_timeout: std::time::Duration::from_secs(30),
         headers,
+        max_response_bytes: Default::default(),
+        proxy: None,
     };
 
     // Create transport
@@ -156,6 +158,8 @@ async fn test_sse_error_handling_invalid_endpoint() {
         base_url: "https://invalid.example.com".to_string(),
         request_timeout: std::time::Duration::from_secs(5),
         headers: HashMap::new(),
+        max_response_bytes: Default::default(),
+        proxy: None,
     };
 
     let transport = SseTransport::new(config).await.unwrap();
@@ -174,6 +178,8 @@ async fn test_sse_error_handling_timeout() {
         base_url: "https://httpbin.org/delay/10".to_string(),
         request_timeout: std::time::Duration::from_millis(100), // Very short timeout
         headers: HashMap::new(),
+        max_response_bytes: Default::default(),
+        proxy: None,
     };
 
     let transport = SseTransport::new(config).await.unwrap();
@@ -184,3 +190,76 @@ async fn test_sse_error_handling_timeout() {
 
     assert!(result.is_err(), "Request should timeout with short timeout");
 }
+
+/// A broken session (404) is recovered by replaying on a new connection
+#[tokio::test]
+async fn test_sse_pool_replays_after_broken_session() {
+    use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("Session not found"))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            "event: message\ndata: {\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{\"tools\":[]}}\n\n",
+            "text/event-stream",
+        ))
+        .mount(&server)
+        .await;
+
+    let pool = SseTransportPool::new(SseTransportConfig::default());
+    let endpoint = format!("{}/mcp", server.uri());
+    let before = only1mcp::metrics::BACKEND_SESSION_RECOVERIES_TOTAL
+        .with_label_values(&["sse", &endpoint])
+        .get();
+
+    let request = McpRequest::new("tools/list", json!({}), Some(json!(1)));
+    let response = pool.send_request(&endpoint, request).await.expect("replay should succeed");
+
+    assert!(response.result.is_some());
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    assert_eq!(
+        only1mcp::metrics::BACKEND_SESSION_RECOVERIES_TOTAL
+            .with_label_values(&["sse", &endpoint])
+            .get(),
+        before + 1.0
+    );
+}
+
+/// A connection dropped after the request was sent is not replayed, as the
+/// backend may already have run it
+#[tokio::test]
+async fn test_sse_pool_does_not_replay_delivered_request() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::AsyncReadExt;
+
+    // Backend that reads each request and hangs up without answering
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}/mcp", listener.local_addr().unwrap());
+    let received = Arc::new(AtomicUsize::new(0));
+    let counter = received.clone();
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut buffer = [0u8; 4096];
+            if socket.read(&mut buffer).await.unwrap_or(0) > 0 {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+    });
+
+    let pool = SseTransportPool::new(SseTransportConfig::default());
+    let request = McpRequest::new(
+        "tools/call",
+        json!({"name": "deploy", "arguments": {}}),
+        Some(json!(1)),
+    );
+    let result = pool.send_request(&endpoint, request).await;
+
+    assert!(result.is_err());
+    assert_eq!(received.load(Ordering::SeqCst), 1);
+}
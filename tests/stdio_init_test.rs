@@ -4,6 +4,7 @@ use only1mcp::transport::stdio::{StdioConfig, StdioTransport};
 use only1mcp::types::McpRequest;
 use serde_json::json;
 use std::collections::HashMap;
+use std::time::Duration;
 
 #[tokio::test]
 async fn test_stdio_sequential_thinking_initialization() {
@@ -16,11 +17,18 @@ async fn test_stdio_sequential_thinking_initialization() {
             "@modelcontextprotocol/server-sequential-thinking".to_string(),
         ],
         env: HashMap::new(),
+        inherit_env: None,
+        redacted_values: Vec::new(),
         cwd: None,
         timeout_ms: 30000,
         max_memory_mb: Some(512),
         max_cpu_percent: Some(50),
         sandbox: false, // Disable sandbox for test
+        isolation: Default::default(),
+        npx: Default::default(),
+        backend_logs: None,
+        pool_size: 1,
+        max_line_bytes: 16 * 1024 * 1024,
     };
 
     // Create a tools/list request
@@ -80,11 +88,18 @@ async fn test_stdio_memory_initialization() {
             "@modelcontextprotocol/server-memory".to_string(),
         ],
         env: HashMap::new(),
+        inherit_env: None,
+        redacted_values: Vec::new(),
         cwd: None,
         timeout_ms: 30000,
         max_memory_mb: Some(512),
         max_cpu_percent: Some(50),
         sandbox: false,
+        isolation: Default::default(),
+        npx: Default::default(),
+        backend_logs: None,
+        pool_size: 1,
+        max_line_bytes: 16 * 1024 * 1024,
     };
 
     // Create a tools/list request
@@ -139,3 +154,188 @@ async fn test_stdio_memory_initialization() {
         "Expected memory tools not found"
     );
 }
+
+/// Minimal MCP server answering every request with its own PID.
+#[cfg(unix)]
+const ECHO_PID_SERVER: &str = r#"
+while IFS= read -r line; do
+  id=$(printf '%s' "$line" | sed -n 's/.*"id":\([0-9][0-9]*\).*/\1/p')
+  [ -z "$id" ] && continue
+  printf '{"jsonrpc":"2.0","id":%s,"result":{"protocolVersion":"2024-11-05","capabilities":{},"pid":%s}}\n' "$id" "$$"
+done
+"#;
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_stdio_pool_round_robin() {
+    let transport = StdioTransport::new();
+
+    let config = StdioConfig {
+        command: "sh".to_string(),
+        args: vec!["-c".to_string(), ECHO_PID_SERVER.to_string()],
+        env: HashMap::new(),
+        inherit_env: None,
+        redacted_values: Vec::new(),
+        cwd: None,
+        timeout_ms: 5000,
+        max_memory_mb: None,
+        max_cpu_percent: None,
+        sandbox: false,
+        isolation: Default::default(),
+        npx: Default::default(),
+        backend_logs: None,
+        pool_size: 2,
+        max_line_bytes: 16 * 1024 * 1024,
+    };
+
+    let mut pids = Vec::new();
+    for id in 1..=4 {
+        let request = McpRequest {
+            jsonrpc: "2.0".to_string(),
+            method: "tools/list".to_string(),
+            params: None,
+            id: Some(json!(id)),
+        };
+        let response = transport
+            .send_request_with_config("pooled".to_string(), &config, request)
+            .await
+            .expect("pooled request failed");
+        assert_eq!(response.id, Some(json!(id)));
+        pids.push(response.result.unwrap()["pid"].as_u64().unwrap());
+    }
+
+    // Requests alternate between the two processes
+    assert_ne!(pids[0], pids[1]);
+    assert_eq!(pids[0], pids[2]);
+    assert_eq!(pids[1], pids[3]);
+
+    let status = transport.process_status("pooled").await;
+    assert_eq!(status.len(), 2);
+    assert!(status
+        .iter()
+        .all(|s| s.running && s.requests == 2 && s.consecutive_failures == 0));
+
+    transport.kill_all().await.unwrap();
+    assert!(transport.process_status("pooled").await.is_empty());
+}
+
+/// MCP server that reads two requests before answering them in reverse
+/// order, echoing each request's `n` parameter.
+#[cfg(unix)]
+const REVERSE_ORDER_SERVER: &str = r#"
+field() { printf '%s' "$1" | sed -n "s/.*\"$2\":\([0-9][0-9]*\).*/\1/p"; }
+IFS= read -r init
+printf '{"jsonrpc":"2.0","id":%s,"result":{"protocolVersion":"2024-11-05","capabilities":{}}}\n' "$(field "$init" id)"
+IFS= read -r initialized
+IFS= read -r first
+IFS= read -r second
+for line in "$second" "$first"; do
+  printf '{"jsonrpc":"2.0","id":%s,"result":{"n":%s}}\n' "$(field "$line" id)" "$(field "$line" n)"
+done
+cat > /dev/null
+"#;
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_stdio_concurrent_requests_are_correlated() {
+    let transport = StdioTransport::new();
+
+    let config = StdioConfig {
+        command: "sh".to_string(),
+        args: vec!["-c".to_string(), REVERSE_ORDER_SERVER.to_string()],
+        env: HashMap::new(),
+        inherit_env: None,
+        redacted_values: Vec::new(),
+        cwd: None,
+        timeout_ms: 5000,
+        max_memory_mb: None,
+        max_cpu_percent: None,
+        sandbox: false,
+        isolation: Default::default(),
+        npx: Default::default(),
+        backend_logs: None,
+        pool_size: 1,
+        max_line_bytes: 16 * 1024 * 1024,
+    };
+
+    // Both callers use the same JSON-RPC id
+    let request = |n: u64| McpRequest {
+        jsonrpc: "2.0".to_string(),
+        method: "tools/call".to_string(),
+        params: Some(json!({"n": n})),
+        id: Some(json!(1)),
+    };
+
+    let (first, second) = tokio::join!(
+        transport.send_request_with_config("reverse".to_string(), &config, request(10)),
+        transport.send_request_with_config("reverse".to_string(), &config, request(20)),
+    );
+
+    // Each caller gets the answer to its own request, under its own id
+    let first = first.expect("first request failed");
+    let second = second.expect("second request failed");
+    assert_eq!(first.result.unwrap()["n"], 10);
+    assert_eq!(second.result.unwrap()["n"], 20);
+    assert_eq!(first.id, Some(json!(1)));
+    assert_eq!(second.id, Some(json!(1)));
+
+    transport.kill_all().await.unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_stdio_idle_process_reaped_and_respawned() {
+    let transport = StdioTransport::new();
+
+    let config = StdioConfig {
+        command: "sh".to_string(),
+        args: vec!["-c".to_string(), ECHO_PID_SERVER.to_string()],
+        env: HashMap::new(),
+        inherit_env: None,
+        redacted_values: Vec::new(),
+        cwd: None,
+        timeout_ms: 5000,
+        max_memory_mb: None,
+        max_cpu_percent: None,
+        sandbox: false,
+        isolation: Default::default(),
+        npx: Default::default(),
+        backend_logs: None,
+        pool_size: 1,
+        max_line_bytes: 16 * 1024 * 1024,
+    };
+    let request = || McpRequest {
+        jsonrpc: "2.0".to_string(),
+        method: "tools/list".to_string(),
+        params: None,
+        id: Some(json!(1)),
+    };
+    let pid_of =
+        |response: only1mcp::types::McpResponse| response.result.unwrap()["pid"].as_u64().unwrap();
+
+    let first = transport
+        .send_request_with_config("idle".to_string(), &config, request())
+        .await
+        .expect("first request failed");
+    let first_pid = pid_of(first);
+
+    // A recently used process is kept
+    assert_eq!(transport.reap_idle(Duration::from_secs(60)).await, 0);
+    assert!(transport.process_status("idle").await[0].running);
+
+    // Once idle past the timeout it is stopped
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(transport.reap_idle(Duration::from_millis(20)).await, 1);
+    let status = transport.process_status("idle").await;
+    assert!(!status[0].running);
+    assert_eq!(status[0].pid, None);
+
+    // And the next request starts a fresh one
+    let second = transport
+        .send_request_with_config("idle".to_string(), &config, request())
+        .await
+        .expect("request after reaping failed");
+    assert_ne!(pid_of(second), first_pid);
+
+    transport.kill_all().await.unwrap();
+}
@@ -0,0 +1,119 @@
+//! Integration tests for STDIO backend processes driven through the proxy
+
+mod common;
+
+use common::*;
+use serde_json::json;
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_stdio_line_limit_fails_only_oversized_response() {
+    // MCP server answering reads of huge.log with a 64 KiB line whose id
+    // comes last
+    const SERVER: &str = r#"
+pad=$(printf '%065536d' 0)
+while IFS= read -r line; do
+  id=$(printf '%s' "$line" | sed -n 's/.*"id":\([0-9][0-9]*\).*/\1/p')
+  [ -z "$id" ] && continue
+  case "$line" in
+    *huge.log*) printf '{"jsonrpc":"2.0","result":{"contents":[{"uri":"file:///huge.log","text":"%s"}]},"id":%s}\n' "$pad" "$id" ;;
+    *) printf '{"jsonrpc":"2.0","id":%s,"result":{"protocolVersion":"2024-11-05","capabilities":{"resources":{}},"contents":[{"uri":"file:///notes.md","text":"hello"}]}}\n' "$id" ;;
+  esac
+done
+"#;
+
+    // Given: A STDIO server and a 4 KiB line limit
+    let mut config = test_config();
+    config.servers = vec![serde_json::from_value(json!({
+        "id": "local",
+        "name": "local",
+        "transport": {"type": "stdio", "command": "sh", "args": ["-c", SERVER]},
+        "health_check": {"enabled": false}
+    }))
+    .unwrap()];
+    config.proxy.limits.max_stdio_line_bytes = 4096;
+    let server = start_test_server(config).await;
+
+    // When: The server answers with a line over the limit
+    let body = server.rpc("resources/read", json!({"uri": "file:///huge.log"})).await;
+
+    // Then: That request fails promptly with a response-too-large error
+    assert_eq!(body["error"]["code"], -32006, "{}", body);
+    assert_eq!(body["id"], 1);
+
+    // And: The process keeps serving requests
+    let body = server.rpc("resources/read", json!({"uri": "file:///notes.md"})).await;
+    assert_eq!(body["result"]["contents"][0]["text"], "hello", "{}", body);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_crashing_stdio_server_restarted_until_crash_loop() {
+    // MCP server that completes its handshake, then exits with an error
+    const SERVER: &str = r#"
+read -r line
+id=$(printf '%s' "$line" | sed -n 's/.*"id":\([0-9][0-9]*\).*/\1/p')
+printf '{"jsonrpc":"2.0","id":%s,"result":{"protocolVersion":"2024-11-05","capabilities":{}}}\n' "$id"
+sleep 0.3
+exit 3
+"#;
+
+    // Given: An eager server restarted on failure at most twice per minute
+    let mut config = test_config();
+    config.servers = vec![serde_json::from_value(json!({
+        "id": "flaky",
+        "name": "flaky",
+        "transport": {"type": "stdio", "command": "sh", "args": ["-c", SERVER]},
+        "health_check": {"enabled": false},
+        "startup": "eager",
+        "restart": {"policy": "on_failure", "max_restarts": 2, "initial_backoff_ms": 50}
+    }))
+    .unwrap()];
+    let server = start_test_server(config).await;
+    let client = test_client();
+    let status = || async {
+        let servers: serde_json::Value = client
+            .get(format!("{}/api/v1/admin/servers", server.url()))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        servers[0].clone()
+    };
+
+    // When: Its processes keep exiting (allow for slow process spawns on
+    // loaded test runners)
+    let mut flaky = status().await;
+    for _ in 0..150 {
+        if flaky["restart"]["crash_loop"] == true {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        flaky = status().await;
+    }
+
+    // Then: It is restarted twice, then marked unhealthy as crash looping
+    assert_eq!(flaky["restart"]["policy"], "on_failure", "{}", flaky);
+    assert_eq!(flaky["restart"]["restarts"], 2, "{}", flaky);
+    assert_eq!(flaky["restart"]["crash_loop"], true, "{}", flaky);
+    assert_eq!(flaky["restart"]["last_exit"], "exit status: 3", "{}", flaky);
+    assert_eq!(flaky["health"], "Unhealthy", "{}", flaky);
+
+    // When: An operator restarts it
+    let restarted = client
+        .post(format!(
+            "{}/api/v1/admin/servers/flaky/restart",
+            server.url()
+        ))
+        .send()
+        .await
+        .unwrap();
+
+    // Then: The crash loop is cleared
+    assert_eq!(restarted.status(), 200);
+    let flaky = status().await;
+    assert_eq!(flaky["restart"]["crash_loop"], false, "{}", flaky);
+    assert_ne!(flaky["health"], "Unhealthy", "{}", flaky);
+}
@@ -20,6 +20,10 @@ fn create_test_config(url: impl Into<String>) -> StreamableHttpConfig {
         url: url.into(),
         headers,
         timeout_ms: 30000,
+        oauth: None,
+        tls: None,
+        signing: None,
+        proxy: None,
     }
 }
 
@@ -246,6 +250,10 @@ async fn test_streamable_http_timeout_handling() {
         url: "http://localhost:8124/mcp".to_string(),
         headers,
         timeout_ms: 1, // 1ms timeout - should fail
+        oauth: None,
+        tls: None,
+        signing: None,
+        proxy: None,
     };
 
     let transport = StreamableHttpTransport::new(config);
@@ -274,8 +282,82 @@ fn test_streamable_http_config_defaults() {
         url: "http://test.com/mcp".to_string(),
         headers: HashMap::new(),
         timeout_ms: 30000,
+        oauth: None,
+        tls: None,
+        signing: None,
+        proxy: None,
     };
 
     assert_eq!(config.timeout_ms, 30000);
     assert!(config.headers.is_empty());
 }
+
+#[tokio::test]
+async fn test_streamable_http_replays_after_session_lost() {
+    use wiremock::matchers::{body_partial_json, header, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    let ok = |session: &str| {
+        ResponseTemplate::new(200)
+            .insert_header("mcp-session-id", session)
+            .set_body_json(json!({"jsonrpc": "2.0", "id": 1, "result": {"tools": []}}))
+    };
+
+    // The first session is handed out once, then replaced by a second one
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({"method": "initialize"})))
+        .respond_with(ok("s1"))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({"method": "initialize"})))
+        .respond_with(ok("s2"))
+        .with_priority(2)
+        .mount(&server)
+        .await;
+
+    // s1 serves one request and is then forgotten by the server
+    Mock::given(method("POST"))
+        .and(header("mcp-session-id", "s1"))
+        .respond_with(ok("s1"))
+        .up_to_n_times(1)
+        .with_priority(3)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(header("mcp-session-id", "s1"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("Unknown session"))
+        .with_priority(4)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(header("mcp-session-id", "s2"))
+        .respond_with(ok("s2"))
+        .with_priority(4)
+        .mount(&server)
+        .await;
+
+    let url = format!("{}/mcp", server.uri());
+    let transport = StreamableHttpTransport::new(create_test_config(url.clone()));
+    let before = only1mcp::metrics::BACKEND_SESSION_RECOVERIES_TOTAL
+        .with_label_values(&["streamable_http", &url])
+        .get();
+
+    let request = McpRequest::new("tools/list", json!({}), Some(json!(1)));
+    transport.send_request(request.clone()).await.expect("first request");
+    assert_eq!(transport.get_session_id().await.as_deref(), Some("s1"));
+
+    // The stale session is replaced and the request replayed transparently
+    let response = transport.send_request(request).await.expect("replayed request");
+    assert!(response.result.is_some());
+    assert_eq!(transport.get_session_id().await.as_deref(), Some("s2"));
+    assert_eq!(
+        only1mcp::metrics::BACKEND_SESSION_RECOVERIES_TOTAL
+            .with_label_values(&["streamable_http", &url])
+            .get(),
+        before + 1.0
+    );
+}
@@ -0,0 +1,433 @@
+//! Integration tests for tools/call handling: streaming, transforms, policies, validation and result filtering
+
+mod common;
+
+use common::*;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_large_tool_result_is_streamed() {
+    // Given: A backend whose tool returns a 256 KiB result
+    let text = "x".repeat(256 * 1024);
+    let backend = mock_backend(vec![sample_tool("dump", "Dump")]).await;
+    mount_tools_call(
+        &backend,
+        "dump",
+        json!({"content": [{"type": "text", "text": text}]}),
+    )
+    .await;
+
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.proxy.streaming.min_bytes = 64 * 1024;
+    let server = start_test_server(config).await;
+    let client = test_client();
+    server.rpc("tools/list", json!({})).await;
+
+    // When: The tool is called
+    let body = server.rpc("tools/call", json!({"name": "dump", "arguments": {}})).await;
+
+    // Then: The result arrives intact
+    assert_eq!(body["id"], 1, "{}", body);
+    assert_eq!(body["result"]["content"][0]["text"], text.as_str());
+
+    // And: It was streamed and charged to the backend
+    let metrics = client
+        .get(format!("{}/api/v1/admin/metrics", server.url()))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    let streamed: f64 = metrics
+        .lines()
+        .filter(|line| line.starts_with("only1mcp_streamed_response_bytes_total"))
+        .filter_map(|line| line.rsplit(' ').next()?.parse::<f64>().ok())
+        .sum();
+    assert!(streamed > text.len() as f64, "streamed: {}", streamed);
+
+    let report: serde_json::Value = client
+        .get(format!("{}/api/v1/admin/costs", server.url()))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(report["total_calls"], 1, "{}", report);
+    assert!(
+        report["servers"][0]["tokens"].as_u64().unwrap() > 1000,
+        "{}",
+        report
+    );
+}
+
+#[tokio::test]
+async fn test_transforms_rewrite_backend_traffic() {
+    use wiremock::{
+        matchers::{body_partial_json, header},
+        Mock, ResponseTemplate,
+    };
+
+    // Given: A backend whose `search` tool is exposed as `web_search`, and
+    // which only answers calls carrying the injected header and default
+    let backend = mock_backend(vec![sample_tool("search", "Search")]).await;
+    Mock::given(header("x-team", "search"))
+        .and(body_partial_json(json!({
+            "method": "tools/call",
+            "params": {"name": "search", "arguments": {"query": "mcp", "limit": 10}}
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0", "id": 1,
+            "result": {"content": [{"type": "text", "text": "found"}], "_meta": {"trace": "x"}}
+        })))
+        .mount(&backend)
+        .await;
+
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.servers[0].transforms = serde_yaml::from_str(
+        r#"
+        set_headers: {X-Team: search}
+        rename_tools: {search: web_search}
+        default_arguments: {search: {limit: 10}}
+        strip_response_fields: [_meta]
+        "#,
+    )
+    .unwrap();
+    let server = start_test_server(config).await;
+
+    // When: Clients list the tools
+    let tools = server.rpc("tools/list", json!({})).await;
+
+    // Then: The tool is advertised under its new name
+    assert_eq!(tools["result"]["tools"][0]["name"], "web_search");
+
+    // When: Clients call it by that name
+    let result = server
+        .rpc(
+            "tools/call",
+            json!({"name": "web_search", "arguments": {"query": "mcp"}}),
+        )
+        .await;
+
+    // Then: The backend got its own name, the header and the default, and
+    // the stripped field never reaches the client
+    assert_eq!(
+        result["result"]["content"][0]["text"], "found",
+        "{}",
+        result
+    );
+    assert!(result["result"].get("_meta").is_none());
+}
+
+#[tokio::test]
+async fn test_oversized_tool_results_truncated() {
+    // Given: A backend whose `dump` tool returns 10 KB of text, and a proxy
+    // limiting tools/call results to 1 KB
+    let backend = mock_backend(vec![sample_tool("dump", "Dump")]).await;
+    mount_tools_call(
+        &backend,
+        "dump",
+        json!({"content": [{"type": "text", "text": "x".repeat(10_240)}]}),
+    )
+    .await;
+
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.context_optimization.truncation =
+        serde_yaml::from_str("{enabled: true, max_bytes: 1024}").unwrap();
+    let server = start_test_server(config).await;
+
+    // When: A client calls the tool
+    let result = server.rpc("tools/call", json!({"name": "dump", "arguments": {}})).await;
+
+    // Then: It gets the first 1 KB with the sizes in `_meta`
+    let result = &result["result"];
+    assert_eq!(
+        result["content"][0]["text"].as_str().map(str::len),
+        Some(1024),
+        "{}",
+        result
+    );
+    assert_eq!(
+        result["_meta"]["truncated"],
+        json!({"original_bytes": 10240, "returned_bytes": 1024})
+    );
+}
+
+#[tokio::test]
+async fn test_policy_scripts_rewrite_reject_and_route_calls() {
+    use wiremock::{matchers::body_partial_json, Mock, MockServer, ResponseTemplate};
+
+    /// Backend answering tools/call for `search` with its `name`, if the
+    /// call carries the limit the script adds
+    async fn start_backend(name: &str) -> MockServer {
+        let backend = mock_backend(vec![sample_tool("search", "Search")]).await;
+        Mock::given(body_partial_json(json!({
+            "method": "tools/call", "params": {"arguments": {"limit": 10}}
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0", "id": 1, "result": {"content": [{"type": "text", "text": name}]}
+        })))
+        .mount(&backend)
+        .await;
+        backend
+    }
+
+    // Given: Scripts adding a limit, rejecting empty queries, sending
+    // archive searches to the second backend (both serve `search`) and
+    // tagging results
+    let primary = start_backend("primary").await;
+    let archive = start_backend("archive").await;
+    let dir = tempfile::tempdir().unwrap();
+    let script = |name: &str, source: &str| {
+        let path = dir.path().join(name);
+        std::fs::write(&path, source).unwrap();
+        Some(path)
+    };
+    let mut config = test_config_with_backends(0, vec![primary.uri(), archive.uri()]);
+    config.proxy.scripts.on_request = script(
+        "request.rhai",
+        r#"if arguments.query == "" { reject("empty query") } arguments.limit = 10;"#,
+    );
+    config.proxy.scripts.on_route = script(
+        "route.rhai",
+        r#"if arguments.query.starts_with("archive:") { "test-backend-1" } else { "test-backend-0" }"#,
+    );
+    config.proxy.scripts.on_response = script(
+        "response.rhai",
+        r#"result.content[0].text += ` via ${server}`;"#,
+    );
+    let server = start_test_server(config).await;
+    let call = |query: &str| {
+        let params = json!({"name": "search", "arguments": {"query": query}});
+        server.rpc("tools/call", params)
+    };
+
+    // When/Then: Calls reach the backend the script picked, with the
+    // rewritten arguments and result
+    let body = call("rust").await;
+    assert_eq!(
+        body["result"]["content"][0]["text"], "primary via test-backend-0",
+        "{}",
+        body
+    );
+    let body = call("archive:rust").await;
+    assert_eq!(
+        body["result"]["content"][0]["text"], "archive via test-backend-1",
+        "{}",
+        body
+    );
+
+    // And: A rejected call never reaches a backend
+    let body = call("").await;
+    let message = body["error"]["message"].as_str().unwrap_or_default();
+    assert!(message.contains("empty query"), "{}", body);
+}
+
+#[tokio::test]
+async fn test_composite_tool_chains_backend_calls() {
+    use wiremock::{matchers::body_partial_json, Mock, ResponseTemplate};
+
+    // Given: A backend whose search result holds the ID its fetch tool takes
+    let backend = mock_backend(vec![
+        sample_tool("search", "Search"),
+        sample_tool("fetch", "Fetch"),
+    ])
+    .await;
+    mount_tools_call(
+        &backend,
+        "search",
+        json!({"content": [{"type": "text", "text": r#"{"hits": [{"id": 42}]}"#}]}),
+    )
+    .await;
+    Mock::given(body_partial_json(json!({
+        "method": "tools/call", "params": {"name": "fetch", "arguments": {"id": 42}}
+    })))
+    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+        "jsonrpc": "2.0", "id": 1,
+        "result": {"content": [{"type": "text", "text": "The Rust book"}]}
+    })))
+    .mount(&backend)
+    .await;
+
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.proxy.composite_tools = serde_yaml::from_str(
+        r#"
+        - name: search_and_fetch
+          description: Fetch the top search hit
+          input_schema: {type: object, properties: {query: {type: string}}}
+          steps:
+            - {id: search, tool: search, arguments: {query: "{{ $.arguments.query }}"}}
+            - {id: fetch, tool: fetch, arguments: {id: "{{ $.steps.search.content[0].text.hits[0].id }}"}}
+        "#,
+    )
+    .unwrap();
+    let server = start_test_server(config).await;
+
+    // When/Then: The composite tool is listed alongside the backend's tools
+    let body = server.rpc("tools/list", json!({})).await;
+    let names: Vec<&str> = body["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|tool| tool["name"].as_str())
+        .collect();
+    assert_eq!(names, ["fetch", "search", "search_and_fetch"], "{}", body);
+
+    // And: Calling it runs both steps and returns the last result
+    let body = server
+        .rpc(
+            "tools/call",
+            json!({"name": "search_and_fetch", "arguments": {"query": "rust"}}),
+        )
+        .await;
+    assert_eq!(
+        body["result"]["content"][0]["text"], "The Rust book",
+        "{}",
+        body
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_tool_arguments_rejected_before_backend() {
+    use wiremock::{matchers::body_partial_json, Mock, ResponseTemplate};
+
+    // Given: A backend whose search tool requires a string query, and
+    // argument validation enabled
+    let backend = mock_backend(vec![json!({
+        "name": "search",
+        "inputSchema": {
+            "type": "object",
+            "properties": {"query": {"type": "string"}},
+            "required": ["query"]
+        }
+    })])
+    .await;
+    Mock::given(body_partial_json(json!({"method": "tools/call"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0", "id": 1, "result": {"content": [{"type": "text", "text": "found"}]}
+        })))
+        .expect(1)
+        .mount(&backend)
+        .await;
+
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.proxy.argument_validation.enabled = true;
+    let server = start_test_server(config).await;
+    server.rpc("tools/list", json!({})).await;
+
+    // When: A call passes a number where the schema wants a string
+    let body = server
+        .rpc(
+            "tools/call",
+            json!({"name": "search", "arguments": {"query": 42}}),
+        )
+        .await;
+
+    // Then: It is rejected as invalid params, naming the argument
+    assert_eq!(body["error"]["code"], -32602, "{}", body);
+    let message = body["error"]["message"].as_str().unwrap();
+    assert!(message.contains("/query"), "{}", message);
+
+    // And: A valid call reaches the backend, which sees only that call
+    let body = server
+        .rpc(
+            "tools/call",
+            json!({"name": "search", "arguments": {"query": "rust"}}),
+        )
+        .await;
+    assert_eq!(body["result"]["content"][0]["text"], "found", "{}", body);
+}
+
+#[tokio::test]
+async fn test_tool_results_sanitized() {
+    // Given: A backend returning colored text, and a report tool whose
+    // results do not match its outputSchema
+    let backend = mock_backend(vec![
+        sample_tool("build", "Build"),
+        json!({
+            "name": "report",
+            "inputSchema": {"type": "object"},
+            "outputSchema": {"type": "object", "required": ["passed"]}
+        }),
+    ])
+    .await;
+    mount_tools_call(
+        &backend,
+        "build",
+        json!({"content": [{"type": "text", "text": "\u{1b}[32mBuild succeeded\u{1b}[0m"}]}),
+    )
+    .await;
+    mount_tools_call(
+        &backend,
+        "report",
+        json!({
+            "content": [{"type": "text", "text": "{}"}],
+            "structuredContent": {"failed": 1}
+        }),
+    )
+    .await;
+
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.proxy.sanitization.enabled = true;
+    config.proxy.sanitization.validate_output = true;
+    let server = start_test_server(config).await;
+    server.rpc("tools/list", json!({})).await;
+
+    // When: The build tool is called
+    let body = server.rpc("tools/call", json!({"name": "build", "arguments": {}})).await;
+
+    // Then: Escape codes are stripped
+    assert_eq!(
+        body["result"]["content"][0]["text"], "Build succeeded",
+        "{}",
+        body
+    );
+
+    // And: A result not matching the outputSchema becomes an error result
+    let body = server.rpc("tools/call", json!({"name": "report", "arguments": {}})).await;
+    assert_eq!(body["result"]["isError"], true, "{}", body);
+}
+
+#[tokio::test]
+async fn test_tool_call_arguments_and_results_redacted() {
+    use wiremock::{matchers::body_partial_json, Mock, ResponseTemplate};
+
+    // Given: A backend echoing an employee ID, expecting the email address
+    // in the arguments to have been redacted
+    let backend = mock_backend(vec![sample_tool("search", "Search")]).await;
+    Mock::given(body_partial_json(json!({
+        "method": "tools/call",
+        "params": {"arguments": {"query": "orders of [REDACTED:email]"}}
+    })))
+    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+        "jsonrpc": "2.0", "id": 1,
+        "result": {"content": [{"type": "text", "text": "Placed by EMP-004217"}]}
+    })))
+    .expect(1)
+    .mount(&backend)
+    .await;
+
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.proxy.redaction.enabled = true;
+    config.proxy.redaction.custom =
+        serde_yaml::from_str(r#"[{name: employee_id, regex: "EMP-\\d{6}"}]"#).unwrap();
+    let server = start_test_server(config).await;
+    server.rpc("tools/list", json!({})).await;
+
+    // When: A tool is called with an email address
+    let body = server
+        .rpc(
+            "tools/call",
+            json!({"name": "search", "arguments": {"query": "orders of jane@example.com"}}),
+        )
+        .await;
+
+    // Then: The backend only saw it redacted, and the employee ID in the
+    // result is redacted by the custom pattern
+    assert_eq!(
+        body["result"]["content"][0]["text"], "Placed by [REDACTED:employee_id]",
+        "{}",
+        body
+    );
+}
@@ -0,0 +1,363 @@
+//! Integration tests for aggregated tool catalogs
+
+mod common;
+
+use common::*;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_backend_timeout_bounds_aggregation() {
+    use std::time::{Duration, Instant};
+    use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+    // Given: A backend that answers far slower than its configured timeout
+    let backend = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_delay(Duration::from_secs(5))
+                .set_body_json(json!({"jsonrpc": "2.0", "id": 1, "result": {"tools": []}})),
+        )
+        .mount(&backend)
+        .await;
+
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.servers[0].timeout_ms = 300;
+    let server = start_test_server(config).await;
+
+    // When: tools/list is aggregated
+    let start = Instant::now();
+    let response = test_client()
+        .post(format!("{}/mcp", server.url()))
+        .json(&json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"}))
+        .send()
+        .await
+        .expect("Failed to send request");
+
+    // Then: The slow backend is abandoned after its own timeout
+    assert!(response.status().is_success() || response.status().is_server_error());
+    assert!(
+        start.elapsed() < Duration::from_secs(3),
+        "Request took {:?}, per-backend timeout was not applied",
+        start.elapsed()
+    );
+}
+
+#[tokio::test]
+async fn test_aggregation_budget_skips_slow_backends() {
+    use std::time::Duration;
+    use wiremock::{matchers::method, Mock, ResponseTemplate};
+
+    // Given: A fast backend and one that answers after the aggregation budget
+    let fast = mock_backend(vec![sample_tool("fast_tool", "Fast")]).await;
+    let slow = mock_backend(vec![]).await;
+    Mock::given(method("POST"))
+        .respond_with(
+            ResponseTemplate::new(200).set_delay(Duration::from_secs(2)).set_body_json(
+                json!({"jsonrpc": "2.0", "id": 1, "result": {"tools": [sample_tool("slow_tool", "Slow")]}}),
+            ),
+        )
+        .with_priority(1)
+        .mount(&slow)
+        .await;
+
+    let mut config = test_config_with_backends(0, vec![fast.uri(), slow.uri()]);
+    config.context_optimization.aggregation.timeout_ms = 500;
+    let server = start_test_server(config).await;
+
+    // When: tools/list is aggregated before the slow backend has ever answered
+    let body = server.rpc("tools/list", json!({})).await;
+
+    // Then: Only the fast backend's tools are returned and the slow one is flagged
+    assert_eq!(body["result"]["tools"].as_array().unwrap().len(), 1);
+    assert_eq!(body["result"]["tools"][0]["name"], "fast_tool");
+    assert_eq!(
+        body["result"]["_meta"]["degraded"],
+        json!(["test-backend-1"])
+    );
+
+    // When: The abandoned fetch has completed in the background
+    tokio::time::sleep(Duration::from_millis(2500)).await;
+    let body = server.rpc("tools/list", json!({})).await;
+
+    // Then: The slow backend's tools it recorded in the registry are served
+    // without waiting for it again
+    assert_eq!(body["result"]["tools"][1]["name"], "slow_tool", "{}", body);
+    assert!(body["result"]["_meta"].is_null(), "{}", body);
+}
+
+#[tokio::test]
+async fn test_tools_list_not_modified_for_matching_version() {
+    // Given: A backend with one tool
+    let backend = mock_backend(vec![sample_tool("echo", "Echo")]).await;
+    let server = start_test_server(test_config_with_backends(0, vec![backend.uri()])).await;
+
+    // When: The full catalog is fetched
+    let full = server.rpc("tools/list", json!({})).await;
+    let version = full["result"]["catalogVersion"].as_str().unwrap().to_string();
+    assert_eq!(full["result"]["tools"][0]["name"], "echo");
+
+    // Then: Asking again with that version returns only a marker
+    let unchanged = server.rpc("tools/list", json!({"ifVersion": version})).await;
+    assert_eq!(unchanged["id"], 1);
+    assert_eq!(unchanged["result"]["notModified"], true);
+    assert_eq!(unchanged["result"]["catalogVersion"], version.as_str());
+    assert!(unchanged["result"].get("tools").is_none());
+
+    // And: A stale version gets the full catalog
+    let stale = server.rpc("tools/list", json!({"ifVersion": "0000000000000000"})).await;
+    assert_eq!(stale["result"]["tools"][0]["name"], "echo");
+    assert_eq!(stale["result"]["catalogVersion"], version.as_str());
+}
+
+#[tokio::test]
+async fn test_registry_keeps_catalog_until_refreshed() {
+    // Given: A backend with one tool
+    let backend = mock_backend(vec![sample_tool("echo", "Echo")]).await;
+    let tools_lists = || async {
+        backend
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|request| String::from_utf8_lossy(&request.body).contains("tools/list"))
+            .count()
+    };
+
+    let config = test_config_with_backends(0, vec![backend.uri()]);
+    let server = start_test_server(config).await;
+    let client = test_client();
+    let admin = |path: &str| format!("{}/api/v1/admin/{}", server.url(), path);
+
+    // When: The tool list is read twice
+    let tools: serde_json::Value =
+        client.get(admin("tools")).send().await.unwrap().json().await.unwrap();
+    assert_eq!(tools[0]["name"], "echo", "{}", tools);
+    let fetched = tools_lists().await;
+    client.get(admin("tools")).send().await.unwrap();
+
+    // Then: The second read is served from the registry
+    assert_eq!(tools_lists().await, fetched);
+    let servers: serde_json::Value =
+        client.get(admin("servers")).send().await.unwrap().json().await.unwrap();
+    assert!(
+        servers[0]["catalog_refreshed_at"].is_string(),
+        "{}",
+        servers
+    );
+
+    // When: The server's catalog is refreshed explicitly
+    let response = client.post(admin("servers/test-backend-0/refresh")).send().await.unwrap();
+
+    // Then: The backend is asked again
+    assert_eq!(response.status(), 200);
+    let result: serde_json::Value = response.json().await.unwrap();
+    assert!(
+        result["message"].as_str().unwrap().contains("1 tools"),
+        "{}",
+        result
+    );
+    assert_eq!(tools_lists().await, fetched + 1);
+
+    // And: Unknown servers are rejected
+    let response = client.post(admin("servers/missing/refresh")).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_names_catalog_and_tools_describe() {
+    // Given: A backend with two documented tools
+    let schema = json!({"type": "object", "properties": {
+        "query": {"type": "string", "description": "Search terms"}
+    }});
+    let backend = mock_backend(vec![
+        json!({"name": "echo", "description": "Echo the input back", "inputSchema": schema}),
+        json!({"name": "search", "description": "Search everything", "inputSchema": schema}),
+    ])
+    .await;
+
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.context_optimization.aggregation.annotate_health = false;
+    let server = start_test_server(config).await;
+    let send = |mode: &'static str, method: &'static str, params: serde_json::Value| {
+        let url = format!("{}/mcp", server.url());
+        async move {
+            test_client()
+                .post(url)
+                .header("x-only1mcp-catalog", mode)
+                .json(&json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params}))
+                .send()
+                .await
+                .expect("Failed to send request")
+                .json::<serde_json::Value>()
+                .await
+                .unwrap()
+        }
+    };
+
+    // When: A client asks for a names-only catalog
+    let names = send("names", "tools/list", json!({})).await;
+
+    // Then: It lists tool names only, with a version of its own
+    let result = &names["result"];
+    assert_eq!(
+        result["tools"][0],
+        json!({"name": "echo", "inputSchema": {"type": "object"}}),
+        "{}",
+        names
+    );
+    assert_eq!(result["_meta"]["catalogMode"], "names");
+    let full = send("full", "tools/list", json!({})).await;
+    assert_ne!(full["result"]["catalogVersion"], result["catalogVersion"]);
+    assert_eq!(full["result"]["tools"][0]["inputSchema"], schema);
+
+    // And: tools/describe returns the chosen tool in full
+    let described = send(
+        "names",
+        "tools/describe",
+        json!({"names": ["search", "missing"]}),
+    )
+    .await;
+    assert_eq!(
+        described["result"],
+        json!({"tools": [
+            {"name": "search", "description": "Search everything", "inputSchema": schema}
+        ]})
+    );
+}
+
+#[tokio::test]
+async fn test_tools_list_polls_revalidate_with_etag() {
+    // Given: A proxy in front of a backend with one tool
+    let backend = mock_backend(vec![sample_tool("echo", "Echo")]).await;
+    let config = test_config_with_backends(0, vec![backend.uri()]);
+    let server = start_test_server(config).await;
+    let client = test_client();
+    let list = |id: u64| json!({"jsonrpc": "2.0", "id": id, "method": "tools/list"});
+
+    // When: A client polls tools/list
+    let first = client
+        .post(format!("{}/mcp", server.url()))
+        .json(&list(1))
+        .send()
+        .await
+        .unwrap();
+
+    // Then: The list carries a strong ETag and the cache TTL as max-age
+    assert_eq!(first.status(), 200);
+    let etag = first.headers()["etag"].to_str().unwrap().to_string();
+    assert!(etag.starts_with('"') && etag.ends_with('"'), "{}", etag);
+    assert_eq!(first.headers()["cache-control"], "private, max-age=300");
+
+    // When: It polls again with that tag and a new id
+    let second = client
+        .post(format!("{}/mcp", server.url()))
+        .header("If-None-Match", &etag)
+        .json(&list(2))
+        .send()
+        .await
+        .unwrap();
+
+    // Then: The unchanged list is not sent again
+    assert_eq!(second.status(), 304);
+    assert_eq!(second.headers()["etag"].to_str().unwrap(), etag);
+    assert!(second.bytes().await.unwrap().is_empty());
+
+    // And: A stale tag gets the full list
+    let third = client
+        .post(format!("{}/mcp", server.url()))
+        .header("If-None-Match", "\"outdated\"")
+        .json(&list(3))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(third.status(), 200);
+    let body: serde_json::Value = third.json().await.unwrap();
+    assert_eq!(body["id"], 3);
+    assert_eq!(body["result"]["tools"][0]["name"], "echo");
+}
+
+#[tokio::test]
+async fn test_tools_list_paged_across_backends() {
+    use wiremock::{matchers::body_partial_json, Mock, ResponseTemplate};
+
+    let tool = |name: &str| sample_tool(name, name);
+
+    // Given: One backend listing its tools in two pages, another in one
+    let paged = mock_backend(vec![]).await;
+    Mock::given(body_partial_json(
+        json!({"method": "tools/list", "params": {"cursor": "p2"}}),
+    ))
+    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+        "jsonrpc": "2.0", "id": 1, "result": {"tools": [tool("alpha_3")]}
+    })))
+    .with_priority(1)
+    .mount(&paged)
+    .await;
+    Mock::given(body_partial_json(json!({"method": "tools/list"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0", "id": 1,
+            "result": {"tools": [tool("alpha_1"), tool("alpha_2")], "nextCursor": "p2"}
+        })))
+        .with_priority(2)
+        .mount(&paged)
+        .await;
+    let single = mock_backend(vec![tool("beta_1")]).await;
+
+    // And: A proxy returning tools/list in pages of two
+    let mut config = test_config_with_backends(0, vec![paged.uri(), single.uri()]);
+    config.context_optimization.aggregation.page_size = 2;
+    let server = start_test_server(config).await;
+    let names = |page: &serde_json::Value| -> Vec<String> {
+        page["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|tool| tool["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // When: A client follows nextCursor until it runs out
+    let first = server.rpc("tools/list", json!({})).await;
+    let cursor = first["result"]["nextCursor"].as_str().unwrap();
+    let second = server.rpc("tools/list", json!({"cursor": cursor})).await;
+
+    // Then: Every tool is listed once, continuing within and across backends
+    assert_eq!(names(&first), ["alpha_1", "alpha_2"]);
+    assert_eq!(names(&second), ["alpha_3", "beta_1"]);
+    assert!(second["result"].get("nextCursor").is_none(), "{}", second);
+}
+
+#[tokio::test]
+async fn test_tool_alias_lists_and_calls_preferred_target() {
+    // Given: Differently named search tools on two backends, aliased as
+    // `search` preferring the first
+    let github = mock_backend(vec![sample_tool("search_repositories", "github")]).await;
+    let text = |name: &str| json!({"content": [{"type": "text", "text": name}]});
+    mount_tools_call(&github, "search_repositories", text("github")).await;
+    let gitlab = mock_backend(vec![sample_tool("search_projects", "gitlab")]).await;
+    mount_tools_call(&gitlab, "search_projects", text("gitlab")).await;
+
+    let mut config = test_config_with_backends(0, vec![github.uri(), gitlab.uri()]);
+    config.proxy.tool_aliases = serde_yaml::from_str(
+        r#"
+        - name: search
+          targets:
+            - {server: test-backend-0, tool: search_repositories}
+            - {server: test-backend-1, tool: search_projects}
+        "#,
+    )
+    .unwrap();
+    let server = start_test_server(config).await;
+
+    // When/Then: Only the alias is listed, described as its first target
+    let body = server.rpc("tools/list", json!({})).await;
+    let tools = body["result"]["tools"].as_array().unwrap();
+    assert_eq!(tools.len(), 1, "{}", body);
+    assert_eq!(tools[0]["name"], "search");
+    assert_eq!(tools[0]["description"], "github");
+
+    // And: Calls reach the preferred target under its own name
+    let body = server.rpc("tools/call", json!({"name": "search", "arguments": {}})).await;
+    assert_eq!(body["result"]["content"][0]["text"], "github", "{}", body);
+}
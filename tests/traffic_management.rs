@@ -0,0 +1,373 @@
+//! Integration tests for traffic management: failover, traffic splits, mirroring, fault injection, load shedding and sharding
+
+mod common;
+
+use common::*;
+use serde_json::json;
+use wiremock::MockServer;
+
+/// A backend serving `tool`, answering every call with `text`
+async fn tool_backend(tool: &str, text: &str) -> MockServer {
+    let backend = mock_backend(vec![sample_tool(tool, tool)]).await;
+    mount_tools_call(
+        &backend,
+        tool,
+        json!({"content": [{"type": "text", "text": text}]}),
+    )
+    .await;
+    backend
+}
+
+/// Text of the proxy's answer to a call of `tool`, empty on errors
+async fn call_text(server: &TestServer, tool: &str) -> String {
+    let body = server.rpc("tools/call", json!({"name": tool, "arguments": {}})).await;
+    body["result"]["content"][0]["text"].as_str().unwrap_or_default().to_string()
+}
+
+/// Whether calls of `tool` come to be answered with `text` within 4s
+async fn served_by(server: &TestServer, tool: &str, text: &str) -> bool {
+    for _ in 0..40 {
+        if call_text(server, tool).await == text {
+            return true;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    false
+}
+
+#[tokio::test]
+async fn test_failover_group_shifts_to_standby_and_back() {
+    use std::sync::atomic::{AtomicU16, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use wiremock::{matchers::path, Mock, ResponseTemplate};
+
+    // Given: A health-checked primary with a standby in one failover group
+    let primary = tool_backend("search", "primary").await;
+    let standby = tool_backend("search", "standby").await;
+    let primary_health = Arc::new(AtomicU16::new(200));
+    let status = primary_health.clone();
+    Mock::given(path("/health"))
+        .respond_with(move |_: &wiremock::Request| {
+            ResponseTemplate::new(status.load(Ordering::SeqCst))
+        })
+        .with_priority(1)
+        .mount(&primary)
+        .await;
+
+    let mut config = test_config_with_backends(0, vec![primary.uri(), standby.uri()]);
+    config.servers[0].health_check.enabled = true;
+    config.servers[0].health_check.interval_seconds = 1;
+    config.servers[0].health_check.healthy_threshold = 1;
+    config.servers[0].health_check.unhealthy_threshold = 1;
+    config.proxy.routing.failover_groups = vec![only1mcp::config::FailoverGroupConfig {
+        name: "search".into(),
+        primary: "test-backend-0".into(),
+        standbys: vec!["test-backend-1".into()],
+    }];
+    let server = start_test_server(config).await;
+
+    // Then: The primary serves every call while it is healthy
+    for _ in 0..4 {
+        assert_eq!(call_text(&server, "search").await, "primary");
+    }
+
+    // When: The primary fails its health check
+    primary_health.store(503, Ordering::SeqCst);
+    // Pooled connections to it are validated against /health, so give the
+    // checker a round to take it out of rotation first
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+
+    // Then: The standby takes over
+    assert!(
+        served_by(&server, "search", "standby").await,
+        "standby never took over"
+    );
+
+    // When: The primary recovers
+    primary_health.store(200, Ordering::SeqCst);
+
+    // Then: Traffic shifts back to it and both shifts are counted
+    assert!(
+        served_by(&server, "search", "primary").await,
+        "traffic never shifted back"
+    );
+    let events = |kind: &str| {
+        only1mcp::metrics::FAILOVER_EVENTS_TOTAL
+            .with_label_values(&["search", kind])
+            .get()
+    };
+    assert!(events("failover") >= 1.0);
+    assert!(events("failback") >= 1.0);
+}
+
+#[tokio::test]
+async fn test_traffic_split_spreads_calls_and_counts_variants() {
+    use wiremock::{matchers::body_partial_json, Mock, ResponseTemplate};
+
+    // Given: A stable version and a failing canary splitting one service
+    let stable = tool_backend("lookup", "stable").await;
+    let canary = mock_backend(vec![sample_tool("lookup", "lookup")]).await;
+    Mock::given(body_partial_json(json!({"method": "tools/call"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0", "id": 1, "error": {"code": -32000, "message": "canary failure"}
+        })))
+        .mount(&canary)
+        .await;
+
+    let mut config = test_config_with_backends(0, vec![stable.uri(), canary.uri()]);
+    config.proxy.routing.traffic_splits = serde_yaml::from_str(
+        "[{service: lookup, variants: [{server: test-backend-0, weight: 1}, {server: test-backend-1, weight: 1}]}]",
+    )
+    .unwrap();
+    let server = start_test_server(config).await;
+    let requests = |server_id: &str, outcome: &str| {
+        only1mcp::metrics::TRAFFIC_SPLIT_REQUESTS_TOTAL
+            .with_label_values(&["lookup", server_id, outcome])
+            .get()
+    };
+
+    // When: Clients call the service repeatedly
+    let mut served = std::collections::HashSet::new();
+    for _ in 0..30 {
+        served.insert(call_text(&server, "lookup").await);
+    }
+
+    // Then: Both versions took traffic, and each variant's outcomes are
+    // counted separately
+    assert_eq!(served.len(), 2, "one variant never served");
+    assert!(requests("test-backend-0", "success") >= 1.0);
+    assert!(requests("test-backend-1", "error") >= 1.0);
+    assert_eq!(requests("test-backend-1", "success"), 0.0);
+}
+
+#[tokio::test]
+async fn test_mirrored_calls_reach_shadow_without_affecting_clients() {
+    use std::time::Duration;
+
+    // Given: A live backend mirroring its calls to a replacement
+    let live = tool_backend("search", "live").await;
+    let shadow = tool_backend("search", "shadow").await;
+
+    let mut config = test_config_with_backends(0, vec![live.uri(), shadow.uri()]);
+    config.servers[0].mirror = serde_yaml::from_str("{server: test-backend-1}").unwrap();
+    let server = start_test_server(config).await;
+
+    // When: Clients call the tool
+    for _ in 0..4 {
+        // Then: Only the live backend answers them
+        assert_eq!(call_text(&server, "search").await, "live");
+    }
+
+    // And: The shadow received a copy of every call
+    let successes = || {
+        only1mcp::metrics::MIRRORED_REQUESTS_TOTAL
+            .with_label_values(&["test-backend-0", "test-backend-1", "success"])
+            .get()
+    };
+    for _ in 0..50 {
+        if successes() >= 4.0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(successes(), 4.0);
+    let mirrored = shadow
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|request| String::from_utf8_lossy(&request.body).contains("tools/call"))
+        .count();
+    assert_eq!(mirrored, 4);
+}
+
+#[tokio::test]
+async fn test_chaos_faults_fail_and_drop_backend_calls() {
+    // Given: A backend serving the `search` tool
+    let backend = tool_backend("search", "found").await;
+
+    // And: Chaos rules failing its calls, and dropping calls to a second
+    // backend with a short timeout
+    let mut config = test_config_with_backends(0, vec![backend.uri(), backend.uri()]);
+    config.servers[0].id = "chaos-failing".to_string();
+    config.servers[1].id = "chaos-dropping".to_string();
+    config.servers[1].timeout_ms = 300;
+    config.proxy.chaos = serde_yaml::from_str(
+        r#"
+enabled: true
+faults:
+  - servers: [chaos-failing]
+    methods: [tools/call]
+    error_percent: 100
+    error: {code: -32000, message: Backend overloaded}
+  - servers: [chaos-dropping]
+    methods: [tools/call]
+    drop_percent: 100
+"#,
+    )
+    .unwrap();
+    let server = start_test_server(config).await;
+
+    // When: Calls reach each backend
+    let mut replies = Vec::new();
+    for _ in 0..6 {
+        let call = json!({"name": "search", "arguments": {}});
+        replies.push(server.rpc("tools/call", call).await);
+    }
+
+    // Then: No call succeeds; they fail with the configured error or time out
+    assert!(
+        replies.iter().all(|reply| reply.get("result").is_none()),
+        "{:?}",
+        replies
+    );
+    assert!(replies.iter().any(|reply| reply["error"]["message"] == "Backend overloaded"));
+
+    // And: Every call, and any retry of it, is counted as a fault
+    let injected = |server_id: &str, fault: &str| {
+        only1mcp::metrics::INJECTED_FAULTS_TOTAL
+            .with_label_values(&[server_id, fault])
+            .get()
+    };
+    assert!(injected("chaos-failing", "error") + injected("chaos-dropping", "drop") >= 6.0);
+}
+
+#[tokio::test]
+async fn test_busy_backend_sheds_excess_calls() {
+    use std::time::Duration;
+    use wiremock::{matchers::body_partial_json, Mock, ResponseTemplate};
+
+    // Given: A slow backend that may handle one call at a time, with no
+    // room to queue more
+    let backend = mock_backend(vec![sample_tool("report", "Report")]).await;
+    Mock::given(body_partial_json(json!({"method": "tools/call"})))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({
+                    "jsonrpc": "2.0", "id": 1,
+                    "result": {"content": [{"type": "text", "text": "done"}]}
+                }))
+                .set_delay(Duration::from_millis(500)),
+        )
+        .mount(&backend)
+        .await;
+
+    let mut config = test_config_with_backends(0, vec![backend.uri()]);
+    config.servers[0].id = "admission-slow".to_string();
+    config.proxy.admission =
+        serde_yaml::from_str("servers: {admission-slow: {max_concurrent: 1, max_queued: 0}}")
+            .unwrap();
+    let server = start_test_server(config).await;
+    let client = test_client();
+    let call = || {
+        client
+            .post(format!("{}/mcp", server.url()))
+            .json(&json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call",
+                          "params": {"name": "report", "arguments": {}}}))
+            .send()
+    };
+
+    // When: Two calls arrive at once
+    let (first, second) = tokio::join!(call(), async {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        call().await
+    });
+
+    // Then: The first is served and the second shed as busy
+    let first = first.unwrap();
+    assert!(first.status().is_success());
+    let second = second.unwrap();
+    assert_eq!(second.status(), reqwest::StatusCode::SERVICE_UNAVAILABLE);
+    let body: serde_json::Value = second.json().await.unwrap();
+    assert_eq!(body["error"]["code"], only1mcp::error::SERVER_BUSY);
+
+    // And: The shed call is counted
+    let shed = only1mcp::metrics::SHED_REQUESTS_TOTAL
+        .with_label_values(&["admission-slow", "queue_full"])
+        .get();
+    assert_eq!(shed, 1.0);
+}
+
+#[tokio::test]
+async fn test_front_door_shards_clients_across_instances() {
+    use wiremock::{
+        matchers::{body_partial_json, header_exists},
+        Mock, ResponseTemplate,
+    };
+
+    /// Downstream instance serving `lookup`, answering calls with `name`
+    /// when the front door forwarded the client id
+    async fn start_instance(name: &str) -> MockServer {
+        let instance = mock_backend(vec![sample_tool("lookup", "Lookup")]).await;
+        Mock::given(body_partial_json(json!({"method": "tools/call"})))
+            .and(header_exists("x-client-id"))
+            .and(header_exists("x-only1mcp-front-door"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0", "id": 1, "result": {"content": [{"type": "text", "text": name}]}
+            })))
+            .mount(&instance)
+            .await;
+        instance
+    }
+
+    // Given: A front door over two instances exposing the same tool
+    let first = start_instance("first").await;
+    let second = start_instance("second").await;
+    let mut config = test_config_with_backends(0, vec![first.uri(), second.uri()]);
+    config.proxy.routing.algorithm = "round_robin".to_string();
+    config.proxy.routing.front_door =
+        serde_yaml::from_str("{enabled: true, instances: [test-backend-0, test-backend-1]}")
+            .unwrap();
+    let server = start_test_server(config).await;
+    let call = |client: String| {
+        let url = format!("{}/mcp", server.url());
+        async move {
+            let body: serde_json::Value = test_client()
+                .post(url)
+                .header("x-client-id", client)
+                .json(&json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call",
+                              "params": {"name": "lookup", "arguments": {}}}))
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+            body["result"]["content"][0]["text"].as_str().unwrap_or_default().to_string()
+        }
+    };
+
+    // When: Many clients call the tool repeatedly
+    let mut instances = std::collections::HashSet::new();
+    for i in 0..20 {
+        let client = format!("client-{}", i);
+        let served = call(client.clone()).await;
+        assert!(!served.is_empty(), "instance did not get the client id");
+
+        // Then: Each client stays on one instance, and the clients are
+        // spread over both
+        assert_eq!(call(client).await, served);
+        instances.insert(served);
+    }
+    assert_eq!(instances.len(), 2, "one instance never served");
+
+    // When: A request arrives from another front door
+    let body: serde_json::Value = test_client()
+        .post(format!("{}/mcp", server.url()))
+        .header("x-only1mcp-front-door", "1")
+        .json(&json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    // Then: It is refused rather than dispatched again
+    assert!(
+        body["error"]["message"].as_str().unwrap_or_default().contains("front door"),
+        "{}",
+        body
+    );
+}
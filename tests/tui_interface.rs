@@ -1,6 +1,9 @@
 //! Integration tests for TUI interface
 
-use only1mcp::tui::{Event, LogEntry, LogLevel, MetricsSnapshot, ServerInfo, ServerStatus};
+use only1mcp::error::Error;
+use only1mcp::tui::{
+    Event, LogEntry, LogLevel, MetricsSnapshot, RemoteOptions, ServerInfo, ServerStatus, TuiClient,
+};
 use tokio::sync::mpsc;
 use tokio::time::{sleep, Duration};
 
@@ -21,6 +24,8 @@ async fn test_tui_event_channel_communication() {
         cache_hit_rate: 0.92,
         error_rate: 0.02,
         active_batches: 12,
+        tokens_saved: [("server1".to_string(), 4_200)].into(),
+        api_cost: [("server1".to_string(), 0.75)].into(),
     };
 
     tx.send(Event::MetricsUpdate(snapshot.clone())).unwrap();
@@ -32,6 +37,7 @@ async fn test_tui_event_channel_communication() {
             assert_eq!(s.uptime_seconds, 3600);
             assert!((s.requests_per_second - 125.5).abs() < 0.01);
             assert_eq!(s.active_servers, 5);
+            assert_eq!(s.total_tokens_saved(), 4_200);
         },
         _ => panic!("Expected MetricsUpdate event"),
     }
@@ -45,16 +51,22 @@ async fn test_tui_server_list_update() {
         ServerInfo {
             id: "server1".to_string(),
             name: "MCP Server 1".to_string(),
+            transport: "HTTP".to_string(),
+            enabled: true,
             status: ServerStatus::Up,
             health_percentage: 100,
             requests_per_second: 50,
+            resources: None,
         },
         ServerInfo {
             id: "server2".to_string(),
             name: "MCP Server 2".to_string(),
+            transport: "HTTP".to_string(),
+            enabled: true,
             status: ServerStatus::Degraded,
             health_percentage: 75,
             requests_per_second: 30,
+            resources: None,
         },
     ];
 
@@ -195,3 +207,94 @@ async fn test_tui_concurrent_event_sending() {
     // Should have received most or all events (30 total)
     assert!(received_count >= 25); // Allow for timing variations
 }
+
+#[tokio::test]
+async fn test_remote_client_sends_token() {
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    // Given: A remote daemon whose admin API requires a bearer token
+    let daemon = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/admin/servers"))
+        .and(header("authorization", "Bearer s3cret"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"id": "fs", "name": "Files", "enabled": true, "transport": "STDIO", "tool_count": 2}
+        ])))
+        .mount(&daemon)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/admin/servers"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&daemon)
+        .await;
+
+    // When/Then: The token is sent with every request
+    let options = RemoteOptions {
+        token: Some("s3cret".to_string()),
+        ..Default::default()
+    };
+    let client = TuiClient::connect(&format!("{}/", daemon.uri()), &options).unwrap();
+    assert_eq!(client.base_url(), daemon.uri());
+    let servers = client.get_servers().await.unwrap();
+    assert_eq!(servers[0].id, "fs");
+
+    // And: Missing credentials are reported as such
+    let anonymous = TuiClient::connect(&daemon.uri(), &RemoteOptions::default()).unwrap();
+    assert!(matches!(
+        anonymous.get_servers().await,
+        Err(Error::AuthFailed(_))
+    ));
+
+    // Only http(s) URLs are accepted
+    assert!(TuiClient::connect("ftp://example.com", &options).is_err());
+    assert!(TuiClient::connect("example.com:8080", &options).is_err());
+}
+
+#[tokio::test]
+async fn test_remote_client_degrades_without_admin_endpoints() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    // Given: A daemon that only exposes the health endpoint
+    let daemon = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/admin/health"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "status": "healthy", "servers_total": 1, "servers_healthy": 1,
+            "tools_total": 3, "uptime_seconds": 10
+        })))
+        .mount(&daemon)
+        .await;
+    let client = TuiClient::connect(&daemon.uri(), &RemoteOptions::default()).unwrap();
+    assert!(client.is_running().await);
+
+    // When: Missing endpoints are used
+    // Then: They fail as unsupported rather than as connection errors
+    let err = client.clear_cache().await.unwrap_err();
+    assert!(matches!(err, Error::Http(_)), "{:?}", err);
+    assert!(
+        err.to_string().contains("cache/clear is not supported"),
+        "{}",
+        err
+    );
+
+    // And: The server poller reports it once instead of every interval
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let poller = tokio::spawn(client.poll_servers(tx, Duration::from_millis(20)));
+    sleep(Duration::from_millis(150)).await;
+    poller.abort();
+
+    let mut warnings = 0;
+    while let Ok(event) = rx.try_recv() {
+        match event {
+            Event::LogMessage(entry) => {
+                assert_eq!(entry.level, LogLevel::Warn);
+                assert!(entry.message.contains("not supported"), "{}", entry.message);
+                warnings += 1;
+            },
+            _ => panic!("Expected only a warning"),
+        }
+    }
+    assert_eq!(warnings, 1);
+}